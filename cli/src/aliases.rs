@@ -0,0 +1,132 @@
+//! The address book: names for pubkeys kept in
+//! ~/.config/sss-token/aliases.toml, looked up wherever the CLI takes a
+//! pubkey that is not valid base58.
+
+use anyhow::{anyhow, Context, Result};
+use serde::{Deserialize, Serialize};
+use solana_sdk::pubkey::Pubkey;
+use std::collections::BTreeMap;
+use std::fs;
+use std::io::ErrorKind;
+use std::path::{Path, PathBuf};
+use std::str::FromStr;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::OnceLock;
+
+/// An `[aliases]` table of `name = "<pubkey or another alias>"`.
+#[derive(Debug, Default, Deserialize, Serialize)]
+#[serde(deny_unknown_fields)]
+pub(crate) struct AliasBook {
+    #[serde(default)]
+    aliases: BTreeMap<String, String>,
+}
+
+impl AliasBook {
+    /// A missing file is an empty book.
+    pub(crate) fn load(path: &Path) -> Result<Self> {
+        let contents = match fs::read_to_string(path) {
+            Ok(contents) => contents,
+            Err(err) if err.kind() == ErrorKind::NotFound => return Ok(Self::default()),
+            Err(err) => {
+                return Err(err)
+                    .with_context(|| format!("Failed to read aliases: {}", path.display()))
+            }
+        };
+        toml::from_str(&contents)
+            .with_context(|| format!("Failed to parse aliases: {}", path.display()))
+    }
+
+    pub(crate) fn save(&self, path: &Path) -> Result<()> {
+        if let Some(dir) = path.parent() {
+            fs::create_dir_all(dir)
+                .with_context(|| format!("Failed to create {}", dir.display()))?;
+        }
+        fs::write(path, toml::to_string(self)?)
+            .with_context(|| format!("Failed to write aliases: {}", path.display()))
+    }
+
+    /// Follows `name` through other aliases until it reaches a pubkey. A
+    /// name that is already a pubkey resolves to itself.
+    pub(crate) fn resolve(&self, name: &str) -> Result<Pubkey> {
+        let mut followed: Vec<&str> = Vec::new();
+        let mut current = name;
+        loop {
+            if let Ok(pubkey) = Pubkey::from_str(current) {
+                return Ok(pubkey);
+            }
+            if followed.contains(&current) {
+                return Err(anyhow!(
+                    "Alias cycle: {} -> {}",
+                    followed.join(" -> "),
+                    current
+                ));
+            }
+            let Some(target) = self.aliases.get(current) else {
+                return Err(match followed.last() {
+                    Some(previous) => {
+                        anyhow!("Alias {} points to unknown alias {}", previous, current)
+                    }
+                    None => anyhow!("Invalid pubkey: {}", name),
+                });
+            };
+            followed.push(current);
+            current = target;
+        }
+    }
+
+    /// Adds or replaces `name`, keeping the book unchanged when the target
+    /// does not resolve, including through a cycle back to `name`.
+    pub(crate) fn insert(&mut self, name: &str, target: &str) -> Result<Pubkey> {
+        if name.is_empty() || name.chars().any(char::is_whitespace) {
+            return Err(anyhow!("Alias names cannot be empty or contain spaces"));
+        }
+        // Valid base58 is never looked up, so the alias could not be used.
+        if Pubkey::from_str(name).is_ok() {
+            return Err(anyhow!("Alias name {} is itself a pubkey", name));
+        }
+        let previous = self.aliases.insert(name.to_string(), target.to_string());
+        self.resolve(name).inspect_err(|_| match previous {
+            Some(previous) => {
+                self.aliases.insert(name.to_string(), previous);
+            }
+            None => {
+                self.aliases.remove(name);
+            }
+        })
+    }
+
+    pub(crate) fn entries(&self) -> impl Iterator<Item = (&str, &str)> {
+        self.aliases
+            .iter()
+            .map(|(name, target)| (name.as_str(), target.as_str()))
+    }
+}
+
+pub(crate) fn default_path() -> PathBuf {
+    let mut path = dirs::home_dir().unwrap_or_else(|| PathBuf::from("."));
+    path.push(".config");
+    path.push("sss-token");
+    path.push("aliases.toml");
+    path
+}
+
+static VERBOSE: AtomicBool = AtomicBool::new(false);
+static BOOK: OnceLock<std::result::Result<AliasBook, String>> = OnceLock::new();
+
+/// With --verbose, every lookup prints the key its alias resolved to.
+pub(crate) fn set_verbose(verbose: bool) {
+    VERBOSE.store(verbose, Ordering::Relaxed);
+}
+
+/// Resolves `name` in the default address book, read on first use.
+pub(crate) fn lookup(name: &str) -> Result<Pubkey> {
+    let book = BOOK
+        .get_or_init(|| AliasBook::load(&default_path()).map_err(|err| format!("{:#}", err)))
+        .as_ref()
+        .map_err(|err| anyhow!("{}", err))?;
+    let pubkey = book.resolve(name)?;
+    if VERBOSE.load(Ordering::Relaxed) {
+        eprintln!("Alias {} resolved to {}", name, pubkey);
+    }
+    Ok(pubkey)
+}
@@ -0,0 +1,311 @@
+//! Decoding of the Anchor events stablecoin-core logs, and of the transfer
+//! hook's decision lines, shared by the audit log and watch commands.
+
+use anchor_lang::{AnchorDeserialize, Discriminator};
+use base64::Engine;
+use serde::Serialize;
+use solana_sdk::pubkey::Pubkey;
+
+/// Returns the text of the `Program <kind>: ` lines logged while
+/// `program_id` was the innermost executing program, so lines from CPI'd
+/// programs are skipped.
+fn program_lines<'a>(logs: &'a [String], program_id: &Pubkey, kind: &str) -> Vec<&'a str> {
+    let program_id = program_id.to_string();
+    let marker = format!("{}: ", kind);
+    let mut stack: Vec<&str> = Vec::new();
+    let mut lines = Vec::new();
+    for line in logs {
+        let Some(rest) = line.strip_prefix("Program ") else {
+            continue;
+        };
+        if let Some(text) = rest.strip_prefix(marker.as_str()) {
+            if stack.last() == Some(&program_id.as_str()) {
+                lines.push(text);
+            }
+        } else if let Some((id, tail)) = rest.split_once(' ') {
+            if tail.starts_with("invoke [") {
+                stack.push(id);
+            } else if tail == "success" || tail.starts_with("failed") {
+                stack.pop();
+            }
+        }
+    }
+    lines
+}
+
+/// Returns the `Program data:` payloads logged by `program_id` itself.
+fn program_data_logs(logs: &[String], program_id: &Pubkey) -> Vec<Vec<u8>> {
+    program_lines(logs, program_id, "data")
+        .into_iter()
+        .filter_map(|data| {
+            let encoded = data.split_whitespace().next().unwrap_or_default();
+            base64::engine::general_purpose::STANDARD
+                .decode(encoded)
+                .ok()
+        })
+        .collect()
+}
+
+pub(crate) fn decode_program_events(logs: &[String], program_id: &Pubkey) -> Vec<DecodedEvent> {
+    program_data_logs(logs, program_id)
+        .iter()
+        .filter_map(|data| decode_event(data))
+        .collect()
+}
+
+/// One `transfer_hook::layout::DECISION_LOG_PREFIX` line: how the hook ruled
+/// on a transfer.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub(crate) struct HookDecision {
+    pub(crate) decision: String,
+    pub(crate) source_owner: String,
+    pub(crate) destination_owner: String,
+    pub(crate) amount: String,
+}
+
+impl HookDecision {
+    /// HOOK_ALLOW, HOOK_DENY or HOOK_EXEMPT.
+    pub(crate) fn action(&self) -> String {
+        format!("HOOK_{}", self.decision.to_ascii_uppercase())
+    }
+
+    /// True when `action` is this decision's action or TransferHookDecision,
+    /// ignoring case.
+    pub(crate) fn matches_action(&self, action: &str) -> bool {
+        let action = action.trim();
+        action.eq_ignore_ascii_case(HOOK_DECISION_EVENT)
+            || action.eq_ignore_ascii_case(&self.action())
+    }
+}
+
+/// Event name the audit log lists hook decisions under.
+pub(crate) const HOOK_DECISION_EVENT: &str = "TransferHookDecision";
+
+/// Decision lines logged by `hook_program_id`; lines with a missing field
+/// are skipped.
+pub(crate) fn decode_hook_decisions(
+    logs: &[String],
+    hook_program_id: &Pubkey,
+) -> Vec<HookDecision> {
+    program_lines(logs, hook_program_id, "log")
+        .into_iter()
+        .filter_map(|line| {
+            let fields = line.strip_prefix(transfer_hook::layout::DECISION_LOG_PREFIX)?;
+            let mut decision = HookDecision {
+                decision: String::new(),
+                source_owner: String::new(),
+                destination_owner: String::new(),
+                amount: String::new(),
+            };
+            for field in fields.split_whitespace() {
+                let (key, value) = field.split_once('=')?;
+                let slot = match key {
+                    "decision" => &mut decision.decision,
+                    "source_owner" => &mut decision.source_owner,
+                    "destination_owner" => &mut decision.destination_owner,
+                    "amount" => &mut decision.amount,
+                    _ => continue,
+                };
+                *slot = value.to_string();
+            }
+            let complete = !decision.decision.is_empty()
+                && !decision.source_owner.is_empty()
+                && !decision.destination_owner.is_empty()
+                && !decision.amount.is_empty();
+            complete.then_some(decision)
+        })
+        .collect()
+}
+
+pub(crate) struct DecodedEvent {
+    pub(crate) name: &'static str,
+    pub(crate) action: &'static str,
+    pub(crate) timestamp: i64,
+    pub(crate) fields: serde_json::Map<String, serde_json::Value>,
+}
+
+impl DecodedEvent {
+    /// True when `action` names this event, either by its action label
+    /// (MINT) or its event name (TokensMinted), ignoring case.
+    pub(crate) fn matches_action(&self, action: &str) -> bool {
+        let action = action.trim();
+        self.name.eq_ignore_ascii_case(action) || self.action.eq_ignore_ascii_case(action)
+    }
+
+    /// The config PDA every core event carries.
+    pub(crate) fn config(&self) -> Option<&str> {
+        self.fields.get("config").and_then(|value| value.as_str())
+    }
+}
+
+trait EventFieldValue {
+    fn to_json(&self) -> serde_json::Value;
+}
+
+impl EventFieldValue for Pubkey {
+    fn to_json(&self) -> serde_json::Value {
+        serde_json::Value::String(self.to_string())
+    }
+}
+
+impl EventFieldValue for String {
+    fn to_json(&self) -> serde_json::Value {
+        serde_json::Value::String(self.clone())
+    }
+}
+
+impl EventFieldValue for bool {
+    fn to_json(&self) -> serde_json::Value {
+        serde_json::Value::Bool(*self)
+    }
+}
+
+impl EventFieldValue for u8 {
+    fn to_json(&self) -> serde_json::Value {
+        serde_json::Value::from(*self)
+    }
+}
+
+impl EventFieldValue for u16 {
+    fn to_json(&self) -> serde_json::Value {
+        serde_json::Value::from(*self)
+    }
+}
+
+impl EventFieldValue for u32 {
+    fn to_json(&self) -> serde_json::Value {
+        serde_json::Value::from(*self)
+    }
+}
+
+impl EventFieldValue for i64 {
+    fn to_json(&self) -> serde_json::Value {
+        serde_json::Value::from(*self)
+    }
+}
+
+/// Amounts are rendered as strings, matching the other JSON outputs.
+impl EventFieldValue for u64 {
+    fn to_json(&self) -> serde_json::Value {
+        serde_json::Value::String(self.to_string())
+    }
+}
+
+impl<T: EventFieldValue> EventFieldValue for Option<T> {
+    fn to_json(&self) -> serde_json::Value {
+        self.as_ref()
+            .map_or(serde_json::Value::Null, EventFieldValue::to_json)
+    }
+}
+
+/// Zero bytes appended to every event body before decoding. Events logged
+/// before a trailing field was added lack its bytes, and decode it as 0 or
+/// None instead of failing; a schema_version of 0 marks such events.
+const EVENT_TAIL_PADDING: usize = 16;
+
+/// Generates `decode_event`, which matches the Anchor event discriminator and
+/// maps each listed field to JSON. Action labels follow the services API.
+macro_rules! event_decoders {
+    ($($event:ident => $action:literal { $($field:ident),* $(,)? }),* $(,)?) => {
+        pub(crate) fn decode_event(data: &[u8]) -> Option<DecodedEvent> {
+            let discriminator = data.get(..8)?;
+            let mut padded = data[8..].to_vec();
+            padded.resize(padded.len() + EVENT_TAIL_PADDING, 0);
+            let mut body = padded.as_slice();
+            $(
+                if discriminator == <stablecoin_core::events::$event as Discriminator>::DISCRIMINATOR {
+                    let event = stablecoin_core::events::$event::deserialize(&mut body).ok()?;
+                    let mut fields = serde_json::Map::new();
+                    $(fields.insert(stringify!($field).to_string(), event.$field.to_json());)*
+                    return Some(DecodedEvent {
+                        name: stringify!($event),
+                        action: $action,
+                        timestamp: event.timestamp,
+                        fields,
+                    });
+                }
+            )*
+            None
+        }
+    };
+}
+
+event_decoders! {
+    StablecoinInitialized => "INIT" { config, mint, authority, name, symbol, preset, timestamp },
+    TokensMinted => "MINT" {
+        config, mint, recipient, amount, minter, new_total_supply, timestamp, schema_version
+    },
+    MintProposed => "MINT_PROPOSE" {
+        config, request, id, requester, recipient, amount, timestamp
+    },
+    MintApproved => "MINT_APPROVE" {
+        config, request, id, requester, approver, recipient, amount, timestamp
+    },
+    MintCancelled => "MINT_CANCEL" {
+        config, request, id, requester, cancelled_by, amount, timestamp
+    },
+    MintApprovalUpdated => "MINT_APPROVAL_UPDATE" {
+        config, require_mint_approval, updated_by, timestamp
+    },
+    TokensBurned => "BURN" {
+        config, mint, burner, amount, new_total_supply, timestamp, schema_version
+    },
+    AccountFrozen => "FREEZE" { config, target_account, frozen_by, timestamp },
+    AccountThawed => "THAW" { config, target_account, thawed_by, timestamp },
+    SystemPaused => "PAUSE" { config, paused_by, timestamp, pause_flags },
+    SystemUnpaused => "UNPAUSE" { config, unpaused_by, timestamp, pause_flags },
+    AdminOpsLockUpdated => "EMERGENCY_LOCK" { config, lock_admin_ops, updated_by, timestamp },
+    RoleUpdated => "ROLE_UPDATED" {
+        config, target, new_roles, added_roles, removed_roles, updated_by, timestamp
+    },
+    AuthorityTransferred => "AUTHORITY_TRANSFER" { config, old_authority, new_authority, timestamp },
+    BlacklistAdded => "BLACKLIST_ADD" {
+        config, wallet, reason, blacklisted_by, timestamp, expires_at, schema_version,
+        reason_code
+    },
+    BlacklistUpdated => "BLACKLIST_UPDATE" {
+        config, wallet, reason, reason_code, updated_by, timestamp
+    },
+    BlacklistRemoved => "BLACKLIST_REMOVE" { config, wallet, removed_by, timestamp },
+    BlacklistEntryClosed => "BLACKLIST_CLOSE" { config, wallet, closed_by, timestamp },
+    AllowlistAdded => "ALLOWLIST_ADD" { config, wallet, added_by, timestamp },
+    AllowlistRemoved => "ALLOWLIST_REMOVE" { config, wallet, removed_by, timestamp },
+    TokensSeized => "SEIZE" {
+        config, from_account, to_account, amount, seized_by, timestamp, schema_version,
+        total_seized
+    },
+    TokensSeizedAndBurned => "SEIZE_BURN" {
+        config, from_account, amount, new_total_supply, seized_by, timestamp
+    },
+    MetadataUpdated => "METADATA_UPDATE" { config, mint, updated_by, name, symbol, uri, timestamp },
+    MetadataFieldUpdated => "METADATA_FIELD_UPDATE" { config, mint, updated_by, key, value, timestamp },
+    SupplyFrozen => "SUPPLY_FROZEN" {
+        config, mint, total_supply, mint_authority_nulled, frozen_by, timestamp
+    },
+    MaxSupplyUpdated => "MAX_SUPPLY_UPDATE" {
+        config, previous_max_supply, max_supply, updated_by, timestamp
+    },
+    QuotaWindowReset => "QUOTA_WINDOW_RESET" {
+        config, role_account, minter, previous_window_total, window_start, timestamp
+    },
+    QuotaWindowUpdated => "QUOTA_WINDOW_UPDATE" {
+        config, previous_window_seconds, quota_window_seconds, updated_by, timestamp
+    },
+    TransferLimitUpdated => "TRANSFER_LIMIT_UPDATE" {
+        config, previous_max_transfer_amount, max_transfer_amount, updated_by, timestamp
+    },
+    SelfTransferUpdated => "SELF_TRANSFER_UPDATE" {
+        config, allow_self_transfer, updated_by, timestamp
+    },
+    TransferHookProgramUpdated => "TRANSFER_HOOK_UPDATE" {
+        config, previous_program, new_program, updated_by, timestamp
+    },
+    TransferHookEnabled => "TRANSFER_HOOK_ENABLE" { config, program, enabled_by, timestamp },
+    TreasuryUpdated => "TREASURY_UPDATE" {
+        config, previous_treasury, treasury, updated_by, timestamp
+    },
+    DefaultAccountStateUpdated => "DEFAULT_STATE_UPDATE" {
+        config, default_frozen, updated_by, timestamp
+    },
+    ConfigMigrated => "CONFIG_MIGRATE" { config, previous_len, new_len, paid_by, timestamp },
+}
@@ -1,31 +1,80 @@
 use anchor_lang::AccountDeserialize;
 use anyhow::{anyhow, Context, Result};
+use base64::Engine;
 use borsh::BorshSerialize;
+use chrono::{DateTime, NaiveDate, NaiveTime, Utc};
 use clap::{Parser, Subcommand, ValueEnum};
+use reqwest::header::{HeaderMap, HeaderName, HeaderValue};
 use serde::{Deserialize, Serialize};
 use sha2::{Digest, Sha256};
-use solana_client::rpc_client::RpcClient;
-use solana_client::rpc_config::{RpcAccountInfoConfig, RpcProgramAccountsConfig};
+use solana_account_decoder::{UiAccountEncoding, UiDataSliceConfig};
+use solana_client::client_error::{ClientError, ClientErrorKind};
+use solana_client::pubsub_client::PubsubClient;
+use solana_client::rpc_client::GetConfirmedSignaturesForAddress2Config;
+use solana_client::rpc_client::{RpcClient, RpcClientConfig};
+use solana_client::rpc_config::{
+    RpcAccountInfoConfig, RpcProgramAccountsConfig, RpcSimulateTransactionConfig,
+    RpcTransactionConfig, RpcTransactionLogsConfig, RpcTransactionLogsFilter,
+};
+use solana_client::rpc_custom_error::JSON_RPC_SERVER_ERROR_NODE_UNHEALTHY;
 use solana_client::rpc_filter::{Memcmp, RpcFilterType};
+use solana_client::rpc_request::{RpcError, RpcResponseErrorData};
+use solana_remote_wallet::locator::Locator;
+use solana_remote_wallet::remote_keypair::generate_remote_keypair;
+use solana_remote_wallet::remote_wallet::maybe_wallet_manager;
+use solana_rpc_client::http_sender::HttpSender;
 use solana_sdk::commitment_config::CommitmentConfig;
-use solana_sdk::instruction::{AccountMeta, Instruction};
+use solana_sdk::compute_budget::ComputeBudgetInstruction;
+use solana_sdk::derivation_path::DerivationPath;
+use solana_sdk::hash::Hash;
+use solana_sdk::instruction::{AccountMeta, Instruction, InstructionError};
+use solana_sdk::message::Message;
+use solana_sdk::packet::PACKET_DATA_SIZE;
 use solana_sdk::pubkey::Pubkey;
-use solana_sdk::signature::{read_keypair_file, Keypair, Signer};
+use solana_sdk::signature::{read_keypair_file, Keypair, Signature, Signer};
 use solana_sdk::system_program;
 use solana_sdk::sysvar;
-use solana_sdk::transaction::Transaction;
+use solana_sdk::transaction::{Transaction, TransactionError};
+use solana_transaction_status::UiTransactionEncoding;
 use spl_associated_token_account::get_associated_token_address_with_program_id;
-use spl_token_2022::extension::StateWithExtensions;
+use spl_associated_token_account::instruction::create_associated_token_account_idempotent;
+use spl_token_2022::extension::{
+    confidential_transfer::ConfidentialTransferMint, default_account_state::DefaultAccountState,
+    metadata_pointer::MetadataPointer, mint_close_authority::MintCloseAuthority,
+    permanent_delegate::PermanentDelegate, transfer_hook::TransferHook, BaseStateWithExtensions,
+    StateWithExtensions,
+};
 use spl_token_2022::state::Account as TokenAccount2022;
+use spl_token_2022::state::AccountState;
+use spl_token_2022::state::Mint as Mint2022;
+use spl_token_metadata_interface::state::TokenMetadata;
 use stablecoin_core::constants::{
+    MAX_DECIMALS, MAX_MINT_BATCH_SIZE, MAX_NAME_LEN, MAX_REASON_LEN, MAX_SYMBOL_LEN, MAX_URI_LEN,
+    MINT_REQUEST_TTL_SECONDS, MIN_QUOTA_WINDOW_SECONDS, PAUSE_BURN, PAUSE_MINT, PAUSE_TRANSFER,
     ROLE_BLACKLISTER, ROLE_BURNER, ROLE_FREEZER, ROLE_MASTER_AUTHORITY, ROLE_MINTER, ROLE_PAUSER,
-    ROLE_SEIZER,
+    ROLE_ROLE_ADMIN, ROLE_SEIZER, VALID_ROLE_MASK,
 };
-use stablecoin_core::state::{BlacklistEntry, RoleAccount, StablecoinConfig};
-use std::collections::HashMap;
+use stablecoin_core::state::{
+    AllowlistEntry, BlacklistEntry, BlacklistReason, MintRequest, RoleAccount, StablecoinConfig,
+    TokenProgram,
+};
+use std::cell::Cell;
+use std::collections::{HashMap, VecDeque};
 use std::fs;
-use std::path::PathBuf;
+use std::io::{BufRead, IsTerminal, Write};
+use std::path::{Path, PathBuf};
 use std::str::FromStr;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::Arc;
+use std::time::{Duration, Instant};
+use uriparse::URIReference;
+
+mod aliases;
+mod events;
+mod program_errors;
+
+use events::{decode_hook_decisions, decode_program_events, DecodedEvent, HOOK_DECISION_EVENT};
+use program_errors::program_error;
 
 #[derive(Parser)]
 #[command(name = "sss-token", version, about = "Solana Stablecoin Standard CLI")]
@@ -33,12 +82,72 @@ struct Cli {
     #[arg(long)]
     cluster: Option<String>,
 
+    /// Keypair file, or usb://ledger[?key=<account>] to sign with a Ledger.
     #[arg(long)]
     keypair: Option<String>,
 
     #[arg(long, value_enum, default_value = "text")]
     output: OutputFormat,
 
+    /// Act as this authority (e.g. a multisig vault) and print the unsigned
+    /// instructions for the multisig program instead of sending them.
+    #[arg(long)]
+    authority: Option<String>,
+
+    /// INSECURE: skip TLS certificate verification for the HTTP RPC endpoint.
+    /// Only for private or test RPC nodes with self-signed certificates.
+    #[arg(long)]
+    insecure_accept_invalid_certs: bool,
+
+    /// Extra HTTP header for every RPC request, e.g. "x-api-key: <KEY>";
+    /// repeatable. Applies whichever source the RPC URL comes from. Keys
+    /// embedded in the URL query need no flag and are passed through as is.
+    #[arg(long = "rpc-header", value_name = "NAME: VALUE")]
+    rpc_headers: Vec<String>,
+
+    /// Attempts per transaction when the RPC fails transiently (expired
+    /// blockhash, rate limiting, unreachable node); 1 disables retrying.
+    #[arg(long, default_value_t = 3, value_parser = clap::value_parser!(u32).range(1..))]
+    max_retries: u32,
+
+    /// Delay before the first retry, doubled on each further one.
+    #[arg(long, default_value_t = 500)]
+    retry_delay_ms: u64,
+
+    /// Compute unit price in micro-lamports, prepended to every transaction.
+    #[arg(long)]
+    priority_fee: Option<u64>,
+
+    /// Compute unit limit for every transaction, e.g. for seize and init,
+    /// whose transfer-hook CPI and sub-instructions exceed the default.
+    #[arg(long)]
+    compute_limit: Option<u32>,
+
+    /// Instead of signing and sending, append each transaction unsigned (as
+    /// one base64 line) to this file for offline or multisig signing.
+    #[arg(long)]
+    export_tx: Option<PathBuf>,
+
+    /// Simulate each transaction and print its logs, compute units, and error
+    /// instead of sending it.
+    #[arg(long, conflicts_with = "export_tx")]
+    simulate: bool,
+
+    /// Skip the confirmation asked before seize, pause, blacklist add and
+    /// freeze. Required for those with --output json, which never prompts.
+    #[arg(long, short = 'y', global = true)]
+    yes: bool,
+
+    /// Read mint, burn and seize amounts as raw base units, without decimal
+    /// scaling, and print those commands' amounts in base units too.
+    #[arg(long, global = true)]
+    raw_amounts: bool,
+
+    /// Print extra details, such as which key each alias resolved to and
+    /// how many keypairs init --mint-prefix tried.
+    #[arg(long, global = true)]
+    verbose: bool,
+
     #[command(subcommand)]
     command: Commands,
 }
@@ -49,22 +158,87 @@ enum OutputFormat {
     Json,
 }
 
+#[derive(Clone, Copy, ValueEnum, PartialEq, Eq, Debug)]
+enum SortOrder {
+    Asc,
+    Desc,
+}
+
+#[derive(Clone, Copy, ValueEnum, PartialEq, Eq, Debug)]
+enum Toggle {
+    On,
+    Off,
+}
+
+impl Toggle {
+    fn enabled(self) -> bool {
+        self == Toggle::On
+    }
+}
+
+/// Token program a new mint is created under; encodes like the program's
+/// `TokenProgram` enum.
+#[derive(Clone, Copy, ValueEnum, PartialEq, Eq, Debug, Default, Deserialize, BorshSerialize)]
+#[serde(rename_all = "kebab-case")]
+enum TokenProgramKind {
+    #[default]
+    Token2022,
+    Legacy,
+}
+
+impl TokenProgramKind {
+    fn id(self) -> Pubkey {
+        match self {
+            TokenProgramKind::Token2022 => TokenProgram::Token2022.id(),
+            TokenProgramKind::Legacy => TokenProgram::Legacy.id(),
+        }
+    }
+}
+
 #[derive(Subcommand)]
 enum Commands {
     Init(InitArgs),
     Mint(MintArgs),
+    MintBatch(MintBatchArgs),
     Burn(BurnArgs),
-    Freeze(AddressArgs),
-    Thaw(AddressArgs),
-    Pause(MintOnlyArgs),
-    Unpause(MintOnlyArgs),
+    Transfer(TransferArgs),
+    Freeze(FreezeArgs),
+    Thaw(FreezeArgs),
+    Pause(PauseArgs),
+    Unpause(PauseArgs),
+    /// Reserve freeze, thaw, blacklist and seize to the master authority.
+    EmergencyLock(MintOnlyArgs),
+    /// Lift the emergency lock.
+    EmergencyUnlock(MintOnlyArgs),
     Blacklist(BlacklistArgs),
+    Allowlist(AllowlistArgs),
     Seize(SeizeArgs),
+    SeizeBatch(SeizeBatchArgs),
     Minters(MintersArgs),
-    Status(MintOnlyArgs),
-    Supply(MintOnlyArgs),
+    Roles(RolesArgs),
+    Config(ConfigArgs),
+    /// Validate an init config file without touching the chain.
+    ConfigCheck(ConfigCheckArgs),
+    /// Manage the address book of names accepted wherever a pubkey is.
+    Alias(AliasArgs),
+    /// Create the transfer hook's extra account metas list for a mint.
+    InitExtraMetas(InitExtraMetasArgs),
+    Metadata(MetadataArgs),
+    Status(StatusArgs),
+    VerifyAuthorities(MintOnlyArgs),
+    /// Check the mint's extensions against what the config expects.
+    Verify(MintOnlyArgs),
+    /// Summarize blacklist, role and supply figures for auditors.
+    Report(ReportArgs),
+    Supply(SupplyArgs),
+    /// Permanently lock minting; cannot be undone.
+    FreezeSupply(SupplyLockArgs),
     Holders(HoldersArgs),
+    Balance(AddressArgs),
     AuditLog(AuditLogArgs),
+    Watch(WatchArgs),
+    ExportIdl(ExportIdlArgs),
+    ImportTx(ImportTxArgs),
 }
 
 #[derive(Parser)]
@@ -86,19 +260,142 @@ struct InitArgs {
 
     #[arg(long)]
     uri: Option<String>,
+
+    /// Minimum seconds between pause/unpause by non-master pausers (0 disables).
+    #[arg(long, default_value_t = 0)]
+    pause_cooldown: i64,
+
+    /// Skip the metadata pointer and in-mint token metadata.
+    #[arg(long)]
+    no_metadata: bool,
+
+    /// Hard supply ceiling in token units; overrides token.max_supply in --config.
+    #[arg(long)]
+    max_supply: Option<String>,
+
+    /// Wallet owning the treasury token account; overrides token.treasury in --config.
+    #[arg(long)]
+    treasury: Option<String>,
+
+    /// Only let allowlisted wallets hold the token instead of using the
+    /// blacklist; requires the transfer hook.
+    #[arg(long)]
+    allowlist: bool,
+
+    /// Minimum seconds between two transfers sent by one wallet, enforced
+    /// by the transfer hook; overrides extensions.transfer_cooldown_seconds.
+    #[arg(long, value_name = "SECONDS")]
+    transfer_cooldown: Option<i64>,
+
+    /// Give an SSS-1 mint a TransferHook extension with no program so
+    /// `config enable-transfer-hook` can turn the hook on later; overrides
+    /// extensions.reserve_transfer_hook.
+    #[arg(long)]
+    reserve_transfer_hook: bool,
+
+    /// Token program for the new mint; legacy only supports plain SSS-1
+    /// mints without metadata. Overrides token.token_program in --config.
+    #[arg(long, value_enum)]
+    token_program: Option<TokenProgramKind>,
+
+    /// Keypair file for the new mint instead of a freshly generated one.
+    #[arg(long)]
+    mint_keypair: Option<String>,
+
+    /// Generate keypairs until the mint address starts with this base58
+    /// prefix (case-sensitive, at most 4 characters).
+    #[arg(long, conflicts_with = "mint_keypair")]
+    mint_prefix: Option<String>,
 }
 
 #[derive(Parser)]
+#[command(args_conflicts_with_subcommands = true, subcommand_negates_reqs = true)]
 struct MintArgs {
+    #[arg(required_unless_present = "to_treasury")]
+    recipient: Option<String>,
+    #[arg(required_unless_present = "to_treasury")]
+    amount: Option<String>,
+
+    /// Mint this amount to the configured treasury instead of a recipient.
+    #[arg(long, value_name = "AMOUNT", conflicts_with_all = ["recipient", "amount"])]
+    to_treasury: Option<String>,
+
+    #[arg(long)]
+    mint: Option<String>,
+
+    #[command(subcommand)]
+    request: Option<MintRequestCmd>,
+}
+
+/// Four-eyes minting: one minter proposes, a second signer approves.
+#[derive(Subcommand)]
+enum MintRequestCmd {
+    /// Propose a mint that another minter must approve
+    Propose(MintProposeArgs),
+    /// Approve another minter's request, minting its amount
+    Approve(MintApproveArgs),
+    /// Withdraw a pending request (requester or master authority)
+    Cancel(MintCancelArgs),
+}
+
+#[derive(Parser)]
+struct MintProposeArgs {
     recipient: String,
     amount: String,
 
+    /// Request id, part of the request's address; defaults to the current
+    /// unix time in milliseconds.
+    #[arg(long)]
+    id: Option<u64>,
+
+    #[arg(long)]
+    mint: Option<String>,
+}
+
+#[derive(Parser)]
+struct MintApproveArgs {
+    /// Id printed by `mint propose`.
+    id: u64,
+
+    #[arg(long)]
+    mint: Option<String>,
+}
+
+#[derive(Parser)]
+struct MintCancelArgs {
+    /// Id printed by `mint propose`.
+    id: u64,
+
+    #[arg(long)]
+    mint: Option<String>,
+}
+
+#[derive(Parser)]
+struct MintBatchArgs {
+    /// CSV file with `recipient,amount` lines (an optional header line is skipped).
+    #[arg(long)]
+    file: PathBuf,
+
     #[arg(long)]
     mint: Option<String>,
 }
 
 #[derive(Parser)]
 struct BurnArgs {
+    #[arg(required_unless_present = "all", conflicts_with = "all")]
+    amount: Option<String>,
+
+    /// Burn the full balance of the burner token account.
+    #[arg(long)]
+    all: bool,
+
+    #[arg(long)]
+    mint: Option<String>,
+}
+
+#[derive(Parser)]
+struct TransferArgs {
+    recipient: String,
     amount: String,
 
     #[arg(long)]
@@ -113,6 +410,19 @@ struct AddressArgs {
     mint: Option<String>,
 }
 
+#[derive(Parser)]
+struct FreezeArgs {
+    address: String,
+
+    /// Treat the address as a wallet owner and target its associated token
+    /// account.
+    #[arg(long)]
+    owner: bool,
+
+    #[arg(long)]
+    mint: Option<String>,
+}
+
 #[derive(Parser)]
 struct BlacklistArgs {
     #[command(subcommand)]
@@ -122,6 +432,28 @@ struct BlacklistArgs {
 #[derive(Subcommand)]
 enum BlacklistCmd {
     Add(BlacklistAddArgs),
+    /// Blacklist every wallet listed in a file.
+    AddBatch(BlacklistAddBatchArgs),
+    /// Correct the reason or code of an active entry, keeping when and by
+    /// whom it was added.
+    Update(BlacklistUpdateArgs),
+    Remove(AddressArgs),
+    /// Remove every wallet listed in a file from the blacklist.
+    RemoveBatch(BlacklistBatchArgs),
+    Check(AddressArgs),
+    /// Close a removed entry and reclaim its rent.
+    Close(AddressArgs),
+}
+
+#[derive(Parser)]
+struct AllowlistArgs {
+    #[command(subcommand)]
+    command: AllowlistCmd,
+}
+
+#[derive(Subcommand)]
+enum AllowlistCmd {
+    Add(AddressArgs),
     Remove(AddressArgs),
     Check(AddressArgs),
 }
@@ -130,9 +462,68 @@ enum BlacklistCmd {
 struct BlacklistAddArgs {
     address: String,
 
+    /// Reason category: sanctions, fraud, court-order, or other.
+    #[arg(long, default_value = "other")]
+    code: String,
+
+    /// Free-text note stored alongside the code.
+    #[arg(long)]
+    reason: Option<String>,
+
+    /// Lift the entry automatically after this long (seconds or a duration such as 30d).
+    #[arg(long)]
+    expires_in: Option<String>,
+
+    #[arg(long)]
+    mint: Option<String>,
+}
+
+#[derive(Parser)]
+struct BlacklistUpdateArgs {
+    address: String,
+
+    /// Free-text note replacing the current one.
     #[arg(long)]
     reason: String,
 
+    /// New reason category: sanctions, fraud, court-order, or other. Kept
+    /// when omitted.
+    #[arg(long)]
+    code: Option<String>,
+
+    #[arg(long)]
+    mint: Option<String>,
+}
+
+#[derive(Parser)]
+struct BlacklistAddBatchArgs {
+    /// File with one wallet per line and an optional reason after a comma;
+    /// rows without one use --reason.
+    #[arg(long)]
+    file: PathBuf,
+
+    /// Reason category for every row: sanctions, fraud, court-order, or other.
+    #[arg(long, default_value = "other")]
+    code: String,
+
+    /// Free-text note for rows that do not carry their own.
+    #[arg(long)]
+    reason: Option<String>,
+
+    /// Lift the entries automatically after this long (seconds or a duration such as 30d).
+    #[arg(long)]
+    expires_in: Option<String>,
+
+    #[arg(long)]
+    mint: Option<String>,
+}
+
+#[derive(Parser)]
+struct BlacklistBatchArgs {
+    /// File with one wallet per line; extra CSV columns are ignored.
+    #[arg(long)]
+    file: PathBuf,
+
     #[arg(long)]
     mint: Option<String>,
 }
@@ -141,8 +532,50 @@ struct BlacklistAddArgs {
 struct SeizeArgs {
     address: String,
 
+    /// Treasury token account; defaults to the configured treasury's ATA.
+    #[arg(long, conflicts_with = "burn")]
+    to: Option<String>,
+
+    /// Treasury wallet; seizes into its ATA, created in the same transaction
+    /// when missing.
+    #[arg(long, conflicts_with_all = ["to", "burn"])]
+    to_owner: Option<String>,
+
+    /// Burn the seized balance instead of moving it to a treasury.
+    #[arg(long)]
+    burn: bool,
+
+    /// Seize only this amount instead of the full balance.
+    #[arg(long, conflicts_with_all = ["all", "burn"])]
+    amount: Option<String>,
+
+    /// Seize the full balance of the target account (the default).
+    #[arg(long)]
+    all: bool,
+
+    #[arg(long)]
+    mint: Option<String>,
+}
+
+#[derive(Parser)]
+struct SeizeBatchArgs {
+    /// File with one target token account or owner address per line; extra
+    /// CSV columns are ignored.
+    #[arg(long)]
+    file: PathBuf,
+
+    /// Treasury token account receiving every seized balance; defaults to
+    /// the configured treasury's ATA.
+    #[arg(long)]
+    to: Option<String>,
+
+    /// Print the seizure plan without sending anything.
     #[arg(long)]
-    to: String,
+    dry_run: bool,
+
+    /// Attempts per account before it is recorded as failed.
+    #[arg(long, default_value_t = 3)]
+    attempts: u32,
 
     #[arg(long)]
     mint: Option<String>,
@@ -159,6 +592,8 @@ enum MintersCmd {
     List(MintOnlyArgs),
     Add(MinterAddArgs),
     Remove(AddressArgs),
+    /// Lifetime and current-window mint totals of one minter
+    Stats(AddressArgs),
 }
 
 #[derive(Parser)]
@@ -168,1862 +603,13229 @@ struct MinterAddArgs {
     #[arg(long)]
     quota: String,
 
+    /// Cap on everything this minter may ever mint, or `none` to lift it.
+    /// Kept unchanged when omitted.
+    #[arg(long, value_name = "AMOUNT|none")]
+    lifetime_quota: Option<String>,
+
     #[arg(long)]
     mint: Option<String>,
 }
 
 #[derive(Parser)]
-struct MintOnlyArgs {
+struct RolesArgs {
+    #[command(subcommand)]
+    command: RolesCmd,
+}
+
+#[derive(Subcommand)]
+enum RolesCmd {
+    Audit(MintOnlyArgs),
+    List(MintOnlyArgs),
+    Grant(RolesUpdateArgs),
+    Revoke(RolesUpdateArgs),
+    Renounce(RolesRenounceArgs),
+    /// Check stored role-account bumps against the canonical PDA bump.
+    Repair(RolesRepairArgs),
+}
+
+#[derive(Parser)]
+struct RolesUpdateArgs {
+    address: String,
+
+    /// Comma-separated roles (burner,pauser,freezer,blacklister,seizer).
+    #[arg(long)]
+    roles: String,
+
     #[arg(long)]
     mint: Option<String>,
 }
 
 #[derive(Parser)]
-struct HoldersArgs {
+struct RolesRenounceArgs {
+    /// Comma-separated roles to drop (minter,burner,pauser,freezer,blacklister,seizer).
     #[arg(long)]
-    min_balance: Option<String>,
+    roles: String,
 
     #[arg(long)]
     mint: Option<String>,
 }
 
 #[derive(Parser)]
-struct AuditLogArgs {
+struct RolesRepairArgs {
+    /// Check only this address's role account; every role account otherwise.
+    address: Option<String>,
+
     #[arg(long)]
-    action: Option<String>,
+    mint: Option<String>,
+}
+
+#[derive(Parser)]
+struct ConfigArgs {
+    #[command(subcommand)]
+    command: ConfigCmd,
+}
+
+#[derive(Subcommand)]
+enum ConfigCmd {
+    /// Dump every field of the decoded StablecoinConfig account.
+    Show(MintOnlyArgs),
+    /// Introduce or lower the hard supply cap (never below current supply).
+    SetMaxSupply(SetMaxSupplyArgs),
+    /// Change the minter quota window length (at least 60 seconds).
+    SetQuotaWindow(SetQuotaWindowArgs),
+    /// Cap the amount of a single wallet transfer, or `none` to remove the cap.
+    SetTransferLimit(SetTransferLimitArgs),
+    /// Point the mint's transfer hook at a new hook program (SSS-2).
+    SetTransferHookProgram(SetTransferHookProgramArgs),
+    /// Turn on the transfer hook for a mint created without a hook program.
+    EnableTransferHook(EnableTransferHookArgs),
+    /// Exempt transfers between one owner's own token accounts from the
+    /// blacklist (SSS-2).
+    SetSelfTransfer(SetSelfTransferArgs),
+    /// Require every mint to go through mint propose and mint approve.
+    SetMintApproval(SetMintApprovalArgs),
+    /// Set the treasury wallet, or `none` to clear it.
+    SetTreasury(SetTreasuryArgs),
+    /// Grow a config created with an older, smaller allocation to the
+    /// current size, paying the extra rent.
+    Migrate(MintOnlyArgs),
+}
+
+#[derive(Parser)]
+struct SetMaxSupplyArgs {
+    amount: String,
 
     #[arg(long)]
-    from: Option<String>,
+    mint: Option<String>,
+}
+
+#[derive(Parser)]
+struct SetQuotaWindowArgs {
+    /// Window length in seconds or as a duration such as 1h or 7d.
+    window: String,
 
     #[arg(long)]
-    to: Option<String>,
+    mint: Option<String>,
+}
+
+#[derive(Parser)]
+struct SetTransferHookProgramArgs {
+    program_id: String,
 
     #[arg(long)]
     mint: Option<String>,
 }
 
-fn main() -> Result<()> {
-    let cli = Cli::parse();
-    run(cli)
+#[derive(Parser)]
+struct EnableTransferHookArgs {
+    /// Hook program; defaults to the bundled transfer hook.
+    program_id: Option<String>,
+
+    #[arg(long)]
+    mint: Option<String>,
 }
 
-fn run(cli: Cli) -> Result<()> {
-    let solana_config = load_solana_cli_config().ok();
+#[derive(Parser)]
+struct SetTransferLimitArgs {
+    /// Largest amount per transfer, or `none` to remove the limit.
+    amount: String,
 
-    match &cli.command {
-        Commands::Init(args) => {
-            let config_file = args
-                .config
-                .as_ref()
-                .map(|path| load_sss_config(path))
-                .transpose()?;
-            let network_override = config_file.as_ref().and_then(|cfg| cfg.network.as_ref());
-            let ctx = build_context(&cli, solana_config.as_ref(), network_override)?;
-            handle_init(&ctx, args, config_file.as_ref())
-        }
-        Commands::Mint(args) => {
-            let ctx = build_context(&cli, solana_config.as_ref(), None)?;
-            handle_mint(&ctx, args)
-        }
-        Commands::Burn(args) => {
-            let ctx = build_context(&cli, solana_config.as_ref(), None)?;
-            handle_burn(&ctx, args)
-        }
-        Commands::Freeze(args) => {
-            let ctx = build_context(&cli, solana_config.as_ref(), None)?;
-            handle_freeze(&ctx, args)
-        }
-        Commands::Thaw(args) => {
-            let ctx = build_context(&cli, solana_config.as_ref(), None)?;
-            handle_thaw(&ctx, args)
-        }
-        Commands::Pause(args) => {
-            let ctx = build_context(&cli, solana_config.as_ref(), None)?;
-            handle_pause(&ctx, args)
-        }
-        Commands::Unpause(args) => {
-            let ctx = build_context(&cli, solana_config.as_ref(), None)?;
-            handle_unpause(&ctx, args)
-        }
-        Commands::Blacklist(args) => {
-            let ctx = build_context(&cli, solana_config.as_ref(), None)?;
-            handle_blacklist(&ctx, &args.command)
-        }
-        Commands::Seize(args) => {
-            let ctx = build_context(&cli, solana_config.as_ref(), None)?;
-            handle_seize(&ctx, args)
-        }
-        Commands::Minters(args) => {
-            let ctx = build_context(&cli, solana_config.as_ref(), None)?;
-            handle_minters(&ctx, &args.command)
-        }
-        Commands::Status(args) => {
-            let ctx = build_context(&cli, solana_config.as_ref(), None)?;
-            handle_status(&ctx, args)
-        }
-        Commands::Supply(args) => {
-            let ctx = build_context(&cli, solana_config.as_ref(), None)?;
-            handle_supply(&ctx, args)
-        }
-        Commands::Holders(args) => {
-            let ctx = build_context(&cli, solana_config.as_ref(), None)?;
-            handle_holders(&ctx, args)
-        }
-        Commands::AuditLog(args) => {
-            let ctx = build_context(&cli, solana_config.as_ref(), None)?;
-            handle_audit_log(&ctx, args)
-        }
-    }
+    #[arg(long)]
+    mint: Option<String>,
 }
 
-#[derive(Debug, Clone)]
-struct ClusterInfo {
-    url: String,
-    label: Option<String>,
+#[derive(Parser)]
+struct SetTreasuryArgs {
+    /// Wallet owning the treasury token account, or `none`.
+    treasury: String,
+
+    #[arg(long)]
+    mint: Option<String>,
 }
 
-#[derive(Clone, Copy)]
-struct AppContext<'a> {
-    client: &'a RpcClient,
-    payer: &'a Keypair,
-    output: OutputFormat,
-    cluster: &'a ClusterInfo,
-    commitment: CommitmentConfig,
+#[derive(Parser)]
+struct SetSelfTransferArgs {
+    #[arg(value_enum)]
+    state: Toggle,
+
+    #[arg(long)]
+    mint: Option<String>,
 }
 
-fn build_context(
-    cli: &Cli,
-    solana_config: Option<&SolanaCliConfig>,
-    network_override: Option<&NetworkConfig>,
-) -> Result<OwnedContext> {
-    let cluster_value = if let Some(value) = cli.cluster.as_deref() {
-        value.to_string()
-    } else if let Some(value) = network_override.and_then(|cfg| cfg.cluster.as_deref()) {
-        value.to_string()
-    } else if let Some(config) = solana_config {
-        config.json_rpc_url.clone()
-    } else {
-        "devnet".to_string()
-    };
+#[derive(Parser)]
+struct SetMintApprovalArgs {
+    /// `on` rejects direct mint and mint-batch.
+    #[arg(value_enum)]
+    state: Toggle,
 
-    let cluster = resolve_cluster(&cluster_value)?;
+    #[arg(long)]
+    mint: Option<String>,
+}
 
-    let keypair_value = if let Some(value) = cli.keypair.as_deref() {
-        value.to_string()
-    } else if let Some(value) = network_override.and_then(|cfg| cfg.keypair_path.as_deref()) {
-        value.to_string()
-    } else if let Some(config) = solana_config {
-        config.keypair_path.clone()
-    } else {
-        return Err(anyhow!(
-            "Missing keypair path. Use --keypair or Solana CLI config."
-        ));
-    };
+#[derive(Parser)]
+struct MetadataArgs {
+    #[command(subcommand)]
+    command: MetadataCmd,
+}
 
-    let commitment_value =
-        if let Some(value) = network_override.and_then(|cfg| cfg.commitment.as_deref()) {
-            Some(value.to_string())
-        } else if let Some(config) = solana_config.and_then(|cfg| cfg.commitment.clone()) {
-            Some(config)
-        } else {
-            None
-        };
+#[derive(Subcommand)]
+enum MetadataCmd {
+    /// Rewrite name, symbol, and/or uri in the config and the mint metadata.
+    Update(MetadataUpdateArgs),
+    /// Print the mint's metadata pointer, Token-2022 metadata, and whether a
+    /// Metaplex metadata account exists.
+    Show(MintOnlyArgs),
+    /// Set an additional_metadata key, e.g. an attestation URL.
+    SetField(MetadataSetFieldArgs),
+}
 
-    let commitment = parse_commitment(commitment_value.as_deref());
+#[derive(Parser)]
+struct MetadataSetFieldArgs {
+    #[arg(long)]
+    key: String,
 
-    let keypair_path = expand_tilde(&keypair_value);
-    let payer = read_keypair_file(&keypair_path)
-        .map_err(|err| anyhow!("Failed to read keypair: {}", err))?;
+    #[arg(long)]
+    value: String,
 
-    let client = RpcClient::new_with_commitment(cluster.url.clone(), commitment);
+    #[arg(long)]
+    mint: Option<String>,
+}
 
-    Ok(OwnedContext {
-        client,
-        payer,
-        output: cli.output.clone(),
-        cluster,
-        commitment,
-    })
+#[derive(Parser)]
+#[command(group(clap::ArgGroup::new("fields").required(true).multiple(true)))]
+struct MetadataUpdateArgs {
+    #[arg(long, group = "fields")]
+    name: Option<String>,
+
+    #[arg(long, group = "fields")]
+    symbol: Option<String>,
+
+    #[arg(long, group = "fields")]
+    uri: Option<String>,
+
+    #[arg(long)]
+    mint: Option<String>,
 }
 
-struct OwnedContext {
-    client: RpcClient,
-    payer: Keypair,
-    output: OutputFormat,
-    cluster: ClusterInfo,
-    commitment: CommitmentConfig,
+#[derive(Parser)]
+struct SupplyLockArgs {
+    /// Also clear the mint's mint authority on-chain. The config PDA stays
+    /// the freeze authority.
+    #[arg(long)]
+    null_mint_authority: bool,
+
+    #[arg(long)]
+    mint: Option<String>,
 }
 
-impl OwnedContext {
-    fn as_ref(&self) -> AppContext<'_> {
-        AppContext {
-            client: &self.client,
-            payer: &self.payer,
-            output: self.output.clone(),
-            cluster: &self.cluster,
-            commitment: self.commitment,
-        }
-    }
+#[derive(Parser)]
+struct MintOnlyArgs {
+    #[arg(long)]
+    mint: Option<String>,
 }
 
-fn handle_init(ctx: &OwnedContext, args: &InitArgs, config: Option<&SssConfig>) -> Result<()> {
-    let preset = args.preset.as_deref().map(|value| value.to_lowercase());
-    let has_config = args.config.is_some();
-    if preset.is_some() && has_config {
-        return Err(anyhow!("--preset and --config are mutually exclusive"));
-    }
+#[derive(Parser)]
+#[command(args_conflicts_with_subcommands = true)]
+struct StatusArgs {
+    #[command(subcommand)]
+    command: Option<StatusCmd>,
 
-    let (token, extensions, roles) = if let Some(config) = config {
-        (
-            config.token.clone(),
-            config.extensions.clone().unwrap_or_default(),
-            config.roles.clone().unwrap_or_default(),
-        )
-    } else {
-        let preset = preset.ok_or_else(|| anyhow!("Missing --preset or --config"))?;
-        let name = args
-            .name
-            .clone()
-            .ok_or_else(|| anyhow!("--name is required when using --preset"))?;
-        let symbol = args
-            .symbol
-            .clone()
-            .ok_or_else(|| anyhow!("--symbol is required when using --preset"))?;
-        let token = TokenConfig {
-            name,
-            symbol,
-            decimals: Some(args.decimals),
-            uri: args.uri.clone(),
-        };
-        let extensions = match preset.as_str() {
-            "sss-1" => ExtensionsConfig::from_preset(false),
-            "sss-2" => ExtensionsConfig::from_preset(true),
-            _ => return Err(anyhow!("Invalid preset: {}", preset)),
-        };
-        (token, extensions, RolesConfig::default())
-    };
+    /// Include every minter's quota and current window usage.
+    #[arg(long)]
+    detailed: bool,
 
-    let decimals = token.decimals.unwrap_or(6);
-    let uri = token.uri.unwrap_or_default();
+    #[arg(long)]
+    mint: Option<String>,
+}
 
-    let enable_transfer_hook = extensions.transfer_hook.unwrap_or(false);
-    let enable_permanent_delegate = extensions.permanent_delegate.unwrap_or(false);
-    let default_account_frozen = extensions.default_account_frozen.unwrap_or(false);
+#[derive(Subcommand)]
+enum StatusCmd {
+    /// Choose whether newly created token accounts start frozen.
+    DefaultFrozen(SetDefaultFrozenArgs),
+}
 
-    if extensions.confidential_transfer.unwrap_or(false) {
-        return Err(anyhow!("Confidential transfer is not supported"));
-    }
+#[derive(Parser)]
+struct SetDefaultFrozenArgs {
+    #[arg(value_enum)]
+    state: Toggle,
 
-    let ctx_ref = ctx.as_ref();
-    let mint_keypair = Keypair::new();
-    let program_id = stablecoin_core::ID;
-    let (config_pda, _) = find_config_pda(&mint_keypair.pubkey(), &program_id);
-    let (role_pda, _) = find_role_pda(&config_pda, &ctx_ref.payer.pubkey(), &program_id);
+    #[arg(long)]
+    mint: Option<String>,
+}
 
-    let transfer_hook_program = if enable_transfer_hook {
-        Some(transfer_hook::ID)
-    } else {
-        None
-    };
-    let extra_metas =
-        transfer_hook_program.map(|id| find_extra_account_metas_pda(&mint_keypair.pubkey(), &id).0);
+#[derive(Parser)]
+struct ReportArgs {
+    /// Overrides --output for the report.
+    #[arg(long, value_enum)]
+    format: Option<OutputFormat>,
 
-    let initialize_ix = build_initialize_instruction(InitializeParams {
-        authority: ctx_ref.payer.pubkey(),
-        mint: mint_keypair.pubkey(),
-        name: token.name,
-        symbol: token.symbol,
-        uri,
-        decimals,
-        enable_permanent_delegate,
-        enable_transfer_hook,
-        default_account_frozen,
-        transfer_hook_program,
-        config_pda,
-        role_pda,
-        extra_metas,
-    })?;
+    #[arg(long)]
+    mint: Option<String>,
+}
 
-    let signature = send_transaction(ctx_ref, vec![initialize_ix], vec![&mint_keypair])?;
+/// Without --minting, --burning or --transfers every operation is covered.
+#[derive(Parser)]
+struct PauseArgs {
+    #[arg(long)]
+    minting: bool,
 
-    let role_map = build_role_assignments(&roles)?;
-    if !role_map.is_empty() {
-        let mut instructions = Vec::new();
-        for (target, assignment) in role_map {
-            instructions.push(build_update_roles_instruction(UpdateRolesParams {
-                authority: ctx_ref.payer.pubkey(),
-                config_pda,
-                target,
-                roles: assignment.roles,
-                mint_quota: assignment.mint_quota,
-            })?);
-        }
-        let _ = send_transaction(ctx_ref, instructions, vec![])?;
-    }
+    #[arg(long)]
+    burning: bool,
 
-    let preset_label = if enable_transfer_hook {
-        "SSS-2"
-    } else {
-        "SSS-1"
-    };
-    let explorer = explorer_url(&signature, ctx_ref.cluster);
+    #[arg(long)]
+    transfers: bool,
 
-    if ctx_ref.output == OutputFormat::Json {
-        let output = InitOutput {
-            mint: mint_keypair.pubkey().to_string(),
-            config: config_pda.to_string(),
-            preset: preset_label.to_string(),
-            signature: signature.clone(),
-            explorer,
-        };
-        print_json(&output)
-    } else {
-        println!("Stablecoin initialized");
-        println!("Mint:     {}", mint_keypair.pubkey());
-        println!("Config:   {}", config_pda);
-        println!("Preset:   {}", preset_label);
-        println!("Tx:       {}", signature);
-        if let Some(url) = explorer {
-            println!("Explorer: {}", url);
-        }
-        Ok(())
+    #[arg(long)]
+    mint: Option<String>,
+}
+
+impl PauseArgs {
+    /// PAUSE_* mask for the selected operations, or None for all of them.
+    fn mask(&self) -> Option<u8> {
+        let mask = [
+            (self.minting, PAUSE_MINT),
+            (self.burning, PAUSE_BURN),
+            (self.transfers, PAUSE_TRANSFER),
+        ]
+        .iter()
+        .filter(|(selected, _)| *selected)
+        .fold(0, |mask, (_, bit)| mask | bit);
+        (mask != 0).then_some(mask)
     }
 }
 
-fn handle_mint(ctx: &OwnedContext, args: &MintArgs) -> Result<()> {
-    let ctx_ref = ctx.as_ref();
-    let mint = resolve_mint(&args.mint)?;
+#[derive(Parser)]
+struct InitExtraMetasArgs {
+    /// Hook program; defaults to the one in the config, then the bundled hook.
+    #[arg(long)]
+    program: Option<String>,
+
+    #[arg(long)]
+    mint: Option<String>,
+}
+
+#[derive(Parser)]
+struct ConfigCheckArgs {
+    /// TOML file as passed to init --config.
+    #[arg(long)]
+    config: String,
+}
+
+#[derive(Parser)]
+struct AliasArgs {
+    #[command(subcommand)]
+    command: AliasCmd,
+}
+
+#[derive(Subcommand)]
+enum AliasCmd {
+    /// Map a name to a pubkey, or to another alias.
+    Add(AliasAddArgs),
+    /// Print every alias and the pubkey it resolves to.
+    List,
+}
+
+#[derive(Parser)]
+struct AliasAddArgs {
+    name: String,
+
+    /// Pubkey, or the name of another alias.
+    pubkey: String,
+}
+
+#[derive(Parser)]
+struct ExportIdlArgs {
+    /// Include the layouts of the stablecoin-core accounts the hook reads.
+    #[arg(long)]
+    with_types: bool,
+
+    /// Write the descriptor to a file instead of stdout.
+    #[arg(long)]
+    out: Option<PathBuf>,
+}
+
+#[derive(Parser)]
+struct ImportTxArgs {
+    #[command(subcommand)]
+    command: ImportTxCmd,
+}
+
+#[derive(Subcommand)]
+enum ImportTxCmd {
+    /// Broadcast the signed transactions of an --export-tx file, in order
+    Submit(ImportTxSubmitArgs),
+}
+
+#[derive(Parser)]
+struct ImportTxSubmitArgs {
+    path: PathBuf,
+}
+
+#[derive(Parser)]
+struct SupplyArgs {
+    /// Also count the token accounts with a nonzero balance. Scans every
+    /// token account of the mint, like `holders`.
+    #[arg(long)]
+    with_holders: bool,
+
+    #[arg(long)]
+    mint: Option<String>,
+}
+
+#[derive(Parser)]
+#[command(args_conflicts_with_subcommands = true)]
+struct HoldersArgs {
+    #[command(subcommand)]
+    command: Option<HoldersCmd>,
+
+    #[arg(long)]
+    min_balance: Option<String>,
+
+    /// Print at most this many holders.
+    #[arg(long)]
+    limit: Option<usize>,
+
+    /// Skip this many holders of the sorted list first.
+    #[arg(long, default_value_t = 0)]
+    offset: usize,
+
+    #[arg(long, value_enum, default_value = "desc")]
+    sort: SortOrder,
+
+    #[arg(long)]
+    mint: Option<String>,
+}
+
+#[derive(Subcommand)]
+enum HoldersCmd {
+    /// Write every holder, the supply, and the current slot to a file.
+    Snapshot(HoldersSnapshotArgs),
+}
+
+#[derive(Parser)]
+struct HoldersSnapshotArgs {
+    #[arg(long)]
+    out: PathBuf,
+
+    #[arg(long, value_enum, default_value = "json")]
+    format: SnapshotFormat,
+
+    #[arg(long)]
+    min_balance: Option<String>,
+
+    #[arg(long)]
+    mint: Option<String>,
+}
+
+#[derive(Clone, Copy, ValueEnum, PartialEq, Eq)]
+enum SnapshotFormat {
+    Json,
+    Csv,
+}
+
+#[derive(Parser)]
+struct AuditLogArgs {
+    #[arg(long)]
+    action: Option<String>,
+
+    /// Start of the range: unix timestamp, YYYY-MM-DD, RFC 3339, today,
+    /// yesterday, or a duration ago such as 7d or 12h.
+    #[arg(long, conflicts_with = "since")]
+    from: Option<String>,
+
+    /// End of the range, in the same formats as --from.
+    #[arg(long)]
+    to: Option<String>,
+
+    /// Shorthand for --from, e.g. --since 7d.
+    #[arg(long)]
+    since: Option<String>,
+
+    /// Most recent config transactions to scan.
+    #[arg(long, default_value_t = 1000)]
+    limit: usize,
+
+    /// Also list the transfer hook's allow/deny/exempt line for each
+    /// transfer, including transfers it denied.
+    #[arg(long)]
+    hook_decisions: bool,
+
+    #[arg(long)]
+    mint: Option<String>,
+}
+
+#[derive(Parser)]
+struct WatchArgs {
+    /// Owner whose token account balance is streamed instead of the mint's
+    /// events.
+    #[arg(long)]
+    balance: Option<String>,
+
+    /// Only print events with this action (MINT) or event name (TokensMinted).
+    #[arg(long, conflicts_with = "balance")]
+    action: Option<String>,
+
+    #[arg(long)]
+    mint: Option<String>,
+}
+
+fn main() -> Result<()> {
+    let cli = Cli::parse();
+    run(cli)
+}
+
+fn run(cli: Cli) -> Result<()> {
+    aliases::set_verbose(cli.verbose);
+    let solana_config = load_solana_cli_config().ok();
+
+    match &cli.command {
+        Commands::Init(args) => {
+            let config_file = args
+                .config
+                .as_ref()
+                .map(|path| load_sss_config(path))
+                .transpose()?;
+            let network_override = config_file.as_ref().and_then(|cfg| cfg.network.as_ref());
+            let ctx = build_context(&cli, solana_config.as_ref(), network_override)?;
+            handle_init(&ctx, args, config_file.as_ref())
+        }
+        Commands::Mint(args) => {
+            let ctx = build_context(&cli, solana_config.as_ref(), None)?;
+            match &args.request {
+                Some(cmd) => handle_mint_request(&ctx, cmd),
+                None => handle_mint(&ctx, args),
+            }
+        }
+        Commands::MintBatch(args) => {
+            let ctx = build_context(&cli, solana_config.as_ref(), None)?;
+            handle_mint_batch(&ctx, args)
+        }
+        Commands::Burn(args) => {
+            let ctx = build_context(&cli, solana_config.as_ref(), None)?;
+            handle_burn(&ctx, args)
+        }
+        Commands::Transfer(args) => {
+            let ctx = build_context(&cli, solana_config.as_ref(), None)?;
+            handle_transfer(&ctx, args)
+        }
+        Commands::Freeze(args) => {
+            let ctx = build_context(&cli, solana_config.as_ref(), None)?;
+            handle_freeze(&ctx, args)
+        }
+        Commands::Thaw(args) => {
+            let ctx = build_context(&cli, solana_config.as_ref(), None)?;
+            handle_thaw(&ctx, args)
+        }
+        Commands::Pause(args) => {
+            let ctx = build_context(&cli, solana_config.as_ref(), None)?;
+            handle_pause(&ctx, args)
+        }
+        Commands::Unpause(args) => {
+            let ctx = build_context(&cli, solana_config.as_ref(), None)?;
+            handle_unpause(&ctx, args)
+        }
+        Commands::EmergencyLock(args) => {
+            let ctx = build_context(&cli, solana_config.as_ref(), None)?;
+            handle_emergency_lock(&ctx, args, true)
+        }
+        Commands::EmergencyUnlock(args) => {
+            let ctx = build_context(&cli, solana_config.as_ref(), None)?;
+            handle_emergency_lock(&ctx, args, false)
+        }
+        Commands::Blacklist(args) => {
+            let ctx = build_context(&cli, solana_config.as_ref(), None)?;
+            handle_blacklist(&ctx, &args.command)
+        }
+        Commands::Allowlist(args) => {
+            let ctx = build_context(&cli, solana_config.as_ref(), None)?;
+            handle_allowlist(&ctx, &args.command)
+        }
+        Commands::Seize(args) => {
+            let ctx = build_context(&cli, solana_config.as_ref(), None)?;
+            handle_seize(&ctx, args)
+        }
+        Commands::SeizeBatch(args) => {
+            let ctx = build_context(&cli, solana_config.as_ref(), None)?;
+            handle_seize_batch(&ctx, args)
+        }
+        Commands::Minters(args) => {
+            let ctx = build_context(&cli, solana_config.as_ref(), None)?;
+            handle_minters(&ctx, &args.command)
+        }
+        Commands::Roles(args) => {
+            let ctx = build_context(&cli, solana_config.as_ref(), None)?;
+            handle_roles(&ctx, &args.command)
+        }
+        Commands::Config(args) => {
+            let ctx = build_context(&cli, solana_config.as_ref(), None)?;
+            handle_config(&ctx, &args.command)
+        }
+        Commands::Metadata(args) => {
+            let ctx = build_context(&cli, solana_config.as_ref(), None)?;
+            handle_metadata(&ctx, &args.command)
+        }
+        Commands::Status(args) => {
+            let ctx = build_context(&cli, solana_config.as_ref(), None)?;
+            handle_status(&ctx, args)
+        }
+        Commands::VerifyAuthorities(args) => {
+            let ctx = build_context(&cli, solana_config.as_ref(), None)?;
+            handle_verify_authorities(&ctx, args)
+        }
+        Commands::Verify(args) => {
+            let ctx = build_context(&cli, solana_config.as_ref(), None)?;
+            handle_verify(&ctx, args)
+        }
+        Commands::Report(args) => {
+            let ctx = build_context(&cli, solana_config.as_ref(), None)?;
+            handle_report(&ctx, args)
+        }
+        Commands::Supply(args) => {
+            let ctx = build_context(&cli, solana_config.as_ref(), None)?;
+            handle_supply(&ctx, args)
+        }
+        Commands::FreezeSupply(args) => {
+            let ctx = build_context(&cli, solana_config.as_ref(), None)?;
+            handle_freeze_supply(&ctx, args)
+        }
+        Commands::Holders(args) => {
+            let ctx = build_context(&cli, solana_config.as_ref(), None)?;
+            handle_holders(&ctx, args)
+        }
+        Commands::Balance(args) => {
+            let ctx = build_context(&cli, solana_config.as_ref(), None)?;
+            handle_balance(&ctx, args)
+        }
+        Commands::AuditLog(args) => {
+            let ctx = build_context(&cli, solana_config.as_ref(), None)?;
+            handle_audit_log(&ctx, args)
+        }
+        Commands::Watch(args) => {
+            let ctx = build_context(&cli, solana_config.as_ref(), None)?;
+            handle_watch(&ctx, args)
+        }
+        Commands::ConfigCheck(args) => handle_config_check(cli.output, args),
+        Commands::Alias(args) => handle_alias(cli.output, &args.command),
+        Commands::InitExtraMetas(args) => {
+            let ctx = build_context(&cli, solana_config.as_ref(), None)?;
+            handle_init_extra_metas(&ctx, args)
+        }
+        Commands::ExportIdl(args) => handle_export_idl(args),
+        Commands::ImportTx(args) => {
+            let ctx = build_context(&cli, solana_config.as_ref(), None)?;
+            handle_import_tx(&ctx, &args.command)
+        }
+    }
+}
+
+#[derive(Debug, Clone)]
+struct ClusterInfo {
+    url: String,
+    label: Option<String>,
+}
+
+#[derive(Clone, Copy)]
+struct AppContext<'a> {
+    client: &'a RpcClient,
+    payer: &'a dyn Signer,
+    output: OutputFormat,
+    cluster: &'a ClusterInfo,
+    commitment: CommitmentConfig,
+    authority_override: Option<Pubkey>,
+    retry: RetryPolicy,
+    compute_budget: ComputeBudget,
+    export_tx: Option<&'a Path>,
+    /// Transactions exported so far this run.
+    exported: &'a Cell<usize>,
+    simulate: bool,
+    assume_yes: bool,
+    raw_amounts: bool,
+}
+
+impl AppContext<'_> {
+    /// Authority that instructions are built for: the --authority override or the payer.
+    fn authority(&self) -> Pubkey {
+        self.authority_override
+            .unwrap_or_else(|| self.payer.pubkey())
+    }
+
+    /// False when transactions are only simulated, exported, or proposed to
+    /// a multisig, so nothing lands on chain from this run.
+    fn submits(&self) -> bool {
+        !self.simulate && self.export_tx.is_none() && self.authority_override.is_none()
+    }
+
+    /// Asks before a destructive action is submitted.
+    fn confirm(&self, prompt: &str) -> Result<()> {
+        let skip = !needs_prompt(self.output, self.assume_yes, self.submits())?;
+        confirm(prompt, skip)
+    }
+
+    /// Parses a mint, burn or seize amount: as raw base units under
+    /// --raw-amounts, otherwise through parse_amount.
+    fn parse_amount(&self, value: &str, decimals: u8) -> Result<u64> {
+        if self.raw_amounts {
+            parse_raw_amount(value)
+        } else {
+            parse_amount(value, decimals)
+        }
+    }
+
+    /// Formats an amount printed by mint, burn or seize; base units under
+    /// --raw-amounts.
+    fn format_amount(&self, amount: u64, decimals: u8) -> String {
+        if self.raw_amounts {
+            amount.to_string()
+        } else {
+            format_amount(amount, decimals)
+        }
+    }
+}
+
+fn build_context(
+    cli: &Cli,
+    solana_config: Option<&SolanaCliConfig>,
+    network_override: Option<&NetworkConfig>,
+) -> Result<OwnedContext> {
+    let cluster_value = if let Some(value) = cli.cluster.as_deref() {
+        value.to_string()
+    } else if let Some(value) = network_override.and_then(|cfg| cfg.cluster.as_deref()) {
+        value.to_string()
+    } else if let Some(config) = solana_config {
+        config.json_rpc_url.clone()
+    } else {
+        "devnet".to_string()
+    };
+
+    let cluster = resolve_cluster(&cluster_value)?;
+
+    let keypair_value = if let Some(value) = cli.keypair.as_deref() {
+        value.to_string()
+    } else if let Some(value) = network_override.and_then(|cfg| cfg.keypair_path.as_deref()) {
+        value.to_string()
+    } else if let Some(config) = solana_config {
+        config.keypair_path.clone()
+    } else {
+        return Err(anyhow!(
+            "Missing keypair path. Use --keypair or Solana CLI config."
+        ));
+    };
+
+    let commitment_value =
+        if let Some(value) = network_override.and_then(|cfg| cfg.commitment.as_deref()) {
+            Some(value.to_string())
+        } else {
+            solana_config.and_then(|cfg| cfg.commitment.clone())
+        };
+
+    let commitment = parse_commitment(commitment_value.as_deref());
+
+    let payer = load_signer(&keypair_value)?;
+
+    let configured_headers = network_override
+        .and_then(|cfg| cfg.rpc_headers.as_deref())
+        .unwrap_or_default();
+    let headers = parse_rpc_headers(configured_headers.iter().chain(&cli.rpc_headers))?;
+    let client = build_rpc_client(
+        &cluster.url,
+        commitment,
+        cli.insecure_accept_invalid_certs,
+        headers,
+    )?;
+    let authority_override = cli.authority.as_deref().map(parse_pubkey).transpose()?;
+
+    Ok(OwnedContext {
+        client,
+        payer,
+        output: cli.output,
+        cluster,
+        commitment,
+        authority_override,
+        retry: RetryPolicy {
+            max_attempts: cli.max_retries,
+            base_delay: Duration::from_millis(cli.retry_delay_ms),
+        },
+        compute_budget: ComputeBudget {
+            unit_price: cli.priority_fee,
+            unit_limit: cli.compute_limit,
+        },
+        export_tx: cli.export_tx.clone(),
+        exported: Cell::new(0),
+        simulate: cli.simulate,
+        assume_yes: cli.yes,
+        raw_amounts: cli.raw_amounts,
+        verbose: cli.verbose,
+    })
+}
+
+/// Resolves --keypair: a keypair file, or a `usb://ledger` URI (with an
+/// optional `?key=<account>/<change>` derivation path) for a hardware wallet.
+fn load_signer(value: &str) -> Result<Box<dyn Signer>> {
+    if !value.starts_with("usb://") {
+        let keypair = read_keypair_file(expand_tilde(value))
+            .map_err(|err| anyhow!("Failed to read keypair: {}", err))?;
+        return Ok(Box::new(keypair));
+    }
+    let uri = URIReference::try_from(value)
+        .map_err(|err| anyhow!("Invalid keypair URI {}: {}", value, err))?;
+    let locator = Locator::new_from_uri(&uri)
+        .map_err(|err| anyhow!("Invalid keypair URI {}: {}", value, err))?;
+    let derivation_path = DerivationPath::from_uri_key_query(&uri)
+        .map_err(|err| anyhow!("Invalid derivation path in {}: {}", value, err))?
+        .unwrap_or_default();
+    let wallet_manager = maybe_wallet_manager()
+        .map_err(|err| {
+            anyhow!(
+                "Hardware wallets are unavailable ({}); build with --features ledger",
+                err
+            )
+        })?
+        .ok_or_else(|| anyhow!("No hardware wallet found; connect and unlock it first"))?;
+    let keypair =
+        generate_remote_keypair(locator, derivation_path, &wallet_manager, false, "keypair")
+            .map_err(|err| anyhow!("Failed to open hardware wallet: {}", err))?;
+    Ok(Box::new(keypair))
+}
+
+/// Parses `NAME: VALUE` headers; a later value for the same name replaces
+/// an earlier one.
+fn parse_rpc_headers<'a>(values: impl IntoIterator<Item = &'a String>) -> Result<HeaderMap> {
+    let mut headers = HeaderMap::new();
+    for value in values {
+        let (name, header_value) = value
+            .split_once(':')
+            .ok_or_else(|| anyhow!("Invalid RPC header (expected NAME: VALUE): {}", value))?;
+        let name = HeaderName::from_bytes(name.trim().as_bytes())
+            .map_err(|_| anyhow!("Invalid RPC header name: {}", name.trim()))?;
+        // The value is usually an API key, so it is left out of the error.
+        let header_value = HeaderValue::from_str(header_value.trim())
+            .map_err(|_| anyhow!("Invalid value for RPC header {}", name))?;
+        headers.insert(name, header_value);
+    }
+    Ok(headers)
+}
+
+fn build_rpc_client(
+    url: &str,
+    commitment: CommitmentConfig,
+    accept_invalid_certs: bool,
+    headers: HeaderMap,
+) -> Result<RpcClient> {
+    if !accept_invalid_certs && headers.is_empty() {
+        return Ok(RpcClient::new_with_commitment(url.to_string(), commitment));
+    }
+
+    if accept_invalid_certs {
+        eprintln!(
+            "WARNING: TLS certificate verification is disabled for {}. \
+             Anyone on the network path can impersonate this RPC node; \
+             never use --insecure-accept-invalid-certs with a public endpoint.",
+            url
+        );
+    }
+    let mut default_headers = HttpSender::default_headers();
+    default_headers.extend(headers);
+    let timeout = Duration::from_secs(30);
+    let http = reqwest::Client::builder()
+        .default_headers(default_headers)
+        .timeout(timeout)
+        .pool_idle_timeout(timeout)
+        .danger_accept_invalid_certs(accept_invalid_certs)
+        .build()
+        .context("Failed to build RPC HTTP client")?;
+    Ok(RpcClient::new_sender(
+        HttpSender::new_with_client(url, http),
+        RpcClientConfig::with_commitment(commitment),
+    ))
+}
+
+struct OwnedContext {
+    client: RpcClient,
+    payer: Box<dyn Signer>,
+    output: OutputFormat,
+    cluster: ClusterInfo,
+    commitment: CommitmentConfig,
+    authority_override: Option<Pubkey>,
+    retry: RetryPolicy,
+    compute_budget: ComputeBudget,
+    export_tx: Option<PathBuf>,
+    exported: Cell<usize>,
+    simulate: bool,
+    assume_yes: bool,
+    raw_amounts: bool,
+    verbose: bool,
+}
+
+impl OwnedContext {
+    fn as_ref(&self) -> AppContext<'_> {
+        AppContext {
+            client: &self.client,
+            payer: self.payer.as_ref(),
+            output: self.output,
+            cluster: &self.cluster,
+            commitment: self.commitment,
+            authority_override: self.authority_override,
+            retry: self.retry,
+            compute_budget: self.compute_budget,
+            export_tx: self.export_tx.as_deref(),
+            exported: &self.exported,
+            simulate: self.simulate,
+            assume_yes: self.assume_yes,
+            raw_amounts: self.raw_amounts,
+        }
+    }
+}
+
+fn handle_init(ctx: &OwnedContext, args: &InitArgs, config: Option<&SssConfig>) -> Result<()> {
+    if ctx.authority_override.is_some() {
+        return Err(anyhow!(
+            "--authority is not supported for init; initialize with a keypair and transfer authority to the multisig"
+        ));
+    }
+    let preset = args.preset.as_deref().map(|value| value.to_lowercase());
+    let has_config = args.config.is_some();
+    if preset.is_some() && has_config {
+        return Err(anyhow!("--preset and --config are mutually exclusive"));
+    }
+
+    let (token, extensions, roles) = if let Some(config) = config {
+        (
+            config.token.clone(),
+            config.extensions.clone().unwrap_or_default(),
+            config.roles.clone().unwrap_or_default(),
+        )
+    } else {
+        let preset = preset.ok_or_else(|| anyhow!("Missing --preset or --config"))?;
+        let name = args
+            .name
+            .clone()
+            .ok_or_else(|| anyhow!("--name is required when using --preset"))?;
+        let symbol = args
+            .symbol
+            .clone()
+            .ok_or_else(|| anyhow!("--symbol is required when using --preset"))?;
+        let token = TokenConfig {
+            name,
+            symbol,
+            decimals: Some(args.decimals),
+            uri: args.uri.clone(),
+            max_supply: None,
+            treasury: None,
+            token_program: None,
+        };
+        let extensions = match preset.as_str() {
+            "sss-1" => ExtensionsConfig::from_preset(false),
+            "sss-2" => ExtensionsConfig::from_preset(true),
+            "sss-3" => ExtensionsConfig {
+                confidential_transfer: Some(true),
+                ..ExtensionsConfig::from_preset(false)
+            },
+            _ => return Err(anyhow!("Invalid preset: {}", preset)),
+        };
+        (token, extensions, RolesConfig::default())
+    };
+
+    let decimals = token.decimals.unwrap_or(6);
+    check_decimals(decimals)?;
+    let uri = token.uri.unwrap_or_default();
+    let max_supply = args
+        .max_supply
+        .as_deref()
+        .or(token.max_supply.as_deref())
+        .map(|value| parse_amount(value, decimals))
+        .transpose()?;
+    if args.pause_cooldown < 0 {
+        return Err(anyhow!("--pause-cooldown must not be negative"));
+    }
+
+    let enable_transfer_hook = extensions.transfer_hook.unwrap_or(false);
+    let enable_permanent_delegate = extensions.permanent_delegate.unwrap_or(false);
+    let default_account_frozen = extensions.default_account_frozen.unwrap_or(false);
+    let enable_metadata = extensions.metadata.unwrap_or(true) && !args.no_metadata;
+    let enable_allowlist = extensions.allowlist.unwrap_or(false) || args.allowlist;
+    if enable_allowlist && !enable_transfer_hook {
+        return Err(anyhow!("Allowlist mode requires the transfer hook (SSS-2)"));
+    }
+    let max_transfer_amount = extensions
+        .max_transfer_amount
+        .as_deref()
+        .map(|value| parse_transfer_limit(value, decimals))
+        .transpose()?
+        .flatten();
+    if max_transfer_amount.is_some() && !enable_transfer_hook {
+        return Err(anyhow!(
+            "max_transfer_amount requires the transfer hook (SSS-2)"
+        ));
+    }
+    let transfer_cooldown_seconds = args
+        .transfer_cooldown
+        .or(extensions.transfer_cooldown_seconds);
+    if let Some(seconds) = transfer_cooldown_seconds {
+        check_transfer_cooldown(seconds, enable_transfer_hook)?;
+    }
+
+    let reserve_transfer_hook = (extensions.reserve_transfer_hook.unwrap_or(false)
+        || args.reserve_transfer_hook)
+        && !enable_transfer_hook;
+
+    let enable_confidential = extensions.confidential_transfer.unwrap_or(false);
+    let treasury = args
+        .treasury
+        .as_deref()
+        .or(token.treasury.as_deref())
+        .map(parse_pubkey)
+        .transpose()?;
+    let token_program = args
+        .token_program
+        .or(token.token_program)
+        .unwrap_or_default();
+    if token_program == TokenProgramKind::Legacy {
+        // Every extension-backed feature needs a Token-2022 mint.
+        check_legacy_mint(
+            enable_metadata,
+            enable_permanent_delegate,
+            enable_transfer_hook || reserve_transfer_hook,
+            default_account_frozen,
+            enable_confidential,
+        )?;
+    }
+
+    let ctx_ref = ctx.as_ref();
+    let mint_keypair = match (args.mint_keypair.as_deref(), args.mint_prefix.as_deref()) {
+        (Some(path), _) => read_keypair_file(expand_tilde(path))
+            .map_err(|err| anyhow!("Failed to read mint keypair: {}", err))?,
+        (None, Some(prefix)) => {
+            check_mint_prefix(prefix)?;
+            let expected = 58u64.pow(prefix.len() as u32);
+            if prefix.len() >= 3 {
+                eprintln!(
+                    "Grinding a mint address starting with {} takes about {} attempts; \
+                     each extra character multiplies that by 58.",
+                    prefix, expected
+                );
+            }
+            let show_progress =
+                ctx_ref.output == OutputFormat::Text && std::io::stderr().is_terminal();
+            let (keypair, attempts) =
+                grind_mint_keypair(prefix, MAX_MINT_PREFIX_ATTEMPTS, |attempts| {
+                    if show_progress {
+                        eprint!("\rTried {} keypairs...", attempts);
+                    }
+                })?;
+            if show_progress {
+                eprintln!();
+            }
+            if ctx.verbose {
+                eprintln!("Found {} after {} attempts", keypair.pubkey(), attempts);
+            }
+            keypair
+        }
+        (None, None) => Keypair::new(),
+    };
+    let program_id = stablecoin_core::ID;
+    let (config_pda, _) = find_config_pda(&mint_keypair.pubkey(), &program_id);
+    if ctx_ref
+        .client
+        .get_account_with_commitment(&config_pda, ctx_ref.commitment)?
+        .value
+        .is_some()
+    {
+        return Err(anyhow!(
+            "Stablecoin already initialized for this mint: {}",
+            mint_keypair.pubkey()
+        ));
+    }
+    let (role_pda, _) = find_role_pda(&config_pda, &ctx_ref.payer.pubkey(), &program_id);
+    if let Some(treasury) = &treasury {
+        check_mint_recipient(treasury, &config_pda, &mint_keypair.pubkey())?;
+    }
+
+    let transfer_hook_program = if enable_transfer_hook {
+        Some(transfer_hook::ID)
+    } else {
+        None
+    };
+    let extra_metas =
+        transfer_hook_program.map(|id| find_extra_account_metas_pda(&mint_keypair.pubkey(), &id).0);
+
+    let initialize_ix = build_initialize_instruction(InitializeParams {
+        authority: ctx_ref.payer.pubkey(),
+        mint: mint_keypair.pubkey(),
+        name: token.name,
+        symbol: token.symbol,
+        uri,
+        decimals,
+        enable_permanent_delegate,
+        enable_transfer_hook,
+        default_account_frozen,
+        transfer_hook_program,
+        pause_cooldown: args.pause_cooldown,
+        enable_metadata,
+        max_supply,
+        enable_allowlist,
+        enable_confidential,
+        treasury,
+        token_program,
+        transfer_cooldown_seconds,
+        reserve_transfer_hook,
+        config_pda,
+        role_pda,
+        extra_metas,
+    })?;
+
+    let mut init_instructions = vec![initialize_ix];
+    if max_transfer_amount.is_some() {
+        // Same transaction, so the mint never accepts transfers without the cap.
+        init_instructions.push(build_update_transfer_limit_instruction(
+            UpdateTransferLimitParams {
+                authority: ctx_ref.payer.pubkey(),
+                config_pda,
+                max_transfer_amount,
+            },
+        )?);
+    }
+    let Some(signature) = send_transaction(ctx_ref, init_instructions, vec![&mint_keypair])? else {
+        return Ok(());
+    };
+
+    let role_map = build_role_assignments(&roles)?;
+    let mut instructions = Vec::new();
+    for (target, assignment) in role_map {
+        instructions.push(build_update_roles_instruction(UpdateRolesParams {
+            authority: ctx_ref.payer.pubkey(),
+            config_pda,
+            target,
+            roles: assignment.roles,
+            mint_quota: assignment.mint_quota,
+            lifetime_quota: None,
+        })?);
+    }
+    for chunk in chunk_instructions(
+        instructions,
+        &ctx_ref.compute_budget.instructions(),
+        &ctx_ref.payer.pubkey(),
+    )? {
+        let _ = send_transaction(ctx_ref, chunk, vec![])?;
+    }
+
+    let preset_label = preset_label(enable_transfer_hook, enable_confidential);
+    let explorer = explorer_url(&signature, ctx_ref.cluster);
+
+    if ctx_ref.output == OutputFormat::Json {
+        let output = InitOutput {
+            mint: mint_keypair.pubkey().to_string(),
+            config: config_pda.to_string(),
+            preset: preset_label.to_string(),
+            signature: signature.clone(),
+            explorer,
+            compute_budget: ctx_ref.compute_budget.output(),
+        };
+        print_json(&output)
+    } else {
+        println!("Stablecoin initialized");
+        println!("Mint:     {}", mint_keypair.pubkey());
+        println!("Config:   {}", config_pda);
+        println!("Preset:   {}", preset_label);
+        println!("Tx:       {}", signature);
+        if let Some(url) = explorer {
+            println!("Explorer: {}", url);
+        }
+        Ok(())
+    }
+}
+
+fn handle_mint(ctx: &OwnedContext, args: &MintArgs) -> Result<()> {
+    let ctx_ref = ctx.as_ref();
+    let mint = resolve_mint(&args.mint)?;
+    let config_pda = find_config_pda(&mint, &stablecoin_core::ID).0;
+    let config = fetch_config(ctx_ref, &config_pda)?;
+    if config.minting_locked {
+        return Err(anyhow!("Minting is permanently locked for this stablecoin"));
+    }
+    check_direct_mint(&config)?;
+    let (recipient, amount) = match (&args.to_treasury, &args.recipient, &args.amount) {
+        (Some(amount), _, _) => (configured_treasury(&config)?, amount),
+        (None, Some(recipient), Some(amount)) => (parse_pubkey(recipient)?, amount),
+        _ => return Err(anyhow!("Pass a recipient and amount, or --to-treasury")),
+    };
+    let amount = ctx_ref.parse_amount(amount, config.decimals)?;
+    check_mint_recipient(&recipient, &config_pda, &mint)?;
+    let token_program = config.token_program_id();
+    let recipient_ata =
+        get_associated_token_address_with_program_id(&recipient, &mint, &token_program);
+    let mint_ix = build_mint_instruction(MintParams {
+        minter: ctx_ref.authority(),
+        mint,
+        recipient,
+        recipient_ata,
+        amount,
+        token_program,
+        check_blacklist: config.features.transfer_hook(),
+    })?;
+    let Some(signature) = send_transaction(ctx_ref, vec![mint_ix], vec![])? else {
+        return Ok(());
+    };
+    let supply = ctx_ref.client.get_token_supply(&mint)?;
+    // Read back after the mint: the program may have rolled the window over.
+    let role = fetch_role_account(
+        ctx_ref,
+        &find_role_pda(&config_pda, &ctx_ref.authority(), &stablecoin_core::ID).0,
+    )?;
+    let quota_remaining = role.as_ref().and_then(|role| {
+        window_quota_remaining(role, Utc::now().timestamp(), config.quota_window())
+    });
+    let explorer = explorer_url(&signature, ctx_ref.cluster);
+    if ctx_ref.output == OutputFormat::Json {
+        let output = MintOutput {
+            signature: signature.clone(),
+            explorer,
+            pdas: PdaOutput::new(&config_pda, Some(&ctx_ref.authority())),
+            compute_budget: ctx_ref.compute_budget.output(),
+            new_supply: supply.amount,
+            quota_remaining: quota_remaining.map(|remaining| remaining.to_string()),
+        };
+        print_json(&output)
+    } else {
+        println!(
+            "Minted {} tokens to {}",
+            ctx_ref.format_amount(amount, config.decimals),
+            recipient
+        );
+        println!("New supply: {}", supply.amount);
+        if let (Some(remaining), Some(quota)) =
+            (quota_remaining, role.and_then(|role| role.mint_quota))
+        {
+            println!(
+                "Quota remaining this window: {} of {}",
+                ctx_ref.format_amount(remaining, config.decimals),
+                ctx_ref.format_amount(quota, config.decimals)
+            );
+        }
+        println!("Tx: {}", signature);
+        if let Some(url) = explorer {
+            println!("Explorer: {}", url);
+        }
+        Ok(())
+    }
+}
+
+fn handle_mint_request(ctx: &OwnedContext, cmd: &MintRequestCmd) -> Result<()> {
+    let ctx_ref = ctx.as_ref();
+    match cmd {
+        MintRequestCmd::Propose(args) => {
+            let mint = resolve_mint(&args.mint)?;
+            let config_pda = find_config_pda(&mint, &stablecoin_core::ID).0;
+            let config = fetch_config(ctx_ref, &config_pda)?;
+            if config.minting_locked {
+                return Err(anyhow!("Minting is permanently locked for this stablecoin"));
+            }
+            let recipient = parse_pubkey(&args.recipient)?;
+            let amount = ctx_ref.parse_amount(&args.amount, config.decimals)?;
+            check_mint_recipient(&recipient, &config_pda, &mint)?;
+            let id = match args.id {
+                Some(id) => id,
+                None => u64::try_from(Utc::now().timestamp_millis())?,
+            };
+            let request_pda = find_mint_request_pda(&config_pda, id, &stablecoin_core::ID).0;
+            let propose_ix = build_propose_mint_instruction(ProposeMintParams {
+                requester: ctx_ref.authority(),
+                config_pda,
+                id,
+                recipient,
+                amount,
+            })?;
+            let Some(signature) = send_transaction(ctx_ref, vec![propose_ix], vec![])? else {
+                return Ok(());
+            };
+            let explorer = explorer_url(&signature, ctx_ref.cluster);
+            if ctx_ref.output == OutputFormat::Json {
+                let output = MintRequestOutput {
+                    signature: signature.clone(),
+                    explorer,
+                    pdas: PdaOutput::new(&config_pda, Some(&ctx_ref.authority())),
+                    request: request_pda.to_string(),
+                    id,
+                    compute_budget: ctx_ref.compute_budget.output(),
+                };
+                print_json(&output)
+            } else {
+                println!(
+                    "Proposed minting {} tokens to {}",
+                    ctx_ref.format_amount(amount, config.decimals),
+                    recipient
+                );
+                println!("Request: {} (id {})", request_pda, id);
+                println!(
+                    "Approve with: sss-token mint approve {} --mint {}",
+                    id, mint
+                );
+                println!("Tx: {}", signature);
+                if let Some(url) = explorer {
+                    println!("Explorer: {}", url);
+                }
+                Ok(())
+            }
+        }
+        MintRequestCmd::Approve(args) => {
+            let mint = resolve_mint(&args.mint)?;
+            let config_pda = find_config_pda(&mint, &stablecoin_core::ID).0;
+            let config = fetch_config(ctx_ref, &config_pda)?;
+            let request_pda = find_mint_request_pda(&config_pda, args.id, &stablecoin_core::ID).0;
+            let request = fetch_mint_request(ctx_ref, &request_pda)?
+                .ok_or_else(|| anyhow!("No pending mint request with id {}", args.id))?;
+            if request.requester == ctx_ref.authority() {
+                return Err(anyhow!(
+                    "A mint request must be approved by someone other than its requester ({})",
+                    request.requester
+                ));
+            }
+            if Utc::now().timestamp().saturating_sub(request.created_at) >= MINT_REQUEST_TTL_SECONDS
+            {
+                return Err(anyhow!(
+                    "Mint request {} has expired; withdraw it with mint cancel",
+                    args.id
+                ));
+            }
+            let approve_ix = build_approve_mint_instruction(ApproveMintParams {
+                approver: ctx_ref.authority(),
+                mint,
+                id: args.id,
+                requester: request.requester,
+                recipient: request.recipient,
+                token_program: config.token_program_id(),
+                check_blacklist: config.features.transfer_hook(),
+            })?;
+            let Some(signature) = send_transaction(ctx_ref, vec![approve_ix], vec![])? else {
+                return Ok(());
+            };
+            let explorer = explorer_url(&signature, ctx_ref.cluster);
+            if ctx_ref.output == OutputFormat::Json {
+                let output = MintRequestOutput {
+                    signature: signature.clone(),
+                    explorer,
+                    pdas: PdaOutput::new(&config_pda, Some(&ctx_ref.authority())),
+                    request: request_pda.to_string(),
+                    id: args.id,
+                    compute_budget: ctx_ref.compute_budget.output(),
+                };
+                print_json(&output)
+            } else {
+                println!(
+                    "Approved minting {} tokens to {}, requested by {}",
+                    ctx_ref.format_amount(request.amount, config.decimals),
+                    request.recipient,
+                    request.requester
+                );
+                println!("Tx: {}", signature);
+                if let Some(url) = explorer {
+                    println!("Explorer: {}", url);
+                }
+                Ok(())
+            }
+        }
+        MintRequestCmd::Cancel(args) => {
+            let mint = resolve_mint(&args.mint)?;
+            let config_pda = find_config_pda(&mint, &stablecoin_core::ID).0;
+            let config = fetch_config(ctx_ref, &config_pda)?;
+            let request_pda = find_mint_request_pda(&config_pda, args.id, &stablecoin_core::ID).0;
+            let request = fetch_mint_request(ctx_ref, &request_pda)?
+                .ok_or_else(|| anyhow!("No pending mint request with id {}", args.id))?;
+            let cancel_ix = build_cancel_mint_instruction(CancelMintParams {
+                authority: ctx_ref.authority(),
+                config_pda,
+                id: args.id,
+                requester: request.requester,
+            })?;
+            let Some(signature) = send_transaction(ctx_ref, vec![cancel_ix], vec![])? else {
+                return Ok(());
+            };
+            let explorer = explorer_url(&signature, ctx_ref.cluster);
+            if ctx_ref.output == OutputFormat::Json {
+                let output = MintRequestOutput {
+                    signature: signature.clone(),
+                    explorer,
+                    pdas: PdaOutput::new(&config_pda, Some(&ctx_ref.authority())),
+                    request: request_pda.to_string(),
+                    id: args.id,
+                    compute_budget: ctx_ref.compute_budget.output(),
+                };
+                print_json(&output)
+            } else {
+                println!(
+                    "Cancelled request {} to mint {} tokens to {}; rent returned to {}",
+                    args.id,
+                    ctx_ref.format_amount(request.amount, config.decimals),
+                    request.recipient,
+                    request.requester
+                );
+                println!("Tx: {}", signature);
+                if let Some(url) = explorer {
+                    println!("Explorer: {}", url);
+                }
+                Ok(())
+            }
+        }
+    }
+}
+
+fn configured_treasury(config: &StablecoinConfig) -> Result<Pubkey> {
+    config
+        .treasury
+        .ok_or_else(|| anyhow!("No treasury is set for this stablecoin; see config set-treasury"))
+}
+
+/// The treasury's associated token account, used when no explicit
+/// destination is given for treasury mints and seizures.
+fn treasury_ata(config: &StablecoinConfig, mint: &Pubkey) -> Result<Pubkey> {
+    Ok(get_associated_token_address_with_program_id(
+        &configured_treasury(config)?,
+        mint,
+        &config.token_program_id(),
+    ))
+}
+
+/// Mirrors the on-chain denylist of recipients that would burn minted tokens.
+fn check_mint_recipient(recipient: &Pubkey, config_pda: &Pubkey, mint: &Pubkey) -> Result<()> {
+    let reason = if *recipient == system_program::id() {
+        "the system program"
+    } else if *recipient == solana_sdk::incinerator::id() {
+        "the incinerator"
+    } else if recipient == config_pda {
+        "the config PDA"
+    } else if recipient == mint {
+        "the mint"
+    } else {
+        return Ok(());
+    };
+    Err(anyhow!(
+        "Refusing to mint to {} ({}): tokens there are unrecoverable",
+        recipient,
+        reason
+    ))
+}
+
+fn handle_mint_batch(ctx: &OwnedContext, args: &MintBatchArgs) -> Result<()> {
+    let ctx_ref = ctx.as_ref();
+    let mint = resolve_mint(&args.mint)?;
+    let config_pda = find_config_pda(&mint, &stablecoin_core::ID).0;
+    let config = fetch_config(ctx_ref, &config_pda)?;
+    if config.minting_locked {
+        return Err(anyhow!("Minting is permanently locked for this stablecoin"));
+    }
+    check_direct_mint(&config)?;
+    let content = fs::read_to_string(&args.file)
+        .with_context(|| format!("Failed to read {}", args.file.display()))?;
+    let entries = parse_recipients_csv(&content, config.decimals)?;
+    for (recipient, _) in &entries {
+        check_mint_recipient(recipient, &config_pda, &mint)?;
+    }
+    let minter = ctx_ref.authority();
+    let token_program = config.token_program_id();
+
+    // mint_batch only targets existing ATAs, so create the missing ones first.
+    let atas: Vec<Pubkey> = entries
+        .iter()
+        .map(|(recipient, _)| {
+            get_associated_token_address_with_program_id(recipient, &mint, &token_program)
+        })
+        .collect();
+    let mut create_instructions = Vec::new();
+    for (chunk_entries, chunk_atas) in entries.chunks(100).zip(atas.chunks(100)) {
+        let accounts = ctx_ref.client.get_multiple_accounts(chunk_atas)?;
+        for ((recipient, _), account) in chunk_entries.iter().zip(accounts) {
+            if account.is_none() {
+                create_instructions.push(create_associated_token_account_idempotent(
+                    &minter,
+                    recipient,
+                    &mint,
+                    &token_program,
+                ));
+            }
+        }
+    }
+    for chunk in chunk_instructions(
+        create_instructions,
+        &ctx_ref.compute_budget.instructions(),
+        &ctx_ref.payer.pubkey(),
+    )? {
+        let _ = send_transaction(ctx_ref, chunk, vec![])?;
+    }
+
+    let mut batches = Vec::new();
+    for (instruction, recipients) in plan_mint_batches(
+        &ctx_ref.payer.pubkey(),
+        &ctx_ref.compute_budget.instructions(),
+        minter,
+        mint,
+        token_program,
+        config.features.transfer_hook(),
+        &entries,
+    )? {
+        let Some(signature) = send_transaction(ctx_ref, vec![instruction], vec![])? else {
+            continue;
+        };
+        batches.push(MintBatchResult {
+            explorer: explorer_url(&signature, ctx_ref.cluster),
+            signature,
+            recipients,
+        });
+    }
+    if batches.is_empty() {
+        return Ok(());
+    }
+
+    let total: u64 = entries.iter().map(|(_, amount)| amount).sum();
+    let supply = ctx_ref.client.get_token_supply(&mint)?;
+    if ctx_ref.output == OutputFormat::Json {
+        let output = MintBatchOutput {
+            recipients: entries.len(),
+            total_amount: total.to_string(),
+            new_supply: supply.amount,
+            batches,
+            compute_budget: ctx_ref.compute_budget.output(),
+        };
+        print_json(&output)
+    } else {
+        println!(
+            "Minted {} tokens to {} recipients in {} transactions",
+            format_amount(total, config.decimals),
+            entries.len(),
+            batches.len()
+        );
+        println!("New supply: {}", supply.amount);
+        for batch in batches {
+            println!("Tx: {} ({} recipients)", batch.signature, batch.recipients);
+            if let Some(url) = batch.explorer {
+                println!("Explorer: {}", url);
+            }
+        }
+        Ok(())
+    }
+}
+
+const MAX_MINT_PREFIX_LEN: usize = 4;
+const MAX_MINT_PREFIX_ATTEMPTS: u64 = 100_000_000;
+const MINT_PREFIX_PROGRESS_INTERVAL: u64 = 100_000;
+
+fn check_mint_prefix(prefix: &str) -> Result<()> {
+    const BASE58: &str = "123456789ABCDEFGHJKLMNPQRSTUVWXYZabcdefghijkmnopqrstuvwxyz";
+    if prefix.is_empty() || prefix.len() > MAX_MINT_PREFIX_LEN {
+        return Err(anyhow!(
+            "Mint prefix must be 1 to {} characters; each one multiplies the search by 58",
+            MAX_MINT_PREFIX_LEN
+        ));
+    }
+    if let Some(invalid) = prefix.chars().find(|c| !BASE58.contains(*c)) {
+        return Err(anyhow!(
+            "Mint prefix contains '{}', which never appears in base58 (0, O, I and l are excluded)",
+            invalid
+        ));
+    }
+    Ok(())
+}
+
+/// Generates keypairs until one's address starts with `prefix`, returning it
+/// with the number of attempts. `progress` is called every
+/// MINT_PREFIX_PROGRESS_INTERVAL attempts.
+fn grind_mint_keypair(
+    prefix: &str,
+    max_attempts: u64,
+    mut progress: impl FnMut(u64),
+) -> Result<(Keypair, u64)> {
+    for attempt in 1..=max_attempts {
+        let keypair = Keypair::new();
+        if keypair.pubkey().to_string().starts_with(prefix) {
+            return Ok((keypair, attempt));
+        }
+        if attempt % MINT_PREFIX_PROGRESS_INTERVAL == 0 {
+            progress(attempt);
+        }
+    }
+    Err(anyhow!(
+        "No mint address starting with {} after {} attempts; try a shorter prefix",
+        prefix,
+        max_attempts
+    ))
+}
+
+/// Preset name for a feature set, matching the one initialize emits.
+fn preset_label(transfer_hook: bool, confidential: bool) -> &'static str {
+    if confidential {
+        "SSS-3"
+    } else if transfer_hook {
+        "SSS-2"
+    } else {
+        "SSS-1"
+    }
+}
+
+/// Mirrors the initialize check, so out-of-range decimals fail before sending.
+fn check_decimals(decimals: u8) -> Result<()> {
+    if decimals > MAX_DECIMALS {
+        return Err(anyhow!(
+            "Decimals must be at most {}, got {}",
+            MAX_DECIMALS,
+            decimals
+        ));
+    }
+    Ok(())
+}
+
+/// Rejects the Token-2022 extensions a legacy SPL Token mint cannot carry.
+fn check_legacy_mint(
+    metadata: bool,
+    permanent_delegate: bool,
+    transfer_hook: bool,
+    default_account_frozen: bool,
+    confidential_transfer: bool,
+) -> Result<()> {
+    let unsupported: Vec<&str> = [
+        ("metadata", metadata),
+        ("permanent_delegate", permanent_delegate),
+        ("transfer_hook", transfer_hook),
+        ("default_account_frozen", default_account_frozen),
+        ("confidential_transfer", confidential_transfer),
+    ]
+    .into_iter()
+    .filter(|(_, enabled)| *enabled)
+    .map(|(name, _)| name)
+    .collect();
+    if !unsupported.is_empty() {
+        return Err(anyhow!(
+            "Legacy SPL Token mints do not support {}; use a Token-2022 mint",
+            unsupported.join(", ")
+        ));
+    }
+    Ok(())
+}
+
+/// Mirrors the on-chain MintApprovalRequired check.
+fn check_direct_mint(config: &StablecoinConfig) -> Result<()> {
+    if config.require_mint_approval {
+        return Err(anyhow!(
+            "Direct minting is disabled for this stablecoin; use mint propose and mint approve"
+        ));
+    }
+    Ok(())
+}
+
+/// Mirrors the on-chain InvalidTransferCooldown check.
+fn check_transfer_cooldown(seconds: i64, transfer_hook: bool) -> Result<()> {
+    if seconds <= 0 {
+        return Err(anyhow!(
+            "transfer cooldown must be a positive number of seconds"
+        ));
+    }
+    if !transfer_hook {
+        return Err(anyhow!(
+            "transfer cooldown requires the transfer hook (SSS-2)"
+        ));
+    }
+    Ok(())
+}
+
+fn parse_recipients_csv(content: &str, decimals: u8) -> Result<Vec<(Pubkey, u64)>> {
+    let mut entries = Vec::new();
+    for (index, line) in content.lines().enumerate() {
+        let line = line.trim();
+        if line.is_empty() || line.starts_with('#') {
+            continue;
+        }
+        let fields: Vec<&str> = line.split(',').map(str::trim).collect();
+        if fields.len() != 2 {
+            return Err(anyhow!("Line {}: expected `recipient,amount`", index + 1));
+        }
+        if entries.is_empty() && fields[0].eq_ignore_ascii_case("recipient") {
+            continue;
+        }
+        let recipient = parse_pubkey(fields[0]).with_context(|| format!("Line {}", index + 1))?;
+        let amount =
+            parse_amount(fields[1], decimals).with_context(|| format!("Line {}", index + 1))?;
+        if amount == 0 {
+            return Err(anyhow!(
+                "Line {}: amount must be greater than zero",
+                index + 1
+            ));
+        }
+        entries.push((recipient, amount));
+    }
+    if entries.is_empty() {
+        return Err(anyhow!("No recipients found"));
+    }
+    Ok(entries)
+}
+
+/// Splits entries into mint_batch instructions that respect both the on-chain
+/// entry cap and the transaction size limit. Returns each instruction with its
+/// recipient count.
+fn plan_mint_batches(
+    fee_payer: &Pubkey,
+    prefix: &[Instruction],
+    minter: Pubkey,
+    mint: Pubkey,
+    token_program: Pubkey,
+    check_blacklist: bool,
+    entries: &[(Pubkey, u64)],
+) -> Result<Vec<(Instruction, usize)>> {
+    let mut batches = Vec::new();
+    let mut start = 0;
+    while start < entries.len() {
+        let mut end = (start + MAX_MINT_BATCH_SIZE).min(entries.len());
+        let instruction = loop {
+            let instruction = build_mint_batch_instruction(MintBatchParams {
+                minter,
+                mint,
+                entries: &entries[start..end],
+                token_program,
+                check_blacklist,
+            })?;
+            if transaction_size(
+                &[prefix, std::slice::from_ref(&instruction)].concat(),
+                fee_payer,
+            )? <= PACKET_DATA_SIZE
+            {
+                break instruction;
+            }
+            if end - start == 1 {
+                return Err(anyhow!(
+                    "A single mint_batch entry does not fit in a transaction"
+                ));
+            }
+            end -= 1;
+        };
+        batches.push((instruction, end - start));
+        start = end;
+    }
+    Ok(batches)
+}
+
+fn handle_burn(ctx: &OwnedContext, args: &BurnArgs) -> Result<()> {
+    let ctx_ref = ctx.as_ref();
+    let mint = resolve_mint(&args.mint)?;
+    let config_pda = find_config_pda(&mint, &stablecoin_core::ID).0;
+    let config = fetch_config(ctx_ref, &config_pda)?;
+    let burner = ctx_ref.authority();
+    let token_program = config.token_program_id();
+    let burner_ata = get_associated_token_address_with_program_id(&burner, &mint, &token_program);
+    // With --all the balance is read just before sending; if it drops before the
+    // burn lands the transaction fails, if it grows the difference stays behind.
+    let amount = match args.amount.as_deref() {
+        Some(value) => ctx_ref.parse_amount(value, config.decimals)?,
+        None => full_balance(fetch_token_account(ctx_ref, &burner_ata)?.amount)?,
+    };
+    let burn_ix = build_burn_instruction(BurnParams {
+        burner,
+        mint,
+        burner_ata,
+        amount,
+        token_program,
+    })?;
+    let Some(signature) = send_transaction(ctx_ref, vec![burn_ix], vec![])? else {
+        return Ok(());
+    };
+    let supply = ctx_ref.client.get_token_supply(&mint)?;
+    let explorer = explorer_url(&signature, ctx_ref.cluster);
+    if ctx_ref.output == OutputFormat::Json {
+        let output = BurnOutput {
+            signature: signature.clone(),
+            explorer,
+            pdas: PdaOutput::new(&config_pda, Some(&ctx_ref.authority())),
+            compute_budget: ctx_ref.compute_budget.output(),
+            new_supply: supply.amount,
+        };
+        print_json(&output)
+    } else {
+        println!(
+            "Burned {} tokens from {}",
+            ctx_ref.format_amount(amount, config.decimals),
+            burner
+        );
+        println!("New supply: {}", supply.amount);
+        println!("Tx: {}", signature);
+        if let Some(url) = explorer {
+            println!("Explorer: {}", url);
+        }
+        Ok(())
+    }
+}
+
+fn handle_transfer(ctx: &OwnedContext, args: &TransferArgs) -> Result<()> {
+    let ctx_ref = ctx.as_ref();
+    let mint = resolve_mint(&args.mint)?;
+    let config_pda = find_config_pda(&mint, &stablecoin_core::ID).0;
+    let config = fetch_config(ctx_ref, &config_pda)?;
+    let amount = parse_amount(&args.amount, config.decimals)?;
+    let owner = ctx_ref.authority();
+    let recipient = parse_pubkey(&args.recipient)?;
+    let token_program = config.token_program_id();
+    let source_ata = get_associated_token_address_with_program_id(&owner, &mint, &token_program);
+    let destination_ata =
+        get_associated_token_address_with_program_id(&recipient, &mint, &token_program);
+    validate_transfer(&source_ata, &destination_ata, amount)?;
+    if let Some(limit) = config
+        .max_transfer_amount
+        .filter(|_| config.features.transfer_hook())
+    {
+        if amount > limit {
+            return Err(anyhow!(
+                "Amount {} exceeds the per-transfer limit of {}",
+                format_amount(amount, config.decimals),
+                format_amount(limit, config.decimals)
+            ));
+        }
+    }
+
+    let mut instructions = Vec::new();
+    let destination_exists = ctx_ref
+        .client
+        .get_account_with_commitment(&destination_ata, ctx_ref.commitment)?
+        .value
+        .is_some();
+    if !destination_exists {
+        instructions.push(create_associated_token_account_idempotent(
+            &owner,
+            &recipient,
+            &mint,
+            &token_program,
+        ));
+    }
+
+    let transfer_hook_program = config
+        .transfer_hook_program
+        .filter(|_| config.features.transfer_hook());
+    let transfer_cooldown = config.features.transfer_cooldown();
+    if let Some(hook_program) = transfer_hook_program.filter(|_| transfer_cooldown) {
+        // The hook cannot create the account itself during a transfer.
+        let last_transfer = find_last_transfer_pda(&config_pda, &owner, &hook_program).0;
+        let initialized = ctx_ref
+            .client
+            .get_account_with_commitment(&last_transfer, ctx_ref.commitment)?
+            .value
+            .is_some();
+        if !initialized {
+            instructions.push(build_init_last_transfer_instruction(
+                InitLastTransferParams {
+                    payer: ctx_ref.payer.pubkey(),
+                    config_pda,
+                    owner,
+                    hook_program,
+                },
+            ));
+        }
+    }
+
+    instructions.push(build_transfer_instruction(TransferParams {
+        owner,
+        recipient,
+        mint,
+        source_ata,
+        destination_ata,
+        amount,
+        decimals: config.decimals,
+        token_program,
+        transfer_hook_program,
+        allowlist_mode: config.allowlist_mode,
+        transfer_cooldown,
+    })?);
+    let Some(signature) = send_transaction(ctx_ref, instructions, vec![])? else {
+        return Ok(());
+    };
+    let explorer = explorer_url(&signature, ctx_ref.cluster);
+    if ctx_ref.output == OutputFormat::Json {
+        let output = TransferOutput {
+            signature: signature.clone(),
+            explorer,
+            compute_budget: ctx_ref.compute_budget.output(),
+            source: source_ata.to_string(),
+            destination: destination_ata.to_string(),
+            created_destination: !destination_exists,
+        };
+        print_json(&output)
+    } else {
+        println!(
+            "Transferred {} tokens to {}",
+            format_amount(amount, config.decimals),
+            recipient
+        );
+        if !destination_exists {
+            println!("Created token account: {}", destination_ata);
+        }
+        println!("Tx: {}", signature);
+        if let Some(url) = explorer {
+            println!("Explorer: {}", url);
+        }
+        Ok(())
+    }
+}
+
+fn validate_transfer(source_ata: &Pubkey, destination_ata: &Pubkey, amount: u64) -> Result<()> {
+    if amount == 0 {
+        return Err(anyhow!("Transfer amount must be greater than zero"));
+    }
+    if source_ata == destination_ata {
+        return Err(anyhow!(
+            "Source and destination token accounts are the same: {}",
+            source_ata
+        ));
+    }
+    Ok(())
+}
+
+fn handle_freeze(ctx: &OwnedContext, args: &FreezeArgs) -> Result<()> {
+    let ctx_ref = ctx.as_ref();
+    let mint = resolve_mint(&args.mint)?;
+    let config_pda = find_config_pda(&mint, &stablecoin_core::ID).0;
+    let token_program = fetch_config(ctx_ref, &config_pda)?.token_program_id();
+    let target = resolve_freeze_target(ctx_ref, args, &mint, &token_program)?;
+    let freeze_ix = build_freeze_instruction(FreezeParams {
+        freezer: ctx_ref.authority(),
+        mint,
+        target_ata: target,
+        token_program,
+    })?;
+    ctx_ref.confirm(&format!("Freeze token account {}?", target))?;
+    let Some(signature) = send_transaction(ctx_ref, vec![freeze_ix], vec![])? else {
+        return Ok(());
+    };
+    let explorer = explorer_url(&signature, ctx_ref.cluster);
+    if ctx_ref.output == OutputFormat::Json {
+        let output = SimpleOutput {
+            signature: signature.clone(),
+            explorer,
+            pdas: Some(PdaOutput::new(&config_pda, Some(&ctx_ref.authority()))),
+            compute_budget: ctx_ref.compute_budget.output(),
+        };
+        print_json(&output)
+    } else {
+        println!("Frozen token account: {}", target);
+        println!("Config: {}", config_pda);
+        println!("Tx: {}", signature);
+        if let Some(url) = explorer {
+            println!("Explorer: {}", url);
+        }
+        Ok(())
+    }
+}
+
+fn handle_thaw(ctx: &OwnedContext, args: &FreezeArgs) -> Result<()> {
+    let ctx_ref = ctx.as_ref();
+    let mint = resolve_mint(&args.mint)?;
+    let config_pda = find_config_pda(&mint, &stablecoin_core::ID).0;
+    let token_program = fetch_config(ctx_ref, &config_pda)?.token_program_id();
+    let target = resolve_freeze_target(ctx_ref, args, &mint, &token_program)?;
+    let thaw_ix = build_thaw_instruction(FreezeParams {
+        freezer: ctx_ref.authority(),
+        mint,
+        target_ata: target,
+        token_program,
+    })?;
+    let Some(signature) = send_transaction(ctx_ref, vec![thaw_ix], vec![])? else {
+        return Ok(());
+    };
+    let explorer = explorer_url(&signature, ctx_ref.cluster);
+    if ctx_ref.output == OutputFormat::Json {
+        let output = SimpleOutput {
+            signature: signature.clone(),
+            explorer,
+            pdas: Some(PdaOutput::new(&config_pda, Some(&ctx_ref.authority()))),
+            compute_budget: ctx_ref.compute_budget.output(),
+        };
+        print_json(&output)
+    } else {
+        println!("Thawed token account: {}", target);
+        println!("Config: {}", config_pda);
+        println!("Tx: {}", signature);
+        if let Some(url) = explorer {
+            println!("Explorer: {}", url);
+        }
+        Ok(())
+    }
+}
+
+/// The token account a freeze or thaw targets: the address itself, or with
+/// `--owner` the owner's ATA, which must exist and belong to the mint.
+fn resolve_freeze_target(
+    ctx: AppContext<'_>,
+    args: &FreezeArgs,
+    mint: &Pubkey,
+    token_program: &Pubkey,
+) -> Result<Pubkey> {
+    let address = parse_pubkey(&args.address)?;
+    if !args.owner {
+        return Ok(address);
+    }
+    let ata = get_associated_token_address_with_program_id(&address, mint, token_program);
+    let account = fetch_token_account(ctx, &ata)
+        .with_context(|| format!("No token account for owner {} ({})", address, ata))?;
+    if account.mint != *mint {
+        return Err(anyhow!("Token account {} mint does not match", ata));
+    }
+    Ok(ata)
+}
+
+fn handle_pause(ctx: &OwnedContext, args: &PauseArgs) -> Result<()> {
+    let ctx_ref = ctx.as_ref();
+    let mint = resolve_mint(&args.mint)?;
+    let config_pda = find_config_pda(&mint, &stablecoin_core::ID).0;
+    let mask = args.mask();
+    let pause_ix = build_pause_instruction(PauseParams {
+        pauser: ctx_ref.authority(),
+        config_pda,
+        unpause: false,
+        mask,
+    })?;
+    let operations = match mask {
+        Some(mask) => paused_operations(mask).join(", "),
+        None => "all operations".to_string(),
+    };
+    ctx_ref.confirm(&format!("Pause {} for mint {}?", operations, mint))?;
+    let Some(signature) = send_transaction(ctx_ref, vec![pause_ix], vec![])? else {
+        return Ok(());
+    };
+    let explorer = explorer_url(&signature, ctx_ref.cluster);
+    if ctx_ref.output == OutputFormat::Json {
+        let output = SimpleOutput {
+            signature: signature.clone(),
+            explorer,
+            pdas: Some(PdaOutput::new(&config_pda, Some(&ctx_ref.authority()))),
+            compute_budget: ctx_ref.compute_budget.output(),
+        };
+        print_json(&output)
+    } else {
+        match mask {
+            Some(mask) => println!("Paused: {}", paused_operations(mask).join(", ")),
+            None => println!("System paused"),
+        }
+        println!("Config: {}", config_pda);
+        println!("Tx: {}", signature);
+        if let Some(url) = explorer {
+            println!("Explorer: {}", url);
+        }
+        Ok(())
+    }
+}
+
+fn handle_unpause(ctx: &OwnedContext, args: &PauseArgs) -> Result<()> {
+    let ctx_ref = ctx.as_ref();
+    let mint = resolve_mint(&args.mint)?;
+    let config_pda = find_config_pda(&mint, &stablecoin_core::ID).0;
+    let mask = args.mask();
+    let unpause_ix = build_pause_instruction(PauseParams {
+        pauser: ctx_ref.authority(),
+        config_pda,
+        unpause: true,
+        mask,
+    })?;
+    let Some(signature) = send_transaction(ctx_ref, vec![unpause_ix], vec![])? else {
+        return Ok(());
+    };
+    let explorer = explorer_url(&signature, ctx_ref.cluster);
+    if ctx_ref.output == OutputFormat::Json {
+        let output = SimpleOutput {
+            signature: signature.clone(),
+            explorer,
+            pdas: Some(PdaOutput::new(&config_pda, Some(&ctx_ref.authority()))),
+            compute_budget: ctx_ref.compute_budget.output(),
+        };
+        print_json(&output)
+    } else {
+        match mask {
+            Some(mask) => println!("Unpaused: {}", paused_operations(mask).join(", ")),
+            None => println!("System unpaused"),
+        }
+        println!("Config: {}", config_pda);
+        println!("Tx: {}", signature);
+        if let Some(url) = explorer {
+            println!("Explorer: {}", url);
+        }
+        Ok(())
+    }
+}
+
+fn handle_emergency_lock(ctx: &OwnedContext, args: &MintOnlyArgs, lock: bool) -> Result<()> {
+    let ctx_ref = ctx.as_ref();
+    let mint = resolve_mint(&args.mint)?;
+    let config_pda = find_config_pda(&mint, &stablecoin_core::ID).0;
+    let lock_ix = build_update_admin_lock_instruction(UpdateAdminLockParams {
+        authority: ctx_ref.authority(),
+        config_pda,
+        lock_admin_ops: lock,
+    })?;
+    if lock {
+        ctx_ref.confirm(&format!(
+            "Reserve freeze, thaw, blacklist and seize to the master authority for mint {}?",
+            mint
+        ))?;
+    }
+    let Some(signature) = send_transaction(ctx_ref, vec![lock_ix], vec![])? else {
+        return Ok(());
+    };
+    let explorer = explorer_url(&signature, ctx_ref.cluster);
+    if ctx_ref.output == OutputFormat::Json {
+        let output = SimpleOutput {
+            signature: signature.clone(),
+            explorer,
+            pdas: Some(PdaOutput::new(&config_pda, Some(&ctx_ref.authority()))),
+            compute_budget: ctx_ref.compute_budget.output(),
+        };
+        print_json(&output)
+    } else {
+        if lock {
+            println!("Emergency lock set");
+        } else {
+            println!("Emergency lock lifted");
+        }
+        println!("Config: {}", config_pda);
+        println!("Tx: {}", signature);
+        if let Some(url) = explorer {
+            println!("Explorer: {}", url);
+        }
+        Ok(())
+    }
+}
+
+/// Creates the hook's extra account metas list on its own, for mints whose
+/// list is missing or ahead of enabling the hook. The hook only needs a
+/// paying signer, so the fee payer funds it.
+fn handle_init_extra_metas(ctx: &OwnedContext, args: &InitExtraMetasArgs) -> Result<()> {
+    let ctx_ref = ctx.as_ref();
+    let mint = resolve_mint(&args.mint)?;
+    let config_pda = find_config_pda(&mint, &stablecoin_core::ID).0;
+    let config = fetch_config(ctx_ref, &config_pda)?;
+    if config.token_program_id() != spl_token_2022::id() {
+        return Err(anyhow!("Transfer hooks need a Token-2022 mint"));
+    }
+    let hook_program = match args.program.as_deref() {
+        Some(program) => parse_pubkey(program)?,
+        None => config.transfer_hook_program.unwrap_or(transfer_hook::ID),
+    };
+    let extra_metas = find_extra_account_metas_pda(&mint, &hook_program).0;
+    if ctx_ref
+        .client
+        .get_account_with_commitment(&extra_metas, ctx_ref.commitment)?
+        .value
+        .is_some_and(|account| account.owner == hook_program)
+    {
+        return Err(anyhow!(
+            "Extra account metas already exist for this mint: {}",
+            extra_metas
+        ));
+    }
+    let init_ix = build_init_extra_metas_instruction(InitExtraMetasParams {
+        payer: ctx_ref.payer.pubkey(),
+        mint,
+        hook_program,
+        allowlist_mode: config.allowlist_mode,
+        transfer_cooldown: config.features.transfer_cooldown(),
+    })?;
+    let Some(signature) = send_transaction(ctx_ref, vec![init_ix], vec![])? else {
+        return Ok(());
+    };
+    let explorer = explorer_url(&signature, ctx_ref.cluster);
+    if ctx_ref.output == OutputFormat::Json {
+        let output = SimpleOutput {
+            signature: signature.clone(),
+            explorer,
+            pdas: Some(PdaOutput::new(&config_pda, None)),
+            compute_budget: ctx_ref.compute_budget.output(),
+        };
+        print_json(&output)
+    } else {
+        println!("Extra account metas created");
+        println!("Account: {}", extra_metas);
+        println!("Program: {}", hook_program);
+        println!("Tx: {}", signature);
+        if let Some(url) = explorer {
+            println!("Explorer: {}", url);
+        }
+        Ok(())
+    }
+}
+
+fn handle_blacklist(ctx: &OwnedContext, cmd: &BlacklistCmd) -> Result<()> {
+    let ctx_ref = ctx.as_ref();
+    match cmd {
+        BlacklistCmd::Add(args) => {
+            let mint = resolve_mint(&args.mint)?;
+            let config_pda = find_config_pda(&mint, &stablecoin_core::ID).0;
+            let config = fetch_config(ctx_ref, &config_pda)?;
+            if !config.features.transfer_hook() {
+                return Err(anyhow!("Transfer hook not enabled for this stablecoin"));
+            }
+            if config.allowlist_mode {
+                return Err(anyhow!(
+                    "This stablecoin uses an allowlist; manage it with `sss-token allowlist`"
+                ));
+            }
+            let wallet = parse_pubkey(&args.address)?;
+            let reason_code = parse_reason_code(&args.code)?;
+            let expires_at = args
+                .expires_in
+                .as_deref()
+                .map(|value| {
+                    let seconds = parse_expires_in(value)?;
+                    Utc::now()
+                        .timestamp()
+                        .checked_add(seconds)
+                        .ok_or_else(|| anyhow!("Expiry too far in the future"))
+                })
+                .transpose()?;
+            let add_ix = build_add_to_blacklist_instruction(AddToBlacklistParams {
+                blacklister: ctx_ref.authority(),
+                config_pda,
+                wallet,
+                reason: args.reason.clone().unwrap_or_default(),
+                expires_at,
+                reason_code,
+            })?;
+            ctx_ref.confirm(&format!(
+                "Blacklist {} ({})? Their transfers will be blocked.",
+                wallet,
+                reason_code_name(reason_code)
+            ))?;
+            let Some(signature) = send_transaction(ctx_ref, vec![add_ix], vec![])? else {
+                return Ok(());
+            };
+            let explorer = explorer_url(&signature, ctx_ref.cluster);
+            if ctx_ref.output == OutputFormat::Json {
+                let output = SimpleOutput {
+                    signature: signature.clone(),
+                    explorer,
+                    pdas: Some(PdaOutput::new(&config_pda, Some(&ctx_ref.authority()))),
+                    compute_budget: ctx_ref.compute_budget.output(),
+                };
+                print_json(&output)
+            } else {
+                println!("Blacklisted: {}", wallet);
+                println!("Code: {}", reason_code_name(reason_code));
+                if let Some(expires_at) = expires_at {
+                    println!("Expires: {}", format_unix_timestamp(expires_at));
+                }
+                println!("Tx: {}", signature);
+                if let Some(url) = explorer {
+                    println!("Explorer: {}", url);
+                }
+                Ok(())
+            }
+        }
+        BlacklistCmd::AddBatch(args) => {
+            let mint = resolve_mint(&args.mint)?;
+            let config_pda = find_config_pda(&mint, &stablecoin_core::ID).0;
+            let config = fetch_config(ctx_ref, &config_pda)?;
+            if !config.features.transfer_hook() {
+                return Err(anyhow!("Transfer hook not enabled for this stablecoin"));
+            }
+            if config.allowlist_mode {
+                return Err(anyhow!(
+                    "This stablecoin uses an allowlist; manage it with `sss-token allowlist`"
+                ));
+            }
+            let content = fs::read_to_string(&args.file)
+                .with_context(|| format!("Failed to read {}", args.file.display()))?;
+            let rows = parse_blacklist_file(&content)?;
+            let reason_code = parse_reason_code(&args.code)?;
+            let expires_at = args
+                .expires_in
+                .as_deref()
+                .map(|value| {
+                    let seconds = parse_expires_in(value)?;
+                    Utc::now()
+                        .timestamp()
+                        .checked_add(seconds)
+                        .ok_or_else(|| anyhow!("Expiry too far in the future"))
+                })
+                .transpose()?;
+            let wallets: Vec<Pubkey> = rows.iter().map(|(wallet, _)| *wallet).collect();
+            let existing = fetch_blacklist_entries(ctx_ref, &config_pda, &wallets)?;
+            let now = Utc::now().timestamp();
+            let mut instructions = Vec::with_capacity(rows.len());
+            let mut unchanged = Vec::new();
+            for ((wallet, reason), entry) in rows.iter().zip(existing) {
+                if entry.is_some_and(|entry| entry.is_in_effect(now)) {
+                    unchanged.push(BlacklistBatchResult::unchanged(wallet));
+                    continue;
+                }
+                let reason = reason.clone().or_else(|| args.reason.clone());
+                instructions.push((
+                    *wallet,
+                    build_add_to_blacklist_instruction(AddToBlacklistParams {
+                        blacklister: ctx_ref.authority(),
+                        config_pda,
+                        wallet: *wallet,
+                        reason: reason.unwrap_or_default(),
+                        expires_at,
+                        reason_code,
+                    })?,
+                ));
+            }
+            if !instructions.is_empty() {
+                ctx_ref.confirm(&format!(
+                    "Blacklist {} wallets ({})? Their transfers will be blocked.",
+                    instructions.len(),
+                    reason_code_name(reason_code)
+                ))?;
+            }
+            let results = send_blacklist_batch(ctx, instructions, unchanged)?;
+            print_blacklist_batch(ctx_ref, "Blacklisted", &results)
+        }
+        BlacklistCmd::RemoveBatch(args) => {
+            let mint = resolve_mint(&args.mint)?;
+            let config_pda = find_config_pda(&mint, &stablecoin_core::ID).0;
+            let config = fetch_config(ctx_ref, &config_pda)?;
+            if !config.features.transfer_hook() {
+                return Err(anyhow!("Transfer hook not enabled for this stablecoin"));
+            }
+            let content = fs::read_to_string(&args.file)
+                .with_context(|| format!("Failed to read {}", args.file.display()))?;
+            let wallets = parse_address_list(&content)?;
+            let existing = fetch_blacklist_entries(ctx_ref, &config_pda, &wallets)?;
+            let mut instructions = Vec::new();
+            let mut unchanged = Vec::new();
+            for (wallet, entry) in wallets.iter().zip(existing) {
+                if !entry.is_some_and(|entry| entry.is_active) {
+                    unchanged.push(BlacklistBatchResult::unchanged(wallet));
+                    continue;
+                }
+                instructions.push((
+                    *wallet,
+                    build_remove_from_blacklist_instruction(RemoveFromBlacklistParams {
+                        blacklister: ctx_ref.authority(),
+                        config_pda,
+                        blacklist_entry: find_blacklist_pda(
+                            &config_pda,
+                            wallet,
+                            &stablecoin_core::ID,
+                        )
+                        .0,
+                    })?,
+                ));
+            }
+            let results = send_blacklist_batch(ctx, instructions, unchanged)?;
+            print_blacklist_batch(ctx_ref, "Removed from blacklist", &results)
+        }
+        BlacklistCmd::Update(args) => {
+            let mint = resolve_mint(&args.mint)?;
+            let config_pda = find_config_pda(&mint, &stablecoin_core::ID).0;
+            let wallet = parse_pubkey(&args.address)?;
+            if args.reason.len() > MAX_REASON_LEN {
+                return Err(anyhow!(
+                    "Reason is {} bytes; at most {} fit",
+                    args.reason.len(),
+                    MAX_REASON_LEN
+                ));
+            }
+            let reason_code = args.code.as_deref().map(parse_reason_code).transpose()?;
+            let blacklist_entry = find_blacklist_pda(&config_pda, &wallet, &stablecoin_core::ID).0;
+            let entry = match fetch_blacklist_entry(ctx_ref, &blacklist_entry)? {
+                Some(entry) if entry.is_active => entry,
+                _ => return Err(anyhow!("{} is not blacklisted", wallet)),
+            };
+            let update_ix = build_update_blacklist_entry_instruction(UpdateBlacklistEntryParams {
+                blacklister: ctx_ref.authority(),
+                config_pda,
+                blacklist_entry,
+                reason: args.reason.clone(),
+                reason_code,
+            })?;
+            let Some(signature) = send_transaction(ctx_ref, vec![update_ix], vec![])? else {
+                return Ok(());
+            };
+            let explorer = explorer_url(&signature, ctx_ref.cluster);
+            if ctx_ref.output == OutputFormat::Json {
+                let output = SimpleOutput {
+                    signature: signature.clone(),
+                    explorer,
+                    pdas: Some(PdaOutput::new(&config_pda, Some(&ctx_ref.authority()))),
+                    compute_budget: ctx_ref.compute_budget.output(),
+                };
+                print_json(&output)
+            } else {
+                println!("Updated blacklist entry: {}", wallet);
+                println!(
+                    "Code: {}",
+                    reason_code_name(reason_code.unwrap_or(entry.reason_code))
+                );
+                println!(
+                    "Blacklisted at: {}",
+                    format_unix_timestamp(entry.blacklisted_at)
+                );
+                println!("Tx: {}", signature);
+                if let Some(url) = explorer {
+                    println!("Explorer: {}", url);
+                }
+                Ok(())
+            }
+        }
+        BlacklistCmd::Remove(args) => {
+            let mint = resolve_mint(&args.mint)?;
+            let config_pda = find_config_pda(&mint, &stablecoin_core::ID).0;
+            let config = fetch_config(ctx_ref, &config_pda)?;
+            if !config.features.transfer_hook() {
+                return Err(anyhow!("Transfer hook not enabled for this stablecoin"));
+            }
+            let wallet = parse_pubkey(&args.address)?;
+            let blacklist_entry = find_blacklist_pda(&config_pda, &wallet, &stablecoin_core::ID).0;
+            let remove_ix = build_remove_from_blacklist_instruction(RemoveFromBlacklistParams {
+                blacklister: ctx_ref.authority(),
+                config_pda,
+                blacklist_entry,
+            })?;
+            let Some(signature) = send_transaction(ctx_ref, vec![remove_ix], vec![])? else {
+                return Ok(());
+            };
+            let explorer = explorer_url(&signature, ctx_ref.cluster);
+            if ctx_ref.output == OutputFormat::Json {
+                let output = SimpleOutput {
+                    signature: signature.clone(),
+                    explorer,
+                    pdas: Some(PdaOutput::new(&config_pda, Some(&ctx_ref.authority()))),
+                    compute_budget: ctx_ref.compute_budget.output(),
+                };
+                print_json(&output)
+            } else {
+                println!("Removed from blacklist: {}", wallet);
+                println!("Tx: {}", signature);
+                if let Some(url) = explorer {
+                    println!("Explorer: {}", url);
+                }
+                Ok(())
+            }
+        }
+        BlacklistCmd::Check(args) => {
+            let mint = resolve_mint(&args.mint)?;
+            let config_pda = find_config_pda(&mint, &stablecoin_core::ID).0;
+            let wallet = parse_pubkey(&args.address)?;
+            let blacklist_entry = find_blacklist_pda(&config_pda, &wallet, &stablecoin_core::ID).0;
+            let status = fetch_blacklist_entry(ctx_ref, &blacklist_entry)?;
+            let now = Utc::now().timestamp();
+            if ctx_ref.output == OutputFormat::Json {
+                let output = BlacklistStatusOutput {
+                    wallet: wallet.to_string(),
+                    is_active: status.as_ref().is_some_and(|entry| entry.is_in_effect(now)),
+                    reason: status.as_ref().map(|entry| entry.reason.clone()),
+                    reason_code: status
+                        .as_ref()
+                        .map(|entry| reason_code_name(entry.reason_code)),
+                    expires_at: status.as_ref().and_then(|entry| entry.expires_at),
+                };
+                print_json(&output)
+            } else {
+                match status {
+                    Some(entry) if entry.is_in_effect(now) => {
+                        println!("Blacklisted: {}", wallet);
+                        println!("Code: {}", reason_code_name(entry.reason_code));
+                        if !entry.reason.is_empty() {
+                            println!("Reason: {}", entry.reason);
+                        }
+                        if let Some(expires_at) = entry.expires_at {
+                            println!("Expires: {}", format_unix_timestamp(expires_at));
+                        }
+                    }
+                    Some(entry) if entry.is_active => {
+                        let expires_at = entry.expires_at.unwrap_or_default();
+                        println!(
+                            "Not blacklisted: {} (expired {})",
+                            wallet,
+                            format_unix_timestamp(expires_at)
+                        );
+                    }
+                    _ => println!("Not blacklisted: {}", wallet),
+                }
+                Ok(())
+            }
+        }
+        BlacklistCmd::Close(args) => {
+            let mint = resolve_mint(&args.mint)?;
+            let config_pda = find_config_pda(&mint, &stablecoin_core::ID).0;
+            let wallet = parse_pubkey(&args.address)?;
+            let blacklist_entry = find_blacklist_pda(&config_pda, &wallet, &stablecoin_core::ID).0;
+            match fetch_blacklist_entry(ctx_ref, &blacklist_entry)? {
+                None => return Err(anyhow!("No blacklist entry for {}", wallet)),
+                Some(entry) if entry.is_in_effect(Utc::now().timestamp()) => {
+                    return Err(anyhow!(
+                        "{} is still blacklisted; run `blacklist remove` first",
+                        wallet
+                    ))
+                }
+                Some(_) => {}
+            }
+            let close_ix = build_close_blacklist_entry_instruction(CloseBlacklistEntryParams {
+                blacklister: ctx_ref.authority(),
+                config_pda,
+                blacklist_entry,
+            })?;
+            let Some(signature) = send_transaction(ctx_ref, vec![close_ix], vec![])? else {
+                return Ok(());
+            };
+            let explorer = explorer_url(&signature, ctx_ref.cluster);
+            if ctx_ref.output == OutputFormat::Json {
+                let output = SimpleOutput {
+                    signature: signature.clone(),
+                    explorer,
+                    pdas: Some(PdaOutput::new(&config_pda, Some(&ctx_ref.authority()))),
+                    compute_budget: ctx_ref.compute_budget.output(),
+                };
+                print_json(&output)
+            } else {
+                println!("Closed blacklist entry: {}", wallet);
+                println!("Tx: {}", signature);
+                if let Some(url) = explorer {
+                    println!("Explorer: {}", url);
+                }
+                Ok(())
+            }
+        }
+    }
+}
+
+/// Wallets and optional per-row reasons from a blacklist batch file. A header
+/// row and duplicate wallets are skipped; the first reason given for a wallet
+/// wins.
+fn parse_blacklist_file(content: &str) -> Result<Vec<(Pubkey, Option<String>)>> {
+    let mut rows: Vec<(Pubkey, Option<String>)> = Vec::new();
+    let mut seen_entry = false;
+    for (index, line) in content.lines().enumerate() {
+        let line = line.trim();
+        if line.is_empty() || line.starts_with('#') {
+            continue;
+        }
+        let (field, reason) = match line.split_once(',') {
+            Some((field, reason)) => (field.trim(), reason.trim().trim_matches('"').trim()),
+            None => (line, ""),
+        };
+        if !seen_entry
+            && ["address", "wallet", "owner"]
+                .iter()
+                .any(|header| field.eq_ignore_ascii_case(header))
+        {
+            seen_entry = true;
+            continue;
+        }
+        seen_entry = true;
+        let wallet = parse_pubkey(field).with_context(|| format!("Line {}", index + 1))?;
+        if reason.len() > MAX_REASON_LEN {
+            return Err(anyhow!(
+                "Line {}: reason longer than {} bytes",
+                index + 1,
+                MAX_REASON_LEN
+            ));
+        }
+        if rows.iter().all(|(existing, _)| *existing != wallet) {
+            rows.push((wallet, (!reason.is_empty()).then(|| reason.to_string())));
+        }
+    }
+    if rows.is_empty() {
+        return Err(anyhow!("No wallets found"));
+    }
+    Ok(rows)
+}
+
+/// Blacklist entries of `wallets`, in order, fetched 100 at a time; None
+/// where a wallet has never been blacklisted.
+fn fetch_blacklist_entries(
+    ctx: AppContext<'_>,
+    config_pda: &Pubkey,
+    wallets: &[Pubkey],
+) -> Result<Vec<Option<BlacklistEntry>>> {
+    let mut entries = Vec::with_capacity(wallets.len());
+    for chunk in wallets.chunks(100) {
+        let pdas: Vec<Pubkey> = chunk
+            .iter()
+            .map(|wallet| find_blacklist_pda(config_pda, wallet, &stablecoin_core::ID).0)
+            .collect();
+        for account in ctx.client.get_multiple_accounts(&pdas)? {
+            entries.push(account.and_then(|account| {
+                BlacklistEntry::try_deserialize(&mut account.data.as_slice()).ok()
+            }));
+        }
+    }
+    Ok(entries)
+}
+
+/// Sends blacklist instructions packed into as few transactions as fit. When
+/// a transaction fails its wallets are retried one at a time, so one bad row
+/// only fails itself. `results` holds rows that needed no transaction.
+fn send_blacklist_batch(
+    ctx: &OwnedContext,
+    instructions: Vec<(Pubkey, Instruction)>,
+    mut results: Vec<BlacklistBatchResult>,
+) -> Result<Vec<BlacklistBatchResult>> {
+    if ctx.authority_override.is_some() {
+        return Err(anyhow!(
+            "--authority is not supported for batch blacklisting; propose entries one at a time"
+        ));
+    }
+    let ctx_ref = ctx.as_ref();
+    let (wallets, instructions): (Vec<Pubkey>, Vec<Instruction>) = instructions.into_iter().unzip();
+    let chunks = chunk_instructions(
+        instructions,
+        &ctx_ref.compute_budget.instructions(),
+        &ctx_ref.payer.pubkey(),
+    )?;
+    // Chunks keep the instructions in order, so wallets line up by position.
+    let mut wallets = wallets.into_iter();
+    for chunk in chunks {
+        let chunk_wallets: Vec<Pubkey> = wallets.by_ref().take(chunk.len()).collect();
+        let sent: Vec<Result<Option<String>>> =
+            match send_transaction(ctx_ref, chunk.clone(), vec![]) {
+                Err(_) if chunk.len() > 1 => chunk
+                    .into_iter()
+                    .map(|instruction| send_transaction(ctx_ref, vec![instruction], vec![]))
+                    .collect(),
+                Err(err) => vec![Err(err)],
+                Ok(signature) => (0..chunk_wallets.len())
+                    .map(|_| Ok(signature.clone()))
+                    .collect(),
+            };
+        for (wallet, result) in chunk_wallets.into_iter().zip(sent) {
+            results.push(match result {
+                Ok(signature) => BlacklistBatchResult {
+                    wallet: wallet.to_string(),
+                    signature,
+                    error: None,
+                },
+                Err(err) => BlacklistBatchResult {
+                    wallet: wallet.to_string(),
+                    signature: None,
+                    error: Some(err.to_string()),
+                },
+            });
+        }
+    }
+    Ok(results)
+}
+
+fn print_blacklist_batch(
+    ctx: AppContext<'_>,
+    verb: &str,
+    results: &[BlacklistBatchResult],
+) -> Result<()> {
+    let failed = results
+        .iter()
+        .filter(|result| result.error.is_some())
+        .count();
+    if ctx.output == OutputFormat::Json {
+        print_json(&results)?;
+    } else {
+        for result in results {
+            match (&result.signature, &result.error) {
+                (_, Some(error)) => println!("  {} failed: {}", result.wallet, error),
+                (Some(signature), None) => println!("  {} tx {}", result.wallet, signature),
+                (None, None) => println!("  {} unchanged", result.wallet),
+            }
+        }
+        println!(
+            "{} {} of {} wallets",
+            verb,
+            results.len() - failed,
+            results.len()
+        );
+    }
+    if failed > 0 {
+        return Err(anyhow!(
+            "{} wallets failed; rerun the same file to retry them",
+            failed
+        ));
+    }
+    Ok(())
+}
+
+fn handle_allowlist(ctx: &OwnedContext, cmd: &AllowlistCmd) -> Result<()> {
+    let ctx_ref = ctx.as_ref();
+    match cmd {
+        AllowlistCmd::Add(args) | AllowlistCmd::Remove(args) => {
+            let mint = resolve_mint(&args.mint)?;
+            let config_pda = find_config_pda(&mint, &stablecoin_core::ID).0;
+            let config = fetch_config(ctx_ref, &config_pda)?;
+            if !config.allowlist_mode {
+                return Err(anyhow!("Allowlist mode not enabled for this stablecoin"));
+            }
+            let wallet = parse_pubkey(&args.address)?;
+            let adding = matches!(cmd, AllowlistCmd::Add(_));
+            let instruction = if adding {
+                build_add_to_allowlist_instruction(AddToAllowlistParams {
+                    blacklister: ctx_ref.authority(),
+                    config_pda,
+                    wallet,
+                })?
+            } else {
+                build_remove_from_allowlist_instruction(RemoveFromAllowlistParams {
+                    blacklister: ctx_ref.authority(),
+                    config_pda,
+                    allowlist_entry: find_allowlist_pda(&config_pda, &wallet, &stablecoin_core::ID)
+                        .0,
+                })?
+            };
+            let Some(signature) = send_transaction(ctx_ref, vec![instruction], vec![])? else {
+                return Ok(());
+            };
+            let explorer = explorer_url(&signature, ctx_ref.cluster);
+            if ctx_ref.output == OutputFormat::Json {
+                let output = SimpleOutput {
+                    signature: signature.clone(),
+                    explorer,
+                    pdas: Some(PdaOutput::new(&config_pda, Some(&ctx_ref.authority()))),
+                    compute_budget: ctx_ref.compute_budget.output(),
+                };
+                print_json(&output)
+            } else {
+                if adding {
+                    println!("Allowlisted: {}", wallet);
+                } else {
+                    println!("Removed from allowlist: {}", wallet);
+                }
+                println!("Tx: {}", signature);
+                if let Some(url) = explorer {
+                    println!("Explorer: {}", url);
+                }
+                Ok(())
+            }
+        }
+        AllowlistCmd::Check(args) => {
+            let mint = resolve_mint(&args.mint)?;
+            let config_pda = find_config_pda(&mint, &stablecoin_core::ID).0;
+            let wallet = parse_pubkey(&args.address)?;
+            let allowlist_entry = find_allowlist_pda(&config_pda, &wallet, &stablecoin_core::ID).0;
+            let is_active = fetch_allowlist_entry(ctx_ref, &allowlist_entry)?
+                .is_some_and(|entry| entry.is_active);
+            if ctx_ref.output == OutputFormat::Json {
+                let output = AllowlistStatusOutput {
+                    wallet: wallet.to_string(),
+                    is_active,
+                };
+                print_json(&output)
+            } else {
+                if is_active {
+                    println!("Allowlisted: {}", wallet);
+                } else {
+                    println!("Not allowlisted: {}", wallet);
+                }
+                Ok(())
+            }
+        }
+    }
+}
+
+fn handle_seize(ctx: &OwnedContext, args: &SeizeArgs) -> Result<()> {
+    let ctx_ref = ctx.as_ref();
+    let mint = resolve_mint(&args.mint)?;
+    let config_pda = find_config_pda(&mint, &stablecoin_core::ID).0;
+    let config = fetch_config(ctx_ref, &config_pda)?;
+    if !config.features.permanent_delegate() {
+        return Err(anyhow!(
+            "Permanent delegate not enabled for this stablecoin"
+        ));
+    }
+    let target_ata = parse_pubkey(&args.address)?;
+    // An owner, given or configured, lets a missing ATA be created; an
+    // explicit --to account has to exist already.
+    let (treasury_ata, treasury_owner) = match (&args.to, &args.to_owner, args.burn) {
+        (_, _, true) => (None, None),
+        (Some(to), _, false) => (Some(parse_pubkey(to)?), None),
+        (None, Some(owner), false) => {
+            let owner = parse_pubkey(owner)?;
+            let ata =
+                get_associated_token_address_with_program_id(&owner, &mint, &spl_token_2022::id());
+            (Some(ata), Some(owner))
+        }
+        (None, None, false) => (
+            Some(treasury_ata(&config, &mint)?),
+            Some(configured_treasury(&config)?),
+        ),
+    };
+    if treasury_ata == Some(target_ata) {
+        return Err(anyhow!(
+            "Seize target and treasury are the same token account: {}",
+            target_ata
+        ));
+    }
+    let target_account = fetch_token_account(ctx_ref, &target_ata)?;
+    if target_account.mint != mint {
+        return Err(anyhow!("Target token account mint does not match"));
+    }
+    let requested = args
+        .amount
+        .as_deref()
+        .map(|amount| ctx_ref.parse_amount(amount, config.decimals))
+        .transpose()?;
+    let amount = seize_amount(target_account.amount, requested)?;
+    let entry = fetch_blacklist_entry(
+        ctx_ref,
+        &find_blacklist_pda(&config_pda, &target_account.owner, &stablecoin_core::ID).0,
+    )?;
+    if let SeizeStatus::Rejected(reason) = classify_seize_target(
+        &mint,
+        &config_pda,
+        Some(&target_account),
+        entry.as_ref(),
+        Utc::now().timestamp(),
+    ) {
+        return Err(anyhow!("Cannot seize from {}: {}", target_ata, reason));
+    }
+    let mut instructions = Vec::new();
+    match treasury_ata {
+        Some(treasury_ata) => {
+            let existing = fetch_token_accounts(ctx_ref, &[treasury_ata])?
+                .pop()
+                .flatten();
+            let (treasury_owner, create_ix) = prepare_treasury_ata(
+                &treasury_ata,
+                treasury_owner,
+                existing.as_ref(),
+                &mint,
+                &ctx_ref.authority(),
+                config.features.default_frozen(),
+            )?;
+            instructions.extend(create_ix);
+            instructions.push(build_seize_instruction(SeizeParams {
+                seizer: ctx_ref.authority(),
+                config_pda,
+                mint,
+                target_ata,
+                target_owner: target_account.owner,
+                treasury_ata,
+                treasury_owner,
+                transfer_hook_program: config.transfer_hook_program.unwrap_or(transfer_hook::ID),
+                amount: requested,
+                transfer_cooldown: config.features.transfer_cooldown(),
+            })?);
+        }
+        None => instructions.push(build_seize_and_burn_instruction(SeizeAndBurnParams {
+            seizer: ctx_ref.authority(),
+            config_pda,
+            mint,
+            target_ata,
+            target_owner: target_account.owner,
+        })),
+    }
+    let prompt = match treasury_ata {
+        Some(treasury_ata) => format!(
+            "Seize {} tokens from {} into {}?",
+            ctx_ref.format_amount(amount, config.decimals),
+            target_ata,
+            treasury_ata
+        ),
+        None => format!(
+            "Seize and burn {} tokens from {}?",
+            ctx_ref.format_amount(amount, config.decimals),
+            target_ata
+        ),
+    };
+    ctx_ref.confirm(&prompt)?;
+    let Some(signature) = send_transaction(ctx_ref, instructions, vec![])? else {
+        return Ok(());
+    };
+    let explorer = explorer_url(&signature, ctx_ref.cluster);
+    if ctx_ref.output == OutputFormat::Json {
+        let output = SimpleOutput {
+            signature: signature.clone(),
+            explorer,
+            pdas: Some(PdaOutput::new(&config_pda, Some(&ctx_ref.authority()))),
+            compute_budget: ctx_ref.compute_budget.output(),
+        };
+        print_json(&output)
+    } else {
+        println!(
+            "{} {} tokens from {}",
+            if args.burn {
+                "Seized and burned"
+            } else {
+                "Seized"
+            },
+            ctx_ref.format_amount(amount, config.decimals),
+            target_ata
+        );
+        println!("Tx: {}", signature);
+        if let Some(url) = explorer {
+            println!("Explorer: {}", url);
+        }
+        Ok(())
+    }
+}
+
+/// Owner of the treasury token account a seize moves funds into, plus the
+/// instruction creating it when it does not exist yet. Only an ATA whose
+/// owner is known can be created, and not on default-frozen mints, where it
+/// would start frozen and refuse the seized funds.
+fn prepare_treasury_ata(
+    treasury_ata: &Pubkey,
+    owner: Option<Pubkey>,
+    existing: Option<&TokenAccountInfo>,
+    mint: &Pubkey,
+    payer: &Pubkey,
+    default_frozen: bool,
+) -> Result<(Pubkey, Option<Instruction>)> {
+    match (existing, owner) {
+        (Some(account), _) if account.mint != *mint => {
+            Err(anyhow!("Treasury token account mint does not match"))
+        }
+        (Some(account), _) => Ok((account.owner, None)),
+        (None, Some(_)) if default_frozen => Err(anyhow!(
+            "Treasury ATA {} does not exist and would be created frozen on this mint; create and thaw it first",
+            treasury_ata
+        )),
+        (None, Some(owner)) => Ok((
+            owner,
+            Some(create_associated_token_account_idempotent(
+                payer,
+                &owner,
+                mint,
+                &spl_token_2022::id(),
+            )),
+        )),
+        (None, None) => Err(anyhow!(
+            "Treasury token account {} does not exist; pass --to-owner <WALLET> to create its ATA",
+            treasury_ata
+        )),
+    }
+}
+
+fn handle_seize_batch(ctx: &OwnedContext, args: &SeizeBatchArgs) -> Result<()> {
+    if ctx.authority_override.is_some() {
+        return Err(anyhow!(
+            "--authority is not supported for seize-batch; propose seizures one at a time with seize"
+        ));
+    }
+    let ctx_ref = ctx.as_ref();
+    let mint = resolve_mint(&args.mint)?;
+    let config_pda = find_config_pda(&mint, &stablecoin_core::ID).0;
+    let config = fetch_config(ctx_ref, &config_pda)?;
+    if !config.features.permanent_delegate() {
+        return Err(anyhow!(
+            "Permanent delegate not enabled for this stablecoin"
+        ));
+    }
+    let content = fs::read_to_string(&args.file)
+        .with_context(|| format!("Failed to read {}", args.file.display()))?;
+    let addresses = parse_address_list(&content)?;
+    let treasury_ata = match &args.to {
+        Some(to) => parse_pubkey(to)?,
+        None => treasury_ata(&config, &mint)?,
+    };
+    let treasury_account = fetch_token_account(ctx_ref, &treasury_ata)?;
+    if treasury_account.mint != mint {
+        return Err(anyhow!("Treasury token account mint does not match"));
+    }
+
+    let mut targets = resolve_seize_targets(ctx_ref, &config_pda, &mint, &addresses)?;
+    for target in &mut targets {
+        if target.token_account == treasury_ata {
+            target.status = SeizeStatus::Rejected("is the treasury account".to_string());
+        }
+    }
+    let ready: Vec<&SeizeTarget> = targets
+        .iter()
+        .filter(|target| matches!(target.status, SeizeStatus::Ready))
+        .collect();
+    let total: u64 = ready.iter().map(|target| target.amount).sum();
+
+    if args.dry_run || ready.is_empty() {
+        return print_seize_batch(ctx_ref, &config, &treasury_ata, &targets, true);
+    }
+    if needs_prompt(ctx_ref.output, ctx_ref.assume_yes, ctx_ref.submits())? {
+        print_seize_plan(&config, &targets);
+        let prompt = format!(
+            "Seize {} tokens from {} accounts into {}? Type 'seize' to continue: ",
+            format_amount(total, config.decimals),
+            ready.len(),
+            treasury_ata
+        );
+        if prompt_answer(&prompt)? != "seize" {
+            return Err(anyhow!("Aborted; nothing was seized"));
+        }
+    }
+
+    let hook_program = config.transfer_hook_program.unwrap_or(transfer_hook::ID);
+    let attempts = args.attempts.max(1);
+    for target in &mut targets {
+        if !matches!(target.status, SeizeStatus::Ready) {
+            continue;
+        }
+        let seize_ix = build_seize_instruction(SeizeParams {
+            seizer: ctx_ref.authority(),
+            config_pda,
+            mint,
+            target_ata: target.token_account,
+            target_owner: target.owner,
+            treasury_ata,
+            treasury_owner: treasury_account.owner,
+            transfer_hook_program: hook_program,
+            amount: None,
+            transfer_cooldown: config.features.transfer_cooldown(),
+        })?;
+        let mut last_error = None;
+        for _ in 0..attempts {
+            match send_transaction(ctx_ref, vec![seize_ix.clone()], vec![]) {
+                Ok(signature) => {
+                    target.status = SeizeStatus::Seized(signature);
+                    break;
+                }
+                Err(err) => {
+                    // A timed-out send may still have landed; an emptied
+                    // account means there is nothing left to retry.
+                    if fetch_token_account(ctx_ref, &target.token_account)
+                        .is_ok_and(|account| account.amount == 0)
+                    {
+                        target.status = SeizeStatus::Seized(None);
+                        break;
+                    }
+                    last_error = Some(err.to_string());
+                }
+            }
+        }
+        if let (SeizeStatus::Ready, Some(error)) = (&target.status, last_error) {
+            target.status = SeizeStatus::Failed(error);
+        }
+    }
+
+    print_seize_batch(ctx_ref, &config, &treasury_ata, &targets, false)?;
+    let failed = targets
+        .iter()
+        .filter(|target| matches!(target.status, SeizeStatus::Failed(_)))
+        .count();
+    if failed > 0 {
+        return Err(anyhow!(
+            "{} seizures failed; rerun the same command to resume (emptied accounts are skipped)",
+            failed
+        ));
+    }
+    Ok(())
+}
+
+struct SeizeTarget {
+    input: Pubkey,
+    token_account: Pubkey,
+    owner: Pubkey,
+    amount: u64,
+    status: SeizeStatus,
+}
+
+enum SeizeStatus {
+    Ready,
+    Empty,
+    Rejected(String),
+    Seized(Option<String>),
+    Failed(String),
+}
+
+impl SeizeStatus {
+    fn label(&self) -> &'static str {
+        match self {
+            SeizeStatus::Ready => "ready",
+            SeizeStatus::Empty => "skipped",
+            SeizeStatus::Rejected(_) => "rejected",
+            SeizeStatus::Seized(_) => "seized",
+            SeizeStatus::Failed(_) => "failed",
+        }
+    }
+
+    fn detail(&self) -> Option<&str> {
+        match self {
+            SeizeStatus::Empty => Some("already empty"),
+            SeizeStatus::Rejected(reason) | SeizeStatus::Failed(reason) => Some(reason),
+            _ => None,
+        }
+    }
+}
+
+/// Reads one address per line, skipping blanks, `#` comments, a header line,
+/// and duplicates.
+fn parse_address_list(content: &str) -> Result<Vec<Pubkey>> {
+    let mut addresses: Vec<Pubkey> = Vec::new();
+    let mut seen_entry = false;
+    for (index, line) in content.lines().enumerate() {
+        let line = line.trim();
+        if line.is_empty() || line.starts_with('#') {
+            continue;
+        }
+        let field = line.split(',').next().unwrap_or_default().trim();
+        if !seen_entry
+            && ["address", "account", "owner"]
+                .iter()
+                .any(|header| field.eq_ignore_ascii_case(header))
+        {
+            seen_entry = true;
+            continue;
+        }
+        seen_entry = true;
+        let address = parse_pubkey(field).with_context(|| format!("Line {}", index + 1))?;
+        if !addresses.contains(&address) {
+            addresses.push(address);
+        }
+    }
+    if addresses.is_empty() {
+        return Err(anyhow!("No accounts found"));
+    }
+    Ok(addresses)
+}
+
+/// Resolves every address to the token account to seize (an address that is
+/// not a token account of this mint is taken as an owner and mapped to its
+/// ATA) and runs the same checks the program applies to seize.
+fn resolve_seize_targets(
+    ctx: AppContext<'_>,
+    config_pda: &Pubkey,
+    mint: &Pubkey,
+    addresses: &[Pubkey],
+) -> Result<Vec<SeizeTarget>> {
+    let direct = fetch_token_accounts(ctx, addresses)?;
+    let token_accounts: Vec<Pubkey> = addresses
+        .iter()
+        .zip(&direct)
+        .map(|(address, account)| match account {
+            Some(account) if account.mint == *mint => *address,
+            _ => get_associated_token_address_with_program_id(address, mint, &spl_token_2022::id()),
+        })
+        .collect();
+    let accounts = fetch_token_accounts(ctx, &token_accounts)?;
+    let entry_pdas: Vec<Pubkey> = addresses
+        .iter()
+        .zip(&accounts)
+        .map(|(address, account)| {
+            let owner = account.as_ref().map_or(*address, |account| account.owner);
+            find_blacklist_pda(config_pda, &owner, &stablecoin_core::ID).0
+        })
+        .collect();
+    let mut entries = Vec::with_capacity(entry_pdas.len());
+    for chunk in entry_pdas.chunks(100) {
+        for account in ctx.client.get_multiple_accounts(chunk)? {
+            entries.push(account.and_then(|account| {
+                BlacklistEntry::try_deserialize(&mut account.data.as_slice()).ok()
+            }));
+        }
+    }
+
+    let now = Utc::now().timestamp();
+    Ok(addresses
+        .iter()
+        .zip(token_accounts)
+        .zip(accounts.iter().zip(&entries))
+        .map(|((input, token_account), (account, entry))| SeizeTarget {
+            input: *input,
+            token_account,
+            owner: account.as_ref().map_or(*input, |account| account.owner),
+            amount: account.as_ref().map_or(0, |account| account.amount),
+            status: classify_seize_target(mint, config_pda, account.as_ref(), entry.as_ref(), now),
+        })
+        .collect())
+}
+
+fn fetch_token_accounts(
+    ctx: AppContext<'_>,
+    addresses: &[Pubkey],
+) -> Result<Vec<Option<TokenAccountInfo>>> {
+    let mut accounts = Vec::with_capacity(addresses.len());
+    for chunk in addresses.chunks(100) {
+        for account in ctx.client.get_multiple_accounts(chunk)? {
+            accounts.push(account.and_then(|account| {
+                if account.owner != spl_token_2022::id() {
+                    return None;
+                }
+                StateWithExtensions::<TokenAccount2022>::unpack(&account.data)
+                    .ok()
+                    .map(|parsed| token_account_info(&parsed.base))
+            }));
+        }
+    }
+    Ok(accounts)
+}
+
+/// Empty accounts are skipped rather than rejected so a rerun after a partial
+/// batch resumes where it stopped.
+fn classify_seize_target(
+    mint: &Pubkey,
+    config_pda: &Pubkey,
+    account: Option<&TokenAccountInfo>,
+    entry: Option<&BlacklistEntry>,
+    now: i64,
+) -> SeizeStatus {
+    let Some(account) = account else {
+        return SeizeStatus::Rejected("no token account for this mint".to_string());
+    };
+    if account.mint != *mint {
+        return SeizeStatus::Rejected("token account mint does not match".to_string());
+    }
+    if account.amount == 0 {
+        return SeizeStatus::Empty;
+    }
+    if !account.is_frozen {
+        return SeizeStatus::Rejected("token account is not frozen".to_string());
+    }
+    let blacklisted = entry.is_some_and(|entry| {
+        entry.is_in_effect(now) && entry.config == *config_pda && entry.wallet == account.owner
+    });
+    if !blacklisted {
+        return SeizeStatus::Rejected("owner is not blacklisted".to_string());
+    }
+    SeizeStatus::Ready
+}
+
+/// Reads one answer to `prompt` from the terminal.
+fn prompt_answer(prompt: &str) -> Result<String> {
+    let stdin = std::io::stdin();
+    if !stdin.is_terminal() {
+        return Err(anyhow!(
+            "Refusing to continue without confirmation; pass --yes to run non-interactively"
+        ));
+    }
+    eprint!("{}", prompt);
+    std::io::stderr().flush()?;
+    let mut answer = String::new();
+    stdin.lock().read_line(&mut answer)?;
+    Ok(answer.trim().to_string())
+}
+
+/// Asks `prompt` with a y/N answer, defaulting to no; `skip` answers yes
+/// without asking.
+fn confirm(prompt: &str, skip: bool) -> Result<()> {
+    if skip || is_yes(&prompt_answer(&format!("{} [y/N] ", prompt))?) {
+        Ok(())
+    } else {
+        Err(anyhow!("Aborted; nothing was sent"))
+    }
+}
+
+fn is_yes(answer: &str) -> bool {
+    matches!(answer.to_ascii_lowercase().as_str(), "y" | "yes")
+}
+
+/// Whether a destructive action needs an interactive answer. JSON output is
+/// for scripts and never prompts, so there --yes is mandatory.
+fn needs_prompt(output: OutputFormat, assume_yes: bool, submits: bool) -> Result<bool> {
+    if assume_yes || !submits {
+        return Ok(false);
+    }
+    if output == OutputFormat::Json {
+        return Err(anyhow!(
+            "--output json does not prompt for confirmation; pass --yes to proceed"
+        ));
+    }
+    Ok(true)
+}
+
+fn print_seize_plan(config: &StablecoinConfig, targets: &[SeizeTarget]) {
+    for target in targets {
+        let detail = target
+            .status
+            .detail()
+            .map(|detail| format!(" ({})", detail))
+            .unwrap_or_default();
+        eprintln!(
+            "  {} {} {}{}",
+            target.token_account,
+            format_amount(target.amount, config.decimals),
+            target.status.label(),
+            detail
+        );
+    }
+}
+
+fn print_seize_batch(
+    ctx: AppContext<'_>,
+    config: &StablecoinConfig,
+    treasury: &Pubkey,
+    targets: &[SeizeTarget],
+    dry_run: bool,
+) -> Result<()> {
+    let count = |label: &str| {
+        targets
+            .iter()
+            .filter(|target| target.status.label() == label)
+            .count()
+    };
+    let total: u64 = targets
+        .iter()
+        .filter(|target| matches!(target.status, SeizeStatus::Ready | SeizeStatus::Seized(_)))
+        .map(|target| target.amount)
+        .sum();
+    if ctx.output == OutputFormat::Json {
+        let output = SeizeBatchOutput {
+            dry_run,
+            treasury: treasury.to_string(),
+            total_amount: total.to_string(),
+            ready: count("ready"),
+            seized: count("seized"),
+            skipped: count("skipped"),
+            rejected: count("rejected"),
+            failed: count("failed"),
+            accounts: targets
+                .iter()
+                .map(|target| {
+                    let signature = match &target.status {
+                        SeizeStatus::Seized(signature) => signature.clone(),
+                        _ => None,
+                    };
+                    SeizeBatchResult {
+                        input: target.input.to_string(),
+                        token_account: target.token_account.to_string(),
+                        owner: target.owner.to_string(),
+                        amount: target.amount.to_string(),
+                        status: target.status.label().to_string(),
+                        detail: target.status.detail().map(str::to_string),
+                        explorer: signature
+                            .as_ref()
+                            .and_then(|signature| explorer_url(signature, ctx.cluster)),
+                        signature,
+                    }
+                })
+                .collect(),
+            compute_budget: ctx.compute_budget.output(),
+        };
+        return print_json(&output);
+    }
+
+    if dry_run {
+        println!("Seizure plan (nothing sent)");
+    } else {
+        println!("Seizure results");
+    }
+    println!("Treasury: {}", treasury);
+    for target in targets {
+        let detail = match &target.status {
+            SeizeStatus::Seized(Some(signature)) => format!(" tx {}", signature),
+            status => status
+                .detail()
+                .map(|detail| format!(" ({})", detail))
+                .unwrap_or_default(),
+        };
+        println!(
+            "  {} {} {}{}",
+            target.token_account,
+            format_amount(target.amount, config.decimals),
+            target.status.label(),
+            detail
+        );
+    }
+    if dry_run {
+        println!(
+            "Would seize {} tokens from {} accounts; {} skipped, {} rejected",
+            format_amount(total, config.decimals),
+            count("ready"),
+            count("skipped"),
+            count("rejected")
+        );
+    } else {
+        println!(
+            "Seized {} tokens from {} accounts; {} skipped, {} rejected, {} failed",
+            format_amount(total, config.decimals),
+            count("seized"),
+            count("skipped"),
+            count("rejected"),
+            count("failed")
+        );
+    }
+    Ok(())
+}
+
+fn handle_minters(ctx: &OwnedContext, cmd: &MintersCmd) -> Result<()> {
+    let ctx_ref = ctx.as_ref();
+    match cmd {
+        MintersCmd::List(args) => {
+            let mint = resolve_mint(&args.mint)?;
+            let config_pda = find_config_pda(&mint, &stablecoin_core::ID).0;
+            let config = fetch_config(ctx_ref, &config_pda)?;
+            let roles = list_role_accounts(ctx_ref, &config_pda)?;
+            let mut minters = Vec::new();
+            for entry in roles {
+                if entry.account.roles & ROLE_MINTER != 0 {
+                    minters.push(minter_info(&entry.account, config.decimals));
+                }
+            }
+            if ctx_ref.output == OutputFormat::Json {
+                let output = MintersOutput {
+                    decimals: config.decimals,
+                    minters: minters.clone(),
+                };
+                print_json(&output)
+            } else {
+                if minters.is_empty() {
+                    println!("No minters found");
+                } else {
+                    for minter in minters {
+                        if let (Some(quota), Some(quota_ui)) = (minter.quota, minter.quota_ui) {
+                            println!(
+                                "{} (quota: {} = {} base units, lifetime minted: {})",
+                                minter.address, quota_ui, quota, minter.lifetime_minted_ui
+                            );
+                        } else {
+                            println!(
+                                "{} (lifetime minted: {})",
+                                minter.address, minter.lifetime_minted_ui
+                            );
+                        }
+                    }
+                }
+                Ok(())
+            }
+        }
+        MintersCmd::Add(args) => {
+            let mint = resolve_mint(&args.mint)?;
+            let config_pda = find_config_pda(&mint, &stablecoin_core::ID).0;
+            let target = parse_pubkey(&args.address)?;
+            let existing = fetch_role_account(
+                ctx_ref,
+                &find_role_pda(&config_pda, &target, &stablecoin_core::ID).0,
+            )?;
+            let existing_roles = existing.as_ref().map(|entry| entry.roles).unwrap_or(0);
+            let roles = existing_roles | ROLE_MINTER;
+            let quota = parse_amount(&args.quota, 0)?;
+            let lifetime_quota = match &args.lifetime_quota {
+                Some(value) => parse_lifetime_quota(value)?,
+                None => existing.and_then(|entry| entry.lifetime_quota),
+            };
+            let ix = build_update_roles_instruction(UpdateRolesParams {
+                authority: ctx_ref.authority(),
+                config_pda,
+                target,
+                roles,
+                mint_quota: Some(quota),
+                lifetime_quota,
+            })?;
+            let Some(signature) = send_transaction(ctx_ref, vec![ix], vec![])? else {
+                return Ok(());
+            };
+            let explorer = explorer_url(&signature, ctx_ref.cluster);
+            if ctx_ref.output == OutputFormat::Json {
+                let output = SimpleOutput {
+                    signature: signature.clone(),
+                    explorer,
+                    pdas: Some(PdaOutput::new(&config_pda, Some(&ctx_ref.authority()))),
+                    compute_budget: ctx_ref.compute_budget.output(),
+                };
+                print_json(&output)
+            } else {
+                println!("Added minter: {}", target);
+                println!("Tx: {}", signature);
+                if let Some(url) = explorer {
+                    println!("Explorer: {}", url);
+                }
+                Ok(())
+            }
+        }
+        MintersCmd::Remove(args) => {
+            let mint = resolve_mint(&args.mint)?;
+            let config_pda = find_config_pda(&mint, &stablecoin_core::ID).0;
+            let target = parse_pubkey(&args.address)?;
+            let existing = fetch_role_account(
+                ctx_ref,
+                &find_role_pda(&config_pda, &target, &stablecoin_core::ID).0,
+            )?
+            .ok_or_else(|| anyhow!("Role account not found"))?;
+            let roles = existing.roles & !ROLE_MINTER;
+            let ix = build_update_roles_instruction(UpdateRolesParams {
+                authority: ctx_ref.authority(),
+                config_pda,
+                target,
+                roles,
+                mint_quota: None,
+                lifetime_quota: None,
+            })?;
+            let Some(signature) = send_transaction(ctx_ref, vec![ix], vec![])? else {
+                return Ok(());
+            };
+            let explorer = explorer_url(&signature, ctx_ref.cluster);
+            if ctx_ref.output == OutputFormat::Json {
+                let output = SimpleOutput {
+                    signature: signature.clone(),
+                    explorer,
+                    pdas: Some(PdaOutput::new(&config_pda, Some(&ctx_ref.authority()))),
+                    compute_budget: ctx_ref.compute_budget.output(),
+                };
+                print_json(&output)
+            } else {
+                println!("Removed minter: {}", target);
+                println!("Tx: {}", signature);
+                if let Some(url) = explorer {
+                    println!("Explorer: {}", url);
+                }
+                Ok(())
+            }
+        }
+        MintersCmd::Stats(args) => {
+            let mint = resolve_mint(&args.mint)?;
+            let config_pda = find_config_pda(&mint, &stablecoin_core::ID).0;
+            let config = fetch_config(ctx_ref, &config_pda)?;
+            let target = parse_pubkey(&args.address)?;
+            let role = fetch_role_account(
+                ctx_ref,
+                &find_role_pda(&config_pda, &target, &stablecoin_core::ID).0,
+            )?
+            .ok_or_else(|| anyhow!("Role account not found"))?;
+            let now = Utc::now().timestamp();
+            let window_seconds = config.quota_window();
+            let window_minted = current_window_minted(&role, now, window_seconds);
+            let window_ends_at =
+                (window_minted > 0).then(|| role.window_start.saturating_add(window_seconds));
+            let output = MinterStatsOutput {
+                address: target.to_string(),
+                is_minter: role.roles & ROLE_MINTER != 0,
+                decimals: config.decimals,
+                quota: role.mint_quota.map(|quota| quota.to_string()),
+                minted_current_window: window_minted.to_string(),
+                window_ends_at,
+                window_resets: role.window_resets,
+                lifetime_minted: role.lifetime_minted.to_string(),
+                lifetime_quota: role.lifetime_quota.map(|quota| quota.to_string()),
+                lifetime_remaining: lifetime_remaining(&role).map(|left| left.to_string()),
+            };
+            if ctx_ref.output == OutputFormat::Json {
+                print_json(&output)
+            } else {
+                println!("Minter: {}", output.address);
+                if !output.is_minter {
+                    println!("Minter role: revoked");
+                }
+                println!(
+                    "Lifetime minted: {}",
+                    format_amount(role.lifetime_minted, config.decimals)
+                );
+                if let (Some(quota), Some(left)) = (role.lifetime_quota, lifetime_remaining(&role))
+                {
+                    println!(
+                        "Lifetime quota: {} ({} remaining)",
+                        format_amount(quota, config.decimals),
+                        format_amount(left, config.decimals)
+                    );
+                }
+                match role.mint_quota {
+                    Some(quota) => println!(
+                        "Current window: {} of {}",
+                        format_amount(window_minted, config.decimals),
+                        format_amount(quota, config.decimals)
+                    ),
+                    None => println!("Current window: not tracked (no quota)"),
+                }
+                if let Some(ends_at) = window_ends_at {
+                    println!("Window resets: {}", format_unix_timestamp(ends_at));
+                }
+                println!("Windows rolled over: {}", role.window_resets);
+                Ok(())
+            }
+        }
+    }
+}
+
+fn handle_roles(ctx: &OwnedContext, cmd: &RolesCmd) -> Result<()> {
+    let ctx_ref = ctx.as_ref();
+    match cmd {
+        RolesCmd::Audit(args) => {
+            let mint = resolve_mint(&args.mint)?;
+            let config_pda = find_config_pda(&mint, &stablecoin_core::ID).0;
+            let config = fetch_config(ctx_ref, &config_pda)?;
+            let roles = list_role_accounts(ctx_ref, &config_pda)?;
+            let report = audit_role_accounts(&config_pda, &config.authority, &roles);
+            if ctx_ref.output == OutputFormat::Json {
+                print_json(&report)
+            } else {
+                println!("Role audit");
+                println!("Config: {}", report.config);
+                println!("Authority: {}", report.authority);
+                println!("Role accounts: {}", report.role_accounts);
+                println!("Master holders: {}", report.master_holders.len());
+                for holder in &report.master_holders {
+                    println!("  {}", holder);
+                }
+                if report.issues.is_empty() {
+                    println!("Result: OK");
+                } else {
+                    println!("Result: {} issue(s) found", report.issues.len());
+                    for issue in &report.issues {
+                        println!("  - {}", issue);
+                    }
+                }
+                Ok(())
+            }
+        }
+        RolesCmd::Repair(args) => {
+            let mint = resolve_mint(&args.mint)?;
+            let config_pda = find_config_pda(&mint, &stablecoin_core::ID).0;
+            let config = fetch_config(ctx_ref, &config_pda)?;
+            let mut roles = match &args.address {
+                Some(address) => {
+                    let authority = parse_pubkey(address)?;
+                    let address = find_role_pda(&config_pda, &authority, &stablecoin_core::ID).0;
+                    let account = fetch_role_account(ctx_ref, &address)?
+                        .ok_or_else(|| anyhow!("Role account not found"))?;
+                    vec![AccountEntry { address, account }]
+                }
+                None => list_role_accounts(ctx_ref, &config_pda)?,
+            };
+            roles.sort_by_key(|entry| entry.account.authority.to_string());
+            let report = check_role_bumps(&config_pda, &config.creator, &roles);
+            if ctx_ref.output == OutputFormat::Json {
+                print_json(&report)
+            } else {
+                println!("Role bump check");
+                println!("Config: {}", report.config);
+                println!("Role accounts: {}", report.role_accounts.len());
+                for check in &report.role_accounts {
+                    if check.ok {
+                        println!("  {} bump {}: OK", check.authority, check.stored_bump);
+                        continue;
+                    }
+                    println!(
+                        "  {} bump {} (canonical {}) at {}: MISMATCH",
+                        check.authority,
+                        check.stored_bump,
+                        check.canonical_bump,
+                        check.role_account
+                    );
+                    if let Some(source) = &check.likely_source {
+                        println!("    likely created by {}", source);
+                    }
+                }
+                if report.mismatches == 0 {
+                    println!("Result: OK");
+                } else {
+                    println!("Result: {} mismatch(es) found", report.mismatches);
+                }
+                Ok(())
+            }
+        }
+        RolesCmd::List(args) => {
+            let mint = resolve_mint(&args.mint)?;
+            let config_pda = find_config_pda(&mint, &stablecoin_core::ID).0;
+            let mut roles = list_role_accounts(ctx_ref, &config_pda)?;
+            roles.sort_by_key(|entry| entry.account.authority.to_string());
+            let output = RolesListOutput {
+                roles: roles
+                    .iter()
+                    .map(|entry| RoleHolder {
+                        address: entry.account.authority.to_string(),
+                        role_account: entry.address.to_string(),
+                        roles: entry.account.roles,
+                        names: role_names(entry.account.roles),
+                    })
+                    .collect(),
+            };
+            if ctx_ref.output == OutputFormat::Json {
+                print_json(&output)
+            } else {
+                if output.roles.is_empty() {
+                    println!("No role accounts");
+                }
+                for holder in &output.roles {
+                    println!(
+                        "{} (0x{:02x}): {}",
+                        holder.address,
+                        holder.roles,
+                        holder.names.join(", ")
+                    );
+                }
+                Ok(())
+            }
+        }
+        RolesCmd::Grant(args) | RolesCmd::Revoke(args) => {
+            let grant = matches!(cmd, RolesCmd::Grant(_));
+            let mint = resolve_mint(&args.mint)?;
+            let config_pda = find_config_pda(&mint, &stablecoin_core::ID).0;
+            let target = parse_pubkey(&args.address)?;
+            let requested = parse_role_names(&args.roles)?;
+            let existing = fetch_role_account(
+                ctx_ref,
+                &find_role_pda(&config_pda, &target, &stablecoin_core::ID).0,
+            )?;
+            if existing.is_none() && !grant {
+                return Err(anyhow!("Role account not found"));
+            }
+            let current = existing.as_ref().map(|entry| entry.roles).unwrap_or(0);
+            let roles = updated_role_mask(current, requested, grant)?;
+            let ix = build_update_roles_instruction(UpdateRolesParams {
+                authority: ctx_ref.authority(),
+                config_pda,
+                target,
+                roles,
+                mint_quota: existing.as_ref().and_then(|entry| entry.mint_quota),
+                lifetime_quota: existing.and_then(|entry| entry.lifetime_quota),
+            })?;
+            let Some(signature) = send_transaction(ctx_ref, vec![ix], vec![])? else {
+                return Ok(());
+            };
+            let explorer = explorer_url(&signature, ctx_ref.cluster);
+            if ctx_ref.output == OutputFormat::Json {
+                let output = SimpleOutput {
+                    signature: signature.clone(),
+                    explorer,
+                    pdas: Some(PdaOutput::new(&config_pda, Some(&ctx_ref.authority()))),
+                    compute_budget: ctx_ref.compute_budget.output(),
+                };
+                print_json(&output)
+            } else {
+                let names = role_names(roles);
+                println!(
+                    "{} roles for {}; now holds: {}",
+                    if grant { "Granted" } else { "Revoked" },
+                    target,
+                    if names.is_empty() {
+                        "none".to_string()
+                    } else {
+                        names.join(", ")
+                    }
+                );
+                println!("Tx: {}", signature);
+                if let Some(url) = explorer {
+                    println!("Explorer: {}", url);
+                }
+                Ok(())
+            }
+        }
+        RolesCmd::Renounce(args) => {
+            let mint = resolve_mint(&args.mint)?;
+            let config_pda = find_config_pda(&mint, &stablecoin_core::ID).0;
+            let roles = parse_role_names(&args.roles)?;
+            let authority = ctx_ref.authority();
+            let role_pda = find_role_pda(&config_pda, &authority, &stablecoin_core::ID).0;
+            let held = fetch_role_account(ctx_ref, &role_pda)?
+                .map(|role| role.roles)
+                .unwrap_or(0);
+            if held & roles == 0 {
+                return Err(anyhow!("{} holds none of the given roles", authority));
+            }
+            if held & roles & ROLE_MASTER_AUTHORITY != 0 {
+                return Err(anyhow!(
+                    "The master authority cannot be renounced; move it with transfer_authority"
+                ));
+            }
+            let renounce_ix = build_renounce_role_instruction(RenounceRoleParams {
+                authority,
+                config_pda,
+                roles,
+            })?;
+            let Some(signature) = send_transaction(ctx_ref, vec![renounce_ix], vec![])? else {
+                return Ok(());
+            };
+            let explorer = explorer_url(&signature, ctx_ref.cluster);
+            if ctx_ref.output == OutputFormat::Json {
+                let output = SimpleOutput {
+                    signature: signature.clone(),
+                    explorer,
+                    pdas: Some(PdaOutput::new(&config_pda, Some(&ctx_ref.authority()))),
+                    compute_budget: ctx_ref.compute_budget.output(),
+                };
+                print_json(&output)
+            } else {
+                println!("Renounced roles: {}", args.roles);
+                if held & !roles == 0 {
+                    println!("Role account closed: {}", role_pda);
+                }
+                println!("Tx: {}", signature);
+                if let Some(url) = explorer {
+                    println!("Explorer: {}", url);
+                }
+                Ok(())
+            }
+        }
+    }
+}
+
+const ROLE_NAMES: [(&str, u8); 8] = [
+    ("master", ROLE_MASTER_AUTHORITY),
+    ("minter", ROLE_MINTER),
+    ("burner", ROLE_BURNER),
+    ("freezer", ROLE_FREEZER),
+    ("pauser", ROLE_PAUSER),
+    ("blacklister", ROLE_BLACKLISTER),
+    ("seizer", ROLE_SEIZER),
+    ("role-admin", ROLE_ROLE_ADMIN),
+];
+
+/// Maps a `--code` name such as `court-order` to its `BlacklistReason` code.
+fn parse_reason_code(value: &str) -> Result<u8> {
+    let name = value.trim().to_ascii_lowercase().replace('_', "-");
+    BlacklistReason::ASSIGNABLE
+        .iter()
+        .find(|reason| reason.name() == name)
+        .map(|reason| reason.code())
+        .ok_or_else(|| {
+            anyhow!(
+                "Unknown reason code: {} (expected sanctions, fraud, court-order, or other)",
+                value
+            )
+        })
+}
+
+/// Name of a stored reason code; codes from a newer program show as unknown.
+fn reason_code_name(code: u8) -> &'static str {
+    BlacklistReason::from_code(code).map_or("unknown", BlacklistReason::name)
+}
+
+/// Parses a comma-separated role list such as `minter,burner` into a bitmask.
+fn parse_role_names(value: &str) -> Result<u8> {
+    let mut roles = 0u8;
+    for name in value
+        .split(',')
+        .map(str::trim)
+        .filter(|name| !name.is_empty())
+    {
+        let lower = name.to_ascii_lowercase();
+        let (_, bit) = ROLE_NAMES
+            .iter()
+            .find(|(role, _)| *role == lower)
+            .ok_or_else(|| anyhow!("Unknown role: {}", name))?;
+        roles |= bit;
+    }
+    if roles == 0 {
+        return Err(anyhow!("No roles given"));
+    }
+    Ok(roles)
+}
+
+/// Names of the known bits set in `roles`, in bit order.
+fn role_names(roles: u8) -> Vec<&'static str> {
+    ROLE_NAMES
+        .iter()
+        .filter(|(_, bit)| roles & bit != 0)
+        .map(|(name, _)| *name)
+        .collect()
+}
+
+/// New mask for `roles grant` / `roles revoke`. The master authority moves
+/// with transfer_authority and minters need a quota, so both are refused here.
+fn updated_role_mask(current: u8, requested: u8, grant: bool) -> Result<u8> {
+    if requested & ROLE_MASTER_AUTHORITY != 0 {
+        return Err(anyhow!(
+            "The master authority cannot be granted or revoked; move it with transfer_authority"
+        ));
+    }
+    if requested & ROLE_MINTER != 0 {
+        return Err(anyhow!(
+            "Use `minters add` / `minters remove` to manage the minter role and its quota"
+        ));
+    }
+    let roles = if grant {
+        current | requested
+    } else {
+        current & !requested
+    };
+    if roles == current {
+        return Err(anyhow!(
+            "Nothing to change: the address {} those roles",
+            if grant {
+                "already holds"
+            } else {
+                "holds none of"
+            }
+        ));
+    }
+    Ok(roles)
+}
+
+/// Compares each role account's stored bump and address with the canonical
+/// PDA for its authority. Constraints read `bump = role_account.bump`, so a
+/// mismatched account fails every instruction that loads it.
+fn check_role_bumps(
+    config_pda: &Pubkey,
+    creator: &Pubkey,
+    roles: &[AccountEntry<RoleAccount>],
+) -> RolesRepairOutput {
+    let role_accounts: Vec<RoleBumpCheck> = roles
+        .iter()
+        .map(|entry| {
+            let account = &entry.account;
+            let (expected, canonical_bump) =
+                find_role_pda(config_pda, &account.authority, &stablecoin_core::ID);
+            let ok = entry.address == expected && account.bump == canonical_bump;
+            RoleBumpCheck {
+                role_account: entry.address.to_string(),
+                authority: account.authority.to_string(),
+                stored_bump: account.bump,
+                canonical_bump,
+                ok,
+                likely_source: (!ok).then(|| likely_role_creator(account, creator).to_string()),
+            }
+        })
+        .collect();
+
+    RolesRepairOutput {
+        config: config_pda.to_string(),
+        mismatches: role_accounts.iter().filter(|check| !check.ok).count(),
+        role_accounts,
+    }
+}
+
+/// The instruction that most likely wrote a role account's bump: initialize
+/// creates the creator's account, transfer_authority and update_roles create
+/// the others through init_if_new.
+fn likely_role_creator(account: &RoleAccount, creator: &Pubkey) -> &'static str {
+    if account.authority == *creator {
+        "initialize"
+    } else if account.roles & ROLE_MASTER_AUTHORITY != 0 {
+        "transfer_authority"
+    } else {
+        "update_roles"
+    }
+}
+
+fn audit_role_accounts(
+    config_pda: &Pubkey,
+    authority: &Pubkey,
+    roles: &[AccountEntry<RoleAccount>],
+) -> RolesAuditOutput {
+    let mut issues = Vec::new();
+    let mut master_holders = Vec::new();
+
+    for entry in roles {
+        let account = &entry.account;
+        if account.config != *config_pda {
+            issues.push(format!(
+                "Role account {} references config {}",
+                entry.address, account.config
+            ));
+        }
+        let expected = find_role_pda(config_pda, &account.authority, &stablecoin_core::ID).0;
+        if entry.address != expected {
+            issues.push(format!(
+                "Role account {} is not the PDA for authority {} (expected {})",
+                entry.address, account.authority, expected
+            ));
+        }
+        let invalid = account.roles & !VALID_ROLE_MASK;
+        if invalid != 0 {
+            issues.push(format!(
+                "Role account {} has invalid role bits 0x{:02x}",
+                entry.address, invalid
+            ));
+        }
+        if account.roles & ROLE_MASTER_AUTHORITY != 0 {
+            master_holders.push(account.authority.to_string());
+        }
+    }
+
+    match master_holders.len() {
+        0 => issues.push("No role account holds the master authority role".to_string()),
+        1 => {
+            if master_holders[0] != authority.to_string() {
+                issues.push(format!(
+                    "Master role holder {} does not match config authority {}",
+                    master_holders[0], authority
+                ));
+            }
+        }
+        count => issues.push(format!(
+            "Expected exactly one master authority holder, found {}",
+            count
+        )),
+    }
+
+    RolesAuditOutput {
+        config: config_pda.to_string(),
+        authority: authority.to_string(),
+        role_accounts: roles.len(),
+        master_holders,
+        ok: issues.is_empty(),
+        issues,
+    }
+}
+
+fn handle_config(ctx: &OwnedContext, cmd: &ConfigCmd) -> Result<()> {
+    let ctx_ref = ctx.as_ref();
+    match cmd {
+        ConfigCmd::Show(args) => {
+            let mint = resolve_mint(&args.mint)?;
+            let config_pda = find_config_pda(&mint, &stablecoin_core::ID).0;
+            let config = fetch_config(ctx_ref, &config_pda)?;
+            let output = config_output(&config_pda, &config);
+            if ctx_ref.output == OutputFormat::Json {
+                print_json(&output)
+            } else {
+                println!("Stablecoin config");
+                println!("Address: {}", output.address);
+                println!("Authority: {}", output.authority);
+                println!("Mint: {}", output.mint);
+                println!("Name: {}", output.name);
+                println!("Symbol: {}", output.symbol);
+                println!("URI: {}", output.uri);
+                println!("Decimals: {}", output.decimals);
+                println!("Paused: {}", output.is_paused);
+                println!("Pause flags: {:#04x}", output.pause_flags);
+                println!("Total minted: {}", output.total_minted);
+                println!("Total burned: {}", output.total_burned);
+                println!("Total seized: {}", output.total_seized);
+                println!("Audit counter: {}", output.audit_counter);
+                println!("Features:");
+                println!(
+                    "  Permanent delegate: {}",
+                    output.features.permanent_delegate
+                );
+                println!("  Transfer hook: {}", output.features.transfer_hook);
+                println!("  Confidential: {}", output.features.confidential);
+                println!("  Default frozen: {}", output.features.default_frozen);
+                println!("  Transfer cooldown: {}", output.features.transfer_cooldown);
+                println!("  Bits: {:#010x}", output.feature_bits);
+                println!(
+                    "Transfer hook program: {}",
+                    output.transfer_hook_program.as_deref().unwrap_or("none")
+                );
+                println!("Bump: {}", output.bump);
+                println!("Pause cooldown: {}s", output.pause_cooldown);
+                println!("Last pause change: {}", output.last_pause_change);
+                println!(
+                    "Max supply: {}",
+                    output.max_supply.as_deref().unwrap_or("uncapped")
+                );
+                println!("Creator: {}", output.creator);
+                println!("Created at: {}", format_unix_timestamp(output.created_at));
+                println!("Quota window: {}s", output.quota_window_seconds);
+                println!("Allowlist mode: {}", output.allowlist_mode);
+                println!(
+                    "Max transfer amount: {}",
+                    output.max_transfer_amount.as_deref().unwrap_or("uncapped")
+                );
+                println!(
+                    "Last action: {} by {}",
+                    format_unix_timestamp(output.last_action_at),
+                    output.last_action_by
+                );
+                println!("Allow self-transfer: {}", output.allow_self_transfer);
+                println!("Treasury: {}", output.treasury.as_deref().unwrap_or("none"));
+                println!("Emergency lock: {}", output.lock_admin_ops);
+                println!("Minting locked: {}", output.minting_locked);
+                println!("Require mint approval: {}", output.require_mint_approval);
+                println!("Token program: {}", output.token_program);
+                println!(
+                    "Transfer cooldown: {}",
+                    output
+                        .transfer_cooldown_seconds
+                        .map_or("none".to_string(), |seconds| format!("{}s", seconds))
+                );
+                Ok(())
+            }
+        }
+        ConfigCmd::SetMaxSupply(args) => {
+            let mint = resolve_mint(&args.mint)?;
+            let config_pda = find_config_pda(&mint, &stablecoin_core::ID).0;
+            let config = fetch_config(ctx_ref, &config_pda)?;
+            let max_supply = parse_amount(&args.amount, config.decimals)?;
+            let supply = ctx_ref
+                .client
+                .get_token_supply(&mint)?
+                .amount
+                .parse::<u64>()?;
+            check_max_supply_update(config.max_supply, supply, max_supply)?;
+            let update_ix = build_update_max_supply_instruction(UpdateMaxSupplyParams {
+                authority: ctx_ref.authority(),
+                config_pda,
+                mint,
+                max_supply,
+            })?;
+            let Some(signature) = send_transaction(ctx_ref, vec![update_ix], vec![])? else {
+                return Ok(());
+            };
+            let explorer = explorer_url(&signature, ctx_ref.cluster);
+            if ctx_ref.output == OutputFormat::Json {
+                let output = SimpleOutput {
+                    signature: signature.clone(),
+                    explorer,
+                    pdas: Some(PdaOutput::new(&config_pda, Some(&ctx_ref.authority()))),
+                    compute_budget: ctx_ref.compute_budget.output(),
+                };
+                print_json(&output)
+            } else {
+                println!("Max supply updated");
+                println!("Max supply: {}", format_amount(max_supply, config.decimals));
+                println!("Tx: {}", signature);
+                if let Some(url) = explorer {
+                    println!("Explorer: {}", url);
+                }
+                Ok(())
+            }
+        }
+        ConfigCmd::SetQuotaWindow(args) => {
+            let mint = resolve_mint(&args.mint)?;
+            let config_pda = find_config_pda(&mint, &stablecoin_core::ID).0;
+            let quota_window_seconds = parse_quota_window(&args.window)?;
+            let update_ix = build_update_quota_window_instruction(UpdateQuotaWindowParams {
+                authority: ctx_ref.authority(),
+                config_pda,
+                quota_window_seconds,
+            })?;
+            let Some(signature) = send_transaction(ctx_ref, vec![update_ix], vec![])? else {
+                return Ok(());
+            };
+            let explorer = explorer_url(&signature, ctx_ref.cluster);
+            if ctx_ref.output == OutputFormat::Json {
+                let output = SimpleOutput {
+                    signature: signature.clone(),
+                    explorer,
+                    pdas: Some(PdaOutput::new(&config_pda, Some(&ctx_ref.authority()))),
+                    compute_budget: ctx_ref.compute_budget.output(),
+                };
+                print_json(&output)
+            } else {
+                println!("Quota window updated");
+                println!("Quota window: {}s", quota_window_seconds);
+                println!("Tx: {}", signature);
+                if let Some(url) = explorer {
+                    println!("Explorer: {}", url);
+                }
+                Ok(())
+            }
+        }
+        ConfigCmd::SetTransferLimit(args) => {
+            let mint = resolve_mint(&args.mint)?;
+            let config_pda = find_config_pda(&mint, &stablecoin_core::ID).0;
+            let config = fetch_config(ctx_ref, &config_pda)?;
+            if !config.features.transfer_hook() {
+                return Err(anyhow!("Transfer hook not enabled for this stablecoin"));
+            }
+            let max_transfer_amount = parse_transfer_limit(&args.amount, config.decimals)?;
+            let update_ix = build_update_transfer_limit_instruction(UpdateTransferLimitParams {
+                authority: ctx_ref.authority(),
+                config_pda,
+                max_transfer_amount,
+            })?;
+            let Some(signature) = send_transaction(ctx_ref, vec![update_ix], vec![])? else {
+                return Ok(());
+            };
+            let explorer = explorer_url(&signature, ctx_ref.cluster);
+            if ctx_ref.output == OutputFormat::Json {
+                let output = SimpleOutput {
+                    signature: signature.clone(),
+                    explorer,
+                    pdas: Some(PdaOutput::new(&config_pda, Some(&ctx_ref.authority()))),
+                    compute_budget: ctx_ref.compute_budget.output(),
+                };
+                print_json(&output)
+            } else {
+                println!("Transfer limit updated");
+                println!(
+                    "Max transfer amount: {}",
+                    max_transfer_amount
+                        .map(|limit| format_amount(limit, config.decimals))
+                        .unwrap_or_else(|| "uncapped".to_string())
+                );
+                println!("Tx: {}", signature);
+                if let Some(url) = explorer {
+                    println!("Explorer: {}", url);
+                }
+                Ok(())
+            }
+        }
+        ConfigCmd::SetTransferHookProgram(args) => {
+            let mint = resolve_mint(&args.mint)?;
+            let config_pda = find_config_pda(&mint, &stablecoin_core::ID).0;
+            let config = fetch_config(ctx_ref, &config_pda)?;
+            if !config.features.transfer_hook() {
+                return Err(anyhow!("Transfer hook not enabled for this stablecoin"));
+            }
+            let hook_program = parse_pubkey(&args.program_id)?;
+            if config.transfer_hook_program == Some(hook_program) {
+                return Err(anyhow!("The mint already uses {}", hook_program));
+            }
+            let update_ix =
+                build_update_transfer_hook_program_instruction(UpdateTransferHookProgramParams {
+                    authority: ctx_ref.authority(),
+                    config_pda,
+                    mint,
+                    hook_program,
+                })?;
+            let Some(signature) = send_transaction(ctx_ref, vec![update_ix], vec![])? else {
+                return Ok(());
+            };
+            let explorer = explorer_url(&signature, ctx_ref.cluster);
+            if ctx_ref.output == OutputFormat::Json {
+                let output = SimpleOutput {
+                    signature: signature.clone(),
+                    explorer,
+                    pdas: Some(PdaOutput::new(&config_pda, Some(&ctx_ref.authority()))),
+                    compute_budget: ctx_ref.compute_budget.output(),
+                };
+                print_json(&output)
+            } else {
+                println!("Transfer hook program updated");
+                if let Some(previous) = config.transfer_hook_program {
+                    println!("Previous program: {}", previous);
+                }
+                println!("Program: {}", hook_program);
+                println!("Tx: {}", signature);
+                if let Some(url) = explorer {
+                    println!("Explorer: {}", url);
+                }
+                Ok(())
+            }
+        }
+        ConfigCmd::EnableTransferHook(args) => {
+            let mint = resolve_mint(&args.mint)?;
+            let config_pda = find_config_pda(&mint, &stablecoin_core::ID).0;
+            let config = fetch_config(ctx_ref, &config_pda)?;
+            if let Some(program) = config.transfer_hook_program {
+                return Err(anyhow!("Transfer hook already enabled with {}", program));
+            }
+            if config.token_program_id() != spl_token_2022::id() {
+                return Err(anyhow!("Transfer hooks need a Token-2022 mint"));
+            }
+            let account = ctx_ref.client.get_account(&mint)?;
+            let state = StateWithExtensions::<Mint2022>::unpack(&account.data)?;
+            match state.get_extension::<TransferHook>() {
+                Err(_) => {
+                    return Err(anyhow!(
+                        "This mint has no TransferHook extension; it can only be added when the mint is created"
+                    ))
+                }
+                Ok(ext) => {
+                    if let Some(program) = Option::<Pubkey>::from(ext.program_id) {
+                        return Err(anyhow!("The mint already points at hook program {}", program));
+                    }
+                }
+            }
+            let hook_program = args
+                .program_id
+                .as_deref()
+                .map(parse_pubkey)
+                .transpose()?
+                .unwrap_or(transfer_hook::ID);
+            let enable_ix = build_enable_transfer_hook_instruction(EnableTransferHookParams {
+                authority: ctx_ref.authority(),
+                config_pda,
+                mint,
+                hook_program,
+            })?;
+            ctx_ref.confirm(&format!(
+                "Enable transfer hook {} for mint {}? Every transfer will be checked against the blacklist.",
+                hook_program, mint
+            ))?;
+            let Some(signature) = send_transaction(ctx_ref, vec![enable_ix], vec![])? else {
+                return Ok(());
+            };
+            let explorer = explorer_url(&signature, ctx_ref.cluster);
+            if ctx_ref.output == OutputFormat::Json {
+                let output = SimpleOutput {
+                    signature: signature.clone(),
+                    explorer,
+                    pdas: Some(PdaOutput::new(&config_pda, Some(&ctx_ref.authority()))),
+                    compute_budget: ctx_ref.compute_budget.output(),
+                };
+                print_json(&output)
+            } else {
+                println!("Transfer hook enabled");
+                println!("Program: {}", hook_program);
+                println!("Tx: {}", signature);
+                if let Some(url) = explorer {
+                    println!("Explorer: {}", url);
+                }
+                Ok(())
+            }
+        }
+        ConfigCmd::SetSelfTransfer(args) => {
+            let mint = resolve_mint(&args.mint)?;
+            let config_pda = find_config_pda(&mint, &stablecoin_core::ID).0;
+            let config = fetch_config(ctx_ref, &config_pda)?;
+            if !config.features.transfer_hook() {
+                return Err(anyhow!("Transfer hook not enabled for this stablecoin"));
+            }
+            let allow_self_transfer = args.state.enabled();
+            let update_ix = build_update_self_transfer_instruction(UpdateSelfTransferParams {
+                authority: ctx_ref.authority(),
+                config_pda,
+                allow_self_transfer,
+            })?;
+            let Some(signature) = send_transaction(ctx_ref, vec![update_ix], vec![])? else {
+                return Ok(());
+            };
+            let explorer = explorer_url(&signature, ctx_ref.cluster);
+            if ctx_ref.output == OutputFormat::Json {
+                let output = SimpleOutput {
+                    signature: signature.clone(),
+                    explorer,
+                    pdas: Some(PdaOutput::new(&config_pda, Some(&ctx_ref.authority()))),
+                    compute_budget: ctx_ref.compute_budget.output(),
+                };
+                print_json(&output)
+            } else {
+                println!("Self-transfer exemption updated");
+                println!("Allow self-transfer: {}", allow_self_transfer);
+                println!("Tx: {}", signature);
+                if let Some(url) = explorer {
+                    println!("Explorer: {}", url);
+                }
+                Ok(())
+            }
+        }
+        ConfigCmd::SetMintApproval(args) => {
+            let mint = resolve_mint(&args.mint)?;
+            let config_pda = find_config_pda(&mint, &stablecoin_core::ID).0;
+            let require_mint_approval = args.state.enabled();
+            let update_ix = build_update_mint_approval_instruction(UpdateMintApprovalParams {
+                authority: ctx_ref.authority(),
+                config_pda,
+                require_mint_approval,
+            })?;
+            let Some(signature) = send_transaction(ctx_ref, vec![update_ix], vec![])? else {
+                return Ok(());
+            };
+            let explorer = explorer_url(&signature, ctx_ref.cluster);
+            if ctx_ref.output == OutputFormat::Json {
+                let output = SimpleOutput {
+                    signature: signature.clone(),
+                    explorer,
+                    pdas: Some(PdaOutput::new(&config_pda, Some(&ctx_ref.authority()))),
+                    compute_budget: ctx_ref.compute_budget.output(),
+                };
+                print_json(&output)
+            } else {
+                println!("Mint approval updated");
+                println!("Require mint approval: {}", require_mint_approval);
+                println!("Tx: {}", signature);
+                if let Some(url) = explorer {
+                    println!("Explorer: {}", url);
+                }
+                Ok(())
+            }
+        }
+        ConfigCmd::SetTreasury(args) => {
+            let mint = resolve_mint(&args.mint)?;
+            let config_pda = find_config_pda(&mint, &stablecoin_core::ID).0;
+            let treasury = if args.treasury.trim().eq_ignore_ascii_case("none") {
+                None
+            } else {
+                let treasury = parse_pubkey(&args.treasury)?;
+                check_mint_recipient(&treasury, &config_pda, &mint)?;
+                Some(treasury)
+            };
+            let update_ix = build_update_treasury_instruction(UpdateTreasuryParams {
+                authority: ctx_ref.authority(),
+                config_pda,
+                treasury,
+            })?;
+            let Some(signature) = send_transaction(ctx_ref, vec![update_ix], vec![])? else {
+                return Ok(());
+            };
+            let explorer = explorer_url(&signature, ctx_ref.cluster);
+            if ctx_ref.output == OutputFormat::Json {
+                let output = SimpleOutput {
+                    signature: signature.clone(),
+                    explorer,
+                    pdas: Some(PdaOutput::new(&config_pda, Some(&ctx_ref.authority()))),
+                    compute_budget: ctx_ref.compute_budget.output(),
+                };
+                print_json(&output)
+            } else {
+                println!("Treasury updated");
+                match treasury {
+                    Some(treasury) => println!("Treasury: {}", treasury),
+                    None => println!("Treasury: none"),
+                }
+                println!("Tx: {}", signature);
+                if let Some(url) = explorer {
+                    println!("Explorer: {}", url);
+                }
+                Ok(())
+            }
+        }
+        ConfigCmd::Migrate(args) => {
+            let mint = resolve_mint(&args.mint)?;
+            let config_pda = find_config_pda(&mint, &stablecoin_core::ID).0;
+            let current_len = ctx_ref
+                .client
+                .get_account(&config_pda)
+                .context("Config account not found")?
+                .data
+                .len();
+            let target_len = 8 + StablecoinConfig::INIT_SPACE;
+            if current_len >= target_len {
+                return Err(anyhow!(
+                    "Config already has {} bytes; nothing to migrate",
+                    current_len
+                ));
+            }
+            let migrate_ix = build_migrate_config_instruction(MigrateConfigParams {
+                payer: ctx_ref.authority(),
+                config_pda,
+            })?;
+            let Some(signature) = send_transaction(ctx_ref, vec![migrate_ix], vec![])? else {
+                return Ok(());
+            };
+            let explorer = explorer_url(&signature, ctx_ref.cluster);
+            if ctx_ref.output == OutputFormat::Json {
+                let output = SimpleOutput {
+                    signature: signature.clone(),
+                    explorer,
+                    pdas: Some(PdaOutput::new(&config_pda, None)),
+                    compute_budget: ctx_ref.compute_budget.output(),
+                };
+                print_json(&output)
+            } else {
+                println!("Config migrated");
+                println!("Size: {} -> {} bytes", current_len, target_len);
+                println!("Tx: {}", signature);
+                if let Some(url) = explorer {
+                    println!("Explorer: {}", url);
+                }
+                Ok(())
+            }
+        }
+    }
+}
+
+fn parse_transfer_limit(value: &str, decimals: u8) -> Result<Option<u64>> {
+    if value.trim().eq_ignore_ascii_case("none") {
+        return Ok(None);
+    }
+    let limit = parse_amount(value, decimals)?;
+    if limit == 0 {
+        return Err(anyhow!(
+            "Transfer limit must be greater than zero; use `none` to remove it"
+        ));
+    }
+    Ok(Some(limit))
+}
+
+/// Lifetime mint cap in base units, or `none` for no cap.
+fn parse_lifetime_quota(value: &str) -> Result<Option<u64>> {
+    if value.trim().eq_ignore_ascii_case("none") {
+        return Ok(None);
+    }
+    Ok(Some(parse_amount(value, 0)?))
+}
+
+/// Seconds until a blacklist entry lapses, as plain seconds or a duration (30d).
+fn parse_expires_in(value: &str) -> Result<i64> {
+    let value = value.trim();
+    let seconds = match value.parse::<i64>() {
+        Ok(seconds) => seconds,
+        Err(_) => {
+            let duration = humantime::parse_duration(value)
+                .map_err(|_| anyhow!("Invalid expiry: {}", value))?;
+            i64::try_from(duration.as_secs()).map_err(|_| anyhow!("Expiry too long"))?
+        }
+    };
+    if seconds <= 0 {
+        return Err(anyhow!("Expiry must be in the future"));
+    }
+    Ok(seconds)
+}
+
+fn parse_quota_window(value: &str) -> Result<i64> {
+    let value = value.trim();
+    let seconds = match value.parse::<i64>() {
+        Ok(seconds) => seconds,
+        Err(_) => {
+            let duration = humantime::parse_duration(value)
+                .map_err(|_| anyhow!("Invalid quota window: {}", value))?;
+            i64::try_from(duration.as_secs()).map_err(|_| anyhow!("Quota window too long"))?
+        }
+    };
+    if seconds < MIN_QUOTA_WINDOW_SECONDS {
+        return Err(anyhow!(
+            "Quota window must be at least {} seconds",
+            MIN_QUOTA_WINDOW_SECONDS
+        ));
+    }
+    Ok(seconds)
+}
+
+/// Mirrors the on-chain rule: a cap may be introduced or lowered, never raised
+/// or set below the current supply.
+fn check_max_supply_update(current: Option<u64>, supply: u64, new_cap: u64) -> Result<()> {
+    if new_cap < supply {
+        return Err(anyhow!(
+            "Max supply {} is below the current supply {}",
+            new_cap,
+            supply
+        ));
+    }
+    if let Some(current) = current {
+        if new_cap > current {
+            return Err(anyhow!(
+                "Max supply can only be lowered (current cap {})",
+                current
+            ));
+        }
+    }
+    Ok(())
+}
+
+const PAUSE_OPERATIONS: [(u8, &str); 3] = [
+    (PAUSE_MINT, "mint"),
+    (PAUSE_BURN, "burn"),
+    (PAUSE_TRANSFER, "transfer"),
+];
+
+/// Names of the operations set in a PAUSE_* mask.
+fn paused_operations(mask: u8) -> Vec<&'static str> {
+    PAUSE_OPERATIONS
+        .into_iter()
+        .filter(|(bit, _)| mask & bit != 0)
+        .map(|(_, name)| name)
+        .collect()
+}
+
+/// Operations a config has paused, counting a legacy pause as all of them.
+fn config_paused_operations(config: &StablecoinConfig) -> Vec<&'static str> {
+    PAUSE_OPERATIONS
+        .into_iter()
+        .filter(|(bit, _)| config.is_operation_paused(*bit))
+        .map(|(_, name)| name)
+        .collect()
+}
+
+/// Remaining mintable amount under the cap, or None when uncapped.
+fn supply_headroom(max_supply: Option<u64>, supply: u64) -> Option<u64> {
+    max_supply.map(|max_supply| max_supply.saturating_sub(supply))
+}
+
+fn handle_metadata(ctx: &OwnedContext, cmd: &MetadataCmd) -> Result<()> {
+    let ctx_ref = ctx.as_ref();
+    match cmd {
+        MetadataCmd::Update(args) => {
+            let mint = resolve_mint(&args.mint)?;
+            let config_pda = find_config_pda(&mint, &stablecoin_core::ID).0;
+            let update_ix = build_update_metadata_instruction(UpdateMetadataParams {
+                authority: ctx_ref.authority(),
+                config_pda,
+                mint,
+                name: args.name.clone(),
+                symbol: args.symbol.clone(),
+                uri: args.uri.clone(),
+            })?;
+            let Some(signature) = send_transaction(ctx_ref, vec![update_ix], vec![])? else {
+                return Ok(());
+            };
+            let explorer = explorer_url(&signature, ctx_ref.cluster);
+            if ctx_ref.output == OutputFormat::Json {
+                let output = SimpleOutput {
+                    signature: signature.clone(),
+                    explorer,
+                    pdas: Some(PdaOutput::new(&config_pda, Some(&ctx_ref.authority()))),
+                    compute_budget: ctx_ref.compute_budget.output(),
+                };
+                print_json(&output)
+            } else {
+                println!("Metadata updated");
+                if let Some(name) = &args.name {
+                    println!("Name: {}", name);
+                }
+                if let Some(symbol) = &args.symbol {
+                    println!("Symbol: {}", symbol);
+                }
+                if let Some(uri) = &args.uri {
+                    println!("URI: {}", uri);
+                }
+                println!("Tx: {}", signature);
+                if let Some(url) = explorer {
+                    println!("Explorer: {}", url);
+                }
+                Ok(())
+            }
+        }
+        MetadataCmd::Show(args) => {
+            let mint = resolve_mint(&args.mint)?;
+            let account = ctx_ref.client.get_account(&mint)?;
+            let mut output = mint_metadata(&mint, &account.data)?;
+            output.metaplex_metadata_exists = ctx_ref
+                .client
+                .get_account_with_commitment(&metaplex_metadata_pda(&mint), ctx_ref.commitment)?
+                .value
+                .is_some();
+            if ctx_ref.output == OutputFormat::Json {
+                return print_json(&output);
+            }
+            println!("Mint: {}", output.mint);
+            match &output.metadata_address {
+                Some(address) => println!(
+                    "Metadata pointer: {} (authority {})",
+                    address,
+                    output.pointer_authority.as_deref().unwrap_or("none")
+                ),
+                None => println!("Metadata pointer: none"),
+            }
+            if output.embedded {
+                println!("Name: {}", output.name);
+                println!("Symbol: {}", output.symbol);
+                println!("URI: {}", output.uri);
+                println!(
+                    "Update authority: {}",
+                    output.update_authority.as_deref().unwrap_or("none")
+                );
+                if !output.additional_metadata.is_empty() {
+                    println!("Additional metadata:");
+                    for field in &output.additional_metadata {
+                        println!("  {}: {}", field.key, field.value);
+                    }
+                }
+            } else {
+                println!("Token metadata: not stored in the mint");
+            }
+            if output.metaplex_metadata_exists {
+                println!("Metaplex metadata: {}", output.metaplex_metadata);
+            } else {
+                println!(
+                    "Metaplex metadata: none (wallets that only read Metaplex show the token unnamed)"
+                );
+            }
+            Ok(())
+        }
+        MetadataCmd::SetField(args) => {
+            let mint = resolve_mint(&args.mint)?;
+            let config_pda = find_config_pda(&mint, &stablecoin_core::ID).0;
+            let update_ix = build_update_metadata_field_instruction(UpdateMetadataFieldParams {
+                authority: ctx_ref.authority(),
+                config_pda,
+                mint,
+                key: args.key.clone(),
+                value: args.value.clone(),
+            })?;
+            let Some(signature) = send_transaction(ctx_ref, vec![update_ix], vec![])? else {
+                return Ok(());
+            };
+            let explorer = explorer_url(&signature, ctx_ref.cluster);
+            if ctx_ref.output == OutputFormat::Json {
+                let output = SimpleOutput {
+                    signature: signature.clone(),
+                    explorer,
+                    pdas: Some(PdaOutput::new(&config_pda, Some(&ctx_ref.authority()))),
+                    compute_budget: ctx_ref.compute_budget.output(),
+                };
+                print_json(&output)
+            } else {
+                println!("Metadata field set");
+                println!("{}: {}", args.key, args.value);
+                println!("Tx: {}", signature);
+                if let Some(url) = explorer {
+                    println!("Explorer: {}", url);
+                }
+                Ok(())
+            }
+        }
+    }
+}
+
+/// Metaplex Token Metadata program, read by wallets that ignore the
+/// Token-2022 metadata extension.
+const METAPLEX_TOKEN_METADATA_ID: Pubkey =
+    solana_sdk::pubkey!("metaqbxxUerdq28cj1RbAWkYQm3ybzjb6a8bt518x1s");
+
+fn metaplex_metadata_pda(mint: &Pubkey) -> Pubkey {
+    Pubkey::find_program_address(
+        &[
+            b"metadata",
+            METAPLEX_TOKEN_METADATA_ID.as_ref(),
+            mint.as_ref(),
+        ],
+        &METAPLEX_TOKEN_METADATA_ID,
+    )
+    .0
+}
+
+/// Decodes the MetadataPointer and the TokenMetadata embedded in the mint.
+/// Metadata the pointer places in another account is reported as not embedded.
+fn mint_metadata(mint: &Pubkey, mint_data: &[u8]) -> Result<MetadataOutput> {
+    let state =
+        StateWithExtensions::<Mint2022>::unpack(mint_data).context("Failed to decode mint")?;
+    let describe = |key: Option<Pubkey>| key.map(|key| key.to_string());
+    let mut output = MetadataOutput {
+        mint: mint.to_string(),
+        metadata_address: None,
+        pointer_authority: None,
+        embedded: false,
+        update_authority: None,
+        name: String::new(),
+        symbol: String::new(),
+        uri: String::new(),
+        additional_metadata: Vec::new(),
+        metaplex_metadata: metaplex_metadata_pda(mint).to_string(),
+        metaplex_metadata_exists: false,
+    };
+    if let Ok(pointer) = state.get_extension::<MetadataPointer>() {
+        output.metadata_address = describe(pointer.metadata_address.into());
+        output.pointer_authority = describe(pointer.authority.into());
+    }
+    if let Ok(metadata) = state.get_variable_len_extension::<TokenMetadata>() {
+        output.embedded = true;
+        output.update_authority = describe(metadata.update_authority.into());
+        output.name = metadata.name;
+        output.symbol = metadata.symbol;
+        output.uri = metadata.uri;
+        output.additional_metadata = metadata
+            .additional_metadata
+            .into_iter()
+            .map(|(key, value)| MetadataFieldOutput { key, value })
+            .collect();
+    }
+    Ok(output)
+}
+
+fn config_output(config_pda: &Pubkey, config: &StablecoinConfig) -> ConfigOutput {
+    ConfigOutput {
+        address: config_pda.to_string(),
+        authority: config.authority.to_string(),
+        mint: config.mint.to_string(),
+        name: config.name.clone(),
+        symbol: config.symbol.clone(),
+        uri: config.uri.clone(),
+        decimals: config.decimals,
+        is_paused: config.is_paused(),
+        pause_flags: config.pause_flags,
+        total_minted: config.total_minted.to_string(),
+        total_burned: config.total_burned.to_string(),
+        audit_counter: config.audit_counter,
+        features: FeatureOutput {
+            permanent_delegate: config.features.permanent_delegate(),
+            transfer_hook: config.features.transfer_hook(),
+            confidential: config.features.confidential(),
+            default_frozen: config.features.default_frozen(),
+            transfer_cooldown: config.features.transfer_cooldown(),
+        },
+        feature_bits: config.features.bits(),
+        transfer_hook_program: config.transfer_hook_program.map(|id| id.to_string()),
+        bump: config.bump,
+        pause_cooldown: config.pause_cooldown,
+        last_pause_change: config.last_pause_change,
+        max_supply: config.max_supply.map(|value| value.to_string()),
+        creator: config.creator.to_string(),
+        created_at: config.created_at,
+        quota_window_seconds: config.quota_window(),
+        allowlist_mode: config.allowlist_mode,
+        max_transfer_amount: config.max_transfer_amount.map(|value| value.to_string()),
+        last_action_at: config.last_action_at,
+        last_action_by: config.last_action_by.to_string(),
+        allow_self_transfer: config.allow_self_transfer,
+        total_seized: config.total_seized.to_string(),
+        treasury: config.treasury.map(|key| key.to_string()),
+        lock_admin_ops: config.lock_admin_ops,
+        minting_locked: config.minting_locked,
+        token_program: config.token_program_id().to_string(),
+        transfer_cooldown_seconds: config.transfer_cooldown_seconds,
+        require_mint_approval: config.require_mint_approval,
+    }
+}
+
+fn handle_status(ctx: &OwnedContext, args: &StatusArgs) -> Result<()> {
+    if let Some(StatusCmd::DefaultFrozen(args)) = &args.command {
+        return handle_set_default_frozen(ctx, args);
+    }
+    let ctx_ref = ctx.as_ref();
+    let mint = resolve_mint(&args.mint)?;
+    let config_pda = find_config_pda(&mint, &stablecoin_core::ID).0;
+    let config = fetch_config(ctx_ref, &config_pda)?;
+    let supply = ctx_ref.client.get_token_supply(&mint)?;
+    let mint_account = ctx_ref.client.get_account(&mint)?;
+    // Mint and freeze go through the config PDA; if either authority moved,
+    // those instructions fail on-chain with nothing in the config to show it.
+    let authorities = verify_authorities(
+        &mint,
+        &mint_account.data,
+        &config_pda,
+        config.minting_locked,
+    )?;
+    let mint_authority_ok = authorities.authority_ok("mint_authority");
+    let freeze_authority_ok = authorities.authority_ok("freeze_authority");
+    let roles = list_role_accounts(ctx_ref, &config_pda)?;
+    let blacklist = list_blacklist_entries(ctx_ref, &config_pda)?;
+    let now = Utc::now().timestamp();
+    let preset = preset_label(
+        config.features.transfer_hook(),
+        config.features.confidential(),
+    );
+    let supply_amount = supply.amount.parse::<u64>()?;
+    let headroom = supply_headroom(config.max_supply, supply_amount);
+    let minters = args.detailed.then(|| minter_details(&roles, &config, now));
+    if ctx_ref.output == OutputFormat::Json {
+        let output = StatusOutput {
+            mint: mint.to_string(),
+            preset: preset.to_string(),
+            is_paused: config.is_paused(),
+            paused_operations: config_paused_operations(&config),
+            pause_cooldown: config.pause_cooldown,
+            lock_admin_ops: config.lock_admin_ops,
+            minting_locked: config.minting_locked,
+            mint_authority_ok,
+            freeze_authority_ok,
+            supply: supply.amount,
+            max_supply: config.max_supply.map(|value| value.to_string()),
+            headroom: headroom.map(|value| value.to_string()),
+            total_minted: config.total_minted.to_string(),
+            total_burned: config.total_burned.to_string(),
+            total_seized: config.total_seized.to_string(),
+            creator: config.creator.to_string(),
+            created_at: config.created_at,
+            last_action_at: config.last_action_at,
+            last_action_by: config.last_action_by.to_string(),
+            features: FeatureOutput {
+                permanent_delegate: config.features.permanent_delegate(),
+                transfer_hook: config.features.transfer_hook(),
+                confidential: config.features.confidential(),
+                default_frozen: config.features.default_frozen(),
+                transfer_cooldown: config.features.transfer_cooldown(),
+            },
+            role_counts: role_counts(&roles),
+            blacklisted: blacklist
+                .iter()
+                .filter(|entry| entry.account.is_in_effect(now))
+                .count(),
+            blacklisted_by_code: blacklist_reason_counts(&blacklist, now),
+            minters,
+        };
+        print_json(&output)
+    } else {
+        println!("Stablecoin status");
+        println!("Mint: {}", mint);
+        println!("Preset: {}", preset);
+        if config.is_paused() {
+            println!(
+                "Status: Paused ({})",
+                config_paused_operations(&config).join(", ")
+            );
+        } else {
+            println!("Status: Active");
+        }
+        if config.pause_cooldown > 0 {
+            println!("Pause cooldown: {}s", config.pause_cooldown);
+        }
+        if config.lock_admin_ops {
+            println!("Emergency lock: freeze, thaw, blacklist and seize are master-only");
+        }
+        if config.minting_locked {
+            println!("Minting: permanently locked");
+        }
+        if !mint_authority_ok {
+            println!("Warning: mint authority is not the config PDA; minting will fail");
+        }
+        if !freeze_authority_ok {
+            println!("Warning: freeze authority is not the config PDA; freeze and thaw will fail");
+        }
+        println!("Supply: {}", format_amount(supply_amount, config.decimals));
+        if let (Some(max_supply), Some(headroom)) = (config.max_supply, headroom) {
+            println!(
+                "Max supply: {} (headroom {})",
+                format_amount(max_supply, config.decimals),
+                format_amount(headroom, config.decimals)
+            );
+        }
+        println!("Total minted: {}", config.total_minted);
+        println!("Total burned: {}", config.total_burned);
+        println!("Total seized: {}", config.total_seized);
+        println!(
+            "Created: {} by {}",
+            format_unix_timestamp(config.created_at),
+            config.creator
+        );
+        // Configs written before the field existed read 0 until their next action.
+        if config.last_action_at > 0 {
+            println!(
+                "Last action: {} by {}",
+                format_unix_timestamp(config.last_action_at),
+                config.last_action_by
+            );
+        }
+        println!("Features:");
+        println!(
+            "  Permanent delegate: {}",
+            config.features.permanent_delegate()
+        );
+        println!("  Transfer hook: {}", config.features.transfer_hook());
+        println!("  Confidential: {}", config.features.confidential());
+        println!("  Default frozen: {}", config.features.default_frozen());
+        println!("Roles:");
+        println!("  Masters: {}", count_role(&roles, ROLE_MASTER_AUTHORITY));
+        println!("  Minters: {}", count_role(&roles, ROLE_MINTER));
+        println!("  Burners: {}", count_role(&roles, ROLE_BURNER));
+        println!("  Freezers: {}", count_role(&roles, ROLE_FREEZER));
+        println!("  Pausers: {}", count_role(&roles, ROLE_PAUSER));
+        println!("  Blacklisters: {}", count_role(&roles, ROLE_BLACKLISTER));
+        println!("  Seizers: {}", count_role(&roles, ROLE_SEIZER));
+        println!("  Role admins: {}", count_role(&roles, ROLE_ROLE_ADMIN));
+        println!(
+            "Blacklisted: {}",
+            blacklist
+                .iter()
+                .filter(|entry| entry.account.is_in_effect(now))
+                .count()
+        );
+        let by_code = blacklist_reason_counts(&blacklist, now);
+        println!("  Sanctions: {}", by_code.sanctions);
+        println!("  Fraud: {}", by_code.fraud);
+        println!("  Court order: {}", by_code.court_order);
+        println!("  Other: {}", by_code.other);
+        if by_code.unspecified > 0 {
+            println!("  Unspecified: {}", by_code.unspecified);
+        }
+        if let Some(minters) = minters {
+            println!("Minter quotas:");
+            for minter in minters {
+                println!(
+                    "  {}: quota {}, window minted {}",
+                    minter.address,
+                    minter.quota_ui.as_deref().unwrap_or("unlimited"),
+                    minter.minted_current_window_ui
+                );
+            }
+        }
+        Ok(())
+    }
+}
+
+/// Quota detail for every account holding the minter role, as `status
+/// --detailed` reports it. Usage from an expired window reads 0.
+fn minter_details(
+    roles: &[AccountEntry<RoleAccount>],
+    config: &StablecoinConfig,
+    now: i64,
+) -> Vec<MinterDetail> {
+    let window_seconds = config.quota_window();
+    roles
+        .iter()
+        .map(|entry| &entry.account)
+        .filter(|role| role.roles & ROLE_MINTER != 0)
+        .map(|role| {
+            let window_minted = current_window_minted(role, now, window_seconds);
+            MinterDetail {
+                address: role.authority.to_string(),
+                quota: role.mint_quota.map(|quota| quota.to_string()),
+                quota_ui: role
+                    .mint_quota
+                    .map(|quota| format_amount(quota, config.decimals)),
+                minted_current_window: window_minted.to_string(),
+                minted_current_window_ui: format_amount(window_minted, config.decimals),
+                window_start: role.window_start,
+                window_seconds,
+            }
+        })
+        .collect()
+}
+
+fn handle_verify_authorities(ctx: &OwnedContext, args: &MintOnlyArgs) -> Result<()> {
+    let ctx_ref = ctx.as_ref();
+    let mint = resolve_mint(&args.mint)?;
+    let config_pda = find_config_pda(&mint, &stablecoin_core::ID).0;
+    let config = fetch_config(ctx_ref, &config_pda)?;
+    let account = ctx_ref.client.get_account(&mint)?;
+    let report = verify_authorities(&mint, &account.data, &config_pda, config.minting_locked)?;
+    if ctx_ref.output == OutputFormat::Json {
+        print_json(&report)?;
+    } else {
+        println!("Authority verification");
+        println!("Mint: {}", report.mint);
+        println!("Config: {}", report.config);
+        for check in &report.checks {
+            println!(
+                "  {}: {} ({})",
+                check.authority,
+                check.actual.as_deref().unwrap_or("none"),
+                check.status.label()
+            );
+        }
+        println!("Result: {}", if report.ok { "OK" } else { "FAILED" });
+    }
+    if report.ok {
+        Ok(())
+    } else {
+        Err(anyhow!("Not every authority is assigned to the config PDA"))
+    }
+}
+
+/// Checks every mint authority the program relies on against the config PDA.
+/// Mint and freeze authority are always required; extension authorities are
+/// required only when the mint carries that extension. Once minting is
+/// locked, an unset mint authority is expected rather than a failure.
+fn verify_authorities(
+    mint: &Pubkey,
+    mint_data: &[u8],
+    config_pda: &Pubkey,
+    minting_locked: bool,
+) -> Result<AuthorityReport> {
+    let state =
+        StateWithExtensions::<Mint2022>::unpack(mint_data).context("Failed to decode mint")?;
+    let mut mint_authority = AuthorityCheck::new(
+        "mint_authority",
+        Some(Option::from(state.base.mint_authority)),
+        config_pda,
+    );
+    // freeze_supply --null-mint-authority clears it on purpose.
+    if minting_locked && mint_authority.status == AuthorityStatus::Unset {
+        mint_authority.status = AuthorityStatus::Renounced;
+    }
+    let mut checks = vec![
+        mint_authority,
+        AuthorityCheck::new(
+            "freeze_authority",
+            Some(Option::from(state.base.freeze_authority)),
+            config_pda,
+        ),
+        AuthorityCheck::new(
+            "close_authority",
+            state
+                .get_extension::<MintCloseAuthority>()
+                .ok()
+                .map(|ext| Option::from(ext.close_authority)),
+            config_pda,
+        ),
+        AuthorityCheck::new(
+            "permanent_delegate",
+            state
+                .get_extension::<PermanentDelegate>()
+                .ok()
+                .map(|ext| Option::from(ext.delegate)),
+            config_pda,
+        ),
+        AuthorityCheck::new(
+            "transfer_hook_authority",
+            state
+                .get_extension::<TransferHook>()
+                .ok()
+                .map(|ext| Option::from(ext.authority)),
+            config_pda,
+        ),
+        AuthorityCheck::new(
+            "metadata_pointer_authority",
+            state
+                .get_extension::<MetadataPointer>()
+                .ok()
+                .map(|ext| Option::from(ext.authority)),
+            config_pda,
+        ),
+        AuthorityCheck::new(
+            "metadata_update_authority",
+            state
+                .get_variable_len_extension::<TokenMetadata>()
+                .ok()
+                .map(|metadata| Option::from(metadata.update_authority)),
+            config_pda,
+        ),
+    ];
+    checks.sort_by_key(|check| check.status == AuthorityStatus::NotPresent);
+
+    Ok(AuthorityReport {
+        mint: mint.to_string(),
+        config: config_pda.to_string(),
+        ok: checks.iter().all(|check| check.status.is_ok()),
+        checks,
+    })
+}
+
+/// Only accounts created afterwards are affected; existing ones keep their state.
+fn handle_set_default_frozen(ctx: &OwnedContext, args: &SetDefaultFrozenArgs) -> Result<()> {
+    let ctx_ref = ctx.as_ref();
+    let mint = resolve_mint(&args.mint)?;
+    let config_pda = find_config_pda(&mint, &stablecoin_core::ID).0;
+    fetch_config(ctx_ref, &config_pda)?;
+    let account = ctx_ref.client.get_account(&mint)?;
+    let state = StateWithExtensions::<Mint2022>::unpack(&account.data)?;
+    if state.get_extension::<DefaultAccountState>().is_err() {
+        return Err(anyhow!(
+            "This mint has no DefaultAccountState extension; only mints initialized with default_account_frozen can change it"
+        ));
+    }
+    let default_frozen = args.state.enabled();
+    let update_ix =
+        build_update_default_account_state_instruction(UpdateDefaultAccountStateParams {
+            authority: ctx_ref.authority(),
+            config_pda,
+            mint,
+            default_frozen,
+        })?;
+    let Some(signature) = send_transaction(ctx_ref, vec![update_ix], vec![])? else {
+        return Ok(());
+    };
+    let explorer = explorer_url(&signature, ctx_ref.cluster);
+    if ctx_ref.output == OutputFormat::Json {
+        let output = SimpleOutput {
+            signature: signature.clone(),
+            explorer,
+            pdas: Some(PdaOutput::new(&config_pda, Some(&ctx_ref.authority()))),
+            compute_budget: ctx_ref.compute_budget.output(),
+        };
+        print_json(&output)
+    } else {
+        println!("Default account state updated");
+        println!("Default frozen: {}", default_frozen);
+        println!("Existing token accounts are unchanged");
+        println!("Tx: {}", signature);
+        if let Some(url) = explorer {
+            println!("Explorer: {}", url);
+        }
+        Ok(())
+    }
+}
+
+fn handle_verify(ctx: &OwnedContext, args: &MintOnlyArgs) -> Result<()> {
+    let ctx_ref = ctx.as_ref();
+    let mint = resolve_mint(&args.mint)?;
+    let config_pda = find_config_pda(&mint, &stablecoin_core::ID).0;
+    let config = fetch_config(ctx_ref, &config_pda)?;
+    let account = ctx_ref.client.get_account(&mint)?;
+    let output = verify_mint(&mint, &account.data, &config_pda, &config)?;
+    if ctx_ref.output == OutputFormat::Json {
+        print_json(&output)?;
+    } else {
+        println!("Mint verification");
+        println!("Mint: {}", output.mint);
+        println!("Config: {}", output.config);
+        for check in &output.checks {
+            println!(
+                "  {}: {} (expected {}, found {})",
+                check.check,
+                if check.passed { "pass" } else { "FAIL" },
+                check.expected,
+                check.actual
+            );
+        }
+        println!("Result: {}", if output.ok { "OK" } else { "FAILED" });
+    }
+    if output.ok {
+        Ok(())
+    } else {
+        Err(anyhow!("The mint does not match its stablecoin config"))
+    }
+}
+
+/// Compares the mint's base state and extensions with what the config says
+/// the mint was initialized with. Feature-gated extensions must be present
+/// exactly when the feature is on; metadata is optional at initialize, so
+/// only its contents are checked when it exists.
+fn verify_mint(
+    mint: &Pubkey,
+    mint_data: &[u8],
+    config_pda: &Pubkey,
+    config: &StablecoinConfig,
+) -> Result<VerifyOutput> {
+    let state =
+        StateWithExtensions::<Mint2022>::unpack(mint_data).context("Failed to decode mint")?;
+    let pda = config_pda.to_string();
+    let describe = |key: Option<Pubkey>| key.map_or_else(|| "none".to_string(), |k| k.to_string());
+    let mint_authority: Option<Pubkey> = state.base.mint_authority.into();
+    // freeze_supply --null-mint-authority clears it once minting is locked.
+    let expected_mint_authority = if config.minting_locked && mint_authority.is_none() {
+        describe(None)
+    } else {
+        pda.clone()
+    };
+    let mut checks = vec![
+        VerifyCheck::new(
+            "decimals",
+            config.decimals.to_string(),
+            state.base.decimals.to_string(),
+        ),
+        VerifyCheck::new(
+            "mint_authority",
+            expected_mint_authority,
+            describe(mint_authority),
+        ),
+        VerifyCheck::new(
+            "freeze_authority",
+            pda.clone(),
+            describe(state.base.freeze_authority.into()),
+        ),
+        VerifyCheck::new(
+            "close_authority",
+            // Legacy SPL Token mints have no extensions at all.
+            if config.token_program_id() == spl_token_2022::id() {
+                pda.clone()
+            } else {
+                "absent".to_string()
+            },
+            state.get_extension::<MintCloseAuthority>().map_or_else(
+                |_| "absent".to_string(),
+                |ext| describe(ext.close_authority.into()),
+            ),
+        ),
+    ];
+
+    let permanent_delegate = state.get_extension::<PermanentDelegate>().ok();
+    checks.push(if config.features.permanent_delegate() {
+        VerifyCheck::new(
+            "permanent_delegate",
+            pda.clone(),
+            permanent_delegate
+                .map_or_else(|| "absent".to_string(), |ext| describe(ext.delegate.into())),
+        )
+    } else {
+        VerifyCheck::presence("permanent_delegate", false, permanent_delegate.is_some())
+    });
+
+    let transfer_hook = state.get_extension::<TransferHook>().ok();
+    if config.features.transfer_hook() {
+        checks.push(VerifyCheck::new(
+            "transfer_hook_program",
+            describe(config.transfer_hook_program),
+            transfer_hook.map_or_else(
+                || "absent".to_string(),
+                |ext| describe(ext.program_id.into()),
+            ),
+        ));
+        checks.push(VerifyCheck::new(
+            "transfer_hook_authority",
+            pda.clone(),
+            transfer_hook.map_or_else(
+                || "absent".to_string(),
+                |ext| describe(ext.authority.into()),
+            ),
+        ));
+    } else {
+        checks.push(VerifyCheck::presence(
+            "transfer_hook_program",
+            false,
+            transfer_hook.is_some(),
+        ));
+    }
+
+    let frozen = state
+        .get_extension::<DefaultAccountState>()
+        .ok()
+        .is_some_and(|ext| ext.state == AccountState::Frozen as u8);
+    checks.push(VerifyCheck::new(
+        "default_account_state",
+        if config.features.default_frozen() {
+            "frozen"
+        } else {
+            "initialized"
+        }
+        .to_string(),
+        if frozen { "frozen" } else { "initialized" }.to_string(),
+    ));
+    checks.push(VerifyCheck::presence(
+        "confidential_transfer",
+        config.features.confidential(),
+        state.get_extension::<ConfidentialTransferMint>().is_ok(),
+    ));
+
+    if let Ok(pointer) = state.get_extension::<MetadataPointer>() {
+        checks.push(VerifyCheck::new(
+            "metadata_address",
+            mint.to_string(),
+            describe(pointer.metadata_address.into()),
+        ));
+        checks.push(VerifyCheck::new(
+            "metadata_pointer_authority",
+            pda.clone(),
+            describe(pointer.authority.into()),
+        ));
+    }
+    if let Ok(metadata) = state.get_variable_len_extension::<TokenMetadata>() {
+        checks.push(VerifyCheck::new(
+            "metadata_update_authority",
+            pda.clone(),
+            describe(metadata.update_authority.into()),
+        ));
+        checks.push(VerifyCheck::new(
+            "metadata_fields",
+            format!("{}/{}/{}", config.name, config.symbol, config.uri),
+            format!("{}/{}/{}", metadata.name, metadata.symbol, metadata.uri),
+        ));
+    }
+
+    Ok(VerifyOutput {
+        mint: mint.to_string(),
+        config: pda,
+        ok: checks.iter().all(|check| check.passed),
+        checks,
+    })
+}
+
+fn handle_report(ctx: &OwnedContext, args: &ReportArgs) -> Result<()> {
+    let ctx_ref = ctx.as_ref();
+    let mint = resolve_mint(&args.mint)?;
+    let config_pda = find_config_pda(&mint, &stablecoin_core::ID).0;
+    let config = fetch_config(ctx_ref, &config_pda)?;
+    let supply = ctx_ref
+        .client
+        .get_token_supply(&mint)?
+        .amount
+        .parse::<u64>()?;
+    let roles = list_role_accounts(ctx_ref, &config_pda)?;
+    let blacklist = list_blacklist_entries(ctx_ref, &config_pda)?;
+    let report = compliance_report(
+        &mint,
+        &config,
+        supply,
+        &roles,
+        &blacklist,
+        Utc::now().timestamp(),
+    );
+    if args.format.unwrap_or(ctx_ref.output) == OutputFormat::Json {
+        return print_json(&report);
+    }
+
+    let decimals = config.decimals;
+    println!("Compliance report");
+    println!("Mint: {}", report.mint);
+    println!("Generated: {}", format_unix_timestamp(report.generated_at));
+    println!("Supply: {}", format_amount(supply, decimals));
+    println!(
+        "Total minted: {}",
+        format_amount(config.total_minted, decimals)
+    );
+    println!(
+        "Total burned: {}",
+        format_amount(config.total_burned, decimals)
+    );
+    println!("Audit counter: {}", report.audit_counter);
+    println!("Roles:");
+    println!("  Masters: {}", report.role_counts.masters);
+    println!("  Minters: {}", report.role_counts.minters);
+    println!("  Burners: {}", report.role_counts.burners);
+    println!("  Freezers: {}", report.role_counts.freezers);
+    println!("  Pausers: {}", report.role_counts.pausers);
+    println!("  Blacklisters: {}", report.role_counts.blacklisters);
+    println!("  Seizers: {}", report.role_counts.seizers);
+    println!("  Role admins: {}", report.role_counts.role_admins);
+    let blacklist = &report.blacklist;
+    println!(
+        "Blacklist: {} entries ({} active, {} inactive)",
+        blacklist.entries, blacklist.active, blacklist.inactive
+    );
+    println!("  Sanctions: {}", blacklist.by_code.sanctions);
+    println!("  Fraud: {}", blacklist.by_code.fraud);
+    println!("  Court order: {}", blacklist.by_code.court_order);
+    println!("  Other: {}", blacklist.by_code.other);
+    if blacklist.by_code.unspecified > 0 {
+        println!("  Unspecified: {}", blacklist.by_code.unspecified);
+    }
+    Ok(())
+}
+
+/// Aggregates the report from already-fetched accounts. Inactive counts
+/// removed and expired entries alike; the reason breakdown covers active ones.
+fn compliance_report(
+    mint: &Pubkey,
+    config: &StablecoinConfig,
+    supply: u64,
+    roles: &[AccountEntry<RoleAccount>],
+    blacklist: &[AccountEntry<BlacklistEntry>],
+    now: i64,
+) -> ComplianceReportOutput {
+    let active = blacklist
+        .iter()
+        .filter(|entry| entry.account.is_in_effect(now))
+        .count();
+    ComplianceReportOutput {
+        mint: mint.to_string(),
+        generated_at: now,
+        supply: supply.to_string(),
+        total_minted: config.total_minted.to_string(),
+        total_burned: config.total_burned.to_string(),
+        audit_counter: config.audit_counter,
+        role_counts: role_counts(roles),
+        blacklist: BlacklistReport {
+            entries: blacklist.len(),
+            active,
+            inactive: blacklist.len() - active,
+            by_code: blacklist_reason_counts(blacklist, now),
+        },
+    }
+}
+
+fn handle_supply(ctx: &OwnedContext, args: &SupplyArgs) -> Result<()> {
+    let ctx_ref = ctx.as_ref();
+    let mint = resolve_mint(&args.mint)?;
+    let supply = ctx_ref.client.get_token_supply(&mint)?;
+    let holder_count = if args.with_holders {
+        let config_pda = find_config_pda(&mint, &stablecoin_core::ID).0;
+        let config = fetch_config(ctx_ref, &config_pda)?;
+        let holders = fetch_holders(ctx_ref, &mint, &config.token_program_id(), Some(1), true)?;
+        Some(holders.len())
+    } else {
+        None
+    };
+    if ctx_ref.output == OutputFormat::Json {
+        let output = SupplyOutput {
+            mint: mint.to_string(),
+            supply: supply.amount,
+            holder_count,
+        };
+        print_json(&output)
+    } else {
+        println!("Supply: {}", supply.amount);
+        if let Some(count) = holder_count {
+            println!("Holders: {}", count);
+        }
+        Ok(())
+    }
+}
+
+fn handle_freeze_supply(ctx: &OwnedContext, args: &SupplyLockArgs) -> Result<()> {
+    let ctx_ref = ctx.as_ref();
+    let mint = resolve_mint(&args.mint)?;
+    let config_pda = find_config_pda(&mint, &stablecoin_core::ID).0;
+    let config = fetch_config(ctx_ref, &config_pda)?;
+    if config.minting_locked && !args.null_mint_authority {
+        return Err(anyhow!("Minting is already locked for this stablecoin"));
+    }
+    let freeze_ix = build_freeze_supply_instruction(FreezeSupplyParams {
+        authority: ctx_ref.authority(),
+        config_pda,
+        mint,
+        null_mint_authority: args.null_mint_authority,
+        token_program: config.token_program_id(),
+    })?;
+    ctx_ref.confirm(&format!(
+        "Permanently lock minting for mint {}{}? This cannot be undone.",
+        mint,
+        if args.null_mint_authority {
+            " and clear its mint authority"
+        } else {
+            ""
+        }
+    ))?;
+    let Some(signature) = send_transaction(ctx_ref, vec![freeze_ix], vec![])? else {
+        return Ok(());
+    };
+    let explorer = explorer_url(&signature, ctx_ref.cluster);
+    if ctx_ref.output == OutputFormat::Json {
+        let output = SimpleOutput {
+            signature: signature.clone(),
+            explorer,
+            pdas: Some(PdaOutput::new(&config_pda, Some(&ctx_ref.authority()))),
+            compute_budget: ctx_ref.compute_budget.output(),
+        };
+        print_json(&output)
+    } else {
+        println!("Minting locked");
+        if args.null_mint_authority {
+            println!("Mint authority cleared");
+        }
+        println!("Config: {}", config_pda);
+        println!("Tx: {}", signature);
+        if let Some(url) = explorer {
+            println!("Explorer: {}", url);
+        }
+        Ok(())
+    }
+}
+
+fn handle_holders(ctx: &OwnedContext, args: &HoldersArgs) -> Result<()> {
+    let ctx_ref = ctx.as_ref();
+    if let Some(HoldersCmd::Snapshot(snapshot)) = &args.command {
+        return handle_holders_snapshot(ctx_ref, snapshot);
+    }
+    let mint = resolve_mint(&args.mint)?;
+    let config_pda = find_config_pda(&mint, &stablecoin_core::ID).0;
+    let stablecoin_config = fetch_config(ctx_ref, &config_pda)?;
+    let min_balance = match args.min_balance.as_deref() {
+        Some(value) => Some(parse_amount(value, stablecoin_config.decimals)?),
+        None => None,
+    };
+
+    // With --limit only the owner and amount are fetched, which keeps the
+    // response small.
+    let holders = fetch_holders(
+        ctx_ref,
+        &mint,
+        &stablecoin_config.token_program_id(),
+        min_balance,
+        args.limit.is_some(),
+    )?;
+    let total = holders.len();
+    let holders = page_holders(holders, args.sort, args.offset, args.limit);
+
+    if ctx_ref.output == OutputFormat::Json {
+        let output = HoldersOutput {
+            total,
+            holders: holders.clone(),
+        };
+        print_json(&output)
+    } else {
+        if holders.is_empty() {
+            println!("No holders found");
+        } else {
+            for holder in holders {
+                println!(
+                    "{} {}",
+                    holder.owner,
+                    format_amount(holder.amount, stablecoin_config.decimals)
+                );
+            }
+        }
+        Ok(())
+    }
+}
+
+fn handle_holders_snapshot(ctx: AppContext<'_>, args: &HoldersSnapshotArgs) -> Result<()> {
+    let mint = resolve_mint(&args.mint)?;
+    let config_pda = find_config_pda(&mint, &stablecoin_core::ID).0;
+    let config = fetch_config(ctx, &config_pda)?;
+    let min_balance = match args.min_balance.as_deref() {
+        Some(value) => Some(parse_amount(value, config.decimals)?),
+        None => None,
+    };
+
+    let slot = ctx.client.get_slot_with_commitment(ctx.commitment)?;
+    let holders = fetch_holders(ctx, &mint, &config.token_program_id(), min_balance, true)?;
+    let snapshot = HoldersSnapshot {
+        mint: mint.to_string(),
+        slot,
+        timestamp: Utc::now().timestamp(),
+        decimals: config.decimals,
+        supply: ctx.client.get_token_supply(&mint)?.amount,
+        holder_count: holders.len(),
+        holders: page_holders(holders, SortOrder::Desc, 0, None),
+    };
+    let content = match args.format {
+        SnapshotFormat::Json => serde_json::to_string_pretty(&snapshot)? + "\n",
+        SnapshotFormat::Csv => snapshot_csv(&snapshot),
+    };
+    fs::write(&args.out, content)
+        .with_context(|| format!("Failed to write {}", args.out.display()))?;
+
+    if ctx.output == OutputFormat::Json {
+        let output = HoldersSnapshotOutput {
+            path: args.out.display().to_string(),
+            slot: snapshot.slot,
+            timestamp: snapshot.timestamp,
+            holder_count: snapshot.holder_count,
+        };
+        print_json(&output)
+    } else {
+        println!(
+            "Wrote {} holders at slot {} to {}",
+            snapshot.holder_count,
+            snapshot.slot,
+            args.out.display()
+        );
+        Ok(())
+    }
+}
+
+/// A `#`-prefixed header record with the snapshot metadata, then one
+/// `owner,token_account,amount` row per holder.
+fn snapshot_csv(snapshot: &HoldersSnapshot) -> String {
+    let mut csv = format!(
+        "# mint={},slot={},timestamp={},decimals={},supply={},holder_count={}\n",
+        snapshot.mint,
+        snapshot.slot,
+        snapshot.timestamp,
+        snapshot.decimals,
+        snapshot.supply,
+        snapshot.holder_count
+    );
+    csv.push_str("owner,token_account,amount\n");
+    for holder in &snapshot.holders {
+        csv.push_str(&format!(
+            "{},{},{}\n",
+            holder.owner, holder.token_account, holder.amount
+        ));
+    }
+    csv
+}
+
+/// Every token account of the mint holding at least `min_balance`. With
+/// `owner_and_amount_only` the RPC returns just those bytes of each account;
+/// the mint filter still matches on the full data.
+fn fetch_holders(
+    ctx: AppContext<'_>,
+    mint: &Pubkey,
+    token_program: &Pubkey,
+    min_balance: Option<u64>,
+    owner_and_amount_only: bool,
+) -> Result<Vec<HolderInfo>> {
+    let data_slice = owner_and_amount_only.then_some(UiDataSliceConfig {
+        offset: TOKEN_ACCOUNT_OWNER_OFFSET,
+        length: 40,
+    });
+    let rpc_config = RpcProgramAccountsConfig {
+        filters: Some(vec![RpcFilterType::Memcmp(Memcmp::new_base58_encoded(
+            0,
+            mint.as_ref(),
+        ))]),
+        account_config: RpcAccountInfoConfig {
+            encoding: None,
+            commitment: Some(ctx.commitment),
+            data_slice,
+            min_context_slot: None,
+        },
+        ..RpcProgramAccountsConfig::default()
+    };
+
+    let accounts = ctx
+        .client
+        .get_program_accounts_with_config(token_program, rpc_config)?;
+
+    let mut holders = Vec::new();
+    for (pubkey, account) in accounts {
+        let (owner, amount) = if data_slice.is_some() {
+            decode_owner_and_amount(&account.data)?
+        } else {
+            let parsed = StateWithExtensions::<TokenAccount2022>::unpack(&account.data)
+                .map_err(|err| anyhow!("Failed to decode token account: {}", err))?;
+            (parsed.base.owner, parsed.base.amount)
+        };
+        if let Some(min) = min_balance {
+            if amount < min {
+                continue;
+            }
+        }
+        holders.push(HolderInfo {
+            owner: owner.to_string(),
+            token_account: pubkey.to_string(),
+            amount,
+        });
+    }
+    Ok(holders)
+}
+
+/// Offset of the owner in a token account; the amount follows it.
+const TOKEN_ACCOUNT_OWNER_OFFSET: usize = 32;
+
+/// Decodes the owner and amount from a token account sliced at the owner.
+fn decode_owner_and_amount(data: &[u8]) -> Result<(Pubkey, u64)> {
+    if data.len() < 40 {
+        return Err(anyhow!("Failed to decode token account: slice too short"));
+    }
+    let owner = Pubkey::try_from(&data[..32]).expect("32-byte slice");
+    let amount = u64::from_le_bytes(data[32..40].try_into().expect("8-byte slice"));
+    Ok((owner, amount))
+}
+
+/// Sorts holders by amount (ties by token account, so pages are stable
+/// between runs) and returns the requested page.
+fn page_holders(
+    mut holders: Vec<HolderInfo>,
+    sort: SortOrder,
+    offset: usize,
+    limit: Option<usize>,
+) -> Vec<HolderInfo> {
+    holders.sort_by(|a, b| {
+        let by_amount = match sort {
+            SortOrder::Asc => a.amount.cmp(&b.amount),
+            SortOrder::Desc => b.amount.cmp(&a.amount),
+        };
+        by_amount.then_with(|| a.token_account.cmp(&b.token_account))
+    });
+    holders
+        .into_iter()
+        .skip(offset)
+        .take(limit.unwrap_or(usize::MAX))
+        .collect()
+}
+
+fn handle_balance(ctx: &OwnedContext, args: &AddressArgs) -> Result<()> {
+    let ctx_ref = ctx.as_ref();
+    let mint = resolve_mint(&args.mint)?;
+    let config_pda = find_config_pda(&mint, &stablecoin_core::ID).0;
+    let config = fetch_config(ctx_ref, &config_pda)?;
+    let owner = parse_pubkey(&args.address)?;
+    let token_account =
+        get_associated_token_address_with_program_id(&owner, &mint, &config.token_program_id());
+    // A missing ATA simply holds nothing.
+    let (amount, is_frozen) = match ctx_ref
+        .client
+        .get_account_with_commitment(&token_account, ctx_ref.commitment)?
+        .value
+    {
+        Some(account) => {
+            let parsed = StateWithExtensions::<TokenAccount2022>::unpack(&account.data)
+                .map_err(|err| anyhow!("Failed to decode token account: {}", err))?;
+            (
+                parsed.base.amount,
+                parsed.base.state == AccountState::Frozen,
+            )
+        }
+        None => (0, false),
+    };
+
+    if ctx_ref.output == OutputFormat::Json {
+        let output = BalanceOutput {
+            owner: owner.to_string(),
+            token_account: token_account.to_string(),
+            amount,
+            is_frozen,
+        };
+        print_json(&output)
+    } else {
+        println!("Owner: {}", owner);
+        println!("Token account: {}", token_account);
+        println!("Balance: {}", format_amount(amount, config.decimals));
+        if is_frozen {
+            println!("Frozen: yes");
+        }
+        Ok(())
+    }
+}
+
+fn handle_audit_log(ctx: &OwnedContext, args: &AuditLogArgs) -> Result<()> {
+    let ctx_ref = ctx.as_ref();
+    let range = TimeRange::from_args(
+        args.from.as_deref().or(args.since.as_deref()),
+        args.to.as_deref(),
+        Utc::now(),
+    )?;
+    let mint = resolve_mint(&args.mint)?;
+    let config_pda = find_config_pda(&mint, &stablecoin_core::ID).0;
+    let hook_program = if args.hook_decisions {
+        let config = fetch_config(ctx_ref, &config_pda)?;
+        Some(
+            config
+                .transfer_hook_program
+                .ok_or_else(|| anyhow!("This stablecoin has no transfer hook"))?,
+        )
+    } else {
+        None
+    };
+    let entries = fetch_audit_log(
+        ctx_ref,
+        &config_pda,
+        &range,
+        args.action.as_deref(),
+        args.limit,
+        hook_program.as_ref(),
+    )?;
+
+    if ctx.output == OutputFormat::Json {
+        let output = AuditLogOutput {
+            from: range.from,
+            to: range.to,
+            entries,
+        };
+        print_json(&output)
+    } else {
+        if entries.is_empty() {
+            println!("No audit log entries found");
+        }
+        for entry in entries {
+            println!(
+                "{} {:<18} {} {}",
+                entry.timestamp,
+                entry.action,
+                entry.signature,
+                summarize_event_data(&entry.data)
+            );
+        }
+        Ok(())
+    }
+}
+
+/// Scans the newest `limit` transactions touching the config and returns the
+/// matching stablecoin-core events, oldest first. With `hook_program`, the
+/// hook's decision lines are listed too, from failed transactions as well
+/// since a denial aborts the transfer.
+fn fetch_audit_log(
+    ctx: AppContext<'_>,
+    config_pda: &Pubkey,
+    range: &TimeRange,
+    action: Option<&str>,
+    limit: usize,
+    hook_program: Option<&Pubkey>,
+) -> Result<Vec<AuditLogEntry>> {
+    // getTransaction rejects the processed commitment level.
+    let commitment = if ctx.commitment.is_at_least_confirmed() {
+        ctx.commitment
+    } else {
+        CommitmentConfig::confirmed()
+    };
+    let mut entries = Vec::new();
+    let mut before = None;
+    let mut scanned = 0;
+    'pages: while scanned < limit {
+        let page_size = (limit - scanned).min(1000);
+        let page = ctx.client.get_signatures_for_address_with_config(
+            config_pda,
+            GetConfirmedSignaturesForAddress2Config {
+                before,
+                until: None,
+                limit: Some(page_size),
+                commitment: Some(commitment),
+            },
+        )?;
+        let Some(last) = page.last() else {
+            break;
+        };
+        before = Some(Signature::from_str(&last.signature)?);
+
+        for status in &page {
+            scanned += 1;
+            // Signatures come newest first, so everything after this is older.
+            if let (Some(from), Some(block_time)) = (range.from, status.block_time) {
+                if block_time < from {
+                    break 'pages;
+                }
+            }
+            let failed = status.err.is_some();
+            if failed && hook_program.is_none() {
+                continue;
+            }
+            if let (Some(to), Some(block_time)) = (range.to, status.block_time) {
+                if block_time > to {
+                    continue;
+                }
+            }
+
+            let signature = Signature::from_str(&status.signature)?;
+            let transaction = ctx.client.get_transaction_with_config(
+                &signature,
+                RpcTransactionConfig {
+                    encoding: Some(UiTransactionEncoding::Base64),
+                    commitment: Some(commitment),
+                    max_supported_transaction_version: Some(0),
+                },
+            )?;
+            let logs: Vec<String> = transaction
+                .transaction
+                .meta
+                .and_then(|meta| Option::from(meta.log_messages))
+                .unwrap_or_default();
+            if let Some(hook_program) = hook_program {
+                for decision in decode_hook_decisions(&logs, hook_program).into_iter().rev() {
+                    if action.is_some_and(|action| !decision.matches_action(action)) {
+                        continue;
+                    }
+                    entries.push(AuditLogEntry {
+                        signature: status.signature.clone(),
+                        slot: transaction.slot,
+                        timestamp: status
+                            .block_time
+                            .map(format_unix_timestamp)
+                            .unwrap_or_default(),
+                        action: decision.action(),
+                        event: HOOK_DECISION_EVENT.to_string(),
+                        data: serde_json::to_value(&decision)?,
+                    });
+                }
+            }
+            // Events of a failed transaction were rolled back with it.
+            if failed {
+                continue;
+            }
+            // Each transaction's events are logged in order; they are reversed with the rest below.
+            for event in decode_program_events(&logs, &stablecoin_core::ID)
+                .into_iter()
+                .rev()
+            {
+                if !event_matches(&event, range, action) {
+                    continue;
+                }
+                entries.push(AuditLogEntry {
+                    signature: status.signature.clone(),
+                    slot: transaction.slot,
+                    timestamp: format_unix_timestamp(event.timestamp),
+                    action: event.action.to_string(),
+                    event: event.name.to_string(),
+                    data: serde_json::Value::Object(event.fields),
+                });
+            }
+        }
+        if page.len() < page_size {
+            break;
+        }
+    }
+    entries.reverse();
+    Ok(entries)
+}
+
+fn event_matches(event: &DecodedEvent, range: &TimeRange, action: Option<&str>) -> bool {
+    if action.is_some_and(|action| !event.matches_action(action)) {
+        return false;
+    }
+    range.from.is_none_or(|from| event.timestamp >= from)
+        && range.to.is_none_or(|to| event.timestamp <= to)
+}
+
+fn format_unix_timestamp(timestamp: i64) -> String {
+    DateTime::from_timestamp(timestamp, 0)
+        .map(|time| time.to_rfc3339())
+        .unwrap_or_else(|| timestamp.to_string())
+}
+
+/// Renders event fields as `key=value` pairs, leaving out the config and
+/// timestamp that every event repeats.
+fn summarize_event_data(data: &serde_json::Value) -> String {
+    let Some(fields) = data.as_object() else {
+        return String::new();
+    };
+    fields
+        .iter()
+        .filter(|(key, _)| !matches!(key.as_str(), "config" | "timestamp"))
+        .map(|(key, value)| match value {
+            serde_json::Value::String(text) => format!("{}={}", key, text),
+            other => format!("{}={}", key, other),
+        })
+        .collect::<Vec<_>>()
+        .join(" ")
+}
+
+/// Inclusive unix-timestamp bounds used to filter events by their `timestamp` field.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+struct TimeRange {
+    from: Option<i64>,
+    to: Option<i64>,
+}
+
+impl TimeRange {
+    fn from_args(from: Option<&str>, to: Option<&str>, now: DateTime<Utc>) -> Result<Self> {
+        let range = Self {
+            from: from.map(|value| parse_time_bound(value, now)).transpose()?,
+            to: to.map(|value| parse_time_bound(value, now)).transpose()?,
+        };
+        if let (Some(from), Some(to)) = (range.from, range.to) {
+            if from > to {
+                return Err(anyhow!("Start of the time range is after its end"));
+            }
+        }
+        Ok(range)
+    }
+}
+
+fn parse_time_bound(value: &str, now: DateTime<Utc>) -> Result<i64> {
+    let value = value.trim();
+    let midnight = |date: NaiveDate| date.and_time(NaiveTime::MIN).and_utc().timestamp();
+    match value.to_lowercase().as_str() {
+        "now" => return Ok(now.timestamp()),
+        "today" => return Ok(midnight(now.date_naive())),
+        "yesterday" => return Ok(midnight(now.date_naive() - chrono::Duration::days(1))),
+        _ => {}
+    }
+    if let Ok(timestamp) = value.parse::<i64>() {
+        return Ok(timestamp);
+    }
+    if let Ok(date) = NaiveDate::parse_from_str(value, "%Y-%m-%d") {
+        return Ok(midnight(date));
+    }
+    if let Ok(datetime) = DateTime::parse_from_rfc3339(value) {
+        return Ok(datetime.timestamp());
+    }
+    if let Ok(duration) = humantime::parse_duration(value) {
+        let seconds = i64::try_from(duration.as_secs())
+            .map_err(|_| anyhow!("Duration too large: {}", value))?;
+        return Ok(now.timestamp().saturating_sub(seconds));
+    }
+    Err(anyhow!(
+        "Invalid time: {} (expected a unix timestamp, YYYY-MM-DD, RFC 3339, today, yesterday, or a duration like 7d)",
+        value
+    ))
+}
+
+/// How long a balance change waits for the logs of the same slot to arrive.
+const WATCH_LOG_WAIT: Duration = Duration::from_secs(2);
+const WATCH_LOG_HISTORY: usize = 64;
+
+fn handle_watch(ctx: &OwnedContext, args: &WatchArgs) -> Result<()> {
+    let stop = ctrl_c_flag()?;
+    match &args.balance {
+        Some(owner) => watch_balance(ctx, &args.mint, owner, &stop),
+        None => watch_events(ctx, &args.mint, args.action.as_deref(), &stop),
+    }
+}
+
+/// Returns a flag that is raised on Ctrl-C, so watch loops can unsubscribe
+/// and exit cleanly instead of being killed mid-line.
+fn ctrl_c_flag() -> Result<Arc<AtomicBool>> {
+    let stop = Arc::new(AtomicBool::new(false));
+    let runtime = tokio::runtime::Builder::new_current_thread()
+        .enable_all()
+        .build()
+        .context("Failed to start the signal handler")?;
+    let flag = Arc::clone(&stop);
+    std::thread::spawn(move || {
+        if runtime.block_on(tokio::signal::ctrl_c()).is_ok() {
+            flag.store(true, Ordering::SeqCst);
+        }
+    });
+    Ok(stop)
+}
+
+/// Streams the stablecoin-core events of one config as they are logged.
+fn watch_events(
+    ctx: &OwnedContext,
+    mint: &Option<String>,
+    action: Option<&str>,
+    stop: &AtomicBool,
+) -> Result<()> {
+    let ctx_ref = ctx.as_ref();
+    let mint = resolve_mint(mint)?;
+    let config_pda = find_config_pda(&mint, &stablecoin_core::ID).0;
+    fetch_config(ctx_ref, &config_pda)?;
+
+    let ws_url = websocket_url(&ctx_ref.cluster.url);
+    let (_logs_subscription, log_updates) = PubsubClient::logs_subscribe(
+        &ws_url,
+        RpcTransactionLogsFilter::Mentions(vec![stablecoin_core::ID.to_string()]),
+        RpcTransactionLogsConfig {
+            commitment: Some(ctx_ref.commitment),
+        },
+    )
+    .map_err(|err| anyhow!("Failed to subscribe to stablecoin-core logs: {}", err))?;
+
+    if ctx_ref.output == OutputFormat::Text {
+        println!("Watching events of {} (Ctrl-C to stop)", config_pda);
+    }
+
+    while !stop.load(Ordering::SeqCst) {
+        let update = match log_updates.recv_timeout(Duration::from_millis(500)) {
+            Ok(update) => update,
+            Err(err) if err.is_timeout() => continue,
+            Err(_) => return Err(anyhow!("Log subscription closed")),
+        };
+        if update.value.err.is_some() {
+            continue;
+        }
+        for event in config_events(&update.value.logs, &config_pda, action) {
+            let entry = AuditLogEntry {
+                signature: update.value.signature.clone(),
+                slot: update.context.slot,
+                timestamp: format_unix_timestamp(event.timestamp),
+                action: event.action.to_string(),
+                event: event.name.to_string(),
+                data: serde_json::Value::Object(event.fields),
+            };
+            if ctx_ref.output == OutputFormat::Json {
+                println!("{}", serde_json::to_string(&entry)?);
+            } else {
+                println!(
+                    "{} {:<18} {} {}",
+                    entry.timestamp,
+                    entry.action,
+                    entry.signature,
+                    summarize_event_data(&entry.data)
+                );
+            }
+        }
+    }
+    Ok(())
+}
+
+/// The core events in `logs` that belong to `config_pda`, optionally narrowed
+/// to one action; the logs subscription sees every mint's transactions.
+fn config_events(logs: &[String], config_pda: &Pubkey, action: Option<&str>) -> Vec<DecodedEvent> {
+    let config = config_pda.to_string();
+    decode_program_events(logs, &stablecoin_core::ID)
+        .into_iter()
+        .filter(|event| event.config() == Some(config.as_str()))
+        .filter(|event| action.is_none_or(|action| event.matches_action(action)))
+        .collect()
+}
+
+fn watch_balance(
+    ctx: &OwnedContext,
+    mint: &Option<String>,
+    owner: &str,
+    stop: &AtomicBool,
+) -> Result<()> {
+    let ctx_ref = ctx.as_ref();
+    let mint = resolve_mint(mint)?;
     let config_pda = find_config_pda(&mint, &stablecoin_core::ID).0;
     let config = fetch_config(ctx_ref, &config_pda)?;
-    let amount = parse_amount(&args.amount, config.decimals)?;
-    let recipient = parse_pubkey(&args.recipient)?;
-    let recipient_ata =
-        get_associated_token_address_with_program_id(&recipient, &mint, &spl_token_2022::id());
-    let mint_ix = build_mint_instruction(MintParams {
-        minter: ctx_ref.payer.pubkey(),
-        mint,
-        recipient,
-        recipient_ata,
-        amount,
-    })?;
-    let signature = send_transaction(ctx_ref, vec![mint_ix], vec![])?;
-    let supply = ctx_ref.client.get_token_supply(&mint)?;
-    let explorer = explorer_url(&signature, ctx_ref.cluster);
-    if ctx_ref.output == OutputFormat::Json {
-        let output = MintOutput {
-            signature: signature.clone(),
-            explorer,
-            new_supply: supply.amount,
+    let owner = parse_pubkey(owner)?;
+    let token_account =
+        get_associated_token_address_with_program_id(&owner, &mint, &config.token_program_id());
+    let mut balance = fetch_token_account(ctx_ref, &token_account)
+        .map(|info| info.amount)
+        .unwrap_or(0);
+
+    let ws_url = websocket_url(&ctx_ref.cluster.url);
+    let (_account_subscription, account_updates) = PubsubClient::account_subscribe(
+        &ws_url,
+        &token_account,
+        Some(RpcAccountInfoConfig {
+            encoding: Some(UiAccountEncoding::Base64),
+            commitment: Some(ctx_ref.commitment),
+            data_slice: None,
+            min_context_slot: None,
+        }),
+    )
+    .map_err(|err| anyhow!("Failed to subscribe to {}: {}", token_account, err))?;
+    let (_logs_subscription, log_updates) = PubsubClient::logs_subscribe(
+        &ws_url,
+        RpcTransactionLogsFilter::Mentions(vec![token_account.to_string()]),
+        RpcTransactionLogsConfig {
+            commitment: Some(ctx_ref.commitment),
+        },
+    )
+    .map_err(|err| anyhow!("Failed to subscribe to logs for {}: {}", token_account, err))?;
+
+    if ctx_ref.output == OutputFormat::Text {
+        println!("Watching {} (owner {})", token_account, owner);
+        println!("Balance: {}", format_amount(balance, config.decimals));
+    }
+
+    let mut recent_logs: VecDeque<WatchedLogs> = VecDeque::new();
+    while !stop.load(Ordering::SeqCst) {
+        let update = match account_updates.recv_timeout(Duration::from_millis(500)) {
+            Ok(update) => update,
+            Err(err) if err.is_timeout() => continue,
+            Err(_) => return Err(anyhow!("Account subscription closed")),
+        };
+        let Some(data) = update.value.data.decode() else {
+            continue;
+        };
+        let new_balance = StateWithExtensions::<TokenAccount2022>::unpack(&data)
+            .map(|parsed| parsed.base.amount)
+            .unwrap_or(0);
+        if new_balance == balance {
+            continue;
+        }
+        let delta = i128::from(new_balance) - i128::from(balance);
+        balance = new_balance;
+
+        let slot = update.context.slot;
+        let deadline = Instant::now() + WATCH_LOG_WAIT;
+        let matched = loop {
+            while let Ok(entry) = log_updates.try_recv() {
+                recent_logs.push_back(WatchedLogs {
+                    slot: entry.context.slot,
+                    signature: entry.value.signature,
+                    failed: entry.value.err.is_some(),
+                    logs: entry.value.logs,
+                });
+                if recent_logs.len() > WATCH_LOG_HISTORY {
+                    recent_logs.pop_front();
+                }
+            }
+            let position = recent_logs
+                .iter()
+                .position(|entry| entry.slot == slot && !entry.failed);
+            if let Some(position) = position {
+                break recent_logs.remove(position);
+            }
+            if Instant::now() >= deadline {
+                break None;
+            }
+            std::thread::sleep(Duration::from_millis(100));
+        };
+
+        let cause = classify_balance_change(
+            delta,
+            matched
+                .as_ref()
+                .map(|entry| entry.logs.as_slice())
+                .unwrap_or(&[]),
+        );
+        let output = BalanceChangeOutput {
+            slot,
+            timestamp: Utc::now().to_rfc3339(),
+            balance: new_balance,
+            delta: delta.to_string(),
+            cause: cause.to_string(),
+            signature: matched.map(|entry| entry.signature),
+        };
+        if ctx_ref.output == OutputFormat::Json {
+            println!("{}", serde_json::to_string(&output)?);
+        } else {
+            let sign = if delta > 0 { "+" } else { "-" };
+            println!(
+                "[{}] slot {} balance {} ({}{}) {}{}",
+                output.timestamp,
+                slot,
+                format_amount(new_balance, config.decimals),
+                sign,
+                format_amount(delta.unsigned_abs() as u64, config.decimals),
+                cause,
+                output
+                    .signature
+                    .as_ref()
+                    .map(|signature| format!(" {}", signature))
+                    .unwrap_or_default()
+            );
+        }
+    }
+    Ok(())
+}
+
+struct WatchedLogs {
+    slot: u64,
+    signature: String,
+    failed: bool,
+    logs: Vec<String>,
+}
+
+/// Best-effort cause of a balance change from the logs of the transaction that made it.
+fn classify_balance_change(delta: i128, logs: &[String]) -> &'static str {
+    let has = |instruction: &str| {
+        let needle = format!("Program log: Instruction: {}", instruction);
+        logs.iter().any(|line| line == &needle)
+    };
+    if logs.is_empty() {
+        "unknown"
+    } else if has("Seize") {
+        if delta < 0 {
+            "seized"
+        } else {
+            "seize proceeds"
+        }
+    } else if delta > 0 && (has("Mint") || has("MintBatch") || has("MintTo")) {
+        "mint received"
+    } else if delta < 0 && has("Burn") {
+        "burn"
+    } else if has("TransferChecked") || has("Transfer") {
+        if delta > 0 {
+            "transfer in"
+        } else {
+            "transfer out"
+        }
+    } else {
+        "unknown"
+    }
+}
+
+/// Pubsub endpoint for an RPC URL, following the solana CLI convention of
+/// swapping the scheme and using port + 1 when a port is given.
+fn websocket_url(rpc_url: &str) -> String {
+    let (scheme, rest) = if let Some(rest) = rpc_url.strip_prefix("https://") {
+        ("wss://", rest)
+    } else if let Some(rest) = rpc_url.strip_prefix("http://") {
+        ("ws://", rest)
+    } else {
+        return rpc_url.to_string();
+    };
+    let (host, path) = match rest.find('/') {
+        Some(index) => rest.split_at(index),
+        None => (rest, ""),
+    };
+    let host = match host.rsplit_once(':') {
+        Some((name, port)) => match port.parse::<u16>() {
+            Ok(port) => format!("{}:{}", name, port.saturating_add(1)),
+            Err(_) => host.to_string(),
+        },
+        None => host.to_string(),
+    };
+    format!("{}{}{}", scheme, host, path)
+}
+
+fn handle_export_idl(args: &ExportIdlArgs) -> Result<()> {
+    let descriptor = hook_descriptor(args.with_types);
+    let json = serde_json::to_string_pretty(&descriptor)?;
+    if let Some(path) = &args.out {
+        fs::write(path, json).with_context(|| format!("Failed to write {}", path.display()))?;
+    } else {
+        println!("{}", json);
+    }
+    Ok(())
+}
+
+fn hook_descriptor(with_types: bool) -> HookDescriptorOutput {
+    use transfer_hook::layout;
+
+    let instructions = layout::instructions()
+        .iter()
+        .map(|instruction| HookInstructionOutput {
+            name: instruction.name.to_string(),
+            discriminator: instruction.discriminator.to_vec(),
+            accounts: instruction
+                .accounts
+                .iter()
+                .enumerate()
+                .map(|(index, account)| HookAccountOutput {
+                    index,
+                    name: account.name.to_string(),
+                    writable: account.writable,
+                    signer: account.signer,
+                })
+                .collect(),
+        })
+        .collect();
+    let account_types = with_types.then(|| {
+        layout::ACCOUNT_TYPES
+            .iter()
+            .map(|account| HookAccountTypeOutput {
+                name: account.name.to_string(),
+                owner: stablecoin_core::ID.to_string(),
+                discriminator: account_discriminator(account.name).to_vec(),
+                fields: account
+                    .fields
+                    .iter()
+                    .map(|field| HookFieldOutput {
+                        name: field.name.to_string(),
+                        ty: field.ty.to_string(),
+                    })
+                    .collect(),
+            })
+            .collect()
+    });
+    HookDescriptorOutput {
+        program_id: transfer_hook::ID.to_string(),
+        extra_account_metas_seeds: vec!["extra-account-metas".to_string(), "mint".to_string()],
+        instructions,
+        account_types,
+    }
+}
+
+#[derive(Debug, Clone, Default, Deserialize)]
+#[serde(deny_unknown_fields)]
+struct SssConfig {
+    token: TokenConfig,
+    extensions: Option<ExtensionsConfig>,
+    roles: Option<RolesConfig>,
+    network: Option<NetworkConfig>,
+}
+
+#[derive(Debug, Clone, Default, Deserialize)]
+#[serde(deny_unknown_fields)]
+struct TokenConfig {
+    name: String,
+    symbol: String,
+    decimals: Option<u8>,
+    uri: Option<String>,
+    max_supply: Option<String>,
+    /// Wallet owning the treasury token account.
+    treasury: Option<String>,
+    /// token2022 (default) or legacy.
+    token_program: Option<TokenProgramKind>,
+}
+
+#[derive(Debug, Clone, Deserialize)]
+#[serde(deny_unknown_fields)]
+struct ExtensionsConfig {
+    permanent_delegate: Option<bool>,
+    transfer_hook: Option<bool>,
+    default_account_frozen: Option<bool>,
+    confidential_transfer: Option<bool>,
+    metadata: Option<bool>,
+    allowlist: Option<bool>,
+    /// Per-transfer cap in token units, enforced by the transfer hook.
+    max_transfer_amount: Option<String>,
+    /// Minimum seconds between two transfers sent by one wallet, enforced by
+    /// the transfer hook.
+    transfer_cooldown_seconds: Option<i64>,
+    /// Without transfer_hook, adds an empty TransferHook extension so the
+    /// hook can be enabled after initialize.
+    reserve_transfer_hook: Option<bool>,
+}
+
+impl Default for ExtensionsConfig {
+    fn default() -> Self {
+        Self {
+            permanent_delegate: Some(false),
+            transfer_hook: Some(false),
+            default_account_frozen: Some(false),
+            confidential_transfer: Some(false),
+            metadata: Some(true),
+            allowlist: Some(false),
+            max_transfer_amount: None,
+            transfer_cooldown_seconds: None,
+            reserve_transfer_hook: Some(false),
+        }
+    }
+}
+
+impl ExtensionsConfig {
+    fn from_preset(enable_transfer_hook: bool) -> Self {
+        Self {
+            permanent_delegate: Some(enable_transfer_hook),
+            transfer_hook: Some(enable_transfer_hook),
+            default_account_frozen: Some(false),
+            confidential_transfer: Some(false),
+            metadata: Some(true),
+            allowlist: Some(false),
+            max_transfer_amount: None,
+            transfer_cooldown_seconds: None,
+            reserve_transfer_hook: Some(false),
+        }
+    }
+}
+
+#[derive(Debug, Clone, Default, Deserialize)]
+#[serde(deny_unknown_fields)]
+struct RolesConfig {
+    minters: Option<Vec<MinterConfig>>,
+    freezers: Option<Vec<String>>,
+    pausers: Option<Vec<String>>,
+    blacklisters: Option<Vec<String>>,
+    seizers: Option<Vec<String>>,
+    burners: Option<Vec<String>>,
+}
+
+#[derive(Debug, Clone, Deserialize)]
+#[serde(deny_unknown_fields)]
+struct MinterConfig {
+    pubkey: String,
+    quota: u64,
+}
+
+#[derive(Debug, Clone, Deserialize)]
+#[serde(deny_unknown_fields)]
+struct NetworkConfig {
+    cluster: Option<String>,
+    keypair_path: Option<String>,
+    commitment: Option<String>,
+    /// Same format as --rpc-header; the flag wins for a repeated name.
+    rpc_headers: Option<Vec<String>>,
+}
+
+#[derive(Debug, Clone, Deserialize)]
+struct SolanaCliConfig {
+    json_rpc_url: String,
+    keypair_path: String,
+    commitment: Option<String>,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+struct RoleAssignment {
+    roles: u8,
+    mint_quota: Option<u64>,
+}
+
+fn build_role_assignments(config: &RolesConfig) -> Result<HashMap<Pubkey, RoleAssignment>> {
+    let mut assignments = HashMap::new();
+
+    if let Some(minters) = &config.minters {
+        for entry in minters {
+            let pubkey = parse_pubkey(&entry.pubkey)?;
+            let assignment = assignments.entry(pubkey).or_insert(RoleAssignment {
+                roles: 0,
+                mint_quota: None,
+            });
+            assignment.roles |= ROLE_MINTER;
+            assignment.mint_quota = Some(entry.quota);
+        }
+    }
+
+    apply_role_list(&mut assignments, config.freezers.as_ref(), ROLE_FREEZER)?;
+    apply_role_list(&mut assignments, config.pausers.as_ref(), ROLE_PAUSER)?;
+    apply_role_list(
+        &mut assignments,
+        config.blacklisters.as_ref(),
+        ROLE_BLACKLISTER,
+    )?;
+    apply_role_list(&mut assignments, config.seizers.as_ref(), ROLE_SEIZER)?;
+    apply_role_list(&mut assignments, config.burners.as_ref(), ROLE_BURNER)?;
+
+    Ok(assignments)
+}
+
+fn apply_role_list(
+    assignments: &mut HashMap<Pubkey, RoleAssignment>,
+    list: Option<&Vec<String>>,
+    role: u8,
+) -> Result<()> {
+    if let Some(list) = list {
+        for entry in list {
+            let pubkey = parse_pubkey(entry)?;
+            let assignment = assignments.entry(pubkey).or_insert(RoleAssignment {
+                roles: 0,
+                mint_quota: None,
+            });
+            assignment.roles |= role;
+        }
+    }
+    Ok(())
+}
+
+fn load_sss_config(path: &str) -> Result<SssConfig> {
+    let contents = fs::read_to_string(expand_tilde(path))
+        .with_context(|| format!("Failed to read config: {}", path))?;
+    let mut config =
+        parse_sss_config(&contents, ConfigFormat::detect(path, &contents)).with_context(|| {
+            format!(
+                "Failed to parse config {} (sss-token config-check --config {} lists every problem)",
+                path, path
+            )
+        })?;
+    if let Some(roles) = config.roles.as_mut() {
+        resolve_role_aliases(roles, parse_pubkey)
+            .with_context(|| format!("Failed to resolve roles in config {}", path))?;
+    }
+    Ok(config)
+}
+
+/// Replaces alias names in the role lists with the pubkeys they resolve to,
+/// so everything after loading sees base58 only.
+fn resolve_role_aliases(
+    roles: &mut RolesConfig,
+    resolve: impl Fn(&str) -> Result<Pubkey>,
+) -> Result<()> {
+    let resolve_in_place = |value: &mut String| -> Result<()> {
+        *value = resolve(value)?.to_string();
+        Ok(())
+    };
+    for minter in roles.minters.iter_mut().flatten() {
+        resolve_in_place(&mut minter.pubkey)?;
+    }
+    for list in [
+        &mut roles.freezers,
+        &mut roles.pausers,
+        &mut roles.blacklisters,
+        &mut roles.seizers,
+        &mut roles.burners,
+    ] {
+        for value in list.iter_mut().flatten() {
+            resolve_in_place(value)?;
+        }
+    }
+    Ok(())
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum ConfigFormat {
+    Toml,
+    Json,
+}
+
+impl ConfigFormat {
+    /// `.json` and `.toml` decide by extension; any other file is JSON when
+    /// it starts with `{` and TOML otherwise.
+    fn detect(path: &str, contents: &str) -> Self {
+        match Path::new(path)
+            .extension()
+            .and_then(|ext| ext.to_str())
+            .map(str::to_ascii_lowercase)
+            .as_deref()
+        {
+            Some("json") => Self::Json,
+            Some("toml") => Self::Toml,
+            _ if contents.trim_start().starts_with('{') => Self::Json,
+            _ => Self::Toml,
+        }
+    }
+}
+
+/// Parses an init config. Unknown keys are errors rather than ignored, so a
+/// misspelled or misplaced setting cannot silently fall back to its default;
+/// the message names the line.
+fn parse_sss_config(contents: &str, format: ConfigFormat) -> Result<SssConfig> {
+    match format {
+        ConfigFormat::Toml => toml::from_str(contents).map_err(|err| match err.span() {
+            Some(span) => anyhow!(
+                "line {}: {}",
+                contents[..span.start].matches('\n').count() + 1,
+                err.message()
+            ),
+            None => anyhow!("{}", err.message()),
+        }),
+        // serde_json already ends its messages with the line and column.
+        ConfigFormat::Json => serde_json::from_str(contents).map_err(|err| anyhow!("{}", err)),
+    }
+}
+
+fn handle_alias(output: OutputFormat, cmd: &AliasCmd) -> Result<()> {
+    let path = aliases::default_path();
+    let mut book = aliases::AliasBook::load(&path)?;
+    match cmd {
+        AliasCmd::Add(args) => {
+            let pubkey = book.insert(&args.name, &args.pubkey)?;
+            book.save(&path)?;
+            if output == OutputFormat::Json {
+                print_json(&AliasOutput {
+                    name: args.name.clone(),
+                    target: args.pubkey.clone(),
+                    pubkey: Some(pubkey.to_string()),
+                    error: None,
+                })
+            } else {
+                println!("Alias {} -> {}", args.name, pubkey);
+                println!("Saved to {}", path.display());
+                Ok(())
+            }
+        }
+        AliasCmd::List => {
+            let listing = AliasListOutput {
+                path: path.display().to_string(),
+                aliases: alias_entries(&book),
+            };
+            if output == OutputFormat::Json {
+                print_json(&listing)
+            } else {
+                if listing.aliases.is_empty() {
+                    println!("No aliases in {}", listing.path);
+                }
+                for alias in &listing.aliases {
+                    match (&alias.pubkey, &alias.error) {
+                        (Some(pubkey), _) if *pubkey == alias.target => {
+                            println!("{} -> {}", alias.name, pubkey)
+                        }
+                        (Some(pubkey), _) => {
+                            println!("{} -> {} ({})", alias.name, alias.target, pubkey)
+                        }
+                        (None, error) => println!(
+                            "{} -> {} (unresolved: {})",
+                            alias.name,
+                            alias.target,
+                            error.as_deref().unwrap_or_default()
+                        ),
+                    }
+                }
+                Ok(())
+            }
+        }
+    }
+}
+
+/// Every alias with what it resolves to; a hand-edited file may hold
+/// broken ones, which are listed with the reason instead of failing.
+fn alias_entries(book: &aliases::AliasBook) -> Vec<AliasOutput> {
+    book.entries()
+        .map(|(name, target)| {
+            let resolved = book.resolve(name);
+            AliasOutput {
+                name: name.to_string(),
+                target: target.to_string(),
+                pubkey: resolved.as_ref().ok().map(Pubkey::to_string),
+                error: resolved.err().map(|err| err.to_string()),
+            }
+        })
+        .collect()
+}
+
+fn handle_config_check(output: OutputFormat, args: &ConfigCheckArgs) -> Result<()> {
+    let contents = fs::read_to_string(expand_tilde(&args.config))
+        .with_context(|| format!("Failed to read config: {}", args.config))?;
+    let report = check_sss_config(&args.config, &contents);
+    if output == OutputFormat::Json {
+        print_json(&report)?;
+    } else {
+        for error in &report.errors {
+            println!("error: {}", error);
+        }
+        for warning in &report.warnings {
+            println!("warning: {}", warning);
+        }
+        if report.errors.is_empty() {
+            println!(
+                "{}: OK ({} warning(s))",
+                report.config,
+                report.warnings.len()
+            );
+        }
+    }
+    if report.errors.is_empty() {
+        Ok(())
+    } else {
+        Err(anyhow!(
+            "{}: {} error(s) found",
+            report.config,
+            report.errors.len()
+        ))
+    }
+}
+
+/// Everything init would reject in the file, plus combinations it accepts
+/// but that are probably not meant. Nothing here reads the chain.
+fn check_sss_config(path: &str, contents: &str) -> ConfigCheckOutput {
+    let mut report = ConfigCheckOutput {
+        config: path.to_string(),
+        errors: Vec::new(),
+        warnings: Vec::new(),
+    };
+    let config = match parse_sss_config(contents, ConfigFormat::detect(path, contents)) {
+        Ok(config) => config,
+        Err(err) => {
+            report.errors.push(err.to_string());
+            return report;
+        }
+    };
+    let errors = &mut report.errors;
+    let warnings = &mut report.warnings;
+
+    let token = &config.token;
+    for (field, value, max) in [
+        ("name", &token.name, MAX_NAME_LEN),
+        ("symbol", &token.symbol, MAX_SYMBOL_LEN),
+        (
+            "uri",
+            token.uri.as_ref().unwrap_or(&String::new()),
+            MAX_URI_LEN,
+        ),
+    ] {
+        if value.len() > max {
+            errors.push(format!(
+                "token.{} is {} bytes; at most {} fit",
+                field,
+                value.len(),
+                max
+            ));
+        }
+    }
+    let decimals = token.decimals.unwrap_or(6);
+    if let Err(err) = check_decimals(decimals) {
+        errors.push(format!("token.decimals: {}", err));
+    }
+    if let Some(Err(err)) = token
+        .max_supply
+        .as_deref()
+        .map(|value| parse_amount(value, decimals))
+    {
+        errors.push(format!("token.max_supply: {}", err));
+    }
+    if let Some(Err(err)) = token.treasury.as_deref().map(parse_pubkey) {
+        errors.push(format!("token.treasury: {}", err));
+    }
+
+    let extensions = config.extensions.clone().unwrap_or_default();
+    let transfer_hook = extensions.transfer_hook.unwrap_or(false);
+    if token.token_program == Some(TokenProgramKind::Legacy) {
+        if let Err(err) = check_legacy_mint(
+            extensions.metadata.unwrap_or(true),
+            extensions.permanent_delegate.unwrap_or(false),
+            transfer_hook || extensions.reserve_transfer_hook.unwrap_or(false),
+            extensions.default_account_frozen.unwrap_or(false),
+            extensions.confidential_transfer.unwrap_or(false),
+        ) {
+            errors.push(format!("token.token_program: {}", err));
+        }
+    }
+    if extensions.allowlist.unwrap_or(false) && !transfer_hook {
+        errors.push("extensions.allowlist requires transfer_hook (SSS-2)".to_string());
+    }
+    if let Some(value) = extensions.max_transfer_amount.as_deref() {
+        if let Err(err) = parse_transfer_limit(value, decimals) {
+            errors.push(format!("extensions.max_transfer_amount: {}", err));
+        } else if !transfer_hook {
+            errors
+                .push("extensions.max_transfer_amount requires transfer_hook (SSS-2)".to_string());
+        }
+    }
+    if let Some(Err(err)) = extensions
+        .transfer_cooldown_seconds
+        .map(|seconds| check_transfer_cooldown(seconds, transfer_hook))
+    {
+        errors.push(format!("extensions.transfer_cooldown_seconds: {}", err));
+    }
+    if transfer_hook && !extensions.permanent_delegate.unwrap_or(false) {
+        warnings.push(
+            "extensions.transfer_hook without permanent_delegate: blacklisted balances cannot be seized"
+                .to_string(),
+        );
+    }
+    if extensions.permanent_delegate.unwrap_or(false) && !transfer_hook {
+        warnings.push(
+            "extensions.permanent_delegate without transfer_hook: seize needs a blacklist, which needs the hook"
+                .to_string(),
+        );
+    }
+
+    let roles = config.roles.clone().unwrap_or_default();
+    let minters = roles.minters.as_deref().unwrap_or_default();
+    for minter in minters {
+        if let Err(err) = parse_pubkey(&minter.pubkey) {
+            errors.push(format!("roles.minters: {}", err));
+        } else if minter.quota == 0 {
+            warnings.push(format!(
+                "roles.minters: {} has a zero quota and cannot mint",
+                minter.pubkey
+            ));
+        }
+    }
+    let lists = [
+        ("freezers", &roles.freezers),
+        ("pausers", &roles.pausers),
+        ("blacklisters", &roles.blacklisters),
+        ("seizers", &roles.seizers),
+        ("burners", &roles.burners),
+    ];
+    for (name, list) in lists {
+        let list = list.as_deref().unwrap_or_default();
+        let mut seen = Vec::new();
+        for entry in list {
+            match parse_pubkey(entry) {
+                Ok(pubkey) if seen.contains(&pubkey) => {
+                    warnings.push(format!("roles.{}: {} is listed twice", name, entry))
+                }
+                Ok(pubkey) => seen.push(pubkey),
+                Err(err) => errors.push(format!("roles.{}: {}", name, err)),
+            }
+        }
+        // update_roles rejects compliance roles on mints without the hook.
+        if !list.is_empty() && !transfer_hook && matches!(name, "blacklisters" | "seizers") {
+            errors.push(format!(
+                "roles.{} requires extensions.transfer_hook (SSS-2)",
+                name
+            ));
+        }
+    }
+
+    if let Some(network) = &config.network {
+        if let Some(Err(err)) = network.cluster.as_deref().map(resolve_cluster) {
+            errors.push(format!("network.cluster: {}", err));
+        }
+        if let Some(commitment) = network.commitment.as_deref() {
+            if !matches!(commitment, "processed" | "confirmed" | "finalized") {
+                warnings.push(format!(
+                    "network.commitment: unknown level {}, confirmed is used",
+                    commitment
+                ));
+            }
+        }
+        if let Some(Err(err)) = network.rpc_headers.as_ref().map(parse_rpc_headers) {
+            errors.push(format!("network.rpc_headers: {}", err));
+        }
+    }
+    report
+}
+
+fn load_solana_cli_config() -> Result<SolanaCliConfig> {
+    let path = default_solana_config_path();
+    let contents = fs::read_to_string(&path)
+        .with_context(|| format!("Failed to read Solana config: {}", path.display()))?;
+    serde_yaml::from_str(&contents).context("Failed to parse Solana config")
+}
+
+fn default_solana_config_path() -> PathBuf {
+    let mut path = dirs::home_dir().unwrap_or_else(|| PathBuf::from("."));
+    path.push(".config");
+    path.push("solana");
+    path.push("cli");
+    path.push("config.yml");
+    path
+}
+
+fn resolve_cluster(input: &str) -> Result<ClusterInfo> {
+    let lowered = input.to_lowercase();
+    let (url, label) = match lowered.as_str() {
+        "devnet" => (
+            "https://api.devnet.solana.com".to_string(),
+            Some("devnet".to_string()),
+        ),
+        "testnet" => (
+            "https://api.testnet.solana.com".to_string(),
+            Some("testnet".to_string()),
+        ),
+        "mainnet" | "mainnet-beta" => (
+            "https://api.mainnet-beta.solana.com".to_string(),
+            Some("mainnet-beta".to_string()),
+        ),
+        "localnet" => (
+            "http://127.0.0.1:8899".to_string(),
+            Some("localnet".to_string()),
+        ),
+        _ => {
+            if input.starts_with("http://") || input.starts_with("https://") {
+                let label = if lowered.contains("devnet") {
+                    Some("devnet".to_string())
+                } else if lowered.contains("testnet") {
+                    Some("testnet".to_string())
+                } else if lowered.contains("mainnet") {
+                    Some("mainnet-beta".to_string())
+                } else {
+                    None
+                };
+                (input.to_string(), label)
+            } else {
+                return Err(anyhow!("Unknown cluster: {}", input));
+            }
+        }
+    };
+    Ok(ClusterInfo { url, label })
+}
+
+fn parse_commitment(value: Option<&str>) -> CommitmentConfig {
+    match value.unwrap_or("confirmed") {
+        "processed" => CommitmentConfig::processed(),
+        "finalized" => CommitmentConfig::finalized(),
+        _ => CommitmentConfig::confirmed(),
+    }
+}
+
+fn expand_tilde(path: &str) -> PathBuf {
+    if let Some(stripped) = path.strip_prefix("~/").or_else(|| path.strip_prefix("~\\")) {
+        if let Some(home) = dirs::home_dir() {
+            return home.join(stripped);
+        }
+    }
+    PathBuf::from(path)
+}
+
+/// Reads a base58 pubkey or, failing that, a name from the address book.
+fn parse_pubkey(value: &str) -> Result<Pubkey> {
+    Pubkey::from_str(value).or_else(|_| aliases::lookup(value))
+}
+
+fn resolve_mint(mint: &Option<String>) -> Result<Pubkey> {
+    let value = mint.as_deref().ok_or_else(|| anyhow!("Missing --mint"))?;
+    parse_pubkey(value)
+}
+
+/// Parses an amount into base units. A `u` suffix (`1000u`) always reads the
+/// value in UI units and a `base` suffix (`1000base`) always in base units;
+/// unsuffixed values are UI units only when they contain a decimal point.
+fn parse_amount(value: &str, decimals: u8) -> Result<u64> {
+    let sanitized = value.trim().replace('_', "");
+    if let Some(raw) = sanitized.strip_suffix("base") {
+        if raw.contains('.') {
+            return Err(anyhow!("Base-unit amounts cannot have decimal places"));
+        }
+        return Ok(raw.parse()?);
+    }
+    if let Some(ui) = sanitized.strip_suffix('u') {
+        return parse_ui_amount(ui, decimals);
+    }
+    if sanitized.contains('.') {
+        parse_ui_amount(&sanitized, decimals)
+    } else {
+        Ok(sanitized.parse()?)
+    }
+}
+
+/// Parses an amount given in base units; decimal points are rejected rather
+/// than scaled.
+fn parse_raw_amount(value: &str) -> Result<u64> {
+    let sanitized = value.trim().replace('_', "");
+    if sanitized.contains('.') {
+        return Err(anyhow!(
+            "Raw amounts are base units and cannot have decimal places: {}",
+            value
+        ));
+    }
+    sanitized
+        .parse()
+        .with_context(|| format!("Invalid raw amount: {}", value))
+}
+
+fn parse_ui_amount(value: &str, decimals: u8) -> Result<u64> {
+    if value.is_empty() {
+        return Err(anyhow!("Missing amount"));
+    }
+    let (whole, fractional) = value.split_once('.').unwrap_or((value, ""));
+    let whole_value: u64 = if whole.is_empty() { 0 } else { whole.parse()? };
+    let mut fraction = fractional.to_string();
+    if fraction.len() > decimals as usize {
+        return Err(anyhow!("Too many decimal places"));
+    }
+    while fraction.len() < decimals as usize {
+        fraction.push('0');
+    }
+    let fractional_value: u64 = if fraction.is_empty() {
+        0
+    } else {
+        fraction.parse()?
+    };
+    let scale = 10u64
+        .checked_pow(decimals as u32)
+        .ok_or_else(|| anyhow!("Decimal overflow"))?;
+    let total = whole_value
+        .checked_mul(scale)
+        .and_then(|value| value.checked_add(fractional_value))
+        .ok_or_else(|| anyhow!("Amount overflow"))?;
+    Ok(total)
+}
+
+fn minter_info(role: &RoleAccount, decimals: u8) -> MinterInfo {
+    MinterInfo {
+        address: role.authority.to_string(),
+        quota: role.mint_quota.map(|quota| quota.to_string()),
+        quota_ui: role.mint_quota.map(|quota| format_amount(quota, decimals)),
+        lifetime_minted: role.lifetime_minted.to_string(),
+        lifetime_minted_ui: format_amount(role.lifetime_minted, decimals),
+    }
+}
+
+/// What the minter has minted in the quota window still running at `now`;
+/// an expired window counts as empty, as the program rolls it on next mint.
+fn current_window_minted(role: &RoleAccount, now: i64, window_seconds: i64) -> u64 {
+    if role.window_start == 0 || now.saturating_sub(role.window_start) >= window_seconds {
+        0
+    } else {
+        role.minted_current_window
+    }
+}
+
+/// What the minter may still mint in the window running at `now`, if it has
+/// a quota; a window that has since expired leaves the whole quota.
+fn window_quota_remaining(role: &RoleAccount, now: i64, window_seconds: i64) -> Option<u64> {
+    role.mint_quota
+        .map(|quota| quota.saturating_sub(current_window_minted(role, now, window_seconds)))
+}
+
+/// What the minter may still mint under its lifetime quota, if it has one.
+fn lifetime_remaining(role: &RoleAccount) -> Option<u64> {
+    role.lifetime_quota
+        .map(|quota| quota.saturating_sub(role.lifetime_minted))
+}
+
+fn format_amount(amount: u64, decimals: u8) -> String {
+    if decimals == 0 {
+        return amount.to_string();
+    }
+    let scale = 10u64.pow(decimals as u32);
+    let whole = amount / scale;
+    let frac = amount % scale;
+    format!("{}.{:0width$}", whole, frac, width = decimals as usize)
+}
+
+fn explorer_url(signature: &str, cluster: &ClusterInfo) -> Option<String> {
+    cluster.label.as_ref().map(|label| {
+        format!(
+            "https://explorer.solana.com/tx/{}?cluster={}",
+            signature, label
+        )
+    })
+}
+
+/// Signs and sends the instructions, returning the signature. With an
+/// --authority override nothing is sent: the instructions are printed for
+/// submission to the multisig program and `None` is returned.
+fn send_transaction(
+    ctx: AppContext<'_>,
+    instructions: Vec<Instruction>,
+    extra_signers: Vec<&Keypair>,
+) -> Result<Option<String>> {
+    if let Some(path) = ctx.export_tx {
+        let instructions = [ctx.compute_budget.instructions(), instructions].concat();
+        let mut transaction = Transaction::new_with_payer(&instructions, Some(&ctx.payer.pubkey()));
+        transaction.message.recent_blockhash = latest_blockhash(ctx)?;
+        // Throwaway keys generated for this run (e.g. a new mint) sign here,
+        // since nobody else holds them; the payer and authority sign offline.
+        let local: Vec<&dyn Signer> = extra_signers
+            .into_iter()
+            .filter(|signer| {
+                signer.pubkey() != ctx.payer.pubkey() && signer.pubkey() != ctx.authority()
+            })
+            .map(|signer| signer as &dyn Signer)
+            .collect();
+        if !local.is_empty() {
+            let blockhash = transaction.message.recent_blockhash;
+            transaction.try_partial_sign(&local, blockhash)?;
+        }
+        export_transaction(ctx, path, &transaction)?;
+        return Ok(None);
+    }
+    if ctx.simulate {
+        let instructions = [ctx.compute_budget.instructions(), instructions].concat();
+        simulate_transaction(ctx, &instructions)?;
+        return Ok(None);
+    }
+    if let Some(authority) = ctx.authority_override {
+        print_multisig_proposal(ctx, &authority, &instructions)?;
+        return Ok(None);
+    }
+    let instructions = [ctx.compute_budget.instructions(), instructions].concat();
+    let mut transaction = Transaction::new_with_payer(&instructions, Some(&ctx.payer.pubkey()));
+    let mut signers: Vec<&dyn Signer> = vec![ctx.payer];
+    for signer in extra_signers {
+        if signer.pubkey() != ctx.payer.pubkey() {
+            signers.push(signer);
+        }
+    }
+    transaction.sign(&signers, latest_blockhash(ctx)?);
+    submit_transaction(ctx, &mut transaction, Some(&signers)).map(Some)
+}
+
+/// Sends and confirms `transaction`, retrying transient RPC failures. With
+/// `signers` it is re-signed with a fresh blockhash once the old one expires;
+/// a transaction signed elsewhere can only be resent as is.
+fn submit_transaction(
+    ctx: AppContext<'_>,
+    transaction: &mut Transaction,
+    signers: Option<&[&dyn Signer]>,
+) -> Result<String> {
+    let mut attempt = 1;
+    loop {
+        let err = match ctx.client.send_and_confirm_transaction(&*transaction) {
+            Ok(signature) => return Ok(signature.to_string()),
+            Err(err) if attempt < ctx.retry.max_attempts && is_transient_rpc_error(&err) => err,
+            Err(err) => return Err(explain_program_error(err, &transaction.message)),
+        };
+        warn_retry(ctx, attempt, &err);
+        std::thread::sleep(ctx.retry.delay(attempt));
+        attempt += 1;
+
+        // The failed attempt may still have landed or be about to. Only
+        // re-sign with a fresh blockhash once the old one has expired;
+        // until then resend the same transaction, which cannot execute twice.
+        let signature = transaction.signatures[0];
+        if let Ok(Some(status)) = ctx.client.get_signature_status(&signature) {
+            if let Err(err) = status {
+                return Err(explain_program_error(err.into(), &transaction.message));
+            }
+            return Ok(signature.to_string());
+        }
+        let Some(signers) = signers else {
+            continue;
+        };
+        let expired = ctx
+            .client
+            .is_blockhash_valid(
+                &transaction.message.recent_blockhash,
+                CommitmentConfig::processed(),
+            )
+            .is_ok_and(|valid| !valid);
+        if expired {
+            transaction.sign(signers, latest_blockhash(ctx)?);
+        }
+    }
+}
+
+/// Adds the name and message of a stablecoin-core or transfer-hook custom
+/// error, which the RPC only reports by number.
+fn explain_program_error(err: ClientError, message: &Message) -> anyhow::Error {
+    let logs = match err.kind() {
+        ClientErrorKind::RpcError(RpcError::RpcResponseError {
+            data: RpcResponseErrorData::SendTransactionPreflightFailure(result),
+            ..
+        }) => result.logs.clone().unwrap_or_default(),
+        _ => Vec::new(),
+    };
+    match err
+        .get_transaction_error()
+        .and_then(|error| describe_program_error(&error, &logs, message))
+    {
+        Some(description) => anyhow::Error::from(err).context(description),
+        None => err.into(),
+    }
+}
+
+/// Describes a custom instruction error as `Name: message`. The raising
+/// program is the innermost one logged as failing, since a hook error
+/// surfaces on the outer Token-2022 or core instruction; without logs it is
+/// the failing instruction's own program.
+fn describe_program_error(
+    error: &TransactionError,
+    logs: &[String],
+    message: &Message,
+) -> Option<String> {
+    let TransactionError::InstructionError(index, InstructionError::Custom(code)) = error else {
+        return None;
+    };
+    let raised_by = logs
+        .iter()
+        .find_map(|line| {
+            let rest = line.strip_prefix("Program ")?;
+            let (program, _) = rest.split_once(" failed: custom program error")?;
+            Pubkey::from_str(program).ok()
+        })
+        .or_else(|| message.program_id(usize::from(*index)).copied())?;
+    let (name, text) = program_error(&raised_by, *code)?;
+    Some(format!(
+        "{}: {} (error {} in instruction {})",
+        name, text, code, index
+    ))
+}
+
+/// Simulates the unsigned transaction against the latest blockhash, prints the
+/// result, and fails when the transaction would fail. Signatures are not
+/// checked, so --authority transactions can be simulated too.
+fn simulate_transaction(ctx: AppContext<'_>, instructions: &[Instruction]) -> Result<()> {
+    let message = Message::new(instructions, Some(&ctx.payer.pubkey()));
+    let fee_lamports = ctx.client.get_fee_for_message(&message).ok();
+    let transaction = Transaction::new_unsigned(message);
+    let result = ctx
+        .client
+        .simulate_transaction_with_config(
+            &transaction,
+            RpcSimulateTransactionConfig {
+                sig_verify: false,
+                replace_recent_blockhash: true,
+                commitment: Some(ctx.commitment),
+                ..RpcSimulateTransactionConfig::default()
+            },
+        )?
+        .value;
+    let logs = result.logs.unwrap_or_default();
+    let err =
+        result.err.map(
+            |err| match describe_program_error(&err, &logs, &transaction.message) {
+                Some(description) => format!("{} ({})", description, err),
+                None => err.to_string(),
+            },
+        );
+    let output = SimulationOutput {
+        logs,
+        units_consumed: result.units_consumed,
+        fee_lamports,
+        err,
+    };
+
+    if ctx.output == OutputFormat::Json {
+        print_json(&output)?;
+    } else {
+        println!("Simulation (not sent)");
+        for line in &output.logs {
+            println!("  {}", line);
+        }
+        if let Some(units) = output.units_consumed {
+            println!("Compute units: {}", units);
+        }
+        if let Some(fee) = output.fee_lamports {
+            println!("Fee: {} lamports", fee);
+        }
+        println!("Result: {}", output.err.as_deref().unwrap_or("success"));
+    }
+    match output.err {
+        Some(err) => Err(anyhow!("Simulation failed: {}", err)),
+        None => Ok(()),
+    }
+}
+
+fn encode_transaction(transaction: &Transaction) -> Result<String> {
+    Ok(base64::engine::general_purpose::STANDARD.encode(bincode::serialize(transaction)?))
+}
+
+fn decode_transaction(encoded: &str) -> Result<Transaction> {
+    let bytes = base64::engine::general_purpose::STANDARD
+        .decode(encoded.trim())
+        .context("Transaction is not valid base64")?;
+    bincode::deserialize(&bytes).context("Failed to decode transaction")
+}
+
+/// Pubkeys whose signatures `transaction` still lacks.
+fn missing_signers(transaction: &Transaction) -> Vec<Pubkey> {
+    let required = transaction.message.header.num_required_signatures as usize;
+    transaction
+        .message
+        .account_keys
+        .iter()
+        .zip(&transaction.signatures)
+        .take(required)
+        .filter(|(_, signature)| **signature == Signature::default())
+        .map(|(key, _)| *key)
+        .collect()
+}
+
+/// Appends `transaction` to the --export-tx file as one base64 line; the
+/// first export of a run replaces whatever the file held before.
+fn export_transaction(ctx: AppContext<'_>, path: &Path, transaction: &Transaction) -> Result<()> {
+    let index = ctx.exported.get();
+    let mut file = if index == 0 {
+        fs::File::create(path)
+    } else {
+        fs::OpenOptions::new().append(true).open(path)
+    }
+    .with_context(|| format!("Failed to open {}", path.display()))?;
+    writeln!(file, "{}", encode_transaction(transaction)?)?;
+    ctx.exported.set(index + 1);
+
+    let output = ExportedTransactionOutput {
+        path: path.display().to_string(),
+        index,
+        message_hash: transaction.message.hash().to_string(),
+        fee_payer: ctx.payer.pubkey().to_string(),
+        missing_signers: missing_signers(transaction)
+            .iter()
+            .map(Pubkey::to_string)
+            .collect(),
+    };
+    if ctx.output == OutputFormat::Json {
+        return print_json(&output);
+    }
+    println!(
+        "Exported unsigned transaction {} to {}",
+        output.index, output.path
+    );
+    println!("Message hash: {}", output.message_hash);
+    println!("Signatures needed: {}", output.missing_signers.join(", "));
+    Ok(())
+}
+
+fn handle_import_tx(ctx: &OwnedContext, cmd: &ImportTxCmd) -> Result<()> {
+    let ctx_ref = ctx.as_ref();
+    match cmd {
+        ImportTxCmd::Submit(args) => {
+            let contents = fs::read_to_string(&args.path)
+                .with_context(|| format!("Failed to read {}", args.path.display()))?;
+            let mut transactions = Vec::new();
+            for (index, line) in contents
+                .lines()
+                .filter(|line| !line.trim().is_empty())
+                .enumerate()
+            {
+                let transaction = decode_transaction(line)
+                    .with_context(|| format!("Transaction {} in {}", index, args.path.display()))?;
+                let missing = missing_signers(&transaction);
+                if !missing.is_empty() {
+                    return Err(anyhow!(
+                        "Transaction {} is missing signatures from {}",
+                        index,
+                        missing
+                            .iter()
+                            .map(Pubkey::to_string)
+                            .collect::<Vec<_>>()
+                            .join(", ")
+                    ));
+                }
+                transaction
+                    .verify()
+                    .with_context(|| format!("Transaction {} has an invalid signature", index))?;
+                transactions.push(transaction);
+            }
+            if transactions.is_empty() {
+                return Err(anyhow!("{} holds no transactions", args.path.display()));
+            }
+
+            let mut submitted = Vec::new();
+            for mut transaction in transactions {
+                let signature = submit_transaction(ctx_ref, &mut transaction, None)?;
+                let explorer = explorer_url(&signature, ctx_ref.cluster);
+                if ctx_ref.output == OutputFormat::Text {
+                    println!("Submitted: {}", signature);
+                    if let Some(url) = &explorer {
+                        println!("Explorer: {}", url);
+                    }
+                }
+                submitted.push(SimpleOutput {
+                    signature,
+                    explorer,
+                    pdas: None,
+                    compute_budget: None,
+                });
+            }
+            if ctx_ref.output == OutputFormat::Json {
+                print_json(&submitted)?;
+            }
+            Ok(())
+        }
+    }
+}
+
+/// Compute-budget settings from --priority-fee and --compute-limit.
+#[derive(Clone, Copy, Default)]
+struct ComputeBudget {
+    unit_price: Option<u64>,
+    unit_limit: Option<u32>,
+}
+
+impl ComputeBudget {
+    /// ComputeBudget program instructions to put ahead of a transaction's own.
+    fn instructions(&self) -> Vec<Instruction> {
+        let mut instructions = Vec::new();
+        if let Some(limit) = self.unit_limit {
+            instructions.push(ComputeBudgetInstruction::set_compute_unit_limit(limit));
+        }
+        if let Some(price) = self.unit_price {
+            instructions.push(ComputeBudgetInstruction::set_compute_unit_price(price));
+        }
+        instructions
+    }
+
+    /// Echoed in JSON output when either flag is set, so scripts can audit spend.
+    fn output(&self) -> Option<ComputeBudgetOutput> {
+        if self.unit_price.is_none() && self.unit_limit.is_none() {
+            return None;
+        }
+        Some(ComputeBudgetOutput {
+            priority_fee_micro_lamports: self.unit_price,
+            compute_unit_limit: self.unit_limit,
+        })
+    }
+}
+
+/// How transient RPC failures are retried; set by --max-retries and
+/// --retry-delay-ms.
+#[derive(Clone, Copy)]
+struct RetryPolicy {
+    max_attempts: u32,
+    base_delay: Duration,
+}
+
+impl RetryPolicy {
+    /// Backoff before the retry that follows failed attempt `attempt` (1-based).
+    fn delay(&self, attempt: u32) -> Duration {
+        self.base_delay
+            .saturating_mul(1u32 << attempt.saturating_sub(1).min(16))
+    }
+}
+
+/// Fetches the latest blockhash, retrying transient failures per the policy.
+fn latest_blockhash(ctx: AppContext<'_>) -> Result<Hash> {
+    let mut attempt = 1;
+    loop {
+        match ctx.client.get_latest_blockhash() {
+            Ok(value) => return Ok(value),
+            Err(err) if attempt < ctx.retry.max_attempts && is_transient_rpc_error(&err) => {
+                warn_retry(ctx, attempt, &err);
+                std::thread::sleep(ctx.retry.delay(attempt));
+                attempt += 1;
+            }
+            Err(err) => return Err(err.into()),
+        }
+    }
+}
+
+fn warn_retry(ctx: AppContext<'_>, attempt: u32, err: &ClientError) {
+    if ctx.output == OutputFormat::Text {
+        eprintln!(
+            "Warning: RPC attempt {}/{} failed ({}); retrying in {}ms",
+            attempt,
+            ctx.retry.max_attempts,
+            err,
+            ctx.retry.delay(attempt).as_millis()
+        );
+    }
+}
+
+/// True when the RPC node rejected or dropped the request without the
+/// transaction executing: an expired or unknown blockhash, rate limiting,
+/// an unreachable or unhealthy node. Program errors such as Unauthorized
+/// are never transient.
+fn is_transient_rpc_error(err: &ClientError) -> bool {
+    if let Some(tx_err) = err.get_transaction_error() {
+        return tx_err == TransactionError::BlockhashNotFound;
+    }
+    match err.kind() {
+        ClientErrorKind::Io(_) => true,
+        ClientErrorKind::Reqwest(err) => {
+            err.is_connect()
+                || err.is_timeout()
+                || err
+                    .status()
+                    .is_some_and(|status| status.as_u16() == 429 || status.is_server_error())
+        }
+        ClientErrorKind::RpcError(RpcError::RpcResponseError { code, .. }) => {
+            *code == JSON_RPC_SERVER_ERROR_NODE_UNHEALTHY
+        }
+        // send_and_confirm_transaction gives up like this only once the
+        // blockhash has expired without the transaction landing.
+        ClientErrorKind::RpcError(RpcError::ForUser(message)) => {
+            message.starts_with("unable to confirm transaction")
+        }
+        _ => false,
+    }
+}
+
+/// Groups instructions into as few transactions as fit the packet size limit,
+/// preserving order, leaving room for `prefix` (the compute-budget
+/// instructions) in each. Never yields an empty group.
+fn chunk_instructions(
+    instructions: Vec<Instruction>,
+    prefix: &[Instruction],
+    payer: &Pubkey,
+) -> Result<Vec<Vec<Instruction>>> {
+    let mut chunks: Vec<Vec<Instruction>> = Vec::new();
+    let mut current: Vec<Instruction> = Vec::new();
+    for instruction in instructions {
+        current.push(instruction);
+        if transaction_size(&[prefix, &current].concat(), payer)? <= PACKET_DATA_SIZE {
+            continue;
+        }
+        let overflow = current.pop().expect("just pushed");
+        if current.is_empty() {
+            return Err(anyhow!(
+                "Instruction for program {} does not fit in a single transaction",
+                overflow.program_id
+            ));
+        }
+        chunks.push(std::mem::take(&mut current));
+        current.push(overflow);
+    }
+    if !current.is_empty() {
+        chunks.push(current);
+    }
+    Ok(chunks)
+}
+
+fn transaction_size(instructions: &[Instruction], payer: &Pubkey) -> Result<usize> {
+    let transaction = Transaction::new_unsigned(Message::new(instructions, Some(payer)));
+    Ok(bincode::serialized_size(&transaction)? as usize)
+}
+
+fn print_multisig_proposal(
+    ctx: AppContext<'_>,
+    authority: &Pubkey,
+    instructions: &[Instruction],
+) -> Result<()> {
+    let output = MultisigProposalOutput {
+        authority: authority.to_string(),
+        instructions: instructions.iter().map(InstructionOutput::from).collect(),
+    };
+    if ctx.output == OutputFormat::Json {
+        return print_json(&output);
+    }
+    println!(
+        "Unsigned instructions for multisig authority {}",
+        output.authority
+    );
+    for (index, instruction) in output.instructions.iter().enumerate() {
+        println!("Instruction {}", index);
+        println!("  Program: {}", instruction.program_id);
+        for account in &instruction.accounts {
+            let mut flags = Vec::new();
+            if account.is_signer {
+                flags.push("signer");
+            }
+            if account.is_writable {
+                flags.push("writable");
+            }
+            println!("  Account: {} {}", account.pubkey, flags.join(","));
+        }
+        println!("  Data (base64): {}", instruction.data);
+    }
+    Ok(())
+}
+
+fn fetch_config(ctx: AppContext<'_>, config_pda: &Pubkey) -> Result<StablecoinConfig> {
+    let account = ctx.client.get_account(config_pda)?;
+    let mut data = account.data.as_slice();
+    StablecoinConfig::try_deserialize(&mut data).context("Failed to decode config")
+}
+
+fn fetch_role_account(ctx: AppContext<'_>, role_pda: &Pubkey) -> Result<Option<RoleAccount>> {
+    let account = match ctx.client.get_account(role_pda) {
+        Ok(value) => value,
+        Err(_) => return Ok(None),
+    };
+    let mut data = account.data.as_slice();
+    let decoded = RoleAccount::try_deserialize(&mut data).context("Failed to decode role")?;
+    Ok(Some(decoded))
+}
+
+fn fetch_blacklist_entry(
+    ctx: AppContext<'_>,
+    entry_pda: &Pubkey,
+) -> Result<Option<BlacklistEntry>> {
+    let account = match ctx.client.get_account(entry_pda) {
+        Ok(value) => value,
+        Err(_) => return Ok(None),
+    };
+    let mut data = account.data.as_slice();
+    let decoded =
+        BlacklistEntry::try_deserialize(&mut data).context("Failed to decode blacklist")?;
+    Ok(Some(decoded))
+}
+
+fn fetch_mint_request(ctx: AppContext<'_>, request_pda: &Pubkey) -> Result<Option<MintRequest>> {
+    let account = match ctx.client.get_account(request_pda) {
+        Ok(value) => value,
+        Err(_) => return Ok(None),
+    };
+    let mut data = account.data.as_slice();
+    let decoded =
+        MintRequest::try_deserialize(&mut data).context("Failed to decode mint request")?;
+    Ok(Some(decoded))
+}
+
+fn fetch_allowlist_entry(
+    ctx: AppContext<'_>,
+    entry_pda: &Pubkey,
+) -> Result<Option<AllowlistEntry>> {
+    let account = match ctx.client.get_account(entry_pda) {
+        Ok(value) => value,
+        Err(_) => return Ok(None),
+    };
+    let mut data = account.data.as_slice();
+    let decoded =
+        AllowlistEntry::try_deserialize(&mut data).context("Failed to decode allowlist entry")?;
+    Ok(Some(decoded))
+}
+
+fn list_role_accounts(
+    ctx: AppContext<'_>,
+    config_pda: &Pubkey,
+) -> Result<Vec<AccountEntry<RoleAccount>>> {
+    let config = RpcProgramAccountsConfig {
+        filters: Some(vec![RpcFilterType::Memcmp(Memcmp::new_base58_encoded(
+            8,
+            config_pda.as_ref(),
+        ))]),
+        account_config: RpcAccountInfoConfig {
+            encoding: None,
+            commitment: Some(ctx.commitment),
+            data_slice: None,
+            min_context_slot: None,
+        },
+        ..RpcProgramAccountsConfig::default()
+    };
+
+    let accounts = ctx
+        .client
+        .get_program_accounts_with_config(&stablecoin_core::ID, config)?;
+
+    let mut result = Vec::new();
+    for (address, account) in accounts {
+        let mut data = account.data.as_slice();
+        if let Ok(decoded) = RoleAccount::try_deserialize(&mut data) {
+            result.push(AccountEntry {
+                address,
+                account: decoded,
+            });
+        }
+    }
+    Ok(result)
+}
+
+fn list_blacklist_entries(
+    ctx: AppContext<'_>,
+    config_pda: &Pubkey,
+) -> Result<Vec<AccountEntry<BlacklistEntry>>> {
+    let config = RpcProgramAccountsConfig {
+        filters: Some(vec![RpcFilterType::Memcmp(Memcmp::new_base58_encoded(
+            8,
+            config_pda.as_ref(),
+        ))]),
+        account_config: RpcAccountInfoConfig {
+            encoding: None,
+            commitment: Some(ctx.commitment),
+            data_slice: None,
+            min_context_slot: None,
+        },
+        ..RpcProgramAccountsConfig::default()
+    };
+
+    let accounts = ctx
+        .client
+        .get_program_accounts_with_config(&stablecoin_core::ID, config)?;
+
+    let mut result = Vec::new();
+    for (address, account) in accounts {
+        let mut data = account.data.as_slice();
+        if let Ok(decoded) = BlacklistEntry::try_deserialize(&mut data) {
+            result.push(AccountEntry {
+                address,
+                account: decoded,
+            });
+        }
+    }
+    Ok(result)
+}
+
+fn count_role(entries: &[AccountEntry<RoleAccount>], role: u8) -> usize {
+    entries
+        .iter()
+        .filter(|entry| entry.account.roles & role != 0)
+        .count()
+}
+
+fn role_counts(entries: &[AccountEntry<RoleAccount>]) -> RoleCounts {
+    RoleCounts {
+        masters: count_role(entries, ROLE_MASTER_AUTHORITY),
+        minters: count_role(entries, ROLE_MINTER),
+        burners: count_role(entries, ROLE_BURNER),
+        freezers: count_role(entries, ROLE_FREEZER),
+        pausers: count_role(entries, ROLE_PAUSER),
+        blacklisters: count_role(entries, ROLE_BLACKLISTER),
+        seizers: count_role(entries, ROLE_SEIZER),
+        role_admins: count_role(entries, ROLE_ROLE_ADMIN),
+    }
+}
+
+/// In-effect entries per reason code. Legacy entries and codes this build
+/// does not know are counted as unspecified.
+fn blacklist_reason_counts(
+    entries: &[AccountEntry<BlacklistEntry>],
+    now: i64,
+) -> BlacklistReasonCounts {
+    let mut counts = BlacklistReasonCounts::default();
+    for entry in entries
+        .iter()
+        .filter(|entry| entry.account.is_in_effect(now))
+    {
+        match BlacklistReason::from_code(entry.account.reason_code) {
+            Some(BlacklistReason::Sanctions) => counts.sanctions += 1,
+            Some(BlacklistReason::Fraud) => counts.fraud += 1,
+            Some(BlacklistReason::CourtOrder) => counts.court_order += 1,
+            Some(BlacklistReason::Other) => counts.other += 1,
+            Some(BlacklistReason::Unspecified) | None => counts.unspecified += 1,
+        }
+    }
+    counts
+}
+
+fn fetch_token_account(ctx: AppContext<'_>, address: &Pubkey) -> Result<TokenAccountInfo> {
+    let account = ctx.client.get_account(address)?;
+    let parsed = StateWithExtensions::<TokenAccount2022>::unpack(&account.data)
+        .map_err(|err| anyhow!("Failed to decode token account: {}", err))?;
+    Ok(token_account_info(&parsed.base))
+}
+
+fn token_account_info(account: &TokenAccount2022) -> TokenAccountInfo {
+    TokenAccountInfo {
+        owner: account.owner,
+        mint: account.mint,
+        amount: account.amount,
+        is_frozen: account.state == AccountState::Frozen,
+    }
+}
+
+fn full_balance(balance: u64) -> Result<u64> {
+    if balance == 0 {
+        return Err(anyhow!("Token account has no balance"));
+    }
+    Ok(balance)
+}
+
+/// Amount a seize moves: the requested part of the balance, or all of it.
+fn seize_amount(balance: u64, requested: Option<u64>) -> Result<u64> {
+    match requested {
+        None => full_balance(balance),
+        Some(0) => Err(anyhow!("Seize amount must be greater than zero")),
+        Some(amount) if amount > balance => Err(anyhow!(
+            "Seize amount {} exceeds the account balance {}",
+            amount,
+            balance
+        )),
+        Some(amount) => Ok(amount),
+    }
+}
+
+#[derive(Clone)]
+struct AccountEntry<T> {
+    address: Pubkey,
+    account: T,
+}
+
+#[derive(Clone, Copy)]
+struct TokenAccountInfo {
+    owner: Pubkey,
+    mint: Pubkey,
+    amount: u64,
+    is_frozen: bool,
+}
+
+fn find_config_pda(mint: &Pubkey, program_id: &Pubkey) -> (Pubkey, u8) {
+    Pubkey::find_program_address(&[b"stablecoin", mint.as_ref()], program_id)
+}
+
+fn find_role_pda(config: &Pubkey, authority: &Pubkey, program_id: &Pubkey) -> (Pubkey, u8) {
+    Pubkey::find_program_address(&[b"role", config.as_ref(), authority.as_ref()], program_id)
+}
+
+fn find_mint_request_pda(config: &Pubkey, id: u64, program_id: &Pubkey) -> (Pubkey, u8) {
+    Pubkey::find_program_address(
+        &[b"mint_request", config.as_ref(), &id.to_le_bytes()],
+        program_id,
+    )
+}
+
+fn find_allowlist_pda(config: &Pubkey, wallet: &Pubkey, program_id: &Pubkey) -> (Pubkey, u8) {
+    Pubkey::find_program_address(
+        &[b"allowlist", config.as_ref(), wallet.as_ref()],
+        program_id,
+    )
+}
+
+fn find_blacklist_pda(config: &Pubkey, wallet: &Pubkey, program_id: &Pubkey) -> (Pubkey, u8) {
+    Pubkey::find_program_address(
+        &[b"blacklist", config.as_ref(), wallet.as_ref()],
+        program_id,
+    )
+}
+
+fn find_extra_account_metas_pda(mint: &Pubkey, hook_program: &Pubkey) -> (Pubkey, u8) {
+    Pubkey::find_program_address(&[b"extra-account-metas", mint.as_ref()], hook_program)
+}
+
+/// The hook-owned account recording when `owner` last sent a transfer.
+fn find_last_transfer_pda(config: &Pubkey, owner: &Pubkey, hook_program: &Pubkey) -> (Pubkey, u8) {
+    Pubkey::find_program_address(
+        &[
+            stablecoin_core::instructions::initialize::LAST_TRANSFER_SEED,
+            config.as_ref(),
+            owner.as_ref(),
+        ],
+        hook_program,
+    )
+}
+
+fn account_discriminator(name: &str) -> [u8; 8] {
+    let mut hasher = Sha256::new();
+    hasher.update(format!("account:{}", name));
+    let hash = hasher.finalize();
+    let mut output = [0u8; 8];
+    output.copy_from_slice(&hash[..8]);
+    output
+}
+
+fn anchor_discriminator(name: &str) -> [u8; 8] {
+    let mut hasher = Sha256::new();
+    hasher.update(format!("global:{}", name));
+    let hash = hasher.finalize();
+    let mut output = [0u8; 8];
+    output.copy_from_slice(&hash[..8]);
+    output
+}
+
+fn build_instruction(
+    name: &str,
+    data: Vec<u8>,
+    accounts: Vec<AccountMeta>,
+    program_id: Pubkey,
+) -> Instruction {
+    let mut payload = Vec::with_capacity(8 + data.len());
+    payload.extend_from_slice(&anchor_discriminator(name));
+    payload.extend_from_slice(&data);
+    Instruction {
+        program_id,
+        accounts,
+        data: payload,
+    }
+}
+
+#[derive(BorshSerialize)]
+struct InitializeArgs {
+    name: String,
+    symbol: String,
+    uri: String,
+    decimals: u8,
+    enable_permanent_delegate: bool,
+    enable_transfer_hook: bool,
+    default_account_frozen: bool,
+    transfer_hook_program: Option<Pubkey>,
+    pause_cooldown: i64,
+    enable_metadata: bool,
+    max_supply: Option<u64>,
+    enable_allowlist: bool,
+    enable_confidential: bool,
+    treasury: Option<Pubkey>,
+    token_program: TokenProgramKind,
+    transfer_cooldown_seconds: Option<i64>,
+    reserve_transfer_hook: bool,
+}
+
+#[derive(BorshSerialize)]
+struct PauseMaskArgs {
+    mask: Option<u8>,
+}
+
+#[derive(BorshSerialize)]
+struct UpdateRolesArgs {
+    target: Pubkey,
+    roles: u8,
+    mint_quota: Option<u64>,
+    lifetime_quota: Option<u64>,
+}
+
+#[derive(BorshSerialize)]
+struct FreezeSupplyArgs {
+    null_mint_authority: bool,
+}
+
+#[derive(BorshSerialize)]
+struct UpdateMaxSupplyArgs {
+    max_supply: u64,
+}
+
+#[derive(BorshSerialize)]
+struct UpdateQuotaWindowArgs {
+    quota_window_seconds: i64,
+}
+
+#[derive(BorshSerialize)]
+struct UpdateTransferLimitArgs {
+    max_transfer_amount: Option<u64>,
+}
+
+#[derive(BorshSerialize)]
+struct UpdateAdminLockArgs {
+    lock_admin_ops: bool,
+}
+
+#[derive(BorshSerialize)]
+struct UpdateSelfTransferArgs {
+    allow_self_transfer: bool,
+}
+
+#[derive(BorshSerialize)]
+struct UpdateMintApprovalArgs {
+    require_mint_approval: bool,
+}
+
+#[derive(BorshSerialize)]
+struct UpdateDefaultAccountStateArgs {
+    default_frozen: bool,
+}
+
+#[derive(BorshSerialize)]
+struct UpdateTreasuryArgs {
+    treasury: Option<Pubkey>,
+}
+
+#[derive(BorshSerialize)]
+struct UpdateMetadataArgs {
+    name: Option<String>,
+    symbol: Option<String>,
+    uri: Option<String>,
+}
+
+#[derive(BorshSerialize)]
+struct UpdateMetadataFieldArgs {
+    key: String,
+    value: String,
+}
+
+#[derive(BorshSerialize)]
+struct MintBatchEntry {
+    recipient: Pubkey,
+    amount: u64,
+}
+
+#[derive(BorshSerialize)]
+struct MintBurnArgs {
+    amount: u64,
+}
+
+#[derive(BorshSerialize)]
+struct AddToBlacklistArgs {
+    wallet: Pubkey,
+    reason: String,
+    expires_at: Option<i64>,
+    reason_code: u8,
+}
+
+#[derive(BorshSerialize)]
+struct UpdateBlacklistEntryArgs {
+    reason: String,
+    reason_code: Option<u8>,
+}
+
+#[derive(BorshSerialize)]
+struct AddToAllowlistArgs {
+    wallet: Pubkey,
+}
+
+struct InitializeParams {
+    authority: Pubkey,
+    mint: Pubkey,
+    name: String,
+    symbol: String,
+    uri: String,
+    decimals: u8,
+    enable_permanent_delegate: bool,
+    enable_transfer_hook: bool,
+    default_account_frozen: bool,
+    transfer_hook_program: Option<Pubkey>,
+    pause_cooldown: i64,
+    enable_metadata: bool,
+    max_supply: Option<u64>,
+    enable_allowlist: bool,
+    enable_confidential: bool,
+    treasury: Option<Pubkey>,
+    token_program: TokenProgramKind,
+    transfer_cooldown_seconds: Option<i64>,
+    reserve_transfer_hook: bool,
+    config_pda: Pubkey,
+    role_pda: Pubkey,
+    extra_metas: Option<Pubkey>,
+}
+
+fn build_initialize_instruction(params: InitializeParams) -> Result<Instruction> {
+    let mut accounts = vec![
+        AccountMeta::new(params.authority, true),
+        AccountMeta::new(params.mint, true),
+        AccountMeta::new(params.config_pda, false),
+        AccountMeta::new(params.role_pda, false),
+    ];
+
+    if params.enable_transfer_hook {
+        let extra_metas = params
+            .extra_metas
+            .ok_or_else(|| anyhow!("Missing extra account metas"))?;
+        let hook_program = params
+            .transfer_hook_program
+            .ok_or_else(|| anyhow!("Missing transfer hook program"))?;
+        accounts.push(AccountMeta::new(extra_metas, false));
+        accounts.push(AccountMeta::new_readonly(hook_program, false));
+    } else {
+        // Anchor reads the program id in an optional account's slot as None.
+        accounts.push(AccountMeta::new_readonly(stablecoin_core::ID, false));
+        accounts.push(AccountMeta::new_readonly(stablecoin_core::ID, false));
+    }
+
+    accounts.push(AccountMeta::new_readonly(params.token_program.id(), false));
+    accounts.push(AccountMeta::new_readonly(system_program::id(), false));
+    accounts.push(AccountMeta::new_readonly(sysvar::rent::id(), false));
+
+    let data = InitializeArgs {
+        name: params.name,
+        symbol: params.symbol,
+        uri: params.uri,
+        decimals: params.decimals,
+        enable_permanent_delegate: params.enable_permanent_delegate,
+        enable_transfer_hook: params.enable_transfer_hook,
+        default_account_frozen: params.default_account_frozen,
+        transfer_hook_program: if params.enable_transfer_hook {
+            params.transfer_hook_program
+        } else {
+            None
+        },
+        pause_cooldown: params.pause_cooldown,
+        enable_metadata: params.enable_metadata,
+        max_supply: params.max_supply,
+        enable_allowlist: params.enable_allowlist,
+        enable_confidential: params.enable_confidential,
+        treasury: params.treasury,
+        token_program: params.token_program,
+        transfer_cooldown_seconds: params.transfer_cooldown_seconds,
+        reserve_transfer_hook: params.reserve_transfer_hook,
+    }
+    .try_to_vec()?;
+
+    Ok(build_instruction(
+        "initialize",
+        data,
+        accounts,
+        stablecoin_core::ID,
+    ))
+}
+
+struct MintParams {
+    minter: Pubkey,
+    mint: Pubkey,
+    recipient: Pubkey,
+    recipient_ata: Pubkey,
+    amount: u64,
+    token_program: Pubkey,
+    /// Pass the recipient's blacklist PDA so the program refuses to mint to
+    /// a blacklisted wallet; required when the transfer hook is enabled.
+    check_blacklist: bool,
+}
+
+fn build_mint_instruction(params: MintParams) -> Result<Instruction> {
+    let config_pda = find_config_pda(&params.mint, &stablecoin_core::ID).0;
+    let role_pda = find_role_pda(&config_pda, &params.minter, &stablecoin_core::ID).0;
+    let accounts = vec![
+        AccountMeta::new(params.minter, true),
+        AccountMeta::new(config_pda, false),
+        AccountMeta::new(role_pda, false),
+        AccountMeta::new(params.mint, false),
+        AccountMeta::new_readonly(params.recipient, false),
+        AccountMeta::new(params.recipient_ata, false),
+        AccountMeta::new_readonly(params.token_program, false),
+        AccountMeta::new_readonly(spl_associated_token_account::id(), false),
+        AccountMeta::new_readonly(system_program::id(), false),
+        // Anchor reads the program id in an optional account's slot as None.
+        AccountMeta::new_readonly(
+            if params.check_blacklist {
+                find_blacklist_pda(&config_pda, &params.recipient, &stablecoin_core::ID).0
+            } else {
+                stablecoin_core::ID
+            },
+            false,
+        ),
+    ];
+    let data = MintBurnArgs {
+        amount: params.amount,
+    }
+    .try_to_vec()?;
+    Ok(build_instruction(
+        "mint",
+        data,
+        accounts,
+        stablecoin_core::ID,
+    ))
+}
+
+#[derive(BorshSerialize)]
+struct ProposeMintArgs {
+    id: u64,
+    recipient: Pubkey,
+    amount: u64,
+}
+
+struct ProposeMintParams {
+    requester: Pubkey,
+    config_pda: Pubkey,
+    id: u64,
+    recipient: Pubkey,
+    amount: u64,
+}
+
+fn build_propose_mint_instruction(params: ProposeMintParams) -> Result<Instruction> {
+    let role_pda = find_role_pda(&params.config_pda, &params.requester, &stablecoin_core::ID).0;
+    let request_pda = find_mint_request_pda(&params.config_pda, params.id, &stablecoin_core::ID).0;
+    let accounts = vec![
+        AccountMeta::new(params.requester, true),
+        AccountMeta::new_readonly(params.config_pda, false),
+        AccountMeta::new_readonly(role_pda, false),
+        AccountMeta::new(request_pda, false),
+        AccountMeta::new_readonly(system_program::id(), false),
+    ];
+    let data = ProposeMintArgs {
+        id: params.id,
+        recipient: params.recipient,
+        amount: params.amount,
+    }
+    .try_to_vec()?;
+    Ok(build_instruction(
+        "propose_mint",
+        data,
+        accounts,
+        stablecoin_core::ID,
+    ))
+}
+
+struct ApproveMintParams {
+    approver: Pubkey,
+    mint: Pubkey,
+    id: u64,
+    requester: Pubkey,
+    recipient: Pubkey,
+    token_program: Pubkey,
+    /// Pass the recipient's blacklist PDA; required when the transfer hook
+    /// is enabled.
+    check_blacklist: bool,
+}
+
+fn build_approve_mint_instruction(params: ApproveMintParams) -> Result<Instruction> {
+    let config_pda = find_config_pda(&params.mint, &stablecoin_core::ID).0;
+    let accounts = vec![
+        AccountMeta::new(params.approver, true),
+        AccountMeta::new(config_pda, false),
+        AccountMeta::new_readonly(
+            find_role_pda(&config_pda, &params.approver, &stablecoin_core::ID).0,
+            false,
+        ),
+        AccountMeta::new(
+            find_role_pda(&config_pda, &params.requester, &stablecoin_core::ID).0,
+            false,
+        ),
+        AccountMeta::new(
+            find_mint_request_pda(&config_pda, params.id, &stablecoin_core::ID).0,
+            false,
+        ),
+        AccountMeta::new(params.requester, false),
+        AccountMeta::new(params.mint, false),
+        AccountMeta::new_readonly(params.recipient, false),
+        AccountMeta::new(
+            get_associated_token_address_with_program_id(
+                &params.recipient,
+                &params.mint,
+                &params.token_program,
+            ),
+            false,
+        ),
+        AccountMeta::new_readonly(params.token_program, false),
+        AccountMeta::new_readonly(spl_associated_token_account::id(), false),
+        AccountMeta::new_readonly(system_program::id(), false),
+        // Anchor reads the program id in an optional account's slot as None.
+        AccountMeta::new_readonly(
+            if params.check_blacklist {
+                find_blacklist_pda(&config_pda, &params.recipient, &stablecoin_core::ID).0
+            } else {
+                stablecoin_core::ID
+            },
+            false,
+        ),
+    ];
+    Ok(build_instruction(
+        "approve_mint",
+        Vec::new(),
+        accounts,
+        stablecoin_core::ID,
+    ))
+}
+
+struct CancelMintParams {
+    authority: Pubkey,
+    config_pda: Pubkey,
+    id: u64,
+    requester: Pubkey,
+}
+
+fn build_cancel_mint_instruction(params: CancelMintParams) -> Result<Instruction> {
+    let accounts = vec![
+        AccountMeta::new_readonly(params.authority, true),
+        AccountMeta::new_readonly(params.config_pda, false),
+        // The requester needs no role account, which it may no longer have;
+        // anyone else passes theirs. The program id in the slot reads as None.
+        AccountMeta::new_readonly(
+            if params.authority == params.requester {
+                stablecoin_core::ID
+            } else {
+                find_role_pda(&params.config_pda, &params.authority, &stablecoin_core::ID).0
+            },
+            false,
+        ),
+        AccountMeta::new(
+            find_mint_request_pda(&params.config_pda, params.id, &stablecoin_core::ID).0,
+            false,
+        ),
+        AccountMeta::new(params.requester, false),
+    ];
+    Ok(build_instruction(
+        "cancel_mint",
+        Vec::new(),
+        accounts,
+        stablecoin_core::ID,
+    ))
+}
+
+struct MintBatchParams<'a> {
+    minter: Pubkey,
+    mint: Pubkey,
+    entries: &'a [(Pubkey, u64)],
+    token_program: Pubkey,
+    /// Follow each recipient ATA with the recipient's blacklist PDA, as the
+    /// program requires when the transfer hook is enabled.
+    check_blacklist: bool,
+}
+
+fn build_mint_batch_instruction(params: MintBatchParams<'_>) -> Result<Instruction> {
+    let config_pda = find_config_pda(&params.mint, &stablecoin_core::ID).0;
+    let role_pda = find_role_pda(&config_pda, &params.minter, &stablecoin_core::ID).0;
+    let mut accounts = vec![
+        AccountMeta::new_readonly(params.minter, true),
+        AccountMeta::new(config_pda, false),
+        AccountMeta::new(role_pda, false),
+        AccountMeta::new(params.mint, false),
+        AccountMeta::new_readonly(params.token_program, false),
+    ];
+    let mut entries = Vec::with_capacity(params.entries.len());
+    for (recipient, amount) in params.entries {
+        accounts.push(AccountMeta::new(
+            get_associated_token_address_with_program_id(
+                recipient,
+                &params.mint,
+                &params.token_program,
+            ),
+            false,
+        ));
+        if params.check_blacklist {
+            accounts.push(AccountMeta::new_readonly(
+                find_blacklist_pda(&config_pda, recipient, &stablecoin_core::ID).0,
+                false,
+            ));
+        }
+        entries.push(MintBatchEntry {
+            recipient: *recipient,
+            amount: *amount,
+        });
+    }
+    Ok(build_instruction(
+        "mint_batch",
+        entries.try_to_vec()?,
+        accounts,
+        stablecoin_core::ID,
+    ))
+}
+
+struct BurnParams {
+    burner: Pubkey,
+    mint: Pubkey,
+    burner_ata: Pubkey,
+    amount: u64,
+    token_program: Pubkey,
+}
+
+fn build_burn_instruction(params: BurnParams) -> Result<Instruction> {
+    let config_pda = find_config_pda(&params.mint, &stablecoin_core::ID).0;
+    let role_pda = find_role_pda(&config_pda, &params.burner, &stablecoin_core::ID).0;
+    let accounts = vec![
+        AccountMeta::new(params.burner, true),
+        AccountMeta::new(config_pda, false),
+        AccountMeta::new(role_pda, false),
+        AccountMeta::new(params.mint, false),
+        AccountMeta::new(params.burner_ata, false),
+        AccountMeta::new_readonly(params.token_program, false),
+    ];
+    let data = MintBurnArgs {
+        amount: params.amount,
+    }
+    .try_to_vec()?;
+    Ok(build_instruction(
+        "burn",
+        data,
+        accounts,
+        stablecoin_core::ID,
+    ))
+}
+
+struct TransferParams {
+    owner: Pubkey,
+    recipient: Pubkey,
+    mint: Pubkey,
+    source_ata: Pubkey,
+    destination_ata: Pubkey,
+    amount: u64,
+    decimals: u8,
+    token_program: Pubkey,
+    /// Set when the mint has the transfer hook enabled.
+    transfer_hook_program: Option<Pubkey>,
+    /// The hook checks allowlist entries instead of blacklist entries.
+    allowlist_mode: bool,
+    /// The hook also takes the owner's LastTransfer PDA, writable.
+    transfer_cooldown: bool,
+}
+
+fn build_transfer_instruction(params: TransferParams) -> Result<Instruction> {
+    let mut instruction = spl_token_2022::instruction::transfer_checked(
+        &params.token_program,
+        &params.source_ata,
+        &params.mint,
+        &params.destination_ata,
+        &params.owner,
+        &[],
+        params.amount,
+        params.decimals,
+    )
+    .map_err(|err| anyhow!("Failed to build transfer instruction: {}", err))?;
+
+    // Token-2022 forwards these to the hook's execute in the order registered
+    // at initialize; both list entries are keyed by token account owner.
+    if let Some(hook_program) = params.transfer_hook_program {
+        let config_pda = find_config_pda(&params.mint, &stablecoin_core::ID).0;
+        let find_entry_pda = if params.allowlist_mode {
+            find_allowlist_pda
+        } else {
+            find_blacklist_pda
         };
-        print_json(&output)
-    } else {
-        println!(
-            "Minted {} tokens to {}",
-            format_amount(amount, config.decimals),
-            recipient
-        );
-        println!("New supply: {}", supply.amount);
-        println!("Tx: {}", signature);
-        if let Some(url) = explorer {
-            println!("Explorer: {}", url);
+        instruction.accounts.extend([
+            AccountMeta::new_readonly(stablecoin_core::ID, false),
+            AccountMeta::new_readonly(config_pda, false),
+            AccountMeta::new_readonly(
+                find_entry_pda(&config_pda, &params.owner, &stablecoin_core::ID).0,
+                false,
+            ),
+            AccountMeta::new_readonly(
+                find_entry_pda(&config_pda, &params.recipient, &stablecoin_core::ID).0,
+                false,
+            ),
+            AccountMeta::new_readonly(hook_program, false),
+            AccountMeta::new_readonly(
+                find_extra_account_metas_pda(&params.mint, &hook_program).0,
+                false,
+            ),
+        ]);
+        if params.transfer_cooldown {
+            instruction.accounts.push(AccountMeta::new(
+                find_last_transfer_pda(&config_pda, &params.owner, &hook_program).0,
+                false,
+            ));
+        }
+    }
+    Ok(instruction)
+}
+
+struct FreezeParams {
+    freezer: Pubkey,
+    mint: Pubkey,
+    target_ata: Pubkey,
+    token_program: Pubkey,
+}
+
+fn build_freeze_instruction(params: FreezeParams) -> Result<Instruction> {
+    let config_pda = find_config_pda(&params.mint, &stablecoin_core::ID).0;
+    let role_pda = find_role_pda(&config_pda, &params.freezer, &stablecoin_core::ID).0;
+    let accounts = vec![
+        AccountMeta::new(params.freezer, true),
+        AccountMeta::new(config_pda, false),
+        AccountMeta::new(role_pda, false),
+        AccountMeta::new_readonly(params.mint, false),
+        AccountMeta::new(params.target_ata, false),
+        AccountMeta::new_readonly(params.token_program, false),
+    ];
+    Ok(build_instruction(
+        "freeze_account",
+        Vec::new(),
+        accounts,
+        stablecoin_core::ID,
+    ))
+}
+
+fn build_thaw_instruction(params: FreezeParams) -> Result<Instruction> {
+    let config_pda = find_config_pda(&params.mint, &stablecoin_core::ID).0;
+    let role_pda = find_role_pda(&config_pda, &params.freezer, &stablecoin_core::ID).0;
+    let accounts = vec![
+        AccountMeta::new(params.freezer, true),
+        AccountMeta::new(config_pda, false),
+        AccountMeta::new(role_pda, false),
+        AccountMeta::new_readonly(params.mint, false),
+        AccountMeta::new(params.target_ata, false),
+        AccountMeta::new_readonly(params.token_program, false),
+    ];
+    Ok(build_instruction(
+        "thaw_account",
+        Vec::new(),
+        accounts,
+        stablecoin_core::ID,
+    ))
+}
+
+struct PauseParams {
+    pauser: Pubkey,
+    config_pda: Pubkey,
+    unpause: bool,
+    mask: Option<u8>,
+}
+
+fn build_pause_instruction(params: PauseParams) -> Result<Instruction> {
+    let role_pda = find_role_pda(&params.config_pda, &params.pauser, &stablecoin_core::ID).0;
+    let accounts = vec![
+        AccountMeta::new(params.pauser, true),
+        AccountMeta::new(params.config_pda, false),
+        AccountMeta::new(role_pda, false),
+    ];
+    let name = if params.unpause { "unpause" } else { "pause" };
+    let data = PauseMaskArgs { mask: params.mask }.try_to_vec()?;
+    Ok(build_instruction(name, data, accounts, stablecoin_core::ID))
+}
+
+struct UpdateAdminLockParams {
+    authority: Pubkey,
+    config_pda: Pubkey,
+    lock_admin_ops: bool,
+}
+
+fn build_update_admin_lock_instruction(params: UpdateAdminLockParams) -> Result<Instruction> {
+    let role_pda = find_role_pda(&params.config_pda, &params.authority, &stablecoin_core::ID).0;
+    let accounts = vec![
+        AccountMeta::new_readonly(params.authority, true),
+        AccountMeta::new(params.config_pda, false),
+        AccountMeta::new_readonly(role_pda, false),
+    ];
+    let data = UpdateAdminLockArgs {
+        lock_admin_ops: params.lock_admin_ops,
+    }
+    .try_to_vec()?;
+    Ok(build_instruction(
+        "update_admin_lock",
+        data,
+        accounts,
+        stablecoin_core::ID,
+    ))
+}
+
+struct UpdateRolesParams {
+    authority: Pubkey,
+    config_pda: Pubkey,
+    target: Pubkey,
+    roles: u8,
+    mint_quota: Option<u64>,
+    lifetime_quota: Option<u64>,
+}
+
+fn build_update_roles_instruction(params: UpdateRolesParams) -> Result<Instruction> {
+    let role_pda = find_role_pda(&params.config_pda, &params.authority, &stablecoin_core::ID).0;
+    let target_role_pda = find_role_pda(&params.config_pda, &params.target, &stablecoin_core::ID).0;
+    let accounts = vec![
+        AccountMeta::new(params.authority, true),
+        AccountMeta::new(params.config_pda, false),
+        AccountMeta::new(role_pda, false),
+        AccountMeta::new(target_role_pda, false),
+        AccountMeta::new_readonly(params.target, false),
+        AccountMeta::new_readonly(system_program::id(), false),
+    ];
+    let data = UpdateRolesArgs {
+        target: params.target,
+        roles: params.roles,
+        mint_quota: params.mint_quota,
+        lifetime_quota: params.lifetime_quota,
+    }
+    .try_to_vec()?;
+    Ok(build_instruction(
+        "update_roles",
+        data,
+        accounts,
+        stablecoin_core::ID,
+    ))
+}
+
+struct UpdateMaxSupplyParams {
+    authority: Pubkey,
+    config_pda: Pubkey,
+    mint: Pubkey,
+    max_supply: u64,
+}
+
+fn build_update_max_supply_instruction(params: UpdateMaxSupplyParams) -> Result<Instruction> {
+    let role_pda = find_role_pda(&params.config_pda, &params.authority, &stablecoin_core::ID).0;
+    let accounts = vec![
+        AccountMeta::new_readonly(params.authority, true),
+        AccountMeta::new(params.config_pda, false),
+        AccountMeta::new_readonly(role_pda, false),
+        AccountMeta::new_readonly(params.mint, false),
+    ];
+    let data = UpdateMaxSupplyArgs {
+        max_supply: params.max_supply,
+    }
+    .try_to_vec()?;
+    Ok(build_instruction(
+        "update_max_supply",
+        data,
+        accounts,
+        stablecoin_core::ID,
+    ))
+}
+
+struct FreezeSupplyParams {
+    authority: Pubkey,
+    config_pda: Pubkey,
+    mint: Pubkey,
+    null_mint_authority: bool,
+    token_program: Pubkey,
+}
+
+fn build_freeze_supply_instruction(params: FreezeSupplyParams) -> Result<Instruction> {
+    let role_pda = find_role_pda(&params.config_pda, &params.authority, &stablecoin_core::ID).0;
+    let accounts = vec![
+        AccountMeta::new_readonly(params.authority, true),
+        AccountMeta::new(params.config_pda, false),
+        AccountMeta::new_readonly(role_pda, false),
+        AccountMeta::new(params.mint, false),
+        AccountMeta::new_readonly(params.token_program, false),
+    ];
+    let data = FreezeSupplyArgs {
+        null_mint_authority: params.null_mint_authority,
+    }
+    .try_to_vec()?;
+    Ok(build_instruction(
+        "freeze_supply",
+        data,
+        accounts,
+        stablecoin_core::ID,
+    ))
+}
+
+struct UpdateQuotaWindowParams {
+    authority: Pubkey,
+    config_pda: Pubkey,
+    quota_window_seconds: i64,
+}
+
+fn build_update_quota_window_instruction(params: UpdateQuotaWindowParams) -> Result<Instruction> {
+    let role_pda = find_role_pda(&params.config_pda, &params.authority, &stablecoin_core::ID).0;
+    let accounts = vec![
+        AccountMeta::new_readonly(params.authority, true),
+        AccountMeta::new(params.config_pda, false),
+        AccountMeta::new_readonly(role_pda, false),
+    ];
+    let data = UpdateQuotaWindowArgs {
+        quota_window_seconds: params.quota_window_seconds,
+    }
+    .try_to_vec()?;
+    Ok(build_instruction(
+        "update_quota_window",
+        data,
+        accounts,
+        stablecoin_core::ID,
+    ))
+}
+
+struct UpdateTransferLimitParams {
+    authority: Pubkey,
+    config_pda: Pubkey,
+    max_transfer_amount: Option<u64>,
+}
+
+fn build_update_transfer_limit_instruction(
+    params: UpdateTransferLimitParams,
+) -> Result<Instruction> {
+    let role_pda = find_role_pda(&params.config_pda, &params.authority, &stablecoin_core::ID).0;
+    let accounts = vec![
+        AccountMeta::new_readonly(params.authority, true),
+        AccountMeta::new(params.config_pda, false),
+        AccountMeta::new_readonly(role_pda, false),
+    ];
+    let data = UpdateTransferLimitArgs {
+        max_transfer_amount: params.max_transfer_amount,
+    }
+    .try_to_vec()?;
+    Ok(build_instruction(
+        "update_transfer_limit",
+        data,
+        accounts,
+        stablecoin_core::ID,
+    ))
+}
+
+struct UpdateSelfTransferParams {
+    authority: Pubkey,
+    config_pda: Pubkey,
+    allow_self_transfer: bool,
+}
+
+fn build_update_self_transfer_instruction(params: UpdateSelfTransferParams) -> Result<Instruction> {
+    let role_pda = find_role_pda(&params.config_pda, &params.authority, &stablecoin_core::ID).0;
+    let accounts = vec![
+        AccountMeta::new_readonly(params.authority, true),
+        AccountMeta::new(params.config_pda, false),
+        AccountMeta::new_readonly(role_pda, false),
+    ];
+    let data = UpdateSelfTransferArgs {
+        allow_self_transfer: params.allow_self_transfer,
+    }
+    .try_to_vec()?;
+    Ok(build_instruction(
+        "update_self_transfer",
+        data,
+        accounts,
+        stablecoin_core::ID,
+    ))
+}
+
+struct UpdateMintApprovalParams {
+    authority: Pubkey,
+    config_pda: Pubkey,
+    require_mint_approval: bool,
+}
+
+fn build_update_mint_approval_instruction(params: UpdateMintApprovalParams) -> Result<Instruction> {
+    let role_pda = find_role_pda(&params.config_pda, &params.authority, &stablecoin_core::ID).0;
+    let accounts = vec![
+        AccountMeta::new_readonly(params.authority, true),
+        AccountMeta::new(params.config_pda, false),
+        AccountMeta::new_readonly(role_pda, false),
+    ];
+    let data = UpdateMintApprovalArgs {
+        require_mint_approval: params.require_mint_approval,
+    }
+    .try_to_vec()?;
+    Ok(build_instruction(
+        "update_mint_approval",
+        data,
+        accounts,
+        stablecoin_core::ID,
+    ))
+}
+
+struct UpdateDefaultAccountStateParams {
+    authority: Pubkey,
+    config_pda: Pubkey,
+    mint: Pubkey,
+    default_frozen: bool,
+}
+
+fn build_update_default_account_state_instruction(
+    params: UpdateDefaultAccountStateParams,
+) -> Result<Instruction> {
+    let role_pda = find_role_pda(&params.config_pda, &params.authority, &stablecoin_core::ID).0;
+    let accounts = vec![
+        AccountMeta::new_readonly(params.authority, true),
+        AccountMeta::new(params.config_pda, false),
+        AccountMeta::new_readonly(role_pda, false),
+        AccountMeta::new(params.mint, false),
+        AccountMeta::new_readonly(spl_token_2022::id(), false),
+    ];
+    let data = UpdateDefaultAccountStateArgs {
+        default_frozen: params.default_frozen,
+    }
+    .try_to_vec()?;
+    Ok(build_instruction(
+        "update_default_account_state",
+        data,
+        accounts,
+        stablecoin_core::ID,
+    ))
+}
+
+struct UpdateTreasuryParams {
+    authority: Pubkey,
+    config_pda: Pubkey,
+    treasury: Option<Pubkey>,
+}
+
+fn build_update_treasury_instruction(params: UpdateTreasuryParams) -> Result<Instruction> {
+    let role_pda = find_role_pda(&params.config_pda, &params.authority, &stablecoin_core::ID).0;
+    let accounts = vec![
+        AccountMeta::new_readonly(params.authority, true),
+        AccountMeta::new(params.config_pda, false),
+        AccountMeta::new_readonly(role_pda, false),
+    ];
+    let data = UpdateTreasuryArgs {
+        treasury: params.treasury,
+    }
+    .try_to_vec()?;
+    Ok(build_instruction(
+        "update_treasury",
+        data,
+        accounts,
+        stablecoin_core::ID,
+    ))
+}
+
+struct MigrateConfigParams {
+    payer: Pubkey,
+    config_pda: Pubkey,
+}
+
+fn build_migrate_config_instruction(params: MigrateConfigParams) -> Result<Instruction> {
+    let accounts = vec![
+        AccountMeta::new(params.payer, true),
+        AccountMeta::new(params.config_pda, false),
+        AccountMeta::new_readonly(system_program::ID, false),
+    ];
+    Ok(build_instruction(
+        "migrate_config",
+        Vec::new(),
+        accounts,
+        stablecoin_core::ID,
+    ))
+}
+
+struct UpdateTransferHookProgramParams {
+    authority: Pubkey,
+    config_pda: Pubkey,
+    mint: Pubkey,
+    hook_program: Pubkey,
+}
+
+fn build_update_transfer_hook_program_instruction(
+    params: UpdateTransferHookProgramParams,
+) -> Result<Instruction> {
+    let role_pda = find_role_pda(&params.config_pda, &params.authority, &stablecoin_core::ID).0;
+    let extra_metas = find_extra_account_metas_pda(&params.mint, &params.hook_program).0;
+    let accounts = vec![
+        AccountMeta::new(params.authority, true),
+        AccountMeta::new(params.config_pda, false),
+        AccountMeta::new_readonly(role_pda, false),
+        AccountMeta::new(params.mint, false),
+        AccountMeta::new_readonly(params.hook_program, false),
+        AccountMeta::new(extra_metas, false),
+        AccountMeta::new_readonly(spl_token_2022::id(), false),
+        AccountMeta::new_readonly(system_program::id(), false),
+    ];
+    Ok(build_instruction(
+        "update_transfer_hook_program",
+        Vec::new(),
+        accounts,
+        stablecoin_core::ID,
+    ))
+}
+
+struct EnableTransferHookParams {
+    authority: Pubkey,
+    config_pda: Pubkey,
+    mint: Pubkey,
+    hook_program: Pubkey,
+}
+
+fn build_enable_transfer_hook_instruction(params: EnableTransferHookParams) -> Result<Instruction> {
+    let role_pda = find_role_pda(&params.config_pda, &params.authority, &stablecoin_core::ID).0;
+    let extra_metas = find_extra_account_metas_pda(&params.mint, &params.hook_program).0;
+    let accounts = vec![
+        AccountMeta::new(params.authority, true),
+        AccountMeta::new(params.config_pda, false),
+        AccountMeta::new_readonly(role_pda, false),
+        AccountMeta::new(params.mint, false),
+        AccountMeta::new_readonly(params.hook_program, false),
+        AccountMeta::new(extra_metas, false),
+        AccountMeta::new_readonly(spl_token_2022::id(), false),
+        AccountMeta::new_readonly(system_program::id(), false),
+    ];
+    Ok(build_instruction(
+        "enable_transfer_hook",
+        Vec::new(),
+        accounts,
+        stablecoin_core::ID,
+    ))
+}
+
+struct InitExtraMetasParams {
+    payer: Pubkey,
+    mint: Pubkey,
+    hook_program: Pubkey,
+    allowlist_mode: bool,
+    transfer_cooldown: bool,
+}
+
+/// The hook's InitializeExtraAccountMetaList, built by the same code
+/// initialize runs on-chain so the list always matches what core expects.
+fn build_init_extra_metas_instruction(params: InitExtraMetasParams) -> Result<Instruction> {
+    stablecoin_core::instructions::initialize::initialize_extra_account_metas_ix(
+        &params.hook_program,
+        &params.mint,
+        &params.payer,
+        params.allowlist_mode,
+        params.transfer_cooldown,
+    )
+    .map_err(|err| anyhow!("Failed to build the extra account metas list: {}", err))
+}
+
+struct InitLastTransferParams {
+    payer: Pubkey,
+    config_pda: Pubkey,
+    owner: Pubkey,
+    hook_program: Pubkey,
+}
+
+/// The hook's initialize_last_transfer; a no-op when the account exists.
+fn build_init_last_transfer_instruction(params: InitLastTransferParams) -> Instruction {
+    let last_transfer =
+        find_last_transfer_pda(&params.config_pda, &params.owner, &params.hook_program).0;
+    let accounts = vec![
+        AccountMeta::new(params.payer, true),
+        AccountMeta::new_readonly(params.config_pda, false),
+        AccountMeta::new_readonly(params.owner, false),
+        AccountMeta::new(last_transfer, false),
+        AccountMeta::new_readonly(system_program::id(), false),
+    ];
+    build_instruction(
+        "initialize_last_transfer",
+        Vec::new(),
+        accounts,
+        params.hook_program,
+    )
+}
+
+struct UpdateMetadataParams {
+    authority: Pubkey,
+    config_pda: Pubkey,
+    mint: Pubkey,
+    name: Option<String>,
+    symbol: Option<String>,
+    uri: Option<String>,
+}
+
+fn build_update_metadata_instruction(params: UpdateMetadataParams) -> Result<Instruction> {
+    let role_pda = find_role_pda(&params.config_pda, &params.authority, &stablecoin_core::ID).0;
+    let accounts = vec![
+        AccountMeta::new(params.authority, true),
+        AccountMeta::new(params.config_pda, false),
+        AccountMeta::new_readonly(role_pda, false),
+        AccountMeta::new(params.mint, false),
+        AccountMeta::new_readonly(spl_token_2022::id(), false),
+        AccountMeta::new_readonly(system_program::id(), false),
+    ];
+    let data = UpdateMetadataArgs {
+        name: params.name,
+        symbol: params.symbol,
+        uri: params.uri,
+    }
+    .try_to_vec()?;
+    Ok(build_instruction(
+        "update_metadata",
+        data,
+        accounts,
+        stablecoin_core::ID,
+    ))
+}
+
+struct UpdateMetadataFieldParams {
+    authority: Pubkey,
+    config_pda: Pubkey,
+    mint: Pubkey,
+    key: String,
+    value: String,
+}
+
+fn build_update_metadata_field_instruction(
+    params: UpdateMetadataFieldParams,
+) -> Result<Instruction> {
+    let role_pda = find_role_pda(&params.config_pda, &params.authority, &stablecoin_core::ID).0;
+    let accounts = vec![
+        AccountMeta::new(params.authority, true),
+        AccountMeta::new(params.config_pda, false),
+        AccountMeta::new_readonly(role_pda, false),
+        AccountMeta::new(params.mint, false),
+        AccountMeta::new_readonly(spl_token_2022::id(), false),
+        AccountMeta::new_readonly(system_program::id(), false),
+    ];
+    let data = UpdateMetadataFieldArgs {
+        key: params.key,
+        value: params.value,
+    }
+    .try_to_vec()?;
+    Ok(build_instruction(
+        "update_metadata_field",
+        data,
+        accounts,
+        stablecoin_core::ID,
+    ))
+}
+
+struct AddToBlacklistParams {
+    blacklister: Pubkey,
+    config_pda: Pubkey,
+    wallet: Pubkey,
+    reason: String,
+    expires_at: Option<i64>,
+    reason_code: u8,
+}
+
+fn build_add_to_blacklist_instruction(params: AddToBlacklistParams) -> Result<Instruction> {
+    let role_pda = find_role_pda(
+        &params.config_pda,
+        &params.blacklister,
+        &stablecoin_core::ID,
+    )
+    .0;
+    let blacklist_pda =
+        find_blacklist_pda(&params.config_pda, &params.wallet, &stablecoin_core::ID).0;
+    let accounts = vec![
+        AccountMeta::new(params.blacklister, true),
+        AccountMeta::new(params.config_pda, false),
+        AccountMeta::new(role_pda, false),
+        AccountMeta::new(blacklist_pda, false),
+        AccountMeta::new_readonly(params.wallet, false),
+        AccountMeta::new_readonly(system_program::id(), false),
+    ];
+    let data = AddToBlacklistArgs {
+        wallet: params.wallet,
+        reason: params.reason,
+        expires_at: params.expires_at,
+        reason_code: params.reason_code,
+    }
+    .try_to_vec()?;
+    Ok(build_instruction(
+        "add_to_blacklist",
+        data,
+        accounts,
+        stablecoin_core::ID,
+    ))
+}
+
+struct UpdateBlacklistEntryParams {
+    blacklister: Pubkey,
+    config_pda: Pubkey,
+    blacklist_entry: Pubkey,
+    reason: String,
+    reason_code: Option<u8>,
+}
+
+fn build_update_blacklist_entry_instruction(
+    params: UpdateBlacklistEntryParams,
+) -> Result<Instruction> {
+    let role_pda = find_role_pda(
+        &params.config_pda,
+        &params.blacklister,
+        &stablecoin_core::ID,
+    )
+    .0;
+    let accounts = vec![
+        AccountMeta::new_readonly(params.blacklister, true),
+        AccountMeta::new(params.config_pda, false),
+        AccountMeta::new_readonly(role_pda, false),
+        AccountMeta::new(params.blacklist_entry, false),
+    ];
+    let data = UpdateBlacklistEntryArgs {
+        reason: params.reason,
+        reason_code: params.reason_code,
+    }
+    .try_to_vec()?;
+    Ok(build_instruction(
+        "update_blacklist_entry",
+        data,
+        accounts,
+        stablecoin_core::ID,
+    ))
+}
+
+struct RemoveFromBlacklistParams {
+    blacklister: Pubkey,
+    config_pda: Pubkey,
+    blacklist_entry: Pubkey,
+}
+
+fn build_remove_from_blacklist_instruction(
+    params: RemoveFromBlacklistParams,
+) -> Result<Instruction> {
+    let role_pda = find_role_pda(
+        &params.config_pda,
+        &params.blacklister,
+        &stablecoin_core::ID,
+    )
+    .0;
+    let accounts = vec![
+        AccountMeta::new(params.blacklister, true),
+        AccountMeta::new(params.config_pda, false),
+        AccountMeta::new(role_pda, false),
+        AccountMeta::new(params.blacklist_entry, false),
+    ];
+    Ok(build_instruction(
+        "remove_from_blacklist",
+        Vec::new(),
+        accounts,
+        stablecoin_core::ID,
+    ))
+}
+
+struct CloseBlacklistEntryParams {
+    blacklister: Pubkey,
+    config_pda: Pubkey,
+    blacklist_entry: Pubkey,
+}
+
+fn build_close_blacklist_entry_instruction(
+    params: CloseBlacklistEntryParams,
+) -> Result<Instruction> {
+    let role_pda = find_role_pda(
+        &params.config_pda,
+        &params.blacklister,
+        &stablecoin_core::ID,
+    )
+    .0;
+    let accounts = vec![
+        AccountMeta::new(params.blacklister, true),
+        AccountMeta::new_readonly(params.config_pda, false),
+        AccountMeta::new_readonly(role_pda, false),
+        AccountMeta::new(params.blacklist_entry, false),
+    ];
+    Ok(build_instruction(
+        "close_blacklist_entry",
+        Vec::new(),
+        accounts,
+        stablecoin_core::ID,
+    ))
+}
+
+struct RenounceRoleParams {
+    authority: Pubkey,
+    config_pda: Pubkey,
+    roles: u8,
+}
+
+fn build_renounce_role_instruction(params: RenounceRoleParams) -> Result<Instruction> {
+    let role_pda = find_role_pda(&params.config_pda, &params.authority, &stablecoin_core::ID).0;
+    let accounts = vec![
+        AccountMeta::new(params.authority, true),
+        AccountMeta::new_readonly(params.config_pda, false),
+        AccountMeta::new(role_pda, false),
+    ];
+    Ok(build_instruction(
+        "renounce_role",
+        params.roles.try_to_vec()?,
+        accounts,
+        stablecoin_core::ID,
+    ))
+}
+
+struct AddToAllowlistParams {
+    blacklister: Pubkey,
+    config_pda: Pubkey,
+    wallet: Pubkey,
+}
+
+fn build_add_to_allowlist_instruction(params: AddToAllowlistParams) -> Result<Instruction> {
+    let role_pda = find_role_pda(
+        &params.config_pda,
+        &params.blacklister,
+        &stablecoin_core::ID,
+    )
+    .0;
+    let allowlist_pda =
+        find_allowlist_pda(&params.config_pda, &params.wallet, &stablecoin_core::ID).0;
+    let accounts = vec![
+        AccountMeta::new(params.blacklister, true),
+        AccountMeta::new(params.config_pda, false),
+        AccountMeta::new(role_pda, false),
+        AccountMeta::new(allowlist_pda, false),
+        AccountMeta::new_readonly(params.wallet, false),
+        AccountMeta::new_readonly(system_program::id(), false),
+    ];
+    let data = AddToAllowlistArgs {
+        wallet: params.wallet,
+    }
+    .try_to_vec()?;
+    Ok(build_instruction(
+        "add_to_allowlist",
+        data,
+        accounts,
+        stablecoin_core::ID,
+    ))
+}
+
+struct RemoveFromAllowlistParams {
+    blacklister: Pubkey,
+    config_pda: Pubkey,
+    allowlist_entry: Pubkey,
+}
+
+fn build_remove_from_allowlist_instruction(
+    params: RemoveFromAllowlistParams,
+) -> Result<Instruction> {
+    let role_pda = find_role_pda(
+        &params.config_pda,
+        &params.blacklister,
+        &stablecoin_core::ID,
+    )
+    .0;
+    let accounts = vec![
+        AccountMeta::new(params.blacklister, true),
+        AccountMeta::new(params.config_pda, false),
+        AccountMeta::new(role_pda, false),
+        AccountMeta::new(params.allowlist_entry, false),
+    ];
+    Ok(build_instruction(
+        "remove_from_allowlist",
+        Vec::new(),
+        accounts,
+        stablecoin_core::ID,
+    ))
+}
+
+struct SeizeParams {
+    seizer: Pubkey,
+    config_pda: Pubkey,
+    mint: Pubkey,
+    target_ata: Pubkey,
+    target_owner: Pubkey,
+    treasury_ata: Pubkey,
+    treasury_owner: Pubkey,
+    transfer_hook_program: Pubkey,
+    /// None seizes the full balance.
+    amount: Option<u64>,
+    /// Forward the target owner's LastTransfer PDA to the hook.
+    transfer_cooldown: bool,
+}
+
+fn build_seize_instruction(params: SeizeParams) -> Result<Instruction> {
+    let role_pda = find_role_pda(&params.config_pda, &params.seizer, &stablecoin_core::ID).0;
+    let blacklist_entry = find_blacklist_pda(
+        &params.config_pda,
+        &params.target_owner,
+        &stablecoin_core::ID,
+    )
+    .0;
+    // The hook derives the destination entry from the treasury's owner.
+    let destination_blacklist_entry = find_blacklist_pda(
+        &params.config_pda,
+        &params.treasury_owner,
+        &stablecoin_core::ID,
+    )
+    .0;
+    let mut accounts = vec![
+        AccountMeta::new(params.seizer, true),
+        AccountMeta::new(params.config_pda, false),
+        AccountMeta::new(role_pda, false),
+        AccountMeta::new_readonly(params.mint, false),
+        AccountMeta::new(params.target_ata, false),
+        AccountMeta::new(params.treasury_ata, false),
+        AccountMeta::new_readonly(blacklist_entry, false),
+        AccountMeta::new_readonly(
+            find_extra_account_metas_pda(&params.mint, &params.transfer_hook_program).0,
+            false,
+        ),
+        AccountMeta::new_readonly(stablecoin_core::ID, false),
+        AccountMeta::new_readonly(destination_blacklist_entry, false),
+        AccountMeta::new_readonly(params.transfer_hook_program, false),
+        AccountMeta::new_readonly(spl_token_2022::id(), false),
+    ];
+    if params.transfer_cooldown {
+        accounts.push(AccountMeta::new(
+            find_last_transfer_pda(
+                &params.config_pda,
+                &params.target_owner,
+                &params.transfer_hook_program,
+            )
+            .0,
+            false,
+        ));
+    }
+    Ok(build_instruction(
+        "seize",
+        params.amount.try_to_vec()?,
+        accounts,
+        stablecoin_core::ID,
+    ))
+}
+
+struct SeizeAndBurnParams {
+    seizer: Pubkey,
+    config_pda: Pubkey,
+    mint: Pubkey,
+    target_ata: Pubkey,
+    target_owner: Pubkey,
+}
+
+fn build_seize_and_burn_instruction(params: SeizeAndBurnParams) -> Instruction {
+    let role_pda = find_role_pda(&params.config_pda, &params.seizer, &stablecoin_core::ID).0;
+    let blacklist_entry = find_blacklist_pda(
+        &params.config_pda,
+        &params.target_owner,
+        &stablecoin_core::ID,
+    )
+    .0;
+    let accounts = vec![
+        AccountMeta::new_readonly(params.seizer, true),
+        AccountMeta::new(params.config_pda, false),
+        AccountMeta::new_readonly(role_pda, false),
+        AccountMeta::new(params.mint, false),
+        AccountMeta::new(params.target_ata, false),
+        AccountMeta::new_readonly(blacklist_entry, false),
+        AccountMeta::new_readonly(spl_token_2022::id(), false),
+    ];
+    build_instruction("seize_and_burn", Vec::new(), accounts, stablecoin_core::ID)
+}
+
+#[derive(Serialize)]
+struct InitOutput {
+    mint: String,
+    config: String,
+    preset: String,
+    signature: String,
+    explorer: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    compute_budget: Option<ComputeBudgetOutput>,
+}
+
+#[derive(Serialize)]
+struct MintOutput {
+    signature: String,
+    explorer: Option<String>,
+    #[serde(flatten)]
+    pdas: PdaOutput,
+    new_supply: String,
+    /// Left of the minter's window quota; None when it has no quota.
+    quota_remaining: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    compute_budget: Option<ComputeBudgetOutput>,
+}
+
+#[derive(Serialize)]
+struct MintRequestOutput {
+    signature: String,
+    explorer: Option<String>,
+    #[serde(flatten)]
+    pdas: PdaOutput,
+    request: String,
+    id: u64,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    compute_budget: Option<ComputeBudgetOutput>,
+}
+
+#[derive(Serialize)]
+struct MintBatchOutput {
+    recipients: usize,
+    total_amount: String,
+    new_supply: String,
+    batches: Vec<MintBatchResult>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    compute_budget: Option<ComputeBudgetOutput>,
+}
+
+#[derive(Serialize)]
+struct MintBatchResult {
+    signature: String,
+    explorer: Option<String>,
+    recipients: usize,
+}
+
+#[derive(Serialize)]
+struct BlacklistBatchResult {
+    wallet: String,
+    signature: Option<String>,
+    error: Option<String>,
+}
+
+impl BlacklistBatchResult {
+    /// A wallet already in the requested state, so nothing was sent for it.
+    fn unchanged(wallet: &Pubkey) -> Self {
+        Self {
+            wallet: wallet.to_string(),
+            signature: None,
+            error: None,
         }
-        Ok(())
     }
 }
 
-fn handle_burn(ctx: &OwnedContext, args: &BurnArgs) -> Result<()> {
-    let ctx_ref = ctx.as_ref();
-    let mint = resolve_mint(&args.mint)?;
-    let config_pda = find_config_pda(&mint, &stablecoin_core::ID).0;
-    let config = fetch_config(ctx_ref, &config_pda)?;
-    let amount = parse_amount(&args.amount, config.decimals)?;
-    let burner = ctx_ref.payer.pubkey();
-    let burner_ata =
-        get_associated_token_address_with_program_id(&burner, &mint, &spl_token_2022::id());
-    let burn_ix = build_burn_instruction(BurnParams {
-        burner,
-        mint,
-        burner_ata,
-        amount,
-    })?;
-    let signature = send_transaction(ctx_ref, vec![burn_ix], vec![])?;
-    let supply = ctx_ref.client.get_token_supply(&mint)?;
-    let explorer = explorer_url(&signature, ctx_ref.cluster);
-    if ctx_ref.output == OutputFormat::Json {
-        let output = BurnOutput {
-            signature: signature.clone(),
-            explorer,
-            new_supply: supply.amount,
-        };
-        print_json(&output)
-    } else {
-        println!(
-            "Burned {} tokens from {}",
-            format_amount(amount, config.decimals),
-            burner
-        );
-        println!("New supply: {}", supply.amount);
-        println!("Tx: {}", signature);
-        if let Some(url) = explorer {
-            println!("Explorer: {}", url);
+#[derive(Serialize)]
+struct SeizeBatchOutput {
+    dry_run: bool,
+    treasury: String,
+    total_amount: String,
+    ready: usize,
+    seized: usize,
+    skipped: usize,
+    rejected: usize,
+    failed: usize,
+    accounts: Vec<SeizeBatchResult>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    compute_budget: Option<ComputeBudgetOutput>,
+}
+
+#[derive(Serialize)]
+struct SeizeBatchResult {
+    input: String,
+    token_account: String,
+    owner: String,
+    amount: String,
+    status: String,
+    detail: Option<String>,
+    signature: Option<String>,
+    explorer: Option<String>,
+}
+
+#[derive(Serialize)]
+struct BurnOutput {
+    signature: String,
+    explorer: Option<String>,
+    #[serde(flatten)]
+    pdas: PdaOutput,
+    new_supply: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    compute_budget: Option<ComputeBudgetOutput>,
+}
+
+#[derive(Serialize)]
+struct TransferOutput {
+    signature: String,
+    explorer: Option<String>,
+    source: String,
+    destination: String,
+    created_destination: bool,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    compute_budget: Option<ComputeBudgetOutput>,
+}
+
+#[derive(Serialize)]
+struct SimpleOutput {
+    signature: String,
+    explorer: Option<String>,
+    /// None for transactions the CLI did not build, e.g. import-tx submit.
+    #[serde(flatten)]
+    pdas: Option<PdaOutput>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    compute_budget: Option<ComputeBudgetOutput>,
+}
+
+/// Config and signer role PDAs, so scripts can chain commands without
+/// deriving them.
+#[derive(Serialize)]
+struct PdaOutput {
+    config: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    role_account: Option<String>,
+}
+
+impl PdaOutput {
+    /// `signer` is the wallet whose role PDA authorized the instruction, or
+    /// None when no role account is involved.
+    fn new(config_pda: &Pubkey, signer: Option<&Pubkey>) -> Self {
+        Self {
+            config: config_pda.to_string(),
+            role_account: signer.map(|signer| {
+                find_role_pda(config_pda, signer, &stablecoin_core::ID)
+                    .0
+                    .to_string()
+            }),
         }
-        Ok(())
     }
 }
 
-fn handle_freeze(ctx: &OwnedContext, args: &AddressArgs) -> Result<()> {
-    let ctx_ref = ctx.as_ref();
-    let mint = resolve_mint(&args.mint)?;
-    let target = parse_pubkey(&args.address)?;
-    let config_pda = find_config_pda(&mint, &stablecoin_core::ID).0;
-    let freeze_ix = build_freeze_instruction(FreezeParams {
-        freezer: ctx_ref.payer.pubkey(),
-        mint,
-        target_ata: target,
-    })?;
-    let signature = send_transaction(ctx_ref, vec![freeze_ix], vec![])?;
-    let explorer = explorer_url(&signature, ctx_ref.cluster);
-    if ctx_ref.output == OutputFormat::Json {
-        let output = SimpleOutput {
-            signature: signature.clone(),
-            explorer,
-        };
-        print_json(&output)
-    } else {
-        println!("Frozen token account: {}", target);
-        println!("Config: {}", config_pda);
-        println!("Tx: {}", signature);
-        if let Some(url) = explorer {
-            println!("Explorer: {}", url);
+#[derive(Serialize)]
+struct ComputeBudgetOutput {
+    priority_fee_micro_lamports: Option<u64>,
+    compute_unit_limit: Option<u32>,
+}
+
+#[derive(Serialize)]
+struct BlacklistStatusOutput {
+    wallet: String,
+    is_active: bool,
+    reason: Option<String>,
+    reason_code: Option<&'static str>,
+    expires_at: Option<i64>,
+}
+
+#[derive(Serialize)]
+struct AllowlistStatusOutput {
+    wallet: String,
+    is_active: bool,
+}
+
+#[derive(Serialize)]
+struct MintersOutput {
+    decimals: u8,
+    minters: Vec<MinterInfo>,
+}
+
+#[derive(Serialize, Clone)]
+struct MinterInfo {
+    address: String,
+    /// Remaining quota in base units.
+    quota: Option<String>,
+    /// The same quota scaled by the mint decimals.
+    quota_ui: Option<String>,
+    lifetime_minted: String,
+    lifetime_minted_ui: String,
+}
+
+#[derive(Serialize)]
+struct MinterStatsOutput {
+    address: String,
+    is_minter: bool,
+    decimals: u8,
+    quota: Option<String>,
+    minted_current_window: String,
+    window_ends_at: Option<i64>,
+    window_resets: u32,
+    lifetime_minted: String,
+    lifetime_quota: Option<String>,
+    lifetime_remaining: Option<String>,
+}
+
+#[derive(Serialize)]
+struct RolesListOutput {
+    roles: Vec<RoleHolder>,
+}
+
+#[derive(Serialize)]
+struct RoleHolder {
+    address: String,
+    role_account: String,
+    roles: u8,
+    names: Vec<&'static str>,
+}
+
+#[derive(Serialize)]
+struct RolesAuditOutput {
+    config: String,
+    authority: String,
+    role_accounts: usize,
+    master_holders: Vec<String>,
+    ok: bool,
+    issues: Vec<String>,
+}
+
+#[derive(Serialize)]
+struct RolesRepairOutput {
+    config: String,
+    role_accounts: Vec<RoleBumpCheck>,
+    mismatches: usize,
+}
+
+#[derive(Serialize)]
+struct RoleBumpCheck {
+    role_account: String,
+    authority: String,
+    stored_bump: u8,
+    canonical_bump: u8,
+    ok: bool,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    likely_source: Option<String>,
+}
+
+#[derive(Serialize)]
+struct HookDescriptorOutput {
+    program_id: String,
+    extra_account_metas_seeds: Vec<String>,
+    instructions: Vec<HookInstructionOutput>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    account_types: Option<Vec<HookAccountTypeOutput>>,
+}
+
+#[derive(Serialize)]
+struct HookInstructionOutput {
+    name: String,
+    discriminator: Vec<u8>,
+    accounts: Vec<HookAccountOutput>,
+}
+
+#[derive(Serialize)]
+struct HookAccountOutput {
+    index: usize,
+    name: String,
+    writable: bool,
+    signer: bool,
+}
+
+#[derive(Serialize)]
+struct HookAccountTypeOutput {
+    name: String,
+    owner: String,
+    discriminator: Vec<u8>,
+    fields: Vec<HookFieldOutput>,
+}
+
+#[derive(Serialize)]
+struct HookFieldOutput {
+    name: String,
+    #[serde(rename = "type")]
+    ty: String,
+}
+
+#[derive(Serialize)]
+struct SimulationOutput {
+    logs: Vec<String>,
+    units_consumed: Option<u64>,
+    fee_lamports: Option<u64>,
+    err: Option<String>,
+}
+
+#[derive(Serialize)]
+struct ExportedTransactionOutput {
+    path: String,
+    index: usize,
+    message_hash: String,
+    fee_payer: String,
+    missing_signers: Vec<String>,
+}
+
+#[derive(Serialize)]
+struct MultisigProposalOutput {
+    authority: String,
+    instructions: Vec<InstructionOutput>,
+}
+
+#[derive(Serialize)]
+struct InstructionOutput {
+    program_id: String,
+    accounts: Vec<AccountMetaOutput>,
+    data: String,
+}
+
+#[derive(Serialize)]
+struct AccountMetaOutput {
+    pubkey: String,
+    is_signer: bool,
+    is_writable: bool,
+}
+
+impl From<&Instruction> for InstructionOutput {
+    fn from(instruction: &Instruction) -> Self {
+        Self {
+            program_id: instruction.program_id.to_string(),
+            accounts: instruction
+                .accounts
+                .iter()
+                .map(|meta| AccountMetaOutput {
+                    pubkey: meta.pubkey.to_string(),
+                    is_signer: meta.is_signer,
+                    is_writable: meta.is_writable,
+                })
+                .collect(),
+            data: base64::engine::general_purpose::STANDARD.encode(&instruction.data),
         }
-        Ok(())
     }
 }
 
-fn handle_thaw(ctx: &OwnedContext, args: &AddressArgs) -> Result<()> {
-    let ctx_ref = ctx.as_ref();
-    let mint = resolve_mint(&args.mint)?;
-    let target = parse_pubkey(&args.address)?;
-    let config_pda = find_config_pda(&mint, &stablecoin_core::ID).0;
-    let thaw_ix = build_thaw_instruction(FreezeParams {
-        freezer: ctx_ref.payer.pubkey(),
-        mint,
-        target_ata: target,
-    })?;
-    let signature = send_transaction(ctx_ref, vec![thaw_ix], vec![])?;
-    let explorer = explorer_url(&signature, ctx_ref.cluster);
-    if ctx_ref.output == OutputFormat::Json {
-        let output = SimpleOutput {
-            signature: signature.clone(),
-            explorer,
-        };
-        print_json(&output)
-    } else {
-        println!("Thawed token account: {}", target);
-        println!("Config: {}", config_pda);
-        println!("Tx: {}", signature);
-        if let Some(url) = explorer {
-            println!("Explorer: {}", url);
-        }
-        Ok(())
+#[derive(Serialize)]
+struct StatusOutput {
+    mint: String,
+    preset: String,
+    is_paused: bool,
+    paused_operations: Vec<&'static str>,
+    pause_cooldown: i64,
+    lock_admin_ops: bool,
+    minting_locked: bool,
+    /// False when the mint's mint or freeze authority is not the config PDA.
+    mint_authority_ok: bool,
+    freeze_authority_ok: bool,
+    supply: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    max_supply: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    headroom: Option<String>,
+    total_minted: String,
+    total_burned: String,
+    total_seized: String,
+    creator: String,
+    created_at: i64,
+    last_action_at: i64,
+    last_action_by: String,
+    features: FeatureOutput,
+    role_counts: RoleCounts,
+    blacklisted: usize,
+    blacklisted_by_code: BlacklistReasonCounts,
+    /// Only present with --detailed.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    minters: Option<Vec<MinterDetail>>,
+}
+
+#[derive(Serialize, Debug, PartialEq, Eq)]
+struct MinterDetail {
+    address: String,
+    quota: Option<String>,
+    quota_ui: Option<String>,
+    minted_current_window: String,
+    minted_current_window_ui: String,
+    /// Unix start of the minter's last window; 0 before its first mint.
+    window_start: i64,
+    window_seconds: i64,
+}
+
+#[derive(Serialize)]
+struct AliasListOutput {
+    path: String,
+    aliases: Vec<AliasOutput>,
+}
+
+#[derive(Serialize)]
+struct AliasOutput {
+    name: String,
+    target: String,
+    pubkey: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    error: Option<String>,
+}
+
+#[derive(Serialize)]
+struct ConfigCheckOutput {
+    config: String,
+    errors: Vec<String>,
+    warnings: Vec<String>,
+}
+
+#[derive(Serialize)]
+struct MetadataOutput {
+    mint: String,
+    metadata_address: Option<String>,
+    pointer_authority: Option<String>,
+    /// Whether the TokenMetadata extension lives in the mint itself.
+    embedded: bool,
+    update_authority: Option<String>,
+    name: String,
+    symbol: String,
+    uri: String,
+    additional_metadata: Vec<MetadataFieldOutput>,
+    metaplex_metadata: String,
+    metaplex_metadata_exists: bool,
+}
+
+#[derive(Serialize)]
+struct MetadataFieldOutput {
+    key: String,
+    value: String,
+}
+
+#[derive(Serialize)]
+struct ConfigOutput {
+    address: String,
+    authority: String,
+    mint: String,
+    name: String,
+    symbol: String,
+    uri: String,
+    decimals: u8,
+    is_paused: bool,
+    pause_flags: u8,
+    total_minted: String,
+    total_burned: String,
+    audit_counter: u64,
+    features: FeatureOutput,
+    feature_bits: u32,
+    transfer_hook_program: Option<String>,
+    bump: u8,
+    pause_cooldown: i64,
+    last_pause_change: i64,
+    max_supply: Option<String>,
+    creator: String,
+    created_at: i64,
+    quota_window_seconds: i64,
+    allowlist_mode: bool,
+    max_transfer_amount: Option<String>,
+    last_action_at: i64,
+    last_action_by: String,
+    allow_self_transfer: bool,
+    total_seized: String,
+    treasury: Option<String>,
+    lock_admin_ops: bool,
+    minting_locked: bool,
+    token_program: String,
+    transfer_cooldown_seconds: Option<i64>,
+    require_mint_approval: bool,
+}
+
+#[derive(Serialize)]
+struct FeatureOutput {
+    permanent_delegate: bool,
+    transfer_hook: bool,
+    confidential: bool,
+    default_frozen: bool,
+    transfer_cooldown: bool,
+}
+
+#[derive(Serialize, Debug, PartialEq, Eq)]
+struct RoleCounts {
+    masters: usize,
+    minters: usize,
+    burners: usize,
+    freezers: usize,
+    pausers: usize,
+    blacklisters: usize,
+    seizers: usize,
+    role_admins: usize,
+}
+
+#[derive(Serialize, Default, Debug, PartialEq, Eq)]
+struct BlacklistReasonCounts {
+    sanctions: usize,
+    fraud: usize,
+    court_order: usize,
+    other: usize,
+    unspecified: usize,
+}
+
+#[derive(Serialize)]
+struct AuthorityReport {
+    mint: String,
+    config: String,
+    ok: bool,
+    checks: Vec<AuthorityCheck>,
+}
+
+#[derive(Serialize)]
+struct AuthorityCheck {
+    authority: String,
+    actual: Option<String>,
+    status: AuthorityStatus,
+}
+
+#[derive(Serialize, Clone, Copy, PartialEq, Eq, Debug)]
+#[serde(rename_all = "snake_case")]
+enum AuthorityStatus {
+    Match,
+    Mismatch,
+    Unset,
+    NotPresent,
+    /// Unset on purpose after freeze_supply locked minting.
+    Renounced,
+}
+
+impl AuthorityReport {
+    /// True when the named authority is the config PDA, or the mint authority
+    /// was renounced after minting was locked.
+    fn authority_ok(&self, authority: &str) -> bool {
+        self.checks.iter().any(|check| {
+            check.authority == authority
+                && matches!(
+                    check.status,
+                    AuthorityStatus::Match | AuthorityStatus::Renounced
+                )
+        })
     }
 }
 
-fn handle_pause(ctx: &OwnedContext, args: &MintOnlyArgs) -> Result<()> {
-    let ctx_ref = ctx.as_ref();
-    let mint = resolve_mint(&args.mint)?;
-    let config_pda = find_config_pda(&mint, &stablecoin_core::ID).0;
-    let pause_ix = build_pause_instruction(PauseParams {
-        pauser: ctx_ref.payer.pubkey(),
-        config_pda,
-        unpause: false,
-    })?;
-    let signature = send_transaction(ctx_ref, vec![pause_ix], vec![])?;
-    let explorer = explorer_url(&signature, ctx_ref.cluster);
-    if ctx_ref.output == OutputFormat::Json {
-        let output = SimpleOutput {
-            signature: signature.clone(),
-            explorer,
-        };
-        print_json(&output)
-    } else {
-        println!("System paused");
-        println!("Config: {}", config_pda);
-        println!("Tx: {}", signature);
-        if let Some(url) = explorer {
-            println!("Explorer: {}", url);
+impl AuthorityStatus {
+    fn is_ok(self) -> bool {
+        matches!(
+            self,
+            AuthorityStatus::Match | AuthorityStatus::NotPresent | AuthorityStatus::Renounced
+        )
+    }
+
+    fn label(self) -> &'static str {
+        match self {
+            AuthorityStatus::Match => "match",
+            AuthorityStatus::Mismatch => "MISMATCH",
+            AuthorityStatus::Unset => "UNSET",
+            AuthorityStatus::NotPresent => "extension not present",
+            AuthorityStatus::Renounced => "renounced, minting locked",
         }
-        Ok(())
     }
 }
 
-fn handle_unpause(ctx: &OwnedContext, args: &MintOnlyArgs) -> Result<()> {
-    let ctx_ref = ctx.as_ref();
-    let mint = resolve_mint(&args.mint)?;
-    let config_pda = find_config_pda(&mint, &stablecoin_core::ID).0;
-    let unpause_ix = build_pause_instruction(PauseParams {
-        pauser: ctx_ref.payer.pubkey(),
-        config_pda,
-        unpause: true,
-    })?;
-    let signature = send_transaction(ctx_ref, vec![unpause_ix], vec![])?;
-    let explorer = explorer_url(&signature, ctx_ref.cluster);
-    if ctx_ref.output == OutputFormat::Json {
-        let output = SimpleOutput {
-            signature: signature.clone(),
-            explorer,
+impl AuthorityCheck {
+    /// `actual` is None when the extension is absent and Some(None) when the
+    /// authority slot exists but is unset.
+    fn new(authority: &str, actual: Option<Option<Pubkey>>, expected: &Pubkey) -> Self {
+        let status = match actual {
+            None => AuthorityStatus::NotPresent,
+            Some(None) => AuthorityStatus::Unset,
+            Some(Some(key)) if key == *expected => AuthorityStatus::Match,
+            Some(Some(_)) => AuthorityStatus::Mismatch,
         };
-        print_json(&output)
-    } else {
-        println!("System unpaused");
-        println!("Config: {}", config_pda);
-        println!("Tx: {}", signature);
-        if let Some(url) = explorer {
-            println!("Explorer: {}", url);
+        Self {
+            authority: authority.to_string(),
+            actual: actual.flatten().map(|key| key.to_string()),
+            status,
         }
-        Ok(())
     }
 }
 
-fn handle_blacklist(ctx: &OwnedContext, cmd: &BlacklistCmd) -> Result<()> {
-    let ctx_ref = ctx.as_ref();
-    match cmd {
-        BlacklistCmd::Add(args) => {
-            let mint = resolve_mint(&args.mint)?;
-            let config_pda = find_config_pda(&mint, &stablecoin_core::ID).0;
-            let config = fetch_config(ctx_ref, &config_pda)?;
-            if !config.features.transfer_hook {
-                return Err(anyhow!("Transfer hook not enabled for this stablecoin"));
-            }
-            let wallet = parse_pubkey(&args.address)?;
-            let add_ix = build_add_to_blacklist_instruction(AddToBlacklistParams {
-                blacklister: ctx_ref.payer.pubkey(),
-                config_pda,
-                wallet,
-                reason: args.reason.clone(),
-            })?;
-            let signature = send_transaction(ctx_ref, vec![add_ix], vec![])?;
-            let explorer = explorer_url(&signature, ctx_ref.cluster);
-            if ctx_ref.output == OutputFormat::Json {
-                let output = SimpleOutput {
-                    signature: signature.clone(),
-                    explorer,
-                };
-                print_json(&output)
-            } else {
-                println!("Blacklisted: {}", wallet);
-                println!("Tx: {}", signature);
-                if let Some(url) = explorer {
-                    println!("Explorer: {}", url);
-                }
-                Ok(())
-            }
-        }
-        BlacklistCmd::Remove(args) => {
-            let mint = resolve_mint(&args.mint)?;
-            let config_pda = find_config_pda(&mint, &stablecoin_core::ID).0;
-            let config = fetch_config(ctx_ref, &config_pda)?;
-            if !config.features.transfer_hook {
-                return Err(anyhow!("Transfer hook not enabled for this stablecoin"));
-            }
-            let wallet = parse_pubkey(&args.address)?;
-            let blacklist_entry = find_blacklist_pda(&config_pda, &wallet, &stablecoin_core::ID).0;
-            let remove_ix = build_remove_from_blacklist_instruction(RemoveFromBlacklistParams {
-                blacklister: ctx_ref.payer.pubkey(),
-                config_pda,
-                blacklist_entry,
-            })?;
-            let signature = send_transaction(ctx_ref, vec![remove_ix], vec![])?;
-            let explorer = explorer_url(&signature, ctx_ref.cluster);
-            if ctx_ref.output == OutputFormat::Json {
-                let output = SimpleOutput {
-                    signature: signature.clone(),
-                    explorer,
-                };
-                print_json(&output)
-            } else {
-                println!("Removed from blacklist: {}", wallet);
-                println!("Tx: {}", signature);
-                if let Some(url) = explorer {
-                    println!("Explorer: {}", url);
-                }
-                Ok(())
-            }
-        }
-        BlacklistCmd::Check(args) => {
-            let mint = resolve_mint(&args.mint)?;
-            let config_pda = find_config_pda(&mint, &stablecoin_core::ID).0;
-            let wallet = parse_pubkey(&args.address)?;
-            let blacklist_entry = find_blacklist_pda(&config_pda, &wallet, &stablecoin_core::ID).0;
-            let status = fetch_blacklist_entry(ctx_ref, &blacklist_entry)?;
-            if ctx_ref.output == OutputFormat::Json {
-                let output = BlacklistStatusOutput {
-                    wallet: wallet.to_string(),
-                    is_active: status
-                        .as_ref()
-                        .map(|entry| entry.is_active)
-                        .unwrap_or(false),
-                    reason: status.as_ref().map(|entry| entry.reason.clone()),
-                };
-                print_json(&output)
-            } else {
-                match status {
-                    Some(entry) if entry.is_active => {
-                        println!("Blacklisted: {}", wallet);
-                        println!("Reason: {}", entry.reason);
-                    }
-                    _ => println!("Not blacklisted: {}", wallet),
-                }
-                Ok(())
-            }
-        }
-    }
+#[derive(Serialize)]
+struct VerifyOutput {
+    mint: String,
+    config: String,
+    ok: bool,
+    checks: Vec<VerifyCheck>,
 }
 
-fn handle_seize(ctx: &OwnedContext, args: &SeizeArgs) -> Result<()> {
-    let ctx_ref = ctx.as_ref();
-    let mint = resolve_mint(&args.mint)?;
-    let config_pda = find_config_pda(&mint, &stablecoin_core::ID).0;
-    let config = fetch_config(ctx_ref, &config_pda)?;
-    if !config.features.permanent_delegate {
-        return Err(anyhow!(
-            "Permanent delegate not enabled for this stablecoin"
-        ));
-    }
-    let target_ata = parse_pubkey(&args.address)?;
-    let treasury_ata = parse_pubkey(&args.to)?;
-    let target_account = fetch_token_account(ctx_ref, &target_ata)?;
-    if target_account.mint != mint {
-        return Err(anyhow!("Target token account mint does not match"));
-    }
-    let blacklist_entry =
-        find_blacklist_pda(&config_pda, &target_account.owner, &stablecoin_core::ID).0;
-    let seize_ix = build_seize_instruction(SeizeParams {
-        seizer: ctx_ref.payer.pubkey(),
-        config_pda,
-        mint,
-        target_ata,
-        treasury_ata,
-        blacklist_entry,
-    })?;
-    let signature = send_transaction(ctx_ref, vec![seize_ix], vec![])?;
-    let explorer = explorer_url(&signature, ctx_ref.cluster);
-    if ctx_ref.output == OutputFormat::Json {
-        let output = SimpleOutput {
-            signature: signature.clone(),
-            explorer,
-        };
-        print_json(&output)
-    } else {
-        println!("Seized tokens from {}", target_ata);
-        println!("Tx: {}", signature);
-        if let Some(url) = explorer {
-            println!("Explorer: {}", url);
+#[derive(Serialize)]
+struct VerifyCheck {
+    check: &'static str,
+    passed: bool,
+    expected: String,
+    actual: String,
+}
+
+impl VerifyCheck {
+    fn new(check: &'static str, expected: String, actual: String) -> Self {
+        Self {
+            check,
+            passed: expected == actual,
+            expected,
+            actual,
         }
-        Ok(())
+    }
+
+    fn presence(check: &'static str, expected: bool, actual: bool) -> Self {
+        let label = |present: bool| if present { "present" } else { "absent" }.to_string();
+        Self::new(check, label(expected), label(actual))
     }
 }
 
-fn handle_minters(ctx: &OwnedContext, cmd: &MintersCmd) -> Result<()> {
-    let ctx_ref = ctx.as_ref();
-    match cmd {
-        MintersCmd::List(args) => {
-            let mint = resolve_mint(&args.mint)?;
-            let config_pda = find_config_pda(&mint, &stablecoin_core::ID).0;
-            let roles = list_role_accounts(ctx_ref, &config_pda)?;
-            let mut minters = Vec::new();
-            for entry in roles {
-                if entry.account.roles & ROLE_MINTER != 0 {
-                    minters.push(MinterInfo {
-                        address: entry.account.authority.to_string(),
-                        quota: entry.account.mint_quota.map(|value: u64| value.to_string()),
-                    });
-                }
-            }
-            if ctx_ref.output == OutputFormat::Json {
-                let output = MintersOutput {
-                    minters: minters.clone(),
-                };
-                print_json(&output)
-            } else {
-                if minters.is_empty() {
-                    println!("No minters found");
-                } else {
-                    for minter in minters {
-                        if let Some(quota) = minter.quota {
-                            println!("{} (quota: {})", minter.address, quota);
-                        } else {
-                            println!("{}", minter.address);
-                        }
-                    }
-                }
-                Ok(())
-            }
-        }
-        MintersCmd::Add(args) => {
-            let mint = resolve_mint(&args.mint)?;
-            let config_pda = find_config_pda(&mint, &stablecoin_core::ID).0;
-            let target = parse_pubkey(&args.address)?;
-            let existing = fetch_role_account(
-                ctx_ref,
-                &find_role_pda(&config_pda, &target, &stablecoin_core::ID).0,
-            )?;
-            let existing_roles = existing.map(|entry| entry.roles).unwrap_or(0);
-            let roles = existing_roles | ROLE_MINTER;
-            let quota = parse_amount(&args.quota, 0)?;
-            let ix = build_update_roles_instruction(UpdateRolesParams {
-                authority: ctx_ref.payer.pubkey(),
-                config_pda,
-                target,
-                roles,
-                mint_quota: Some(quota),
-            })?;
-            let signature = send_transaction(ctx_ref, vec![ix], vec![])?;
-            let explorer = explorer_url(&signature, ctx_ref.cluster);
-            if ctx_ref.output == OutputFormat::Json {
-                let output = SimpleOutput {
-                    signature: signature.clone(),
-                    explorer,
-                };
-                print_json(&output)
-            } else {
-                println!("Added minter: {}", target);
-                println!("Tx: {}", signature);
-                if let Some(url) = explorer {
-                    println!("Explorer: {}", url);
-                }
-                Ok(())
-            }
-        }
-        MintersCmd::Remove(args) => {
-            let mint = resolve_mint(&args.mint)?;
-            let config_pda = find_config_pda(&mint, &stablecoin_core::ID).0;
-            let target = parse_pubkey(&args.address)?;
-            let existing = fetch_role_account(
-                ctx_ref,
-                &find_role_pda(&config_pda, &target, &stablecoin_core::ID).0,
-            )?
-            .ok_or_else(|| anyhow!("Role account not found"))?;
-            let roles = existing.roles & !ROLE_MINTER;
-            let ix = build_update_roles_instruction(UpdateRolesParams {
-                authority: ctx_ref.payer.pubkey(),
-                config_pda,
-                target,
+#[derive(Serialize)]
+struct ComplianceReportOutput {
+    mint: String,
+    generated_at: i64,
+    supply: String,
+    total_minted: String,
+    total_burned: String,
+    audit_counter: u64,
+    role_counts: RoleCounts,
+    blacklist: BlacklistReport,
+}
+
+#[derive(Serialize, Debug, PartialEq, Eq)]
+struct BlacklistReport {
+    entries: usize,
+    active: usize,
+    inactive: usize,
+    by_code: BlacklistReasonCounts,
+}
+
+#[derive(Serialize)]
+struct SupplyOutput {
+    mint: String,
+    supply: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    holder_count: Option<usize>,
+}
+
+#[derive(Serialize, Clone)]
+struct HolderInfo {
+    owner: String,
+    token_account: String,
+    amount: u64,
+}
+
+#[derive(Serialize)]
+struct HoldersOutput {
+    /// Holders matching --min-balance before --offset and --limit.
+    total: usize,
+    holders: Vec<HolderInfo>,
+}
+
+#[derive(Serialize)]
+struct HoldersSnapshot {
+    mint: String,
+    slot: u64,
+    timestamp: i64,
+    decimals: u8,
+    supply: String,
+    holder_count: usize,
+    holders: Vec<HolderInfo>,
+}
+
+#[derive(Serialize)]
+struct HoldersSnapshotOutput {
+    path: String,
+    slot: u64,
+    timestamp: i64,
+    holder_count: usize,
+}
+
+#[derive(Serialize)]
+struct BalanceOutput {
+    owner: String,
+    token_account: String,
+    amount: u64,
+    is_frozen: bool,
+}
+
+#[derive(Serialize)]
+struct BalanceChangeOutput {
+    slot: u64,
+    timestamp: String,
+    balance: u64,
+    delta: String,
+    cause: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    signature: Option<String>,
+}
+
+#[derive(Serialize)]
+struct AuditLogOutput {
+    #[serde(skip_serializing_if = "Option::is_none")]
+    from: Option<i64>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    to: Option<i64>,
+    entries: Vec<AuditLogEntry>,
+}
+
+#[derive(Serialize)]
+struct AuditLogEntry {
+    signature: String,
+    slot: u64,
+    timestamp: String,
+    action: String,
+    event: String,
+    data: serde_json::Value,
+}
+
+fn print_json<T: Serialize>(value: &T) -> Result<()> {
+    println!("{}", serde_json::to_string_pretty(value)?);
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::events::decode_event;
+    use super::{
+        aliases, audit_role_accounts, blacklist_reason_counts, build_add_to_allowlist_instruction,
+        build_add_to_blacklist_instruction, build_approve_mint_instruction, build_burn_instruction,
+        build_cancel_mint_instruction, build_close_blacklist_entry_instruction,
+        build_enable_transfer_hook_instruction, build_freeze_instruction,
+        build_freeze_supply_instruction, build_init_extra_metas_instruction,
+        build_init_last_transfer_instruction, build_initialize_instruction,
+        build_migrate_config_instruction, build_mint_batch_instruction, build_mint_instruction,
+        build_pause_instruction, build_propose_mint_instruction,
+        build_remove_from_allowlist_instruction, build_remove_from_blacklist_instruction,
+        build_renounce_role_instruction, build_role_assignments, build_rpc_client,
+        build_seize_and_burn_instruction, build_seize_instruction, build_thaw_instruction,
+        build_transfer_instruction, build_update_admin_lock_instruction,
+        build_update_blacklist_entry_instruction, build_update_default_account_state_instruction,
+        build_update_max_supply_instruction, build_update_metadata_field_instruction,
+        build_update_metadata_instruction, build_update_mint_approval_instruction,
+        build_update_quota_window_instruction, build_update_roles_instruction,
+        build_update_self_transfer_instruction, build_update_transfer_hook_program_instruction,
+        build_update_transfer_limit_instruction, build_update_treasury_instruction, check_decimals,
+        check_direct_mint, check_legacy_mint, check_max_supply_update, check_mint_prefix,
+        check_mint_recipient, check_role_bumps, check_sss_config, chunk_instructions,
+        classify_balance_change, classify_seize_target, compliance_report, config_events,
+        config_output, confirm, current_window_minted, decode_hook_decisions,
+        decode_owner_and_amount, decode_program_events, decode_transaction, describe_program_error,
+        encode_transaction, event_matches, find_allowlist_pda, find_blacklist_pda, find_config_pda,
+        find_extra_account_metas_pda, find_last_transfer_pda, find_mint_request_pda, find_role_pda,
+        format_amount, grind_mint_keypair, hook_descriptor, is_transient_rpc_error, is_yes,
+        lifetime_remaining, load_signer, metaplex_metadata_pda, mint_metadata, minter_details,
+        minter_info, missing_signers, needs_prompt, page_holders, parse_address_list, parse_amount,
+        parse_blacklist_file, parse_expires_in, parse_lifetime_quota, parse_quota_window,
+        parse_raw_amount, parse_reason_code, parse_recipients_csv, parse_role_names,
+        parse_rpc_headers, parse_sss_config, parse_time_bound, parse_transfer_limit,
+        paused_operations, plan_mint_batches, prepare_treasury_ata, preset_label, reason_code_name,
+        resolve_role_aliases, role_names, seize_amount, snapshot_csv, supply_headroom,
+        transaction_size, treasury_ata, updated_role_mask, validate_transfer, verify_authorities,
+        verify_mint, websocket_url, window_quota_remaining, AccountEntry, AddToAllowlistParams,
+        AddToBlacklistParams, ApproveMintParams, AuthorityStatus, BlacklistArgs, BlacklistCmd,
+        BlacklistReasonCounts, BlacklistReport, BurnParams, CancelMintParams, Cli, ClientError,
+        CloseBlacklistEntryParams, Commands, ComputeBudget, ComputeBudgetInstruction, ConfigFormat,
+        EnableTransferHookParams, FreezeParams, FreezeSupplyParams, Hash, HolderInfo,
+        HoldersSnapshot, InitExtraMetasParams, InitLastTransferParams, InitializeParams,
+        InstructionOutput, MigrateConfigParams, MintApproveArgs, MintArgs, MintBatchParams,
+        MintCancelArgs, MintParams, MintRequestCmd, MinterConfig, OutputFormat, PauseParams,
+        PdaOutput, ProposeMintParams, RemoveFromAllowlistParams, RemoveFromBlacklistParams,
+        RenounceRoleParams, RetryPolicy, RolesConfig, RpcError, SeizeAndBurnParams, SeizeParams,
+        SeizeStatus, SimpleOutput, SortOrder, StatusArgs, StatusCmd, TimeRange, TokenAccountInfo,
+        TokenProgramKind, Transaction, TransactionError, TransferParams, UpdateAdminLockParams,
+        UpdateBlacklistEntryParams, UpdateDefaultAccountStateParams, UpdateMaxSupplyParams,
+        UpdateMetadataFieldParams, UpdateMetadataParams, UpdateMintApprovalParams,
+        UpdateQuotaWindowParams, UpdateRolesParams, UpdateSelfTransferParams,
+        UpdateTransferHookProgramParams, UpdateTransferLimitParams, UpdateTreasuryParams,
+        JSON_RPC_SERVER_ERROR_NODE_UNHEALTHY, MAX_REASON_LEN, PAUSE_BURN, PAUSE_MINT,
+        PAUSE_TRANSFER,
+    };
+    use base64::Engine;
+    use chrono::{DateTime, Utc};
+    use clap::Parser;
+    use reqwest::header::HeaderMap;
+    use solana_client::rpc_request::RpcResponseErrorData;
+    use solana_sdk::commitment_config::CommitmentConfig;
+    use solana_sdk::instruction::{AccountMeta, Instruction, InstructionError};
+    use solana_sdk::message::Message;
+    use solana_sdk::pubkey::Pubkey;
+    use solana_sdk::signature::{Keypair, Signer};
+    use solana_sdk::{system_program, sysvar};
+    use spl_associated_token_account::get_associated_token_address_with_program_id;
+    use spl_pod::optional_keys::OptionalNonZeroPubkey;
+    use spl_token_2022::extension::{
+        metadata_pointer::MetadataPointer, mint_close_authority::MintCloseAuthority,
+        permanent_delegate::PermanentDelegate, transfer_hook::TransferHook, ExtensionType,
+        StateWithExtensionsMut,
+    };
+    use spl_token_2022::solana_program::program_option::COption;
+    use spl_token_2022::state::Mint;
+    use spl_token_metadata_interface::state::TokenMetadata;
+    use stablecoin_core::constants::{
+        EVENT_SCHEMA_VERSION, ROLE_BLACKLISTER, ROLE_BURNER, ROLE_MASTER_AUTHORITY, ROLE_MINTER,
+        ROLE_ROLE_ADMIN,
+    };
+    use stablecoin_core::state::{
+        BlacklistEntry, BlacklistReason, FeatureFlags, RoleAccount, StablecoinConfig, TokenProgram,
+    };
+    use std::time::Duration;
+
+    fn role_entry(config: &Pubkey, authority: &Pubkey, roles: u8) -> AccountEntry<RoleAccount> {
+        let (address, bump) = find_role_pda(config, authority, &stablecoin_core::ID);
+        AccountEntry {
+            address,
+            account: RoleAccount {
+                config: *config,
+                authority: *authority,
                 roles,
                 mint_quota: None,
-            })?;
-            let signature = send_transaction(ctx_ref, vec![ix], vec![])?;
-            let explorer = explorer_url(&signature, ctx_ref.cluster);
-            if ctx_ref.output == OutputFormat::Json {
-                let output = SimpleOutput {
-                    signature: signature.clone(),
-                    explorer,
-                };
-                print_json(&output)
-            } else {
-                println!("Removed minter: {}", target);
-                println!("Tx: {}", signature);
-                if let Some(url) = explorer {
-                    println!("Explorer: {}", url);
-                }
-                Ok(())
-            }
+                minted_current_window: 0,
+                window_start: 0,
+                bump,
+                lifetime_minted: 0,
+                window_resets: 0,
+                lifetime_quota: None,
+            },
         }
     }
-}
 
-fn handle_status(ctx: &OwnedContext, args: &MintOnlyArgs) -> Result<()> {
-    let ctx_ref = ctx.as_ref();
-    let mint = resolve_mint(&args.mint)?;
-    let config_pda = find_config_pda(&mint, &stablecoin_core::ID).0;
-    let config = fetch_config(ctx_ref, &config_pda)?;
-    let supply = ctx_ref.client.get_token_supply(&mint)?;
-    let roles = list_role_accounts(ctx_ref, &config_pda)?;
-    let blacklist = list_blacklist_entries(ctx_ref, &config_pda)?;
-    let preset = if config.features.transfer_hook {
-        "SSS-2"
-    } else {
-        "SSS-1"
-    };
-    if ctx_ref.output == OutputFormat::Json {
-        let output = StatusOutput {
-            mint: mint.to_string(),
-            preset: preset.to_string(),
-            is_paused: config.is_paused,
-            supply: supply.amount,
-            total_minted: config.total_minted.to_string(),
-            total_burned: config.total_burned.to_string(),
-            features: FeatureOutput {
-                permanent_delegate: config.features.permanent_delegate,
-                transfer_hook: config.features.transfer_hook,
-                confidential: config.features.confidential,
-                default_frozen: config.features.default_frozen,
-            },
-            role_counts: RoleCounts {
-                masters: count_role(&roles, ROLE_MASTER_AUTHORITY),
-                minters: count_role(&roles, ROLE_MINTER),
-                burners: count_role(&roles, ROLE_BURNER),
-                freezers: count_role(&roles, ROLE_FREEZER),
-                pausers: count_role(&roles, ROLE_PAUSER),
-                blacklisters: count_role(&roles, ROLE_BLACKLISTER),
-                seizers: count_role(&roles, ROLE_SEIZER),
-            },
-            blacklisted: blacklist
-                .iter()
-                .filter(|entry| entry.account.is_active)
-                .count(),
-        };
-        print_json(&output)
-    } else {
-        println!("Stablecoin status");
-        println!("Mint: {}", mint);
-        println!("Preset: {}", preset);
-        println!(
-            "Status: {}",
-            if config.is_paused { "Paused" } else { "Active" }
-        );
-        println!(
-            "Supply: {}",
-            format_amount(supply.amount.parse::<u64>()?, config.decimals)
+    fn sample_config(mint: Pubkey) -> StablecoinConfig {
+        StablecoinConfig {
+            authority: Pubkey::new_unique(),
+            mint,
+            name: "Test USD".to_string(),
+            symbol: "TUSD".to_string(),
+            uri: String::new(),
+            decimals: 6,
+            pause_flags: 0,
+            total_minted: 0,
+            total_burned: 0,
+            audit_counter: 0,
+            features: FeatureFlags::default(),
+            transfer_hook_program: None,
+            bump: 255,
+            pause_cooldown: 0,
+            last_pause_change: 0,
+            max_supply: None,
+            creator: Pubkey::new_unique(),
+            created_at: 0,
+            quota_window_seconds: 0,
+            allowlist_mode: false,
+            max_transfer_amount: None,
+            last_action_at: 0,
+            last_action_by: Pubkey::new_unique(),
+            allow_self_transfer: false,
+            total_seized: 0,
+            treasury: None,
+            lock_admin_ops: false,
+            minting_locked: false,
+            token_program: Pubkey::default(),
+            transfer_cooldown_seconds: None,
+            require_mint_approval: false,
+        }
+    }
+
+    #[test]
+    fn parses_amounts_with_decimals() {
+        assert_eq!(parse_amount("1", 6).unwrap(), 1);
+        assert_eq!(parse_amount("1.5", 6).unwrap(), 1_500_000);
+        assert_eq!(parse_amount("0.000001", 6).unwrap(), 1);
+        assert_eq!(parse_amount("1_000.25", 2).unwrap(), 100_025);
+    }
+
+    #[test]
+    fn parses_amounts_with_unit_suffixes() {
+        assert_eq!(parse_amount("1000u", 6).unwrap(), 1_000_000_000);
+        assert_eq!(parse_amount("1.5u", 6).unwrap(), 1_500_000);
+        assert_eq!(parse_amount("1000base", 6).unwrap(), 1000);
+        assert_eq!(parse_amount("1_000base", 6).unwrap(), 1000);
+        assert_eq!(parse_amount("7u", 0).unwrap(), 7);
+        assert!(parse_amount("1.5base", 6).is_err());
+        assert!(parse_amount("0.0000001u", 6).is_err());
+        assert!(parse_amount("u", 6).is_err());
+        assert!(parse_amount("base", 6).is_err());
+        assert!(parse_amount("18446744073709551615u", 6).is_err());
+    }
+
+    #[test]
+    fn raw_amounts_skip_decimal_scaling() {
+        assert_eq!(parse_raw_amount("1500000").unwrap(), 1_500_000);
+        assert_eq!(parse_raw_amount(" 1_000 ").unwrap(), 1000);
+        assert!(parse_raw_amount("1.5").is_err());
+        assert!(parse_raw_amount("1000u").is_err());
+        assert!(parse_raw_amount("").is_err());
+        let cli = Cli::try_parse_from(["sss-token", "burn", "5", "--raw-amounts"]).unwrap();
+        assert!(cli.raw_amounts);
+        let cli = Cli::try_parse_from(["sss-token", "burn", "5"]).unwrap();
+        assert!(!cli.raw_amounts);
+    }
+
+    #[test]
+    fn supply_counts_holders_only_on_request() {
+        let with_holders =
+            |args: &[&str]| match Cli::try_parse_from(args.iter().copied()).unwrap().command {
+                Commands::Supply(args) => args.with_holders,
+                _ => unreachable!(),
+            };
+        assert!(!with_holders(&["sss-token", "supply"]));
+        assert!(with_holders(&["sss-token", "supply", "--with-holders"]));
+    }
+
+    #[test]
+    fn resolves_aliases_through_other_aliases() {
+        let treasury = Pubkey::new_unique();
+        let mut book = aliases::AliasBook::default();
+        assert_eq!(
+            book.insert("treasury", &treasury.to_string()).unwrap(),
+            treasury
         );
-        println!("Total minted: {}", config.total_minted);
-        println!("Total burned: {}", config.total_burned);
-        println!("Features:");
-        println!(
-            "  Permanent delegate: {}",
-            config.features.permanent_delegate
+        assert_eq!(book.insert("ops", "treasury").unwrap(), treasury);
+        assert_eq!(book.resolve("ops").unwrap(), treasury);
+        assert_eq!(book.resolve(&treasury.to_string()).unwrap(), treasury);
+        assert_eq!(
+            book.resolve("nope").unwrap_err().to_string(),
+            "Invalid pubkey: nope"
         );
-        println!("  Transfer hook: {}", config.features.transfer_hook);
-        println!("  Confidential: {}", config.features.confidential);
-        println!("  Default frozen: {}", config.features.default_frozen);
-        println!("Roles:");
-        println!("  Masters: {}", count_role(&roles, ROLE_MASTER_AUTHORITY));
-        println!("  Minters: {}", count_role(&roles, ROLE_MINTER));
-        println!("  Burners: {}", count_role(&roles, ROLE_BURNER));
-        println!("  Freezers: {}", count_role(&roles, ROLE_FREEZER));
-        println!("  Pausers: {}", count_role(&roles, ROLE_PAUSER));
-        println!("  Blacklisters: {}", count_role(&roles, ROLE_BLACKLISTER));
-        println!("  Seizers: {}", count_role(&roles, ROLE_SEIZER));
-        println!(
-            "Blacklisted: {}",
-            blacklist
-                .iter()
-                .filter(|entry| entry.account.is_active)
-                .count()
+
+        // Pointing treasury back at ops would close a cycle; the old target stays.
+        let err = book.insert("treasury", "ops").unwrap_err();
+        assert_eq!(err.to_string(), "Alias cycle: treasury -> ops -> treasury");
+        assert_eq!(book.resolve("treasury").unwrap(), treasury);
+        assert!(book.insert("missing", "nowhere").is_err());
+        assert!(book.resolve("missing").is_err());
+        assert!(book.insert(&treasury.to_string(), "ops").is_err());
+        assert!(book.insert("two words", "ops").is_err());
+        assert_eq!(
+            book.entries().collect::<Vec<_>>(),
+            vec![
+                ("ops", "treasury"),
+                ("treasury", treasury.to_string().as_str())
+            ]
         );
-        Ok(())
-    }
-}
 
-fn handle_supply(ctx: &OwnedContext, args: &MintOnlyArgs) -> Result<()> {
-    let ctx_ref = ctx.as_ref();
-    let mint = resolve_mint(&args.mint)?;
-    let supply = ctx_ref.client.get_token_supply(&mint)?;
-    if ctx_ref.output == OutputFormat::Json {
-        let output = SupplyOutput {
-            mint: mint.to_string(),
-            supply: supply.amount,
+        let minter = Pubkey::new_unique();
+        book.insert("minter", &minter.to_string()).unwrap();
+        let mut roles = RolesConfig {
+            minters: Some(vec![MinterConfig {
+                pubkey: "minter".to_string(),
+                quota: 1,
+            }]),
+            pausers: Some(vec!["ops".to_string(), minter.to_string()]),
+            ..RolesConfig::default()
         };
-        print_json(&output)
-    } else {
-        println!("Supply: {}", supply.amount);
-        Ok(())
+        resolve_role_aliases(&mut roles, |name| book.resolve(name)).unwrap();
+        assert_eq!(roles.minters.unwrap()[0].pubkey, minter.to_string());
+        assert_eq!(
+            roles.pausers.unwrap(),
+            vec![treasury.to_string(), minter.to_string()]
+        );
+        let mut unknown = RolesConfig {
+            burners: Some(vec!["nobody".to_string()]),
+            ..RolesConfig::default()
+        };
+        assert!(resolve_role_aliases(&mut unknown, |name| book.resolve(name)).is_err());
     }
-}
 
-fn handle_holders(ctx: &OwnedContext, args: &HoldersArgs) -> Result<()> {
-    let ctx_ref = ctx.as_ref();
-    let mint = resolve_mint(&args.mint)?;
-    let config_pda = find_config_pda(&mint, &stablecoin_core::ID).0;
-    let stablecoin_config = fetch_config(ctx_ref, &config_pda)?;
-    let min_balance = match args.min_balance.as_deref() {
-        Some(value) => Some(parse_amount(value, stablecoin_config.decimals)?),
-        None => None,
-    };
+    #[test]
+    fn formats_amounts() {
+        assert_eq!(format_amount(1_500_000, 6), "1.500000");
+        assert_eq!(format_amount(100, 2), "1.00");
+        assert_eq!(format_amount(10, 0), "10");
+    }
 
-    let mut rpc_config = RpcProgramAccountsConfig::default();
-    rpc_config.filters = Some(vec![RpcFilterType::Memcmp(Memcmp::new_base58_encoded(
-        0,
-        mint.as_ref(),
-    ))]);
-    rpc_config.account_config = RpcAccountInfoConfig {
-        encoding: None,
-        commitment: Some(ctx_ref.commitment),
-        data_slice: None,
-        min_context_slot: None,
-    };
+    #[test]
+    fn minter_quotas_show_ui_and_base_units() {
+        let mut entry = role_entry(&Pubkey::new_unique(), &Pubkey::new_unique(), ROLE_MINTER);
+        entry.account.mint_quota = Some(1_000_000);
+        entry.account.lifetime_minted = 2_500_000;
+        let info = minter_info(&entry.account, 6);
+        assert_eq!(info.quota.as_deref(), Some("1000000"));
+        assert_eq!(info.quota_ui.as_deref(), Some("1.000000"));
+        assert_eq!(info.lifetime_minted, "2500000");
+        assert_eq!(info.lifetime_minted_ui, "2.500000");
+        entry.account.mint_quota = None;
+        let unlimited = minter_info(&entry.account, 6);
+        assert!(unlimited.quota.is_none() && unlimited.quota_ui.is_none());
+    }
 
-    let accounts = ctx_ref
-        .client
-        .get_program_accounts_with_config(&spl_token_2022::id(), rpc_config)?;
+    #[test]
+    fn rejects_unknown_config_keys() {
+        let base = "[token]\nname = \"Test USD\"\nsymbol = \"TUSD\"\n";
+        assert_eq!(
+            parse_sss_config(base, ConfigFormat::Toml)
+                .unwrap()
+                .token
+                .symbol,
+            "TUSD"
+        );
 
-    let mut holders = Vec::new();
-    for (pubkey, account) in accounts {
-        let parsed = StateWithExtensions::<TokenAccount2022>::unpack(&account.data)
-            .map_err(|err| anyhow!("Failed to decode token account: {}", err))?;
-        let amount = parsed.base.amount;
-        if let Some(min) = min_balance {
-            if amount < min {
-                continue;
-            }
+        let misspelled = base.replace("symbol", "symbl");
+        let err = parse_sss_config(&misspelled, ConfigFormat::Toml)
+            .unwrap_err()
+            .to_string();
+        assert!(err.starts_with("line 3: unknown field `symbl`"), "{err}");
+
+        // [roles] nested under [token] would otherwise drop every role.
+        let misplaced = format!("{base}[token.roles]\npausers = []\n");
+        let err = parse_sss_config(&misplaced, ConfigFormat::Toml)
+            .unwrap_err()
+            .to_string();
+        assert!(err.contains("unknown field `roles`"), "{err}");
+
+        for (table, key) in [
+            ("extensions", "transfer_hooks = true"),
+            ("network", "clustr = \"devnet\""),
+        ] {
+            let err = parse_sss_config(&format!("{base}[{table}]\n{key}\n"), ConfigFormat::Toml)
+                .unwrap_err()
+                .to_string();
+            assert!(err.starts_with("line 5: unknown field"), "{err}");
         }
-        holders.push(HolderInfo {
-            owner: parsed.base.owner.to_string(),
-            token_account: pubkey.to_string(),
-            amount,
-        });
+        let minter = format!(
+            "{base}[roles]\nminters = [{{ pubkey = \"{}\", quota = 1, qouta = 2 }}]\n",
+            Pubkey::new_unique()
+        );
+        let err = parse_sss_config(&minter, ConfigFormat::Toml)
+            .unwrap_err()
+            .to_string();
+        assert!(err.contains("unknown field `qouta`"), "{err}");
+    }
+
+    #[test]
+    fn reads_json_init_configs_like_toml() {
+        let minter = Pubkey::new_unique();
+        let freezer = Pubkey::new_unique();
+        let toml = format!(
+            "[token]\nname = \"Test USD\"\nsymbol = \"TUSD\"\n\n\
+             [roles]\nminters = [{{ pubkey = \"{minter}\", quota = 1000 }}]\n\
+             freezers = [\"{minter}\", \"{freezer}\"]\n"
+        );
+        let json = format!(
+            r#"{{
+  "token": {{ "name": "Test USD", "symbol": "TUSD" }},
+  "roles": {{
+    "minters": [{{ "pubkey": "{minter}", "quota": 1000 }}],
+    "freezers": ["{minter}", "{freezer}"]
+  }}
+}}"#
+        );
+
+        assert_eq!(ConfigFormat::detect("sss.json", &toml), ConfigFormat::Json);
+        assert_eq!(ConfigFormat::detect("sss.TOML", &json), ConfigFormat::Toml);
+        assert_eq!(ConfigFormat::detect("sss.conf", &json), ConfigFormat::Json);
+        assert_eq!(ConfigFormat::detect("sss.conf", &toml), ConfigFormat::Toml);
+
+        let from_toml = parse_sss_config(&toml, ConfigFormat::Toml).unwrap();
+        let from_json = parse_sss_config(&json, ConfigFormat::Json).unwrap();
+        assert_eq!(from_json.token.symbol, "TUSD");
+        assert_eq!(
+            build_role_assignments(&from_toml.roles.unwrap()).unwrap(),
+            build_role_assignments(&from_json.roles.unwrap()).unwrap()
+        );
+
+        let misspelled = json.replace("\"symbol\"", "\"symbl\"");
+        let err = parse_sss_config(&misspelled, ConfigFormat::Json)
+            .unwrap_err()
+            .to_string();
+        assert!(err.contains("unknown field `symbl`"), "{err}");
+        assert!(err.contains("line 2"), "{err}");
+        assert!(check_sss_config("sss.json", &json).errors.is_empty());
     }
 
-    holders.sort_by(|a, b| b.amount.cmp(&a.amount));
+    #[test]
+    fn checks_init_config_files_offline() {
+        let wallet = Pubkey::new_unique();
+        let valid = format!(
+            "[token]\nname = \"Test USD\"\nsymbol = \"TUSD\"\ndecimals = 6\n\n\
+             [extensions]\ntransfer_hook = true\npermanent_delegate = true\n\n\
+             [roles]\nminters = [{{ pubkey = \"{wallet}\", quota = 1000 }}]\n\
+             blacklisters = [\"{wallet}\"]\n"
+        );
+        let report = check_sss_config("sss.toml", &valid);
+        assert!(report.errors.is_empty(), "{:?}", report.errors);
+        assert!(report.warnings.is_empty(), "{:?}", report.warnings);
+
+        let typo = valid.replace("transfer_hook = true", "transfer_hooks = true");
+        let report = check_sss_config("sss.toml", &typo);
+        assert_eq!(report.errors.len(), 1);
+        assert!(report.errors[0].starts_with("line 7: unknown field `transfer_hooks`"));
+
+        let incoherent = valid
+            .replace("decimals = 6", "decimals = 12")
+            .replace("transfer_hook = true", "transfer_hook = false")
+            .replace(
+                &format!("blacklisters = [\"{wallet}\"]"),
+                "pausers = [\"nope\"]",
+            );
+        let report = check_sss_config("sss.toml", &incoherent);
+        assert_eq!(report.errors.len(), 2, "{:?}", report.errors);
+        assert!(report.errors[0].starts_with("token.decimals"));
+        assert_eq!(report.errors[1], "roles.pausers: Invalid pubkey: nope");
+        assert_eq!(report.warnings.len(), 1);
+
+        let sss1_compliance = valid.replace("transfer_hook = true", "transfer_hook = false");
+        let report = check_sss_config("sss.toml", &sss1_compliance);
+        assert_eq!(
+            report.errors,
+            vec!["roles.blacklisters requires extensions.transfer_hook (SSS-2)".to_string()]
+        );
+    }
 
-    if ctx_ref.output == OutputFormat::Json {
-        let output = HoldersOutput {
-            holders: holders.clone(),
+    #[test]
+    fn creates_missing_treasury_atas_only_for_known_owners() {
+        let mint = Pubkey::new_unique();
+        let payer = Pubkey::new_unique();
+        let owner = Pubkey::new_unique();
+        let ata =
+            get_associated_token_address_with_program_id(&owner, &mint, &spl_token_2022::id());
+        let existing = TokenAccountInfo {
+            owner,
+            mint,
+            amount: 0,
+            is_frozen: false,
         };
-        print_json(&output)
-    } else {
-        if holders.is_empty() {
-            println!("No holders found");
-        } else {
-            for holder in holders {
-                println!(
-                    "{} {}",
-                    holder.owner,
-                    format_amount(holder.amount, stablecoin_config.decimals)
-                );
-            }
-        }
-        Ok(())
+
+        let (resolved, create) =
+            prepare_treasury_ata(&ata, None, Some(&existing), &mint, &payer, true).unwrap();
+        assert_eq!(resolved, owner);
+        assert!(create.is_none());
+
+        let (resolved, create) =
+            prepare_treasury_ata(&ata, Some(owner), None, &mint, &payer, false).unwrap();
+        assert_eq!(resolved, owner);
+        let create = create.unwrap();
+        assert_eq!(create.program_id, spl_associated_token_account::id());
+        assert_eq!(create.accounts[0].pubkey, payer);
+        assert_eq!(create.accounts[1].pubkey, ata);
+
+        assert!(prepare_treasury_ata(&ata, None, None, &mint, &payer, false).is_err());
+        assert!(prepare_treasury_ata(&ata, Some(owner), None, &mint, &payer, true).is_err());
+        let foreign = TokenAccountInfo {
+            mint: Pubkey::new_unique(),
+            ..existing
+        };
+        assert!(prepare_treasury_ata(&ata, None, Some(&foreign), &mint, &payer, false).is_err());
     }
-}
 
-fn handle_audit_log(ctx: &OwnedContext, _args: &AuditLogArgs) -> Result<()> {
-    if ctx.output == OutputFormat::Json {
-        let output = AuditLogOutput { entries: vec![] };
-        print_json(&output)
-    } else {
-        println!("Audit log backend not configured");
-        Ok(())
+    #[test]
+    fn expired_quota_windows_count_as_empty() {
+        let mut role =
+            role_entry(&Pubkey::new_unique(), &Pubkey::new_unique(), ROLE_MINTER).account;
+        role.minted_current_window = 700;
+        assert_eq!(current_window_minted(&role, 1_000, 3_600), 0);
+        role.window_start = 1_000;
+        assert_eq!(current_window_minted(&role, 4_599, 3_600), 700);
+        assert_eq!(current_window_minted(&role, 4_600, 3_600), 0);
+
+        assert_eq!(window_quota_remaining(&role, 4_599, 3_600), None);
+        role.mint_quota = Some(1_000);
+        assert_eq!(window_quota_remaining(&role, 4_599, 3_600), Some(300));
+        assert_eq!(window_quota_remaining(&role, 4_600, 3_600), Some(1_000));
+        role.minted_current_window = 1_200;
+        assert_eq!(window_quota_remaining(&role, 4_599, 3_600), Some(0));
     }
-}
 
-#[derive(Debug, Clone, Default, Deserialize)]
-struct SssConfig {
-    token: TokenConfig,
-    extensions: Option<ExtensionsConfig>,
-    roles: Option<RolesConfig>,
-    network: Option<NetworkConfig>,
-}
+    #[test]
+    fn reports_remaining_lifetime_quota() {
+        assert_eq!(parse_lifetime_quota("5000").unwrap(), Some(5_000));
+        assert_eq!(parse_lifetime_quota("None").unwrap(), None);
+        assert!(parse_lifetime_quota("1.5").is_err());
+
+        let mut role =
+            role_entry(&Pubkey::new_unique(), &Pubkey::new_unique(), ROLE_MINTER).account;
+        role.lifetime_minted = 1_200;
+        assert_eq!(lifetime_remaining(&role), None);
+        role.lifetime_quota = Some(5_000);
+        assert_eq!(lifetime_remaining(&role), Some(3_800));
+        role.lifetime_quota = Some(1_000);
+        assert_eq!(lifetime_remaining(&role), Some(0));
+    }
 
-#[derive(Debug, Clone, Default, Deserialize)]
-struct TokenConfig {
-    name: String,
-    symbol: String,
-    decimals: Option<u8>,
-    uri: Option<String>,
-}
+    #[test]
+    fn rejects_degenerate_transfers() {
+        let source = Pubkey::new_unique();
+        let destination = Pubkey::new_unique();
+        assert!(validate_transfer(&source, &destination, 1).is_ok());
+        assert!(validate_transfer(&source, &destination, 0).is_err());
+        assert!(validate_transfer(&source, &source, 1).is_err());
+    }
 
-#[derive(Debug, Clone, Deserialize)]
-struct ExtensionsConfig {
-    permanent_delegate: Option<bool>,
-    transfer_hook: Option<bool>,
-    default_account_frozen: Option<bool>,
-    confidential_transfer: Option<bool>,
-}
+    #[test]
+    fn audits_role_accounts() {
+        let config = find_config_pda(&Pubkey::new_unique(), &stablecoin_core::ID).0;
+        let authority = Pubkey::new_unique();
+        let minter = Pubkey::new_unique();
+
+        let healthy = vec![
+            role_entry(&config, &authority, ROLE_MASTER_AUTHORITY),
+            role_entry(&config, &minter, ROLE_MINTER),
+        ];
+        assert!(audit_role_accounts(&config, &authority, &healthy).ok);
+
+        let desynced = vec![role_entry(&config, &minter, ROLE_MASTER_AUTHORITY)];
+        assert!(!audit_role_accounts(&config, &authority, &desynced).ok);
+
+        let duplicated = vec![
+            role_entry(&config, &authority, ROLE_MASTER_AUTHORITY),
+            role_entry(&config, &minter, ROLE_MASTER_AUTHORITY | ROLE_MINTER),
+        ];
+        assert!(!audit_role_accounts(&config, &authority, &duplicated).ok);
+
+        // Every bit is assigned now that 0x80 is the role admin.
+        let role_admin = vec![role_entry(
+            &config,
+            &authority,
+            ROLE_MASTER_AUTHORITY | ROLE_ROLE_ADMIN,
+        )];
+        assert!(audit_role_accounts(&config, &authority, &role_admin).ok);
+    }
+
+    #[test]
+    fn checks_role_bumps() {
+        let config = find_config_pda(&Pubkey::new_unique(), &stablecoin_core::ID).0;
+        let creator = Pubkey::new_unique();
+        let admin = Pubkey::new_unique();
+        let burner = Pubkey::new_unique();
+
+        let mut roles = vec![
+            role_entry(&config, &creator, ROLE_MINTER),
+            role_entry(&config, &admin, ROLE_MASTER_AUTHORITY),
+            role_entry(&config, &burner, ROLE_BURNER),
+        ];
+        let report = check_role_bumps(&config, &creator, &roles);
+        assert_eq!(report.mismatches, 0);
+        assert!(report.role_accounts.iter().all(|check| check.ok));
+
+        for entry in &mut roles {
+            entry.account.bump = entry.account.bump.wrapping_sub(1);
+        }
+        let report = check_role_bumps(&config, &creator, &roles);
+        assert_eq!(report.mismatches, 3);
+        let sources: Vec<_> = report
+            .role_accounts
+            .iter()
+            .map(|check| check.likely_source.as_deref())
+            .collect();
+        assert_eq!(
+            sources,
+            vec![
+                Some("initialize"),
+                Some("transfer_authority"),
+                Some("update_roles")
+            ]
+        );
+
+        // A correct bump on an account stored at the wrong address still fails.
+        let mut misplaced = role_entry(&config, &burner, ROLE_BURNER);
+        misplaced.address = Pubkey::new_unique();
+        assert_eq!(
+            check_role_bumps(&config, &creator, &[misplaced]).mismatches,
+            1
+        );
+    }
+
+    #[test]
+    fn multisig_proposal_marks_authority_as_signer() {
+        let multisig_vault = Pubkey::new_unique();
+        let config = Pubkey::new_unique();
+        let instruction = build_pause_instruction(PauseParams {
+            pauser: multisig_vault,
+            config_pda: config,
+            unpause: false,
+            mask: None,
+        })
+        .unwrap();
+
+        let output = InstructionOutput::from(&instruction);
+        assert_eq!(output.program_id, stablecoin_core::ID.to_string());
+        assert_eq!(output.accounts[0].pubkey, multisig_vault.to_string());
+        assert!(output.accounts[0].is_signer);
+        assert!(!output.accounts[1].is_signer);
+        let data = base64::engine::general_purpose::STANDARD
+            .decode(&output.data)
+            .unwrap();
+        assert_eq!(data, instruction.data);
+    }
+
+    #[test]
+    fn pause_defaults_to_every_operation() {
+        let mask = |args: &[&str]| match Cli::try_parse_from(args.iter().copied()).unwrap().command
+        {
+            Commands::Pause(args) | Commands::Unpause(args) => args.mask(),
+            _ => unreachable!(),
+        };
+
+        assert_eq!(mask(&["sss-token", "pause"]), None);
+        assert_eq!(
+            mask(&["sss-token", "pause", "--minting", "--transfers"]),
+            Some(PAUSE_MINT | PAUSE_TRANSFER)
+        );
+        assert_eq!(
+            mask(&["sss-token", "unpause", "--burning"]),
+            Some(PAUSE_BURN)
+        );
+        assert_eq!(
+            paused_operations(PAUSE_MINT | PAUSE_TRANSFER),
+            ["mint", "transfer"]
+        );
+    }
 
-impl Default for ExtensionsConfig {
-    fn default() -> Self {
-        Self {
-            permanent_delegate: Some(false),
-            transfer_hook: Some(false),
-            default_account_frozen: Some(false),
-            confidential_transfer: Some(false),
-        }
+    #[test]
+    fn burn_takes_an_amount_or_all() {
+        let parse = |args: &[&str]| Cli::try_parse_from(args.iter().copied());
+
+        let cli = parse(&["sss-token", "burn", "--all"]).unwrap();
+        assert!(
+            matches!(cli.command, Commands::Burn(ref args) if args.all && args.amount.is_none())
+        );
+        let cli = parse(&["sss-token", "burn", "1.5"]).unwrap();
+        assert!(matches!(cli.command, Commands::Burn(ref args) if !args.all));
+        assert!(parse(&["sss-token", "burn"]).is_err());
+        assert!(parse(&["sss-token", "burn", "1.5", "--all"]).is_err());
     }
-}
 
-impl ExtensionsConfig {
-    fn from_preset(enable_transfer_hook: bool) -> Self {
-        Self {
-            permanent_delegate: Some(enable_transfer_hook),
-            transfer_hook: Some(enable_transfer_hook),
-            default_account_frozen: Some(false),
-            confidential_transfer: Some(false),
-        }
+    #[test]
+    fn invalid_certs_are_only_accepted_on_request() {
+        let cli = Cli::try_parse_from(["sss-token", "status"]).unwrap();
+        assert!(!cli.insecure_accept_invalid_certs);
+        let cli = Cli::try_parse_from(["sss-token", "--insecure-accept-invalid-certs", "status"])
+            .unwrap();
+        assert!(cli.insecure_accept_invalid_certs);
+
+        let commitment = CommitmentConfig::confirmed();
+        let client = build_rpc_client(
+            "https://rpc.internal:8899",
+            commitment,
+            true,
+            HeaderMap::new(),
+        )
+        .unwrap();
+        assert_eq!(client.url(), "https://rpc.internal:8899");
+        assert_eq!(client.commitment(), commitment);
     }
-}
 
-#[derive(Debug, Clone, Default, Deserialize)]
-struct RolesConfig {
-    minters: Option<Vec<MinterConfig>>,
-    freezers: Option<Vec<String>>,
-    pausers: Option<Vec<String>>,
-    blacklisters: Option<Vec<String>>,
-    seizers: Option<Vec<String>>,
-    burners: Option<Vec<String>>,
-}
+    #[test]
+    fn rpc_headers_are_parsed_and_applied_to_the_client() {
+        let cli = Cli::try_parse_from([
+            "sss-token",
+            "--rpc-header",
+            "x-api-key: secret",
+            "--rpc-header",
+            "Authorization:Bearer token",
+            "status",
+        ])
+        .unwrap();
+        let configured = [
+            "x-api-key: from-toml".to_string(),
+            "x-team: ops".to_string(),
+        ];
+        let headers = parse_rpc_headers(configured.iter().chain(&cli.rpc_headers)).unwrap();
+        assert_eq!(headers.len(), 3);
+        assert_eq!(headers["x-api-key"], "secret");
+        assert_eq!(headers["authorization"], "Bearer token");
+        assert_eq!(headers["x-team"], "ops");
+
+        assert!(parse_rpc_headers(&["x-api-key".to_string()]).is_err());
+        assert!(parse_rpc_headers(&["bad name: value".to_string()]).is_err());
+        let err = parse_rpc_headers(&["x-api-key: line\nbreak".to_string()]).unwrap_err();
+        assert!(!err.to_string().contains("line"));
+
+        // A key embedded in the URL is left untouched.
+        let url = "https://rpc.example.com/?api-key=secret";
+        let client = build_rpc_client(url, CommitmentConfig::confirmed(), false, headers).unwrap();
+        assert_eq!(client.url(), url);
+    }
 
-#[derive(Debug, Clone, Deserialize)]
-struct MinterConfig {
-    pubkey: String,
-    quota: u64,
-}
+    #[test]
+    fn compute_budget_flags_prepend_instructions_and_echo_in_output() {
+        let cli = Cli::try_parse_from(["sss-token", "status"]).unwrap();
+        let unset = ComputeBudget {
+            unit_price: cli.priority_fee,
+            unit_limit: cli.compute_limit,
+        };
+        assert!(unset.instructions().is_empty());
+        assert!(unset.output().is_none());
+
+        let cli = Cli::try_parse_from([
+            "sss-token",
+            "--priority-fee",
+            "25000",
+            "--compute-limit",
+            "300000",
+            "status",
+        ])
+        .unwrap();
+        let budget = ComputeBudget {
+            unit_price: cli.priority_fee,
+            unit_limit: cli.compute_limit,
+        };
+        assert_eq!(
+            budget.instructions(),
+            vec![
+                ComputeBudgetInstruction::set_compute_unit_limit(300_000),
+                ComputeBudgetInstruction::set_compute_unit_price(25_000),
+            ]
+        );
+        let output = serde_json::to_value(budget.output()).unwrap();
+        assert_eq!(output["priority_fee_micro_lamports"], 25_000);
+        assert_eq!(output["compute_unit_limit"], 300_000);
+    }
 
-#[derive(Debug, Clone, Deserialize)]
-struct NetworkConfig {
-    cluster: Option<String>,
-    keypair_path: Option<String>,
-    commitment: Option<String>,
-}
+    #[test]
+    fn simple_output_carries_config_and_role_pdas() {
+        let config_pda = Pubkey::new_unique();
+        let signer = Pubkey::new_unique();
+        let output = SimpleOutput {
+            signature: "sig".to_string(),
+            explorer: None,
+            pdas: Some(PdaOutput::new(&config_pda, Some(&signer))),
+            compute_budget: None,
+        };
+        let value = serde_json::to_value(&output).unwrap();
+        assert_eq!(value["config"], config_pda.to_string());
+        assert_eq!(
+            value["role_account"],
+            find_role_pda(&config_pda, &signer, &stablecoin_core::ID)
+                .0
+                .to_string()
+        );
 
-#[derive(Debug, Clone, Deserialize)]
-struct SolanaCliConfig {
-    json_rpc_url: String,
-    keypair_path: String,
-    commitment: Option<String>,
-}
+        let output = SimpleOutput {
+            signature: "sig".to_string(),
+            explorer: None,
+            pdas: Some(PdaOutput::new(&config_pda, None)),
+            compute_budget: None,
+        };
+        let value = serde_json::to_value(&output).unwrap();
+        assert_eq!(value["config"], config_pda.to_string());
+        assert!(value.get("role_account").is_none());
 
-#[derive(Clone, Copy)]
-struct RoleAssignment {
-    roles: u8,
-    mint_quota: Option<u64>,
-}
+        let output = SimpleOutput {
+            signature: "sig".to_string(),
+            explorer: None,
+            pdas: None,
+            compute_budget: None,
+        };
+        let value = serde_json::to_value(&output).unwrap();
+        assert!(value.get("config").is_none());
+    }
 
-fn build_role_assignments(config: &RolesConfig) -> Result<HashMap<Pubkey, RoleAssignment>> {
-    let mut assignments = HashMap::new();
+    #[test]
+    fn simulate_is_opt_in_and_excludes_export() {
+        let cli = Cli::try_parse_from(["sss-token", "status"]).unwrap();
+        assert!(!cli.simulate);
+        let cli = Cli::try_parse_from(["sss-token", "--simulate", "status"]).unwrap();
+        assert!(cli.simulate);
+        assert!(Cli::try_parse_from([
+            "sss-token",
+            "--simulate",
+            "--export-tx",
+            "tx.txt",
+            "status"
+        ])
+        .is_err());
+    }
 
-    if let Some(minters) = &config.minters {
-        for entry in minters {
-            let pubkey = parse_pubkey(&entry.pubkey)?;
-            let assignment = assignments.entry(pubkey).or_insert(RoleAssignment {
-                roles: 0,
-                mint_quota: None,
-            });
-            assignment.roles |= ROLE_MINTER;
-            assignment.mint_quota = Some(entry.quota);
+    #[test]
+    fn exported_transactions_round_trip_and_list_missing_signers() {
+        let payer = Keypair::new();
+        let mint = Keypair::new();
+        let instruction = Instruction::new_with_bytes(
+            stablecoin_core::ID,
+            &[1, 2, 3],
+            vec![
+                AccountMeta::new(payer.pubkey(), true),
+                AccountMeta::new(mint.pubkey(), true),
+            ],
+        );
+        let mut transaction = Transaction::new_with_payer(&[instruction], Some(&payer.pubkey()));
+        transaction.message.recent_blockhash = Hash::new_unique();
+        let blockhash = transaction.message.recent_blockhash;
+        transaction.try_partial_sign(&[&mint], blockhash).unwrap();
+        assert_eq!(missing_signers(&transaction), vec![payer.pubkey()]);
+
+        let decoded = decode_transaction(&encode_transaction(&transaction).unwrap()).unwrap();
+        assert_eq!(decoded, transaction);
+        assert_eq!(decoded.message.hash(), transaction.message.hash());
+
+        let mut signed = decoded;
+        signed.try_partial_sign(&[&payer], blockhash).unwrap();
+        assert!(missing_signers(&signed).is_empty());
+        assert!(signed.verify().is_ok());
+        assert!(decode_transaction("not base64!").is_err());
+    }
+
+    #[test]
+    fn retries_only_transient_rpc_errors_with_backoff() {
+        let cli = Cli::try_parse_from(["sss-token", "status"]).unwrap();
+        assert_eq!((cli.max_retries, cli.retry_delay_ms), (3, 500));
+        assert!(Cli::try_parse_from(["sss-token", "--max-retries", "0", "status"]).is_err());
+
+        let policy = RetryPolicy {
+            max_attempts: 3,
+            base_delay: Duration::from_millis(500),
+        };
+        assert_eq!(policy.delay(1), Duration::from_millis(500));
+        assert_eq!(policy.delay(2), Duration::from_millis(1_000));
+        assert_eq!(policy.delay(3), Duration::from_millis(2_000));
+
+        let transient = [
+            ClientError::from(TransactionError::BlockhashNotFound),
+            ClientError::from(RpcError::ForUser(
+                "unable to confirm transaction. This can happen ...".to_string(),
+            )),
+            ClientError::from(RpcError::RpcResponseError {
+                code: JSON_RPC_SERVER_ERROR_NODE_UNHEALTHY,
+                message: "Node is unhealthy".to_string(),
+                data: RpcResponseErrorData::Empty,
+            }),
+            ClientError::from(std::io::Error::from(std::io::ErrorKind::ConnectionReset)),
+        ];
+        for err in &transient {
+            assert!(is_transient_rpc_error(err), "{}", err);
         }
+        let unauthorized = TransactionError::InstructionError(0, InstructionError::Custom(6000));
+        assert!(!is_transient_rpc_error(&ClientError::from(unauthorized)));
+        assert!(!is_transient_rpc_error(&ClientError::from(
+            TransactionError::InsufficientFundsForFee
+        )));
     }
 
-    apply_role_list(&mut assignments, config.freezers.as_ref(), ROLE_FREEZER)?;
-    apply_role_list(&mut assignments, config.pausers.as_ref(), ROLE_PAUSER)?;
-    apply_role_list(
-        &mut assignments,
-        config.blacklisters.as_ref(),
-        ROLE_BLACKLISTER,
-    )?;
-    apply_role_list(&mut assignments, config.seizers.as_ref(), ROLE_SEIZER)?;
-    apply_role_list(&mut assignments, config.burners.as_ref(), ROLE_BURNER)?;
+    #[test]
+    fn parses_human_time_bounds() {
+        let now = DateTime::parse_from_rfc3339("2024-03-10T15:30:00Z")
+            .unwrap()
+            .with_timezone(&Utc);
+        let day = 86_400;
+        let midnight = 1_710_028_800;
+
+        assert_eq!(parse_time_bound("1700000000", now).unwrap(), 1_700_000_000);
+        assert_eq!(parse_time_bound("2024-03-10", now).unwrap(), midnight);
+        assert_eq!(parse_time_bound("today", now).unwrap(), midnight);
+        assert_eq!(parse_time_bound("yesterday", now).unwrap(), midnight - day);
+        assert_eq!(
+            parse_time_bound("7d", now).unwrap(),
+            now.timestamp() - 7 * day
+        );
+        assert_eq!(
+            parse_time_bound("12h", now).unwrap(),
+            now.timestamp() - 12 * 3600
+        );
+        assert_eq!(
+            parse_time_bound("2024-03-10T00:00:00+01:00", now).unwrap(),
+            midnight - 3600
+        );
+        assert!(parse_time_bound("last tuesday", now).is_err());
 
-    Ok(assignments)
-}
+        let range = TimeRange::from_args(Some("7d"), Some("now"), now).unwrap();
+        assert_eq!(range.from, Some(now.timestamp() - 7 * day));
+        assert_eq!(range.to, Some(now.timestamp()));
+        assert!(TimeRange::from_args(Some("today"), Some("yesterday"), now).is_err());
+    }
 
-fn apply_role_list(
-    assignments: &mut HashMap<Pubkey, RoleAssignment>,
-    list: Option<&Vec<String>>,
-    role: u8,
-) -> Result<()> {
-    if let Some(list) = list {
-        for entry in list {
-            let pubkey = parse_pubkey(entry)?;
-            let assignment = assignments.entry(pubkey).or_insert(RoleAssignment {
-                roles: 0,
-                mint_quota: None,
-            });
-            assignment.roles |= role;
+    #[test]
+    fn chunks_role_assignments_to_fit_transactions() {
+        let authority = Pubkey::new_unique();
+        let config = Pubkey::new_unique();
+        let instructions: Vec<_> = (0..20)
+            .map(|_| {
+                build_update_roles_instruction(UpdateRolesParams {
+                    authority,
+                    config_pda: config,
+                    target: Pubkey::new_unique(),
+                    roles: ROLE_MINTER,
+                    mint_quota: Some(1_000),
+                    lifetime_quota: None,
+                })
+                .unwrap()
+            })
+            .collect();
+
+        let prefix = ComputeBudget {
+            unit_price: Some(10_000),
+            unit_limit: Some(400_000),
         }
+        .instructions();
+        let chunks = chunk_instructions(instructions.clone(), &prefix, &authority).unwrap();
+        assert!(chunks.len() > 1);
+        assert!(chunks.iter().all(|chunk| !chunk.is_empty()));
+        for chunk in &chunks {
+            assert!(
+                transaction_size(&[prefix.as_slice(), chunk].concat(), &authority).unwrap()
+                    <= solana_sdk::packet::PACKET_DATA_SIZE
+            );
+        }
+        let flattened: Vec<_> = chunks.into_iter().flatten().collect();
+        assert_eq!(flattened, instructions);
+
+        assert!(chunk_instructions(Vec::new(), &[], &authority)
+            .unwrap()
+            .is_empty());
     }
-    Ok(())
-}
 
-fn load_sss_config(path: &str) -> Result<SssConfig> {
-    let contents = fs::read_to_string(expand_tilde(path))
-        .with_context(|| format!("Failed to read config: {}", path))?;
-    toml::from_str(&contents).context("Failed to parse config")
-}
+    #[test]
+    fn hook_descriptor_matches_account_discriminators() {
+        use anchor_lang::Discriminator;
+        use stablecoin_core::state::{BlacklistEntry, StablecoinConfig};
+
+        let without_types = hook_descriptor(false);
+        assert!(without_types.account_types.is_none());
+
+        let descriptor = hook_descriptor(true);
+        assert_eq!(descriptor.program_id, transfer_hook::ID.to_string());
+        let execute = &descriptor.instructions[0];
+        assert_eq!(execute.name, "execute");
+        assert_eq!(execute.accounts.len(), 10);
+        assert_eq!(execute.accounts[6].name, "stablecoin_config");
+
+        let types = descriptor.account_types.unwrap();
+        assert_eq!(
+            types[0].discriminator,
+            StablecoinConfig::DISCRIMINATOR.to_vec()
+        );
+        assert_eq!(
+            types[1].discriminator,
+            BlacklistEntry::DISCRIMINATOR.to_vec()
+        );
+    }
 
-fn load_solana_cli_config() -> Result<SolanaCliConfig> {
-    let path = default_solana_config_path();
-    let contents = fs::read_to_string(&path)
-        .with_context(|| format!("Failed to read Solana config: {}", path.display()))?;
-    serde_yaml::from_str(&contents).context("Failed to parse Solana config")
-}
+    #[test]
+    fn parses_recipient_csv() {
+        let first = Pubkey::new_unique();
+        let second = Pubkey::new_unique();
+        let content = format!(
+            "recipient,amount\n# airdrop\n{},1.5\n\n{}, 2\n",
+            first, second
+        );
+        let entries = parse_recipients_csv(&content, 6).unwrap();
+        assert_eq!(entries, vec![(first, 1_500_000), (second, 2)]);
 
-fn default_solana_config_path() -> PathBuf {
-    let mut path = dirs::home_dir().unwrap_or_else(|| PathBuf::from("."));
-    path.push(".config");
-    path.push("solana");
-    path.push("cli");
-    path.push("config.yml");
-    path
-}
+        assert!(parse_recipients_csv("recipient,amount\n", 6).is_err());
+        assert!(parse_recipients_csv(&format!("{},1,extra", first), 6).is_err());
+        assert!(parse_recipients_csv(&format!("{},0", first), 6).is_err());
+        assert!(parse_recipients_csv("not-a-key,1", 6).is_err());
+    }
 
-fn resolve_cluster(input: &str) -> Result<ClusterInfo> {
-    let lowered = input.to_lowercase();
-    let (url, label) = match lowered.as_str() {
-        "devnet" => (
-            "https://api.devnet.solana.com".to_string(),
-            Some("devnet".to_string()),
-        ),
-        "testnet" => (
-            "https://api.testnet.solana.com".to_string(),
-            Some("testnet".to_string()),
-        ),
-        "mainnet" | "mainnet-beta" => (
-            "https://api.mainnet-beta.solana.com".to_string(),
-            Some("mainnet-beta".to_string()),
-        ),
-        "localnet" => (
-            "http://127.0.0.1:8899".to_string(),
-            Some("localnet".to_string()),
-        ),
-        _ => {
-            if input.starts_with("http://") || input.starts_with("https://") {
-                let label = if lowered.contains("devnet") {
-                    Some("devnet".to_string())
-                } else if lowered.contains("testnet") {
-                    Some("testnet".to_string())
-                } else if lowered.contains("mainnet") {
-                    Some("mainnet-beta".to_string())
-                } else {
-                    None
-                };
-                (input.to_string(), label)
-            } else {
-                return Err(anyhow!("Unknown cluster: {}", input));
+    #[test]
+    fn plans_mint_batches_within_limits() {
+        let minter = Pubkey::new_unique();
+        let mint = Pubkey::new_unique();
+        let entries: Vec<_> = (0..40).map(|i| (Pubkey::new_unique(), i + 1)).collect();
+
+        for check_blacklist in [false, true] {
+            let batches = plan_mint_batches(
+                &Pubkey::new_unique(),
+                &[],
+                minter,
+                mint,
+                spl_token_2022::id(),
+                check_blacklist,
+                &entries,
+            )
+            .unwrap();
+            let planned: usize = batches.iter().map(|(_, count)| count).sum();
+            assert_eq!(planned, entries.len());
+            let accounts_per_entry = if check_blacklist { 2 } else { 1 };
+            for (instruction, count) in &batches {
+                assert!(*count <= stablecoin_core::constants::MAX_MINT_BATCH_SIZE);
+                assert_eq!(instruction.accounts.len(), 5 + accounts_per_entry * count);
+                assert!(
+                    transaction_size(std::slice::from_ref(instruction), &Pubkey::new_unique())
+                        .unwrap()
+                        <= solana_sdk::packet::PACKET_DATA_SIZE
+                );
             }
         }
-    };
-    Ok(ClusterInfo { url, label })
-}
-
-fn parse_commitment(value: Option<&str>) -> CommitmentConfig {
-    match value.unwrap_or("confirmed") {
-        "processed" => CommitmentConfig::processed(),
-        "finalized" => CommitmentConfig::finalized(),
-        _ => CommitmentConfig::confirmed(),
     }
-}
 
-fn expand_tilde(path: &str) -> PathBuf {
-    if let Some(stripped) = path.strip_prefix("~/").or_else(|| path.strip_prefix("~\\")) {
-        if let Some(home) = dirs::home_dir() {
-            return home.join(stripped);
-        }
-    }
-    PathBuf::from(path)
-}
+    #[test]
+    fn config_show_dumps_every_field() {
+        let mint = Pubkey::new_unique();
+        let (config_pda, bump) = find_config_pda(&mint, &stablecoin_core::ID);
+        let hook = Pubkey::new_unique();
+        let config = StablecoinConfig {
+            authority: Pubkey::new_unique(),
+            mint,
+            name: "Test USD".to_string(),
+            symbol: "TUSD".to_string(),
+            uri: String::new(),
+            decimals: 6,
+            pause_flags: PAUSE_MINT | PAUSE_TRANSFER,
+            total_minted: 500,
+            total_burned: 20,
+            audit_counter: 7,
+            features: FeatureFlags::new(true, true, false, false),
+            transfer_hook_program: Some(hook),
+            bump,
+            pause_cooldown: 60,
+            last_pause_change: 1_700_000_000,
+            max_supply: Some(1_000),
+            creator: Pubkey::new_unique(),
+            created_at: 1_690_000_000,
+            quota_window_seconds: 0,
+            allowlist_mode: true,
+            max_transfer_amount: Some(250),
+            last_action_at: 1_700_000_100,
+            last_action_by: Pubkey::new_unique(),
+            allow_self_transfer: true,
+            total_seized: 900,
+            treasury: Some(Pubkey::new_unique()),
+            lock_admin_ops: true,
+            minting_locked: true,
+            token_program: TokenProgram::Legacy.id(),
+            transfer_cooldown_seconds: Some(30),
+            require_mint_approval: true,
+        };
 
-fn parse_pubkey(value: &str) -> Result<Pubkey> {
-    Pubkey::from_str(value).map_err(|_| anyhow!("Invalid pubkey: {}", value))
-}
+        let value = serde_json::to_value(config_output(&config_pda, &config)).unwrap();
+        assert_eq!(value["address"], config_pda.to_string());
+        assert_eq!(value["transfer_hook_program"], hook.to_string());
+        assert_eq!(value["bump"], bump);
+        assert_eq!(value["total_minted"], "500");
+        assert_eq!(value["features"]["transfer_hook"], true);
+        assert_eq!(value["last_pause_change"], 1_700_000_000);
+        assert_eq!(value["is_paused"], true);
+        assert_eq!(value["pause_flags"], PAUSE_MINT | PAUSE_TRANSFER);
+        assert_eq!(value["max_supply"], "1000");
+        assert_eq!(value["creator"], config.creator.to_string());
+        assert_eq!(value["created_at"], 1_690_000_000);
+        assert_eq!(value["quota_window_seconds"], 86_400);
+        assert_eq!(value["allowlist_mode"], true);
+        assert_eq!(value["max_transfer_amount"], "250");
+        assert_eq!(value["last_action_at"], 1_700_000_100);
+        assert_eq!(value["last_action_by"], config.last_action_by.to_string());
+        assert_eq!(value["allow_self_transfer"], true);
+        assert_eq!(value["total_seized"], "900");
+        assert_eq!(value["lock_admin_ops"], true);
+        assert_eq!(value["minting_locked"], true);
+        assert_eq!(value["transfer_cooldown_seconds"], 30);
+        assert_eq!(value["require_mint_approval"], true);
+        assert_eq!(
+            value["token_program"],
+            TokenProgram::Legacy.id().to_string()
+        );
+        assert_eq!(value["treasury"], config.treasury.unwrap().to_string());
 
-fn resolve_mint(mint: &Option<String>) -> Result<Pubkey> {
-    let value = mint.as_deref().ok_or_else(|| anyhow!("Missing --mint"))?;
-    parse_pubkey(value)
-}
+        let without_hook = StablecoinConfig {
+            transfer_hook_program: None,
+            ..config
+        };
+        let value = serde_json::to_value(config_output(&config_pda, &without_hook)).unwrap();
+        assert!(value["transfer_hook_program"].is_null());
+    }
 
-fn parse_amount(value: &str, decimals: u8) -> Result<u64> {
-    let sanitized = value.replace('_', "");
-    if let Some((whole, fractional)) = sanitized.split_once('.') {
-        let whole_value: u64 = if whole.is_empty() { 0 } else { whole.parse()? };
-        let mut fraction = fractional.to_string();
-        if fraction.len() > decimals as usize {
-            return Err(anyhow!("Too many decimal places"));
-        }
-        while fraction.len() < decimals as usize {
-            fraction.push('0');
-        }
-        let fractional_value: u64 = if fraction.is_empty() {
-            0
-        } else {
-            fraction.parse()?
-        };
-        let scale = 10u64
-            .checked_pow(decimals as u32)
-            .ok_or_else(|| anyhow!("Decimal overflow"))?;
-        let total = whole_value
-            .checked_mul(scale)
-            .and_then(|value| value.checked_add(fractional_value))
-            .ok_or_else(|| anyhow!("Amount overflow"))?;
-        Ok(total)
-    } else {
-        Ok(sanitized.parse()?)
+    #[test]
+    fn compliance_report_aggregates_roles_blacklist_and_totals() {
+        let mint = Pubkey::new_unique();
+        let (config_pda, bump) = find_config_pda(&mint, &stablecoin_core::ID);
+        let config = StablecoinConfig {
+            authority: Pubkey::new_unique(),
+            mint,
+            name: "Test USD".to_string(),
+            symbol: "TUSD".to_string(),
+            uri: String::new(),
+            decimals: 6,
+            pause_flags: 0,
+            total_minted: 5_000,
+            total_burned: 1_200,
+            audit_counter: 42,
+            features: FeatureFlags::new(true, true, false, false),
+            transfer_hook_program: Some(Pubkey::new_unique()),
+            bump,
+            pause_cooldown: 0,
+            last_pause_change: 0,
+            max_supply: None,
+            creator: Pubkey::new_unique(),
+            created_at: 0,
+            quota_window_seconds: 0,
+            allowlist_mode: false,
+            max_transfer_amount: None,
+            last_action_at: 0,
+            last_action_by: Pubkey::new_unique(),
+            allow_self_transfer: false,
+            total_seized: 0,
+            treasury: None,
+            lock_admin_ops: false,
+            minting_locked: false,
+            token_program: Pubkey::default(),
+            transfer_cooldown_seconds: None,
+            require_mint_approval: false,
+        };
+        let roles = vec![
+            role_entry(
+                &config_pda,
+                &config.authority,
+                ROLE_MASTER_AUTHORITY | ROLE_MINTER,
+            ),
+            role_entry(
+                &config_pda,
+                &Pubkey::new_unique(),
+                ROLE_MINTER | ROLE_BURNER,
+            ),
+            role_entry(&config_pda, &Pubkey::new_unique(), ROLE_BLACKLISTER),
+        ];
+        let entry =
+            |reason: BlacklistReason, is_active: bool, expires_at: Option<i64>| AccountEntry {
+                address: Pubkey::new_unique(),
+                account: BlacklistEntry {
+                    config: config_pda,
+                    wallet: Pubkey::new_unique(),
+                    blacklisted_at: 0,
+                    blacklisted_by: config.authority,
+                    reason: String::new(),
+                    is_active,
+                    bump: 255,
+                    expires_at,
+                    reason_code: reason.code(),
+                },
+            };
+        let blacklist = vec![
+            entry(BlacklistReason::Sanctions, true, None),
+            entry(BlacklistReason::Fraud, true, Some(2_000)),
+            entry(BlacklistReason::Fraud, false, None),
+            entry(BlacklistReason::CourtOrder, true, Some(500)),
+        ];
+
+        let report = compliance_report(&mint, &config, 3_800, &roles, &blacklist, 1_000);
+        assert_eq!(report.supply, "3800");
+        assert_eq!(report.total_minted, "5000");
+        assert_eq!(report.total_burned, "1200");
+        assert_eq!(report.audit_counter, 42);
+        assert_eq!(report.generated_at, 1_000);
+        assert_eq!(report.role_counts.masters, 1);
+        assert_eq!(report.role_counts.minters, 2);
+        assert_eq!(report.role_counts.burners, 1);
+        assert_eq!(report.role_counts.blacklisters, 1);
+        assert_eq!(report.role_counts.seizers, 0);
+        assert_eq!(
+            report.blacklist,
+            BlacklistReport {
+                entries: 4,
+                active: 2,
+                inactive: 2,
+                by_code: BlacklistReasonCounts {
+                    sanctions: 1,
+                    fraud: 1,
+                    court_order: 0,
+                    other: 0,
+                    unspecified: 0,
+                },
+            }
+        );
+
+        let value = serde_json::to_value(&report).unwrap();
+        assert_eq!(value["mint"], mint.to_string());
+        assert_eq!(value["blacklist"]["by_code"]["sanctions"], 1);
+        assert_eq!(value["role_counts"]["minters"], 2);
     }
-}
 
-fn format_amount(amount: u64, decimals: u8) -> String {
-    if decimals == 0 {
-        return amount.to_string();
+    #[test]
+    fn encodes_partial_metadata_updates() {
+        let authority = Pubkey::new_unique();
+        let mint = Pubkey::new_unique();
+        let config_pda = find_config_pda(&mint, &stablecoin_core::ID).0;
+        let ix = build_update_metadata_instruction(UpdateMetadataParams {
+            authority,
+            config_pda,
+            mint,
+            name: None,
+            symbol: None,
+            uri: Some("https://x.io".to_string()),
+        })
+        .unwrap();
+
+        assert_eq!(ix.accounts.len(), 6);
+        assert!(ix.accounts[0].is_signer && ix.accounts[0].is_writable);
+        assert!(ix.accounts[3].is_writable);
+        let mut expected = vec![0u8, 0u8, 1u8];
+        expected.extend_from_slice(&12u32.to_le_bytes());
+        expected.extend_from_slice(b"https://x.io");
+        assert_eq!(&ix.data[8..], expected.as_slice());
+
+        assert!(Cli::try_parse_from(["sss-token", "metadata", "update"]).is_err());
+        assert!(
+            Cli::try_parse_from(["sss-token", "metadata", "update", "--symbol", "USDX"]).is_ok()
+        );
     }
-    let scale = 10u64.pow(decimals as u32);
-    let whole = amount / scale;
-    let frac = amount % scale;
-    format!("{}.{:0width$}", whole, frac, width = decimals as usize)
-}
 
-fn explorer_url(signature: &str, cluster: &ClusterInfo) -> Option<String> {
-    cluster.label.as_ref().map(|label| {
-        format!(
-            "https://explorer.solana.com/tx/{}?cluster={}",
-            signature, label
-        )
-    })
-}
+    #[test]
+    fn shows_embedded_token_metadata() {
+        let mint = Pubkey::new_unique();
+        let config_pda = find_config_pda(&mint, &stablecoin_core::ID).0;
+        let metadata = TokenMetadata {
+            update_authority: OptionalNonZeroPubkey::try_from(Some(config_pda)).unwrap(),
+            mint,
+            name: "Test USD".to_string(),
+            symbol: "TUSD".to_string(),
+            uri: "https://example.com/tusd.json".to_string(),
+            additional_metadata: vec![(
+                "attestation".to_string(),
+                "https://example.com/attestation.pdf".to_string(),
+            )],
+        };
+        let space =
+            ExtensionType::try_calculate_account_len::<Mint>(&[ExtensionType::MetadataPointer])
+                .unwrap()
+                + metadata.tlv_size_of().unwrap();
+        let mut data = vec![0u8; space];
+        let mut state = StateWithExtensionsMut::<Mint>::unpack_uninitialized(&mut data).unwrap();
+        let pointer = state.init_extension::<MetadataPointer>(true).unwrap();
+        pointer.authority = OptionalNonZeroPubkey::try_from(Some(config_pda)).unwrap();
+        pointer.metadata_address = OptionalNonZeroPubkey::try_from(Some(mint)).unwrap();
+        state.base = Mint {
+            mint_authority: COption::Some(config_pda),
+            supply: 0,
+            decimals: 6,
+            is_initialized: true,
+            freeze_authority: COption::Some(config_pda),
+        };
+        state.pack_base();
+        state.init_account_type().unwrap();
+        state.init_variable_len_extension(&metadata, false).unwrap();
+
+        let output = mint_metadata(&mint, &data).unwrap();
+        assert_eq!(output.metadata_address, Some(mint.to_string()));
+        assert!(output.embedded);
+        assert_eq!(output.update_authority, Some(config_pda.to_string()));
+        assert_eq!(output.symbol, "TUSD");
+        assert_eq!(output.additional_metadata.len(), 1);
+        assert_eq!(output.additional_metadata[0].key, "attestation");
+        assert!(!output.metaplex_metadata_exists);
+        assert_eq!(
+            output.metaplex_metadata,
+            metaplex_metadata_pda(&mint).to_string()
+        );
 
-fn send_transaction(
-    ctx: AppContext<'_>,
-    instructions: Vec<Instruction>,
-    extra_signers: Vec<&Keypair>,
-) -> Result<String> {
-    let blockhash = ctx.client.get_latest_blockhash()?;
-    let mut transaction = Transaction::new_with_payer(&instructions, Some(&ctx.payer.pubkey()));
-    let mut signers: Vec<&dyn Signer> = vec![ctx.payer];
-    for signer in extra_signers {
-        if signer.pubkey() != ctx.payer.pubkey() {
-            signers.push(signer);
-        }
+        assert!(Cli::try_parse_from(["sss-token", "metadata", "set-field", "--key", "x"]).is_err());
+        assert!(Cli::try_parse_from([
+            "sss-token",
+            "metadata",
+            "set-field",
+            "--key",
+            "attestation",
+            "--value",
+            "https://example.com/a.pdf",
+        ])
+        .is_ok());
     }
-    transaction.sign(&signers, blockhash);
-    let signature = ctx.client.send_and_confirm_transaction(&transaction)?;
-    Ok(signature.to_string())
-}
 
-fn fetch_config(ctx: AppContext<'_>, config_pda: &Pubkey) -> Result<StablecoinConfig> {
-    let account = ctx.client.get_account(config_pda)?;
-    let mut data = account.data.as_slice();
-    StablecoinConfig::try_deserialize(&mut data).context("Failed to decode config")
-}
+    #[test]
+    fn verifies_each_mint_authority_against_the_config() {
+        let mint = Pubkey::new_unique();
+        let config_pda = find_config_pda(&mint, &stablecoin_core::ID).0;
+        let other = Pubkey::new_unique();
+        let build = |freeze_authority: COption<Pubkey>| {
+            let space = ExtensionType::try_calculate_account_len::<Mint>(&[
+                ExtensionType::MintCloseAuthority,
+                ExtensionType::PermanentDelegate,
+            ])
+            .unwrap();
+            let mut data = vec![0u8; space];
+            let mut state =
+                StateWithExtensionsMut::<Mint>::unpack_uninitialized(&mut data).unwrap();
+            state
+                .init_extension::<MintCloseAuthority>(true)
+                .unwrap()
+                .close_authority = OptionalNonZeroPubkey::try_from(Some(config_pda)).unwrap();
+            state
+                .init_extension::<PermanentDelegate>(true)
+                .unwrap()
+                .delegate = OptionalNonZeroPubkey::try_from(Some(other)).unwrap();
+            state.base = Mint {
+                mint_authority: COption::Some(config_pda),
+                supply: 0,
+                decimals: 6,
+                is_initialized: true,
+                freeze_authority,
+            };
+            state.pack_base();
+            state.init_account_type().unwrap();
+            data
+        };
 
-fn fetch_role_account(ctx: AppContext<'_>, role_pda: &Pubkey) -> Result<Option<RoleAccount>> {
-    let account = match ctx.client.get_account(role_pda) {
-        Ok(value) => value,
-        Err(_) => return Ok(None),
-    };
-    let mut data = account.data.as_slice();
-    let decoded = RoleAccount::try_deserialize(&mut data).context("Failed to decode role")?;
-    Ok(Some(decoded))
-}
+        let data = build(COption::None);
+        let report = verify_authorities(&mint, &data, &config_pda, false).unwrap();
+        assert!(!report.ok);
+        let status = |name: &str| {
+            report
+                .checks
+                .iter()
+                .find(|check| check.authority == name)
+                .unwrap()
+                .status
+        };
+        assert_eq!(status("mint_authority"), AuthorityStatus::Match);
+        assert_eq!(status("freeze_authority"), AuthorityStatus::Unset);
+        assert_eq!(status("close_authority"), AuthorityStatus::Match);
+        assert_eq!(status("permanent_delegate"), AuthorityStatus::Mismatch);
+        assert_eq!(
+            status("transfer_hook_authority"),
+            AuthorityStatus::NotPresent
+        );
+        assert_eq!(
+            status("metadata_update_authority"),
+            AuthorityStatus::NotPresent
+        );
+        assert_eq!(report.checks.len(), 7);
+        assert!(report.authority_ok("mint_authority"));
+        assert!(!report.authority_ok("freeze_authority"));
+        // An absent extension passes verification but is not assigned.
+        assert!(!report.authority_ok("transfer_hook_authority"));
+
+        let report =
+            verify_authorities(&mint, &build(COption::Some(other)), &config_pda, false).unwrap();
+        assert!(!report.authority_ok("freeze_authority"));
+        let mut data = build(COption::Some(config_pda));
+        let report = verify_authorities(&mint, &data, &config_pda, false).unwrap();
+        assert!(report.authority_ok("freeze_authority"));
+
+        // A null mint authority only passes once minting is locked.
+        let mut state = StateWithExtensionsMut::<Mint>::unpack(&mut data).unwrap();
+        state.base.mint_authority = COption::None;
+        state.pack_base();
+        let report = verify_authorities(&mint, &data, &config_pda, false).unwrap();
+        assert!(!report.authority_ok("mint_authority"));
+        let report = verify_authorities(&mint, &data, &config_pda, true).unwrap();
+        assert!(report.authority_ok("mint_authority"));
+    }
 
-fn fetch_blacklist_entry(
-    ctx: AppContext<'_>,
-    entry_pda: &Pubkey,
-) -> Result<Option<BlacklistEntry>> {
-    let account = match ctx.client.get_account(entry_pda) {
-        Ok(value) => value,
-        Err(_) => return Ok(None),
-    };
-    let mut data = account.data.as_slice();
-    let decoded =
-        BlacklistEntry::try_deserialize(&mut data).context("Failed to decode blacklist")?;
-    Ok(Some(decoded))
-}
+    #[test]
+    fn detailed_status_lists_minter_windows() {
+        let config_pda = Pubkey::new_unique();
+        let config = StablecoinConfig {
+            quota_window_seconds: 3_600,
+            ..sample_config(Pubkey::new_unique())
+        };
+        let now = 1_700_000_000;
+        let mut active = role_entry(&config_pda, &Pubkey::new_unique(), ROLE_MINTER);
+        active.account.mint_quota = Some(5_000_000);
+        active.account.minted_current_window = 1_500_000;
+        active.account.window_start = now - 600;
+        let mut expired = role_entry(&config_pda, &Pubkey::new_unique(), ROLE_MINTER);
+        expired.account.minted_current_window = 2_000_000;
+        expired.account.window_start = now - 7_200;
+        let burner = role_entry(&config_pda, &Pubkey::new_unique(), ROLE_BURNER);
+
+        let details = minter_details(&[active.clone(), expired.clone(), burner], &config, now);
+        assert_eq!(details.len(), 2);
+        assert_eq!(details[0].address, active.account.authority.to_string());
+        assert_eq!(details[0].quota.as_deref(), Some("5000000"));
+        assert_eq!(details[0].quota_ui.as_deref(), Some("5.000000"));
+        assert_eq!(details[0].minted_current_window, "1500000");
+        assert_eq!(details[0].window_start, now - 600);
+        assert_eq!(details[0].window_seconds, 3_600);
+        assert_eq!(details[1].quota, None);
+        assert_eq!(details[1].minted_current_window, "0");
+        assert_eq!(details[1].window_start, now - 7_200);
+
+        let cli = Cli::try_parse_from(["sss-token", "status", "--detailed"]).unwrap();
+        assert!(matches!(cli.command, Commands::Status(args) if args.detailed));
+        let cli = Cli::try_parse_from(["sss-token", "status"]).unwrap();
+        assert!(matches!(cli.command, Commands::Status(args) if !args.detailed));
+        let cli = Cli::try_parse_from(["sss-token", "status", "default-frozen", "off"]).unwrap();
+        assert!(matches!(
+            cli.command,
+            Commands::Status(StatusArgs {
+                command: Some(StatusCmd::DefaultFrozen(args)),
+                ..
+            }) if !args.state.enabled()
+        ));
+        assert!(
+            Cli::try_parse_from(["sss-token", "status", "--detailed", "default-frozen", "on"])
+                .is_err()
+        );
+    }
 
-fn list_role_accounts(
-    ctx: AppContext<'_>,
-    config_pda: &Pubkey,
-) -> Result<Vec<AccountEntry<RoleAccount>>> {
-    let mut config = RpcProgramAccountsConfig::default();
-    config.filters = Some(vec![RpcFilterType::Memcmp(Memcmp::new_base58_encoded(
-        8,
-        config_pda.as_ref(),
-    ))]);
-    config.account_config = RpcAccountInfoConfig {
-        encoding: None,
-        commitment: Some(ctx.commitment),
-        data_slice: None,
-        min_context_slot: None,
-    };
+    #[test]
+    fn verifies_mint_extensions_against_the_config() {
+        let mint = Pubkey::new_unique();
+        let config_pda = find_config_pda(&mint, &stablecoin_core::ID).0;
+        let hook = Pubkey::new_unique();
+        let config = StablecoinConfig {
+            features: FeatureFlags::new(true, true, false, false),
+            transfer_hook_program: Some(hook),
+            ..sample_config(mint)
+        };
+        let build = |hook_program: Pubkey| {
+            let space = ExtensionType::try_calculate_account_len::<Mint>(&[
+                ExtensionType::MintCloseAuthority,
+                ExtensionType::PermanentDelegate,
+                ExtensionType::TransferHook,
+            ])
+            .unwrap();
+            let mut data = vec![0u8; space];
+            let mut state =
+                StateWithExtensionsMut::<Mint>::unpack_uninitialized(&mut data).unwrap();
+            let pda = OptionalNonZeroPubkey::try_from(Some(config_pda)).unwrap();
+            state
+                .init_extension::<MintCloseAuthority>(true)
+                .unwrap()
+                .close_authority = pda;
+            state
+                .init_extension::<PermanentDelegate>(true)
+                .unwrap()
+                .delegate = pda;
+            let extension = state.init_extension::<TransferHook>(true).unwrap();
+            extension.authority = pda;
+            extension.program_id = OptionalNonZeroPubkey::try_from(Some(hook_program)).unwrap();
+            state.base = Mint {
+                mint_authority: COption::Some(config_pda),
+                supply: 0,
+                decimals: 6,
+                is_initialized: true,
+                freeze_authority: COption::Some(config_pda),
+            };
+            state.pack_base();
+            state.init_account_type().unwrap();
+            data
+        };
 
-    let accounts = ctx
-        .client
-        .get_program_accounts_with_config(&stablecoin_core::ID, config)?;
+        let output = verify_mint(&mint, &build(hook), &config_pda, &config).unwrap();
+        assert!(output.ok);
+        assert!(output.checks.iter().all(|check| check.passed));
+
+        let output =
+            verify_mint(&mint, &build(Pubkey::new_unique()), &config_pda, &config).unwrap();
+        assert!(!output.ok);
+        let failed: Vec<_> = output
+            .checks
+            .iter()
+            .filter(|check| !check.passed)
+            .map(|check| check.check)
+            .collect();
+        assert_eq!(failed, ["transfer_hook_program"]);
+
+        // A mint initialized as SSS-1 elsewhere must not carry the SSS-2 extensions.
+        let sss1 = StablecoinConfig {
+            features: FeatureFlags::new(false, false, false, false),
+            transfer_hook_program: None,
+            ..config
+        };
+        let output = verify_mint(&mint, &build(hook), &config_pda, &sss1).unwrap();
+        let failed: Vec<_> = output
+            .checks
+            .iter()
+            .filter(|check| !check.passed)
+            .map(|check| check.check)
+            .collect();
+        assert_eq!(failed, ["permanent_delegate", "transfer_hook_program"]);
+        let value = serde_json::to_value(&output).unwrap();
+        assert_eq!(value["ok"], false);
+        assert_eq!(value["checks"][0]["passed"], true);
+    }
 
-    let mut result = Vec::new();
-    for (_key, account) in accounts {
-        let mut data = account.data.as_slice();
-        if let Ok(decoded) = RoleAccount::try_deserialize(&mut data) {
-            result.push(AccountEntry { account: decoded });
+    #[test]
+    fn classifies_balance_changes_from_logs() {
+        let logs = |lines: &[&str]| {
+            lines
+                .iter()
+                .map(|line| line.to_string())
+                .collect::<Vec<_>>()
+        };
+        let mint = logs(&[
+            "Program log: Instruction: Mint",
+            "Program log: Instruction: MintTo",
+        ]);
+        let seize = logs(&[
+            "Program log: Instruction: Seize",
+            "Program log: Instruction: TransferChecked",
+        ]);
+        let transfer = logs(&["Program log: Instruction: TransferChecked"]);
+
+        assert_eq!(classify_balance_change(10, &mint), "mint received");
+        assert_eq!(classify_balance_change(-10, &seize), "seized");
+        assert_eq!(classify_balance_change(10, &seize), "seize proceeds");
+        assert_eq!(classify_balance_change(10, &transfer), "transfer in");
+        assert_eq!(classify_balance_change(-10, &transfer), "transfer out");
+        assert_eq!(classify_balance_change(-10, &[]), "unknown");
+
+        assert_eq!(
+            websocket_url("https://api.devnet.solana.com"),
+            "wss://api.devnet.solana.com"
+        );
+        assert_eq!(
+            websocket_url("http://127.0.0.1:8899"),
+            "ws://127.0.0.1:8900"
+        );
+        assert_eq!(
+            websocket_url("https://rpc.example.com:443/key"),
+            "wss://rpc.example.com:444/key"
+        );
+    }
+
+    #[test]
+    fn max_supply_can_only_be_introduced_or_lowered() {
+        assert!(check_max_supply_update(None, 500, 1_000).is_ok());
+        assert!(check_max_supply_update(Some(1_000), 500, 800).is_ok());
+        assert!(check_max_supply_update(Some(1_000), 500, 500).is_ok());
+        assert!(check_max_supply_update(Some(1_000), 500, 1_200).is_err());
+        assert!(check_max_supply_update(None, 500, 499).is_err());
+
+        assert_eq!(supply_headroom(Some(1_000), 750), Some(250));
+        assert_eq!(supply_headroom(Some(1_000), 1_200), Some(0));
+        assert_eq!(supply_headroom(None, 750), None);
+    }
+
+    #[test]
+    fn rejects_unrecoverable_mint_recipients() {
+        let mint = Pubkey::new_unique();
+        let config_pda = find_config_pda(&mint, &stablecoin_core::ID).0;
+        for forbidden in [
+            solana_sdk::system_program::id(),
+            solana_sdk::incinerator::id(),
+            config_pda,
+            mint,
+        ] {
+            assert!(check_mint_recipient(&forbidden, &config_pda, &mint).is_err());
         }
+        assert!(check_mint_recipient(&Pubkey::new_unique(), &config_pda, &mint).is_ok());
     }
-    Ok(result)
-}
 
-fn list_blacklist_entries(
-    ctx: AppContext<'_>,
-    config_pda: &Pubkey,
-) -> Result<Vec<AccountEntry<BlacklistEntry>>> {
-    let mut config = RpcProgramAccountsConfig::default();
-    config.filters = Some(vec![RpcFilterType::Memcmp(Memcmp::new_base58_encoded(
-        8,
-        config_pda.as_ref(),
-    ))]);
-    config.account_config = RpcAccountInfoConfig {
-        encoding: None,
-        commitment: Some(ctx.commitment),
-        data_slice: None,
-        min_context_slot: None,
-    };
+    #[test]
+    fn decodes_core_events_from_program_data_logs() {
+        use anchor_lang::Event;
+        use base64::Engine;
+        use stablecoin_core::events::{TokensBurned, TokensMinted};
+
+        let config = Pubkey::new_unique();
+        let minted = TokensMinted {
+            config,
+            mint: Pubkey::new_unique(),
+            recipient: Pubkey::new_unique(),
+            amount: 5_000_000,
+            minter: Pubkey::new_unique(),
+            new_total_supply: 5_000_000,
+            timestamp: 1_700_000_000,
+            schema_version: EVENT_SCHEMA_VERSION,
+        };
+        let burned = TokensBurned {
+            config,
+            mint: minted.mint,
+            burner: Pubkey::new_unique(),
+            amount: 1,
+            new_total_supply: 4_999_999,
+            timestamp: 1_700_000_100,
+            schema_version: EVENT_SCHEMA_VERSION,
+        };
+        let encode = |data: Vec<u8>| {
+            format!(
+                "Program data: {}",
+                base64::engine::general_purpose::STANDARD.encode(data)
+            )
+        };
+        let core = stablecoin_core::ID.to_string();
+        let other = Pubkey::new_unique().to_string();
+        let logs = vec![
+            format!("Program {} invoke [1]", core),
+            "Program log: Instruction: MintTokens".to_string(),
+            format!("Program {} invoke [2]", other),
+            encode(burned.data()),
+            format!("Program {} success", other),
+            encode(minted.data()),
+            "Program data: not-base64!".to_string(),
+            format!("Program {} consumed 12000 of 200000 compute units", core),
+            format!("Program {} success", core),
+            format!("Program {} invoke [1]", core),
+            encode(burned.data()),
+            format!("Program {} failed: custom program error: 0x1", core),
+        ];
+
+        let events = decode_program_events(&logs, &stablecoin_core::ID);
+        assert_eq!(events.len(), 2);
+        assert_eq!(events[0].name, "TokensMinted");
+        assert_eq!(events[0].action, "MINT");
+        assert_eq!(events[0].timestamp, 1_700_000_000);
+        assert_eq!(events[0].fields["amount"], "5000000");
+        assert_eq!(events[0].fields["recipient"], minted.recipient.to_string());
+        assert_eq!(events[0].fields["schema_version"], EVENT_SCHEMA_VERSION);
+        assert_eq!(events[1].name, "TokensBurned");
+
+        // A mint event logged before schema_version existed still decodes.
+        let mut unversioned = minted.data();
+        unversioned.truncate(unversioned.len() - 2);
+        let event = decode_event(&unversioned).unwrap();
+        assert_eq!(event.fields["amount"], "5000000");
+        assert_eq!(event.fields["schema_version"], 0);
+
+        let range = TimeRange::default();
+        assert!(event_matches(&events[0], &range, Some("mint")));
+        assert!(event_matches(&events[0], &range, Some("TokensMinted")));
+        assert!(!event_matches(&events[0], &range, Some("BURN")));
+        let range = TimeRange {
+            from: Some(1_700_000_050),
+            to: None,
+        };
+        assert!(!event_matches(&events[0], &range, None));
+        assert!(event_matches(&events[1], &range, None));
+    }
 
-    let accounts = ctx
-        .client
-        .get_program_accounts_with_config(&stablecoin_core::ID, config)?;
+    #[test]
+    fn decodes_transfer_hook_decision_lines() {
+        let hook = transfer_hook::ID.to_string();
+        let token = spl_token_2022::id().to_string();
+        let (source, destination) = (Pubkey::new_unique(), Pubkey::new_unique());
+        let line = |decision: &str| {
+            format!(
+                "Program log: sss-hook: decision={} source_owner={} destination_owner={} amount=250",
+                decision, source, destination
+            )
+        };
+        let logs = vec![
+            format!("Program {} invoke [1]", token),
+            // Another program logging the same text is not the hook.
+            line("allow"),
+            format!("Program {} invoke [2]", hook),
+            line("allow"),
+            "Program log: sss-hook: decision=deny amount=1".to_string(),
+            format!("Program {} success", hook),
+            format!("Program {} invoke [2]", hook),
+            line("deny"),
+            "Program log: AnchorError occurred. Error Code: TransferDenied.".to_string(),
+            format!("Program {} failed: custom program error: 0x1770", hook),
+            format!("Program {} failed: custom program error: 0x1770", token),
+        ];
+
+        let decisions = decode_hook_decisions(&logs, &transfer_hook::ID);
+        assert_eq!(decisions.len(), 2);
+        assert_eq!(decisions[0].decision, "allow");
+        assert_eq!(decisions[0].source_owner, source.to_string());
+        assert_eq!(decisions[0].destination_owner, destination.to_string());
+        assert_eq!(decisions[0].amount, "250");
+        assert_eq!(decisions[1].action(), "HOOK_DENY");
+        assert!(decisions[1].matches_action("hook_deny"));
+        assert!(decisions[1].matches_action("TransferHookDecision"));
+        assert!(!decisions[1].matches_action("HOOK_ALLOW"));
+    }
 
-    let mut result = Vec::new();
-    for (_key, account) in accounts {
-        let mut data = account.data.as_slice();
-        if let Ok(decoded) = BlacklistEntry::try_deserialize(&mut data) {
-            result.push(AccountEntry { account: decoded });
+    #[test]
+    fn watch_streams_only_this_configs_events() {
+        use anchor_lang::Event;
+        use base64::Engine;
+        use stablecoin_core::events::TokensMinted;
+
+        let minted = |config: Pubkey| TokensMinted {
+            config,
+            mint: Pubkey::new_unique(),
+            recipient: Pubkey::new_unique(),
+            amount: 1,
+            minter: Pubkey::new_unique(),
+            new_total_supply: 1,
+            timestamp: 1_700_000_000,
+            schema_version: EVENT_SCHEMA_VERSION,
+        };
+        let config = Pubkey::new_unique();
+        let core = stablecoin_core::ID.to_string();
+        let logs = vec![
+            format!("Program {} invoke [1]", core),
+            format!(
+                "Program data: {}",
+                base64::engine::general_purpose::STANDARD.encode(minted(config).data())
+            ),
+            format!(
+                "Program data: {}",
+                base64::engine::general_purpose::STANDARD
+                    .encode(minted(Pubkey::new_unique()).data())
+            ),
+            format!("Program {} success", core),
+        ];
+
+        let events = config_events(&logs, &config, None);
+        assert_eq!(events.len(), 1);
+        assert_eq!(events[0].config(), Some(config.to_string().as_str()));
+        assert_eq!(config_events(&logs, &config, Some("mint")).len(), 1);
+        assert!(config_events(&logs, &config, Some("BURN")).is_empty());
+
+        let parse = |args: &[&str]| Cli::try_parse_from(args.iter().copied());
+        let cli = parse(&["sss-token", "watch", "--action", "MINT"]).unwrap();
+        match cli.command {
+            Commands::Watch(args) => {
+                assert_eq!(args.balance, None);
+                assert_eq!(args.action.as_deref(), Some("MINT"));
+            }
+            _ => panic!("expected watch"),
         }
+        let owner = Pubkey::new_unique().to_string();
+        assert!(parse(&["sss-token", "watch", "--balance", &owner]).is_ok());
+        assert!(parse(&[
+            "sss-token",
+            "watch",
+            "--balance",
+            &owner,
+            "--action",
+            "MINT"
+        ])
+        .is_err());
     }
-    Ok(result)
-}
-
-fn count_role(entries: &[AccountEntry<RoleAccount>], role: u8) -> usize {
-    entries
-        .iter()
-        .filter(|entry| entry.account.roles & role != 0)
-        .count()
-}
 
-fn fetch_token_account(ctx: AppContext<'_>, address: &Pubkey) -> Result<TokenAccountInfo> {
-    let account = ctx.client.get_account(address)?;
-    let parsed = StateWithExtensions::<TokenAccount2022>::unpack(&account.data)
-        .map_err(|err| anyhow!("Failed to decode token account: {}", err))?;
-    Ok(TokenAccountInfo {
-        owner: parsed.base.owner,
-        mint: parsed.base.mint,
-    })
-}
+    #[test]
+    fn appends_hook_accounts_to_transfers_when_enabled() {
+        let owner = Pubkey::new_unique();
+        let recipient = Pubkey::new_unique();
+        let mint = Pubkey::new_unique();
+        let hook_program = Pubkey::new_unique();
+        let params = |transfer_hook_program, allowlist_mode| TransferParams {
+            owner,
+            recipient,
+            mint,
+            source_ata: Pubkey::new_unique(),
+            destination_ata: Pubkey::new_unique(),
+            amount: 10,
+            decimals: 6,
+            token_program: spl_token_2022::id(),
+            transfer_hook_program,
+            allowlist_mode,
+            transfer_cooldown: false,
+        };
 
-#[derive(Clone)]
-struct AccountEntry<T> {
-    account: T,
-}
+        let plain = build_transfer_instruction(params(None, false)).unwrap();
+        assert_eq!(plain.accounts.len(), 4);
+
+        let hooked = build_transfer_instruction(params(Some(hook_program), false)).unwrap();
+        let config = find_config_pda(&mint, &stablecoin_core::ID).0;
+        let extra: Vec<Pubkey> = hooked.accounts[4..]
+            .iter()
+            .map(|meta| meta.pubkey)
+            .collect();
+        assert_eq!(
+            extra,
+            vec![
+                stablecoin_core::ID,
+                config,
+                find_blacklist_pda(&config, &owner, &stablecoin_core::ID).0,
+                find_blacklist_pda(&config, &recipient, &stablecoin_core::ID).0,
+                hook_program,
+                find_extra_account_metas_pda(&mint, &hook_program).0,
+            ]
+        );
+        assert!(hooked.accounts[4..]
+            .iter()
+            .all(|meta| !meta.is_writable && !meta.is_signer));
+
+        let allowlisted = build_transfer_instruction(params(Some(hook_program), true)).unwrap();
+        assert_eq!(
+            allowlisted.accounts[6].pubkey,
+            find_allowlist_pda(&config, &owner, &stablecoin_core::ID).0
+        );
+        assert_eq!(
+            allowlisted.accounts[7].pubkey,
+            find_allowlist_pda(&config, &recipient, &stablecoin_core::ID).0
+        );
 
-#[derive(Clone, Copy)]
-struct TokenAccountInfo {
-    owner: Pubkey,
-    mint: Pubkey,
-}
+        let cooldown = build_transfer_instruction(TransferParams {
+            transfer_cooldown: true,
+            ..params(Some(hook_program), false)
+        })
+        .unwrap();
+        let last_transfer = cooldown.accounts.last().unwrap();
+        assert_eq!(cooldown.accounts.len(), hooked.accounts.len() + 1);
+        assert_eq!(
+            last_transfer.pubkey,
+            find_last_transfer_pda(&config, &owner, &hook_program).0
+        );
+        assert!(last_transfer.is_writable && !last_transfer.is_signer);
+    }
 
-fn find_config_pda(mint: &Pubkey, program_id: &Pubkey) -> (Pubkey, u8) {
-    Pubkey::find_program_address(&[b"stablecoin", mint.as_ref()], program_id)
-}
+    #[test]
+    fn loads_keypair_files_and_checks_usb_uris() {
+        let keypair = Keypair::new();
+        let path = std::env::temp_dir().join(format!("sss-signer-{}.json", std::process::id()));
+        solana_sdk::signature::write_keypair_file(&keypair, &path).unwrap();
+        let signer = load_signer(path.to_str().unwrap()).unwrap();
+        std::fs::remove_file(&path).unwrap();
+        assert_eq!(signer.pubkey(), keypair.pubkey());
+
+        assert!(load_signer("/nonexistent/keypair.json").is_err());
+        let err = load_signer("usb://ledger?key=not-a-path").err().unwrap();
+        assert!(err.to_string().contains("derivation path"), "{}", err);
+        let err = load_signer("usb://trezor").err().unwrap();
+        assert!(err.to_string().contains("Invalid keypair URI"), "{}", err);
+    }
 
-fn find_role_pda(config: &Pubkey, authority: &Pubkey, program_id: &Pubkey) -> (Pubkey, u8) {
-    Pubkey::find_program_address(&[b"role", config.as_ref(), authority.as_ref()], program_id)
-}
+    #[test]
+    fn pages_holders_in_a_stable_order() {
+        let holder = |token_account: &str, amount| HolderInfo {
+            owner: String::new(),
+            token_account: token_account.to_string(),
+            amount,
+        };
+        let holders = vec![
+            holder("c", 5),
+            holder("a", 10),
+            holder("b", 5),
+            holder("d", 1),
+        ];
+        let accounts = |page: Vec<HolderInfo>| {
+            page.into_iter()
+                .map(|holder| holder.token_account)
+                .collect::<Vec<_>>()
+        };
+        assert_eq!(
+            accounts(page_holders(holders.clone(), SortOrder::Desc, 0, None)),
+            ["a", "b", "c", "d"]
+        );
+        assert_eq!(
+            accounts(page_holders(holders.clone(), SortOrder::Asc, 1, Some(2))),
+            ["b", "c"]
+        );
+        assert!(page_holders(holders, SortOrder::Desc, 4, Some(2)).is_empty());
 
-fn find_blacklist_pda(config: &Pubkey, wallet: &Pubkey, program_id: &Pubkey) -> (Pubkey, u8) {
-    Pubkey::find_program_address(
-        &[b"blacklist", config.as_ref(), wallet.as_ref()],
-        program_id,
-    )
-}
+        let owner = Pubkey::new_unique();
+        let mut data = owner.to_bytes().to_vec();
+        data.extend_from_slice(&42u64.to_le_bytes());
+        assert_eq!(decode_owner_and_amount(&data).unwrap(), (owner, 42));
+        assert!(decode_owner_and_amount(&data[..39]).is_err());
+    }
 
-fn find_extra_account_metas_pda(mint: &Pubkey, hook_program: &Pubkey) -> (Pubkey, u8) {
-    Pubkey::find_program_address(&[b"extra-account-metas", mint.as_ref()], hook_program)
-}
+    #[test]
+    fn writes_holder_snapshots_as_csv() {
+        let snapshot = HoldersSnapshot {
+            mint: "Mint".to_string(),
+            slot: 42,
+            timestamp: 1_700_000_000,
+            decimals: 6,
+            supply: "150".to_string(),
+            holder_count: 2,
+            holders: vec![
+                HolderInfo {
+                    owner: "alice".to_string(),
+                    token_account: "ata1".to_string(),
+                    amount: 100,
+                },
+                HolderInfo {
+                    owner: "bob".to_string(),
+                    token_account: "ata2".to_string(),
+                    amount: 50,
+                },
+            ],
+        };
+        assert_eq!(
+            snapshot_csv(&snapshot),
+            "# mint=Mint,slot=42,timestamp=1700000000,decimals=6,supply=150,holder_count=2\n\
+             owner,token_account,amount\n\
+             alice,ata1,100\n\
+             bob,ata2,50\n"
+        );
+    }
 
-fn anchor_discriminator(name: &str) -> [u8; 8] {
-    let mut hasher = Sha256::new();
-    hasher.update(format!("global:{}", name));
-    let hash = hasher.finalize();
-    let mut output = [0u8; 8];
-    output.copy_from_slice(&hash[..8]);
-    output
-}
+    #[test]
+    fn checks_decimals_range() {
+        assert!(check_decimals(0).is_ok());
+        assert!(check_decimals(9).is_ok());
+        assert!(check_decimals(10).is_err());
+    }
 
-fn build_instruction(
-    name: &str,
-    data: Vec<u8>,
-    accounts: Vec<AccountMeta>,
-    program_id: Pubkey,
-) -> Instruction {
-    let mut payload = Vec::with_capacity(8 + data.len());
-    payload.extend_from_slice(&anchor_discriminator(name));
-    payload.extend_from_slice(&data);
-    Instruction {
-        program_id,
-        accounts,
-        data: payload,
+    #[test]
+    fn legacy_mints_reject_token_2022_extensions() {
+        assert!(check_legacy_mint(false, false, false, false, false).is_ok());
+        let err = check_legacy_mint(true, false, true, false, false)
+            .unwrap_err()
+            .to_string();
+        assert!(err.contains("metadata, transfer_hook"), "{err}");
+
+        let config =
+            "[token]\nname = \"Test USD\"\nsymbol = \"TUSD\"\ntoken_program = \"legacy\"\n";
+        let parsed = parse_sss_config(config, ConfigFormat::Toml).unwrap();
+        assert_eq!(parsed.token.token_program, Some(TokenProgramKind::Legacy));
+        assert_eq!(TokenProgramKind::Legacy.id(), TokenProgram::Legacy.id());
+        // Metadata is on by default, which a legacy mint cannot carry.
+        let report = check_sss_config("sss.toml", config);
+        assert_eq!(report.errors.len(), 1, "{:?}", report.errors);
+        let report = check_sss_config(
+            "sss.toml",
+            &format!("{config}[extensions]\nmetadata = false\n"),
+        );
+        assert!(report.errors.is_empty(), "{:?}", report.errors);
     }
-}
 
-#[derive(BorshSerialize)]
-struct InitializeArgs {
-    name: String,
-    symbol: String,
-    uri: String,
-    decimals: u8,
-    enable_permanent_delegate: bool,
-    enable_transfer_hook: bool,
-    default_account_frozen: bool,
-    transfer_hook_program: Option<Pubkey>,
-}
+    #[test]
+    fn destructive_actions_prompt_unless_skipped() {
+        let cli = Cli::try_parse_from(["sss-token", "pause"]).unwrap();
+        assert!(!cli.yes);
+        let cli = Cli::try_parse_from(["sss-token", "-y", "pause"]).unwrap();
+        assert!(cli.yes);
+        let cli = Cli::try_parse_from([
+            "sss-token",
+            "seize-batch",
+            "--file",
+            "targets.txt",
+            "--to",
+            "Treasury",
+            "--yes",
+        ])
+        .unwrap();
+        assert!(cli.yes);
+
+        assert!(needs_prompt(OutputFormat::Text, false, true).unwrap());
+        assert!(!needs_prompt(OutputFormat::Text, true, true).unwrap());
+        assert!(!needs_prompt(OutputFormat::Text, false, false).unwrap());
+        assert!(!needs_prompt(OutputFormat::Json, true, true).unwrap());
+        assert!(!needs_prompt(OutputFormat::Json, false, false).unwrap());
+        let err = needs_prompt(OutputFormat::Json, false, true).unwrap_err();
+        assert!(err.to_string().contains("--yes"));
+
+        assert!(confirm("Freeze?", true).is_ok());
+        assert!(is_yes("y"));
+        assert!(is_yes("YES"));
+        assert!(!is_yes(""));
+        assert!(!is_yes("no"));
+    }
 
-#[derive(BorshSerialize)]
-struct UpdateRolesArgs {
-    target: Pubkey,
-    roles: u8,
-    mint_quota: Option<u64>,
-}
+    #[test]
+    fn parses_role_names() {
+        assert_eq!(parse_role_names("minter").unwrap(), ROLE_MINTER);
+        assert_eq!(
+            parse_role_names(" Minter, burner,").unwrap(),
+            ROLE_MINTER | ROLE_BURNER
+        );
+        assert_eq!(parse_role_names("master").unwrap(), ROLE_MASTER_AUTHORITY);
+        assert!(parse_role_names("").is_err());
+        assert!(parse_role_names("minter,admin").is_err());
+        assert_eq!(
+            role_names(ROLE_MINTER | ROLE_BLACKLISTER),
+            vec!["minter", "blacklister"]
+        );
+        assert!(role_names(0).is_empty());
+    }
 
-#[derive(BorshSerialize)]
-struct MintBurnArgs {
-    amount: u64,
-}
+    #[test]
+    fn parses_reason_codes_and_counts_entries_by_code() {
+        assert_eq!(parse_reason_code("sanctions").unwrap(), 1);
+        assert_eq!(parse_reason_code(" Court_Order").unwrap(), 3);
+        assert_eq!(parse_reason_code("other").unwrap(), 4);
+        assert!(parse_reason_code("unspecified").is_err());
+        assert!(parse_reason_code("spam").is_err());
+        assert_eq!(reason_code_name(2), "fraud");
+        assert_eq!(reason_code_name(9), "unknown");
+
+        let entry = |reason_code: u8, is_active: bool, expires_at: Option<i64>| AccountEntry {
+            address: Pubkey::new_unique(),
+            account: BlacklistEntry {
+                config: Pubkey::new_unique(),
+                wallet: Pubkey::new_unique(),
+                blacklisted_at: 0,
+                blacklisted_by: Pubkey::new_unique(),
+                reason: String::new(),
+                is_active,
+                bump: 255,
+                expires_at,
+                reason_code,
+            },
+        };
+        let entries = vec![
+            entry(BlacklistReason::Sanctions.code(), true, None),
+            entry(BlacklistReason::Sanctions.code(), true, Some(2_000)),
+            entry(BlacklistReason::Fraud.code(), false, None),
+            entry(BlacklistReason::CourtOrder.code(), true, Some(999)),
+            entry(BlacklistReason::Other.code(), true, None),
+            entry(0, true, None),
+            entry(9, true, None),
+        ];
+        assert_eq!(
+            blacklist_reason_counts(&entries, 1_000),
+            BlacklistReasonCounts {
+                sanctions: 2,
+                fraud: 0,
+                court_order: 0,
+                other: 1,
+                unspecified: 2,
+            }
+        );
+    }
 
-#[derive(BorshSerialize)]
-struct AddToBlacklistArgs {
-    wallet: Pubkey,
-    reason: String,
-}
+    #[test]
+    fn grant_and_revoke_only_touch_requested_roles() {
+        let roles = ROLE_MINTER | ROLE_BURNER;
+        assert_eq!(
+            updated_role_mask(roles, ROLE_BLACKLISTER, true).unwrap(),
+            roles | ROLE_BLACKLISTER
+        );
+        assert_eq!(
+            updated_role_mask(roles, ROLE_BURNER, false).unwrap(),
+            ROLE_MINTER
+        );
+        assert!(updated_role_mask(roles, ROLE_BURNER, true).is_err());
+        assert!(updated_role_mask(roles, ROLE_BLACKLISTER, false).is_err());
+        assert!(updated_role_mask(roles, ROLE_MINTER, true).is_err());
+        assert!(updated_role_mask(ROLE_MASTER_AUTHORITY, ROLE_MASTER_AUTHORITY, false).is_err());
+    }
 
-struct InitializeParams {
-    authority: Pubkey,
-    mint: Pubkey,
-    name: String,
-    symbol: String,
-    uri: String,
-    decimals: u8,
-    enable_permanent_delegate: bool,
-    enable_transfer_hook: bool,
-    default_account_frozen: bool,
-    transfer_hook_program: Option<Pubkey>,
-    config_pda: Pubkey,
-    role_pda: Pubkey,
-    extra_metas: Option<Pubkey>,
-}
+    #[test]
+    fn parses_and_encodes_quota_windows() {
+        assert_eq!(parse_quota_window("3600").unwrap(), 3600);
+        assert_eq!(parse_quota_window("1h").unwrap(), 3600);
+        assert_eq!(parse_quota_window("7d").unwrap(), 604_800);
+        assert!(parse_quota_window("59").is_err());
+        assert!(parse_quota_window("30s").is_err());
+        assert!(parse_quota_window("-60").is_err());
+        assert!(parse_quota_window("soon").is_err());
+
+        let authority = Pubkey::new_unique();
+        let config_pda = Pubkey::new_unique();
+        let instruction = build_update_quota_window_instruction(UpdateQuotaWindowParams {
+            authority,
+            config_pda,
+            quota_window_seconds: 3600,
+        })
+        .unwrap();
+        assert_eq!(&instruction.data[8..], &3600i64.to_le_bytes());
+        assert_eq!(instruction.accounts[1].pubkey, config_pda);
+        assert_eq!(
+            instruction.accounts[2].pubkey,
+            find_role_pda(&config_pda, &authority, &stablecoin_core::ID).0
+        );
+    }
 
-fn build_initialize_instruction(params: InitializeParams) -> Result<Instruction> {
-    let mut accounts = vec![
-        AccountMeta::new(params.authority, true),
-        AccountMeta::new(params.mint, true),
-        AccountMeta::new(params.config_pda, false),
-        AccountMeta::new(params.role_pda, false),
-    ];
+    /// Checks a CLI-built instruction against the program's own Anchor client
+    /// types: same discriminator and borsh args, same account order and signers,
+    /// and every account the program mutates passed as writable.
+    fn assert_matches_program(
+        instruction: &Instruction,
+        accounts: impl anchor_lang::ToAccountMetas,
+        data: impl anchor_lang::InstructionData,
+    ) {
+        assert_eq!(instruction.program_id, stablecoin_core::ID);
+        assert_eq!(instruction.data, data.data(), "instruction data");
+        let expected = accounts.to_account_metas(None);
+        let actual = &instruction.accounts[..expected.len().min(instruction.accounts.len())];
+        assert_eq!(actual.len(), expected.len(), "account count");
+        for (index, (actual, expected)) in actual.iter().zip(&expected).enumerate() {
+            assert_eq!(actual.pubkey, expected.pubkey, "account {}", index);
+            assert_eq!(actual.is_signer, expected.is_signer, "signer {}", index);
+            assert!(
+                actual.is_writable || !expected.is_writable,
+                "account {} must be writable",
+                index
+            );
+        }
+    }
 
-    if params.enable_transfer_hook {
-        let extra_metas = params
-            .extra_metas
-            .ok_or_else(|| anyhow!("Missing extra account metas"))?;
-        let hook_program = params
-            .transfer_hook_program
-            .ok_or_else(|| anyhow!("Missing transfer hook program"))?;
-        accounts.push(AccountMeta::new(extra_metas, false));
-        accounts.push(AccountMeta::new_readonly(hook_program, false));
+    #[test]
+    fn initialize_matches_the_program_interface() {
+        use stablecoin_core::instructions::initialize::InitializeArgs;
+
+        let authority = Pubkey::new_unique();
+        let mint = Pubkey::new_unique();
+        let config = find_config_pda(&mint, &stablecoin_core::ID).0;
+        let role = find_role_pda(&config, &authority, &stablecoin_core::ID).0;
+        let extra_metas = find_extra_account_metas_pda(&mint, &transfer_hook::ID).0;
+        let treasury = Pubkey::new_unique();
+        for enable_transfer_hook in [false, true] {
+            let instruction = build_initialize_instruction(InitializeParams {
+                authority,
+                mint,
+                name: "Test USD".to_string(),
+                symbol: "TUSD".to_string(),
+                uri: "https://example.com".to_string(),
+                decimals: 6,
+                enable_permanent_delegate: enable_transfer_hook,
+                enable_transfer_hook,
+                default_account_frozen: false,
+                transfer_hook_program: Some(transfer_hook::ID),
+                pause_cooldown: 30,
+                enable_metadata: true,
+                max_supply: Some(1_000),
+                enable_allowlist: enable_transfer_hook,
+                enable_confidential: !enable_transfer_hook,
+                treasury: enable_transfer_hook.then_some(treasury),
+                token_program: TokenProgramKind::Token2022,
+                transfer_cooldown_seconds: enable_transfer_hook.then_some(60),
+                reserve_transfer_hook: !enable_transfer_hook,
+                config_pda: config,
+                role_pda: role,
+                extra_metas: Some(extra_metas),
+            })
+            .unwrap();
+            assert_matches_program(
+                &instruction,
+                stablecoin_core::accounts::Initialize {
+                    authority,
+                    mint,
+                    config,
+                    role_account: role,
+                    extra_metas_account: enable_transfer_hook.then_some(extra_metas),
+                    transfer_hook_program: enable_transfer_hook.then_some(transfer_hook::ID),
+                    token_program: spl_token_2022::id(),
+                    system_program: system_program::id(),
+                    rent: sysvar::rent::id(),
+                },
+                stablecoin_core::instruction::Initialize {
+                    args: InitializeArgs {
+                        name: "Test USD".to_string(),
+                        symbol: "TUSD".to_string(),
+                        uri: "https://example.com".to_string(),
+                        decimals: 6,
+                        enable_permanent_delegate: enable_transfer_hook,
+                        enable_transfer_hook,
+                        default_account_frozen: false,
+                        transfer_hook_program: enable_transfer_hook.then_some(transfer_hook::ID),
+                        pause_cooldown: 30,
+                        enable_metadata: true,
+                        max_supply: Some(1_000),
+                        enable_allowlist: enable_transfer_hook,
+                        enable_confidential: !enable_transfer_hook,
+                        treasury: enable_transfer_hook.then_some(treasury),
+                        token_program: TokenProgram::Token2022,
+                        transfer_cooldown_seconds: enable_transfer_hook.then_some(60),
+                        reserve_transfer_hook: !enable_transfer_hook,
+                    },
+                },
+            );
+        }
+        assert_eq!(preset_label(false, false), "SSS-1");
+        assert_eq!(preset_label(true, false), "SSS-2");
+        assert_eq!(preset_label(false, true), "SSS-3");
     }
 
-    accounts.push(AccountMeta::new_readonly(spl_token_2022::id(), false));
-    accounts.push(AccountMeta::new_readonly(system_program::id(), false));
-    accounts.push(AccountMeta::new_readonly(sysvar::rent::id(), false));
+    #[test]
+    fn mint_request_instructions_match_the_program_interface() {
+        use stablecoin_core::instructions::mint_request::{
+            ProposeMintArgs, UpdateMintApprovalArgs,
+        };
 
-    let data = InitializeArgs {
-        name: params.name,
-        symbol: params.symbol,
-        uri: params.uri,
-        decimals: params.decimals,
-        enable_permanent_delegate: params.enable_permanent_delegate,
-        enable_transfer_hook: params.enable_transfer_hook,
-        default_account_frozen: params.default_account_frozen,
-        transfer_hook_program: if params.enable_transfer_hook {
-            params.transfer_hook_program
-        } else {
-            None
-        },
-    }
-    .try_to_vec()?;
+        let requester = Pubkey::new_unique();
+        let approver = Pubkey::new_unique();
+        let mint = Pubkey::new_unique();
+        let recipient = Pubkey::new_unique();
+        let config = find_config_pda(&mint, &stablecoin_core::ID).0;
+        let mint_request = find_mint_request_pda(&config, 7, &stablecoin_core::ID).0;
+        let token_program = TokenProgram::Token2022.id();
+
+        let instruction = build_propose_mint_instruction(ProposeMintParams {
+            requester,
+            config_pda: config,
+            id: 7,
+            recipient,
+            amount: 42,
+        })
+        .unwrap();
+        assert_matches_program(
+            &instruction,
+            stablecoin_core::accounts::ProposeMint {
+                requester,
+                config,
+                role_account: find_role_pda(&config, &requester, &stablecoin_core::ID).0,
+                mint_request,
+                system_program: system_program::id(),
+            },
+            stablecoin_core::instruction::ProposeMint {
+                args: ProposeMintArgs {
+                    id: 7,
+                    recipient,
+                    amount: 42,
+                },
+            },
+        );
 
-    Ok(build_instruction(
-        "initialize",
-        data,
-        accounts,
-        stablecoin_core::ID,
-    ))
-}
+        let instruction = build_approve_mint_instruction(ApproveMintParams {
+            approver,
+            mint,
+            id: 7,
+            requester,
+            recipient,
+            token_program,
+            check_blacklist: true,
+        })
+        .unwrap();
+        assert_matches_program(
+            &instruction,
+            stablecoin_core::accounts::ApproveMint {
+                approver,
+                config,
+                approver_role_account: find_role_pda(&config, &approver, &stablecoin_core::ID).0,
+                requester_role_account: find_role_pda(&config, &requester, &stablecoin_core::ID).0,
+                mint_request,
+                requester,
+                mint,
+                recipient,
+                recipient_ata: get_associated_token_address_with_program_id(
+                    &recipient,
+                    &mint,
+                    &token_program,
+                ),
+                token_program,
+                associated_token_program: spl_associated_token_account::id(),
+                system_program: system_program::id(),
+                recipient_blacklist_entry: Some(
+                    find_blacklist_pda(&config, &recipient, &stablecoin_core::ID).0,
+                ),
+            },
+            stablecoin_core::instruction::ApproveMint {},
+        );
 
-struct MintParams {
-    minter: Pubkey,
-    mint: Pubkey,
-    recipient: Pubkey,
-    recipient_ata: Pubkey,
-    amount: u64,
-}
+        for (authority, role_account) in [
+            (requester, None),
+            (
+                approver,
+                Some(find_role_pda(&config, &approver, &stablecoin_core::ID).0),
+            ),
+        ] {
+            let instruction = build_cancel_mint_instruction(CancelMintParams {
+                authority,
+                config_pda: config,
+                id: 7,
+                requester,
+            })
+            .unwrap();
+            assert_matches_program(
+                &instruction,
+                stablecoin_core::accounts::CancelMint {
+                    authority,
+                    config,
+                    role_account,
+                    mint_request,
+                    requester,
+                },
+                stablecoin_core::instruction::CancelMint {},
+            );
+        }
 
-fn build_mint_instruction(params: MintParams) -> Result<Instruction> {
-    let config_pda = find_config_pda(&params.mint, &stablecoin_core::ID).0;
-    let role_pda = find_role_pda(&config_pda, &params.minter, &stablecoin_core::ID).0;
-    let accounts = vec![
-        AccountMeta::new(params.minter, true),
-        AccountMeta::new(config_pda, false),
-        AccountMeta::new(role_pda, false),
-        AccountMeta::new_readonly(params.mint, false),
-        AccountMeta::new_readonly(params.recipient, false),
-        AccountMeta::new(params.recipient_ata, false),
-        AccountMeta::new_readonly(spl_token_2022::id(), false),
-        AccountMeta::new_readonly(spl_associated_token_account::id(), false),
-        AccountMeta::new_readonly(system_program::id(), false),
-    ];
-    let data = MintBurnArgs {
-        amount: params.amount,
+        let authority = Pubkey::new_unique();
+        for require_mint_approval in [true, false] {
+            let instruction = build_update_mint_approval_instruction(UpdateMintApprovalParams {
+                authority,
+                config_pda: config,
+                require_mint_approval,
+            })
+            .unwrap();
+            assert_matches_program(
+                &instruction,
+                stablecoin_core::accounts::UpdateMintApproval {
+                    authority,
+                    config,
+                    role_account: find_role_pda(&config, &authority, &stablecoin_core::ID).0,
+                },
+                stablecoin_core::instruction::UpdateMintApproval {
+                    args: UpdateMintApprovalArgs {
+                        require_mint_approval,
+                    },
+                },
+            );
+        }
     }
-    .try_to_vec()?;
-    Ok(build_instruction(
-        "mint",
-        data,
-        accounts,
-        stablecoin_core::ID,
-    ))
-}
 
-struct BurnParams {
-    burner: Pubkey,
-    mint: Pubkey,
-    burner_ata: Pubkey,
-    amount: u64,
-}
+    #[test]
+    fn mint_takes_propose_and_approve_subcommands() {
+        let recipient = Pubkey::new_unique().to_string();
+        let cli = Cli::try_parse_from(["sss-token", "mint", &recipient, "5"]).unwrap();
+        let Commands::Mint(args) = cli.command else {
+            panic!("expected mint");
+        };
+        assert!(args.request.is_none());
+        assert_eq!(args.recipient.as_deref(), Some(recipient.as_str()));
+
+        let cli =
+            Cli::try_parse_from(["sss-token", "mint", "propose", &recipient, "5", "--id", "9"])
+                .unwrap();
+        let Commands::Mint(MintArgs {
+            request: Some(MintRequestCmd::Propose(args)),
+            ..
+        }) = cli.command
+        else {
+            panic!("expected mint propose");
+        };
+        assert_eq!(args.id, Some(9));
+        assert_eq!(args.amount, "5");
+
+        let cli = Cli::try_parse_from(["sss-token", "mint", "approve", "9"]).unwrap();
+        assert!(matches!(
+            cli.command,
+            Commands::Mint(MintArgs {
+                request: Some(MintRequestCmd::Approve(MintApproveArgs { id: 9, .. })),
+                ..
+            })
+        ));
+        let cli = Cli::try_parse_from(["sss-token", "mint", "cancel", "9"]).unwrap();
+        assert!(matches!(
+            cli.command,
+            Commands::Mint(MintArgs {
+                request: Some(MintRequestCmd::Cancel(MintCancelArgs { id: 9, .. })),
+                ..
+            })
+        ));
+        assert!(Cli::try_parse_from(["sss-token", "mint", &recipient, "5", "approve"]).is_err());
 
-fn build_burn_instruction(params: BurnParams) -> Result<Instruction> {
-    let config_pda = find_config_pda(&params.mint, &stablecoin_core::ID).0;
-    let role_pda = find_role_pda(&config_pda, &params.burner, &stablecoin_core::ID).0;
-    let accounts = vec![
-        AccountMeta::new(params.burner, true),
-        AccountMeta::new(config_pda, false),
-        AccountMeta::new(role_pda, false),
-        AccountMeta::new(params.mint, false),
-        AccountMeta::new(params.burner_ata, false),
-        AccountMeta::new_readonly(spl_token_2022::id(), false),
-    ];
-    let data = MintBurnArgs {
-        amount: params.amount,
+        let mint = Pubkey::new_unique();
+        assert!(check_direct_mint(&sample_config(mint)).is_ok());
+        let approval_only = StablecoinConfig {
+            require_mint_approval: true,
+            ..sample_config(mint)
+        };
+        assert!(check_direct_mint(&approval_only).is_err());
     }
-    .try_to_vec()?;
-    Ok(build_instruction(
-        "burn",
-        data,
-        accounts,
-        stablecoin_core::ID,
-    ))
-}
 
-struct FreezeParams {
-    freezer: Pubkey,
-    mint: Pubkey,
-    target_ata: Pubkey,
-}
+    #[test]
+    fn supply_instructions_match_the_program_interface() {
+        use stablecoin_core::instructions::mint_batch::MintBatchEntry;
+        use stablecoin_core::instructions::supply::UpdateMaxSupplyArgs;
+
+        let minter = Pubkey::new_unique();
+        let mint = Pubkey::new_unique();
+        let recipient = Pubkey::new_unique();
+        let config = find_config_pda(&mint, &stablecoin_core::ID).0;
+        let role = find_role_pda(&config, &minter, &stablecoin_core::ID).0;
+        // Legacy mints take the same accounts with the SPL Token program.
+        let token_program = TokenProgram::Legacy.id();
+        let recipient_ata =
+            get_associated_token_address_with_program_id(&recipient, &mint, &token_program);
+
+        let instruction = build_mint_instruction(MintParams {
+            minter,
+            mint,
+            recipient,
+            recipient_ata,
+            amount: 42,
+            token_program,
+            check_blacklist: false,
+        })
+        .unwrap();
+        assert_matches_program(
+            &instruction,
+            stablecoin_core::accounts::MintTokens {
+                minter,
+                config,
+                role_account: role,
+                mint,
+                recipient,
+                recipient_ata,
+                token_program,
+                associated_token_program: spl_associated_token_account::id(),
+                system_program: system_program::id(),
+                recipient_blacklist_entry: None,
+            },
+            stablecoin_core::instruction::Mint { amount: 42 },
+        );
+        let instruction = build_mint_instruction(MintParams {
+            minter,
+            mint,
+            recipient,
+            recipient_ata,
+            amount: 42,
+            token_program,
+            check_blacklist: true,
+        })
+        .unwrap();
+        assert_matches_program(
+            &instruction,
+            stablecoin_core::accounts::MintTokens {
+                minter,
+                config,
+                role_account: role,
+                mint,
+                recipient,
+                recipient_ata,
+                token_program,
+                associated_token_program: spl_associated_token_account::id(),
+                system_program: system_program::id(),
+                recipient_blacklist_entry: Some(
+                    find_blacklist_pda(&config, &recipient, &stablecoin_core::ID).0,
+                ),
+            },
+            stablecoin_core::instruction::Mint { amount: 42 },
+        );
 
-fn build_freeze_instruction(params: FreezeParams) -> Result<Instruction> {
-    let config_pda = find_config_pda(&params.mint, &stablecoin_core::ID).0;
-    let role_pda = find_role_pda(&config_pda, &params.freezer, &stablecoin_core::ID).0;
-    let accounts = vec![
-        AccountMeta::new(params.freezer, true),
-        AccountMeta::new(config_pda, false),
-        AccountMeta::new(role_pda, false),
-        AccountMeta::new_readonly(params.mint, false),
-        AccountMeta::new(params.target_ata, false),
-        AccountMeta::new_readonly(spl_token_2022::id(), false),
-    ];
-    Ok(build_instruction(
-        "freeze_account",
-        Vec::new(),
-        accounts,
-        stablecoin_core::ID,
-    ))
-}
+        let instruction = build_mint_batch_instruction(MintBatchParams {
+            minter,
+            mint,
+            entries: &[(recipient, 7)],
+            token_program,
+            check_blacklist: false,
+        })
+        .unwrap();
+        assert_matches_program(
+            &instruction,
+            stablecoin_core::accounts::MintBatch {
+                minter,
+                config,
+                role_account: role,
+                mint,
+                token_program,
+            },
+            stablecoin_core::instruction::MintBatch {
+                entries: vec![MintBatchEntry {
+                    recipient,
+                    amount: 7,
+                }],
+            },
+        );
+        assert_eq!(instruction.accounts.len(), 6);
+        assert_eq!(instruction.accounts[5].pubkey, recipient_ata);
+        assert!(instruction.accounts[5].is_writable);
+        let instruction = build_mint_batch_instruction(MintBatchParams {
+            minter,
+            mint,
+            entries: &[(recipient, 7)],
+            token_program,
+            check_blacklist: true,
+        })
+        .unwrap();
+        assert_eq!(instruction.accounts.len(), 7);
+        assert_eq!(
+            instruction.accounts[6].pubkey,
+            find_blacklist_pda(&config, &recipient, &stablecoin_core::ID).0
+        );
+        assert!(!instruction.accounts[6].is_writable);
+
+        let burner_ata =
+            get_associated_token_address_with_program_id(&minter, &mint, &token_program);
+        let instruction = build_burn_instruction(BurnParams {
+            burner: minter,
+            mint,
+            burner_ata,
+            amount: 3,
+            token_program,
+        })
+        .unwrap();
+        assert_matches_program(
+            &instruction,
+            stablecoin_core::accounts::Burn {
+                burner: minter,
+                config,
+                role_account: role,
+                mint,
+                burner_ata,
+                token_program,
+            },
+            stablecoin_core::instruction::Burn { amount: 3 },
+        );
 
-fn build_thaw_instruction(params: FreezeParams) -> Result<Instruction> {
-    let config_pda = find_config_pda(&params.mint, &stablecoin_core::ID).0;
-    let role_pda = find_role_pda(&config_pda, &params.freezer, &stablecoin_core::ID).0;
-    let accounts = vec![
-        AccountMeta::new(params.freezer, true),
-        AccountMeta::new(config_pda, false),
-        AccountMeta::new(role_pda, false),
-        AccountMeta::new_readonly(params.mint, false),
-        AccountMeta::new(params.target_ata, false),
-        AccountMeta::new_readonly(spl_token_2022::id(), false),
-    ];
-    Ok(build_instruction(
-        "thaw_account",
-        Vec::new(),
-        accounts,
-        stablecoin_core::ID,
-    ))
-}
+        let instruction = build_update_max_supply_instruction(UpdateMaxSupplyParams {
+            authority: minter,
+            config_pda: config,
+            mint,
+            max_supply: 500,
+        })
+        .unwrap();
+        assert_matches_program(
+            &instruction,
+            stablecoin_core::accounts::UpdateMaxSupply {
+                authority: minter,
+                config,
+                role_account: role,
+                mint,
+            },
+            stablecoin_core::instruction::UpdateMaxSupply {
+                args: UpdateMaxSupplyArgs { max_supply: 500 },
+            },
+        );
+    }
 
-struct PauseParams {
-    pauser: Pubkey,
-    config_pda: Pubkey,
-    unpause: bool,
-}
+    #[test]
+    fn admin_instructions_match_the_program_interface() {
+        use stablecoin_core::instructions::default_state::UpdateDefaultAccountStateArgs;
+        use stablecoin_core::instructions::metadata::{
+            UpdateMetadataArgs, UpdateMetadataFieldArgs,
+        };
+        use stablecoin_core::instructions::pause::{PauseArgs, UpdateAdminLockArgs};
+        use stablecoin_core::instructions::quota::UpdateQuotaWindowArgs;
+        use stablecoin_core::instructions::roles::UpdateRolesArgs;
+        use stablecoin_core::instructions::self_transfer::UpdateSelfTransferArgs;
+        use stablecoin_core::instructions::supply::FreezeSupplyArgs;
+        use stablecoin_core::instructions::transfer_limit::UpdateTransferLimitArgs;
+        use stablecoin_core::instructions::treasury::UpdateTreasuryArgs;
+
+        let authority = Pubkey::new_unique();
+        let target = Pubkey::new_unique();
+        let mint = Pubkey::new_unique();
+        let config = find_config_pda(&mint, &stablecoin_core::ID).0;
+        let role = find_role_pda(&config, &authority, &stablecoin_core::ID).0;
+
+        for (unpause, mask) in [(false, None), (true, Some(PAUSE_TRANSFER))] {
+            let instruction = build_pause_instruction(PauseParams {
+                pauser: authority,
+                config_pda: config,
+                unpause,
+                mask,
+            })
+            .unwrap();
+            let args = PauseArgs { mask };
+            if unpause {
+                assert_matches_program(
+                    &instruction,
+                    stablecoin_core::accounts::Unpause {
+                        pauser: authority,
+                        config,
+                        role_account: role,
+                    },
+                    stablecoin_core::instruction::Unpause { args },
+                );
+            } else {
+                assert_matches_program(
+                    &instruction,
+                    stablecoin_core::accounts::Pause {
+                        pauser: authority,
+                        config,
+                        role_account: role,
+                    },
+                    stablecoin_core::instruction::Pause { args },
+                );
+            }
+        }
 
-fn build_pause_instruction(params: PauseParams) -> Result<Instruction> {
-    let role_pda = find_role_pda(&params.config_pda, &params.pauser, &stablecoin_core::ID).0;
-    let accounts = vec![
-        AccountMeta::new(params.pauser, true),
-        AccountMeta::new(params.config_pda, false),
-        AccountMeta::new(role_pda, false),
-    ];
-    let name = if params.unpause { "unpause" } else { "pause" };
-    Ok(build_instruction(
-        name,
-        Vec::new(),
-        accounts,
-        stablecoin_core::ID,
-    ))
-}
+        let instruction = build_update_roles_instruction(UpdateRolesParams {
+            authority,
+            config_pda: config,
+            target,
+            roles: ROLE_MINTER,
+            mint_quota: Some(10),
+            lifetime_quota: Some(1_000),
+        })
+        .unwrap();
+        assert_matches_program(
+            &instruction,
+            stablecoin_core::accounts::UpdateRoles {
+                authority,
+                config,
+                role_account: role,
+                target_role_account: find_role_pda(&config, &target, &stablecoin_core::ID).0,
+                target,
+                system_program: system_program::id(),
+            },
+            stablecoin_core::instruction::UpdateRoles {
+                args: UpdateRolesArgs {
+                    target,
+                    roles: ROLE_MINTER,
+                    mint_quota: Some(10),
+                    lifetime_quota: Some(1_000),
+                },
+            },
+        );
 
-struct UpdateRolesParams {
-    authority: Pubkey,
-    config_pda: Pubkey,
-    target: Pubkey,
-    roles: u8,
-    mint_quota: Option<u64>,
-}
+        let instruction = build_update_metadata_instruction(UpdateMetadataParams {
+            authority,
+            config_pda: config,
+            mint,
+            name: None,
+            symbol: Some("NEW".to_string()),
+            uri: None,
+        })
+        .unwrap();
+        assert_matches_program(
+            &instruction,
+            stablecoin_core::accounts::UpdateMetadata {
+                authority,
+                config,
+                role_account: role,
+                mint,
+                token_2022_program: spl_token_2022::id(),
+                system_program: system_program::id(),
+            },
+            stablecoin_core::instruction::UpdateMetadata {
+                args: UpdateMetadataArgs {
+                    name: None,
+                    symbol: Some("NEW".to_string()),
+                    uri: None,
+                },
+            },
+        );
 
-fn build_update_roles_instruction(params: UpdateRolesParams) -> Result<Instruction> {
-    let role_pda = find_role_pda(&params.config_pda, &params.authority, &stablecoin_core::ID).0;
-    let target_role_pda = find_role_pda(&params.config_pda, &params.target, &stablecoin_core::ID).0;
-    let accounts = vec![
-        AccountMeta::new(params.authority, true),
-        AccountMeta::new(params.config_pda, false),
-        AccountMeta::new(role_pda, false),
-        AccountMeta::new(target_role_pda, false),
-        AccountMeta::new_readonly(params.target, false),
-        AccountMeta::new_readonly(system_program::id(), false),
-    ];
-    let data = UpdateRolesArgs {
-        target: params.target,
-        roles: params.roles,
-        mint_quota: params.mint_quota,
-    }
-    .try_to_vec()?;
-    Ok(build_instruction(
-        "update_roles",
-        data,
-        accounts,
-        stablecoin_core::ID,
-    ))
-}
+        let instruction = build_update_metadata_field_instruction(UpdateMetadataFieldParams {
+            authority,
+            config_pda: config,
+            mint,
+            key: "attestation".to_string(),
+            value: "https://example.com/attestation.pdf".to_string(),
+        })
+        .unwrap();
+        assert_matches_program(
+            &instruction,
+            stablecoin_core::accounts::UpdateMetadataField {
+                authority,
+                config,
+                role_account: role,
+                mint,
+                token_2022_program: spl_token_2022::id(),
+                system_program: system_program::id(),
+            },
+            stablecoin_core::instruction::UpdateMetadataField {
+                args: UpdateMetadataFieldArgs {
+                    key: "attestation".to_string(),
+                    value: "https://example.com/attestation.pdf".to_string(),
+                },
+            },
+        );
 
-struct AddToBlacklistParams {
-    blacklister: Pubkey,
-    config_pda: Pubkey,
-    wallet: Pubkey,
-    reason: String,
-}
+        let instruction = build_update_quota_window_instruction(UpdateQuotaWindowParams {
+            authority,
+            config_pda: config,
+            quota_window_seconds: 3_600,
+        })
+        .unwrap();
+        assert_matches_program(
+            &instruction,
+            stablecoin_core::accounts::UpdateQuotaWindow {
+                authority,
+                config,
+                role_account: role,
+            },
+            stablecoin_core::instruction::UpdateQuotaWindow {
+                args: UpdateQuotaWindowArgs {
+                    quota_window_seconds: 3_600,
+                },
+            },
+        );
 
-fn build_add_to_blacklist_instruction(params: AddToBlacklistParams) -> Result<Instruction> {
-    let role_pda = find_role_pda(
-        &params.config_pda,
-        &params.blacklister,
-        &stablecoin_core::ID,
-    )
-    .0;
-    let blacklist_pda =
-        find_blacklist_pda(&params.config_pda, &params.wallet, &stablecoin_core::ID).0;
-    let accounts = vec![
-        AccountMeta::new(params.blacklister, true),
-        AccountMeta::new(params.config_pda, false),
-        AccountMeta::new(role_pda, false),
-        AccountMeta::new(blacklist_pda, false),
-        AccountMeta::new_readonly(params.wallet, false),
-        AccountMeta::new_readonly(system_program::id(), false),
-    ];
-    let data = AddToBlacklistArgs {
-        wallet: params.wallet,
-        reason: params.reason,
+        for max_transfer_amount in [Some(10_000), None] {
+            let instruction = build_update_transfer_limit_instruction(UpdateTransferLimitParams {
+                authority,
+                config_pda: config,
+                max_transfer_amount,
+            })
+            .unwrap();
+            assert_matches_program(
+                &instruction,
+                stablecoin_core::accounts::UpdateTransferLimit {
+                    authority,
+                    config,
+                    role_account: role,
+                },
+                stablecoin_core::instruction::UpdateTransferLimit {
+                    args: UpdateTransferLimitArgs {
+                        max_transfer_amount,
+                    },
+                },
+            );
+        }
+
+        for allow_self_transfer in [true, false] {
+            let instruction = build_update_self_transfer_instruction(UpdateSelfTransferParams {
+                authority,
+                config_pda: config,
+                allow_self_transfer,
+            })
+            .unwrap();
+            assert_matches_program(
+                &instruction,
+                stablecoin_core::accounts::UpdateSelfTransfer {
+                    authority,
+                    config,
+                    role_account: role,
+                },
+                stablecoin_core::instruction::UpdateSelfTransfer {
+                    args: UpdateSelfTransferArgs {
+                        allow_self_transfer,
+                    },
+                },
+            );
+        }
+        for null_mint_authority in [true, false] {
+            let instruction = build_freeze_supply_instruction(FreezeSupplyParams {
+                authority,
+                config_pda: config,
+                mint,
+                null_mint_authority,
+                token_program: spl_token_2022::id(),
+            })
+            .unwrap();
+            assert_matches_program(
+                &instruction,
+                stablecoin_core::accounts::FreezeSupply {
+                    authority,
+                    config,
+                    role_account: role,
+                    mint,
+                    token_program: spl_token_2022::id(),
+                },
+                stablecoin_core::instruction::FreezeSupply {
+                    args: FreezeSupplyArgs {
+                        null_mint_authority,
+                    },
+                },
+            );
+        }
+        for lock_admin_ops in [true, false] {
+            let instruction = build_update_admin_lock_instruction(UpdateAdminLockParams {
+                authority,
+                config_pda: config,
+                lock_admin_ops,
+            })
+            .unwrap();
+            assert_matches_program(
+                &instruction,
+                stablecoin_core::accounts::UpdateAdminLock {
+                    authority,
+                    config,
+                    role_account: role,
+                },
+                stablecoin_core::instruction::UpdateAdminLock {
+                    args: UpdateAdminLockArgs { lock_admin_ops },
+                },
+            );
+        }
+        for treasury in [Some(Pubkey::new_unique()), None] {
+            let instruction = build_update_treasury_instruction(UpdateTreasuryParams {
+                authority,
+                config_pda: config,
+                treasury,
+            })
+            .unwrap();
+            assert_matches_program(
+                &instruction,
+                stablecoin_core::accounts::UpdateTreasury {
+                    authority,
+                    config,
+                    role_account: role,
+                },
+                stablecoin_core::instruction::UpdateTreasury {
+                    args: UpdateTreasuryArgs { treasury },
+                },
+            );
+        }
+        let instruction = build_migrate_config_instruction(MigrateConfigParams {
+            payer: authority,
+            config_pda: config,
+        })
+        .unwrap();
+        assert_matches_program(
+            &instruction,
+            stablecoin_core::accounts::MigrateConfig {
+                payer: authority,
+                config,
+                system_program: system_program::ID,
+            },
+            stablecoin_core::instruction::MigrateConfig {},
+        );
+        for default_frozen in [true, false] {
+            let instruction =
+                build_update_default_account_state_instruction(UpdateDefaultAccountStateParams {
+                    authority,
+                    config_pda: config,
+                    mint,
+                    default_frozen,
+                })
+                .unwrap();
+            assert_matches_program(
+                &instruction,
+                stablecoin_core::accounts::UpdateDefaultAccountState {
+                    authority,
+                    config,
+                    role_account: role,
+                    mint,
+                    token_2022_program: spl_token_2022::id(),
+                },
+                stablecoin_core::instruction::UpdateDefaultAccountState {
+                    args: UpdateDefaultAccountStateArgs { default_frozen },
+                },
+            );
+        }
+        let mint = Pubkey::new_unique();
+        let new_transfer_hook_program = Pubkey::new_unique();
+        let instruction =
+            build_update_transfer_hook_program_instruction(UpdateTransferHookProgramParams {
+                authority,
+                config_pda: config,
+                mint,
+                hook_program: new_transfer_hook_program,
+            })
+            .unwrap();
+        assert_matches_program(
+            &instruction,
+            stablecoin_core::accounts::UpdateTransferHookProgram {
+                authority,
+                config,
+                role_account: role,
+                mint,
+                new_transfer_hook_program,
+                extra_metas_account: find_extra_account_metas_pda(
+                    &mint,
+                    &new_transfer_hook_program,
+                )
+                .0,
+                token_2022_program: spl_token_2022::id(),
+                system_program: system_program::id(),
+            },
+            stablecoin_core::instruction::UpdateTransferHookProgram {},
+        );
+        let extra_metas = find_extra_account_metas_pda(&mint, &transfer_hook::ID).0;
+        let instruction = build_enable_transfer_hook_instruction(EnableTransferHookParams {
+            authority,
+            config_pda: config,
+            mint,
+            hook_program: transfer_hook::ID,
+        })
+        .unwrap();
+        assert_matches_program(
+            &instruction,
+            stablecoin_core::accounts::EnableTransferHook {
+                authority,
+                config,
+                role_account: role,
+                mint,
+                transfer_hook_program: transfer_hook::ID,
+                extra_metas_account: extra_metas,
+                token_2022_program: spl_token_2022::id(),
+                system_program: system_program::id(),
+            },
+            stablecoin_core::instruction::EnableTransferHook {},
+        );
+        // Standalone list creation targets the same PDA enable_transfer_hook
+        // checks, paid by the signer.
+        let blacklist = build_init_extra_metas_instruction(InitExtraMetasParams {
+            payer: authority,
+            mint,
+            hook_program: transfer_hook::ID,
+            allowlist_mode: false,
+            transfer_cooldown: false,
+        })
+        .unwrap();
+        assert_eq!(blacklist.program_id, transfer_hook::ID);
+        assert_eq!(blacklist.accounts[0].pubkey, extra_metas);
+        assert!(blacklist.accounts[0].is_writable);
+        assert_eq!(blacklist.accounts[1].pubkey, mint);
+        assert!(blacklist.accounts[2].pubkey == authority && blacklist.accounts[2].is_signer);
+        let allowlist = build_init_extra_metas_instruction(InitExtraMetasParams {
+            payer: authority,
+            mint,
+            hook_program: transfer_hook::ID,
+            allowlist_mode: true,
+            transfer_cooldown: false,
+        })
+        .unwrap();
+        assert_ne!(blacklist.data, allowlist.data);
+        let cooldown = build_init_extra_metas_instruction(InitExtraMetasParams {
+            payer: authority,
+            mint,
+            hook_program: transfer_hook::ID,
+            allowlist_mode: false,
+            transfer_cooldown: true,
+        })
+        .unwrap();
+        assert!(cooldown.data.len() > blacklist.data.len());
+
+        let owner = Pubkey::new_unique();
+        let init_last_transfer = build_init_last_transfer_instruction(InitLastTransferParams {
+            payer: authority,
+            config_pda: config,
+            owner,
+            hook_program: transfer_hook::ID,
+        });
+        assert_eq!(init_last_transfer.program_id, transfer_hook::ID);
+        assert_eq!(
+            init_last_transfer.data,
+            transfer_hook::layout::INITIALIZE_LAST_TRANSFER_DISCRIMINATOR
+        );
+        let layout = transfer_hook::layout::INITIALIZE_LAST_TRANSFER_ACCOUNTS;
+        assert_eq!(init_last_transfer.accounts.len(), layout.len());
+        for (meta, account) in init_last_transfer.accounts.iter().zip(layout) {
+            assert_eq!(
+                (meta.is_writable, meta.is_signer),
+                (account.writable, account.signer),
+                "{}",
+                account.name
+            );
+        }
+        assert_eq!(
+            init_last_transfer.accounts[3].pubkey,
+            find_last_transfer_pda(&config, &owner, &transfer_hook::ID).0
+        );
+
+        assert_eq!(parse_transfer_limit("none", 6).unwrap(), None);
+        assert_eq!(parse_transfer_limit("2.5", 6).unwrap(), Some(2_500_000));
+        assert!(parse_transfer_limit("0", 6).is_err());
     }
-    .try_to_vec()?;
-    Ok(build_instruction(
-        "add_to_blacklist",
-        data,
-        accounts,
-        stablecoin_core::ID,
-    ))
-}
 
-struct RemoveFromBlacklistParams {
-    blacklister: Pubkey,
-    config_pda: Pubkey,
-    blacklist_entry: Pubkey,
-}
+    #[test]
+    fn compliance_instructions_match_the_program_interface() {
+        use stablecoin_core::instructions::allowlist::AddToAllowlistArgs;
+        use stablecoin_core::instructions::blacklist::{
+            AddToBlacklistArgs, UpdateBlacklistEntryArgs,
+        };
 
-fn build_remove_from_blacklist_instruction(
-    params: RemoveFromBlacklistParams,
-) -> Result<Instruction> {
-    let role_pda = find_role_pda(
-        &params.config_pda,
-        &params.blacklister,
-        &stablecoin_core::ID,
-    )
-    .0;
-    let accounts = vec![
-        AccountMeta::new(params.blacklister, true),
-        AccountMeta::new(params.config_pda, false),
-        AccountMeta::new(role_pda, false),
-        AccountMeta::new(params.blacklist_entry, false),
-    ];
-    Ok(build_instruction(
-        "remove_from_blacklist",
-        Vec::new(),
-        accounts,
-        stablecoin_core::ID,
-    ))
-}
+        let officer = Pubkey::new_unique();
+        let wallet = Pubkey::new_unique();
+        let treasury_owner = Pubkey::new_unique();
+        let mint = Pubkey::new_unique();
+        let config = find_config_pda(&mint, &stablecoin_core::ID).0;
+        let role = find_role_pda(&config, &officer, &stablecoin_core::ID).0;
+        let blacklist_entry = find_blacklist_pda(&config, &wallet, &stablecoin_core::ID).0;
+        let target_ata =
+            get_associated_token_address_with_program_id(&wallet, &mint, &spl_token_2022::id());
+        let treasury_ata = get_associated_token_address_with_program_id(
+            &treasury_owner,
+            &mint,
+            &spl_token_2022::id(),
+        );
 
-struct SeizeParams {
-    seizer: Pubkey,
-    config_pda: Pubkey,
-    mint: Pubkey,
-    target_ata: Pubkey,
-    treasury_ata: Pubkey,
-    blacklist_entry: Pubkey,
-}
+        let instruction = build_add_to_blacklist_instruction(AddToBlacklistParams {
+            blacklister: officer,
+            config_pda: config,
+            wallet,
+            reason: "OFAC".to_string(),
+            expires_at: Some(1_800_000_000),
+            reason_code: BlacklistReason::Sanctions.code(),
+        })
+        .unwrap();
+        assert_matches_program(
+            &instruction,
+            stablecoin_core::accounts::AddToBlacklist {
+                blacklister: officer,
+                config,
+                role_account: role,
+                blacklist_entry,
+                wallet,
+                system_program: system_program::id(),
+            },
+            stablecoin_core::instruction::AddToBlacklist {
+                args: AddToBlacklistArgs {
+                    wallet,
+                    reason: "OFAC".to_string(),
+                    expires_at: Some(1_800_000_000),
+                    reason_code: BlacklistReason::Sanctions.code(),
+                },
+            },
+        );
 
-fn build_seize_instruction(params: SeizeParams) -> Result<Instruction> {
-    let role_pda = find_role_pda(&params.config_pda, &params.seizer, &stablecoin_core::ID).0;
-    let accounts = vec![
-        AccountMeta::new(params.seizer, true),
-        AccountMeta::new(params.config_pda, false),
-        AccountMeta::new(role_pda, false),
-        AccountMeta::new_readonly(params.mint, false),
-        AccountMeta::new(params.target_ata, false),
-        AccountMeta::new(params.treasury_ata, false),
-        AccountMeta::new_readonly(params.blacklist_entry, false),
-        AccountMeta::new_readonly(spl_token_2022::id(), false),
-    ];
-    Ok(build_instruction(
-        "seize",
-        Vec::new(),
-        accounts,
-        stablecoin_core::ID,
-    ))
-}
+        let instruction = build_remove_from_blacklist_instruction(RemoveFromBlacklistParams {
+            blacklister: officer,
+            config_pda: config,
+            blacklist_entry,
+        })
+        .unwrap();
+        assert_matches_program(
+            &instruction,
+            stablecoin_core::accounts::RemoveFromBlacklist {
+                blacklister: officer,
+                config,
+                role_account: role,
+                blacklist_entry,
+            },
+            stablecoin_core::instruction::RemoveFromBlacklist {},
+        );
 
-#[derive(Serialize)]
-struct InitOutput {
-    mint: String,
-    config: String,
-    preset: String,
-    signature: String,
-    explorer: Option<String>,
-}
+        let instruction = build_update_blacklist_entry_instruction(UpdateBlacklistEntryParams {
+            blacklister: officer,
+            config_pda: config,
+            blacklist_entry,
+            reason: "OFAC SDN update".to_string(),
+            reason_code: Some(BlacklistReason::CourtOrder.code()),
+        })
+        .unwrap();
+        assert_matches_program(
+            &instruction,
+            stablecoin_core::accounts::UpdateBlacklistEntry {
+                blacklister: officer,
+                config,
+                role_account: role,
+                blacklist_entry,
+            },
+            stablecoin_core::instruction::UpdateBlacklistEntry {
+                args: UpdateBlacklistEntryArgs {
+                    reason: "OFAC SDN update".to_string(),
+                    reason_code: Some(BlacklistReason::CourtOrder.code()),
+                },
+            },
+        );
 
-#[derive(Serialize)]
-struct MintOutput {
-    signature: String,
-    explorer: Option<String>,
-    new_supply: String,
-}
+        let instruction = build_close_blacklist_entry_instruction(CloseBlacklistEntryParams {
+            blacklister: officer,
+            config_pda: config,
+            blacklist_entry,
+        })
+        .unwrap();
+        assert_matches_program(
+            &instruction,
+            stablecoin_core::accounts::CloseBlacklistEntry {
+                blacklister: officer,
+                config,
+                role_account: role,
+                blacklist_entry,
+            },
+            stablecoin_core::instruction::CloseBlacklistEntry {},
+        );
 
-#[derive(Serialize)]
-struct BurnOutput {
-    signature: String,
-    explorer: Option<String>,
-    new_supply: String,
-}
+        let instruction = build_renounce_role_instruction(RenounceRoleParams {
+            authority: officer,
+            config_pda: config,
+            roles: ROLE_BLACKLISTER,
+        })
+        .unwrap();
+        assert_matches_program(
+            &instruction,
+            stablecoin_core::accounts::RenounceRole {
+                authority: officer,
+                config,
+                role_account: role,
+            },
+            stablecoin_core::instruction::RenounceRole {
+                roles: ROLE_BLACKLISTER,
+            },
+        );
 
-#[derive(Serialize)]
-struct SimpleOutput {
-    signature: String,
-    explorer: Option<String>,
-}
+        let allowlist_entry = find_allowlist_pda(&config, &wallet, &stablecoin_core::ID).0;
+        let instruction = build_add_to_allowlist_instruction(AddToAllowlistParams {
+            blacklister: officer,
+            config_pda: config,
+            wallet,
+        })
+        .unwrap();
+        assert_matches_program(
+            &instruction,
+            stablecoin_core::accounts::AddToAllowlist {
+                blacklister: officer,
+                config,
+                role_account: role,
+                allowlist_entry,
+                wallet,
+                system_program: system_program::id(),
+            },
+            stablecoin_core::instruction::AddToAllowlist {
+                args: AddToAllowlistArgs { wallet },
+            },
+        );
 
-#[derive(Serialize)]
-struct BlacklistStatusOutput {
-    wallet: String,
-    is_active: bool,
-    reason: Option<String>,
-}
+        let instruction = build_remove_from_allowlist_instruction(RemoveFromAllowlistParams {
+            blacklister: officer,
+            config_pda: config,
+            allowlist_entry,
+        })
+        .unwrap();
+        assert_matches_program(
+            &instruction,
+            stablecoin_core::accounts::RemoveFromAllowlist {
+                blacklister: officer,
+                config,
+                role_account: role,
+                allowlist_entry,
+            },
+            stablecoin_core::instruction::RemoveFromAllowlist {},
+        );
 
-#[derive(Serialize)]
-struct MintersOutput {
-    minters: Vec<MinterInfo>,
-}
+        let freeze = FreezeParams {
+            freezer: officer,
+            mint,
+            target_ata,
+            token_program: spl_token_2022::id(),
+        };
+        assert_matches_program(
+            &build_freeze_instruction(freeze).unwrap(),
+            stablecoin_core::accounts::FreezeAccount {
+                freezer: officer,
+                config,
+                role_account: role,
+                mint,
+                target_ata,
+                token_program: spl_token_2022::id(),
+            },
+            stablecoin_core::instruction::FreezeAccount {},
+        );
+        let thaw = FreezeParams {
+            freezer: officer,
+            mint,
+            target_ata,
+            token_program: spl_token_2022::id(),
+        };
+        assert_matches_program(
+            &build_thaw_instruction(thaw).unwrap(),
+            stablecoin_core::accounts::ThawAccount {
+                freezer: officer,
+                config,
+                role_account: role,
+                mint,
+                target_ata,
+                token_program: spl_token_2022::id(),
+            },
+            stablecoin_core::instruction::ThawAccount {},
+        );
 
-#[derive(Serialize, Clone)]
-struct MinterInfo {
-    address: String,
-    quota: Option<String>,
-}
+        let instruction = build_seize_instruction(SeizeParams {
+            seizer: officer,
+            config_pda: config,
+            mint,
+            target_ata,
+            target_owner: wallet,
+            treasury_ata,
+            treasury_owner,
+            transfer_hook_program: transfer_hook::ID,
+            amount: Some(250),
+            transfer_cooldown: false,
+        })
+        .unwrap();
+        assert_matches_program(
+            &instruction,
+            stablecoin_core::accounts::Seize {
+                seizer: officer,
+                config,
+                role_account: role,
+                mint,
+                target_ata,
+                treasury_ata,
+                blacklist_entry,
+                extra_metas_account: find_extra_account_metas_pda(&mint, &transfer_hook::ID).0,
+                stablecoin_core_program: stablecoin_core::ID,
+                destination_blacklist_entry: find_blacklist_pda(
+                    &config,
+                    &treasury_owner,
+                    &stablecoin_core::ID,
+                )
+                .0,
+                transfer_hook_program: transfer_hook::ID,
+                token_2022_program: spl_token_2022::id(),
+            },
+            stablecoin_core::instruction::Seize { amount: Some(250) },
+        );
 
-#[derive(Serialize)]
-struct StatusOutput {
-    mint: String,
-    preset: String,
-    is_paused: bool,
-    supply: String,
-    total_minted: String,
-    total_burned: String,
-    features: FeatureOutput,
-    role_counts: RoleCounts,
-    blacklisted: usize,
-}
+        let instruction = build_seize_and_burn_instruction(SeizeAndBurnParams {
+            seizer: officer,
+            config_pda: config,
+            mint,
+            target_ata,
+            target_owner: wallet,
+        });
+        assert_matches_program(
+            &instruction,
+            stablecoin_core::accounts::SeizeAndBurn {
+                seizer: officer,
+                config,
+                role_account: role,
+                mint,
+                target_ata,
+                blacklist_entry,
+                token_2022_program: spl_token_2022::id(),
+            },
+            stablecoin_core::instruction::SeizeAndBurn {},
+        );
 
-#[derive(Serialize)]
-struct FeatureOutput {
-    permanent_delegate: bool,
-    transfer_hook: bool,
-    confidential: bool,
-    default_frozen: bool,
-}
+        assert_eq!(seize_amount(500, None).unwrap(), 500);
+        assert_eq!(seize_amount(500, Some(500)).unwrap(), 500);
+        assert_eq!(seize_amount(500, Some(120)).unwrap(), 120);
+        assert!(seize_amount(500, Some(501)).is_err());
+        assert!(seize_amount(500, Some(0)).is_err());
+        assert!(seize_amount(0, None).is_err());
+
+        let target = target_ata.to_string();
+        let treasury = treasury_ata.to_string();
+        assert!(Cli::try_parse_from(["sss-token", "seize", &target, "--burn"]).is_ok());
+        // Without --to the seized balance goes to the configured treasury.
+        let cli = Cli::try_parse_from(["sss-token", "seize", &target]).unwrap();
+        assert!(matches!(cli.command, Commands::Seize(args) if args.to.is_none() && !args.burn));
+        assert!(
+            Cli::try_parse_from(["sss-token", "seize", &target, "--to", &treasury, "--burn"])
+                .is_err()
+        );
+        assert!(Cli::try_parse_from([
+            "sss-token",
+            "seize",
+            &target,
+            "--to",
+            &treasury,
+            "--amount",
+            "1.5"
+        ])
+        .is_ok());
+        assert!(Cli::try_parse_from([
+            "sss-token",
+            "seize",
+            &target,
+            "--to",
+            &treasury,
+            "--amount",
+            "1",
+            "--all"
+        ])
+        .is_err());
+    }
 
-#[derive(Serialize)]
-struct RoleCounts {
-    masters: usize,
-    minters: usize,
-    burners: usize,
-    freezers: usize,
-    pausers: usize,
-    blacklisters: usize,
-    seizers: usize,
-}
+    #[test]
+    fn treasury_mints_resolve_the_configured_treasury_ata() {
+        let mint = Pubkey::new_unique();
+        let treasury = Pubkey::new_unique();
+        let config = StablecoinConfig {
+            treasury: Some(treasury),
+            ..sample_config(mint)
+        };
+        assert_eq!(
+            treasury_ata(&config, &mint).unwrap(),
+            get_associated_token_address_with_program_id(&treasury, &mint, &spl_token_2022::id())
+        );
+        assert!(treasury_ata(&sample_config(mint), &mint).is_err());
 
-#[derive(Serialize)]
-struct SupplyOutput {
-    mint: String,
-    supply: String,
-}
+        let cli = Cli::try_parse_from(["sss-token", "mint", "--to-treasury", "1000"]).unwrap();
+        assert!(matches!(
+            cli.command,
+            Commands::Mint(args) if args.to_treasury.as_deref() == Some("1000")
+                && args.recipient.is_none()
+        ));
+        let recipient = Pubkey::new_unique().to_string();
+        assert!(Cli::try_parse_from(["sss-token", "mint", &recipient, "5"]).is_ok());
+        assert!(Cli::try_parse_from(["sss-token", "mint", &recipient]).is_err());
+        assert!(
+            Cli::try_parse_from(["sss-token", "mint", &recipient, "5", "--to-treasury", "5"])
+                .is_err()
+        );
+        assert!(Cli::try_parse_from(["sss-token", "config", "set-treasury", "none"]).is_ok());
+    }
 
-#[derive(Serialize, Clone)]
-struct HolderInfo {
-    owner: String,
-    token_account: String,
-    amount: u64,
-}
+    #[test]
+    fn parses_freeze_and_thaw_by_owner() {
+        let owner = Pubkey::new_unique().to_string();
+        for verb in ["freeze", "thaw"] {
+            let cli = Cli::try_parse_from(["sss-token", verb, &owner, "--owner"]).unwrap();
+            let (Commands::Freeze(args) | Commands::Thaw(args)) = cli.command else {
+                panic!("expected {verb}");
+            };
+            assert!(args.owner);
+            assert_eq!(args.address, owner);
+
+            let cli = Cli::try_parse_from(["sss-token", verb, &owner]).unwrap();
+            let (Commands::Freeze(args) | Commands::Thaw(args)) = cli.command else {
+                panic!("expected {verb}");
+            };
+            assert!(!args.owner);
+        }
+    }
 
-#[derive(Serialize)]
-struct HoldersOutput {
-    holders: Vec<HolderInfo>,
-}
+    #[test]
+    fn program_error_tables_follow_the_program_sources() {
+        use super::program_errors::{STABLECOIN_ERRORS, TRANSFER_HOOK_ERRORS};
+
+        let declared = |source: &'static str| {
+            let mut errors = Vec::new();
+            let mut message = None;
+            for line in source.lines().map(str::trim) {
+                if let Some(rest) = line.strip_prefix("#[msg(\"") {
+                    message = rest.strip_suffix("\")]");
+                } else if let (Some(text), Some(name)) = (message, line.strip_suffix(',')) {
+                    errors.push((name, text));
+                    message = None;
+                }
+            }
+            errors
+        };
+        assert_eq!(
+            declared(include_str!("../../programs/stablecoin-core/src/errors.rs")),
+            STABLECOIN_ERRORS
+        );
+        assert_eq!(
+            declared(include_str!("../../programs/transfer-hook/src/errors.rs")),
+            TRANSFER_HOOK_ERRORS
+        );
+    }
 
-#[derive(Serialize)]
-struct AuditLogOutput {
-    entries: Vec<serde_json::Value>,
-}
+    #[test]
+    fn names_custom_errors_of_the_raising_program() {
+        let payer = Pubkey::new_unique();
+        let message = Message::new(
+            &[
+                ComputeBudgetInstruction::set_compute_unit_limit(400_000),
+                Instruction::new_with_bytes(stablecoin_core::ID, &[], vec![]),
+                Instruction::new_with_bytes(spl_token_2022::id(), &[], vec![]),
+            ],
+            Some(&payer),
+        );
+        let custom =
+            |index, code| TransactionError::InstructionError(index, InstructionError::Custom(code));
+
+        let quota = describe_program_error(&custom(1, 0x1773), &[], &message).unwrap();
+        assert!(
+            quota.starts_with("QuotaExceeded: Minting quota exceeded"),
+            "{}",
+            quota
+        );
 
-fn print_json<T: Serialize>(value: &T) -> Result<()> {
-    println!("{}", serde_json::to_string_pretty(value)?);
-    Ok(())
-}
+        // A hook error surfaces on the Token-2022 instruction that invoked it.
+        let hook = transfer_hook::ID.to_string();
+        let token = spl_token_2022::id().to_string();
+        let logs = vec![
+            format!("Program {} invoke [1]", token),
+            format!("Program {} invoke [2]", hook),
+            format!("Program {} failed: custom program error: 0x1770", hook),
+            format!("Program {} failed: custom program error: 0x1770", token),
+        ];
+        let denied = describe_program_error(&custom(2, 6000), &logs, &message).unwrap();
+        assert!(denied.starts_with("TransferDenied:"), "{}", denied);
+        let seize = describe_program_error(&custom(1, 6000), &logs, &message).unwrap();
+        assert!(seize.starts_with("TransferDenied:"), "{}", seize);
+
+        assert!(describe_program_error(&custom(2, 17), &[], &message).is_none());
+        assert!(describe_program_error(&custom(1, 6999), &[], &message).is_none());
+        assert!(
+            describe_program_error(&TransactionError::AccountNotFound, &[], &message).is_none()
+        );
+    }
 
-#[cfg(test)]
-mod tests {
-    use super::{format_amount, parse_amount};
+    #[test]
+    fn grinds_mint_keypairs_for_short_base58_prefixes() {
+        assert!(check_mint_prefix("Ab1").is_ok());
+        assert!(check_mint_prefix("").is_err());
+        assert!(check_mint_prefix("ABCDE").is_err());
+        assert!(check_mint_prefix("0x").is_err());
+        assert!(check_mint_prefix("Il").is_err());
+
+        let mut reports = 0;
+        let (keypair, attempts) = grind_mint_keypair("A", 100_000, |_| reports += 1).unwrap();
+        assert!(keypair.pubkey().to_string().starts_with('A'));
+        assert!(attempts >= 1);
+        assert_eq!(reports, 0);
+        assert!(grind_mint_keypair("zzzz", 1, |_| {}).is_err());
+
+        let cli = Cli::try_parse_from(["sss-token", "init", "--mint-prefix", "USD", "--verbose"])
+            .unwrap();
+        let Commands::Init(args) = cli.command else {
+            panic!("expected init");
+        };
+        assert_eq!(args.mint_prefix.as_deref(), Some("USD"));
+        assert!(cli.verbose);
+        assert!(Cli::try_parse_from([
+            "sss-token",
+            "init",
+            "--mint-prefix",
+            "USD",
+            "--mint-keypair",
+            "mint.json",
+        ])
+        .is_err());
+    }
 
     #[test]
-    fn parses_amounts_with_decimals() {
-        assert_eq!(parse_amount("1", 6).unwrap(), 1);
-        assert_eq!(parse_amount("1.5", 6).unwrap(), 1_500_000);
-        assert_eq!(parse_amount("0.000001", 6).unwrap(), 1);
-        assert_eq!(parse_amount("1_000.25", 2).unwrap(), 100_025);
+    fn parses_blacklist_batch_files_and_packs_instructions() {
+        let first = Pubkey::new_unique();
+        let second = Pubkey::new_unique();
+        let content =
+            format!("wallet,reason\n{first}, \"OFAC SDN, 2024\"\n{second}\n{first},later\n");
+        let rows = parse_blacklist_file(&content).unwrap();
+        assert_eq!(
+            rows,
+            vec![(first, Some("OFAC SDN, 2024".to_string())), (second, None)]
+        );
+        assert!(parse_blacklist_file("wallet\n").is_err());
+        let long = format!("{first},{}\n", "x".repeat(MAX_REASON_LEN + 1));
+        assert!(parse_blacklist_file(&long).is_err());
+
+        let blacklister = Pubkey::new_unique();
+        let config_pda = Pubkey::new_unique();
+        let instructions: Vec<Instruction> = (0..20)
+            .map(|_| {
+                build_add_to_blacklist_instruction(AddToBlacklistParams {
+                    blacklister,
+                    config_pda,
+                    wallet: Pubkey::new_unique(),
+                    reason: String::new(),
+                    expires_at: None,
+                    reason_code: 1,
+                })
+                .unwrap()
+            })
+            .collect();
+        let chunks = chunk_instructions(instructions, &[], &blacklister).unwrap();
+        assert!(chunks.len() > 1 && chunks.len() < 20);
+        assert_eq!(chunks.iter().map(Vec::len).sum::<usize>(), 20);
+
+        let cli = Cli::try_parse_from([
+            "sss-token",
+            "blacklist",
+            "add-batch",
+            "--file",
+            "addrs.csv",
+            "--code",
+            "sanctions",
+        ])
+        .unwrap();
+        let Commands::Blacklist(BlacklistArgs {
+            command: BlacklistCmd::AddBatch(args),
+        }) = cli.command
+        else {
+            panic!("expected blacklist add-batch");
+        };
+        assert_eq!(args.code, "sanctions");
+        assert!(Cli::try_parse_from(["sss-token", "blacklist", "remove-batch"]).is_err());
     }
 
     #[test]
-    fn formats_amounts() {
-        assert_eq!(format_amount(1_500_000, 6), "1.500000");
-        assert_eq!(format_amount(100, 2), "1.00");
-        assert_eq!(format_amount(10, 0), "10");
+    fn plans_batch_seizures_and_skips_emptied_accounts() {
+        let first = Pubkey::new_unique();
+        let second = Pubkey::new_unique();
+        let content = format!("address,note\n{first},court order\n\n# dup\n{first}\n{second}\n");
+        assert_eq!(parse_address_list(&content).unwrap(), vec![first, second]);
+        assert!(parse_address_list("address\n").is_err());
+        assert!(parse_address_list("not-a-key\n").is_err());
+
+        let mint = Pubkey::new_unique();
+        let config_pda = find_config_pda(&mint, &stablecoin_core::ID).0;
+        let owner = Pubkey::new_unique();
+        let account = TokenAccountInfo {
+            owner,
+            mint,
+            amount: 500,
+            is_frozen: true,
+        };
+        let entry = BlacklistEntry {
+            config: config_pda,
+            wallet: owner,
+            blacklisted_at: 0,
+            blacklisted_by: Pubkey::new_unique(),
+            reason: "OFAC".to_string(),
+            is_active: true,
+            bump: 255,
+            expires_at: None,
+            reason_code: BlacklistReason::Sanctions.code(),
+        };
+        let status = |account: &TokenAccountInfo, entry: Option<&BlacklistEntry>| {
+            classify_seize_target(&mint, &config_pda, Some(account), entry, 1_000).label()
+        };
+
+        assert_eq!(status(&account, Some(&entry)), "ready");
+        assert_eq!(status(&account, None), "rejected");
+        let thawed = TokenAccountInfo {
+            is_frozen: false,
+            ..account
+        };
+        assert_eq!(status(&thawed, Some(&entry)), "rejected");
+        let emptied = TokenAccountInfo {
+            amount: 0,
+            ..account
+        };
+        assert_eq!(status(&emptied, None), "skipped");
+        let other_mint = TokenAccountInfo {
+            mint: Pubkey::new_unique(),
+            ..account
+        };
+        assert_eq!(status(&other_mint, Some(&entry)), "rejected");
+        assert!(matches!(
+            classify_seize_target(&mint, &config_pda, None, Some(&entry), 1_000),
+            SeizeStatus::Rejected(_)
+        ));
+        let lifted = BlacklistEntry {
+            is_active: false,
+            ..entry
+        };
+        assert_eq!(status(&account, Some(&lifted)), "rejected");
+        let expiring = BlacklistEntry {
+            expires_at: Some(1_000),
+            ..lifted
+        };
+        assert_eq!(status(&account, Some(&expiring)), "rejected");
+        let expiring = BlacklistEntry {
+            is_active: true,
+            ..expiring
+        };
+        assert_eq!(status(&account, Some(&expiring)), "ready");
+        let expired = BlacklistEntry {
+            expires_at: Some(999),
+            ..expiring
+        };
+        assert_eq!(status(&account, Some(&expired)), "rejected");
+        assert_eq!(parse_expires_in("30d").unwrap(), 30 * 86_400);
+        assert_eq!(parse_expires_in("3600").unwrap(), 3_600);
+        assert!(parse_expires_in("0").is_err());
+        assert!(parse_expires_in("soon").is_err());
     }
 }
@@ -0,0 +1,237 @@
+//! Names and messages of the custom errors stablecoin-core and the
+//! transfer hook return, so a failed transaction can say "QuotaExceeded"
+//! instead of "custom program error: 0x1773".
+
+use solana_sdk::pubkey::Pubkey;
+
+/// Anchor numbers a program's own errors from 6000 in declaration order.
+const ERROR_CODE_OFFSET: u32 = 6000;
+
+/// `StablecoinError`, in declaration order.
+pub(crate) const STABLECOIN_ERRORS: &[(&str, &str)] = &[
+    ("Unauthorized", "Caller does not have the required role"),
+    (
+        "FeatureNotEnabled",
+        "This feature was not enabled during initialization",
+    ),
+    ("SystemPaused", "System is paused"),
+    ("QuotaExceeded", "Minting quota exceeded for current window"),
+    ("AlreadyBlacklisted", "Address is already blacklisted"),
+    ("NotBlacklisted", "Address is not blacklisted"),
+    ("AccountNotFrozen", "Account must be frozen before seizure"),
+    (
+        "TargetNotBlacklisted",
+        "Target must be blacklisted before seizure",
+    ),
+    (
+        "NameTooLong",
+        "Name exceeds maximum length of 32 characters",
+    ),
+    (
+        "SymbolTooLong",
+        "Symbol exceeds maximum length of 10 characters",
+    ),
+    ("UriTooLong", "URI exceeds maximum length of 200 characters"),
+    (
+        "InvalidTransferHookProgram",
+        "Transfer hook program must be provided when enabled",
+    ),
+    (
+        "MissingExtraAccountMetas",
+        "Missing extra account metas PDA",
+    ),
+    (
+        "InvalidExtraAccountMetas",
+        "Extra account metas PDA does not match expected address",
+    ),
+    (
+        "ReasonTooLong",
+        "Reason exceeds maximum length of 128 characters",
+    ),
+    ("InvalidRoles", "Invalid role bitmask"),
+    ("SelfTransfer", "Cannot transfer authority to self"),
+    ("InsufficientBalance", "Insufficient token balance"),
+    ("Overflow", "Arithmetic overflow"),
+    (
+        "AccountFrozen",
+        "Account is frozen and cannot perform this action",
+    ),
+    (
+        "PauseCooldownActive",
+        "Pause state changed too recently; cooldown still active",
+    ),
+    (
+        "InvalidPauseCooldown",
+        "Pause cooldown must not be negative",
+    ),
+    (
+        "InvalidBatchSize",
+        "Batch must contain between 1 and 16 entries with the accounts each one needs",
+    ),
+    (
+        "EmptyMetadataUpdate",
+        "Metadata update must change at least one field",
+    ),
+    (
+        "SupplyCapExceeded",
+        "Mint would exceed the configured maximum supply",
+    ),
+    (
+        "InvalidMaxSupply",
+        "Max supply can only be lowered and not below the current supply",
+    ),
+    (
+        "SeizeToSelf",
+        "Seize target and treasury must be different token accounts",
+    ),
+    (
+        "ForbiddenRecipient",
+        "Recipient is a well-known address that can never use minted tokens",
+    ),
+    (
+        "InvalidQuotaWindow",
+        "Quota window must be at least 60 seconds",
+    ),
+    (
+        "AllowlistRequiresTransferHook",
+        "Allowlist mode requires the transfer hook",
+    ),
+    (
+        "AllowlistModeEnabled",
+        "The blacklist is not used while allowlist mode is on",
+    ),
+    ("AllowlistModeDisabled", "Allowlist mode is not enabled"),
+    ("AlreadyAllowlisted", "Address is already allowlisted"),
+    ("NotAllowlisted", "Address is not allowlisted"),
+    (
+        "InvalidTransferLimit",
+        "Transfer limit must be greater than zero",
+    ),
+    (
+        "BlacklistEntryActive",
+        "Blacklist entry is still active; remove it before closing",
+    ),
+    (
+        "InvalidPauseMask",
+        "Pause mask must name at least one of mint, burn, or transfer",
+    ),
+    ("InvalidExpiry", "Blacklist expiry must be in the future"),
+    (
+        "CannotRenounceMasterAuthority",
+        "The master authority cannot be renounced; transfer it instead",
+    ),
+    (
+        "LastMasterAuthority",
+        "The master authority cannot be removed from its own holder; transfer it instead",
+    ),
+    ("InvalidDecimals", "Decimals must be at most 9"),
+    (
+        "InvalidReasonCode",
+        "Blacklist reason code must be sanctions, fraud, court order, or other",
+    ),
+    (
+        "LifetimeQuotaExceeded",
+        "Minting would exceed the minter's lifetime quota",
+    ),
+    (
+        "InvalidMetadataField",
+        "Metadata key must be 1-32 bytes and not name, symbol, or uri; values are capped at 200 bytes",
+    ),
+    (
+        "ConfigTooLarge",
+        "Config would not fit the space allocated for it",
+    ),
+    (
+        "MintingLocked",
+        "Minting has been permanently locked for this stablecoin",
+    ),
+    (
+        "InvalidTokenProgram",
+        "Token program does not match the one the stablecoin was created with",
+    ),
+    ("Token2022Required", "This feature needs a Token-2022 mint"),
+    (
+        "TransferHookAlreadyEnabled",
+        "The mint already has a transfer hook program",
+    ),
+    (
+        "SelfApproval",
+        "A mint request must be approved by someone other than its requester",
+    ),
+    (
+        "InvalidTransferCooldown",
+        "Transfer cooldown must be positive and needs the transfer hook",
+    ),
+    ("TransferDenied", "Recipient is blacklisted"),
+    (
+        "MissingBlacklistEntry",
+        "The recipient's blacklist PDA must be passed while the transfer hook is enabled",
+    ),
+    (
+        "MintApprovalRequired",
+        "Direct minting is disabled; use propose_mint and approve_mint",
+    ),
+    (
+        "MintRequestExpired",
+        "The mint request has expired and can only be cancelled",
+    ),
+];
+
+/// `TransferHookError`, in declaration order.
+pub(crate) const TRANSFER_HOOK_ERRORS: &[(&str, &str)] = &[
+    ("TransferDenied", "Transfer denied by blacklist"),
+    ("FeatureNotEnabled", "Transfer hook feature not enabled"),
+    (
+        "InvalidExtraAccountMetas",
+        "Invalid extra account metas account",
+    ),
+    (
+        "InvalidCoreProgram",
+        "Invalid stablecoin core program account",
+    ),
+    ("InvalidConfig", "Invalid stablecoin config account"),
+    ("InvalidHookProgram", "Invalid transfer hook program id"),
+    ("InvalidBlacklistEntry", "Invalid blacklist entry account"),
+    (
+        "TokenAccountMintMismatch",
+        "Token account mint does not match the transferred mint",
+    ),
+    ("SystemPaused", "Stablecoin is paused"),
+    (
+        "NotAllowlisted",
+        "Transfer denied: wallet is not allowlisted",
+    ),
+    (
+        "AmountLimitExceeded",
+        "Transfer amount exceeds the per-transfer limit",
+    ),
+    (
+        "CooldownActive",
+        "Transfer denied: the sender's transfer cooldown has not elapsed",
+    ),
+    (
+        "LastTransferMissing",
+        "The sender's last-transfer account is missing; initialize it first",
+    ),
+    (
+        "NotTransferring",
+        "Execute may only be invoked by Token-2022 during a transfer",
+    ),
+];
+
+/// Name and message of custom error `code` raised by `program_id`. The
+/// transfer hook is the only other Anchor program these transactions reach,
+/// and it may have been migrated to a new id, so any other program's codes
+/// from 6000 up are read as hook errors.
+pub(crate) fn program_error(
+    program_id: &Pubkey,
+    code: u32,
+) -> Option<(&'static str, &'static str)> {
+    let index = usize::try_from(code.checked_sub(ERROR_CODE_OFFSET)?).ok()?;
+    let table = if *program_id == stablecoin_core::ID {
+        STABLECOIN_ERRORS
+    } else {
+        TRANSFER_HOOK_ERRORS
+    };
+    table.get(index).copied()
+}
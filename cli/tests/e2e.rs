@@ -0,0 +1,311 @@
+//! End-to-end CLI flows against a local `solana-test-validator` running the
+//! freshly built programs, to catch drift between the CLI and the on-chain
+//! account orders, discriminators, and borsh layouts.
+//!
+//! Needs the Solana tool suite on PATH and `anchor build` output in
+//! `target/deploy`. Run with
+//! `cargo test -p sss-token-cli --test e2e -- --ignored`.
+
+use serde_json::Value;
+use solana_client::rpc_client::RpcClient;
+use solana_sdk::commitment_config::CommitmentConfig;
+use solana_sdk::native_token::LAMPORTS_PER_SOL;
+use solana_sdk::pubkey::Pubkey;
+use solana_sdk::signature::{write_keypair_file, Keypair, Signer};
+use spl_associated_token_account::get_associated_token_address_with_program_id;
+use std::net::TcpListener;
+use std::path::{Path, PathBuf};
+use std::process::{Child, Command, Stdio};
+use std::thread::sleep;
+use std::time::{Duration, Instant};
+
+const STARTUP_TIMEOUT: Duration = Duration::from_secs(60);
+
+struct Validator {
+    child: Child,
+    ledger: PathBuf,
+    url: String,
+}
+
+impl Validator {
+    fn start(workdir: &Path) -> Self {
+        let deploy = Path::new(env!("CARGO_MANIFEST_DIR")).join("../target/deploy");
+        let core_so = deploy.join("stablecoin_core.so");
+        let hook_so = deploy.join("transfer_hook.so");
+        for program in [&core_so, &hook_so] {
+            assert!(
+                program.exists(),
+                "{} is missing; run `anchor build` first",
+                program.display()
+            );
+        }
+
+        let rpc_port = free_port();
+        let ledger = workdir.join("ledger");
+        let child = Command::new("solana-test-validator")
+            .arg("--reset")
+            .arg("--quiet")
+            .arg("--ledger")
+            .arg(&ledger)
+            .args(["--rpc-port", &rpc_port.to_string()])
+            .args(["--faucet-port", &free_port().to_string()])
+            .arg("--bpf-program")
+            .arg(stablecoin_core::ID.to_string())
+            .arg(&core_so)
+            .arg("--bpf-program")
+            .arg(transfer_hook::ID.to_string())
+            .arg(&hook_so)
+            .stdout(Stdio::null())
+            .stderr(Stdio::null())
+            .spawn()
+            .expect("solana-test-validator must be on PATH");
+
+        let validator = Self {
+            child,
+            ledger,
+            url: format!("http://127.0.0.1:{}", rpc_port),
+        };
+        let client = validator.client();
+        let started = Instant::now();
+        while client.get_health().is_err() {
+            assert!(
+                started.elapsed() < STARTUP_TIMEOUT,
+                "validator did not become healthy"
+            );
+            sleep(Duration::from_millis(500));
+        }
+        validator
+    }
+
+    fn client(&self) -> RpcClient {
+        RpcClient::new_with_commitment(self.url.clone(), CommitmentConfig::confirmed())
+    }
+}
+
+impl Drop for Validator {
+    fn drop(&mut self) {
+        let _ = self.child.kill();
+        let _ = self.child.wait();
+        if let Some(workdir) = self.ledger.parent() {
+            let _ = std::fs::remove_dir_all(workdir);
+        }
+    }
+}
+
+fn free_port() -> u16 {
+    TcpListener::bind("127.0.0.1:0")
+        .and_then(|listener| listener.local_addr())
+        .map(|addr| addr.port())
+        .expect("no free local port")
+}
+
+struct Harness {
+    validator: Validator,
+    keypair_path: PathBuf,
+    payer: Keypair,
+}
+
+impl Harness {
+    fn start(name: &str) -> Self {
+        let workdir = std::env::temp_dir().join(format!("sss-e2e-{}-{}", std::process::id(), name));
+        std::fs::create_dir_all(&workdir).unwrap();
+        let validator = Validator::start(&workdir);
+
+        let payer = Keypair::new();
+        let keypair_path = workdir.join("payer.json");
+        write_keypair_file(&payer, &keypair_path).unwrap();
+        let client = validator.client();
+        let signature = client
+            .request_airdrop(&payer.pubkey(), 10 * LAMPORTS_PER_SOL)
+            .unwrap();
+        let started = Instant::now();
+        while !client.confirm_transaction(&signature).unwrap_or(false) {
+            assert!(started.elapsed() < STARTUP_TIMEOUT, "airdrop not confirmed");
+            sleep(Duration::from_millis(250));
+        }
+
+        Self {
+            validator,
+            keypair_path,
+            payer,
+        }
+    }
+
+    /// Runs the CLI with JSON output and returns the parsed result.
+    fn run(&self, args: &[&str]) -> Value {
+        let output = Command::new(env!("CARGO_BIN_EXE_sss-token-cli"))
+            .args(["--cluster", &self.validator.url])
+            .arg("--keypair")
+            .arg(&self.keypair_path)
+            .args(["--output", "json", "--yes"])
+            .args(args)
+            .output()
+            .unwrap();
+        assert!(
+            output.status.success(),
+            "sss-token {:?} failed:\n{}",
+            args,
+            String::from_utf8_lossy(&output.stderr)
+        );
+        serde_json::from_slice(&output.stdout).unwrap_or_else(|err| {
+            panic!(
+                "sss-token {:?} printed invalid JSON ({}):\n{}",
+                args,
+                err,
+                String::from_utf8_lossy(&output.stdout)
+            )
+        })
+    }
+
+    /// Runs the CLI expecting it to fail and returns its stderr.
+    fn run_err(&self, args: &[&str]) -> String {
+        let output = Command::new(env!("CARGO_BIN_EXE_sss-token-cli"))
+            .args(["--cluster", &self.validator.url])
+            .arg("--keypair")
+            .arg(&self.keypair_path)
+            .args(args)
+            .output()
+            .unwrap();
+        assert!(
+            !output.status.success(),
+            "sss-token {:?} unexpectedly succeeded",
+            args
+        );
+        String::from_utf8_lossy(&output.stderr).into_owned()
+    }
+
+    fn balance(&self, owner: &Pubkey, mint: &Pubkey) -> String {
+        let ata = get_associated_token_address_with_program_id(owner, mint, &spl_token_2022::id());
+        self.validator
+            .client()
+            .get_token_account_balance(&ata)
+            .unwrap()
+            .amount
+    }
+}
+
+#[test]
+#[ignore = "needs solana-test-validator and anchor build output"]
+fn sss2_lifecycle() {
+    let harness = Harness::start("sss2");
+    let payer = harness.payer.pubkey().to_string();
+
+    let init = harness.run(&[
+        "init", "--preset", "sss-2", "--name", "E2E USD", "--symbol", "EUSD",
+    ]);
+    let mint_address = init["mint"].as_str().unwrap().to_string();
+    let mint = mint_address.parse::<Pubkey>().unwrap();
+    let with_mint = |args: &[&str]| {
+        let mut args = args.to_vec();
+        args.extend(["--mint", mint_address.as_str()]);
+        harness.run(&args)
+    };
+
+    let minted = with_mint(&["mint", &payer, "100"]);
+    assert_eq!(minted["new_supply"], "100");
+    let burned = with_mint(&["burn", "10"]);
+    assert_eq!(burned["new_supply"], "90");
+
+    let holder = Pubkey::new_unique();
+    let holder_address = holder.to_string();
+    with_mint(&["mint", &holder_address, "50"]);
+    assert_eq!(harness.balance(&holder, &mint), "50");
+
+    with_mint(&["blacklist", "add", &holder_address, "--reason", "e2e"]);
+    let check = with_mint(&["blacklist", "check", &holder_address]);
+    assert_eq!(check["is_active"], true);
+
+    let holder_ata =
+        get_associated_token_address_with_program_id(&holder, &mint, &spl_token_2022::id())
+            .to_string();
+    let payer_ata = get_associated_token_address_with_program_id(
+        &harness.payer.pubkey(),
+        &mint,
+        &spl_token_2022::id(),
+    )
+    .to_string();
+    with_mint(&["freeze", &holder_ata]);
+    with_mint(&["seize", &holder_ata, "--to", &payer_ata, "--all"]);
+    assert_eq!(harness.balance(&holder, &mint), "0");
+    let balance = with_mint(&["balance", &holder_address]);
+    assert_eq!(balance["amount"], 0);
+    assert_eq!(balance["is_frozen"], true);
+    assert_eq!(harness.balance(&harness.payer.pubkey(), &mint), "140");
+
+    let status = with_mint(&["status"]);
+    assert_eq!(status["preset"], "SSS-2");
+    assert_eq!(status["supply"], "140");
+    assert_eq!(status["blacklisted"], 1);
+    assert_eq!(status["is_paused"], false);
+}
+
+#[test]
+#[ignore = "needs solana-test-validator and anchor build output"]
+fn sss1_mint_and_burn() {
+    let harness = Harness::start("sss1");
+    let payer = harness.payer.pubkey().to_string();
+
+    let init = harness.run(&[
+        "init",
+        "--preset",
+        "sss-1",
+        "--name",
+        "Plain USD",
+        "--symbol",
+        "PUSD",
+    ]);
+    let mint_address = init["mint"].as_str().unwrap().to_string();
+    let with_mint = |args: &[&str]| {
+        let mut args = args.to_vec();
+        args.extend(["--mint", mint_address.as_str()]);
+        harness.run(&args)
+    };
+
+    with_mint(&["mint", &payer, "1.5"]);
+    let burned = with_mint(&["burn", "0.5"]);
+    assert_eq!(burned["new_supply"], "1000000");
+
+    let status = with_mint(&["status"]);
+    assert_eq!(status["preset"], "SSS-1");
+    assert_eq!(status["features"]["transfer_hook"], false);
+}
+
+#[test]
+#[ignore = "needs solana-test-validator and anchor build output"]
+fn init_rejects_an_initialized_mint() {
+    let harness = Harness::start("reinit");
+    let mint = Keypair::new();
+    let mint_keypair_path = harness.keypair_path.with_file_name("mint.json");
+    write_keypair_file(&mint, &mint_keypair_path).unwrap();
+    let mint_keypair = mint_keypair_path.to_str().unwrap();
+
+    let init = harness.run(&[
+        "init",
+        "--preset",
+        "sss-1",
+        "--name",
+        "Vanity USD",
+        "--symbol",
+        "VUSD",
+        "--mint-keypair",
+        mint_keypair,
+    ]);
+    assert_eq!(init["mint"], mint.pubkey().to_string());
+
+    let stderr = harness.run_err(&[
+        "init",
+        "--preset",
+        "sss-1",
+        "--name",
+        "Vanity USD",
+        "--symbol",
+        "VUSD",
+        "--mint-keypair",
+        mint_keypair,
+    ]);
+    assert!(
+        stderr.contains("already initialized"),
+        "unexpected error:\n{}",
+        stderr
+    );
+}
@@ -5,12 +5,45 @@ pub const ROLE_FREEZER: u8 = 0x08;
 pub const ROLE_PAUSER: u8 = 0x10;
 pub const ROLE_BLACKLISTER: u8 = 0x20;
 pub const ROLE_SEIZER: u8 = 0x40;
+/// Grants and revokes the operational roles below without the master bit.
+pub const ROLE_ROLE_ADMIN: u8 = 0x80;
 
-pub const VALID_ROLE_MASK: u8 = 0x7F;
+pub const VALID_ROLE_MASK: u8 = 0xFF;
+
+/// Roles a role admin may grant. Minters are left out because granting one
+/// sets its quota, which stays with the master authority.
+pub const ROLE_ADMIN_GRANTABLE: u8 =
+    ROLE_BURNER | ROLE_FREEZER | ROLE_PAUSER | ROLE_BLACKLISTER | ROLE_SEIZER;
+/// Roles a role admin may revoke.
+pub const ROLE_ADMIN_REVOCABLE: u8 = ROLE_ADMIN_GRANTABLE | ROLE_MINTER;
+
+/// Set by configs written while pause was a single bool; read as PAUSE_ALL.
+pub const PAUSE_LEGACY: u8 = 0x01;
+pub const PAUSE_MINT: u8 = 0x02;
+pub const PAUSE_BURN: u8 = 0x04;
+pub const PAUSE_TRANSFER: u8 = 0x08;
+
+pub const PAUSE_ALL: u8 = PAUSE_MINT | PAUSE_BURN | PAUSE_TRANSFER;
 
 pub const MAX_NAME_LEN: usize = 32;
 pub const MAX_SYMBOL_LEN: usize = 10;
 pub const MAX_URI_LEN: usize = 200;
 pub const MAX_REASON_LEN: usize = 128;
+pub const MAX_METADATA_KEY_LEN: usize = 32;
+pub const MAX_METADATA_VALUE_LEN: usize = 200;
+/// SPL convention; beyond this `10^decimals` no longer fits amounts sensibly in a u64.
+pub const MAX_DECIMALS: u8 = 9;
 
 pub const MINT_QUOTA_WINDOW_SECONDS: i64 = 86_400;
+pub const MIN_QUOTA_WINDOW_SECONDS: i64 = 60;
+
+pub const MAX_MINT_BATCH_SIZE: usize = 16;
+
+/// How long a proposed mint stays approvable; older requests can only be cancelled.
+pub const MINT_REQUEST_TTL_SECONDS: i64 = 7 * 86_400;
+
+/// Layout version of the versioned events (TokensMinted, TokensBurned,
+/// TokensSeized, BlacklistAdded), carried in their trailing `schema_version`.
+/// Bump it whenever one of them gains or loses a field; fields are only ever
+/// appended, so older decoders still read the prefix.
+pub const EVENT_SCHEMA_VERSION: u16 = 3;
@@ -61,4 +61,109 @@ pub enum StablecoinError {
 
     #[msg("Account is frozen and cannot perform this action")]
     AccountFrozen,
+
+    #[msg("Pause state changed too recently; cooldown still active")]
+    PauseCooldownActive,
+
+    #[msg("Pause cooldown must not be negative")]
+    InvalidPauseCooldown,
+
+    #[msg("Batch must contain between 1 and 16 entries with the accounts each one needs")]
+    InvalidBatchSize,
+
+    #[msg("Metadata update must change at least one field")]
+    EmptyMetadataUpdate,
+
+    #[msg("Mint would exceed the configured maximum supply")]
+    SupplyCapExceeded,
+
+    #[msg("Max supply can only be lowered and not below the current supply")]
+    InvalidMaxSupply,
+
+    #[msg("Seize target and treasury must be different token accounts")]
+    SeizeToSelf,
+
+    #[msg("Recipient is a well-known address that can never use minted tokens")]
+    ForbiddenRecipient,
+
+    #[msg("Quota window must be at least 60 seconds")]
+    InvalidQuotaWindow,
+
+    #[msg("Allowlist mode requires the transfer hook")]
+    AllowlistRequiresTransferHook,
+
+    #[msg("The blacklist is not used while allowlist mode is on")]
+    AllowlistModeEnabled,
+
+    #[msg("Allowlist mode is not enabled")]
+    AllowlistModeDisabled,
+
+    #[msg("Address is already allowlisted")]
+    AlreadyAllowlisted,
+
+    #[msg("Address is not allowlisted")]
+    NotAllowlisted,
+
+    #[msg("Transfer limit must be greater than zero")]
+    InvalidTransferLimit,
+
+    #[msg("Blacklist entry is still active; remove it before closing")]
+    BlacklistEntryActive,
+
+    #[msg("Pause mask must name at least one of mint, burn, or transfer")]
+    InvalidPauseMask,
+
+    #[msg("Blacklist expiry must be in the future")]
+    InvalidExpiry,
+
+    #[msg("The master authority cannot be renounced; transfer it instead")]
+    CannotRenounceMasterAuthority,
+
+    #[msg("The master authority cannot be removed from its own holder; transfer it instead")]
+    LastMasterAuthority,
+
+    #[msg("Decimals must be at most 9")]
+    InvalidDecimals,
+
+    #[msg("Blacklist reason code must be sanctions, fraud, court order, or other")]
+    InvalidReasonCode,
+
+    #[msg("Minting would exceed the minter's lifetime quota")]
+    LifetimeQuotaExceeded,
+
+    #[msg("Metadata key must be 1-32 bytes and not name, symbol, or uri; values are capped at 200 bytes")]
+    InvalidMetadataField,
+
+    #[msg("Config would not fit the space allocated for it")]
+    ConfigTooLarge,
+
+    #[msg("Minting has been permanently locked for this stablecoin")]
+    MintingLocked,
+
+    #[msg("Token program does not match the one the stablecoin was created with")]
+    InvalidTokenProgram,
+
+    #[msg("This feature needs a Token-2022 mint")]
+    Token2022Required,
+
+    #[msg("The mint already has a transfer hook program")]
+    TransferHookAlreadyEnabled,
+
+    #[msg("A mint request must be approved by someone other than its requester")]
+    SelfApproval,
+
+    #[msg("Transfer cooldown must be positive and needs the transfer hook")]
+    InvalidTransferCooldown,
+
+    #[msg("Recipient is blacklisted")]
+    TransferDenied,
+
+    #[msg("The recipient's blacklist PDA must be passed while the transfer hook is enabled")]
+    MissingBlacklistEntry,
+
+    #[msg("Direct minting is disabled; use propose_mint and approve_mint")]
+    MintApprovalRequired,
+
+    #[msg("The mint request has expired and can only be cancelled")]
+    MintRequestExpired,
 }
@@ -20,6 +20,49 @@ pub struct TokensMinted {
     pub minter: Pubkey,
     pub new_total_supply: u64,
     pub timestamp: i64,
+    pub schema_version: u16,
+}
+
+#[event]
+pub struct MintProposed {
+    pub config: Pubkey,
+    pub request: Pubkey,
+    pub id: u64,
+    pub requester: Pubkey,
+    pub recipient: Pubkey,
+    pub amount: u64,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct MintApproved {
+    pub config: Pubkey,
+    pub request: Pubkey,
+    pub id: u64,
+    pub requester: Pubkey,
+    pub approver: Pubkey,
+    pub recipient: Pubkey,
+    pub amount: u64,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct MintCancelled {
+    pub config: Pubkey,
+    pub request: Pubkey,
+    pub id: u64,
+    pub requester: Pubkey,
+    pub cancelled_by: Pubkey,
+    pub amount: u64,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct MintApprovalUpdated {
+    pub config: Pubkey,
+    pub require_mint_approval: bool,
+    pub updated_by: Pubkey,
+    pub timestamp: i64,
 }
 
 #[event]
@@ -30,6 +73,7 @@ pub struct TokensBurned {
     pub amount: u64,
     pub new_total_supply: u64,
     pub timestamp: i64,
+    pub schema_version: u16,
 }
 
 #[event]
@@ -53,6 +97,7 @@ pub struct SystemPaused {
     pub config: Pubkey,
     pub paused_by: Pubkey,
     pub timestamp: i64,
+    pub pause_flags: u8,
 }
 
 #[event]
@@ -60,6 +105,15 @@ pub struct SystemUnpaused {
     pub config: Pubkey,
     pub unpaused_by: Pubkey,
     pub timestamp: i64,
+    pub pause_flags: u8,
+}
+
+#[event]
+pub struct AdminOpsLockUpdated {
+    pub config: Pubkey,
+    pub lock_admin_ops: bool,
+    pub updated_by: Pubkey,
+    pub timestamp: i64,
 }
 
 #[event]
@@ -67,6 +121,8 @@ pub struct RoleUpdated {
     pub config: Pubkey,
     pub target: Pubkey,
     pub new_roles: u8,
+    pub added_roles: u8,
+    pub removed_roles: u8,
     pub updated_by: Pubkey,
     pub timestamp: i64,
 }
@@ -86,6 +142,19 @@ pub struct BlacklistAdded {
     pub reason: String,
     pub blacklisted_by: Pubkey,
     pub timestamp: i64,
+    pub expires_at: Option<i64>,
+    pub schema_version: u16,
+    pub reason_code: u8,
+}
+
+#[event]
+pub struct BlacklistUpdated {
+    pub config: Pubkey,
+    pub wallet: Pubkey,
+    pub reason: String,
+    pub reason_code: u8,
+    pub updated_by: Pubkey,
+    pub timestamp: i64,
 }
 
 #[event]
@@ -104,4 +173,160 @@ pub struct TokensSeized {
     pub amount: u64,
     pub seized_by: Pubkey,
     pub timestamp: i64,
+    pub schema_version: u16,
+    /// Config total_seized after this seizure (schema version 3).
+    pub total_seized: u64,
+}
+
+#[event]
+pub struct MetadataUpdated {
+    pub config: Pubkey,
+    pub mint: Pubkey,
+    pub updated_by: Pubkey,
+    pub name: String,
+    pub symbol: String,
+    pub uri: String,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct MetadataFieldUpdated {
+    pub config: Pubkey,
+    pub mint: Pubkey,
+    pub updated_by: Pubkey,
+    pub key: String,
+    pub value: String,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct SupplyFrozen {
+    pub config: Pubkey,
+    pub mint: Pubkey,
+    pub total_supply: u64,
+    pub mint_authority_nulled: bool,
+    pub frozen_by: Pubkey,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct MaxSupplyUpdated {
+    pub config: Pubkey,
+    pub previous_max_supply: Option<u64>,
+    pub max_supply: u64,
+    pub updated_by: Pubkey,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct QuotaWindowUpdated {
+    pub config: Pubkey,
+    pub previous_window_seconds: i64,
+    pub quota_window_seconds: i64,
+    pub updated_by: Pubkey,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct AllowlistAdded {
+    pub config: Pubkey,
+    pub wallet: Pubkey,
+    pub added_by: Pubkey,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct AllowlistRemoved {
+    pub config: Pubkey,
+    pub wallet: Pubkey,
+    pub removed_by: Pubkey,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct TransferLimitUpdated {
+    pub config: Pubkey,
+    pub previous_max_transfer_amount: Option<u64>,
+    pub max_transfer_amount: Option<u64>,
+    pub updated_by: Pubkey,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct BlacklistEntryClosed {
+    pub config: Pubkey,
+    pub wallet: Pubkey,
+    pub closed_by: Pubkey,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct QuotaWindowReset {
+    pub config: Pubkey,
+    pub role_account: Pubkey,
+    pub minter: Pubkey,
+    pub previous_window_total: u64,
+    pub window_start: i64,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct TransferHookProgramUpdated {
+    pub config: Pubkey,
+    pub previous_program: Option<Pubkey>,
+    pub new_program: Pubkey,
+    pub updated_by: Pubkey,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct TransferHookEnabled {
+    pub config: Pubkey,
+    pub program: Pubkey,
+    pub enabled_by: Pubkey,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct TokensSeizedAndBurned {
+    pub config: Pubkey,
+    pub from_account: Pubkey,
+    pub amount: u64,
+    pub new_total_supply: u64,
+    pub seized_by: Pubkey,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct SelfTransferUpdated {
+    pub config: Pubkey,
+    pub allow_self_transfer: bool,
+    pub updated_by: Pubkey,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct TreasuryUpdated {
+    pub config: Pubkey,
+    pub previous_treasury: Option<Pubkey>,
+    pub treasury: Option<Pubkey>,
+    pub updated_by: Pubkey,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct DefaultAccountStateUpdated {
+    pub config: Pubkey,
+    pub default_frozen: bool,
+    pub updated_by: Pubkey,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct ConfigMigrated {
+    pub config: Pubkey,
+    pub previous_len: u32,
+    pub new_len: u32,
+    pub paid_by: Pubkey,
+    pub timestamp: i64,
 }
@@ -0,0 +1,146 @@
+use anchor_lang::prelude::*;
+
+use crate::constants::{ROLE_BLACKLISTER, ROLE_MASTER_AUTHORITY};
+use crate::errors::StablecoinError;
+use crate::events::{AllowlistAdded, AllowlistRemoved};
+use crate::state::{AllowlistEntry, RoleAccount, StablecoinConfig};
+use crate::utils::has_any_role;
+
+#[derive(AnchorSerialize, AnchorDeserialize, Clone)]
+pub struct AddToAllowlistArgs {
+    pub wallet: Pubkey,
+}
+
+#[derive(Accounts)]
+pub struct AddToAllowlist<'info> {
+    #[account(mut)]
+    pub blacklister: Signer<'info>,
+
+    #[account(mut)]
+    pub config: Account<'info, StablecoinConfig>,
+
+    #[account(
+        seeds = [b"role", config.key().as_ref(), blacklister.key().as_ref()],
+        bump = role_account.bump
+    )]
+    pub role_account: Account<'info, RoleAccount>,
+
+    #[account(
+        init_if_needed,
+        payer = blacklister,
+        space = 8 + AllowlistEntry::INIT_SPACE,
+        seeds = [b"allowlist", config.key().as_ref(), wallet.key().as_ref()],
+        bump
+    )]
+    pub allowlist_entry: Account<'info, AllowlistEntry>,
+
+    /// CHECK: Verified against args.wallet before use.
+    pub wallet: UncheckedAccount<'info>,
+
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct RemoveFromAllowlist<'info> {
+    pub blacklister: Signer<'info>,
+
+    #[account(mut)]
+    pub config: Account<'info, StablecoinConfig>,
+
+    #[account(
+        seeds = [b"role", config.key().as_ref(), blacklister.key().as_ref()],
+        bump = role_account.bump
+    )]
+    pub role_account: Account<'info, RoleAccount>,
+
+    #[account(mut)]
+    pub allowlist_entry: Account<'info, AllowlistEntry>,
+}
+
+pub fn add_handler(ctx: Context<AddToAllowlist>, args: AddToAllowlistArgs) -> Result<()> {
+    let config = &mut ctx.accounts.config;
+    let role_account = &ctx.accounts.role_account;
+    let entry = &mut ctx.accounts.allowlist_entry;
+
+    require!(
+        config.allowlist_mode,
+        StablecoinError::AllowlistModeDisabled
+    );
+    require!(
+        role_account.config == config.key(),
+        StablecoinError::Unauthorized
+    );
+    require!(
+        has_any_role(role_account.roles, ROLE_MASTER_AUTHORITY | ROLE_BLACKLISTER),
+        StablecoinError::Unauthorized
+    );
+    require!(
+        config.admin_ops_allowed(role_account.roles),
+        StablecoinError::SystemPaused
+    );
+    require!(
+        args.wallet == ctx.accounts.wallet.key(),
+        StablecoinError::Unauthorized
+    );
+
+    if entry.config != Pubkey::default() {
+        require!(entry.config == config.key(), StablecoinError::Unauthorized);
+    }
+
+    if entry.is_active {
+        return err!(StablecoinError::AlreadyAllowlisted);
+    }
+
+    entry.config = config.key();
+    entry.wallet = args.wallet;
+    entry.is_active = true;
+    entry.bump = ctx.bumps.allowlist_entry;
+    config.record_action(ctx.accounts.blacklister.key(), Clock::get()?.unix_timestamp);
+
+    emit!(AllowlistAdded {
+        config: config.key(),
+        wallet: entry.wallet,
+        added_by: ctx.accounts.blacklister.key(),
+        timestamp: Clock::get()?.unix_timestamp,
+    });
+    Ok(())
+}
+
+pub fn remove_handler(ctx: Context<RemoveFromAllowlist>) -> Result<()> {
+    let config = &mut ctx.accounts.config;
+    let role_account = &ctx.accounts.role_account;
+    let entry = &mut ctx.accounts.allowlist_entry;
+
+    require!(
+        config.allowlist_mode,
+        StablecoinError::AllowlistModeDisabled
+    );
+    require!(
+        role_account.config == config.key(),
+        StablecoinError::Unauthorized
+    );
+    require!(
+        has_any_role(role_account.roles, ROLE_MASTER_AUTHORITY | ROLE_BLACKLISTER),
+        StablecoinError::Unauthorized
+    );
+    require!(
+        config.admin_ops_allowed(role_account.roles),
+        StablecoinError::SystemPaused
+    );
+    require!(entry.config == config.key(), StablecoinError::Unauthorized);
+
+    if !entry.is_active {
+        return err!(StablecoinError::NotAllowlisted);
+    }
+
+    entry.is_active = false;
+    config.record_action(ctx.accounts.blacklister.key(), Clock::get()?.unix_timestamp);
+
+    emit!(AllowlistRemoved {
+        config: config.key(),
+        wallet: entry.wallet,
+        removed_by: ctx.accounts.blacklister.key(),
+        timestamp: Clock::get()?.unix_timestamp,
+    });
+    Ok(())
+}
@@ -1,15 +1,21 @@
 use anchor_lang::prelude::*;
 
-use crate::constants::{MAX_REASON_LEN, ROLE_BLACKLISTER, ROLE_MASTER_AUTHORITY};
+use crate::constants::{
+    EVENT_SCHEMA_VERSION, MAX_REASON_LEN, ROLE_BLACKLISTER, ROLE_MASTER_AUTHORITY,
+};
 use crate::errors::StablecoinError;
-use crate::events::{BlacklistAdded, BlacklistRemoved};
-use crate::state::{BlacklistEntry, RoleAccount, StablecoinConfig};
+use crate::events::{BlacklistAdded, BlacklistEntryClosed, BlacklistRemoved, BlacklistUpdated};
+use crate::state::{BlacklistEntry, BlacklistReason, RoleAccount, StablecoinConfig};
 use crate::utils::has_any_role;
 
 #[derive(AnchorSerialize, AnchorDeserialize, Clone)]
 pub struct AddToBlacklistArgs {
     pub wallet: Pubkey,
     pub reason: String,
+    /// Unix time after which the entry stops blocking transfers.
+    pub expires_at: Option<i64>,
+    /// `BlacklistReason` code; 0 (unspecified) is rejected.
+    pub reason_code: u8,
 }
 
 #[derive(Accounts)]
@@ -41,6 +47,30 @@ pub struct AddToBlacklist<'info> {
     pub system_program: Program<'info, System>,
 }
 
+#[derive(AnchorSerialize, AnchorDeserialize, Clone)]
+pub struct UpdateBlacklistEntryArgs {
+    pub reason: String,
+    /// New `BlacklistReason` code; None keeps the current one.
+    pub reason_code: Option<u8>,
+}
+
+#[derive(Accounts)]
+pub struct UpdateBlacklistEntry<'info> {
+    pub blacklister: Signer<'info>,
+
+    #[account(mut)]
+    pub config: Account<'info, StablecoinConfig>,
+
+    #[account(
+        seeds = [b"role", config.key().as_ref(), blacklister.key().as_ref()],
+        bump = role_account.bump
+    )]
+    pub role_account: Account<'info, RoleAccount>,
+
+    #[account(mut)]
+    pub blacklist_entry: Account<'info, BlacklistEntry>,
+}
+
 #[derive(Accounts)]
 pub struct RemoveFromBlacklist<'info> {
     pub blacklister: Signer<'info>,
@@ -58,15 +88,36 @@ pub struct RemoveFromBlacklist<'info> {
     pub blacklist_entry: Account<'info, BlacklistEntry>,
 }
 
+#[derive(Accounts)]
+pub struct CloseBlacklistEntry<'info> {
+    #[account(mut)]
+    pub blacklister: Signer<'info>,
+
+    pub config: Account<'info, StablecoinConfig>,
+
+    #[account(
+        seeds = [b"role", config.key().as_ref(), blacklister.key().as_ref()],
+        bump = role_account.bump
+    )]
+    pub role_account: Account<'info, RoleAccount>,
+
+    #[account(mut, close = blacklister)]
+    pub blacklist_entry: Account<'info, BlacklistEntry>,
+}
+
 pub fn add_handler(ctx: Context<AddToBlacklist>, args: AddToBlacklistArgs) -> Result<()> {
-    let config = &ctx.accounts.config;
+    let config = &mut ctx.accounts.config;
     let role_account = &ctx.accounts.role_account;
     let entry = &mut ctx.accounts.blacklist_entry;
 
     require!(
-        config.features.transfer_hook,
+        config.features.transfer_hook(),
         StablecoinError::FeatureNotEnabled
     );
+    require!(
+        !config.allowlist_mode,
+        StablecoinError::AllowlistModeEnabled
+    );
     require!(
         role_account.config == config.key(),
         StablecoinError::Unauthorized
@@ -75,48 +126,124 @@ pub fn add_handler(ctx: Context<AddToBlacklist>, args: AddToBlacklistArgs) -> Re
         has_any_role(role_account.roles, ROLE_MASTER_AUTHORITY | ROLE_BLACKLISTER),
         StablecoinError::Unauthorized
     );
+    require!(
+        config.admin_ops_allowed(role_account.roles),
+        StablecoinError::SystemPaused
+    );
     require!(
         args.reason.len() <= MAX_REASON_LEN,
         StablecoinError::ReasonTooLong
     );
+    require!(
+        BlacklistReason::from_code(args.reason_code)
+            .is_some_and(|reason| reason != BlacklistReason::Unspecified),
+        StablecoinError::InvalidReasonCode
+    );
     require!(
         args.wallet == ctx.accounts.wallet.key(),
         StablecoinError::Unauthorized
     );
 
+    let now = Clock::get()?.unix_timestamp;
+    if let Some(expires_at) = args.expires_at {
+        require!(expires_at > now, StablecoinError::InvalidExpiry);
+    }
+
     if entry.config != Pubkey::default() {
         require!(entry.config == config.key(), StablecoinError::Unauthorized);
     }
 
-    if entry.is_active {
+    // A lapsed entry can be re-added without removing it first.
+    if entry.is_in_effect(now) {
         return err!(StablecoinError::AlreadyBlacklisted);
     }
 
     entry.config = config.key();
     entry.wallet = args.wallet;
-    entry.blacklisted_at = Clock::get()?.unix_timestamp;
+    entry.blacklisted_at = now;
     entry.blacklisted_by = ctx.accounts.blacklister.key();
     entry.reason = args.reason;
     entry.is_active = true;
     entry.bump = ctx.bumps.blacklist_entry;
+    entry.expires_at = args.expires_at;
+    entry.reason_code = args.reason_code;
+    config.record_action(ctx.accounts.blacklister.key(), now);
 
     emit!(BlacklistAdded {
         config: config.key(),
         wallet: entry.wallet,
         reason: entry.reason.clone(),
         blacklisted_by: ctx.accounts.blacklister.key(),
-        timestamp: Clock::get()?.unix_timestamp,
+        timestamp: now,
+        expires_at: entry.expires_at,
+        schema_version: EVENT_SCHEMA_VERSION,
+        reason_code: entry.reason_code,
+    });
+    Ok(())
+}
+
+/// Rewrites the reason and optionally the code of an active entry in place,
+/// so blacklisted_at and blacklisted_by survive the correction.
+pub fn update_handler(
+    ctx: Context<UpdateBlacklistEntry>,
+    args: UpdateBlacklistEntryArgs,
+) -> Result<()> {
+    let config = &mut ctx.accounts.config;
+    let role_account = &ctx.accounts.role_account;
+    let entry = &mut ctx.accounts.blacklist_entry;
+
+    require!(
+        config.features.transfer_hook(),
+        StablecoinError::FeatureNotEnabled
+    );
+    require!(
+        role_account.config == config.key(),
+        StablecoinError::Unauthorized
+    );
+    require!(
+        has_any_role(role_account.roles, ROLE_MASTER_AUTHORITY | ROLE_BLACKLISTER),
+        StablecoinError::Unauthorized
+    );
+    require!(
+        config.admin_ops_allowed(role_account.roles),
+        StablecoinError::SystemPaused
+    );
+    require!(entry.config == config.key(), StablecoinError::Unauthorized);
+    require!(entry.is_active, StablecoinError::NotBlacklisted);
+    require!(
+        args.reason.len() <= MAX_REASON_LEN,
+        StablecoinError::ReasonTooLong
+    );
+    if let Some(reason_code) = args.reason_code {
+        require!(
+            BlacklistReason::from_code(reason_code)
+                .is_some_and(|reason| reason != BlacklistReason::Unspecified),
+            StablecoinError::InvalidReasonCode
+        );
+        entry.reason_code = reason_code;
+    }
+    entry.reason = args.reason;
+    let now = Clock::get()?.unix_timestamp;
+    config.record_action(ctx.accounts.blacklister.key(), now);
+
+    emit!(BlacklistUpdated {
+        config: config.key(),
+        wallet: entry.wallet,
+        reason: entry.reason.clone(),
+        reason_code: entry.reason_code,
+        updated_by: ctx.accounts.blacklister.key(),
+        timestamp: now,
     });
     Ok(())
 }
 
 pub fn remove_handler(ctx: Context<RemoveFromBlacklist>) -> Result<()> {
-    let config = &ctx.accounts.config;
+    let config = &mut ctx.accounts.config;
     let role_account = &ctx.accounts.role_account;
     let entry = &mut ctx.accounts.blacklist_entry;
 
     require!(
-        config.features.transfer_hook,
+        config.features.transfer_hook(),
         StablecoinError::FeatureNotEnabled
     );
     require!(
@@ -127,6 +254,10 @@ pub fn remove_handler(ctx: Context<RemoveFromBlacklist>) -> Result<()> {
         has_any_role(role_account.roles, ROLE_MASTER_AUTHORITY | ROLE_BLACKLISTER),
         StablecoinError::Unauthorized
     );
+    require!(
+        config.admin_ops_allowed(role_account.roles),
+        StablecoinError::SystemPaused
+    );
     require!(entry.config == config.key(), StablecoinError::Unauthorized);
 
     if !entry.is_active {
@@ -134,6 +265,7 @@ pub fn remove_handler(ctx: Context<RemoveFromBlacklist>) -> Result<()> {
     }
 
     entry.is_active = false;
+    config.record_action(ctx.accounts.blacklister.key(), Clock::get()?.unix_timestamp);
 
     emit!(BlacklistRemoved {
         config: config.key(),
@@ -143,3 +275,37 @@ pub fn remove_handler(ctx: Context<RemoveFromBlacklist>) -> Result<()> {
     });
     Ok(())
 }
+
+/// Closes a lifted or expired entry and refunds its rent. The hook treats a
+/// missing entry as not blacklisted, so closing only frees the account.
+pub fn close_handler(ctx: Context<CloseBlacklistEntry>) -> Result<()> {
+    let config = &ctx.accounts.config;
+    let role_account = &ctx.accounts.role_account;
+    let entry = &ctx.accounts.blacklist_entry;
+
+    require!(
+        role_account.config == config.key(),
+        StablecoinError::Unauthorized
+    );
+    require!(
+        has_any_role(role_account.roles, ROLE_MASTER_AUTHORITY | ROLE_BLACKLISTER),
+        StablecoinError::Unauthorized
+    );
+    require!(
+        config.admin_ops_allowed(role_account.roles),
+        StablecoinError::SystemPaused
+    );
+    require!(entry.config == config.key(), StablecoinError::Unauthorized);
+    require!(
+        !entry.is_in_effect(Clock::get()?.unix_timestamp),
+        StablecoinError::BlacklistEntryActive
+    );
+
+    emit!(BlacklistEntryClosed {
+        config: config.key(),
+        wallet: entry.wallet,
+        closed_by: ctx.accounts.blacklister.key(),
+        timestamp: Clock::get()?.unix_timestamp,
+    });
+    Ok(())
+}
@@ -1,8 +1,8 @@
 use anchor_lang::prelude::*;
-use anchor_spl::token_2022::{self, Token2022};
-use anchor_spl::token_interface::{Mint, TokenAccount};
+use anchor_spl::token_2022;
+use anchor_spl::token_interface::{Mint, TokenAccount, TokenInterface};
 
-use crate::constants::{ROLE_BURNER, ROLE_MASTER_AUTHORITY};
+use crate::constants::{EVENT_SCHEMA_VERSION, PAUSE_BURN, ROLE_BURNER, ROLE_MASTER_AUTHORITY};
 use crate::errors::StablecoinError;
 use crate::events::TokensBurned;
 use crate::state::{RoleAccount, StablecoinConfig};
@@ -27,7 +27,8 @@ pub struct Burn<'info> {
     #[account(mut)]
     pub burner_ata: InterfaceAccount<'info, TokenAccount>,
 
-    pub token_2022_program: Program<'info, Token2022>,
+    #[account(address = config.token_program_id() @ StablecoinError::InvalidTokenProgram)]
+    pub token_program: Interface<'info, TokenInterface>,
 }
 
 pub fn handler(ctx: Context<Burn>, amount: u64) -> Result<()> {
@@ -36,7 +37,10 @@ pub fn handler(ctx: Context<Burn>, amount: u64) -> Result<()> {
     let mint = &ctx.accounts.mint;
     let burner_ata = &ctx.accounts.burner_ata;
 
-    require!(!config.is_paused, StablecoinError::SystemPaused);
+    require!(
+        !config.is_operation_paused(PAUSE_BURN),
+        StablecoinError::SystemPaused
+    );
     require!(
         role_account.config == config.key(),
         StablecoinError::Unauthorized
@@ -61,10 +65,7 @@ pub fn handler(ctx: Context<Burn>, amount: u64) -> Result<()> {
         from: burner_ata.to_account_info(),
         authority: ctx.accounts.burner.to_account_info(),
     };
-    let cpi_ctx = CpiContext::new(
-        ctx.accounts.token_2022_program.to_account_info(),
-        cpi_accounts,
-    );
+    let cpi_ctx = CpiContext::new(ctx.accounts.token_program.to_account_info(), cpi_accounts);
     token_2022::burn(cpi_ctx, amount)?;
 
     config.total_burned = config
@@ -75,6 +76,7 @@ pub fn handler(ctx: Context<Burn>, amount: u64) -> Result<()> {
         .audit_counter
         .checked_add(1)
         .ok_or(StablecoinError::Overflow)?;
+    config.record_action(ctx.accounts.burner.key(), Clock::get()?.unix_timestamp);
 
     let new_total_supply = mint
         .supply
@@ -88,6 +90,7 @@ pub fn handler(ctx: Context<Burn>, amount: u64) -> Result<()> {
         amount,
         new_total_supply,
         timestamp: Clock::get()?.unix_timestamp,
+        schema_version: EVENT_SCHEMA_VERSION,
     });
     Ok(())
 }
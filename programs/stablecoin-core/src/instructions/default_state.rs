@@ -0,0 +1,116 @@
+use anchor_lang::prelude::*;
+use anchor_lang::solana_program::program::invoke_signed;
+use anchor_spl::token_2022::spl_token_2022::{
+    extension::{
+        default_account_state::{self, DefaultAccountState},
+        BaseStateWithExtensions, StateWithExtensions,
+    },
+    state::{AccountState, Mint as Token2022Mint},
+};
+use anchor_spl::token_2022::Token2022;
+
+use crate::constants::ROLE_MASTER_AUTHORITY;
+use crate::errors::StablecoinError;
+use crate::events::DefaultAccountStateUpdated;
+use crate::state::{FeatureFlags, RoleAccount, StablecoinConfig};
+use crate::utils::has_any_role;
+
+#[derive(AnchorSerialize, AnchorDeserialize, Clone)]
+pub struct UpdateDefaultAccountStateArgs {
+    pub default_frozen: bool,
+}
+
+#[derive(Accounts)]
+pub struct UpdateDefaultAccountState<'info> {
+    pub authority: Signer<'info>,
+
+    #[account(mut)]
+    pub config: Account<'info, StablecoinConfig>,
+
+    #[account(
+        seeds = [b"role", config.key().as_ref(), authority.key().as_ref()],
+        bump = role_account.bump
+    )]
+    pub role_account: Account<'info, RoleAccount>,
+
+    /// CHECK: Verified against config.mint; owned and parsed by Token-2022.
+    #[account(mut)]
+    pub mint: UncheckedAccount<'info>,
+
+    pub token_2022_program: Program<'info, Token2022>,
+}
+
+/// Switches the state new token accounts are created in. Existing accounts
+/// keep their state; only the mint's DefaultAccountState extension changes,
+/// so it needs a mint initialized with default_account_frozen.
+pub fn handler(
+    ctx: Context<UpdateDefaultAccountState>,
+    args: UpdateDefaultAccountStateArgs,
+) -> Result<()> {
+    let config = &ctx.accounts.config;
+    let role_account = &ctx.accounts.role_account;
+    let mint = &ctx.accounts.mint;
+
+    require!(
+        role_account.config == config.key(),
+        StablecoinError::Unauthorized
+    );
+    require!(
+        has_any_role(role_account.roles, ROLE_MASTER_AUTHORITY),
+        StablecoinError::Unauthorized
+    );
+    require!(config.mint == mint.key(), StablecoinError::Unauthorized);
+    require!(
+        has_default_account_state(&mint.to_account_info())?,
+        StablecoinError::FeatureNotEnabled
+    );
+
+    let state = if args.default_frozen {
+        AccountState::Frozen
+    } else {
+        AccountState::Initialized
+    };
+    let mint_key = mint.key();
+    let update_ix = default_account_state::instruction::update_default_account_state(
+        &ctx.accounts.token_2022_program.key(),
+        &mint_key,
+        &config.key(),
+        &[],
+        &state,
+    )?;
+    let signer_seeds: &[&[u8]] = &[b"stablecoin", mint_key.as_ref(), &[config.bump]];
+    invoke_signed(
+        &update_ix,
+        &[
+            mint.to_account_info(),
+            config.to_account_info(),
+            ctx.accounts.token_2022_program.to_account_info(),
+        ],
+        &[signer_seeds],
+    )?;
+
+    let config = &mut ctx.accounts.config;
+    config
+        .features
+        .set(FeatureFlags::DEFAULT_FROZEN, args.default_frozen);
+    config.audit_counter = config
+        .audit_counter
+        .checked_add(1)
+        .ok_or(StablecoinError::Overflow)?;
+    config.record_action(ctx.accounts.authority.key(), Clock::get()?.unix_timestamp);
+
+    emit!(DefaultAccountStateUpdated {
+        config: config.key(),
+        default_frozen: args.default_frozen,
+        updated_by: ctx.accounts.authority.key(),
+        timestamp: Clock::get()?.unix_timestamp,
+    });
+
+    Ok(())
+}
+
+fn has_default_account_state(mint_info: &AccountInfo) -> Result<bool> {
+    let data = mint_info.try_borrow_data()?;
+    let mint = StateWithExtensions::<Token2022Mint>::unpack(&data)?;
+    Ok(mint.get_extension::<DefaultAccountState>().is_ok())
+}
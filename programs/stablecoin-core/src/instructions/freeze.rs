@@ -1,6 +1,6 @@
 use anchor_lang::prelude::*;
-use anchor_spl::token_2022::{self, Token2022};
-use anchor_spl::token_interface::{Mint, TokenAccount};
+use anchor_spl::token_2022;
+use anchor_spl::token_interface::{Mint, TokenAccount, TokenInterface};
 
 use crate::constants::{ROLE_FREEZER, ROLE_MASTER_AUTHORITY};
 use crate::errors::StablecoinError;
@@ -26,7 +26,8 @@ pub struct FreezeAccount<'info> {
     #[account(mut)]
     pub target_ata: InterfaceAccount<'info, TokenAccount>,
 
-    pub token_2022_program: Program<'info, Token2022>,
+    #[account(address = config.token_program_id() @ StablecoinError::InvalidTokenProgram)]
+    pub token_program: Interface<'info, TokenInterface>,
 }
 
 #[derive(Accounts)]
@@ -47,7 +48,8 @@ pub struct ThawAccount<'info> {
     #[account(mut)]
     pub target_ata: InterfaceAccount<'info, TokenAccount>,
 
-    pub token_2022_program: Program<'info, Token2022>,
+    #[account(address = config.token_program_id() @ StablecoinError::InvalidTokenProgram)]
+    pub token_program: Interface<'info, TokenInterface>,
 }
 
 pub fn freeze_handler(ctx: Context<FreezeAccount>) -> Result<()> {
@@ -63,6 +65,10 @@ pub fn freeze_handler(ctx: Context<FreezeAccount>) -> Result<()> {
         has_any_role(role_account.roles, ROLE_MASTER_AUTHORITY | ROLE_FREEZER),
         StablecoinError::Unauthorized
     );
+    require!(
+        config.admin_ops_allowed(role_account.roles),
+        StablecoinError::SystemPaused
+    );
     require!(config.mint == mint.key(), StablecoinError::Unauthorized);
     require!(
         ctx.accounts.target_ata.mint == mint.key(),
@@ -78,7 +84,7 @@ pub fn freeze_handler(ctx: Context<FreezeAccount>) -> Result<()> {
         authority: config.to_account_info(),
     };
     let cpi_ctx = CpiContext::new_with_signer(
-        ctx.accounts.token_2022_program.to_account_info(),
+        ctx.accounts.token_program.to_account_info(),
         cpi_accounts,
         &signer_seeds_arr,
     );
@@ -88,6 +94,7 @@ pub fn freeze_handler(ctx: Context<FreezeAccount>) -> Result<()> {
         .audit_counter
         .checked_add(1)
         .ok_or(StablecoinError::Overflow)?;
+    config.record_action(ctx.accounts.freezer.key(), Clock::get()?.unix_timestamp);
 
     emit!(AccountFrozen {
         config: config.key(),
@@ -111,6 +118,10 @@ pub fn thaw_handler(ctx: Context<ThawAccount>) -> Result<()> {
         has_any_role(role_account.roles, ROLE_MASTER_AUTHORITY | ROLE_FREEZER),
         StablecoinError::Unauthorized
     );
+    require!(
+        config.admin_ops_allowed(role_account.roles),
+        StablecoinError::SystemPaused
+    );
     require!(config.mint == mint.key(), StablecoinError::Unauthorized);
     require!(
         ctx.accounts.target_ata.mint == mint.key(),
@@ -126,7 +137,7 @@ pub fn thaw_handler(ctx: Context<ThawAccount>) -> Result<()> {
         authority: config.to_account_info(),
     };
     let cpi_ctx = CpiContext::new_with_signer(
-        ctx.accounts.token_2022_program.to_account_info(),
+        ctx.accounts.token_program.to_account_info(),
         cpi_accounts,
         &signer_seeds_arr,
     );
@@ -136,6 +147,7 @@ pub fn thaw_handler(ctx: Context<ThawAccount>) -> Result<()> {
         .audit_counter
         .checked_add(1)
         .ok_or(StablecoinError::Overflow)?;
+    config.record_action(ctx.accounts.freezer.key(), Clock::get()?.unix_timestamp);
 
     emit!(AccountThawed {
         config: config.key(),
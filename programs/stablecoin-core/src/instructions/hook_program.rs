@@ -0,0 +1,300 @@
+use anchor_lang::prelude::*;
+use anchor_lang::solana_program::program::{invoke, invoke_signed};
+use anchor_spl::token_2022::spl_token_2022::extension::{
+    transfer_hook::{self, TransferHook},
+    BaseStateWithExtensions, StateWithExtensions,
+};
+use anchor_spl::token_2022::spl_token_2022::state::Mint as Token2022Mint;
+use anchor_spl::token_2022::Token2022;
+use spl_transfer_hook_interface::get_extra_account_metas_address;
+
+use crate::constants::ROLE_MASTER_AUTHORITY;
+use crate::errors::StablecoinError;
+use crate::events::{TransferHookEnabled, TransferHookProgramUpdated};
+use crate::instructions::initialize::{
+    initialize_extra_account_metas_ix, require_expected_extra_metas,
+};
+use crate::state::{FeatureFlags, RoleAccount, StablecoinConfig};
+use crate::utils::has_any_role;
+
+#[derive(Accounts)]
+pub struct UpdateTransferHookProgram<'info> {
+    /// Pays for the new program's extra account metas.
+    #[account(mut)]
+    pub authority: Signer<'info>,
+
+    #[account(mut)]
+    pub config: Account<'info, StablecoinConfig>,
+
+    #[account(
+        seeds = [b"role", config.key().as_ref(), authority.key().as_ref()],
+        bump = role_account.bump
+    )]
+    pub role_account: Account<'info, RoleAccount>,
+
+    /// CHECK: Verified against config.mint; owned and parsed by Token-2022.
+    #[account(mut)]
+    pub mint: UncheckedAccount<'info>,
+
+    /// CHECK: Must be executable; it is invoked to set up its extra account metas.
+    pub new_transfer_hook_program: UncheckedAccount<'info>,
+
+    /// CHECK: Verified against the new program's extra account metas PDA.
+    #[account(mut)]
+    pub extra_metas_account: UncheckedAccount<'info>,
+
+    pub token_2022_program: Program<'info, Token2022>,
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct EnableTransferHook<'info> {
+    /// Pays for the hook program's extra account metas.
+    #[account(mut)]
+    pub authority: Signer<'info>,
+
+    #[account(mut)]
+    pub config: Account<'info, StablecoinConfig>,
+
+    #[account(
+        seeds = [b"role", config.key().as_ref(), authority.key().as_ref()],
+        bump = role_account.bump
+    )]
+    pub role_account: Account<'info, RoleAccount>,
+
+    /// CHECK: Verified against config.mint; owned and parsed by Token-2022.
+    #[account(mut)]
+    pub mint: UncheckedAccount<'info>,
+
+    /// CHECK: Must be executable; it is invoked to set up its extra account metas.
+    pub transfer_hook_program: UncheckedAccount<'info>,
+
+    /// CHECK: Verified against the program's extra account metas PDA.
+    #[account(mut)]
+    pub extra_metas_account: UncheckedAccount<'info>,
+
+    pub token_2022_program: Program<'info, Token2022>,
+    pub system_program: Program<'info, System>,
+}
+
+/// Repoints the mint's TransferHook extension and the config at a new hook
+/// program, first creating that program's extra account metas for the mint.
+/// A list that already exists must match the one this config would create.
+pub fn handler(ctx: Context<UpdateTransferHookProgram>) -> Result<()> {
+    let config = &ctx.accounts.config;
+    let role_account = &ctx.accounts.role_account;
+    let mint = &ctx.accounts.mint;
+    let new_program = &ctx.accounts.new_transfer_hook_program;
+
+    require!(
+        role_account.config == config.key(),
+        StablecoinError::Unauthorized
+    );
+    require!(
+        has_any_role(role_account.roles, ROLE_MASTER_AUTHORITY),
+        StablecoinError::Unauthorized
+    );
+    require!(
+        config.features.transfer_hook(),
+        StablecoinError::FeatureNotEnabled
+    );
+    require!(config.mint == mint.key(), StablecoinError::Unauthorized);
+    require!(
+        new_program.executable && config.transfer_hook_program != Some(new_program.key()),
+        StablecoinError::InvalidTransferHookProgram
+    );
+
+    let mint_key = mint.key();
+    let new_program_id = new_program.key();
+    let extra_metas_account = &ctx.accounts.extra_metas_account;
+    require!(
+        extra_metas_account.key() == get_extra_account_metas_address(&mint_key, &new_program_id),
+        StablecoinError::InvalidExtraAccountMetas
+    );
+
+    if extra_metas_account.owner != &new_program_id {
+        let extra_metas_ix = initialize_extra_account_metas_ix(
+            &new_program_id,
+            &mint_key,
+            &ctx.accounts.authority.key(),
+            config.allowlist_mode,
+            config.features.transfer_cooldown(),
+        )?;
+        invoke(
+            &extra_metas_ix,
+            &[
+                extra_metas_account.to_account_info(),
+                mint.to_account_info(),
+                ctx.accounts.authority.to_account_info(),
+                ctx.accounts.system_program.to_account_info(),
+                new_program.to_account_info(),
+            ],
+        )?;
+    } else {
+        require_expected_extra_metas(
+            extra_metas_account,
+            &new_program_id,
+            config.allowlist_mode,
+            config.features.transfer_cooldown(),
+        )?;
+    }
+
+    let update_ix = transfer_hook::instruction::update(
+        &ctx.accounts.token_2022_program.key(),
+        &mint_key,
+        &config.key(),
+        &[],
+        Some(new_program_id),
+    )?;
+    let signer_seeds: &[&[u8]] = &[b"stablecoin", mint_key.as_ref(), &[config.bump]];
+    invoke_signed(
+        &update_ix,
+        &[
+            mint.to_account_info(),
+            config.to_account_info(),
+            ctx.accounts.token_2022_program.to_account_info(),
+        ],
+        &[signer_seeds],
+    )?;
+
+    let config = &mut ctx.accounts.config;
+    let previous_program = config.transfer_hook_program;
+    config.transfer_hook_program = Some(new_program_id);
+    config.audit_counter = config
+        .audit_counter
+        .checked_add(1)
+        .ok_or(StablecoinError::Overflow)?;
+    config.record_action(ctx.accounts.authority.key(), Clock::get()?.unix_timestamp);
+
+    emit!(TransferHookProgramUpdated {
+        config: config.key(),
+        previous_program,
+        new_program: new_program_id,
+        updated_by: ctx.accounts.authority.key(),
+        timestamp: Clock::get()?.unix_timestamp,
+    });
+
+    Ok(())
+}
+
+/// Turns on blacklist enforcement for a mint that was initialized without a
+/// hook program. Token-2022 extensions cannot be added after initialize, so
+/// the mint must already carry a TransferHook extension with the config PDA
+/// as authority and no program set, as initialize's reserve_transfer_hook
+/// leaves it.
+pub fn enable_handler(ctx: Context<EnableTransferHook>) -> Result<()> {
+    let config = &ctx.accounts.config;
+    let role_account = &ctx.accounts.role_account;
+    let mint = &ctx.accounts.mint;
+    let hook_program = &ctx.accounts.transfer_hook_program;
+
+    require!(
+        role_account.config == config.key(),
+        StablecoinError::Unauthorized
+    );
+    require!(
+        has_any_role(role_account.roles, ROLE_MASTER_AUTHORITY),
+        StablecoinError::Unauthorized
+    );
+    require!(config.mint == mint.key(), StablecoinError::Unauthorized);
+    require!(
+        config.token_program_id() == ctx.accounts.token_2022_program.key(),
+        StablecoinError::Token2022Required
+    );
+    require!(
+        !config.features.transfer_hook() && config.transfer_hook_program.is_none(),
+        StablecoinError::TransferHookAlreadyEnabled
+    );
+    require!(
+        hook_program.executable,
+        StablecoinError::InvalidTransferHookProgram
+    );
+    {
+        let data = mint.try_borrow_data()?;
+        let state = StateWithExtensions::<Token2022Mint>::unpack(&data)?;
+        let extension = state
+            .get_extension::<TransferHook>()
+            .map_err(|_| StablecoinError::FeatureNotEnabled)?;
+        require!(
+            Option::<Pubkey>::from(extension.program_id).is_none(),
+            StablecoinError::TransferHookAlreadyEnabled
+        );
+        require!(
+            Option::<Pubkey>::from(extension.authority) == Some(config.key()),
+            StablecoinError::Unauthorized
+        );
+    }
+
+    let mint_key = mint.key();
+    let hook_program_id = hook_program.key();
+    let extra_metas_account = &ctx.accounts.extra_metas_account;
+    require!(
+        extra_metas_account.key() == get_extra_account_metas_address(&mint_key, &hook_program_id),
+        StablecoinError::InvalidExtraAccountMetas
+    );
+
+    // The list may already have been created on its own, e.g. with
+    // sss-token init-extra-metas, but is only kept if it matches.
+    if extra_metas_account.owner != &hook_program_id {
+        let extra_metas_ix = initialize_extra_account_metas_ix(
+            &hook_program_id,
+            &mint_key,
+            &ctx.accounts.authority.key(),
+            config.allowlist_mode,
+            config.features.transfer_cooldown(),
+        )?;
+        invoke(
+            &extra_metas_ix,
+            &[
+                extra_metas_account.to_account_info(),
+                mint.to_account_info(),
+                ctx.accounts.authority.to_account_info(),
+                ctx.accounts.system_program.to_account_info(),
+                hook_program.to_account_info(),
+            ],
+        )?;
+    } else {
+        require_expected_extra_metas(
+            extra_metas_account,
+            &hook_program_id,
+            config.allowlist_mode,
+            config.features.transfer_cooldown(),
+        )?;
+    }
+
+    let update_ix = transfer_hook::instruction::update(
+        &ctx.accounts.token_2022_program.key(),
+        &mint_key,
+        &config.key(),
+        &[],
+        Some(hook_program_id),
+    )?;
+    let signer_seeds: &[&[u8]] = &[b"stablecoin", mint_key.as_ref(), &[config.bump]];
+    invoke_signed(
+        &update_ix,
+        &[
+            mint.to_account_info(),
+            config.to_account_info(),
+            ctx.accounts.token_2022_program.to_account_info(),
+        ],
+        &[signer_seeds],
+    )?;
+
+    let config = &mut ctx.accounts.config;
+    config.features.set(FeatureFlags::TRANSFER_HOOK, true);
+    config.transfer_hook_program = Some(hook_program_id);
+    config.audit_counter = config
+        .audit_counter
+        .checked_add(1)
+        .ok_or(StablecoinError::Overflow)?;
+    config.record_action(ctx.accounts.authority.key(), Clock::get()?.unix_timestamp);
+
+    emit!(TransferHookEnabled {
+        config: config.key(),
+        program: hook_program_id,
+        enabled_by: ctx.accounts.authority.key(),
+        timestamp: Clock::get()?.unix_timestamp,
+    });
+
+    Ok(())
+}
@@ -3,20 +3,31 @@ use anchor_lang::solana_program::instruction::{AccountMeta, Instruction};
 use anchor_lang::solana_program::program::invoke;
 use anchor_lang::system_program;
 use anchor_spl::token_2022::spl_token_2022::{
-    extension::{default_account_state, transfer_hook, ExtensionType},
+    extension::{
+        confidential_transfer, default_account_state, metadata_pointer, transfer_hook,
+        ExtensionType,
+    },
     instruction as token_2022_instruction,
     state::{AccountState, Mint as Token2022Mint},
 };
-use anchor_spl::token_2022::Token2022;
+use anchor_spl::token_2022_extensions::spl_pod::optional_keys::OptionalNonZeroPubkey;
+use anchor_spl::token_2022_extensions::spl_token_metadata_interface::state::TokenMetadata;
+use anchor_spl::token_2022_extensions::{token_metadata_initialize, TokenMetadataInitialize};
+use anchor_spl::token_interface::TokenInterface;
 use spl_tlv_account_resolution::account::ExtraAccountMeta;
 use spl_tlv_account_resolution::seeds::Seed;
+use spl_tlv_account_resolution::state::ExtraAccountMetaList;
 use spl_transfer_hook_interface::get_extra_account_metas_address;
-use spl_transfer_hook_interface::instruction::TransferHookInstruction;
+use spl_transfer_hook_interface::instruction::{ExecuteInstruction, TransferHookInstruction};
 
-use crate::constants::{MAX_NAME_LEN, MAX_SYMBOL_LEN, MAX_URI_LEN, ROLE_MASTER_AUTHORITY};
+use crate::constants::{
+    MAX_DECIMALS, MAX_NAME_LEN, MAX_SYMBOL_LEN, MAX_URI_LEN, MINT_QUOTA_WINDOW_SECONDS,
+    ROLE_MASTER_AUTHORITY,
+};
 use crate::errors::StablecoinError;
 use crate::events::StablecoinInitialized;
-use crate::state::{FeatureFlags, RoleAccount, StablecoinConfig};
+use crate::instructions::mint::require_allowed_recipient;
+use crate::state::{FeatureFlags, RoleAccount, StablecoinConfig, TokenProgram};
 
 const SOURCE_TOKEN_ACCOUNT_INDEX: u8 = 0;
 const MINT_ACCOUNT_INDEX: u8 = 1;
@@ -25,6 +36,8 @@ const CORE_PROGRAM_INDEX: u8 = 5;
 const CONFIG_ACCOUNT_INDEX: u8 = 6;
 const TOKEN_ACCOUNT_OWNER_OFFSET: u8 = 32;
 const TOKEN_ACCOUNT_OWNER_LENGTH: u8 = 32;
+/// Seed of the hook-owned LastTransfer PDA, ["last-transfer", config, owner].
+pub const LAST_TRANSFER_SEED: &[u8] = b"last-transfer";
 
 #[derive(AnchorSerialize, AnchorDeserialize, Clone)]
 pub struct InitializeArgs {
@@ -36,6 +49,25 @@ pub struct InitializeArgs {
     pub enable_transfer_hook: bool,
     pub default_account_frozen: bool,
     pub transfer_hook_program: Option<Pubkey>,
+    pub pause_cooldown: i64,
+    pub enable_metadata: bool,
+    pub max_supply: Option<u64>,
+    pub enable_allowlist: bool,
+    /// Adds the ConfidentialTransferMint extension (SSS-3).
+    pub enable_confidential: bool,
+    /// Wallet owning the treasury token account, if any.
+    pub treasury: Option<Pubkey>,
+    /// Legacy mints carry no extensions, so every feature flag above must be
+    /// off for them.
+    pub token_program: TokenProgram,
+    /// Minimum seconds between two transfers sent by one owner, enforced by
+    /// the transfer hook. Needs enable_transfer_hook; None leaves transfers
+    /// unthrottled and the hook writes nothing.
+    pub transfer_cooldown_seconds: Option<i64>,
+    /// Adds a TransferHook extension with no program, owned by the config
+    /// PDA, so enable_transfer_hook can turn the hook on later. Ignored when
+    /// enable_transfer_hook is set.
+    pub reserve_transfer_hook: bool,
 }
 
 #[derive(Accounts)]
@@ -71,7 +103,7 @@ pub struct Initialize<'info> {
     /// CHECK: Optional transfer hook program validated by args when enabled.
     pub transfer_hook_program: Option<UncheckedAccount<'info>>,
 
-    pub token_2022_program: Program<'info, Token2022>,
+    pub token_program: Interface<'info, TokenInterface>,
 
     pub system_program: Program<'info, System>,
 
@@ -88,6 +120,14 @@ pub fn handler(ctx: Context<Initialize>, args: InitializeArgs) -> Result<()> {
         StablecoinError::SymbolTooLong
     );
     require!(args.uri.len() <= MAX_URI_LEN, StablecoinError::UriTooLong);
+    require!(
+        args.decimals <= MAX_DECIMALS,
+        StablecoinError::InvalidDecimals
+    );
+    require!(
+        args.pause_cooldown >= 0,
+        StablecoinError::InvalidPauseCooldown
+    );
 
     if args.enable_transfer_hook {
         require!(
@@ -95,25 +135,79 @@ pub fn handler(ctx: Context<Initialize>, args: InitializeArgs) -> Result<()> {
             StablecoinError::InvalidTransferHookProgram
         );
     }
+    require!(
+        !args.enable_allowlist || args.enable_transfer_hook,
+        StablecoinError::AllowlistRequiresTransferHook
+    );
+    if let Some(cooldown) = args.transfer_cooldown_seconds {
+        require!(
+            cooldown > 0 && args.enable_transfer_hook,
+            StablecoinError::InvalidTransferCooldown
+        );
+    }
+    require!(
+        ctx.accounts.token_program.key() == args.token_program.id(),
+        StablecoinError::InvalidTokenProgram
+    );
+    let legacy = args.token_program == TokenProgram::Legacy;
+    require!(
+        !legacy
+            || !(args.enable_metadata
+                || args.enable_permanent_delegate
+                || args.enable_transfer_hook
+                || args.reserve_transfer_hook
+                || args.default_account_frozen
+                || args.enable_confidential),
+        StablecoinError::Token2022Required
+    );
 
     let mint_key = ctx.accounts.mint.key();
-    let token_program_id = ctx.accounts.token_2022_program.key();
+    let token_program_id = ctx.accounts.token_program.key();
     let config_key = ctx.accounts.config.key();
     let config_bump = ctx.bumps.config;
 
-    let mut extensions = vec![ExtensionType::MintCloseAuthority];
+    // A legacy mint is the bare 82-byte layout: no close authority either.
+    let mut extensions = vec![];
+    if !legacy {
+        extensions.push(ExtensionType::MintCloseAuthority);
+    }
+    if args.enable_metadata {
+        extensions.push(ExtensionType::MetadataPointer);
+    }
     if args.enable_permanent_delegate {
         extensions.push(ExtensionType::PermanentDelegate);
     }
-    if args.enable_transfer_hook {
+    if args.enable_transfer_hook || args.reserve_transfer_hook {
         extensions.push(ExtensionType::TransferHook);
     }
     if args.default_account_frozen {
         extensions.push(ExtensionType::DefaultAccountState);
     }
+    if args.enable_confidential {
+        extensions.push(ExtensionType::ConfidentialTransferMint);
+    }
+
+    if let Some(treasury) = args.treasury {
+        require_allowed_recipient(&treasury, &config_key, &mint_key)?;
+    }
 
     let base_len = ExtensionType::try_calculate_account_len::<Token2022Mint>(&extensions)?;
-    let lamports = Rent::get()?.minimum_balance(base_len);
+    // Token metadata is reallocated into the mint after initialize_mint2, so the
+    // account is funded up front for its final size.
+    let metadata_len = if args.enable_metadata {
+        TokenMetadata {
+            update_authority: OptionalNonZeroPubkey(config_key),
+            mint: mint_key,
+            name: args.name.clone(),
+            symbol: args.symbol.clone(),
+            uri: args.uri.clone(),
+            additional_metadata: vec![],
+        }
+        .tlv_size_of()?
+    } else {
+        0
+    };
+    let lamports = Rent::get()?.minimum_balance(base_len + metadata_len);
     let create_accounts = system_program::CreateAccount {
         from: ctx.accounts.authority.to_account_info(),
         to: ctx.accounts.mint.to_account_info(),
@@ -125,15 +219,30 @@ pub fn handler(ctx: Context<Initialize>, args: InitializeArgs) -> Result<()> {
     system_program::create_account(create_ctx, lamports, base_len as u64, &token_program_id)?;
 
     let mint_info = ctx.accounts.mint.to_account_info();
-    let token_program_info = ctx.accounts.token_2022_program.to_account_info();
+    let token_program_info = ctx.accounts.token_program.to_account_info();
     let authority_info = ctx.accounts.authority.to_account_info();
 
-    let close_ix = token_2022_instruction::initialize_mint_close_authority(
-        &token_program_id,
-        &mint_key,
-        Some(&config_key),
-    )?;
-    invoke(&close_ix, &[mint_info.clone(), token_program_info.clone()])?;
+    if !legacy {
+        let close_ix = token_2022_instruction::initialize_mint_close_authority(
+            &token_program_id,
+            &mint_key,
+            Some(&config_key),
+        )?;
+        invoke(&close_ix, &[mint_info.clone(), token_program_info.clone()])?;
+    }
+
+    if args.enable_metadata {
+        let pointer_ix = metadata_pointer::instruction::initialize(
+            &token_program_id,
+            &mint_key,
+            Some(config_key),
+            Some(mint_key),
+        )?;
+        invoke(
+            &pointer_ix,
+            &[mint_info.clone(), token_program_info.clone()],
+        )?;
+    }
 
     if args.enable_permanent_delegate {
         let delegate_ix = token_2022_instruction::initialize_permanent_delegate(
@@ -147,13 +256,17 @@ pub fn handler(ctx: Context<Initialize>, args: InitializeArgs) -> Result<()> {
         )?;
     }
 
-    if args.enable_transfer_hook {
-        let hook_program_id = args.transfer_hook_program.unwrap();
+    if args.enable_transfer_hook || args.reserve_transfer_hook {
+        let hook_program_id = if args.enable_transfer_hook {
+            args.transfer_hook_program
+        } else {
+            None
+        };
         let hook_ix = transfer_hook::instruction::initialize(
             &token_program_id,
             &mint_key,
             Some(config_key),
-            Some(hook_program_id),
+            hook_program_id,
         )?;
         invoke(&hook_ix, &[mint_info.clone(), token_program_info.clone()])?;
     }
@@ -171,6 +284,22 @@ pub fn handler(ctx: Context<Initialize>, args: InitializeArgs) -> Result<()> {
         )?;
     }
 
+    if args.enable_confidential {
+        // Accounts are approved on configure so holders need no extra step;
+        // the config PDA can still change that later. No auditor key is set.
+        let confidential_ix = confidential_transfer::instruction::initialize_mint(
+            &token_program_id,
+            &mint_key,
+            Some(config_key),
+            true,
+            None,
+        )?;
+        invoke(
+            &confidential_ix,
+            &[mint_info.clone(), token_program_info.clone()],
+        )?;
+    }
+
     let mint_ix = token_2022_instruction::initialize_mint2(
         &token_program_id,
         &mint_key,
@@ -180,6 +309,27 @@ pub fn handler(ctx: Context<Initialize>, args: InitializeArgs) -> Result<()> {
     )?;
     invoke(&mint_ix, &[mint_info.clone(), token_program_info.clone()])?;
 
+    if args.enable_metadata {
+        let config_info = ctx.accounts.config.to_account_info();
+        let signer_seeds: &[&[&[u8]]] = &[&[b"stablecoin", mint_key.as_ref(), &[config_bump]]];
+        token_metadata_initialize(
+            CpiContext::new_with_signer(
+                token_program_info.clone(),
+                TokenMetadataInitialize {
+                    token_program_id: token_program_info.clone(),
+                    metadata: mint_info.clone(),
+                    update_authority: config_info.clone(),
+                    mint_authority: config_info,
+                    mint: mint_info.clone(),
+                },
+                signer_seeds,
+            ),
+            args.name.clone(),
+            args.symbol.clone(),
+            args.uri.clone(),
+        )?;
+    }
+
     let config = &mut ctx.accounts.config;
     config.authority = ctx.accounts.authority.key();
     config.mint = mint_key;
@@ -187,22 +337,45 @@ pub fn handler(ctx: Context<Initialize>, args: InitializeArgs) -> Result<()> {
     config.symbol = args.symbol;
     config.uri = args.uri;
     config.decimals = args.decimals;
-    config.is_paused = false;
+    config.pause_flags = 0;
     config.total_minted = 0;
     config.total_burned = 0;
     config.audit_counter = 0;
-    config.features = FeatureFlags {
-        permanent_delegate: args.enable_permanent_delegate,
-        transfer_hook: args.enable_transfer_hook,
-        confidential: false,
-        default_frozen: args.default_account_frozen,
-    };
+    config.features = FeatureFlags::new(
+        args.enable_permanent_delegate,
+        args.enable_transfer_hook,
+        args.enable_confidential,
+        args.default_account_frozen,
+    );
+    config.features.set(
+        FeatureFlags::TRANSFER_COOLDOWN,
+        args.transfer_cooldown_seconds.is_some(),
+    );
     config.transfer_hook_program = if args.enable_transfer_hook {
         args.transfer_hook_program
     } else {
         None
     };
     config.bump = config_bump;
+    config.pause_cooldown = args.pause_cooldown;
+    config.last_pause_change = 0;
+    config.max_supply = args.max_supply;
+    config.creator = ctx.accounts.authority.key();
+    config.created_at = Clock::get()?.unix_timestamp;
+    config.quota_window_seconds = MINT_QUOTA_WINDOW_SECONDS;
+    config.allowlist_mode = args.enable_allowlist;
+    config.max_transfer_amount = None;
+    config.allow_self_transfer = false;
+    config.total_seized = 0;
+    config.treasury = args.treasury;
+    config.lock_admin_ops = false;
+    config.minting_locked = false;
+    config.token_program = token_program_id;
+    config.transfer_cooldown_seconds = args.transfer_cooldown_seconds;
+    config.require_mint_approval = false;
+    let created_at = config.created_at;
+    config.record_action(ctx.accounts.authority.key(), created_at);
+    config.require_fits(config.to_account_info().data_len())?;
 
     let role_account = &mut ctx.accounts.role_account;
     role_account.config = config.key();
@@ -211,6 +384,9 @@ pub fn handler(ctx: Context<Initialize>, args: InitializeArgs) -> Result<()> {
     role_account.mint_quota = None;
     role_account.minted_current_window = 0;
     role_account.window_start = 0;
+    role_account.lifetime_minted = 0;
+    role_account.window_resets = 0;
+    role_account.lifetime_quota = None;
     role_account.bump = ctx.bumps.role_account;
 
     if args.enable_transfer_hook {
@@ -236,20 +412,13 @@ pub fn handler(ctx: Context<Initialize>, args: InitializeArgs) -> Result<()> {
             StablecoinError::InvalidExtraAccountMetas
         );
 
-        let extra_account_metas = build_extra_account_metas(&hook_program_account.key())?;
-        let extra_metas_ix = Instruction {
-            program_id: hook_program_account.key(),
-            accounts: vec![
-                AccountMeta::new(expected_extra_metas, false),
-                AccountMeta::new_readonly(mint_key, false),
-                AccountMeta::new(authority_info.key(), true),
-                AccountMeta::new_readonly(system_program::ID, false),
-            ],
-            data: TransferHookInstruction::InitializeExtraAccountMetaList {
-                extra_account_metas,
-            }
-            .pack(),
-        };
+        let extra_metas_ix = initialize_extra_account_metas_ix(
+            &hook_program_account.key(),
+            &mint_key,
+            &authority_info.key(),
+            args.enable_allowlist,
+            args.transfer_cooldown_seconds.is_some(),
+        )?;
         invoke(
             &extra_metas_ix,
             &[
@@ -261,7 +430,9 @@ pub fn handler(ctx: Context<Initialize>, args: InitializeArgs) -> Result<()> {
         )?;
     }
 
-    let preset = if args.enable_transfer_hook {
+    let preset = if args.enable_confidential {
+        "SSS-3"
+    } else if args.enable_transfer_hook {
         "SSS-2"
     } else {
         "SSS-1"
@@ -274,13 +445,75 @@ pub fn handler(ctx: Context<Initialize>, args: InitializeArgs) -> Result<()> {
         name: config.name.clone(),
         symbol: config.symbol.clone(),
         preset: preset.to_string(),
-        timestamp: Clock::get()?.unix_timestamp,
+        timestamp: config.created_at,
     });
 
     Ok(())
 }
 
-fn build_extra_account_metas(hook_program_id: &Pubkey) -> Result<Vec<ExtraAccountMeta>> {
+/// The hook's InitializeExtraAccountMetaList for `mint`, funded by `payer`.
+pub fn initialize_extra_account_metas_ix(
+    hook_program_id: &Pubkey,
+    mint: &Pubkey,
+    payer: &Pubkey,
+    allowlist_mode: bool,
+    transfer_cooldown: bool,
+) -> Result<Instruction> {
+    let extra_account_metas =
+        build_extra_account_metas(hook_program_id, allowlist_mode, transfer_cooldown)?;
+    Ok(Instruction {
+        program_id: *hook_program_id,
+        accounts: vec![
+            AccountMeta::new(
+                get_extra_account_metas_address(mint, hook_program_id),
+                false,
+            ),
+            AccountMeta::new_readonly(*mint, false),
+            AccountMeta::new(*payer, true),
+            AccountMeta::new_readonly(system_program::ID, false),
+        ],
+        data: TransferHookInstruction::InitializeExtraAccountMetaList {
+            extra_account_metas,
+        }
+        .pack(),
+    })
+}
+
+/// Fails unless an extra account metas list that already exists holds exactly
+/// the metas this config would have created, so a list written by anyone else
+/// is never adopted as is.
+pub fn require_expected_extra_metas(
+    extra_metas_account: &AccountInfo,
+    hook_program_id: &Pubkey,
+    allowlist_mode: bool,
+    transfer_cooldown: bool,
+) -> Result<()> {
+    let extra_account_metas =
+        build_extra_account_metas(hook_program_id, allowlist_mode, transfer_cooldown)?;
+    let mut expected = vec![0u8; ExtraAccountMetaList::size_of(extra_account_metas.len())?];
+    ExtraAccountMetaList::init::<ExecuteInstruction>(&mut expected, &extra_account_metas)?;
+    let data = extra_metas_account.try_borrow_data()?;
+    require!(
+        data.get(..expected.len()) == Some(&expected[..]),
+        StablecoinError::InvalidExtraAccountMetas
+    );
+    Ok(())
+}
+
+/// The source and destination entry slots resolve to allowlist PDAs in
+/// allowlist mode and to blacklist PDAs otherwise, so a mint enforces one list
+/// or the other but never both. With a transfer cooldown the source owner's
+/// LastTransfer PDA, owned by the hook, follows as a writable account.
+fn build_extra_account_metas(
+    hook_program_id: &Pubkey,
+    allowlist_mode: bool,
+    transfer_cooldown: bool,
+) -> Result<Vec<ExtraAccountMeta>> {
+    let entry_seed: &[u8] = if allowlist_mode {
+        b"allowlist"
+    } else {
+        b"blacklist"
+    };
     let core_program_meta = ExtraAccountMeta::new_with_pubkey(&crate::ID, false, false)?;
     let config_meta = ExtraAccountMeta::new_external_pda_with_seeds(
         CORE_PROGRAM_INDEX,
@@ -295,11 +528,11 @@ fn build_extra_account_metas(hook_program_id: &Pubkey) -> Result<Vec<ExtraAccoun
         false,
         false,
     )?;
-    let source_blacklist_meta = ExtraAccountMeta::new_external_pda_with_seeds(
+    let source_entry_meta = ExtraAccountMeta::new_external_pda_with_seeds(
         CORE_PROGRAM_INDEX,
         &[
             Seed::Literal {
-                bytes: b"blacklist".to_vec(),
+                bytes: entry_seed.to_vec(),
             },
             Seed::AccountKey {
                 index: CONFIG_ACCOUNT_INDEX,
@@ -313,11 +546,11 @@ fn build_extra_account_metas(hook_program_id: &Pubkey) -> Result<Vec<ExtraAccoun
         false,
         false,
     )?;
-    let destination_blacklist_meta = ExtraAccountMeta::new_external_pda_with_seeds(
+    let destination_entry_meta = ExtraAccountMeta::new_external_pda_with_seeds(
         CORE_PROGRAM_INDEX,
         &[
             Seed::Literal {
-                bytes: b"blacklist".to_vec(),
+                bytes: entry_seed.to_vec(),
             },
             Seed::AccountKey {
                 index: CONFIG_ACCOUNT_INDEX,
@@ -334,11 +567,31 @@ fn build_extra_account_metas(hook_program_id: &Pubkey) -> Result<Vec<ExtraAccoun
 
     let hook_program_meta = ExtraAccountMeta::new_with_pubkey(hook_program_id, false, false)?;
 
-    Ok(vec![
+    let mut metas = vec![
         core_program_meta,
         config_meta,
-        source_blacklist_meta,
-        destination_blacklist_meta,
+        source_entry_meta,
+        destination_entry_meta,
         hook_program_meta,
-    ])
+    ];
+    if transfer_cooldown {
+        metas.push(ExtraAccountMeta::new_with_seeds(
+            &[
+                Seed::Literal {
+                    bytes: LAST_TRANSFER_SEED.to_vec(),
+                },
+                Seed::AccountKey {
+                    index: CONFIG_ACCOUNT_INDEX,
+                },
+                Seed::AccountData {
+                    account_index: SOURCE_TOKEN_ACCOUNT_INDEX,
+                    data_index: TOKEN_ACCOUNT_OWNER_OFFSET,
+                    length: TOKEN_ACCOUNT_OWNER_LENGTH,
+                },
+            ],
+            false,
+            true,
+        )?);
+    }
+    Ok(metas)
 }
@@ -0,0 +1,306 @@
+use anchor_lang::prelude::*;
+use anchor_lang::system_program;
+use anchor_spl::token_2022::spl_token_2022::{
+    extension::{BaseStateWithExtensions, StateWithExtensions},
+    state::Mint as Token2022Mint,
+};
+use anchor_spl::token_2022::Token2022;
+use anchor_spl::token_2022_extensions::spl_token_metadata_interface::state::{
+    Field, TokenMetadata,
+};
+use anchor_spl::token_2022_extensions::{token_metadata_update_field, TokenMetadataUpdateField};
+
+use crate::constants::{
+    MAX_METADATA_KEY_LEN, MAX_METADATA_VALUE_LEN, MAX_NAME_LEN, MAX_SYMBOL_LEN, MAX_URI_LEN,
+    ROLE_MASTER_AUTHORITY,
+};
+use crate::errors::StablecoinError;
+use crate::events::{MetadataFieldUpdated, MetadataUpdated};
+use crate::state::{RoleAccount, StablecoinConfig};
+use crate::utils::has_any_role;
+
+#[derive(AnchorSerialize, AnchorDeserialize, Clone)]
+pub struct UpdateMetadataArgs {
+    pub name: Option<String>,
+    pub symbol: Option<String>,
+    pub uri: Option<String>,
+}
+
+#[derive(Accounts)]
+pub struct UpdateMetadata<'info> {
+    /// Pays for any mint reallocation when the metadata grows.
+    #[account(mut)]
+    pub authority: Signer<'info>,
+
+    #[account(mut)]
+    pub config: Account<'info, StablecoinConfig>,
+
+    #[account(
+        seeds = [b"role", config.key().as_ref(), authority.key().as_ref()],
+        bump = role_account.bump
+    )]
+    pub role_account: Account<'info, RoleAccount>,
+
+    /// CHECK: Verified against config.mint; owned and parsed by Token-2022.
+    #[account(mut)]
+    pub mint: UncheckedAccount<'info>,
+
+    pub token_2022_program: Program<'info, Token2022>,
+    pub system_program: Program<'info, System>,
+}
+
+/// Sets one `additional_metadata` entry, e.g. an attestation URL.
+#[derive(AnchorSerialize, AnchorDeserialize, Clone)]
+pub struct UpdateMetadataFieldArgs {
+    pub key: String,
+    pub value: String,
+}
+
+#[derive(Accounts)]
+pub struct UpdateMetadataField<'info> {
+    /// Pays for any mint reallocation when the metadata grows.
+    #[account(mut)]
+    pub authority: Signer<'info>,
+
+    #[account(mut)]
+    pub config: Account<'info, StablecoinConfig>,
+
+    #[account(
+        seeds = [b"role", config.key().as_ref(), authority.key().as_ref()],
+        bump = role_account.bump
+    )]
+    pub role_account: Account<'info, RoleAccount>,
+
+    /// CHECK: Verified against config.mint; owned and parsed by Token-2022.
+    #[account(mut)]
+    pub mint: UncheckedAccount<'info>,
+
+    pub token_2022_program: Program<'info, Token2022>,
+    pub system_program: Program<'info, System>,
+}
+
+pub fn handler(ctx: Context<UpdateMetadata>, args: UpdateMetadataArgs) -> Result<()> {
+    let config = &ctx.accounts.config;
+    let role_account = &ctx.accounts.role_account;
+
+    require!(
+        role_account.config == config.key(),
+        StablecoinError::Unauthorized
+    );
+    require!(
+        has_any_role(role_account.roles, ROLE_MASTER_AUTHORITY),
+        StablecoinError::Unauthorized
+    );
+    require!(
+        config.mint == ctx.accounts.mint.key(),
+        StablecoinError::Unauthorized
+    );
+    require!(
+        args.name.is_some() || args.symbol.is_some() || args.uri.is_some(),
+        StablecoinError::EmptyMetadataUpdate
+    );
+    if let Some(name) = &args.name {
+        require!(name.len() <= MAX_NAME_LEN, StablecoinError::NameTooLong);
+    }
+    if let Some(symbol) = &args.symbol {
+        require!(
+            symbol.len() <= MAX_SYMBOL_LEN,
+            StablecoinError::SymbolTooLong
+        );
+    }
+    if let Some(uri) = &args.uri {
+        require!(uri.len() <= MAX_URI_LEN, StablecoinError::UriTooLong);
+    }
+
+    // Mints initialized without metadata only carry the copy in the config.
+    let mint_info = ctx.accounts.mint.to_account_info();
+    let current = read_token_metadata(&mint_info)?;
+    if let Some(current) = current {
+        let mut updated = current.clone();
+        if let Some(name) = &args.name {
+            updated.name = name.clone();
+        }
+        if let Some(symbol) = &args.symbol {
+            updated.symbol = symbol.clone();
+        }
+        if let Some(uri) = &args.uri {
+            updated.uri = uri.clone();
+        }
+        fund_metadata_realloc(
+            &ctx.accounts.authority,
+            &mint_info,
+            &ctx.accounts.system_program,
+            &current,
+            &updated,
+        )?;
+
+        let mint_key = config.mint;
+        let signer_seeds: &[&[&[u8]]] = &[&[b"stablecoin", mint_key.as_ref(), &[config.bump]]];
+        let token_program_info = ctx.accounts.token_2022_program.to_account_info();
+        let config_info = config.to_account_info();
+        let fields = [
+            (Field::Name, &args.name),
+            (Field::Symbol, &args.symbol),
+            (Field::Uri, &args.uri),
+        ];
+        for (field, value) in fields {
+            let Some(value) = value else {
+                continue;
+            };
+            token_metadata_update_field(
+                CpiContext::new_with_signer(
+                    token_program_info.clone(),
+                    TokenMetadataUpdateField {
+                        token_program_id: token_program_info.clone(),
+                        metadata: mint_info.clone(),
+                        update_authority: config_info.clone(),
+                    },
+                    signer_seeds,
+                ),
+                field,
+                value.clone(),
+            )?;
+        }
+    }
+
+    let config = &mut ctx.accounts.config;
+    if let Some(name) = args.name {
+        config.name = name;
+    }
+    if let Some(symbol) = args.symbol {
+        config.symbol = symbol;
+    }
+    if let Some(uri) = args.uri {
+        config.uri = uri;
+    }
+    config.require_fits(config.to_account_info().data_len())?;
+    config.audit_counter = config
+        .audit_counter
+        .checked_add(1)
+        .ok_or(StablecoinError::Overflow)?;
+    config.record_action(ctx.accounts.authority.key(), Clock::get()?.unix_timestamp);
+
+    emit!(MetadataUpdated {
+        config: config.key(),
+        mint: config.mint,
+        updated_by: ctx.accounts.authority.key(),
+        name: config.name.clone(),
+        symbol: config.symbol.clone(),
+        uri: config.uri.clone(),
+        timestamp: Clock::get()?.unix_timestamp,
+    });
+
+    Ok(())
+}
+
+pub fn update_field_handler(
+    ctx: Context<UpdateMetadataField>,
+    args: UpdateMetadataFieldArgs,
+) -> Result<()> {
+    let config = &ctx.accounts.config;
+    let role_account = &ctx.accounts.role_account;
+
+    require!(
+        role_account.config == config.key(),
+        StablecoinError::Unauthorized
+    );
+    require!(
+        has_any_role(role_account.roles, ROLE_MASTER_AUTHORITY),
+        StablecoinError::Unauthorized
+    );
+    require!(
+        config.mint == ctx.accounts.mint.key(),
+        StablecoinError::Unauthorized
+    );
+    // name, symbol and uri are mirrored in the config; update_metadata keeps
+    // both copies in step.
+    require!(
+        !args.key.is_empty()
+            && args.key.len() <= MAX_METADATA_KEY_LEN
+            && !matches!(args.key.as_str(), "name" | "symbol" | "uri")
+            && args.value.len() <= MAX_METADATA_VALUE_LEN,
+        StablecoinError::InvalidMetadataField
+    );
+
+    let mint_info = ctx.accounts.mint.to_account_info();
+    let current = read_token_metadata(&mint_info)?.ok_or(StablecoinError::FeatureNotEnabled)?;
+    let mut updated = current.clone();
+    updated.set_key_value(args.key.clone(), args.value.clone());
+    fund_metadata_realloc(
+        &ctx.accounts.authority,
+        &mint_info,
+        &ctx.accounts.system_program,
+        &current,
+        &updated,
+    )?;
+
+    let mint_key = config.mint;
+    let signer_seeds: &[&[&[u8]]] = &[&[b"stablecoin", mint_key.as_ref(), &[config.bump]]];
+    let token_program_info = ctx.accounts.token_2022_program.to_account_info();
+    token_metadata_update_field(
+        CpiContext::new_with_signer(
+            token_program_info.clone(),
+            TokenMetadataUpdateField {
+                token_program_id: token_program_info,
+                metadata: mint_info,
+                update_authority: config.to_account_info(),
+            },
+            signer_seeds,
+        ),
+        Field::Key(args.key.clone()),
+        args.value.clone(),
+    )?;
+
+    let config = &mut ctx.accounts.config;
+    config.audit_counter = config
+        .audit_counter
+        .checked_add(1)
+        .ok_or(StablecoinError::Overflow)?;
+    config.record_action(ctx.accounts.authority.key(), Clock::get()?.unix_timestamp);
+
+    emit!(MetadataFieldUpdated {
+        config: config.key(),
+        mint: config.mint,
+        updated_by: ctx.accounts.authority.key(),
+        key: args.key,
+        value: args.value,
+        timestamp: Clock::get()?.unix_timestamp,
+    });
+
+    Ok(())
+}
+
+fn read_token_metadata(mint_info: &AccountInfo) -> Result<Option<TokenMetadata>> {
+    let data = mint_info.try_borrow_data()?;
+    let mint = StateWithExtensions::<Token2022Mint>::unpack(&data)?;
+    Ok(mint.get_variable_len_extension::<TokenMetadata>().ok())
+}
+
+/// Token-2022 reallocates the mint in place, so top it up to stay rent exempt.
+fn fund_metadata_realloc<'info>(
+    payer: &Signer<'info>,
+    mint_info: &AccountInfo<'info>,
+    system_program: &Program<'info, System>,
+    current: &TokenMetadata,
+    updated: &TokenMetadata,
+) -> Result<()> {
+    let new_len = mint_info
+        .data_len()
+        .saturating_sub(current.tlv_size_of()?)
+        .checked_add(updated.tlv_size_of()?)
+        .ok_or(StablecoinError::Overflow)?;
+    let required = Rent::get()?.minimum_balance(new_len);
+    let shortfall = required.saturating_sub(mint_info.lamports());
+    if shortfall == 0 {
+        return Ok(());
+    }
+
+    let transfer_ctx = CpiContext::new(
+        system_program.to_account_info(),
+        system_program::Transfer {
+            from: payer.to_account_info(),
+            to: mint_info.clone(),
+        },
+    );
+    system_program::transfer(transfer_ctx, shortfall)
+}
@@ -0,0 +1,62 @@
+use anchor_lang::prelude::*;
+use anchor_lang::system_program;
+use anchor_lang::Discriminator;
+
+use crate::events::ConfigMigrated;
+use crate::state::StablecoinConfig;
+
+#[derive(Accounts)]
+pub struct MigrateConfig<'info> {
+    /// Pays the rent for the added bytes.
+    #[account(mut)]
+    pub payer: Signer<'info>,
+
+    /// CHECK: Read by hand, since a legacy config with long strings may not
+    /// deserialize until it has grown; the discriminator is checked below.
+    #[account(mut, owner = crate::ID)]
+    pub config: UncheckedAccount<'info>,
+
+    pub system_program: Program<'info, System>,
+}
+
+/// Grows a config allocated before INIT_SPACE was raised to the current
+/// 8 + INIT_SPACE bytes. The new bytes are zeroed, so fields added since
+/// decode to their defaults. Anyone may pay for it; it only adds space and
+/// is a no-op for a config that already has it.
+pub fn handler(ctx: Context<MigrateConfig>) -> Result<()> {
+    let config = &ctx.accounts.config;
+    require!(
+        config.try_borrow_data()?.get(..8) == Some(&StablecoinConfig::DISCRIMINATOR[..]),
+        ErrorCode::AccountDiscriminatorMismatch
+    );
+
+    let previous_len = config.data_len();
+    let new_len = 8 + StablecoinConfig::INIT_SPACE;
+    if previous_len >= new_len {
+        return Ok(());
+    }
+
+    let shortfall = Rent::get()?
+        .minimum_balance(new_len)
+        .saturating_sub(config.lamports());
+    if shortfall > 0 {
+        let transfer_ctx = CpiContext::new(
+            ctx.accounts.system_program.to_account_info(),
+            system_program::Transfer {
+                from: ctx.accounts.payer.to_account_info(),
+                to: config.to_account_info(),
+            },
+        );
+        system_program::transfer(transfer_ctx, shortfall)?;
+    }
+    config.realloc(new_len, true)?;
+
+    emit!(ConfigMigrated {
+        config: config.key(),
+        previous_len: previous_len as u32,
+        new_len: new_len as u32,
+        paid_by: ctx.accounts.payer.key(),
+        timestamp: Clock::get()?.unix_timestamp,
+    });
+    Ok(())
+}
@@ -1,14 +1,14 @@
 use anchor_lang::prelude::*;
 use anchor_spl::{
     associated_token::AssociatedToken,
-    token_2022::{self, Token2022},
-    token_interface::{Mint, TokenAccount},
+    token_2022,
+    token_interface::{Mint, TokenAccount, TokenInterface},
 };
 
-use crate::constants::{MINT_QUOTA_WINDOW_SECONDS, ROLE_MASTER_AUTHORITY, ROLE_MINTER};
+use crate::constants::{EVENT_SCHEMA_VERSION, PAUSE_MINT, ROLE_MASTER_AUTHORITY, ROLE_MINTER};
 use crate::errors::StablecoinError;
-use crate::events::TokensMinted;
-use crate::state::{RoleAccount, StablecoinConfig};
+use crate::events::{QuotaWindowReset, TokensMinted};
+use crate::state::{BlacklistEntry, RoleAccount, StablecoinConfig};
 use crate::utils::has_any_role;
 
 #[derive(Accounts)]
@@ -20,6 +20,7 @@ pub struct MintTokens<'info> {
     pub config: Account<'info, StablecoinConfig>,
 
     #[account(
+        mut,
         seeds = [b"role", config.key().as_ref(), minter.key().as_ref()],
         bump = role_account.bump
     )]
@@ -31,18 +32,138 @@ pub struct MintTokens<'info> {
     /// CHECK: Used only as ATA authority; owner checked against recipient_ata.
     pub recipient: UncheckedAccount<'info>,
 
+    /// Must be the canonical ATA of `recipient`; any other token account, even one
+    /// owned by the recipient, fails the `associated_token` constraint.
     #[account(
         init_if_needed,
         payer = minter,
         associated_token::mint = mint,
         associated_token::authority = recipient,
-        associated_token::token_program = token_2022_program
+        associated_token::token_program = token_program
     )]
     pub recipient_ata: InterfaceAccount<'info, TokenAccount>,
 
-    pub token_2022_program: Program<'info, Token2022>,
+    #[account(address = config.token_program_id() @ StablecoinError::InvalidTokenProgram)]
+    pub token_program: Interface<'info, TokenInterface>,
     pub associated_token_program: Program<'info, AssociatedToken>,
     pub system_program: Program<'info, System>,
+
+    /// CHECK: The recipient's blacklist PDA, which may not exist. Required
+    /// when the transfer hook is enabled and ignored otherwise.
+    #[account(seeds = [b"blacklist", config.key().as_ref(), recipient.key().as_ref()], bump)]
+    pub recipient_blacklist_entry: Option<UncheckedAccount<'info>>,
+}
+
+/// Charges `amount` against the minter's window quota, rolling the window when expired,
+/// and adds it to the lifetime total, which may not pass `lifetime_quota`. Each rollover
+/// emits `QuotaWindowReset`.
+pub(crate) fn consume_mint_quota(
+    role_account: &mut Account<RoleAccount>,
+    amount: u64,
+    now: i64,
+    window_seconds: i64,
+) -> Result<()> {
+    role_account.lifetime_minted = role_account
+        .lifetime_minted
+        .checked_add(amount)
+        .ok_or(StablecoinError::Overflow)?;
+    if let Some(lifetime_quota) = role_account.lifetime_quota {
+        require!(
+            role_account.lifetime_minted <= lifetime_quota,
+            StablecoinError::LifetimeQuotaExceeded
+        );
+    }
+    let Some(quota) = role_account.mint_quota else {
+        return Ok(());
+    };
+    if role_account.window_start == 0
+        || now.saturating_sub(role_account.window_start) >= window_seconds
+    {
+        emit!(QuotaWindowReset {
+            config: role_account.config,
+            role_account: role_account.key(),
+            minter: role_account.authority,
+            previous_window_total: role_account.minted_current_window,
+            window_start: now,
+            timestamp: now,
+        });
+        role_account.window_start = now;
+        role_account.minted_current_window = 0;
+        role_account.window_resets = role_account
+            .window_resets
+            .checked_add(1)
+            .ok_or(StablecoinError::Overflow)?;
+    }
+
+    let new_window_total = role_account
+        .minted_current_window
+        .checked_add(amount)
+        .ok_or(StablecoinError::Overflow)?;
+    require!(new_window_total <= quota, StablecoinError::QuotaExceeded);
+    role_account.minted_current_window = new_window_total;
+    Ok(())
+}
+
+/// Rejects recipients whose tokens would be unrecoverable: the system program,
+/// the incinerator, the config PDA, and the mint itself.
+pub(crate) fn require_allowed_recipient(
+    recipient: &Pubkey,
+    config: &Pubkey,
+    mint: &Pubkey,
+) -> Result<()> {
+    let forbidden = [
+        anchor_lang::system_program::ID,
+        anchor_lang::solana_program::incinerator::ID,
+        *config,
+        *mint,
+    ];
+    require!(
+        !forbidden.contains(recipient),
+        StablecoinError::ForbiddenRecipient
+    );
+    Ok(())
+}
+
+/// Rejects a recipient whose blacklist entry is in effect, since the hook
+/// would strand anything minted to it. A missing entry is not blacklisted.
+fn require_not_blacklisted(entry: &AccountInfo, config: &Pubkey, now: i64) -> Result<()> {
+    if entry.data_is_empty() {
+        return Ok(());
+    }
+    require!(entry.owner == &crate::ID, StablecoinError::Unauthorized);
+    let data = entry.try_borrow_data()?;
+    let entry = BlacklistEntry::try_deserialize(&mut &data[..])?;
+    require!(
+        entry.config != *config || !entry.is_in_effect(now),
+        StablecoinError::TransferDenied
+    );
+    Ok(())
+}
+
+/// With the transfer hook enabled the recipient's blacklist PDA must be
+/// passed, so leaving it out cannot skip the check. `entry` must already be
+/// the recipient's PDA.
+pub(crate) fn require_recipient_not_blacklisted(
+    config: &Account<StablecoinConfig>,
+    entry: Option<&AccountInfo>,
+    now: i64,
+) -> Result<()> {
+    if !config.features.transfer_hook() {
+        return Ok(());
+    }
+    let entry = entry.ok_or(StablecoinError::MissingBlacklistEntry)?;
+    require_not_blacklisted(entry, &config.key(), now)
+}
+
+/// Returns the supply after minting `amount`, rejecting it above `max_supply`.
+pub(crate) fn check_supply_cap(max_supply: Option<u64>, supply: u64, amount: u64) -> Result<u64> {
+    let new_supply = supply
+        .checked_add(amount)
+        .ok_or(StablecoinError::Overflow)?;
+    if let Some(max_supply) = max_supply {
+        require!(new_supply <= max_supply, StablecoinError::SupplyCapExceeded);
+    }
+    Ok(new_supply)
 }
 
 pub fn handler(ctx: Context<MintTokens>, amount: u64) -> Result<()> {
@@ -50,7 +171,12 @@ pub fn handler(ctx: Context<MintTokens>, amount: u64) -> Result<()> {
     let role_account = &mut ctx.accounts.role_account;
     let mint = &ctx.accounts.mint;
 
-    require!(!config.is_paused, StablecoinError::SystemPaused);
+    require!(!config.minting_locked, StablecoinError::MintingLocked);
+    config.require_direct_mint()?;
+    require!(
+        !config.is_operation_paused(PAUSE_MINT),
+        StablecoinError::SystemPaused
+    );
     require!(
         role_account.config == config.key(),
         StablecoinError::Unauthorized
@@ -69,22 +195,23 @@ pub fn handler(ctx: Context<MintTokens>, amount: u64) -> Result<()> {
         StablecoinError::Unauthorized
     );
 
-    if let Some(quota) = role_account.mint_quota {
-        let now = Clock::get()?.unix_timestamp;
-        if role_account.window_start == 0
-            || now.saturating_sub(role_account.window_start) >= MINT_QUOTA_WINDOW_SECONDS
-        {
-            role_account.window_start = now;
-            role_account.minted_current_window = 0;
-        }
-
-        let new_window_total = role_account
-            .minted_current_window
-            .checked_add(amount)
-            .ok_or(StablecoinError::Overflow)?;
-        require!(new_window_total <= quota, StablecoinError::QuotaExceeded);
-        role_account.minted_current_window = new_window_total;
-    }
+    require_allowed_recipient(&ctx.accounts.recipient.key(), &config.key(), &mint.key())?;
+    require_recipient_not_blacklisted(
+        config,
+        ctx.accounts
+            .recipient_blacklist_entry
+            .as_ref()
+            .map(|entry| entry.as_ref()),
+        Clock::get()?.unix_timestamp,
+    )?;
+
+    consume_mint_quota(
+        role_account,
+        amount,
+        Clock::get()?.unix_timestamp,
+        config.quota_window(),
+    )?;
+    let new_total_supply = check_supply_cap(config.max_supply, mint.supply, amount)?;
 
     let mint_key = mint.key();
     let signer_seeds: &[&[u8]] = &[b"stablecoin", mint_key.as_ref(), &[config.bump]];
@@ -95,7 +222,7 @@ pub fn handler(ctx: Context<MintTokens>, amount: u64) -> Result<()> {
         authority: config.to_account_info(),
     };
     let cpi_ctx = CpiContext::new_with_signer(
-        ctx.accounts.token_2022_program.to_account_info(),
+        ctx.accounts.token_program.to_account_info(),
         cpi_accounts,
         &signer_seeds_arr,
     );
@@ -109,11 +236,7 @@ pub fn handler(ctx: Context<MintTokens>, amount: u64) -> Result<()> {
         .audit_counter
         .checked_add(1)
         .ok_or(StablecoinError::Overflow)?;
-
-    let new_total_supply = mint
-        .supply
-        .checked_add(amount)
-        .ok_or(StablecoinError::Overflow)?;
+    config.record_action(ctx.accounts.minter.key(), Clock::get()?.unix_timestamp);
 
     emit!(TokensMinted {
         config: config.key(),
@@ -123,6 +246,7 @@ pub fn handler(ctx: Context<MintTokens>, amount: u64) -> Result<()> {
         minter: ctx.accounts.minter.key(),
         new_total_supply,
         timestamp: Clock::get()?.unix_timestamp,
+        schema_version: EVENT_SCHEMA_VERSION,
     });
     Ok(())
 }
@@ -0,0 +1,181 @@
+use anchor_lang::prelude::*;
+use anchor_spl::{
+    associated_token::get_associated_token_address_with_program_id,
+    token_2022,
+    token_interface::{Mint, TokenAccount, TokenInterface},
+};
+
+use crate::constants::{
+    EVENT_SCHEMA_VERSION, MAX_MINT_BATCH_SIZE, PAUSE_MINT, ROLE_MASTER_AUTHORITY, ROLE_MINTER,
+};
+use crate::errors::StablecoinError;
+use crate::events::TokensMinted;
+use crate::instructions::mint::{
+    check_supply_cap, consume_mint_quota, require_allowed_recipient,
+    require_recipient_not_blacklisted,
+};
+use crate::state::{RoleAccount, StablecoinConfig};
+use crate::utils::has_any_role;
+
+#[derive(AnchorSerialize, AnchorDeserialize, Clone)]
+pub struct MintBatchEntry {
+    pub recipient: Pubkey,
+    pub amount: u64,
+}
+
+/// Recipient ATAs are passed as writable `remaining_accounts`, one per entry
+/// and in the same order. They must already exist. With the transfer hook
+/// enabled each ATA is followed by the recipient's blacklist PDA, which may
+/// not exist.
+#[derive(Accounts)]
+pub struct MintBatch<'info> {
+    pub minter: Signer<'info>,
+
+    #[account(mut)]
+    pub config: Account<'info, StablecoinConfig>,
+
+    #[account(
+        mut,
+        seeds = [b"role", config.key().as_ref(), minter.key().as_ref()],
+        bump = role_account.bump
+    )]
+    pub role_account: Account<'info, RoleAccount>,
+
+    #[account(mut)]
+    pub mint: InterfaceAccount<'info, Mint>,
+
+    #[account(address = config.token_program_id() @ StablecoinError::InvalidTokenProgram)]
+    pub token_program: Interface<'info, TokenInterface>,
+}
+
+pub fn handler<'info>(
+    ctx: Context<'_, '_, 'info, 'info, MintBatch<'info>>,
+    entries: Vec<MintBatchEntry>,
+) -> Result<()> {
+    let config = &mut ctx.accounts.config;
+    let role_account = &mut ctx.accounts.role_account;
+    let mint = &ctx.accounts.mint;
+
+    require!(!config.minting_locked, StablecoinError::MintingLocked);
+    config.require_direct_mint()?;
+    require!(
+        !config.is_operation_paused(PAUSE_MINT),
+        StablecoinError::SystemPaused
+    );
+    require!(
+        role_account.config == config.key(),
+        StablecoinError::Unauthorized
+    );
+    require!(
+        has_any_role(role_account.roles, ROLE_MASTER_AUTHORITY | ROLE_MINTER),
+        StablecoinError::Unauthorized
+    );
+    require!(config.mint == mint.key(), StablecoinError::Unauthorized);
+    require!(
+        !entries.is_empty() && entries.len() <= MAX_MINT_BATCH_SIZE,
+        StablecoinError::InvalidBatchSize
+    );
+    let accounts_per_entry = if config.features.transfer_hook() {
+        2
+    } else {
+        1
+    };
+    require!(
+        ctx.remaining_accounts.len() == entries.len() * accounts_per_entry,
+        StablecoinError::InvalidBatchSize
+    );
+
+    let batch_total = entries.iter().try_fold(0u64, |total, entry| {
+        total
+            .checked_add(entry.amount)
+            .ok_or(StablecoinError::Overflow)
+    })?;
+    let now = Clock::get()?.unix_timestamp;
+    consume_mint_quota(role_account, batch_total, now, config.quota_window())?;
+    check_supply_cap(config.max_supply, mint.supply, batch_total)?;
+
+    let mint_key = mint.key();
+    let token_program_id = ctx.accounts.token_program.key();
+    let signer_seeds: &[&[u8]] = &[b"stablecoin", mint_key.as_ref(), &[config.bump]];
+    let signer_seeds_arr = [signer_seeds];
+    let mut supply = mint.supply;
+
+    for (entry, entry_accounts) in entries
+        .iter()
+        .zip(ctx.remaining_accounts.chunks(accounts_per_entry))
+    {
+        let account_info = &entry_accounts[0];
+        require_allowed_recipient(&entry.recipient, &config.key(), &mint_key)?;
+        let blacklist_entry = entry_accounts.get(1);
+        if let Some(blacklist_entry) = blacklist_entry {
+            let (expected, _) = Pubkey::find_program_address(
+                &[
+                    b"blacklist",
+                    config.key().as_ref(),
+                    entry.recipient.as_ref(),
+                ],
+                &crate::ID,
+            );
+            require!(
+                blacklist_entry.key() == expected,
+                StablecoinError::Unauthorized
+            );
+        }
+        require_recipient_not_blacklisted(config, blacklist_entry, now)?;
+        require!(
+            account_info.key()
+                == get_associated_token_address_with_program_id(
+                    &entry.recipient,
+                    &mint_key,
+                    &token_program_id,
+                ),
+            StablecoinError::Unauthorized
+        );
+        let recipient_ata = InterfaceAccount::<TokenAccount>::try_from(account_info)?;
+        require!(
+            recipient_ata.mint == mint_key,
+            StablecoinError::Unauthorized
+        );
+        require!(
+            recipient_ata.owner == entry.recipient,
+            StablecoinError::Unauthorized
+        );
+
+        let cpi_accounts = token_2022::MintTo {
+            mint: mint.to_account_info(),
+            to: account_info.clone(),
+            authority: config.to_account_info(),
+        };
+        let cpi_ctx = CpiContext::new_with_signer(
+            ctx.accounts.token_program.to_account_info(),
+            cpi_accounts,
+            &signer_seeds_arr,
+        );
+        token_2022::mint_to(cpi_ctx, entry.amount)?;
+
+        config.total_minted = config
+            .total_minted
+            .checked_add(entry.amount)
+            .ok_or(StablecoinError::Overflow)?;
+        config.audit_counter = config
+            .audit_counter
+            .checked_add(1)
+            .ok_or(StablecoinError::Overflow)?;
+        supply = supply
+            .checked_add(entry.amount)
+            .ok_or(StablecoinError::Overflow)?;
+
+        emit!(TokensMinted {
+            config: config.key(),
+            mint: mint_key,
+            recipient: entry.recipient,
+            amount: entry.amount,
+            minter: ctx.accounts.minter.key(),
+            new_total_supply: supply,
+            timestamp: now,
+            schema_version: EVENT_SCHEMA_VERSION,
+        });
+    }
+    config.record_action(ctx.accounts.minter.key(), now);
+    Ok(())
+}
@@ -0,0 +1,382 @@
+use anchor_lang::prelude::*;
+use anchor_spl::{
+    associated_token::AssociatedToken,
+    token_2022,
+    token_interface::{Mint, TokenAccount, TokenInterface},
+};
+
+use crate::constants::{EVENT_SCHEMA_VERSION, PAUSE_MINT, ROLE_MASTER_AUTHORITY, ROLE_MINTER};
+use crate::errors::StablecoinError;
+use crate::events::{MintApprovalUpdated, MintApproved, MintCancelled, MintProposed, TokensMinted};
+use crate::instructions::mint::{
+    check_supply_cap, consume_mint_quota, require_allowed_recipient,
+    require_recipient_not_blacklisted,
+};
+use crate::state::{MintRequest, RoleAccount, StablecoinConfig};
+use crate::utils::has_any_role;
+
+#[derive(AnchorSerialize, AnchorDeserialize, Clone)]
+pub struct ProposeMintArgs {
+    pub id: u64,
+    pub recipient: Pubkey,
+    pub amount: u64,
+}
+
+#[derive(Accounts)]
+#[instruction(args: ProposeMintArgs)]
+pub struct ProposeMint<'info> {
+    #[account(mut)]
+    pub requester: Signer<'info>,
+
+    pub config: Account<'info, StablecoinConfig>,
+
+    #[account(
+        seeds = [b"role", config.key().as_ref(), requester.key().as_ref()],
+        bump = role_account.bump
+    )]
+    pub role_account: Account<'info, RoleAccount>,
+
+    #[account(
+        init,
+        payer = requester,
+        space = 8 + MintRequest::INIT_SPACE,
+        seeds = [b"mint_request", config.key().as_ref(), &args.id.to_le_bytes()],
+        bump
+    )]
+    pub mint_request: Account<'info, MintRequest>,
+
+    pub system_program: Program<'info, System>,
+}
+
+/// The approver pays for the recipient ATA when it does not exist yet; the
+/// request's rent goes back to the requester.
+#[derive(Accounts)]
+pub struct ApproveMint<'info> {
+    #[account(mut)]
+    pub approver: Signer<'info>,
+
+    #[account(mut)]
+    pub config: Account<'info, StablecoinConfig>,
+
+    #[account(
+        seeds = [b"role", config.key().as_ref(), approver.key().as_ref()],
+        bump = approver_role_account.bump
+    )]
+    pub approver_role_account: Account<'info, RoleAccount>,
+
+    /// Charged for the mint, as if the requester had minted directly.
+    #[account(
+        mut,
+        seeds = [b"role", config.key().as_ref(), mint_request.requester.as_ref()],
+        bump = requester_role_account.bump
+    )]
+    pub requester_role_account: Account<'info, RoleAccount>,
+
+    #[account(
+        mut,
+        close = requester,
+        seeds = [b"mint_request", config.key().as_ref(), &mint_request.id.to_le_bytes()],
+        bump = mint_request.bump
+    )]
+    pub mint_request: Account<'info, MintRequest>,
+
+    /// CHECK: Receives the request's rent; must be the recorded requester.
+    #[account(mut, address = mint_request.requester @ StablecoinError::Unauthorized)]
+    pub requester: UncheckedAccount<'info>,
+
+    #[account(mut)]
+    pub mint: InterfaceAccount<'info, Mint>,
+
+    /// CHECK: Used only as ATA authority; must be the recorded recipient.
+    #[account(address = mint_request.recipient @ StablecoinError::Unauthorized)]
+    pub recipient: UncheckedAccount<'info>,
+
+    #[account(
+        init_if_needed,
+        payer = approver,
+        associated_token::mint = mint,
+        associated_token::authority = recipient,
+        associated_token::token_program = token_program
+    )]
+    pub recipient_ata: InterfaceAccount<'info, TokenAccount>,
+
+    #[account(address = config.token_program_id() @ StablecoinError::InvalidTokenProgram)]
+    pub token_program: Interface<'info, TokenInterface>,
+    pub associated_token_program: Program<'info, AssociatedToken>,
+    pub system_program: Program<'info, System>,
+
+    /// CHECK: The recipient's blacklist PDA, which may not exist. Required
+    /// when the transfer hook is enabled and ignored otherwise.
+    #[account(seeds = [b"blacklist", config.key().as_ref(), recipient.key().as_ref()], bump)]
+    pub recipient_blacklist_entry: Option<UncheckedAccount<'info>>,
+}
+
+/// The requester needs no role account, so a request whose requester has
+/// since lost the minter role can still be withdrawn; the master authority
+/// passes its own.
+#[derive(Accounts)]
+pub struct CancelMint<'info> {
+    pub authority: Signer<'info>,
+
+    pub config: Account<'info, StablecoinConfig>,
+
+    #[account(
+        seeds = [b"role", config.key().as_ref(), authority.key().as_ref()],
+        bump = role_account.bump
+    )]
+    pub role_account: Option<Account<'info, RoleAccount>>,
+
+    #[account(
+        mut,
+        close = requester,
+        seeds = [b"mint_request", config.key().as_ref(), &mint_request.id.to_le_bytes()],
+        bump = mint_request.bump
+    )]
+    pub mint_request: Account<'info, MintRequest>,
+
+    /// CHECK: Receives the request's rent; must be the recorded requester.
+    #[account(mut, address = mint_request.requester @ StablecoinError::Unauthorized)]
+    pub requester: UncheckedAccount<'info>,
+}
+
+#[derive(AnchorSerialize, AnchorDeserialize, Clone)]
+pub struct UpdateMintApprovalArgs {
+    pub require_mint_approval: bool,
+}
+
+#[derive(Accounts)]
+pub struct UpdateMintApproval<'info> {
+    pub authority: Signer<'info>,
+
+    #[account(mut)]
+    pub config: Account<'info, StablecoinConfig>,
+
+    #[account(
+        seeds = [b"role", config.key().as_ref(), authority.key().as_ref()],
+        bump = role_account.bump
+    )]
+    pub role_account: Account<'info, RoleAccount>,
+}
+
+pub fn propose_handler(ctx: Context<ProposeMint>, args: ProposeMintArgs) -> Result<()> {
+    let config = &ctx.accounts.config;
+    let role_account = &ctx.accounts.role_account;
+
+    require!(!config.minting_locked, StablecoinError::MintingLocked);
+    require!(
+        !config.is_operation_paused(PAUSE_MINT),
+        StablecoinError::SystemPaused
+    );
+    require!(
+        role_account.config == config.key(),
+        StablecoinError::Unauthorized
+    );
+    require!(
+        has_any_role(role_account.roles, ROLE_MASTER_AUTHORITY | ROLE_MINTER),
+        StablecoinError::Unauthorized
+    );
+    require_allowed_recipient(&args.recipient, &config.key(), &config.mint)?;
+
+    let now = Clock::get()?.unix_timestamp;
+    let mint_request = &mut ctx.accounts.mint_request;
+    mint_request.config = config.key();
+    mint_request.id = args.id;
+    mint_request.requester = ctx.accounts.requester.key();
+    mint_request.recipient = args.recipient;
+    mint_request.amount = args.amount;
+    mint_request.created_at = now;
+    mint_request.bump = ctx.bumps.mint_request;
+
+    emit!(MintProposed {
+        config: config.key(),
+        request: mint_request.key(),
+        id: args.id,
+        requester: ctx.accounts.requester.key(),
+        recipient: args.recipient,
+        amount: args.amount,
+        timestamp: now,
+    });
+    Ok(())
+}
+
+pub fn approve_handler(ctx: Context<ApproveMint>) -> Result<()> {
+    let config = &mut ctx.accounts.config;
+    let approver_role_account = &ctx.accounts.approver_role_account;
+    let requester_role_account = &mut ctx.accounts.requester_role_account;
+    let mint_request = &ctx.accounts.mint_request;
+    let mint = &ctx.accounts.mint;
+
+    require!(!config.minting_locked, StablecoinError::MintingLocked);
+    require!(
+        !config.is_operation_paused(PAUSE_MINT),
+        StablecoinError::SystemPaused
+    );
+    require!(
+        mint_request.config == config.key(),
+        StablecoinError::Unauthorized
+    );
+    require!(
+        approver_role_account.config == config.key()
+            && requester_role_account.config == config.key(),
+        StablecoinError::Unauthorized
+    );
+    require!(
+        ctx.accounts.approver.key() != mint_request.requester,
+        StablecoinError::SelfApproval
+    );
+    require!(
+        has_any_role(
+            approver_role_account.roles,
+            ROLE_MASTER_AUTHORITY | ROLE_MINTER
+        ),
+        StablecoinError::Unauthorized
+    );
+    // The requester may have lost the minter role since proposing.
+    require!(
+        has_any_role(
+            requester_role_account.roles,
+            ROLE_MASTER_AUTHORITY | ROLE_MINTER
+        ),
+        StablecoinError::Unauthorized
+    );
+    require!(config.mint == mint.key(), StablecoinError::Unauthorized);
+
+    let now = Clock::get()?.unix_timestamp;
+    require!(
+        !mint_request.is_expired(now),
+        StablecoinError::MintRequestExpired
+    );
+    // The recipient may have been blacklisted since the request was proposed.
+    require_recipient_not_blacklisted(
+        config,
+        ctx.accounts
+            .recipient_blacklist_entry
+            .as_ref()
+            .map(|entry| entry.as_ref()),
+        now,
+    )?;
+    let amount = mint_request.amount;
+    consume_mint_quota(requester_role_account, amount, now, config.quota_window())?;
+    let new_total_supply = check_supply_cap(config.max_supply, mint.supply, amount)?;
+
+    let mint_key = mint.key();
+    let signer_seeds: &[&[u8]] = &[b"stablecoin", mint_key.as_ref(), &[config.bump]];
+    let signer_seeds_arr = [signer_seeds];
+    let cpi_accounts = token_2022::MintTo {
+        mint: mint.to_account_info(),
+        to: ctx.accounts.recipient_ata.to_account_info(),
+        authority: config.to_account_info(),
+    };
+    let cpi_ctx = CpiContext::new_with_signer(
+        ctx.accounts.token_program.to_account_info(),
+        cpi_accounts,
+        &signer_seeds_arr,
+    );
+    token_2022::mint_to(cpi_ctx, amount)?;
+
+    config.total_minted = config
+        .total_minted
+        .checked_add(amount)
+        .ok_or(StablecoinError::Overflow)?;
+    config.audit_counter = config
+        .audit_counter
+        .checked_add(1)
+        .ok_or(StablecoinError::Overflow)?;
+    config.record_action(ctx.accounts.approver.key(), now);
+
+    emit!(TokensMinted {
+        config: config.key(),
+        mint: mint.key(),
+        recipient: mint_request.recipient,
+        amount,
+        minter: mint_request.requester,
+        new_total_supply,
+        timestamp: now,
+        schema_version: EVENT_SCHEMA_VERSION,
+    });
+    emit!(MintApproved {
+        config: config.key(),
+        request: mint_request.key(),
+        id: mint_request.id,
+        requester: mint_request.requester,
+        approver: ctx.accounts.approver.key(),
+        recipient: mint_request.recipient,
+        amount,
+        timestamp: now,
+    });
+    Ok(())
+}
+
+/// Withdraws a request that should never be approved, or can no longer be,
+/// returning its rent to the requester. Allowed while paused or expired.
+pub fn cancel_handler(ctx: Context<CancelMint>) -> Result<()> {
+    let config = &ctx.accounts.config;
+    let mint_request = &ctx.accounts.mint_request;
+    let authority = ctx.accounts.authority.key();
+
+    require!(
+        mint_request.config == config.key(),
+        StablecoinError::Unauthorized
+    );
+    if authority != mint_request.requester {
+        let role_account = ctx
+            .accounts
+            .role_account
+            .as_ref()
+            .ok_or(StablecoinError::Unauthorized)?;
+        require!(
+            role_account.config == config.key(),
+            StablecoinError::Unauthorized
+        );
+        require!(
+            has_any_role(role_account.roles, ROLE_MASTER_AUTHORITY),
+            StablecoinError::Unauthorized
+        );
+    }
+
+    emit!(MintCancelled {
+        config: config.key(),
+        request: mint_request.key(),
+        id: mint_request.id,
+        requester: mint_request.requester,
+        cancelled_by: authority,
+        amount: mint_request.amount,
+        timestamp: Clock::get()?.unix_timestamp,
+    });
+    Ok(())
+}
+
+/// Turns direct mint and mint_batch off or back on. While off, every mint
+/// needs a proposal approved by a second signer.
+pub fn update_approval_handler(
+    ctx: Context<UpdateMintApproval>,
+    args: UpdateMintApprovalArgs,
+) -> Result<()> {
+    let config = &mut ctx.accounts.config;
+    let role_account = &ctx.accounts.role_account;
+
+    require!(
+        role_account.config == config.key(),
+        StablecoinError::Unauthorized
+    );
+    require!(
+        has_any_role(role_account.roles, ROLE_MASTER_AUTHORITY),
+        StablecoinError::Unauthorized
+    );
+
+    config.require_mint_approval = args.require_mint_approval;
+    config.audit_counter = config
+        .audit_counter
+        .checked_add(1)
+        .ok_or(StablecoinError::Overflow)?;
+    config.record_action(ctx.accounts.authority.key(), Clock::get()?.unix_timestamp);
+
+    emit!(MintApprovalUpdated {
+        config: config.key(),
+        require_mint_approval: args.require_mint_approval,
+        updated_by: ctx.accounts.authority.key(),
+        timestamp: Clock::get()?.unix_timestamp,
+    });
+
+    Ok(())
+}
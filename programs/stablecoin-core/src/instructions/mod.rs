@@ -1,8 +1,20 @@
+pub mod allowlist;
 pub mod blacklist;
 pub mod burn;
+pub mod default_state;
 pub mod freeze;
+pub mod hook_program;
 pub mod initialize;
+pub mod metadata;
+pub mod migrate;
 pub mod mint;
+pub mod mint_batch;
+pub mod mint_request;
 pub mod pause;
+pub mod quota;
 pub mod roles;
 pub mod seize;
+pub mod self_transfer;
+pub mod supply;
+pub mod transfer_limit;
+pub mod treasury;
@@ -2,9 +2,15 @@ use anchor_lang::prelude::*;
 
 use crate::constants::{ROLE_MASTER_AUTHORITY, ROLE_PAUSER};
 use crate::errors::StablecoinError;
-use crate::events::{SystemPaused, SystemUnpaused};
+use crate::events::{AdminOpsLockUpdated, SystemPaused, SystemUnpaused};
 use crate::state::{RoleAccount, StablecoinConfig};
-use crate::utils::has_any_role;
+use crate::utils::{effective_pause_flags, has_any_role, resolve_pause_mask};
+
+/// Operations to pause or resume as PAUSE_* bits; None covers all of them.
+#[derive(AnchorSerialize, AnchorDeserialize, Clone)]
+pub struct PauseArgs {
+    pub mask: Option<u8>,
+}
 
 #[derive(Accounts)]
 pub struct Pause<'info> {
@@ -34,7 +40,39 @@ pub struct Unpause<'info> {
     pub role_account: Account<'info, RoleAccount>,
 }
 
-pub fn pause_handler(ctx: Context<Pause>) -> Result<()> {
+#[derive(AnchorSerialize, AnchorDeserialize, Clone)]
+pub struct UpdateAdminLockArgs {
+    pub lock_admin_ops: bool,
+}
+
+#[derive(Accounts)]
+pub struct UpdateAdminLock<'info> {
+    pub authority: Signer<'info>,
+
+    #[account(mut)]
+    pub config: Account<'info, StablecoinConfig>,
+
+    #[account(
+        seeds = [b"role", config.key().as_ref(), authority.key().as_ref()],
+        bump = role_account.bump
+    )]
+    pub role_account: Account<'info, RoleAccount>,
+}
+
+/// Rejects a pause state change inside the cooldown window unless the caller is master.
+fn enforce_pause_cooldown(config: &StablecoinConfig, roles: u8, now: i64) -> Result<()> {
+    if config.pause_cooldown <= 0 || has_any_role(roles, ROLE_MASTER_AUTHORITY) {
+        return Ok(());
+    }
+    let elapsed = now.saturating_sub(config.last_pause_change);
+    require!(
+        elapsed >= config.pause_cooldown,
+        StablecoinError::PauseCooldownActive
+    );
+    Ok(())
+}
+
+pub fn pause_handler(ctx: Context<Pause>, args: PauseArgs) -> Result<()> {
     let config = &mut ctx.accounts.config;
     let role_account = &ctx.accounts.role_account;
 
@@ -47,21 +85,29 @@ pub fn pause_handler(ctx: Context<Pause>) -> Result<()> {
         StablecoinError::Unauthorized
     );
 
-    config.is_paused = true;
+    let mask = resolve_pause_mask(args.mask)?;
+
+    let now = Clock::get()?.unix_timestamp;
+    enforce_pause_cooldown(config, role_account.roles, now)?;
+
+    config.pause_flags = effective_pause_flags(config.pause_flags) | mask;
+    config.last_pause_change = now;
     config.audit_counter = config
         .audit_counter
         .checked_add(1)
         .ok_or(StablecoinError::Overflow)?;
+    config.record_action(ctx.accounts.pauser.key(), now);
 
     emit!(SystemPaused {
         config: config.key(),
         paused_by: ctx.accounts.pauser.key(),
-        timestamp: Clock::get()?.unix_timestamp,
+        timestamp: now,
+        pause_flags: config.pause_flags,
     });
     Ok(())
 }
 
-pub fn unpause_handler(ctx: Context<Unpause>) -> Result<()> {
+pub fn unpause_handler(ctx: Context<Unpause>, args: PauseArgs) -> Result<()> {
     let config = &mut ctx.accounts.config;
     let role_account = &ctx.accounts.role_account;
 
@@ -74,16 +120,61 @@ pub fn unpause_handler(ctx: Context<Unpause>) -> Result<()> {
         StablecoinError::Unauthorized
     );
 
-    config.is_paused = false;
+    let mask = resolve_pause_mask(args.mask)?;
+
+    let now = Clock::get()?.unix_timestamp;
+    enforce_pause_cooldown(config, role_account.roles, now)?;
+
+    config.pause_flags = effective_pause_flags(config.pause_flags) & !mask;
+    config.last_pause_change = now;
     config.audit_counter = config
         .audit_counter
         .checked_add(1)
         .ok_or(StablecoinError::Overflow)?;
+    config.record_action(ctx.accounts.pauser.key(), now);
 
     emit!(SystemUnpaused {
         config: config.key(),
         unpaused_by: ctx.accounts.pauser.key(),
-        timestamp: Clock::get()?.unix_timestamp,
+        timestamp: now,
+        pause_flags: config.pause_flags,
+    });
+    Ok(())
+}
+
+/// Second pause tier for incidents: while locked, freeze, thaw, blacklist and
+/// seize fail with SystemPaused for everyone but the master authority, so a
+/// compromised operator key cannot undo compliance actions. Independent of
+/// pause_flags.
+pub fn update_admin_lock_handler(
+    ctx: Context<UpdateAdminLock>,
+    args: UpdateAdminLockArgs,
+) -> Result<()> {
+    let config = &mut ctx.accounts.config;
+    let role_account = &ctx.accounts.role_account;
+
+    require!(
+        role_account.config == config.key(),
+        StablecoinError::Unauthorized
+    );
+    require!(
+        has_any_role(role_account.roles, ROLE_MASTER_AUTHORITY),
+        StablecoinError::Unauthorized
+    );
+
+    let now = Clock::get()?.unix_timestamp;
+    config.lock_admin_ops = args.lock_admin_ops;
+    config.audit_counter = config
+        .audit_counter
+        .checked_add(1)
+        .ok_or(StablecoinError::Overflow)?;
+    config.record_action(ctx.accounts.authority.key(), now);
+
+    emit!(AdminOpsLockUpdated {
+        config: config.key(),
+        lock_admin_ops: args.lock_admin_ops,
+        updated_by: ctx.accounts.authority.key(),
+        timestamp: now,
     });
     Ok(())
 }
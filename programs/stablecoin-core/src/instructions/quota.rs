@@ -0,0 +1,64 @@
+use anchor_lang::prelude::*;
+
+use crate::constants::{MIN_QUOTA_WINDOW_SECONDS, ROLE_MASTER_AUTHORITY};
+use crate::errors::StablecoinError;
+use crate::events::QuotaWindowUpdated;
+use crate::state::{RoleAccount, StablecoinConfig};
+use crate::utils::has_any_role;
+
+#[derive(AnchorSerialize, AnchorDeserialize, Clone)]
+pub struct UpdateQuotaWindowArgs {
+    pub quota_window_seconds: i64,
+}
+
+#[derive(Accounts)]
+pub struct UpdateQuotaWindow<'info> {
+    pub authority: Signer<'info>,
+
+    #[account(mut)]
+    pub config: Account<'info, StablecoinConfig>,
+
+    #[account(
+        seeds = [b"role", config.key().as_ref(), authority.key().as_ref()],
+        bump = role_account.bump
+    )]
+    pub role_account: Account<'info, RoleAccount>,
+}
+
+/// Minters keep their current window start; the new length applies from the
+/// next rollover check.
+pub fn handler(ctx: Context<UpdateQuotaWindow>, args: UpdateQuotaWindowArgs) -> Result<()> {
+    let config = &mut ctx.accounts.config;
+    let role_account = &ctx.accounts.role_account;
+
+    require!(
+        role_account.config == config.key(),
+        StablecoinError::Unauthorized
+    );
+    require!(
+        has_any_role(role_account.roles, ROLE_MASTER_AUTHORITY),
+        StablecoinError::Unauthorized
+    );
+    require!(
+        args.quota_window_seconds >= MIN_QUOTA_WINDOW_SECONDS,
+        StablecoinError::InvalidQuotaWindow
+    );
+
+    let previous_window_seconds = config.quota_window();
+    config.quota_window_seconds = args.quota_window_seconds;
+    config.audit_counter = config
+        .audit_counter
+        .checked_add(1)
+        .ok_or(StablecoinError::Overflow)?;
+    config.record_action(ctx.accounts.authority.key(), Clock::get()?.unix_timestamp);
+
+    emit!(QuotaWindowUpdated {
+        config: config.key(),
+        previous_window_seconds,
+        quota_window_seconds: args.quota_window_seconds,
+        updated_by: ctx.accounts.authority.key(),
+        timestamp: Clock::get()?.unix_timestamp,
+    });
+
+    Ok(())
+}
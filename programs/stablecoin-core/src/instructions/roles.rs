@@ -1,16 +1,21 @@
 use anchor_lang::prelude::*;
 
-use crate::constants::{ROLE_BLACKLISTER, ROLE_MASTER_AUTHORITY, ROLE_MINTER, ROLE_SEIZER};
+use crate::constants::{ROLE_MASTER_AUTHORITY, ROLE_MINTER, ROLE_ROLE_ADMIN};
 use crate::errors::StablecoinError;
 use crate::events::RoleUpdated;
 use crate::state::{RoleAccount, StablecoinConfig};
-use crate::utils::{has_any_role, require_valid_roles};
+use crate::utils::{
+    drops_master_authority, has_any_role, move_master_authority, renounce_roles,
+    require_assignable_roles, role_admin_may_update, role_changes,
+};
 
 #[derive(AnchorSerialize, AnchorDeserialize, Clone)]
 pub struct UpdateRolesArgs {
     pub target: Pubkey,
     pub roles: u8,
     pub mint_quota: Option<u64>,
+    /// Cap on everything the target may ever mint; None is unlimited.
+    pub lifetime_quota: Option<u64>,
 }
 
 #[derive(AnchorSerialize, AnchorDeserialize, Clone)]
@@ -68,6 +73,21 @@ pub struct UpdateMinter<'info> {
     pub target_role_account: Account<'info, RoleAccount>,
 }
 
+#[derive(Accounts)]
+pub struct RenounceRole<'info> {
+    #[account(mut)]
+    pub authority: Signer<'info>,
+
+    pub config: Account<'info, StablecoinConfig>,
+
+    #[account(
+        mut,
+        seeds = [b"role", config.key().as_ref(), authority.key().as_ref()],
+        bump = role_account.bump
+    )]
+    pub role_account: Account<'info, RoleAccount>,
+}
+
 #[derive(Accounts)]
 pub struct TransferAuthority<'info> {
     #[account(mut)]
@@ -99,7 +119,7 @@ pub struct TransferAuthority<'info> {
 }
 
 pub fn update_roles_handler(ctx: Context<UpdateRoles>, args: UpdateRolesArgs) -> Result<()> {
-    let config = &ctx.accounts.config;
+    let config = &mut ctx.accounts.config;
     let role_account = &ctx.accounts.role_account;
 
     require!(
@@ -107,39 +127,67 @@ pub fn update_roles_handler(ctx: Context<UpdateRoles>, args: UpdateRolesArgs) ->
         StablecoinError::Unauthorized
     );
     require!(
-        has_any_role(role_account.roles, ROLE_MASTER_AUTHORITY),
+        has_any_role(role_account.roles, ROLE_MASTER_AUTHORITY | ROLE_ROLE_ADMIN),
         StablecoinError::Unauthorized
     );
-    require_valid_roles(args.roles)?;
+    require_assignable_roles(args.roles, config.features.transfer_hook())?;
     require!(
         args.target == ctx.accounts.target.key(),
         StablecoinError::Unauthorized
     );
 
-    if !config.features.transfer_hook {
+    let target_role_account = &mut ctx.accounts.target_role_account;
+    // Role admins hand out operational roles only; the master bit, the role
+    // admin bit and minter quotas stay with the master authority.
+    let master_caller = has_any_role(role_account.roles, ROLE_MASTER_AUTHORITY);
+    if !master_caller {
+        require!(
+            role_admin_may_update(target_role_account.roles, args.roles),
+            StablecoinError::Unauthorized
+        );
         require!(
-            args.roles & (ROLE_BLACKLISTER | ROLE_SEIZER) == 0,
-            StablecoinError::FeatureNotEnabled
+            args.roles & ROLE_MINTER == 0
+                || (args.mint_quota == target_role_account.mint_quota
+                    && args.lifetime_quota == target_role_account.lifetime_quota),
+            StablecoinError::Unauthorized
         );
     }
-
-    let target_role_account = &mut ctx.accounts.target_role_account;
-    target_role_account.config = config.key();
-    target_role_account.authority = ctx.accounts.target.key();
+    // Clearing the master bit from the signer or the recorded authority would
+    // leave nobody able to administer the config.
+    require!(
+        !(drops_master_authority(target_role_account.roles, args.roles)
+            && (args.target == ctx.accounts.authority.key() || args.target == config.authority)),
+        StablecoinError::LastMasterAuthority
+    );
+    // A freshly created role account is zeroed, so its previous roles are 0.
+    let (added_roles, removed_roles) = role_changes(target_role_account.roles, args.roles);
+    target_role_account.init_if_new(
+        config.key(),
+        ctx.accounts.target.key(),
+        ctx.bumps.target_role_account,
+    );
     target_role_account.roles = args.roles;
     if args.roles & ROLE_MINTER != 0 {
         target_role_account.mint_quota = args.mint_quota;
+        target_role_account.lifetime_quota = args.lifetime_quota;
     } else {
         target_role_account.mint_quota = None;
+        target_role_account.lifetime_quota = None;
     }
-    target_role_account.minted_current_window = 0;
-    target_role_account.window_start = 0;
-    target_role_account.bump = ctx.bumps.target_role_account;
+    // A role admin reshuffling a minter's other roles must not hand it a
+    // fresh quota window.
+    if master_caller {
+        target_role_account.minted_current_window = 0;
+        target_role_account.window_start = 0;
+    }
+    config.record_action(ctx.accounts.authority.key(), Clock::get()?.unix_timestamp);
 
     emit!(RoleUpdated {
         config: config.key(),
         target: ctx.accounts.target.key(),
         new_roles: args.roles,
+        added_roles,
+        removed_roles,
         updated_by: ctx.accounts.authority.key(),
         timestamp: Clock::get()?.unix_timestamp,
     });
@@ -147,7 +195,7 @@ pub fn update_roles_handler(ctx: Context<UpdateRoles>, args: UpdateRolesArgs) ->
 }
 
 pub fn update_minter_handler(ctx: Context<UpdateMinter>, args: UpdateMinterArgs) -> Result<()> {
-    let config = &ctx.accounts.config;
+    let config = &mut ctx.accounts.config;
     let role_account = &ctx.accounts.role_account;
     let target_role_account = &mut ctx.accounts.target_role_account;
 
@@ -165,14 +213,55 @@ pub fn update_minter_handler(ctx: Context<UpdateMinter>, args: UpdateMinterArgs)
     );
 
     target_role_account.mint_quota = Some(args.new_quota);
+    config.record_action(ctx.accounts.authority.key(), Clock::get()?.unix_timestamp);
 
     emit!(RoleUpdated {
         config: config.key(),
         target: target_role_account.authority,
         new_roles: target_role_account.roles,
+        added_roles: 0,
+        removed_roles: 0,
+        updated_by: ctx.accounts.authority.key(),
+        timestamp: Clock::get()?.unix_timestamp,
+    });
+    Ok(())
+}
+
+pub fn renounce_role_handler(ctx: Context<RenounceRole>, roles: u8) -> Result<()> {
+    let config = &ctx.accounts.config;
+    let role_account = &mut ctx.accounts.role_account;
+
+    require!(
+        role_account.config == config.key(),
+        StablecoinError::Unauthorized
+    );
+
+    let previous_roles = role_account.roles;
+    let new_roles = renounce_roles(previous_roles, roles)?;
+    let (added_roles, removed_roles) = role_changes(previous_roles, new_roles);
+    role_account.roles = new_roles;
+    // A minter bit granted again later starts without the old caps or window.
+    if new_roles & ROLE_MINTER == 0 {
+        role_account.mint_quota = None;
+        role_account.lifetime_quota = None;
+        role_account.minted_current_window = 0;
+        role_account.window_start = 0;
+    }
+
+    emit!(RoleUpdated {
+        config: config.key(),
+        target: ctx.accounts.authority.key(),
+        new_roles,
+        added_roles,
+        removed_roles,
         updated_by: ctx.accounts.authority.key(),
         timestamp: Clock::get()?.unix_timestamp,
     });
+
+    // Nothing left to hold: close the account and refund its rent.
+    if new_roles == 0 {
+        role_account.close(ctx.accounts.authority.to_account_info())?;
+    }
     Ok(())
 }
 
@@ -194,14 +283,24 @@ pub fn transfer_authority_handler(ctx: Context<TransferAuthority>) -> Result<()>
         StablecoinError::SelfTransfer
     );
 
-    current_role_account.roles &= !ROLE_MASTER_AUTHORITY;
+    let (current_roles, new_roles) =
+        move_master_authority(current_role_account.roles, new_role_account.roles);
+    current_role_account.roles = current_roles;
 
-    new_role_account.config = config.key();
-    new_role_account.authority = ctx.accounts.new_authority.key();
-    new_role_account.roles |= ROLE_MASTER_AUTHORITY;
-    new_role_account.bump = ctx.bumps.new_role_account;
+    // The new authority may already hold roles; its account keeps its bump
+    // and quotas and only gains the master bit.
+    new_role_account.init_if_new(
+        config.key(),
+        ctx.accounts.new_authority.key(),
+        ctx.bumps.new_role_account,
+    );
+    new_role_account.roles = new_roles;
 
     config.authority = ctx.accounts.new_authority.key();
+    config.record_action(
+        ctx.accounts.current_authority.key(),
+        Clock::get()?.unix_timestamp,
+    );
 
     emit!(crate::events::AuthorityTransferred {
         config: config.key(),
@@ -6,9 +6,9 @@ use anchor_spl::token_2022::spl_token_2022::state::AccountState;
 use anchor_spl::token_2022::{self, Token2022};
 use anchor_spl::token_interface::{Mint, TokenAccount};
 
-use crate::constants::{ROLE_MASTER_AUTHORITY, ROLE_SEIZER};
+use crate::constants::{EVENT_SCHEMA_VERSION, ROLE_MASTER_AUTHORITY, ROLE_SEIZER};
 use crate::errors::StablecoinError;
-use crate::events::TokensSeized;
+use crate::events::{TokensSeized, TokensSeizedAndBurned};
 use crate::state::{BlacklistEntry, RoleAccount, StablecoinConfig};
 use crate::utils::has_any_role;
 
@@ -51,7 +51,38 @@ pub struct Seize<'info> {
     pub token_2022_program: Program<'info, Token2022>,
 }
 
-pub fn handler(ctx: Context<Seize>) -> Result<()> {
+#[derive(Accounts)]
+pub struct SeizeAndBurn<'info> {
+    pub seizer: Signer<'info>,
+
+    #[account(mut)]
+    pub config: Account<'info, StablecoinConfig>,
+
+    #[account(
+        seeds = [b"role", config.key().as_ref(), seizer.key().as_ref()],
+        bump = role_account.bump
+    )]
+    pub role_account: Account<'info, RoleAccount>,
+
+    #[account(mut)]
+    pub mint: InterfaceAccount<'info, Mint>,
+
+    #[account(mut)]
+    pub target_ata: InterfaceAccount<'info, TokenAccount>,
+
+    pub blacklist_entry: Account<'info, BlacklistEntry>,
+
+    pub token_2022_program: Program<'info, Token2022>,
+}
+
+/// Moves `amount` (the full balance when None) from a frozen, blacklisted
+/// account to the treasury. Any `remaining_accounts` are forwarded to the
+/// hook after its fixed accounts; mints with the transfer cooldown need the
+/// target owner's LastTransfer PDA there, writable.
+pub fn handler<'info>(
+    ctx: Context<'_, '_, 'info, 'info, Seize<'info>>,
+    amount: Option<u64>,
+) -> Result<()> {
     let config_info = ctx.accounts.config.to_account_info();
     let config = &mut ctx.accounts.config;
     let role_account = &ctx.accounts.role_account;
@@ -68,7 +99,11 @@ pub fn handler(ctx: Context<Seize>) -> Result<()> {
         StablecoinError::Unauthorized
     );
     require!(
-        config.features.permanent_delegate,
+        config.admin_ops_allowed(role_account.roles),
+        StablecoinError::SystemPaused
+    );
+    require!(
+        config.features.permanent_delegate(),
         StablecoinError::FeatureNotEnabled
     );
     require!(
@@ -76,7 +111,7 @@ pub fn handler(ctx: Context<Seize>) -> Result<()> {
         StablecoinError::Unauthorized
     );
     require!(
-        blacklist_entry.is_active,
+        blacklist_entry.is_in_effect(Clock::get()?.unix_timestamp),
         StablecoinError::TargetNotBlacklisted
     );
     require!(
@@ -89,13 +124,21 @@ pub fn handler(ctx: Context<Seize>) -> Result<()> {
         StablecoinError::AccountNotFrozen
     );
     require!(config.mint == mint.key(), StablecoinError::Unauthorized);
+    require!(
+        target_ata.key() != ctx.accounts.treasury_ata.key(),
+        StablecoinError::SeizeToSelf
+    );
 
-    let amount = target_ata.amount;
+    let amount = amount.unwrap_or(target_ata.amount);
+    require!(
+        amount <= target_ata.amount,
+        StablecoinError::InsufficientBalance
+    );
 
     let mint_key = mint.key();
     let signer_seeds: &[&[u8]] = &[b"stablecoin", mint_key.as_ref(), &[config.bump]];
     let signer_seeds_arr = [signer_seeds];
-    let transfer_account_infos = vec![
+    let mut transfer_account_infos = vec![
         target_ata.to_account_info(),
         mint.to_account_info(),
         ctx.accounts.treasury_ata.to_account_info(),
@@ -125,6 +168,14 @@ pub fn handler(ctx: Context<Seize>) -> Result<()> {
         AccountMeta::new_readonly(ctx.accounts.destination_blacklist_entry.key(), false),
         AccountMeta::new_readonly(ctx.accounts.transfer_hook_program.key(), false),
     ]);
+    for account in ctx.remaining_accounts {
+        transfer_ix.accounts.push(if account.is_writable {
+            AccountMeta::new(account.key(), false)
+        } else {
+            AccountMeta::new_readonly(account.key(), false)
+        });
+        transfer_account_infos.push(account.clone());
+    }
     let thaw_accounts = token_2022::ThawAccount {
         account: target_ata.to_account_info(),
         mint: mint.to_account_info(),
@@ -149,10 +200,15 @@ pub fn handler(ctx: Context<Seize>) -> Result<()> {
     );
     token_2022::freeze_account(freeze_ctx)?;
 
+    config.total_seized = config
+        .total_seized
+        .checked_add(amount)
+        .ok_or(StablecoinError::Overflow)?;
     config.audit_counter = config
         .audit_counter
         .checked_add(1)
         .ok_or(StablecoinError::Overflow)?;
+    config.record_action(ctx.accounts.seizer.key(), Clock::get()?.unix_timestamp);
 
     emit!(TokensSeized {
         config: config.key(),
@@ -161,6 +217,124 @@ pub fn handler(ctx: Context<Seize>) -> Result<()> {
         amount,
         seized_by: ctx.accounts.seizer.key(),
         timestamp: Clock::get()?.unix_timestamp,
+        schema_version: EVENT_SCHEMA_VERSION,
+        total_seized: config.total_seized,
+    });
+    Ok(())
+}
+
+/// Destroys the full balance of a frozen, blacklisted account instead of
+/// moving it to a treasury. Burns never invoke the transfer hook, so none of
+/// its accounts are needed.
+pub fn seize_and_burn_handler(ctx: Context<SeizeAndBurn>) -> Result<()> {
+    let config = &mut ctx.accounts.config;
+    let role_account = &ctx.accounts.role_account;
+    let mint = &ctx.accounts.mint;
+    let target_ata = &ctx.accounts.target_ata;
+    let blacklist_entry = &ctx.accounts.blacklist_entry;
+
+    require!(
+        role_account.config == config.key(),
+        StablecoinError::Unauthorized
+    );
+    require!(
+        has_any_role(role_account.roles, ROLE_MASTER_AUTHORITY | ROLE_SEIZER),
+        StablecoinError::Unauthorized
+    );
+    require!(
+        config.admin_ops_allowed(role_account.roles),
+        StablecoinError::SystemPaused
+    );
+    require!(
+        config.features.permanent_delegate(),
+        StablecoinError::FeatureNotEnabled
+    );
+    require!(
+        blacklist_entry.config == config.key(),
+        StablecoinError::Unauthorized
+    );
+    require!(
+        blacklist_entry.is_in_effect(Clock::get()?.unix_timestamp),
+        StablecoinError::TargetNotBlacklisted
+    );
+    require!(
+        blacklist_entry.wallet == target_ata.owner,
+        StablecoinError::Unauthorized
+    );
+    require!(target_ata.mint == mint.key(), StablecoinError::Unauthorized);
+    require!(
+        target_ata.state == AccountState::Frozen,
+        StablecoinError::AccountNotFrozen
+    );
+    require!(config.mint == mint.key(), StablecoinError::Unauthorized);
+
+    let amount = target_ata.amount;
+
+    let mint_key = mint.key();
+    let signer_seeds: &[&[u8]] = &[b"stablecoin", mint_key.as_ref(), &[config.bump]];
+    let signer_seeds_arr = [signer_seeds];
+    let thaw_accounts = token_2022::ThawAccount {
+        account: target_ata.to_account_info(),
+        mint: mint.to_account_info(),
+        authority: config.to_account_info(),
+    };
+    let thaw_ctx = CpiContext::new_with_signer(
+        ctx.accounts.token_2022_program.to_account_info(),
+        thaw_accounts,
+        &signer_seeds_arr,
+    );
+    token_2022::thaw_account(thaw_ctx)?;
+    // The config PDA is the permanent delegate, so it may burn from any account.
+    let burn_accounts = token_2022::Burn {
+        mint: mint.to_account_info(),
+        from: target_ata.to_account_info(),
+        authority: config.to_account_info(),
+    };
+    let burn_ctx = CpiContext::new_with_signer(
+        ctx.accounts.token_2022_program.to_account_info(),
+        burn_accounts,
+        &signer_seeds_arr,
+    );
+    token_2022::burn(burn_ctx, amount)?;
+    let freeze_accounts = token_2022::FreezeAccount {
+        account: target_ata.to_account_info(),
+        mint: mint.to_account_info(),
+        authority: config.to_account_info(),
+    };
+    let freeze_ctx = CpiContext::new_with_signer(
+        ctx.accounts.token_2022_program.to_account_info(),
+        freeze_accounts,
+        &signer_seeds_arr,
+    );
+    token_2022::freeze_account(freeze_ctx)?;
+
+    config.total_burned = config
+        .total_burned
+        .checked_add(amount)
+        .ok_or(StablecoinError::Overflow)?;
+    config.total_seized = config
+        .total_seized
+        .checked_add(amount)
+        .ok_or(StablecoinError::Overflow)?;
+    config.audit_counter = config
+        .audit_counter
+        .checked_add(1)
+        .ok_or(StablecoinError::Overflow)?;
+    let now = Clock::get()?.unix_timestamp;
+    config.record_action(ctx.accounts.seizer.key(), now);
+
+    let new_total_supply = mint
+        .supply
+        .checked_sub(amount)
+        .ok_or(StablecoinError::Overflow)?;
+
+    emit!(TokensSeizedAndBurned {
+        config: config.key(),
+        from_account: target_ata.key(),
+        amount,
+        new_total_supply,
+        seized_by: ctx.accounts.seizer.key(),
+        timestamp: now,
     });
     Ok(())
 }
@@ -0,0 +1,61 @@
+use anchor_lang::prelude::*;
+
+use crate::constants::ROLE_MASTER_AUTHORITY;
+use crate::errors::StablecoinError;
+use crate::events::SelfTransferUpdated;
+use crate::state::{RoleAccount, StablecoinConfig};
+use crate::utils::has_any_role;
+
+#[derive(AnchorSerialize, AnchorDeserialize, Clone)]
+pub struct UpdateSelfTransferArgs {
+    pub allow_self_transfer: bool,
+}
+
+#[derive(Accounts)]
+pub struct UpdateSelfTransfer<'info> {
+    pub authority: Signer<'info>,
+
+    #[account(mut)]
+    pub config: Account<'info, StablecoinConfig>,
+
+    #[account(
+        seeds = [b"role", config.key().as_ref(), authority.key().as_ref()],
+        bump = role_account.bump
+    )]
+    pub role_account: Account<'info, RoleAccount>,
+}
+
+/// The exemption is applied by the transfer hook, so it needs a hook-enabled mint.
+pub fn handler(ctx: Context<UpdateSelfTransfer>, args: UpdateSelfTransferArgs) -> Result<()> {
+    let config = &mut ctx.accounts.config;
+    let role_account = &ctx.accounts.role_account;
+
+    require!(
+        role_account.config == config.key(),
+        StablecoinError::Unauthorized
+    );
+    require!(
+        has_any_role(role_account.roles, ROLE_MASTER_AUTHORITY),
+        StablecoinError::Unauthorized
+    );
+    require!(
+        config.features.transfer_hook(),
+        StablecoinError::FeatureNotEnabled
+    );
+
+    config.allow_self_transfer = args.allow_self_transfer;
+    config.audit_counter = config
+        .audit_counter
+        .checked_add(1)
+        .ok_or(StablecoinError::Overflow)?;
+    config.record_action(ctx.accounts.authority.key(), Clock::get()?.unix_timestamp);
+
+    emit!(SelfTransferUpdated {
+        config: config.key(),
+        allow_self_transfer: args.allow_self_transfer,
+        updated_by: ctx.accounts.authority.key(),
+        timestamp: Clock::get()?.unix_timestamp,
+    });
+
+    Ok(())
+}
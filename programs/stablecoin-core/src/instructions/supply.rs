@@ -0,0 +1,163 @@
+use anchor_lang::prelude::*;
+use anchor_spl::token_2022;
+use anchor_spl::token_2022::spl_token_2022::instruction::AuthorityType;
+use anchor_spl::token_interface::{Mint, TokenInterface};
+
+use crate::constants::ROLE_MASTER_AUTHORITY;
+use crate::errors::StablecoinError;
+use crate::events::{MaxSupplyUpdated, SupplyFrozen};
+use crate::state::{RoleAccount, StablecoinConfig};
+use crate::utils::has_any_role;
+
+#[derive(AnchorSerialize, AnchorDeserialize, Clone)]
+pub struct UpdateMaxSupplyArgs {
+    pub max_supply: u64,
+}
+
+#[derive(Accounts)]
+pub struct UpdateMaxSupply<'info> {
+    pub authority: Signer<'info>,
+
+    #[account(mut)]
+    pub config: Account<'info, StablecoinConfig>,
+
+    #[account(
+        seeds = [b"role", config.key().as_ref(), authority.key().as_ref()],
+        bump = role_account.bump
+    )]
+    pub role_account: Account<'info, RoleAccount>,
+
+    pub mint: InterfaceAccount<'info, Mint>,
+}
+
+#[derive(AnchorSerialize, AnchorDeserialize, Clone)]
+pub struct FreezeSupplyArgs {
+    /// Also clear the mint's mint authority through Token-2022.
+    pub null_mint_authority: bool,
+}
+
+#[derive(Accounts)]
+pub struct FreezeSupply<'info> {
+    pub authority: Signer<'info>,
+
+    #[account(mut)]
+    pub config: Account<'info, StablecoinConfig>,
+
+    #[account(
+        seeds = [b"role", config.key().as_ref(), authority.key().as_ref()],
+        bump = role_account.bump
+    )]
+    pub role_account: Account<'info, RoleAccount>,
+
+    #[account(mut)]
+    pub mint: InterfaceAccount<'info, Mint>,
+
+    #[account(address = config.token_program_id() @ StablecoinError::InvalidTokenProgram)]
+    pub token_program: Interface<'info, TokenInterface>,
+}
+
+/// A cap may be introduced or lowered, but never raised or set below supply.
+pub(crate) fn validate_max_supply(current: Option<u64>, supply: u64, new_cap: u64) -> Result<()> {
+    require!(new_cap >= supply, StablecoinError::InvalidMaxSupply);
+    if let Some(current) = current {
+        require!(new_cap <= current, StablecoinError::InvalidMaxSupply);
+    }
+    Ok(())
+}
+
+pub fn handler(ctx: Context<UpdateMaxSupply>, args: UpdateMaxSupplyArgs) -> Result<()> {
+    let config = &mut ctx.accounts.config;
+    let role_account = &ctx.accounts.role_account;
+
+    require!(
+        role_account.config == config.key(),
+        StablecoinError::Unauthorized
+    );
+    require!(
+        has_any_role(role_account.roles, ROLE_MASTER_AUTHORITY),
+        StablecoinError::Unauthorized
+    );
+    require!(
+        config.mint == ctx.accounts.mint.key(),
+        StablecoinError::Unauthorized
+    );
+    validate_max_supply(config.max_supply, ctx.accounts.mint.supply, args.max_supply)?;
+
+    let previous_max_supply = config.max_supply;
+    config.max_supply = Some(args.max_supply);
+    config.audit_counter = config
+        .audit_counter
+        .checked_add(1)
+        .ok_or(StablecoinError::Overflow)?;
+    config.record_action(ctx.accounts.authority.key(), Clock::get()?.unix_timestamp);
+
+    emit!(MaxSupplyUpdated {
+        config: config.key(),
+        previous_max_supply,
+        max_supply: args.max_supply,
+        updated_by: ctx.accounts.authority.key(),
+        timestamp: Clock::get()?.unix_timestamp,
+    });
+
+    Ok(())
+}
+
+/// Locks minting for good. The lock lives in the config, so the mint
+/// authority stays with the config PDA unless null_mint_authority asks
+/// Token-2022 to clear it as well; the freeze authority is never touched. A
+/// locked config may be called again only to null the mint authority.
+pub fn freeze_supply_handler(ctx: Context<FreezeSupply>, args: FreezeSupplyArgs) -> Result<()> {
+    let config = &ctx.accounts.config;
+    let role_account = &ctx.accounts.role_account;
+    let mint = &ctx.accounts.mint;
+
+    require!(
+        role_account.config == config.key(),
+        StablecoinError::Unauthorized
+    );
+    require!(
+        has_any_role(role_account.roles, ROLE_MASTER_AUTHORITY),
+        StablecoinError::Unauthorized
+    );
+    require!(config.mint == mint.key(), StablecoinError::Unauthorized);
+    require!(
+        !config.minting_locked || args.null_mint_authority,
+        StablecoinError::MintingLocked
+    );
+
+    if args.null_mint_authority {
+        let mint_key = mint.key();
+        let signer_seeds: &[&[u8]] = &[b"stablecoin", mint_key.as_ref(), &[config.bump]];
+        let signer_seeds_arr = [signer_seeds];
+        let cpi_accounts = token_2022::SetAuthority {
+            current_authority: config.to_account_info(),
+            account_or_mint: mint.to_account_info(),
+        };
+        let cpi_ctx = CpiContext::new_with_signer(
+            ctx.accounts.token_program.to_account_info(),
+            cpi_accounts,
+            &signer_seeds_arr,
+        );
+        token_2022::set_authority(cpi_ctx, AuthorityType::MintTokens, None)?;
+    }
+
+    let config = &mut ctx.accounts.config;
+    config.minting_locked = true;
+    config.audit_counter = config
+        .audit_counter
+        .checked_add(1)
+        .ok_or(StablecoinError::Overflow)?;
+    let now = Clock::get()?.unix_timestamp;
+    config.record_action(ctx.accounts.authority.key(), now);
+
+    emit!(SupplyFrozen {
+        config: config.key(),
+        mint: ctx.accounts.mint.key(),
+        total_supply: ctx.accounts.mint.supply,
+        mint_authority_nulled: args.null_mint_authority,
+        frozen_by: ctx.accounts.authority.key(),
+        timestamp: now,
+    });
+
+    Ok(())
+}
@@ -0,0 +1,68 @@
+use anchor_lang::prelude::*;
+
+use crate::constants::ROLE_MASTER_AUTHORITY;
+use crate::errors::StablecoinError;
+use crate::events::TransferLimitUpdated;
+use crate::state::{RoleAccount, StablecoinConfig};
+use crate::utils::has_any_role;
+
+#[derive(AnchorSerialize, AnchorDeserialize, Clone)]
+pub struct UpdateTransferLimitArgs {
+    /// None removes the limit.
+    pub max_transfer_amount: Option<u64>,
+}
+
+#[derive(Accounts)]
+pub struct UpdateTransferLimit<'info> {
+    pub authority: Signer<'info>,
+
+    #[account(mut)]
+    pub config: Account<'info, StablecoinConfig>,
+
+    #[account(
+        seeds = [b"role", config.key().as_ref(), authority.key().as_ref()],
+        bump = role_account.bump
+    )]
+    pub role_account: Account<'info, RoleAccount>,
+}
+
+/// The limit is enforced by the transfer hook, so it needs a hook-enabled mint.
+pub fn handler(ctx: Context<UpdateTransferLimit>, args: UpdateTransferLimitArgs) -> Result<()> {
+    let config = &mut ctx.accounts.config;
+    let role_account = &ctx.accounts.role_account;
+
+    require!(
+        role_account.config == config.key(),
+        StablecoinError::Unauthorized
+    );
+    require!(
+        has_any_role(role_account.roles, ROLE_MASTER_AUTHORITY),
+        StablecoinError::Unauthorized
+    );
+    require!(
+        config.features.transfer_hook(),
+        StablecoinError::FeatureNotEnabled
+    );
+    require!(
+        args.max_transfer_amount != Some(0),
+        StablecoinError::InvalidTransferLimit
+    );
+
+    let previous_max_transfer_amount = config.max_transfer_amount;
+    config.max_transfer_amount = args.max_transfer_amount;
+    config.audit_counter = config
+        .audit_counter
+        .checked_add(1)
+        .ok_or(StablecoinError::Overflow)?;
+    config.record_action(ctx.accounts.authority.key(), Clock::get()?.unix_timestamp);
+
+    emit!(TransferLimitUpdated {
+        config: config.key(),
+        previous_max_transfer_amount,
+        max_transfer_amount: args.max_transfer_amount,
+        updated_by: ctx.accounts.authority.key(),
+        timestamp: Clock::get()?.unix_timestamp,
+    });
+
+    Ok(())
+}
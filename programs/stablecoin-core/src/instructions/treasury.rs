@@ -0,0 +1,63 @@
+use anchor_lang::prelude::*;
+
+use crate::constants::ROLE_MASTER_AUTHORITY;
+use crate::errors::StablecoinError;
+use crate::events::TreasuryUpdated;
+use crate::instructions::mint::require_allowed_recipient;
+use crate::state::{RoleAccount, StablecoinConfig};
+use crate::utils::has_any_role;
+
+#[derive(AnchorSerialize, AnchorDeserialize, Clone)]
+pub struct UpdateTreasuryArgs {
+    /// Wallet owning the treasury token account; None clears it.
+    pub treasury: Option<Pubkey>,
+}
+
+#[derive(Accounts)]
+pub struct UpdateTreasury<'info> {
+    pub authority: Signer<'info>,
+
+    #[account(mut)]
+    pub config: Account<'info, StablecoinConfig>,
+
+    #[account(
+        seeds = [b"role", config.key().as_ref(), authority.key().as_ref()],
+        bump = role_account.bump
+    )]
+    pub role_account: Account<'info, RoleAccount>,
+}
+
+pub fn handler(ctx: Context<UpdateTreasury>, args: UpdateTreasuryArgs) -> Result<()> {
+    let config = &mut ctx.accounts.config;
+    let role_account = &ctx.accounts.role_account;
+
+    require!(
+        role_account.config == config.key(),
+        StablecoinError::Unauthorized
+    );
+    require!(
+        has_any_role(role_account.roles, ROLE_MASTER_AUTHORITY),
+        StablecoinError::Unauthorized
+    );
+    if let Some(treasury) = args.treasury {
+        require_allowed_recipient(&treasury, &config.key(), &config.mint)?;
+    }
+
+    let previous_treasury = config.treasury;
+    config.treasury = args.treasury;
+    config.audit_counter = config
+        .audit_counter
+        .checked_add(1)
+        .ok_or(StablecoinError::Overflow)?;
+    config.record_action(ctx.accounts.authority.key(), Clock::get()?.unix_timestamp);
+
+    emit!(TreasuryUpdated {
+        config: config.key(),
+        previous_treasury,
+        treasury: args.treasury,
+        updated_by: ctx.accounts.authority.key(),
+        timestamp: Clock::get()?.unix_timestamp,
+    });
+
+    Ok(())
+}
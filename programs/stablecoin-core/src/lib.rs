@@ -4,33 +4,71 @@ use anchor_lang::prelude::*;
 
 pub mod constants;
 mod errors;
-mod events;
-mod instructions;
+pub mod events;
+pub mod instructions;
 pub mod state;
 mod utils;
 
-use crate::instructions::blacklist::{AddToBlacklist, RemoveFromBlacklist};
+use crate::instructions::allowlist::{AddToAllowlist, RemoveFromAllowlist};
+use crate::instructions::blacklist::{
+    AddToBlacklist, CloseBlacklistEntry, RemoveFromBlacklist, UpdateBlacklistEntry,
+};
 use crate::instructions::burn::Burn;
+use crate::instructions::default_state::UpdateDefaultAccountState;
 use crate::instructions::freeze::{FreezeAccount, ThawAccount};
+use crate::instructions::hook_program::{EnableTransferHook, UpdateTransferHookProgram};
 use crate::instructions::initialize::Initialize;
+use crate::instructions::metadata::{UpdateMetadata, UpdateMetadataField};
+use crate::instructions::migrate::MigrateConfig;
 use crate::instructions::mint::MintTokens;
-use crate::instructions::pause::{Pause, Unpause};
-use crate::instructions::roles::{TransferAuthority, UpdateMinter, UpdateRoles};
-use crate::instructions::seize::Seize;
+use crate::instructions::mint_batch::MintBatch;
+use crate::instructions::mint_request::{ApproveMint, CancelMint, ProposeMint, UpdateMintApproval};
+use crate::instructions::pause::{Pause, Unpause, UpdateAdminLock};
+use crate::instructions::quota::UpdateQuotaWindow;
+use crate::instructions::roles::{RenounceRole, TransferAuthority, UpdateMinter, UpdateRoles};
+use crate::instructions::seize::{Seize, SeizeAndBurn};
+use crate::instructions::self_transfer::UpdateSelfTransfer;
+use crate::instructions::supply::{FreezeSupply, UpdateMaxSupply};
+use crate::instructions::transfer_limit::UpdateTransferLimit;
+use crate::instructions::treasury::UpdateTreasury;
 
+use crate::instructions::allowlist::__client_accounts_add_to_allowlist;
+use crate::instructions::allowlist::__client_accounts_remove_from_allowlist;
 use crate::instructions::blacklist::__client_accounts_add_to_blacklist;
+use crate::instructions::blacklist::__client_accounts_close_blacklist_entry;
 use crate::instructions::blacklist::__client_accounts_remove_from_blacklist;
+use crate::instructions::blacklist::__client_accounts_update_blacklist_entry;
 use crate::instructions::burn::__client_accounts_burn;
+use crate::instructions::default_state::__client_accounts_update_default_account_state;
 use crate::instructions::freeze::__client_accounts_freeze_account;
 use crate::instructions::freeze::__client_accounts_thaw_account;
+use crate::instructions::hook_program::__client_accounts_enable_transfer_hook;
+use crate::instructions::hook_program::__client_accounts_update_transfer_hook_program;
 use crate::instructions::initialize::__client_accounts_initialize;
+use crate::instructions::metadata::__client_accounts_update_metadata;
+use crate::instructions::metadata::__client_accounts_update_metadata_field;
+use crate::instructions::migrate::__client_accounts_migrate_config;
 use crate::instructions::mint::__client_accounts_mint_tokens;
+use crate::instructions::mint_batch::__client_accounts_mint_batch;
+use crate::instructions::mint_request::__client_accounts_approve_mint;
+use crate::instructions::mint_request::__client_accounts_cancel_mint;
+use crate::instructions::mint_request::__client_accounts_propose_mint;
+use crate::instructions::mint_request::__client_accounts_update_mint_approval;
 use crate::instructions::pause::__client_accounts_pause;
 use crate::instructions::pause::__client_accounts_unpause;
+use crate::instructions::pause::__client_accounts_update_admin_lock;
+use crate::instructions::quota::__client_accounts_update_quota_window;
+use crate::instructions::roles::__client_accounts_renounce_role;
 use crate::instructions::roles::__client_accounts_transfer_authority;
 use crate::instructions::roles::__client_accounts_update_minter;
 use crate::instructions::roles::__client_accounts_update_roles;
 use crate::instructions::seize::__client_accounts_seize;
+use crate::instructions::seize::__client_accounts_seize_and_burn;
+use crate::instructions::self_transfer::__client_accounts_update_self_transfer;
+use crate::instructions::supply::__client_accounts_freeze_supply;
+use crate::instructions::supply::__client_accounts_update_max_supply;
+use crate::instructions::transfer_limit::__client_accounts_update_transfer_limit;
+use crate::instructions::treasury::__client_accounts_update_treasury;
 
 declare_id!("5T8qkjgJVWcUVza36JVFq3GCiKwAXhunKc8NY2nNbtiZ");
 
@@ -49,6 +87,35 @@ pub mod stablecoin_core {
         instructions::mint::handler(ctx, amount)
     }
 
+    pub fn mint_batch<'info>(
+        ctx: Context<'_, '_, 'info, 'info, MintBatch<'info>>,
+        entries: Vec<instructions::mint_batch::MintBatchEntry>,
+    ) -> Result<()> {
+        instructions::mint_batch::handler(ctx, entries)
+    }
+
+    pub fn propose_mint(
+        ctx: Context<ProposeMint>,
+        args: instructions::mint_request::ProposeMintArgs,
+    ) -> Result<()> {
+        instructions::mint_request::propose_handler(ctx, args)
+    }
+
+    pub fn approve_mint(ctx: Context<ApproveMint>) -> Result<()> {
+        instructions::mint_request::approve_handler(ctx)
+    }
+
+    pub fn cancel_mint(ctx: Context<CancelMint>) -> Result<()> {
+        instructions::mint_request::cancel_handler(ctx)
+    }
+
+    pub fn update_mint_approval(
+        ctx: Context<UpdateMintApproval>,
+        args: instructions::mint_request::UpdateMintApprovalArgs,
+    ) -> Result<()> {
+        instructions::mint_request::update_approval_handler(ctx, args)
+    }
+
     pub fn burn(ctx: Context<Burn>, amount: u64) -> Result<()> {
         instructions::burn::handler(ctx, amount)
     }
@@ -61,12 +128,19 @@ pub mod stablecoin_core {
         instructions::freeze::thaw_handler(ctx)
     }
 
-    pub fn pause(ctx: Context<Pause>) -> Result<()> {
-        instructions::pause::pause_handler(ctx)
+    pub fn pause(ctx: Context<Pause>, args: instructions::pause::PauseArgs) -> Result<()> {
+        instructions::pause::pause_handler(ctx, args)
+    }
+
+    pub fn unpause(ctx: Context<Unpause>, args: instructions::pause::PauseArgs) -> Result<()> {
+        instructions::pause::unpause_handler(ctx, args)
     }
 
-    pub fn unpause(ctx: Context<Unpause>) -> Result<()> {
-        instructions::pause::unpause_handler(ctx)
+    pub fn update_admin_lock(
+        ctx: Context<UpdateAdminLock>,
+        args: instructions::pause::UpdateAdminLockArgs,
+    ) -> Result<()> {
+        instructions::pause::update_admin_lock_handler(ctx, args)
     }
 
     pub fn update_roles(
@@ -83,6 +157,10 @@ pub mod stablecoin_core {
         instructions::roles::update_minter_handler(ctx, args)
     }
 
+    pub fn renounce_role(ctx: Context<RenounceRole>, roles: u8) -> Result<()> {
+        instructions::roles::renounce_role_handler(ctx, roles)
+    }
+
     pub fn transfer_authority(ctx: Context<TransferAuthority>) -> Result<()> {
         instructions::roles::transfer_authority_handler(ctx)
     }
@@ -94,11 +172,115 @@ pub mod stablecoin_core {
         instructions::blacklist::add_handler(ctx, args)
     }
 
+    pub fn update_blacklist_entry(
+        ctx: Context<UpdateBlacklistEntry>,
+        args: instructions::blacklist::UpdateBlacklistEntryArgs,
+    ) -> Result<()> {
+        instructions::blacklist::update_handler(ctx, args)
+    }
+
     pub fn remove_from_blacklist(ctx: Context<RemoveFromBlacklist>) -> Result<()> {
         instructions::blacklist::remove_handler(ctx)
     }
 
-    pub fn seize(ctx: Context<Seize>) -> Result<()> {
-        instructions::seize::handler(ctx)
+    pub fn close_blacklist_entry(ctx: Context<CloseBlacklistEntry>) -> Result<()> {
+        instructions::blacklist::close_handler(ctx)
+    }
+
+    pub fn add_to_allowlist(
+        ctx: Context<AddToAllowlist>,
+        args: instructions::allowlist::AddToAllowlistArgs,
+    ) -> Result<()> {
+        instructions::allowlist::add_handler(ctx, args)
+    }
+
+    pub fn remove_from_allowlist(ctx: Context<RemoveFromAllowlist>) -> Result<()> {
+        instructions::allowlist::remove_handler(ctx)
+    }
+
+    pub fn seize<'info>(
+        ctx: Context<'_, '_, 'info, 'info, Seize<'info>>,
+        amount: Option<u64>,
+    ) -> Result<()> {
+        instructions::seize::handler(ctx, amount)
+    }
+
+    pub fn update_metadata(
+        ctx: Context<UpdateMetadata>,
+        args: instructions::metadata::UpdateMetadataArgs,
+    ) -> Result<()> {
+        instructions::metadata::handler(ctx, args)
+    }
+
+    pub fn update_metadata_field(
+        ctx: Context<UpdateMetadataField>,
+        args: instructions::metadata::UpdateMetadataFieldArgs,
+    ) -> Result<()> {
+        instructions::metadata::update_field_handler(ctx, args)
+    }
+
+    pub fn update_max_supply(
+        ctx: Context<UpdateMaxSupply>,
+        args: instructions::supply::UpdateMaxSupplyArgs,
+    ) -> Result<()> {
+        instructions::supply::handler(ctx, args)
+    }
+
+    pub fn freeze_supply(
+        ctx: Context<FreezeSupply>,
+        args: instructions::supply::FreezeSupplyArgs,
+    ) -> Result<()> {
+        instructions::supply::freeze_supply_handler(ctx, args)
+    }
+
+    pub fn update_quota_window(
+        ctx: Context<UpdateQuotaWindow>,
+        args: instructions::quota::UpdateQuotaWindowArgs,
+    ) -> Result<()> {
+        instructions::quota::handler(ctx, args)
+    }
+
+    pub fn update_transfer_limit(
+        ctx: Context<UpdateTransferLimit>,
+        args: instructions::transfer_limit::UpdateTransferLimitArgs,
+    ) -> Result<()> {
+        instructions::transfer_limit::handler(ctx, args)
+    }
+
+    pub fn update_transfer_hook_program(ctx: Context<UpdateTransferHookProgram>) -> Result<()> {
+        instructions::hook_program::handler(ctx)
+    }
+
+    pub fn enable_transfer_hook(ctx: Context<EnableTransferHook>) -> Result<()> {
+        instructions::hook_program::enable_handler(ctx)
+    }
+
+    pub fn seize_and_burn(ctx: Context<SeizeAndBurn>) -> Result<()> {
+        instructions::seize::seize_and_burn_handler(ctx)
+    }
+
+    pub fn update_self_transfer(
+        ctx: Context<UpdateSelfTransfer>,
+        args: instructions::self_transfer::UpdateSelfTransferArgs,
+    ) -> Result<()> {
+        instructions::self_transfer::handler(ctx, args)
+    }
+
+    pub fn update_treasury(
+        ctx: Context<UpdateTreasury>,
+        args: instructions::treasury::UpdateTreasuryArgs,
+    ) -> Result<()> {
+        instructions::treasury::handler(ctx, args)
+    }
+
+    pub fn update_default_account_state(
+        ctx: Context<UpdateDefaultAccountState>,
+        args: instructions::default_state::UpdateDefaultAccountStateArgs,
+    ) -> Result<()> {
+        instructions::default_state::handler(ctx, args)
+    }
+
+    pub fn migrate_config(ctx: Context<MigrateConfig>) -> Result<()> {
+        instructions::migrate::handler(ctx)
     }
 }
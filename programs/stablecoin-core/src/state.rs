@@ -1,5 +1,12 @@
 use anchor_lang::prelude::*;
 
+use crate::constants::{
+    MAX_NAME_LEN, MAX_SYMBOL_LEN, MAX_URI_LEN, MINT_QUOTA_WINDOW_SECONDS, MINT_REQUEST_TTL_SECONDS,
+    ROLE_MASTER_AUTHORITY,
+};
+use crate::errors::StablecoinError;
+use crate::utils::{effective_pause_flags, has_any_role};
+
 #[account]
 pub struct StablecoinConfig {
     pub authority: Pubkey,
@@ -8,25 +15,275 @@ pub struct StablecoinConfig {
     pub symbol: String,
     pub uri: String,
     pub decimals: u8,
-    pub is_paused: bool,
+    /// PAUSE_* bits of the halted operations; 0 when fully running.
+    pub pause_flags: u8,
     pub total_minted: u64,
     pub total_burned: u64,
     pub audit_counter: u64,
     pub features: FeatureFlags,
     pub transfer_hook_program: Option<Pubkey>,
     pub bump: u8,
+    /// Minimum seconds between pause state changes by non-master pausers (0 disables).
+    pub pause_cooldown: i64,
+    pub last_pause_change: i64,
+    /// Hard ceiling on mint supply; None leaves supply uncapped.
+    pub max_supply: Option<u64>,
+    /// Authority that initialized the stablecoin; unlike `authority`, never rotated.
+    pub creator: Pubkey,
+    pub created_at: i64,
+    /// Length of the minter quota window in seconds.
+    pub quota_window_seconds: i64,
+    /// When set, the transfer hook only lets allowlisted wallets send or
+    /// receive, and the blacklist is not used.
+    pub allowlist_mode: bool,
+    /// Largest amount a single wallet transfer may move; None leaves
+    /// transfers uncapped. Enforced by the transfer hook.
+    pub max_transfer_amount: Option<u64>,
+    /// Time and signer of the last mutating instruction, for a cheap
+    /// "last touched" check without scanning history.
+    pub last_action_at: i64,
+    pub last_action_by: Pubkey,
+    /// When set, the transfer hook skips the blacklist for transfers between
+    /// two token accounts of the same owner; pause still applies.
+    pub allow_self_transfer: bool,
+    /// Cumulative amount taken by seize and seize_and_burn.
+    pub total_seized: u64,
+    /// Wallet whose token account receives treasury mints and, by default,
+    /// seized funds; None until set.
+    pub treasury: Option<Pubkey>,
+    /// Emergency lock: while set, freeze, thaw, blacklist, allowlist and
+    /// seize are reserved to the master authority.
+    pub lock_admin_ops: bool,
+    /// Set once by freeze_supply; mint and mint_batch are rejected for good.
+    pub minting_locked: bool,
+    /// Token program that owns the mint. Configs created before legacy SPL
+    /// Token support read the default key and mean Token-2022.
+    pub token_program: Pubkey,
+    /// Minimum seconds between two transfers sent by one owner, enforced by
+    /// the hook; set only together with the TRANSFER_COOLDOWN feature.
+    pub transfer_cooldown_seconds: Option<i64>,
+    /// When set, mint and mint_batch are rejected and supply only grows
+    /// through propose_mint followed by approve_mint.
+    pub require_mint_approval: bool,
 }
 
+const _: () = assert!(
+    StablecoinConfig::MAX_SERIALIZED_LEN <= StablecoinConfig::INIT_SPACE,
+    "StablecoinConfig fields no longer fit INIT_SPACE"
+);
+
 impl StablecoinConfig {
-    pub const INIT_SPACE: usize = 512;
+    /// Bytes allocated after the discriminator for new configs. Configs
+    /// created while this was 512 keep that size until migrate_config grows
+    /// them, so `require_fits` checks the account's real length instead.
+    pub const INIT_SPACE: usize = 640;
+
+    /// Largest borsh encoding of a config: strings at their maximum length
+    /// and every option set. Update it with each new field;
+    /// `largest_config_fits_init_space` checks it against a real encoding.
+    pub const MAX_SERIALIZED_LEN: usize = 32 // authority
+        + 32 // mint
+        + 4 + MAX_NAME_LEN
+        + 4 + MAX_SYMBOL_LEN
+        + 4 + MAX_URI_LEN
+        + 1 // decimals
+        + 1 // pause_flags
+        + 8 // total_minted
+        + 8 // total_burned
+        + 8 // audit_counter
+        + 4 // features
+        + 1 + 32 // transfer_hook_program
+        + 1 // bump
+        + 8 // pause_cooldown
+        + 8 // last_pause_change
+        + 1 + 8 // max_supply
+        + 32 // creator
+        + 8 // created_at
+        + 8 // quota_window_seconds
+        + 1 // allowlist_mode
+        + 1 + 8 // max_transfer_amount
+        + 8 // last_action_at
+        + 32 // last_action_by
+        + 1 // allow_self_transfer
+        + 8 // total_seized
+        + 1 + 32 // treasury
+        + 1 // lock_admin_ops
+        + 1 // minting_locked
+        + 32 // token_program
+        + 1 + 8 // transfer_cooldown_seconds
+        + 1; // require_mint_approval
+
+    /// Reserved bytes still free for future fields.
+    pub const SPARE_SPACE: usize = Self::INIT_SPACE - Self::MAX_SERIALIZED_LEN;
+
+    /// Fails with ConfigTooLarge when this config would not serialize into
+    /// an account of `data_len` bytes (discriminator included), e.g. longer
+    /// strings in a config not yet migrated; Anchor would otherwise only fail
+    /// on exit.
+    pub fn require_fits(&self, data_len: usize) -> Result<()> {
+        let len = self.try_to_vec()?.len();
+        require!(
+            len <= data_len.saturating_sub(8),
+            StablecoinError::ConfigTooLarge
+        );
+        Ok(())
+    }
+
+    /// Configs created before the window was configurable read 0 here and
+    /// keep the original 24-hour window.
+    pub fn quota_window(&self) -> i64 {
+        if self.quota_window_seconds > 0 {
+            self.quota_window_seconds
+        } else {
+            MINT_QUOTA_WINDOW_SECONDS
+        }
+    }
+
+    pub fn record_action(&mut self, actor: Pubkey, timestamp: i64) {
+        self.last_action_at = timestamp;
+        self.last_action_by = actor;
+    }
+
+    /// True when any operation is paused.
+    pub fn is_paused(&self) -> bool {
+        self.pause_flags != 0
+    }
+
+    /// True when every bit of `operation` (PAUSE_* bits) is paused.
+    pub fn is_operation_paused(&self, operation: u8) -> bool {
+        effective_pause_flags(self.pause_flags) & operation == operation
+    }
+
+    pub fn token_program_id(&self) -> Pubkey {
+        if self.token_program == Pubkey::default() {
+            anchor_spl::token_2022::ID
+        } else {
+            self.token_program
+        }
+    }
+
+    /// False while the emergency lock is set and `roles` lack the master bit.
+    pub fn admin_ops_allowed(&self, roles: u8) -> bool {
+        !self.lock_admin_ops || has_any_role(roles, ROLE_MASTER_AUTHORITY)
+    }
+
+    /// Fails with MintApprovalRequired when minting must go through the
+    /// mint request queue.
+    pub fn require_direct_mint(&self) -> Result<()> {
+        require!(
+            !self.require_mint_approval,
+            StablecoinError::MintApprovalRequired
+        );
+        Ok(())
+    }
+}
+
+/// Token program chosen at initialize.
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, Default, Debug, PartialEq, Eq)]
+pub enum TokenProgram {
+    /// Token-2022, required by every extension-backed feature.
+    #[default]
+    Token2022,
+    /// The original SPL Token program, for plain SSS-1 mints without
+    /// extensions.
+    Legacy,
+}
+
+impl TokenProgram {
+    pub fn id(self) -> Pubkey {
+        match self {
+            TokenProgram::Token2022 => anchor_spl::token_2022::ID,
+            TokenProgram::Legacy => anchor_spl::token::ID,
+        }
+    }
 }
 
-#[derive(AnchorSerialize, AnchorDeserialize, Clone, Default)]
+/// Feature bitfield stored as a little-endian u32.
+///
+/// The original layout was four borsh bools, one byte each. The legacy flags
+/// sit on bit 0 of those bytes, so accounts written before the bitfield decode
+/// to the same flags without a realloc or migration instruction. New features
+/// take any other bit; unknown bits are preserved rather than rejected so an
+/// older program can still read a config written by a newer one.
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, Default, Debug, PartialEq, Eq)]
 pub struct FeatureFlags {
-    pub permanent_delegate: bool,
-    pub transfer_hook: bool,
-    pub confidential: bool,
-    pub default_frozen: bool,
+    bits: u32,
+}
+
+impl FeatureFlags {
+    pub const PERMANENT_DELEGATE: u32 = 1 << 0;
+    pub const TRANSFER_HOOK: u32 = 1 << 8;
+    pub const CONFIDENTIAL: u32 = 1 << 16;
+    pub const DEFAULT_FROZEN: u32 = 1 << 24;
+    /// The extra account metas carry the source owner's LastTransfer PDA, so
+    /// the hook enforces transfer_cooldown_seconds. Fixed at initialize.
+    pub const TRANSFER_COOLDOWN: u32 = 1 << 1;
+
+    /// Bits with a meaning in this version; everything else is reserved.
+    pub const KNOWN: u32 = Self::PERMANENT_DELEGATE
+        | Self::TRANSFER_HOOK
+        | Self::CONFIDENTIAL
+        | Self::DEFAULT_FROZEN
+        | Self::TRANSFER_COOLDOWN;
+
+    /// Builds flags from the four values of the legacy bool layout.
+    pub fn new(
+        permanent_delegate: bool,
+        transfer_hook: bool,
+        confidential: bool,
+        default_frozen: bool,
+    ) -> Self {
+        let mut flags = Self::default();
+        flags.set(Self::PERMANENT_DELEGATE, permanent_delegate);
+        flags.set(Self::TRANSFER_HOOK, transfer_hook);
+        flags.set(Self::CONFIDENTIAL, confidential);
+        flags.set(Self::DEFAULT_FROZEN, default_frozen);
+        flags
+    }
+
+    pub const fn from_bits(bits: u32) -> Self {
+        Self { bits }
+    }
+
+    pub const fn bits(&self) -> u32 {
+        self.bits
+    }
+
+    pub const fn contains(&self, flag: u32) -> bool {
+        self.bits & flag == flag
+    }
+
+    pub fn set(&mut self, flag: u32, enabled: bool) {
+        if enabled {
+            self.bits |= flag;
+        } else {
+            self.bits &= !flag;
+        }
+    }
+
+    pub const fn permanent_delegate(&self) -> bool {
+        self.contains(Self::PERMANENT_DELEGATE)
+    }
+
+    pub const fn transfer_hook(&self) -> bool {
+        self.contains(Self::TRANSFER_HOOK)
+    }
+
+    pub const fn confidential(&self) -> bool {
+        self.contains(Self::CONFIDENTIAL)
+    }
+
+    pub const fn default_frozen(&self) -> bool {
+        self.contains(Self::DEFAULT_FROZEN)
+    }
+
+    pub const fn transfer_cooldown(&self) -> bool {
+        self.contains(Self::TRANSFER_COOLDOWN)
+    }
+
+    pub const fn reserved_bits(&self) -> u32 {
+        self.bits & !Self::KNOWN
+    }
 }
 
 #[account]
@@ -38,10 +295,30 @@ pub struct RoleAccount {
     pub minted_current_window: u64,
     pub window_start: i64,
     pub bump: u8,
+    /// Everything this account has ever minted; role updates never reset it.
+    pub lifetime_minted: u64,
+    /// Times the quota window has rolled over.
+    pub window_resets: u32,
+    /// Most this account may ever mint; None is unlimited. Checked against
+    /// `lifetime_minted`.
+    pub lifetime_quota: Option<u64>,
 }
 
 impl RoleAccount {
     pub const INIT_SPACE: usize = 256;
+
+    /// Claims a role account `init_if_needed` may have just created. A new
+    /// account is zeroed, so its config is the default key; an existing one
+    /// keeps its stored bump, roles and quotas. Returns whether it was new.
+    pub fn init_if_new(&mut self, config: Pubkey, authority: Pubkey, bump: u8) -> bool {
+        let created = self.config == Pubkey::default();
+        if created {
+            self.config = config;
+            self.authority = authority;
+            self.bump = bump;
+        }
+        created
+    }
 }
 
 #[account]
@@ -53,8 +330,370 @@ pub struct BlacklistEntry {
     pub reason: String,
     pub is_active: bool,
     pub bump: u8,
+    /// Unix time after which the entry lapses on its own; None never expires.
+    pub expires_at: Option<i64>,
+    /// `BlacklistReason` code; `reason` is a free-text note alongside it.
+    pub reason_code: u8,
 }
 
 impl BlacklistEntry {
     pub const INIT_SPACE: usize = 320;
+
+    /// Active and not yet past `expires_at`. Entries written before expiry
+    /// existed read None from the zeroed account tail and never lapse.
+    pub fn is_in_effect(&self, now: i64) -> bool {
+        match self.expires_at {
+            Some(expires_at) => self.is_active && now <= expires_at,
+            None => self.is_active,
+        }
+    }
+}
+
+/// Why a wallet was blacklisted, stored as a u8 so entries can be
+/// aggregated by cause. Entries written before codes existed read 0.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+#[repr(u8)]
+pub enum BlacklistReason {
+    Unspecified = 0,
+    Sanctions = 1,
+    Fraud = 2,
+    CourtOrder = 3,
+    Other = 4,
+}
+
+impl BlacklistReason {
+    /// Codes `add_to_blacklist` accepts; Unspecified only marks legacy entries.
+    pub const ASSIGNABLE: [Self; 4] = [Self::Sanctions, Self::Fraud, Self::CourtOrder, Self::Other];
+
+    pub fn from_code(code: u8) -> Option<Self> {
+        match code {
+            0 => Some(Self::Unspecified),
+            1 => Some(Self::Sanctions),
+            2 => Some(Self::Fraud),
+            3 => Some(Self::CourtOrder),
+            4 => Some(Self::Other),
+            _ => None,
+        }
+    }
+
+    pub const fn code(self) -> u8 {
+        self as u8
+    }
+
+    pub const fn name(self) -> &'static str {
+        match self {
+            Self::Unspecified => "unspecified",
+            Self::Sanctions => "sanctions",
+            Self::Fraud => "fraud",
+            Self::CourtOrder => "court-order",
+            Self::Other => "other",
+        }
+    }
+}
+
+#[account]
+pub struct AllowlistEntry {
+    pub config: Pubkey,
+    pub wallet: Pubkey,
+    pub is_active: bool,
+    pub bump: u8,
+}
+
+impl AllowlistEntry {
+    pub const INIT_SPACE: usize = 128;
+}
+
+/// A mint proposed by one minter and waiting for a second signer to approve
+/// it. Closed, with its rent returned to the requester, once approved or
+/// cancelled.
+#[account]
+pub struct MintRequest {
+    pub config: Pubkey,
+    /// Requester-chosen id, part of the PDA seeds.
+    pub id: u64,
+    pub requester: Pubkey,
+    pub recipient: Pubkey,
+    pub amount: u64,
+    pub created_at: i64,
+    pub bump: u8,
+}
+
+impl MintRequest {
+    pub const INIT_SPACE: usize = 160;
+
+    pub fn is_expired(&self, now: i64) -> bool {
+        now.saturating_sub(self.created_at) >= MINT_REQUEST_TTL_SECONDS
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use proptest::prelude::*;
+
+    /// The pre-bitfield layout, kept to prove old accounts still decode.
+    #[derive(AnchorSerialize, AnchorDeserialize)]
+    struct LegacyFeatureFlags {
+        permanent_delegate: bool,
+        transfer_hook: bool,
+        confidential: bool,
+        default_frozen: bool,
+    }
+
+    fn legacy(combo: u8) -> LegacyFeatureFlags {
+        LegacyFeatureFlags {
+            permanent_delegate: combo & 1 != 0,
+            transfer_hook: combo & 2 != 0,
+            confidential: combo & 4 != 0,
+            default_frozen: combo & 8 != 0,
+        }
+    }
+
+    #[test]
+    fn every_legacy_layout_decodes_to_the_same_flags() {
+        for combo in 0u8..16 {
+            let old = legacy(combo);
+            let bytes = old.try_to_vec().unwrap();
+            let flags = FeatureFlags::try_from_slice(&bytes).unwrap();
+
+            assert_eq!(flags.permanent_delegate(), old.permanent_delegate);
+            assert_eq!(flags.transfer_hook(), old.transfer_hook);
+            assert_eq!(flags.confidential(), old.confidential);
+            assert_eq!(flags.default_frozen(), old.default_frozen);
+            assert_eq!(flags.reserved_bits(), 0);
+            assert_eq!(
+                flags,
+                FeatureFlags::new(
+                    old.permanent_delegate,
+                    old.transfer_hook,
+                    old.confidential,
+                    old.default_frozen
+                )
+            );
+            // Re-serializing writes the exact bytes the legacy layout did.
+            assert_eq!(flags.try_to_vec().unwrap(), bytes);
+            let back = LegacyFeatureFlags::try_from_slice(&bytes).unwrap();
+            assert_eq!(back.try_to_vec().unwrap(), bytes);
+        }
+    }
+
+    #[test]
+    fn flag_bits_are_distinct_and_have_fixed_positions() {
+        let flags = [
+            (FeatureFlags::PERMANENT_DELEGATE, 0),
+            (FeatureFlags::TRANSFER_HOOK, 8),
+            (FeatureFlags::CONFIDENTIAL, 16),
+            (FeatureFlags::DEFAULT_FROZEN, 24),
+            (FeatureFlags::TRANSFER_COOLDOWN, 1),
+        ];
+        let mut seen = 0u32;
+        for (flag, bit) in flags {
+            assert_eq!(flag, 1 << bit);
+            assert_eq!(seen & flag, 0);
+            seen |= flag;
+        }
+        assert_eq!(seen, FeatureFlags::KNOWN);
+        assert_eq!(FeatureFlags::default().try_to_vec().unwrap(), vec![0; 4]);
+    }
+
+    #[test]
+    fn blacklist_reason_codes_round_trip() {
+        for reason in BlacklistReason::ASSIGNABLE {
+            assert_ne!(reason, BlacklistReason::Unspecified);
+            assert_eq!(BlacklistReason::from_code(reason.code()), Some(reason));
+        }
+        assert_eq!(
+            BlacklistReason::from_code(0),
+            Some(BlacklistReason::Unspecified)
+        );
+        assert_eq!(BlacklistReason::from_code(5), None);
+        assert_eq!(BlacklistReason::CourtOrder.name(), "court-order");
+    }
+
+    #[test]
+    fn existing_role_accounts_keep_their_bump_and_quotas() {
+        use crate::constants::{ROLE_MASTER_AUTHORITY, ROLE_MINTER};
+        use crate::utils::move_master_authority;
+
+        let (config, authority) = (Pubkey::new_unique(), Pubkey::new_unique());
+        // What transfer_authority sees when the new authority is a minter.
+        let mut minter = RoleAccount {
+            config,
+            authority,
+            roles: ROLE_MINTER,
+            mint_quota: Some(5_000),
+            minted_current_window: 1_200,
+            window_start: 1_700_000_000,
+            bump: 251,
+            lifetime_minted: 9_000,
+            window_resets: 3,
+            lifetime_quota: Some(50_000),
+        };
+        assert!(!minter.init_if_new(config, authority, 254));
+        minter.roles = move_master_authority(ROLE_MASTER_AUTHORITY, minter.roles).1;
+        assert_eq!(minter.roles, ROLE_MASTER_AUTHORITY | ROLE_MINTER);
+        assert_eq!(minter.bump, 251);
+        assert_eq!(minter.mint_quota, Some(5_000));
+        assert_eq!(minter.minted_current_window, 1_200);
+        assert_eq!(minter.lifetime_quota, Some(50_000));
+
+        let zeroed = vec![0u8; RoleAccount::INIT_SPACE];
+        let mut created = RoleAccount::deserialize(&mut zeroed.as_slice()).unwrap();
+        assert!(created.init_if_new(config, authority, 254));
+        assert_eq!(created.bump, 254);
+        assert_eq!(created.authority, authority);
+        // Claimed once, it is no longer new.
+        assert!(!created.init_if_new(config, Pubkey::new_unique(), 1));
+        assert_eq!(created.bump, 254);
+    }
+
+    #[test]
+    fn emergency_lock_leaves_admin_ops_to_the_master_authority() {
+        use crate::constants::{ROLE_BLACKLISTER, ROLE_FREEZER, ROLE_SEIZER};
+
+        // Configs written before the lock existed have zero padding there.
+        let zeroed = vec![0u8; StablecoinConfig::INIT_SPACE];
+        let mut config = StablecoinConfig::deserialize(&mut zeroed.as_slice()).unwrap();
+        assert!(!config.lock_admin_ops);
+        assert!(config.admin_ops_allowed(ROLE_FREEZER));
+
+        config.lock_admin_ops = true;
+        assert!(!config.admin_ops_allowed(ROLE_FREEZER | ROLE_BLACKLISTER | ROLE_SEIZER));
+        assert!(!config.admin_ops_allowed(0));
+        assert!(config.admin_ops_allowed(ROLE_MASTER_AUTHORITY));
+        assert!(config.admin_ops_allowed(ROLE_MASTER_AUTHORITY | ROLE_FREEZER));
+    }
+
+    #[test]
+    fn direct_mints_are_refused_while_approval_is_required() {
+        // Configs written before the flag existed have zero padding there.
+        let zeroed = vec![0u8; StablecoinConfig::INIT_SPACE];
+        let mut config = StablecoinConfig::deserialize(&mut zeroed.as_slice()).unwrap();
+        assert!(config.require_direct_mint().is_ok());
+
+        config.require_mint_approval = true;
+        assert_eq!(
+            config.require_direct_mint().unwrap_err(),
+            StablecoinError::MintApprovalRequired.into()
+        );
+    }
+
+    #[test]
+    fn configs_without_a_token_program_use_token_2022() {
+        let zeroed = vec![0u8; StablecoinConfig::INIT_SPACE];
+        let mut config = StablecoinConfig::deserialize(&mut zeroed.as_slice()).unwrap();
+        assert_eq!(config.token_program_id(), anchor_spl::token_2022::ID);
+
+        config.token_program = TokenProgram::Legacy.id();
+        assert_eq!(config.token_program_id(), anchor_spl::token::ID);
+        assert_eq!(TokenProgram::default().id(), anchor_spl::token_2022::ID);
+    }
+
+    #[test]
+    fn largest_config_fits_init_space() {
+        use crate::constants::{MAX_NAME_LEN, MAX_SYMBOL_LEN, MAX_URI_LEN};
+
+        let config = StablecoinConfig {
+            authority: Pubkey::new_unique(),
+            mint: Pubkey::new_unique(),
+            name: "n".repeat(MAX_NAME_LEN),
+            symbol: "s".repeat(MAX_SYMBOL_LEN),
+            uri: "u".repeat(MAX_URI_LEN),
+            decimals: 6,
+            pause_flags: u8::MAX,
+            total_minted: u64::MAX,
+            total_burned: u64::MAX,
+            audit_counter: u64::MAX,
+            features: FeatureFlags::from_bits(u32::MAX),
+            transfer_hook_program: Some(Pubkey::new_unique()),
+            bump: u8::MAX,
+            pause_cooldown: i64::MAX,
+            last_pause_change: i64::MAX,
+            max_supply: Some(u64::MAX),
+            creator: Pubkey::new_unique(),
+            created_at: i64::MAX,
+            quota_window_seconds: i64::MAX,
+            allowlist_mode: true,
+            max_transfer_amount: Some(u64::MAX),
+            last_action_at: i64::MAX,
+            last_action_by: Pubkey::new_unique(),
+            allow_self_transfer: true,
+            total_seized: u64::MAX,
+            treasury: Some(Pubkey::new_unique()),
+            lock_admin_ops: true,
+            minting_locked: true,
+            token_program: Pubkey::new_unique(),
+            transfer_cooldown_seconds: Some(i64::MAX),
+            require_mint_approval: true,
+        };
+        let len = config.try_to_vec().unwrap().len();
+        assert_eq!(len, StablecoinConfig::MAX_SERIALIZED_LEN);
+        assert!(
+            len <= StablecoinConfig::INIT_SPACE,
+            "{len} bytes exceed INIT_SPACE"
+        );
+        config
+            .require_fits(8 + StablecoinConfig::INIT_SPACE)
+            .unwrap();
+        // A config still at its original allocation cannot take it.
+        assert!(config.require_fits(8 + 512).is_err());
+    }
+
+    #[test]
+    fn mint_request_fits_init_space() {
+        let request = MintRequest {
+            config: Pubkey::new_unique(),
+            id: u64::MAX,
+            requester: Pubkey::new_unique(),
+            recipient: Pubkey::new_unique(),
+            amount: u64::MAX,
+            created_at: i64::MAX,
+            bump: u8::MAX,
+        };
+        assert!(request.try_to_vec().unwrap().len() <= MintRequest::INIT_SPACE);
+    }
+
+    #[test]
+    fn mint_requests_expire_after_the_ttl() {
+        let request = MintRequest {
+            config: Pubkey::new_unique(),
+            id: 1,
+            requester: Pubkey::new_unique(),
+            recipient: Pubkey::new_unique(),
+            amount: 1_000,
+            created_at: 1_700_000_000,
+            bump: 255,
+        };
+        assert!(!request.is_expired(request.created_at));
+        assert!(!request.is_expired(request.created_at + MINT_REQUEST_TTL_SECONDS - 1));
+        assert!(request.is_expired(request.created_at + MINT_REQUEST_TTL_SECONDS));
+    }
+
+    proptest! {
+        #[test]
+        fn any_bits_round_trip_in_four_bytes(bits in any::<u32>()) {
+            let flags = FeatureFlags::from_bits(bits);
+            let bytes = flags.try_to_vec().unwrap();
+            prop_assert_eq!(bytes.len(), 4);
+            prop_assert_eq!(&bytes, &bits.to_le_bytes().to_vec());
+            prop_assert_eq!(FeatureFlags::try_from_slice(&bytes).unwrap(), flags);
+        }
+
+        #[test]
+        fn setting_known_flags_preserves_reserved_bits(
+            bits in any::<u32>(),
+            enabled in any::<[bool; 4]>(),
+        ) {
+            let mut flags = FeatureFlags::from_bits(bits);
+            flags.set(FeatureFlags::PERMANENT_DELEGATE, enabled[0]);
+            flags.set(FeatureFlags::TRANSFER_HOOK, enabled[1]);
+            flags.set(FeatureFlags::CONFIDENTIAL, enabled[2]);
+            flags.set(FeatureFlags::DEFAULT_FROZEN, enabled[3]);
+            prop_assert_eq!(flags.reserved_bits(), bits & !FeatureFlags::KNOWN);
+            prop_assert_eq!(flags.permanent_delegate(), enabled[0]);
+            prop_assert_eq!(flags.transfer_hook(), enabled[1]);
+            prop_assert_eq!(flags.confidential(), enabled[2]);
+            prop_assert_eq!(flags.default_frozen(), enabled[3]);
+        }
+    }
 }
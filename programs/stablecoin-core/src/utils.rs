@@ -1,13 +1,333 @@
 use anchor_lang::prelude::*;
 
-use crate::constants::VALID_ROLE_MASK;
+use crate::constants::{
+    PAUSE_ALL, PAUSE_LEGACY, ROLE_ADMIN_GRANTABLE, ROLE_ADMIN_REVOCABLE, ROLE_BLACKLISTER,
+    ROLE_MASTER_AUTHORITY, ROLE_SEIZER, VALID_ROLE_MASK,
+};
 use crate::errors::StablecoinError;
 
 pub fn has_any_role(roles: u8, mask: u8) -> bool {
     roles & mask != 0
 }
 
+// Every bit is assigned since ROLE_ROLE_ADMIN took 0x80; the check stays so
+// shrinking VALID_ROLE_MASK cannot silently accept stray bits.
+#[allow(clippy::bad_bit_mask)]
 pub fn require_valid_roles(roles: u8) -> Result<()> {
     require!(roles & !VALID_ROLE_MASK == 0, StablecoinError::InvalidRoles);
     Ok(())
 }
+
+pub fn grant_roles(roles: u8, mask: u8) -> u8 {
+    roles | mask
+}
+
+pub fn revoke_roles(roles: u8, mask: u8) -> u8 {
+    roles & !mask
+}
+
+/// Bits gained and lost going from `previous` to `new`, as (added, removed).
+pub fn role_changes(previous: u8, new: u8) -> (u8, u8) {
+    (new & !previous, previous & !new)
+}
+
+/// Validates a role bitmask for update_roles; compliance roles need the SSS-2 hook.
+pub fn require_assignable_roles(roles: u8, transfer_hook_enabled: bool) -> Result<()> {
+    require_valid_roles(roles)?;
+    if !transfer_hook_enabled {
+        require!(
+            roles & (ROLE_BLACKLISTER | ROLE_SEIZER) == 0,
+            StablecoinError::FeatureNotEnabled
+        );
+    }
+    Ok(())
+}
+
+/// Whether a role admin without the master bit may take a role account from
+/// `previous` to `new`: it only adds ROLE_ADMIN_GRANTABLE bits and only drops
+/// ROLE_ADMIN_REVOCABLE ones, so the master and role admin bits never move.
+pub fn role_admin_may_update(previous: u8, new: u8) -> bool {
+    let (added, removed) = role_changes(previous, new);
+    added & !ROLE_ADMIN_GRANTABLE == 0 && removed & !ROLE_ADMIN_REVOCABLE == 0
+}
+
+/// Moves the master bit from `current` to `new`, leaving all other bits untouched.
+pub fn move_master_authority(current: u8, new: u8) -> (u8, u8) {
+    (
+        revoke_roles(current, ROLE_MASTER_AUTHORITY),
+        grant_roles(new, ROLE_MASTER_AUTHORITY),
+    )
+}
+
+/// True when going from `previous` to `new` clears the master authority bit.
+pub fn drops_master_authority(previous: u8, new: u8) -> bool {
+    has_any_role(previous, ROLE_MASTER_AUTHORITY) && !has_any_role(new, ROLE_MASTER_AUTHORITY)
+}
+
+/// Roles left after the holder renounces `mask`. At least one held role must
+/// be dropped, and the master authority can only be handed over with
+/// transfer_authority, never renounced, so the config keeps its sole master.
+pub fn renounce_roles(current: u8, mask: u8) -> Result<u8> {
+    require_valid_roles(mask)?;
+    require!(current & mask != 0, StablecoinError::InvalidRoles);
+    require!(
+        !has_any_role(current & mask, ROLE_MASTER_AUTHORITY),
+        StablecoinError::CannotRenounceMasterAuthority
+    );
+    Ok(revoke_roles(current, mask))
+}
+
+/// Resolves the pause/unpause mask argument; None covers every operation.
+pub fn resolve_pause_mask(mask: Option<u8>) -> Result<u8> {
+    let mask = mask.unwrap_or(PAUSE_ALL);
+    require!(
+        mask != 0 && mask & !PAUSE_ALL == 0,
+        StablecoinError::InvalidPauseMask
+    );
+    Ok(mask)
+}
+
+/// Paused operations, reading the former `is_paused = true` byte as PAUSE_ALL.
+pub fn effective_pause_flags(flags: u8) -> u8 {
+    if flags & PAUSE_LEGACY != 0 {
+        PAUSE_ALL
+    } else {
+        flags & PAUSE_ALL
+    }
+}
+
+#[cfg(test)]
+// The properties still mask with !VALID_ROLE_MASK, which is zero while every
+// bit is assigned.
+#[allow(clippy::bad_bit_mask)]
+mod tests {
+    use super::*;
+    use crate::constants::{
+        PAUSE_BURN, PAUSE_MINT, PAUSE_TRANSFER, ROLE_BURNER, ROLE_FREEZER, ROLE_MINTER,
+        ROLE_PAUSER, ROLE_ROLE_ADMIN,
+    };
+    use proptest::prelude::*;
+
+    #[test]
+    fn legacy_pause_byte_reads_as_everything_paused() {
+        assert_eq!(effective_pause_flags(0), 0);
+        assert_eq!(effective_pause_flags(PAUSE_LEGACY), PAUSE_ALL);
+        for operation in [PAUSE_MINT, PAUSE_BURN, PAUSE_TRANSFER] {
+            assert_eq!(effective_pause_flags(operation), operation);
+        }
+        // Unpausing one operation of a legacy pause keeps the rest halted.
+        assert_eq!(
+            effective_pause_flags(PAUSE_LEGACY) & !PAUSE_BURN,
+            PAUSE_MINT | PAUSE_TRANSFER
+        );
+    }
+
+    const ROLE_BITS: [u8; 8] = [
+        ROLE_MASTER_AUTHORITY,
+        ROLE_MINTER,
+        ROLE_BURNER,
+        ROLE_FREEZER,
+        ROLE_PAUSER,
+        ROLE_BLACKLISTER,
+        ROLE_SEIZER,
+        ROLE_ROLE_ADMIN,
+    ];
+
+    #[test]
+    fn role_admins_stay_inside_their_delegation() {
+        // Operational roles can be handed out and taken back.
+        assert!(role_admin_may_update(0, ROLE_FREEZER | ROLE_PAUSER));
+        assert!(role_admin_may_update(
+            ROLE_PAUSER,
+            ROLE_BLACKLISTER | ROLE_SEIZER
+        ));
+        assert!(role_admin_may_update(
+            ROLE_MINTER | ROLE_BURNER,
+            ROLE_BURNER
+        ));
+        // Bits outside the change are untouched, including the master's own.
+        assert!(role_admin_may_update(
+            ROLE_MASTER_AUTHORITY,
+            ROLE_MASTER_AUTHORITY | ROLE_FREEZER
+        ));
+        assert!(role_admin_may_update(
+            ROLE_ROLE_ADMIN,
+            ROLE_ROLE_ADMIN | ROLE_BURNER
+        ));
+
+        assert!(!role_admin_may_update(0, ROLE_MASTER_AUTHORITY));
+        assert!(!role_admin_may_update(
+            ROLE_MASTER_AUTHORITY | ROLE_FREEZER,
+            ROLE_FREEZER
+        ));
+        assert!(!role_admin_may_update(0, ROLE_ROLE_ADMIN));
+        assert!(!role_admin_may_update(ROLE_ROLE_ADMIN, 0));
+        assert!(!role_admin_may_update(0, ROLE_MINTER));
+    }
+
+    #[derive(Debug, Clone)]
+    enum Op {
+        UpdateRoles { target: usize, roles: u8 },
+        TransferAuthority { to: usize },
+        Renounce { target: usize, mask: u8 },
+    }
+
+    fn op(accounts: usize) -> impl Strategy<Value = Op> {
+        prop_oneof![
+            (0..accounts, any::<u8>())
+                .prop_map(|(target, roles)| Op::UpdateRoles { target, roles }),
+            (0..accounts).prop_map(|to| Op::TransferAuthority { to }),
+            (0..accounts, any::<u8>()).prop_map(|(target, mask)| Op::Renounce { target, mask }),
+        ]
+    }
+
+    proptest! {
+        #[test]
+        fn grant_then_revoke_restores_disjoint_bits(roles in 0u8..=VALID_ROLE_MASK, mask in 0u8..=VALID_ROLE_MASK) {
+            let mask = mask & !roles;
+            prop_assert_eq!(revoke_roles(grant_roles(roles, mask), mask), roles);
+        }
+
+        #[test]
+        fn has_any_role_matches_bit_definitions(roles in any::<u8>()) {
+            for bit in ROLE_BITS {
+                prop_assert_eq!(has_any_role(roles, bit), roles & bit == bit);
+            }
+            prop_assert_eq!(has_any_role(roles, VALID_ROLE_MASK), roles & VALID_ROLE_MASK != 0);
+        }
+
+        #[test]
+        fn assignable_roles_are_always_valid(roles in any::<u8>(), hook in any::<bool>()) {
+            if require_assignable_roles(roles, hook).is_ok() {
+                prop_assert_eq!(roles & !VALID_ROLE_MASK, 0);
+                if !hook {
+                    prop_assert_eq!(roles & (ROLE_BLACKLISTER | ROLE_SEIZER), 0);
+                }
+            } else {
+                prop_assert!(roles & !VALID_ROLE_MASK != 0 || (!hook && roles & (ROLE_BLACKLISTER | ROLE_SEIZER) != 0));
+            }
+        }
+
+        #[test]
+        fn pause_masks_only_cover_known_operations(mask in proptest::option::of(any::<u8>())) {
+            match resolve_pause_mask(mask) {
+                Ok(resolved) => {
+                    prop_assert_ne!(resolved, 0);
+                    prop_assert_eq!(resolved & !PAUSE_ALL, 0);
+                    prop_assert_eq!(resolved, mask.unwrap_or(PAUSE_ALL));
+                }
+                Err(_) => prop_assert!(mask.is_some_and(|mask| mask == 0 || mask & !PAUSE_ALL != 0)),
+            }
+        }
+
+        #[test]
+        fn pausing_then_unpausing_a_mask_leaves_other_operations(flags in 0u8..=PAUSE_ALL, mask in 1u8..=PAUSE_ALL) {
+            let flags = flags & PAUSE_ALL;
+            let mask = mask & PAUSE_ALL;
+            let paused = effective_pause_flags(flags) | mask;
+            prop_assert_eq!(paused & mask, mask);
+            let unpaused = effective_pause_flags(paused) & !mask;
+            prop_assert_eq!(unpaused, flags & !mask);
+        }
+
+        #[test]
+        fn role_changes_replay_to_the_new_mask(previous in any::<u8>(), new in any::<u8>()) {
+            let (added, removed) = role_changes(previous, new);
+            prop_assert_eq!(added & removed, 0);
+            prop_assert_eq!(revoke_roles(grant_roles(previous, added), removed), new);
+        }
+
+        #[test]
+        fn role_admin_updates_never_move_master_or_admin_bits(previous in any::<u8>(), new in any::<u8>()) {
+            if role_admin_may_update(previous, new) {
+                let fixed = ROLE_MASTER_AUTHORITY | ROLE_ROLE_ADMIN;
+                prop_assert_eq!(previous & fixed, new & fixed);
+                prop_assert!(!drops_master_authority(previous, new));
+                prop_assert_eq!(role_changes(previous, new).0 & ROLE_MINTER, 0);
+            }
+        }
+
+        #[test]
+        fn move_master_authority_preserves_other_bits(current in 0u8..=VALID_ROLE_MASK, new in 0u8..=VALID_ROLE_MASK) {
+            let (current_after, new_after) = move_master_authority(current, new);
+            prop_assert!(!has_any_role(current_after, ROLE_MASTER_AUTHORITY));
+            prop_assert!(has_any_role(new_after, ROLE_MASTER_AUTHORITY));
+            prop_assert_eq!(current_after | ROLE_MASTER_AUTHORITY, current | ROLE_MASTER_AUTHORITY);
+            prop_assert_eq!(new_after & !ROLE_MASTER_AUTHORITY, new & !ROLE_MASTER_AUTHORITY);
+        }
+
+        #[test]
+        fn renouncing_only_drops_requested_non_master_roles(current in 0u8..=VALID_ROLE_MASK, mask in any::<u8>()) {
+            match renounce_roles(current, mask) {
+                Ok(remaining) => {
+                    prop_assert_eq!(remaining, current & !mask);
+                    prop_assert_ne!(remaining, current);
+                    prop_assert_eq!(
+                        has_any_role(remaining, ROLE_MASTER_AUTHORITY),
+                        has_any_role(current, ROLE_MASTER_AUTHORITY)
+                    );
+                }
+                Err(_) => prop_assert!(
+                    mask & !VALID_ROLE_MASK != 0
+                        || current & mask == 0
+                        || has_any_role(current & mask, ROLE_MASTER_AUTHORITY)
+                ),
+            }
+        }
+
+        #[test]
+        fn drops_master_authority_only_when_the_bit_is_cleared(previous in any::<u8>(), new in any::<u8>()) {
+            prop_assert_eq!(
+                drops_master_authority(previous, new),
+                previous & ROLE_MASTER_AUTHORITY != 0 && new & ROLE_MASTER_AUTHORITY == 0
+            );
+            prop_assert!(!drops_master_authority(previous, grant_roles(new, ROLE_MASTER_AUTHORITY)));
+        }
+
+        /// Models the handlers over a set of role accounts: account 0 starts as
+        /// the sole master and signs every update. Accepted updates that do not
+        /// grant a second master, renounces, and any transfers must keep exactly
+        /// one master and only valid masks.
+        #[test]
+        fn sequences_keep_a_single_master(ops in proptest::collection::vec(op(4), 0..32), hook in any::<bool>()) {
+            let mut roles = [ROLE_MASTER_AUTHORITY, 0, 0, 0];
+            let mut master = 0;
+            for op in ops {
+                match op {
+                    Op::UpdateRoles { target, roles: new_roles } => {
+                        let grants_master = !has_any_role(roles[target], ROLE_MASTER_AUTHORITY)
+                            && has_any_role(new_roles, ROLE_MASTER_AUTHORITY);
+                        let locks_out = target == master
+                            && drops_master_authority(roles[target], new_roles);
+                        if !grants_master
+                            && !locks_out
+                            && require_assignable_roles(new_roles, hook).is_ok()
+                        {
+                            roles[target] = new_roles;
+                        }
+                    }
+                    Op::TransferAuthority { to } => {
+                        if to != master {
+                            let (current, new) = move_master_authority(roles[master], roles[to]);
+                            roles[master] = current;
+                            roles[to] = new;
+                            master = to;
+                        }
+                    }
+                    Op::Renounce { target, mask } => {
+                        if let Ok(remaining) = renounce_roles(roles[target], mask) {
+                            roles[target] = remaining;
+                        }
+                    }
+                }
+                let masters = roles
+                    .iter()
+                    .filter(|value| has_any_role(**value, ROLE_MASTER_AUTHORITY))
+                    .count();
+                prop_assert_eq!(masters, 1);
+                prop_assert!(has_any_role(roles[master], ROLE_MASTER_AUTHORITY));
+                prop_assert!(roles.iter().all(|value| value & !VALID_ROLE_MASK == 0));
+            }
+        }
+    }
+}
@@ -22,4 +22,25 @@ pub enum TransferHookError {
 
     #[msg("Invalid blacklist entry account")]
     InvalidBlacklistEntry,
+
+    #[msg("Token account mint does not match the transferred mint")]
+    TokenAccountMintMismatch,
+
+    #[msg("Stablecoin is paused")]
+    SystemPaused,
+
+    #[msg("Transfer denied: wallet is not allowlisted")]
+    NotAllowlisted,
+
+    #[msg("Transfer amount exceeds the per-transfer limit")]
+    AmountLimitExceeded,
+
+    #[msg("Transfer denied: the sender's transfer cooldown has not elapsed")]
+    CooldownActive,
+
+    #[msg("The sender's last-transfer account is missing; initialize it first")]
+    LastTransferMissing,
+
+    #[msg("Execute may only be invoked by Token-2022 during a transfer")]
+    NotTransferring,
 }
@@ -0,0 +1,202 @@
+//! Static description of the hook's instruction account orders and the
+//! stablecoin-core accounts it reads, for integrators calling it directly.
+
+use spl_transfer_hook_interface::instruction::TransferHookInstruction;
+
+pub struct AccountLayout {
+    pub name: &'static str,
+    pub writable: bool,
+    pub signer: bool,
+}
+
+pub struct InstructionLayout {
+    pub name: &'static str,
+    pub discriminator: [u8; 8],
+    pub accounts: &'static [AccountLayout],
+}
+
+pub struct FieldLayout {
+    pub name: &'static str,
+    pub ty: &'static str,
+}
+
+pub struct AccountTypeLayout {
+    pub name: &'static str,
+    pub fields: &'static [FieldLayout],
+}
+
+const fn account(name: &'static str, writable: bool, signer: bool) -> AccountLayout {
+    AccountLayout {
+        name,
+        writable,
+        signer,
+    }
+}
+
+const fn field(name: &'static str, ty: &'static str) -> FieldLayout {
+    FieldLayout { name, ty }
+}
+
+/// Start of the line `execute` logs for every transfer it rules on, unless the
+/// hook is built with the `no-decision-log` feature:
+///
+/// `sss-hook: decision=<allow|deny|exempt> source_owner=<pubkey>
+/// destination_owner=<pubkey> amount=<u64>`
+///
+/// Owners are the token accounts' owners. `exempt` covers moves by the config
+/// PDA and self-transfers skipped by allow_self_transfer; `deny` is logged
+/// right before the error that aborts the transfer.
+pub const DECISION_LOG_PREFIX: &str = "sss-hook:";
+
+/// Account order parsed by `ExecuteAccounts`.
+pub const EXECUTE_ACCOUNTS: &[AccountLayout] = &[
+    account("source_token_account", false, false),
+    account("mint", false, false),
+    account("destination_token_account", false, false),
+    account("source_owner", false, false),
+    account("extra_account_metas", false, false),
+    account("stablecoin_core_program", false, false),
+    account("stablecoin_config", false, false),
+    // Allowlist entries take these two slots when the config is in allowlist mode.
+    account("source_blacklist_entry", false, false),
+    account("destination_blacklist_entry", false, false),
+    account("transfer_hook_program", false, false),
+];
+
+/// Appended to `EXECUTE_ACCOUNTS` for configs with the transfer cooldown
+/// feature: the source owner's LastTransfer PDA, which execute updates.
+pub const EXECUTE_COOLDOWN_ACCOUNT: AccountLayout = account("source_last_transfer", true, false);
+
+/// Anchor-style discriminator of `initialize_last_transfer`, the first 8
+/// bytes of sha256("global:initialize_last_transfer").
+pub const INITIALIZE_LAST_TRANSFER_DISCRIMINATOR: [u8; 8] = [238, 132, 4, 224, 103, 174, 88, 127];
+
+/// Account order parsed by `InitializeAccounts`.
+pub const INITIALIZE_ACCOUNTS: &[AccountLayout] = &[
+    account("extra_account_metas", true, false),
+    account("mint", false, false),
+    account("authority", true, true),
+    account("system_program", false, false),
+];
+
+/// Account order parsed by `UpdateAccounts`.
+pub const UPDATE_ACCOUNTS: &[AccountLayout] = &[
+    account("extra_account_metas", true, false),
+    account("mint", false, false),
+    account("authority", false, true),
+];
+
+/// Account order parsed by `InitializeLastTransferAccounts`.
+pub const INITIALIZE_LAST_TRANSFER_ACCOUNTS: &[AccountLayout] = &[
+    account("payer", true, true),
+    account("stablecoin_config", false, false),
+    account("owner", false, false),
+    account("last_transfer", true, false),
+    account("system_program", false, false),
+];
+
+/// Mirrors `state::StablecoinConfig`.
+pub const STABLECOIN_CONFIG_FIELDS: &[FieldLayout] = &[
+    field("authority", "pubkey"),
+    field("mint", "pubkey"),
+    field("name", "string"),
+    field("symbol", "string"),
+    field("uri", "string"),
+    field("decimals", "u8"),
+    // Bits: legacy (all) 0x01, mint 0x02, burn 0x04, transfer 0x08.
+    field("pause_flags", "u8"),
+    field("total_minted", "u64"),
+    field("total_burned", "u64"),
+    field("audit_counter", "u64"),
+    // Bitfield: permanent_delegate 1<<0, transfer_cooldown 1<<1, transfer_hook 1<<8,
+    // confidential 1<<16, default_frozen 1<<24; other bits are reserved.
+    field("features", "u32"),
+    field("transfer_hook_program", "option<pubkey>"),
+    field("bump", "u8"),
+    field("pause_cooldown", "i64"),
+    field("last_pause_change", "i64"),
+    field("max_supply", "option<u64>"),
+    field("creator", "pubkey"),
+    field("created_at", "i64"),
+    field("quota_window_seconds", "i64"),
+    field("allowlist_mode", "bool"),
+    field("max_transfer_amount", "option<u64>"),
+    field("last_action_at", "i64"),
+    field("last_action_by", "pubkey"),
+    field("allow_self_transfer", "bool"),
+    field("total_seized", "u64"),
+    field("treasury", "Option<Pubkey>"),
+];
+
+/// Mirrors `state::BlacklistEntry`.
+pub const BLACKLIST_ENTRY_FIELDS: &[FieldLayout] = &[
+    field("config", "pubkey"),
+    field("wallet", "pubkey"),
+    field("blacklisted_at", "i64"),
+    field("blacklisted_by", "pubkey"),
+    field("reason", "string"),
+    field("is_active", "bool"),
+    field("bump", "u8"),
+    field("expires_at", "option<i64>"),
+    field("reason_code", "u8"),
+];
+
+/// Mirrors `state::AllowlistEntry`.
+pub const ALLOWLIST_ENTRY_FIELDS: &[FieldLayout] = &[
+    field("config", "pubkey"),
+    field("wallet", "pubkey"),
+    field("is_active", "bool"),
+    field("bump", "u8"),
+];
+
+pub fn instructions() -> [InstructionLayout; 4] {
+    [
+        InstructionLayout {
+            name: "execute",
+            discriminator: discriminator(TransferHookInstruction::Execute { amount: 0 }),
+            accounts: EXECUTE_ACCOUNTS,
+        },
+        InstructionLayout {
+            name: "initialize_extra_account_meta_list",
+            discriminator: discriminator(TransferHookInstruction::InitializeExtraAccountMetaList {
+                extra_account_metas: Vec::new(),
+            }),
+            accounts: INITIALIZE_ACCOUNTS,
+        },
+        InstructionLayout {
+            name: "update_extra_account_meta_list",
+            discriminator: discriminator(TransferHookInstruction::UpdateExtraAccountMetaList {
+                extra_account_metas: Vec::new(),
+            }),
+            accounts: UPDATE_ACCOUNTS,
+        },
+        InstructionLayout {
+            name: "initialize_last_transfer",
+            discriminator: INITIALIZE_LAST_TRANSFER_DISCRIMINATOR,
+            accounts: INITIALIZE_LAST_TRANSFER_ACCOUNTS,
+        },
+    ]
+}
+
+/// Stablecoin-core accounts the execute path deserializes (Anchor discriminators).
+pub const ACCOUNT_TYPES: &[AccountTypeLayout] = &[
+    AccountTypeLayout {
+        name: "StablecoinConfig",
+        fields: STABLECOIN_CONFIG_FIELDS,
+    },
+    AccountTypeLayout {
+        name: "BlacklistEntry",
+        fields: BLACKLIST_ENTRY_FIELDS,
+    },
+    AccountTypeLayout {
+        name: "AllowlistEntry",
+        fields: ALLOWLIST_ENTRY_FIELDS,
+    },
+];
+
+fn discriminator(instruction: TransferHookInstruction) -> [u8; 8] {
+    let packed = instruction.pack();
+    let mut discriminator = [0u8; 8];
+    discriminator.copy_from_slice(&packed[..8]);
+    discriminator
+}
@@ -2,9 +2,10 @@
 
 use anchor_lang::prelude::*;
 use anchor_lang::solana_program::{
-    entrypoint, entrypoint::ProgramResult, program::invoke_signed, program_error::ProgramError,
+    entrypoint::ProgramResult, program::invoke_signed, program_error::ProgramError,
     system_instruction, system_program,
 };
+use anchor_lang::Discriminator;
 use spl_tlv_account_resolution::account::ExtraAccountMeta;
 use spl_tlv_account_resolution::state::ExtraAccountMetaList;
 use spl_transfer_hook_interface::collect_extra_account_metas_signer_seeds;
@@ -14,8 +15,23 @@ use spl_transfer_hook_interface::instruction::{ExecuteInstruction, TransferHookI
 use std::str::FromStr;
 
 mod errors;
+pub mod layout;
 mod state;
 
+/// Length of the base SPL token account layout (mint, owner, amount, ...).
+const TOKEN_ACCOUNT_BASE_LEN: usize = 165;
+
+/// Token-2022 appends an account type byte after the base layout, then its
+/// extensions as (u16 type, u16 length, value) entries.
+const ACCOUNT_TYPE_ACCOUNT: u8 = 2;
+/// ExtensionType::TransferHookAccount, whose value is the `transferring` bool
+/// Token-2022 sets on both token accounts only while it invokes the hook.
+const TRANSFER_HOOK_ACCOUNT_EXTENSION: u16 = 15;
+
+fn token_2022_program_id() -> Pubkey {
+    Pubkey::from_str("TokenzQdBNbLqP5VEhdkAS6EPFLC1PHnBqCXEpPxuEb").expect("valid Token-2022 id")
+}
+
 declare_id!("5gVGKwPB7qstEN5Kp8fJGCURGPGz2GQnYHQAtD1zKSLB");
 
 #[cfg(feature = "idl-build")]
@@ -37,7 +53,8 @@ fn stablecoin_core_program_id() -> Pubkey {
         .expect("valid stablecoin core program id")
 }
 
-entrypoint!(process_instruction);
+#[cfg(not(feature = "no-entrypoint"))]
+anchor_lang::solana_program::entrypoint!(process_instruction);
 
 pub fn process_instruction<'a>(
     program_id: &Pubkey,
@@ -52,13 +69,19 @@ fn process_instruction_inner<'a>(
     accounts: &'a [AccountInfo<'a>],
     instruction_data: &[u8],
 ) -> Result<()> {
+    // The one instruction outside the transfer-hook interface, told apart by
+    // its Anchor-style discriminator.
+    if instruction_data.get(..8) == Some(&layout::INITIALIZE_LAST_TRANSFER_DISCRIMINATOR[..]) {
+        let accounts = InitializeLastTransferAccounts::parse(accounts)?;
+        return initialize_last_transfer(program_id, &accounts);
+    }
     let instruction = TransferHookInstruction::unpack(instruction_data)
         .map_err(|_| errors::TransferHookError::InvalidExtraAccountMetas)?;
 
     match instruction {
-        TransferHookInstruction::Execute { .. } => {
+        TransferHookInstruction::Execute { amount } => {
             let accounts = ExecuteAccounts::parse(accounts)?;
-            execute_handler(program_id, &accounts, instruction_data)
+            execute_handler(program_id, &accounts, instruction_data, amount)
         }
         TransferHookInstruction::InitializeExtraAccountMetaList {
             extra_account_metas,
@@ -86,12 +109,14 @@ struct ExecuteAccounts<'info> {
     source_blacklist_entry: &'info AccountInfo<'info>,
     destination_blacklist_entry: &'info AccountInfo<'info>,
     transfer_hook_program: &'info AccountInfo<'info>,
+    /// Only resolved for configs with the transfer cooldown feature.
+    source_last_transfer: Option<&'info AccountInfo<'info>>,
 }
 
 impl<'info> ExecuteAccounts<'info> {
     fn parse(accounts: &'info [AccountInfo<'info>]) -> Result<Self> {
         require!(
-            accounts.len() >= 10,
+            accounts.len() >= layout::EXECUTE_ACCOUNTS.len(),
             errors::TransferHookError::InvalidExtraAccountMetas
         );
         Ok(Self {
@@ -105,6 +130,7 @@ impl<'info> ExecuteAccounts<'info> {
             source_blacklist_entry: &accounts[7],
             destination_blacklist_entry: &accounts[8],
             transfer_hook_program: &accounts[9],
+            source_last_transfer: accounts.get(layout::EXECUTE_ACCOUNTS.len()),
         })
     }
 }
@@ -119,7 +145,7 @@ struct InitializeAccounts<'info> {
 impl<'info> InitializeAccounts<'info> {
     fn parse(accounts: &'info [AccountInfo<'info>]) -> Result<Self> {
         require!(
-            accounts.len() >= 4,
+            accounts.len() >= layout::INITIALIZE_ACCOUNTS.len(),
             errors::TransferHookError::InvalidExtraAccountMetas
         );
         Ok(Self {
@@ -131,6 +157,30 @@ impl<'info> InitializeAccounts<'info> {
     }
 }
 
+struct InitializeLastTransferAccounts<'info> {
+    payer: &'info AccountInfo<'info>,
+    stablecoin_config: &'info AccountInfo<'info>,
+    owner: &'info AccountInfo<'info>,
+    last_transfer: &'info AccountInfo<'info>,
+    system_program: &'info AccountInfo<'info>,
+}
+
+impl<'info> InitializeLastTransferAccounts<'info> {
+    fn parse(accounts: &'info [AccountInfo<'info>]) -> Result<Self> {
+        require!(
+            accounts.len() >= layout::INITIALIZE_LAST_TRANSFER_ACCOUNTS.len(),
+            errors::TransferHookError::InvalidExtraAccountMetas
+        );
+        Ok(Self {
+            payer: &accounts[0],
+            stablecoin_config: &accounts[1],
+            owner: &accounts[2],
+            last_transfer: &accounts[3],
+            system_program: &accounts[4],
+        })
+    }
+}
+
 struct UpdateAccounts<'info> {
     extra_account_metas: &'info AccountInfo<'info>,
     mint: &'info AccountInfo<'info>,
@@ -140,7 +190,7 @@ struct UpdateAccounts<'info> {
 impl<'info> UpdateAccounts<'info> {
     fn parse(accounts: &'info [AccountInfo<'info>]) -> Result<Self> {
         require!(
-            accounts.len() >= 3,
+            accounts.len() >= layout::UPDATE_ACCOUNTS.len(),
             errors::TransferHookError::InvalidExtraAccountMetas
         );
         Ok(Self {
@@ -155,7 +205,19 @@ fn execute_handler(
     program_id: &Pubkey,
     accounts: &ExecuteAccounts,
     instruction_data: &[u8],
+    amount: u64,
 ) -> Result<()> {
+    // Anyone can call Execute directly; only Token-2022 mid-transfer may
+    // have the hook rule on it and restart the sender's cooldown.
+    for token_account in [
+        accounts.source_token_account,
+        accounts.destination_token_account,
+    ] {
+        require!(
+            is_transferring(token_account),
+            errors::TransferHookError::NotTransferring
+        );
+    }
     require!(
         accounts.extra_account_metas.owner == program_id,
         errors::TransferHookError::InvalidExtraAccountMetas
@@ -179,7 +241,7 @@ fn execute_handler(
 
     let config = deserialize_config(accounts.stablecoin_config)?;
     require!(
-        config.features.transfer_hook,
+        config.features.transfer_hook(),
         errors::TransferHookError::FeatureNotEnabled
     );
     require!(
@@ -190,6 +252,14 @@ fn execute_handler(
         config.mint == *accounts.mint.key,
         errors::TransferHookError::InvalidConfig
     );
+    check_token_account_mints(
+        &config.mint,
+        accounts.mint.key,
+        &[
+            accounts.source_token_account,
+            accounts.destination_token_account,
+        ],
+    )?;
 
     if !accounts.source_blacklist_entry.data_is_empty() {
         require!(
@@ -206,18 +276,219 @@ fn execute_handler(
 
     validate_extra_account_metas(accounts, instruction_data, program_id)?;
 
-    let is_core_authority = accounts.source_owner.key == accounts.stablecoin_config.key;
-    if !is_core_authority {
-        check_blacklist(
-            accounts.source_blacklist_entry,
-            accounts.stablecoin_config.key,
-        )?;
-        check_blacklist(
-            accounts.destination_blacklist_entry,
-            accounts.stablecoin_config.key,
-        )?;
+    let source_owner = token_account_owner(accounts.source_token_account)?;
+    let destination_owner = token_account_owner(accounts.destination_token_account)?;
+    let decision = transfer_decision(&config, accounts, amount, source_owner == destination_owner);
+    if decision.as_ref().ok() == Some(&Decision::Allow) && config.features.transfer_cooldown() {
+        record_last_transfer(accounts)?;
     }
+    #[cfg(not(feature = "no-decision-log"))]
+    msg!(
+        "{} decision={} source_owner={} destination_owner={} amount={}",
+        layout::DECISION_LOG_PREFIX,
+        decision
+            .as_ref()
+            .map_or("deny", |decision| decision.label()),
+        source_owner,
+        destination_owner,
+        amount
+    );
+    decision.map(|_| ())
+}
 
+/// A transfer the hook lets through; denials are the error instead.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Decision {
+    Allow,
+    Exempt,
+}
+
+impl Decision {
+    #[cfg_attr(feature = "no-decision-log", allow(dead_code))]
+    fn label(self) -> &'static str {
+        match self {
+            Decision::Allow => "allow",
+            Decision::Exempt => "exempt",
+        }
+    }
+}
+
+fn transfer_decision(
+    config: &state::StablecoinConfig,
+    accounts: &ExecuteAccounts,
+    amount: u64,
+    same_owner: bool,
+) -> Result<Decision> {
+    // Moves authorized by the config PDA (seize via the permanent delegate) stay
+    // available while paused and ignore the transfer limit so compliance
+    // actions are not blocked.
+    if accounts.source_owner.key == accounts.stablecoin_config.key {
+        return Ok(Decision::Exempt);
+    }
+    require!(
+        !config.transfers_paused(),
+        errors::TransferHookError::SystemPaused
+    );
+    if let Some(limit) = config.max_transfer_amount {
+        require!(
+            amount <= limit,
+            errors::TransferHookError::AmountLimitExceeded
+        );
+    }
+    // Consolidating between one's own token accounts moves no value to
+    // anyone else, so the config can exempt it from the blacklist.
+    if config.allow_self_transfer && !config.allowlist_mode && same_owner {
+        return Ok(Decision::Exempt);
+    }
+    // The extra metas resolve the entry slots to allowlist PDAs in
+    // allowlist mode, so the same two accounts are checked either way.
+    let check_entry = if config.allowlist_mode {
+        check_allowlist
+    } else {
+        check_blacklist
+    };
+    check_entry(
+        accounts.source_blacklist_entry,
+        accounts.stablecoin_config.key,
+    )?;
+    check_entry(
+        accounts.destination_blacklist_entry,
+        accounts.stablecoin_config.key,
+    )?;
+    if let Some(cooldown) = config
+        .transfer_cooldown_seconds
+        .filter(|_| config.features.transfer_cooldown())
+    {
+        check_cooldown(accounts, cooldown)?;
+    }
+    Ok(Decision::Allow)
+}
+
+/// Loads the source owner's LastTransfer account. The extra account metas
+/// already tie its address to the config and owner.
+fn load_last_transfer<'a>(accounts: &ExecuteAccounts<'a>) -> Result<&'a AccountInfo<'a>> {
+    let account = accounts
+        .source_last_transfer
+        .ok_or(errors::TransferHookError::LastTransferMissing)?;
+    require!(
+        account.owner == &ID && !account.data_is_empty(),
+        errors::TransferHookError::LastTransferMissing
+    );
+    Ok(account)
+}
+
+fn read_last_transfer(account: &AccountInfo) -> Result<state::LastTransfer> {
+    let data = account.data.borrow();
+    let mut slice: &[u8] = &data;
+    state::LastTransfer::try_deserialize(&mut slice)
+}
+
+fn check_cooldown(accounts: &ExecuteAccounts, cooldown: i64) -> Result<()> {
+    let last_transfer = read_last_transfer(load_last_transfer(accounts)?)?;
+    if last_transfer.last_transfer_at == 0 {
+        return Ok(());
+    }
+    require!(
+        cooldown_elapsed(
+            last_transfer.last_transfer_at,
+            Clock::get()?.unix_timestamp,
+            cooldown
+        ),
+        errors::TransferHookError::CooldownActive
+    );
+    Ok(())
+}
+
+fn cooldown_elapsed(last_transfer_at: i64, now: i64, cooldown: i64) -> bool {
+    now.saturating_sub(last_transfer_at) >= cooldown
+}
+
+/// Stamps the source owner's LastTransfer with the current time. Only
+/// allowed transfers count, so seizures and self-transfers do not start a
+/// cooldown.
+fn record_last_transfer(accounts: &ExecuteAccounts) -> Result<()> {
+    let account = load_last_transfer(accounts)?;
+    let mut last_transfer = read_last_transfer(account)?;
+    last_transfer.last_transfer_at = Clock::get()?.unix_timestamp;
+    let mut data = account.try_borrow_mut_data()?;
+    last_transfer.try_serialize(&mut &mut data[..])?;
+    Ok(())
+}
+
+/// Creates the LastTransfer PDA an owner needs before sending on a mint with
+/// the transfer cooldown feature. Anyone may pay for it; an existing account
+/// is left untouched so clients can always prepend this instruction.
+fn initialize_last_transfer(
+    program_id: &Pubkey,
+    accounts: &InitializeLastTransferAccounts,
+) -> Result<()> {
+    if !accounts.payer.is_signer {
+        return Err(ProgramError::MissingRequiredSignature.into());
+    }
+    require!(
+        accounts.system_program.key == &system_program::ID,
+        errors::TransferHookError::InvalidExtraAccountMetas
+    );
+    let config = deserialize_config(accounts.stablecoin_config)?;
+    require!(
+        config.features.transfer_cooldown(),
+        errors::TransferHookError::FeatureNotEnabled
+    );
+
+    let (expected, bump) = Pubkey::find_program_address(
+        &[
+            state::LastTransfer::SEED,
+            accounts.stablecoin_config.key.as_ref(),
+            accounts.owner.key.as_ref(),
+        ],
+        program_id,
+    );
+    require!(
+        accounts.last_transfer.key == &expected,
+        errors::TransferHookError::LastTransferMissing
+    );
+    if accounts.last_transfer.owner == program_id {
+        return Ok(());
+    }
+    require!(
+        accounts.last_transfer.owner == &system_program::ID,
+        errors::TransferHookError::LastTransferMissing
+    );
+
+    let space = state::LastTransfer::ACCOUNT_LEN;
+    let lamports = Rent::get()?.minimum_balance(space);
+    let bump_seed = [bump];
+    let signer_seeds: &[&[u8]] = &[
+        state::LastTransfer::SEED,
+        accounts.stablecoin_config.key.as_ref(),
+        accounts.owner.key.as_ref(),
+        &bump_seed,
+    ];
+    let create_ix = system_instruction::create_account(
+        accounts.payer.key,
+        accounts.last_transfer.key,
+        lamports,
+        space as u64,
+        program_id,
+    );
+    invoke_signed(
+        &create_ix,
+        &[
+            accounts.payer.clone(),
+            accounts.last_transfer.clone(),
+            accounts.system_program.clone(),
+        ],
+        &[signer_seeds],
+    )?;
+
+    let last_transfer = state::LastTransfer {
+        config: *accounts.stablecoin_config.key,
+        owner: *accounts.owner.key,
+        last_transfer_at: 0,
+        bump,
+    };
+    let mut data = accounts.last_transfer.try_borrow_mut_data()?;
+    last_transfer.try_serialize(&mut &mut data[..])?;
     Ok(())
 }
 
@@ -317,7 +588,7 @@ fn validate_extra_account_metas(
     instruction_data: &[u8],
     program_id: &Pubkey,
 ) -> Result<()> {
-    let account_infos = vec![
+    let mut account_infos = vec![
         accounts.source_token_account.clone(),
         accounts.mint.clone(),
         accounts.destination_token_account.clone(),
@@ -329,6 +600,7 @@ fn validate_extra_account_metas(
         accounts.destination_blacklist_entry.clone(),
         accounts.transfer_hook_program.clone(),
     ];
+    account_infos.extend(accounts.source_last_transfer.cloned());
     let data = accounts.extra_account_metas.try_borrow_data()?;
     ExtraAccountMetaList::check_account_infos::<ExecuteInstruction>(
         &account_infos,
@@ -340,10 +612,100 @@ fn validate_extra_account_metas(
     Ok(())
 }
 
+/// True for a Token-2022 account whose TransferHookAccount extension has
+/// `transferring` set.
+fn is_transferring(account: &AccountInfo) -> bool {
+    if account.owner != &token_2022_program_id() {
+        return false;
+    }
+    let data = account.data.borrow();
+    if data.get(TOKEN_ACCOUNT_BASE_LEN) != Some(&ACCOUNT_TYPE_ACCOUNT) {
+        return false;
+    }
+    let mut tlv = &data[TOKEN_ACCOUNT_BASE_LEN + 1..];
+    while tlv.len() >= 4 {
+        let extension = u16::from_le_bytes([tlv[0], tlv[1]]);
+        let len = u16::from_le_bytes([tlv[2], tlv[3]]) as usize;
+        let Some(value) = tlv.get(4..4 + len) else {
+            return false;
+        };
+        if extension == TRANSFER_HOOK_ACCOUNT_EXTENSION {
+            return value.first().is_some_and(|&transferring| transferring != 0);
+        }
+        tlv = &tlv[4 + len..];
+    }
+    false
+}
+
+/// Reads the mint stored in the first 32 bytes of a token account.
+fn token_account_mint(account: &AccountInfo) -> Result<Pubkey> {
+    let data = account.data.borrow();
+    require!(
+        data.len() >= TOKEN_ACCOUNT_BASE_LEN,
+        errors::TransferHookError::TokenAccountMintMismatch
+    );
+    let mint: [u8; 32] = data[..32]
+        .try_into()
+        .map_err(|_| errors::TransferHookError::TokenAccountMintMismatch)?;
+    Ok(Pubkey::new_from_array(mint))
+}
+
+/// Requires every token account to hold `mint` and, read from its own data
+/// rather than trusted from the passed mint, to belong to the config's mint.
+/// A config reinitialized for another mint, or a swapped mint whose metas PDA
+/// still lines up, fails the second check with InvalidConfig.
+fn check_token_account_mints(
+    config_mint: &Pubkey,
+    mint: &Pubkey,
+    token_accounts: &[&AccountInfo],
+) -> Result<()> {
+    for account in token_accounts {
+        let account_mint = token_account_mint(account)?;
+        require!(
+            account_mint == *mint,
+            errors::TransferHookError::TokenAccountMintMismatch
+        );
+        require!(
+            account_mint == *config_mint,
+            errors::TransferHookError::InvalidConfig
+        );
+    }
+    Ok(())
+}
+
+/// Reads the owner stored at bytes 32..64 of a token account, the same field
+/// the extra account metas seed the blacklist PDAs from.
+fn token_account_owner(account: &AccountInfo) -> Result<Pubkey> {
+    let data = account.data.borrow();
+    let owner: [u8; 32] = data
+        .get(32..64)
+        .and_then(|bytes| bytes.try_into().ok())
+        .ok_or(errors::TransferHookError::TokenAccountMintMismatch)?;
+    Ok(Pubkey::new_from_array(owner))
+}
+
 fn deserialize_config(account: &AccountInfo) -> Result<state::StablecoinConfig> {
+    require!(
+        account.owner == &stablecoin_core_program_id(),
+        errors::TransferHookError::InvalidConfig
+    );
     let data = account.data.borrow();
-    let mut slice: &[u8] = &data;
-    state::StablecoinConfig::try_deserialize(&mut slice)
+    decode_config(&data)
+}
+
+/// Decodes a config, reporting truncated data, a wrong discriminator, or
+/// strings long enough to run past the end of the account as InvalidConfig
+/// instead of a raw deserialization error. The bound is the account's real
+/// length, since configs created before core grew its allocation are smaller
+/// until migrated.
+fn decode_config(data: &[u8]) -> Result<state::StablecoinConfig> {
+    require!(
+        data.get(..8) == Some(&state::StablecoinConfig::DISCRIMINATOR[..]),
+        errors::TransferHookError::InvalidConfig
+    );
+    let mut body = &data[8..];
+    state::StablecoinConfig::deserialize(&mut body)
+        .map_err(|_| error!(errors::TransferHookError::InvalidConfig))
 }
 
 fn check_blacklist(account: &AccountInfo, expected_config: &Pubkey) -> Result<()> {
@@ -357,8 +719,400 @@ fn check_blacklist(account: &AccountInfo, expected_config: &Pubkey) -> Result<()
     if entry.config != *expected_config {
         return Ok(());
     }
-    if entry.is_active {
+    // Clock is a sysvar syscall, so it is readable in the hook even though
+    // Token-2022 does not pass the Clock account through the CPI.
+    let expired = match entry.expires_at {
+        Some(expires_at) => Clock::get()?.unix_timestamp > expires_at,
+        None => false,
+    };
+    if entry.is_active && !expired {
         return err!(errors::TransferHookError::TransferDenied);
     }
     Ok(())
 }
+
+fn check_allowlist(account: &AccountInfo, expected_config: &Pubkey) -> Result<()> {
+    require!(
+        !account.data_is_empty(),
+        errors::TransferHookError::NotAllowlisted
+    );
+
+    let data = account.data.borrow();
+    let mut slice: &[u8] = &data;
+    let entry = state::AllowlistEntry::try_deserialize(&mut slice)?;
+    require!(
+        entry.config == *expected_config && entry.is_active,
+        errors::TransferHookError::NotAllowlisted
+    );
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn config(name: &str) -> state::StablecoinConfig {
+        state::StablecoinConfig {
+            authority: Pubkey::new_unique(),
+            mint: Pubkey::new_unique(),
+            name: name.to_string(),
+            symbol: "TUSD".to_string(),
+            uri: "https://example.com".to_string(),
+            decimals: 6,
+            pause_flags: 0,
+            total_minted: 0,
+            total_burned: 0,
+            audit_counter: 0,
+            features: state::FeatureFlags::default(),
+            transfer_hook_program: Some(ID),
+            bump: 255,
+            pause_cooldown: 0,
+            last_pause_change: 0,
+            max_supply: None,
+            creator: Pubkey::new_unique(),
+            created_at: 0,
+            quota_window_seconds: 0,
+            allowlist_mode: false,
+            max_transfer_amount: None,
+            last_action_at: 0,
+            last_action_by: Pubkey::new_unique(),
+            allow_self_transfer: false,
+            total_seized: 0,
+            treasury: None,
+            lock_admin_ops: false,
+            minting_locked: false,
+            token_program: Pubkey::default(),
+            transfer_cooldown_seconds: None,
+            require_mint_approval: false,
+        }
+    }
+
+    fn encode(config: &state::StablecoinConfig) -> Vec<u8> {
+        let mut data = Vec::new();
+        config.try_serialize(&mut data).unwrap();
+        data
+    }
+
+    #[test]
+    fn truncated_or_oversized_configs_are_invalid() {
+        let invalid: Error = errors::TransferHookError::InvalidConfig.into();
+        let mut data = encode(&config("Test USD"));
+        for len in 0..data.len() {
+            assert_eq!(
+                decode_config(&data[..len]).err().unwrap(),
+                invalid,
+                "{len} bytes"
+            );
+        }
+        // Both the original 8 + 512 and the current 8 + 640 byte accounts.
+        for account_len in [8 + 512, 8 + 640] {
+            data.resize(account_len, 0);
+            assert_eq!(decode_config(&data).unwrap().name, "Test USD");
+        }
+
+        // Strings that run past the end of a legacy-sized account.
+        let mut oversized = encode(&config(&"x".repeat(512)));
+        oversized.truncate(8 + 512);
+        assert_eq!(decode_config(&oversized).err().unwrap(), invalid);
+
+        data[0] ^= 0xff;
+        assert_eq!(decode_config(&data).err().unwrap(), invalid);
+    }
+
+    #[test]
+    fn token_accounts_must_hold_the_config_mint() {
+        let config_mint = Pubkey::new_unique();
+        let other_mint = Pubkey::new_unique();
+        let token_data = |mint: &Pubkey| {
+            let mut data = vec![0u8; TOKEN_ACCOUNT_BASE_LEN];
+            data[..32].copy_from_slice(mint.as_ref());
+            data
+        };
+        let (key, owner) = (Pubkey::new_unique(), Pubkey::new_unique());
+        let (mut source_lamports, mut destination_lamports) = (0, 0);
+        let mut source_data = token_data(&config_mint);
+        let mut destination_data = token_data(&config_mint);
+        let source = AccountInfo::new(
+            &key,
+            false,
+            false,
+            &mut source_lamports,
+            &mut source_data,
+            &owner,
+            false,
+            0,
+        );
+        let destination = AccountInfo::new(
+            &key,
+            false,
+            false,
+            &mut destination_lamports,
+            &mut destination_data,
+            &owner,
+            false,
+            0,
+        );
+        let accounts = [&source, &destination];
+        assert!(check_token_account_mints(&config_mint, &config_mint, &accounts).is_ok());
+
+        destination.data.borrow_mut()[..32].copy_from_slice(other_mint.as_ref());
+        assert_eq!(
+            check_token_account_mints(&config_mint, &config_mint, &accounts)
+                .err()
+                .unwrap(),
+            errors::TransferHookError::TokenAccountMintMismatch.into()
+        );
+
+        // Both accounts and the passed mint agree with each other but not
+        // with the config: the config was not created for this mint.
+        source.data.borrow_mut()[..32].copy_from_slice(other_mint.as_ref());
+        assert_eq!(
+            check_token_account_mints(&config_mint, &other_mint, &accounts)
+                .err()
+                .unwrap(),
+            errors::TransferHookError::InvalidConfig.into()
+        );
+    }
+
+    fn account_infos<'a>(
+        keys: &'a [Pubkey],
+        lamports: &'a mut [u64],
+        data: &'a mut [Vec<u8>],
+        owner: &'a Pubkey,
+    ) -> Vec<AccountInfo<'a>> {
+        keys.iter()
+            .zip(lamports.iter_mut())
+            .zip(data.iter_mut())
+            .map(|((key, lamports), data)| {
+                AccountInfo::new(key, false, false, lamports, data, owner, false, 0)
+            })
+            .collect()
+    }
+
+    #[test]
+    fn transfers_are_allowed_exempted_or_denied() {
+        let keys: Vec<Pubkey> = layout::EXECUTE_ACCOUNTS
+            .iter()
+            .map(|_| Pubkey::new_unique())
+            .collect();
+        let owner = Pubkey::new_unique();
+        let mut lamports = vec![0u64; keys.len()];
+        let mut data = vec![Vec::<u8>::new(); keys.len()];
+        let infos = account_infos(&keys, &mut lamports, &mut data, &owner);
+        let accounts = ExecuteAccounts::parse(&infos).unwrap();
+        let denied = |result: Result<Decision>| result.err().unwrap();
+
+        let mut config = config("Test USD");
+        assert_eq!(
+            transfer_decision(&config, &accounts, 10, false).unwrap(),
+            Decision::Allow
+        );
+
+        config.max_transfer_amount = Some(5);
+        assert_eq!(
+            denied(transfer_decision(&config, &accounts, 10, false)),
+            errors::TransferHookError::AmountLimitExceeded.into()
+        );
+        config.max_transfer_amount = None;
+
+        config.allow_self_transfer = true;
+        assert_eq!(
+            transfer_decision(&config, &accounts, 10, true).unwrap(),
+            Decision::Exempt
+        );
+        // Allowlist mode ignores the self-transfer exemption, and there are
+        // no entries here.
+        config.allowlist_mode = true;
+        assert_eq!(
+            denied(transfer_decision(&config, &accounts, 10, true)),
+            errors::TransferHookError::NotAllowlisted.into()
+        );
+        config.allowlist_mode = false;
+
+        // PAUSE_TRANSFER
+        config.pause_flags = 0x08;
+        assert_eq!(
+            denied(transfer_decision(&config, &accounts, 10, false)),
+            errors::TransferHookError::SystemPaused.into()
+        );
+
+        // The config PDA moving funds (seize) is exempt even while paused.
+        let mut seize_keys = keys.clone();
+        seize_keys[3] = seize_keys[6];
+        let mut seize_lamports = lamports.clone();
+        let mut seize_data = data.clone();
+        let seize_infos = account_infos(&seize_keys, &mut seize_lamports, &mut seize_data, &owner);
+        let seizure = ExecuteAccounts::parse(&seize_infos).unwrap();
+        assert_eq!(
+            transfer_decision(&config, &seizure, 10, false).unwrap(),
+            Decision::Exempt
+        );
+    }
+
+    #[test]
+    fn cooldown_needs_the_last_transfer_account() {
+        let keys: Vec<Pubkey> = (0..=layout::EXECUTE_ACCOUNTS.len())
+            .map(|_| Pubkey::new_unique())
+            .collect();
+        let owner = Pubkey::new_unique();
+        let mut lamports = vec![0u64; keys.len()];
+        let mut data = vec![Vec::<u8>::new(); keys.len()];
+        let infos = account_infos(&keys, &mut lamports, &mut data, &owner);
+        let mut config = config("Test USD");
+        config.features = state::FeatureFlags::try_from_slice(
+            &state::FeatureFlags::TRANSFER_COOLDOWN.to_le_bytes(),
+        )
+        .unwrap();
+        config.transfer_cooldown_seconds = Some(60);
+
+        let without = ExecuteAccounts::parse(&infos[..keys.len() - 1]).unwrap();
+        assert_eq!(
+            transfer_decision(&config, &without, 10, false)
+                .err()
+                .unwrap(),
+            errors::TransferHookError::LastTransferMissing.into()
+        );
+
+        let mut record = Vec::new();
+        state::LastTransfer {
+            config: keys[6],
+            owner: keys[3],
+            last_transfer_at: 0,
+            bump: 255,
+        }
+        .try_serialize(&mut record)
+        .unwrap();
+        let mut record_lamports = 1;
+        let initialized = AccountInfo::new(
+            &keys[keys.len() - 1],
+            false,
+            true,
+            &mut record_lamports,
+            &mut record,
+            &ID,
+            false,
+            0,
+        );
+        let mut with_infos = infos[..keys.len() - 1].to_vec();
+        with_infos.push(initialized);
+        let with = ExecuteAccounts::parse(&with_infos).unwrap();
+        // Never transferred yet, so no cooldown applies.
+        assert_eq!(
+            transfer_decision(&config, &with, 10, false).unwrap(),
+            Decision::Allow
+        );
+
+        assert!(!cooldown_elapsed(1_000, 1_059, 60));
+        assert!(cooldown_elapsed(1_000, 1_060, 60));
+    }
+
+    /// A Token-2022 account of `mint` with a TransferHookAccount extension.
+    fn hook_token_account(mint: &Pubkey, owner: &Pubkey, transferring: bool) -> Vec<u8> {
+        let mut data = vec![0u8; TOKEN_ACCOUNT_BASE_LEN];
+        data[..32].copy_from_slice(mint.as_ref());
+        data[32..64].copy_from_slice(owner.as_ref());
+        data.push(ACCOUNT_TYPE_ACCOUNT);
+        // An ImmutableOwner entry first, as the associated token program adds.
+        data.extend_from_slice(&7u16.to_le_bytes());
+        data.extend_from_slice(&0u16.to_le_bytes());
+        data.extend_from_slice(&TRANSFER_HOOK_ACCOUNT_EXTENSION.to_le_bytes());
+        data.extend_from_slice(&1u16.to_le_bytes());
+        data.push(transferring as u8);
+        data
+    }
+
+    #[test]
+    fn direct_execute_calls_are_rejected() {
+        let keys: Vec<Pubkey> = (0..=layout::EXECUTE_ACCOUNTS.len())
+            .map(|_| Pubkey::new_unique())
+            .collect();
+        let token_program = token_2022_program_id();
+        let (mint, victim) = (keys[1], keys[3]);
+        let mut lamports = vec![0u64; keys.len()];
+        let mut data = vec![Vec::<u8>::new(); keys.len()];
+        data[0] = hook_token_account(&mint, &victim, false);
+        data[2] = hook_token_account(&mint, &Pubkey::new_unique(), false);
+        let owners: Vec<Pubkey> = (0..keys.len())
+            .map(|index| match index {
+                0 | 2 => token_program,
+                _ => ID,
+            })
+            .collect();
+        let infos: Vec<AccountInfo> = keys
+            .iter()
+            .zip(lamports.iter_mut())
+            .zip(data.iter_mut())
+            .zip(&owners)
+            .map(|(((key, lamports), data), owner)| {
+                AccountInfo::new(key, false, true, lamports, data, owner, false, 0)
+            })
+            .collect();
+        let execute = TransferHookInstruction::Execute { amount: 10 }.pack();
+        let not_transferring: ProgramError =
+            Error::from(errors::TransferHookError::NotTransferring).into();
+        assert_eq!(
+            process_instruction(&ID, &infos, &execute).unwrap_err(),
+            not_transferring
+        );
+
+        assert!(!is_transferring(&infos[0]));
+        let mut transferring = hook_token_account(&mint, &victim, true);
+        let mut account_lamports = 0;
+        let mid_transfer = AccountInfo::new(
+            &keys[0],
+            false,
+            true,
+            &mut account_lamports,
+            &mut transferring,
+            &token_program,
+            false,
+            0,
+        );
+        assert!(is_transferring(&mid_transfer));
+        // The same bytes under another owner are not a token account.
+        let mut forged = hook_token_account(&mint, &victim, true);
+        let mut forged_lamports = 0;
+        let forged = AccountInfo::new(
+            &keys[0],
+            false,
+            true,
+            &mut forged_lamports,
+            &mut forged,
+            &ID,
+            false,
+            0,
+        );
+        assert!(!is_transferring(&forged));
+    }
+
+    #[test]
+    fn initialize_last_transfer_discriminator_matches_its_name() {
+        let hash = anchor_lang::solana_program::hash::hash(b"global:initialize_last_transfer");
+        assert_eq!(
+            hash.to_bytes()[..8],
+            layout::INITIALIZE_LAST_TRANSFER_DISCRIMINATOR
+        );
+    }
+
+    #[test]
+    fn configs_not_owned_by_core_are_invalid() {
+        let key = Pubkey::new_unique();
+        let mut data = encode(&config("Test USD"));
+        let mut lamports = 0;
+        let owner = Pubkey::new_unique();
+        let account = AccountInfo::new(
+            &key,
+            false,
+            false,
+            &mut lamports,
+            &mut data,
+            &owner,
+            false,
+            0,
+        );
+        assert_eq!(
+            deserialize_config(&account).err().unwrap(),
+            errors::TransferHookError::InvalidConfig.into()
+        );
+    }
+}
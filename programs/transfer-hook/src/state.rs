@@ -8,21 +8,59 @@ pub struct StablecoinConfig {
     pub symbol: String,
     pub uri: String,
     pub decimals: u8,
-    pub is_paused: bool,
+    pub pause_flags: u8,
     pub total_minted: u64,
     pub total_burned: u64,
     pub audit_counter: u64,
     pub features: FeatureFlags,
     pub transfer_hook_program: Option<Pubkey>,
     pub bump: u8,
+    pub pause_cooldown: i64,
+    pub last_pause_change: i64,
+    pub max_supply: Option<u64>,
+    pub creator: Pubkey,
+    pub created_at: i64,
+    pub quota_window_seconds: i64,
+    pub allowlist_mode: bool,
+    pub max_transfer_amount: Option<u64>,
+    pub last_action_at: i64,
+    pub last_action_by: Pubkey,
+    pub allow_self_transfer: bool,
+    pub total_seized: u64,
+    pub treasury: Option<Pubkey>,
+    pub lock_admin_ops: bool,
+    pub minting_locked: bool,
+    pub token_program: Pubkey,
+    pub transfer_cooldown_seconds: Option<i64>,
+    pub require_mint_approval: bool,
+}
+
+impl StablecoinConfig {
+    /// The former `is_paused = true` byte, which pauses every operation.
+    const PAUSE_LEGACY: u8 = 0x01;
+    const PAUSE_TRANSFER: u8 = 0x08;
+
+    pub fn transfers_paused(&self) -> bool {
+        self.pause_flags & (Self::PAUSE_LEGACY | Self::PAUSE_TRANSFER) != 0
+    }
 }
 
 #[derive(AnchorSerialize, AnchorDeserialize, Clone, Default)]
 pub struct FeatureFlags {
-    pub permanent_delegate: bool,
-    pub transfer_hook: bool,
-    pub confidential: bool,
-    pub default_frozen: bool,
+    bits: u32,
+}
+
+impl FeatureFlags {
+    pub const TRANSFER_HOOK: u32 = 1 << 8;
+    pub const TRANSFER_COOLDOWN: u32 = 1 << 1;
+
+    pub const fn transfer_hook(&self) -> bool {
+        self.bits & Self::TRANSFER_HOOK != 0
+    }
+
+    pub const fn transfer_cooldown(&self) -> bool {
+        self.bits & Self::TRANSFER_COOLDOWN != 0
+    }
 }
 
 #[account]
@@ -34,4 +72,31 @@ pub struct BlacklistEntry {
     pub reason: String,
     pub is_active: bool,
     pub bump: u8,
+    pub expires_at: Option<i64>,
+    pub reason_code: u8,
+}
+
+/// When an owner last sent a transfer the hook allowed. Owned by this
+/// program, unlike the mirrored core accounts, and written by `execute`.
+#[account]
+pub struct LastTransfer {
+    pub config: Pubkey,
+    pub owner: Pubkey,
+    /// Unix time of the last allowed transfer; 0 before the first one.
+    pub last_transfer_at: i64,
+    pub bump: u8,
+}
+
+impl LastTransfer {
+    /// Seed prefix of ["last-transfer", config, owner].
+    pub const SEED: &'static [u8] = b"last-transfer";
+    pub const ACCOUNT_LEN: usize = 8 + 32 + 32 + 8 + 1;
+}
+
+#[account]
+pub struct AllowlistEntry {
+    pub config: Pubkey,
+    pub wallet: Pubkey,
+    pub is_active: bool,
+    pub bump: u8,
 }
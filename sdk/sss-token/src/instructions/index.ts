@@ -9,17 +9,22 @@ import {
 import {
   ASSOCIATED_TOKEN_PROGRAM_ID,
   TOKEN_2022_PROGRAM_ID,
+  TOKEN_PROGRAM_ID,
 } from "@solana/spl-token";
 
 import {
   STABLECOIN_CORE_PROGRAM_ID,
   TRANSFER_HOOK_PROGRAM_ID,
+  findAllowlistEntryPda,
   findBlacklistEntryPda,
   findConfigPda,
   findExtraAccountMetasPda,
+  findLastTransferPda,
+  findMintRequestPda,
   findRoleAccountPda,
   getAssociatedTokenAddress,
 } from "../utils";
+import { BlacklistReasonCode, TokenProgram } from "../types";
 
 type Amount = bigint | number;
 
@@ -39,6 +44,12 @@ class BorshWriter {
     this.writeU8(value ? 1 : 0);
   }
 
+  writeU32(value: number): void {
+    const buffer = Buffer.alloc(4);
+    buffer.writeUInt32LE(value);
+    this.parts.push(buffer);
+  }
+
   writeU64(value: Amount): void {
     const bigValue = typeof value === "bigint" ? value : BigInt(value);
     if (bigValue < 0n) {
@@ -49,6 +60,13 @@ class BorshWriter {
     this.parts.push(buffer);
   }
 
+  writeI64(value: Amount): void {
+    const bigValue = typeof value === "bigint" ? value : BigInt(value);
+    const buffer = Buffer.alloc(8);
+    buffer.writeBigInt64LE(bigValue);
+    this.parts.push(buffer);
+  }
+
   writeString(value: string): void {
     const bytes = Buffer.from(value, "utf8");
     const length = Buffer.alloc(4);
@@ -101,6 +119,15 @@ function encodeInitializeArgs(params: {
   enableTransferHook: boolean;
   defaultAccountFrozen: boolean;
   transferHookProgramId?: Optional<PublicKey>;
+  pauseCooldown: Amount;
+  enableMetadata: boolean;
+  maxSupply?: Optional<Amount>;
+  enableAllowlist: boolean;
+  enableConfidential: boolean;
+  treasury?: Optional<PublicKey>;
+  tokenProgram: TokenProgram;
+  transferCooldownSeconds?: Optional<Amount>;
+  reserveTransferHook: boolean;
 }): Buffer {
   const writer = new BorshWriter();
   writer.writeString(params.name);
@@ -111,6 +138,15 @@ function encodeInitializeArgs(params: {
   writer.writeBool(params.enableTransferHook);
   writer.writeBool(params.defaultAccountFrozen);
   writer.writeOption(params.transferHookProgramId, (value) => writer.writePubkey(value));
+  writer.writeI64(params.pauseCooldown);
+  writer.writeBool(params.enableMetadata);
+  writer.writeOption(params.maxSupply, (value) => writer.writeU64(value));
+  writer.writeBool(params.enableAllowlist);
+  writer.writeBool(params.enableConfidential);
+  writer.writeOption(params.treasury, (value) => writer.writePubkey(value));
+  writer.writeU8(params.tokenProgram);
+  writer.writeOption(params.transferCooldownSeconds, (value) => writer.writeI64(value));
+  writer.writeBool(params.reserveTransferHook);
   return writer.concat();
 }
 
@@ -118,11 +154,25 @@ function encodeUpdateRolesArgs(
   target: PublicKey,
   roles: number,
   mintQuota?: Optional<Amount>,
+  lifetimeQuota?: Optional<Amount>,
 ): Buffer {
   const writer = new BorshWriter();
   writer.writePubkey(target);
   writer.writeU8(roles);
   writer.writeOption(mintQuota, (value) => writer.writeU64(value));
+  writer.writeOption(lifetimeQuota, (value) => writer.writeU64(value));
+  return writer.concat();
+}
+
+function encodePauseArgs(mask?: Optional<number>): Buffer {
+  const writer = new BorshWriter();
+  writer.writeOption(mask, (value) => writer.writeU8(value));
+  return writer.concat();
+}
+
+function encodeRenounceRoleArgs(roles: number): Buffer {
+  const writer = new BorshWriter();
+  writer.writeU8(roles);
   return writer.concat();
 }
 
@@ -138,10 +188,68 @@ function encodeMintOrBurnArgs(amount: Amount): Buffer {
   return writer.concat();
 }
 
-function encodeAddToBlacklistArgs(wallet: PublicKey, reason: string): Buffer {
+function encodeMintBatchArgs(entries: MintBatchEntry[]): Buffer {
+  const writer = new BorshWriter();
+  writer.writeU32(entries.length);
+  for (const entry of entries) {
+    writer.writePubkey(entry.recipient);
+    writer.writeU64(entry.amount);
+  }
+  return writer.concat();
+}
+
+function encodeUpdateMaxSupplyArgs(maxSupply: Amount): Buffer {
+  const writer = new BorshWriter();
+  writer.writeU64(maxSupply);
+  return writer.concat();
+}
+
+function encodeUpdateQuotaWindowArgs(quotaWindowSeconds: Amount): Buffer {
+  const writer = new BorshWriter();
+  writer.writeI64(quotaWindowSeconds);
+  return writer.concat();
+}
+
+function encodeUpdateMetadataArgs(params: {
+  name?: Optional<string>;
+  symbol?: Optional<string>;
+  uri?: Optional<string>;
+}): Buffer {
+  const writer = new BorshWriter();
+  writer.writeOption(params.name, (value) => writer.writeString(value));
+  writer.writeOption(params.symbol, (value) => writer.writeString(value));
+  writer.writeOption(params.uri, (value) => writer.writeString(value));
+  return writer.concat();
+}
+
+function encodeUpdateMetadataFieldArgs(key: string, value: string): Buffer {
+  const writer = new BorshWriter();
+  writer.writeString(key);
+  writer.writeString(value);
+  return writer.concat();
+}
+
+function encodeAddToBlacklistArgs(
+  wallet: PublicKey,
+  reason: string,
+  expiresAt: Optional<Amount>,
+  reasonCode: BlacklistReasonCode,
+): Buffer {
   const writer = new BorshWriter();
   writer.writePubkey(wallet);
   writer.writeString(reason);
+  writer.writeOption(expiresAt, (value) => writer.writeI64(value));
+  writer.writeU8(reasonCode);
+  return writer.concat();
+}
+
+function encodeUpdateBlacklistEntryArgs(
+  reason: string,
+  reasonCode?: Optional<BlacklistReasonCode>,
+): Buffer {
+  const writer = new BorshWriter();
+  writer.writeString(reason);
+  writer.writeOption(reasonCode, (value) => writer.writeU8(value));
   return writer.concat();
 }
 
@@ -156,6 +264,21 @@ export interface InitializeInstructionParams {
   enableTransferHook?: boolean;
   defaultAccountFrozen?: boolean;
   transferHookProgramId?: PublicKey;
+  pauseCooldown?: Amount;
+  enableMetadata?: boolean;
+  maxSupply?: Optional<Amount>;
+  /** Enforce an allowlist instead of the blacklist; requires the transfer hook. */
+  enableAllowlist?: boolean;
+  /** Add the ConfidentialTransferMint extension (SSS-3). */
+  enableConfidential?: boolean;
+  /** Wallet owning the treasury token account. */
+  treasury?: Optional<PublicKey>;
+  /** LEGACY creates a plain SPL Token mint; every extension must stay off. */
+  tokenProgram?: TokenProgram;
+  /** Minimum seconds between two transfers sent by one owner; requires the transfer hook. */
+  transferCooldownSeconds?: Optional<Amount>;
+  /** Without the hook, add a TransferHook extension with no program so it can be enabled later. */
+  reserveTransferHook?: boolean;
   configPda?: PublicKey;
   roleAccountPda?: PublicKey;
   extraAccountMetasPda?: PublicKey;
@@ -175,6 +298,7 @@ export function buildInitializeInstruction(
   const defaultAccountFrozen = params.defaultAccountFrozen ?? false;
   const transferHookProgramId =
     params.transferHookProgramId ?? TRANSFER_HOOK_PROGRAM_ID;
+  const tokenProgram = params.tokenProgram ?? TokenProgram.TOKEN_2022;
 
   const configPda = params.configPda ?? findConfigPda(params.mint, programId)[0];
   const roleAccountPda =
@@ -206,7 +330,10 @@ export function buildInitializeInstruction(
   });
 
   keys.push({
-    pubkey: params.token2022ProgramId ?? TOKEN_2022_PROGRAM_ID,
+    pubkey:
+      tokenProgram === TokenProgram.LEGACY
+        ? TOKEN_PROGRAM_ID
+        : params.token2022ProgramId ?? TOKEN_2022_PROGRAM_ID,
     isSigner: false,
     isWritable: false,
   });
@@ -230,6 +357,15 @@ export function buildInitializeInstruction(
     enableTransferHook,
     defaultAccountFrozen,
     transferHookProgramId: enableTransferHook ? transferHookProgramId : null,
+    pauseCooldown: params.pauseCooldown ?? 0,
+    enableMetadata: params.enableMetadata ?? true,
+    maxSupply: params.maxSupply ?? null,
+    enableAllowlist: params.enableAllowlist ?? false,
+    enableConfidential: params.enableConfidential ?? false,
+    treasury: params.treasury ?? null,
+    tokenProgram,
+    transferCooldownSeconds: params.transferCooldownSeconds ?? null,
+    reserveTransferHook: params.reserveTransferHook ?? false,
   });
 
   return buildInstruction("initialize", data, keys, programId);
@@ -246,6 +382,11 @@ export interface MintInstructionParams {
   token2022ProgramId?: PublicKey;
   associatedTokenProgramId?: PublicKey;
   systemProgramId?: PublicKey;
+  /**
+   * Recipient's blacklist PDA; on SSS-2 mints it is required and the program
+   * refuses to mint to a blacklisted wallet.
+   */
+  recipientBlacklistEntry?: Optional<PublicKey>;
   programId?: PublicKey;
 }
 
@@ -282,12 +423,260 @@ export function buildMintInstruction(params: MintInstructionParams): Transaction
       isSigner: false,
       isWritable: false,
     },
+    // Anchor reads the program id in an optional account's slot as None.
+    {
+      pubkey: params.recipientBlacklistEntry ?? programId,
+      isSigner: false,
+      isWritable: false,
+    },
   ];
 
   const data = encodeMintOrBurnArgs(params.amount);
   return buildInstruction("mint", data, keys, programId);
 }
 
+export interface ProposeMintInstructionParams {
+  requester: PublicKey;
+  mint: PublicKey;
+  /** Requester-chosen id; part of the request PDA seeds. */
+  id: Amount;
+  recipient: PublicKey;
+  amount: Amount;
+  configPda?: PublicKey;
+  roleAccountPda?: PublicKey;
+  systemProgramId?: PublicKey;
+  programId?: PublicKey;
+}
+
+export function buildProposeMintInstruction(
+  params: ProposeMintInstructionParams,
+): TransactionInstruction {
+  const programId = params.programId ?? STABLECOIN_CORE_PROGRAM_ID;
+  const configPda = params.configPda ?? findConfigPda(params.mint, programId)[0];
+  const roleAccountPda =
+    params.roleAccountPda ??
+    findRoleAccountPda(configPda, params.requester, programId)[0];
+
+  const keys: AccountMeta[] = [
+    { pubkey: params.requester, isSigner: true, isWritable: true },
+    { pubkey: configPda, isSigner: false, isWritable: false },
+    { pubkey: roleAccountPda, isSigner: false, isWritable: false },
+    {
+      pubkey: findMintRequestPda(configPda, BigInt(params.id), programId)[0],
+      isSigner: false,
+      isWritable: true,
+    },
+    {
+      pubkey: params.systemProgramId ?? SystemProgram.programId,
+      isSigner: false,
+      isWritable: false,
+    },
+  ];
+
+  const writer = new BorshWriter();
+  writer.writeU64(params.id);
+  writer.writePubkey(params.recipient);
+  writer.writeU64(params.amount);
+  return buildInstruction("propose_mint", writer.concat(), keys, programId);
+}
+
+export interface ApproveMintInstructionParams {
+  approver: PublicKey;
+  mint: PublicKey;
+  id: Amount;
+  /** Requester and recipient recorded in the MintRequest account. */
+  requester: PublicKey;
+  recipient: PublicKey;
+  configPda?: PublicKey;
+  recipientAta?: PublicKey;
+  token2022ProgramId?: PublicKey;
+  associatedTokenProgramId?: PublicKey;
+  systemProgramId?: PublicKey;
+  /** Recipient's blacklist PDA; required on SSS-2 mints. */
+  recipientBlacklistEntry?: Optional<PublicKey>;
+  programId?: PublicKey;
+}
+
+export function buildApproveMintInstruction(
+  params: ApproveMintInstructionParams,
+): TransactionInstruction {
+  const programId = params.programId ?? STABLECOIN_CORE_PROGRAM_ID;
+  const configPda = params.configPda ?? findConfigPda(params.mint, programId)[0];
+  const token2022ProgramId = params.token2022ProgramId ?? TOKEN_2022_PROGRAM_ID;
+  const associatedTokenProgramId =
+    params.associatedTokenProgramId ?? ASSOCIATED_TOKEN_PROGRAM_ID;
+  const recipientAta =
+    params.recipientAta ??
+    getAssociatedTokenAddress(params.mint, params.recipient, {
+      tokenProgramId: token2022ProgramId,
+      associatedTokenProgramId,
+    });
+
+  const keys: AccountMeta[] = [
+    { pubkey: params.approver, isSigner: true, isWritable: true },
+    { pubkey: configPda, isSigner: false, isWritable: true },
+    {
+      pubkey: findRoleAccountPda(configPda, params.approver, programId)[0],
+      isSigner: false,
+      isWritable: false,
+    },
+    {
+      pubkey: findRoleAccountPda(configPda, params.requester, programId)[0],
+      isSigner: false,
+      isWritable: true,
+    },
+    {
+      pubkey: findMintRequestPda(configPda, BigInt(params.id), programId)[0],
+      isSigner: false,
+      isWritable: true,
+    },
+    { pubkey: params.requester, isSigner: false, isWritable: true },
+    { pubkey: params.mint, isSigner: false, isWritable: true },
+    { pubkey: params.recipient, isSigner: false, isWritable: false },
+    { pubkey: recipientAta, isSigner: false, isWritable: true },
+    { pubkey: token2022ProgramId, isSigner: false, isWritable: false },
+    { pubkey: associatedTokenProgramId, isSigner: false, isWritable: false },
+    {
+      pubkey: params.systemProgramId ?? SystemProgram.programId,
+      isSigner: false,
+      isWritable: false,
+    },
+    // Anchor reads the program id in an optional account's slot as None.
+    {
+      pubkey: params.recipientBlacklistEntry ?? programId,
+      isSigner: false,
+      isWritable: false,
+    },
+  ];
+
+  return buildInstruction("approve_mint", Buffer.alloc(0), keys, programId);
+}
+
+export interface CancelMintInstructionParams {
+  /** The requester, or the master authority. */
+  authority: PublicKey;
+  configPda: PublicKey;
+  id: Amount;
+  requester: PublicKey;
+  programId?: PublicKey;
+}
+
+export function buildCancelMintInstruction(
+  params: CancelMintInstructionParams,
+): TransactionInstruction {
+  const programId = params.programId ?? STABLECOIN_CORE_PROGRAM_ID;
+
+  const keys: AccountMeta[] = [
+    { pubkey: params.authority, isSigner: true, isWritable: false },
+    { pubkey: params.configPda, isSigner: false, isWritable: false },
+    // The requester needs no role account; the program id reads as None.
+    {
+      pubkey: params.authority.equals(params.requester)
+        ? programId
+        : findRoleAccountPda(params.configPda, params.authority, programId)[0],
+      isSigner: false,
+      isWritable: false,
+    },
+    {
+      pubkey: findMintRequestPda(params.configPda, BigInt(params.id), programId)[0],
+      isSigner: false,
+      isWritable: true,
+    },
+    { pubkey: params.requester, isSigner: false, isWritable: true },
+  ];
+
+  return buildInstruction("cancel_mint", Buffer.alloc(0), keys, programId);
+}
+
+export interface UpdateMintApprovalInstructionParams {
+  authority: PublicKey;
+  configPda: PublicKey;
+  /** When true, mint and mint_batch fail and minting needs propose/approve. */
+  requireMintApproval: boolean;
+  roleAccountPda?: PublicKey;
+  programId?: PublicKey;
+}
+
+export function buildUpdateMintApprovalInstruction(
+  params: UpdateMintApprovalInstructionParams,
+): TransactionInstruction {
+  const programId = params.programId ?? STABLECOIN_CORE_PROGRAM_ID;
+  const roleAccountPda =
+    params.roleAccountPda ??
+    findRoleAccountPda(params.configPda, params.authority, programId)[0];
+
+  const keys: AccountMeta[] = [
+    { pubkey: params.authority, isSigner: true, isWritable: false },
+    { pubkey: params.configPda, isSigner: false, isWritable: true },
+    { pubkey: roleAccountPda, isSigner: false, isWritable: false },
+  ];
+
+  const writer = new BorshWriter();
+  writer.writeBool(params.requireMintApproval);
+  return buildInstruction("update_mint_approval", writer.concat(), keys, programId);
+}
+
+export const MAX_MINT_BATCH_SIZE = 16;
+
+export interface MintBatchEntry {
+  recipient: PublicKey;
+  amount: Amount;
+}
+
+export interface MintBatchInstructionParams {
+  minter: PublicKey;
+  mint: PublicKey;
+  entries: MintBatchEntry[];
+  configPda?: PublicKey;
+  roleAccountPda?: PublicKey;
+  token2022ProgramId?: PublicKey;
+  /** Follow each ATA with the recipient's blacklist PDA; required on SSS-2 mints. */
+  checkBlacklist?: boolean;
+  programId?: PublicKey;
+}
+
+/** Recipient ATAs must already exist; they are appended as remaining accounts. */
+export function buildMintBatchInstruction(
+  params: MintBatchInstructionParams,
+): TransactionInstruction {
+  if (params.entries.length === 0 || params.entries.length > MAX_MINT_BATCH_SIZE) {
+    throw new RangeError(`mint_batch takes 1 to ${MAX_MINT_BATCH_SIZE} entries`);
+  }
+  const programId = params.programId ?? STABLECOIN_CORE_PROGRAM_ID;
+  const configPda = params.configPda ?? findConfigPda(params.mint, programId)[0];
+  const roleAccountPda =
+    params.roleAccountPda ??
+    findRoleAccountPda(configPda, params.minter, programId)[0];
+  const token2022ProgramId = params.token2022ProgramId ?? TOKEN_2022_PROGRAM_ID;
+
+  const keys: AccountMeta[] = [
+    { pubkey: params.minter, isSigner: true, isWritable: false },
+    { pubkey: configPda, isSigner: false, isWritable: true },
+    { pubkey: roleAccountPda, isSigner: false, isWritable: true },
+    { pubkey: params.mint, isSigner: false, isWritable: true },
+    { pubkey: token2022ProgramId, isSigner: false, isWritable: false },
+  ];
+  for (const entry of params.entries) {
+    keys.push({
+      pubkey: getAssociatedTokenAddress(params.mint, entry.recipient, {
+        tokenProgramId: token2022ProgramId,
+      }),
+      isSigner: false,
+      isWritable: true,
+    });
+    if (params.checkBlacklist) {
+      keys.push({
+        pubkey: findBlacklistEntryPda(configPda, entry.recipient, programId)[0],
+        isSigner: false,
+        isWritable: false,
+      });
+    }
+  }
+
+  const data = encodeMintBatchArgs(params.entries);
+  return buildInstruction("mint_batch", data, keys, programId);
+}
+
 export interface BurnInstructionParams {
   burner: PublicKey;
   mint: PublicKey;
@@ -377,6 +766,8 @@ export function buildThawInstruction(
 export interface PauseInstructionParams {
   pauser: PublicKey;
   configPda: PublicKey;
+  /** PAUSE_* bits to pause or resume; omitted covers every operation. */
+  mask?: Optional<number>;
   roleAccountPda?: PublicKey;
   programId?: PublicKey;
 }
@@ -395,7 +786,7 @@ export function buildPauseInstruction(
     { pubkey: roleAccountPda, isSigner: false, isWritable: true },
   ];
 
-  return buildInstruction("pause", Buffer.alloc(0), keys, programId);
+  return buildInstruction("pause", encodePauseArgs(params.mask), keys, programId);
 }
 
 export function buildUnpauseInstruction(
@@ -412,7 +803,40 @@ export function buildUnpauseInstruction(
     { pubkey: roleAccountPda, isSigner: false, isWritable: true },
   ];
 
-  return buildInstruction("unpause", Buffer.alloc(0), keys, programId);
+  return buildInstruction(
+    "unpause",
+    encodePauseArgs(params.mask),
+    keys,
+    programId,
+  );
+}
+
+export interface UpdateAdminLockInstructionParams {
+  authority: PublicKey;
+  configPda: PublicKey;
+  /** When true, freeze, thaw, blacklist and seize become master-only. */
+  lockAdminOps: boolean;
+  roleAccountPda?: PublicKey;
+  programId?: PublicKey;
+}
+
+export function buildUpdateAdminLockInstruction(
+  params: UpdateAdminLockInstructionParams,
+): TransactionInstruction {
+  const programId = params.programId ?? STABLECOIN_CORE_PROGRAM_ID;
+  const roleAccountPda =
+    params.roleAccountPda ??
+    findRoleAccountPda(params.configPda, params.authority, programId)[0];
+
+  const keys: AccountMeta[] = [
+    { pubkey: params.authority, isSigner: true, isWritable: false },
+    { pubkey: params.configPda, isSigner: false, isWritable: true },
+    { pubkey: roleAccountPda, isSigner: false, isWritable: false },
+  ];
+
+  const writer = new BorshWriter();
+  writer.writeBool(params.lockAdminOps);
+  return buildInstruction("update_admin_lock", writer.concat(), keys, programId);
 }
 
 export interface UpdateRolesInstructionParams {
@@ -421,6 +845,8 @@ export interface UpdateRolesInstructionParams {
   target: PublicKey;
   roles: number;
   mintQuota?: Optional<Amount>;
+  /** Cap on the minter's lifetime_minted; ignored without the MINTER role. */
+  lifetimeQuota?: Optional<Amount>;
   roleAccountPda?: PublicKey;
   targetRoleAccountPda?: PublicKey;
   systemProgramId?: PublicKey;
@@ -451,7 +877,12 @@ export function buildUpdateRolesInstruction(
     },
   ];
 
-  const data = encodeUpdateRolesArgs(params.target, params.roles, params.mintQuota);
+  const data = encodeUpdateRolesArgs(
+    params.target,
+    params.roles,
+    params.mintQuota,
+    params.lifetimeQuota,
+  );
   return buildInstruction("update_roles", data, keys, programId);
 }
 
@@ -483,6 +914,33 @@ export function buildUpdateMinterInstruction(
   return buildInstruction("update_minter", data, keys, programId);
 }
 
+export interface RenounceRoleInstructionParams {
+  authority: PublicKey;
+  configPda: PublicKey;
+  roles: number;
+  roleAccountPda?: PublicKey;
+  programId?: PublicKey;
+}
+
+/** Drops roles the authority holds itself; an emptied role account is closed. */
+export function buildRenounceRoleInstruction(
+  params: RenounceRoleInstructionParams,
+): TransactionInstruction {
+  const programId = params.programId ?? STABLECOIN_CORE_PROGRAM_ID;
+  const roleAccountPda =
+    params.roleAccountPda ??
+    findRoleAccountPda(params.configPda, params.authority, programId)[0];
+
+  const keys: AccountMeta[] = [
+    { pubkey: params.authority, isSigner: true, isWritable: true },
+    { pubkey: params.configPda, isSigner: false, isWritable: false },
+    { pubkey: roleAccountPda, isSigner: false, isWritable: true },
+  ];
+
+  const data = encodeRenounceRoleArgs(params.roles);
+  return buildInstruction("renounce_role", data, keys, programId);
+}
+
 export interface TransferAuthorityInstructionParams {
   currentAuthority: PublicKey;
   configPda: PublicKey;
@@ -520,13 +978,399 @@ export function buildTransferAuthorityInstruction(
   return buildInstruction("transfer_authority", Buffer.alloc(0), keys, programId);
 }
 
-export interface AddToBlacklistInstructionParams {
-  blacklister: PublicKey;
+export interface UpdateMaxSupplyInstructionParams {
+  authority: PublicKey;
   configPda: PublicKey;
-  wallet: PublicKey;
-  reason: string;
+  mint: PublicKey;
+  maxSupply: Amount;
   roleAccountPda?: PublicKey;
-  blacklistEntryPda?: PublicKey;
+  programId?: PublicKey;
+}
+
+export function buildUpdateMaxSupplyInstruction(
+  params: UpdateMaxSupplyInstructionParams,
+): TransactionInstruction {
+  const programId = params.programId ?? STABLECOIN_CORE_PROGRAM_ID;
+  const roleAccountPda =
+    params.roleAccountPda ??
+    findRoleAccountPda(params.configPda, params.authority, programId)[0];
+
+  const keys: AccountMeta[] = [
+    { pubkey: params.authority, isSigner: true, isWritable: false },
+    { pubkey: params.configPda, isSigner: false, isWritable: true },
+    { pubkey: roleAccountPda, isSigner: false, isWritable: false },
+    { pubkey: params.mint, isSigner: false, isWritable: false },
+  ];
+
+  const data = encodeUpdateMaxSupplyArgs(params.maxSupply);
+  return buildInstruction("update_max_supply", data, keys, programId);
+}
+
+export interface UpdateQuotaWindowInstructionParams {
+  authority: PublicKey;
+  configPda: PublicKey;
+  /** Minter quota window in seconds; the program rejects anything under 60. */
+  quotaWindowSeconds: Amount;
+  roleAccountPda?: PublicKey;
+  programId?: PublicKey;
+}
+
+export function buildUpdateQuotaWindowInstruction(
+  params: UpdateQuotaWindowInstructionParams,
+): TransactionInstruction {
+  const programId = params.programId ?? STABLECOIN_CORE_PROGRAM_ID;
+  const roleAccountPda =
+    params.roleAccountPda ??
+    findRoleAccountPda(params.configPda, params.authority, programId)[0];
+
+  const keys: AccountMeta[] = [
+    { pubkey: params.authority, isSigner: true, isWritable: false },
+    { pubkey: params.configPda, isSigner: false, isWritable: true },
+    { pubkey: roleAccountPda, isSigner: false, isWritable: false },
+  ];
+
+  const data = encodeUpdateQuotaWindowArgs(params.quotaWindowSeconds);
+  return buildInstruction("update_quota_window", data, keys, programId);
+}
+
+export interface UpdateTransferLimitInstructionParams {
+  authority: PublicKey;
+  configPda: PublicKey;
+  /** Largest amount per wallet transfer in base units; null removes the limit. */
+  maxTransferAmount: Optional<Amount>;
+  roleAccountPda?: PublicKey;
+  programId?: PublicKey;
+}
+
+export function buildUpdateTransferLimitInstruction(
+  params: UpdateTransferLimitInstructionParams,
+): TransactionInstruction {
+  const programId = params.programId ?? STABLECOIN_CORE_PROGRAM_ID;
+  const roleAccountPda =
+    params.roleAccountPda ??
+    findRoleAccountPda(params.configPda, params.authority, programId)[0];
+
+  const keys: AccountMeta[] = [
+    { pubkey: params.authority, isSigner: true, isWritable: false },
+    { pubkey: params.configPda, isSigner: false, isWritable: true },
+    { pubkey: roleAccountPda, isSigner: false, isWritable: false },
+  ];
+
+  const writer = new BorshWriter();
+  writer.writeOption(params.maxTransferAmount, (value) => writer.writeU64(value));
+  return buildInstruction("update_transfer_limit", writer.concat(), keys, programId);
+}
+
+export interface FreezeSupplyInstructionParams {
+  authority: PublicKey;
+  configPda: PublicKey;
+  mint: PublicKey;
+  /** Also clear the mint authority; the config PDA stays freeze authority. */
+  nullMintAuthority?: boolean;
+  roleAccountPda?: PublicKey;
+  token2022ProgramId?: PublicKey;
+  programId?: PublicKey;
+}
+
+/** Permanently locks minting. Irreversible. */
+export function buildFreezeSupplyInstruction(
+  params: FreezeSupplyInstructionParams,
+): TransactionInstruction {
+  const programId = params.programId ?? STABLECOIN_CORE_PROGRAM_ID;
+  const roleAccountPda =
+    params.roleAccountPda ??
+    findRoleAccountPda(params.configPda, params.authority, programId)[0];
+
+  const keys: AccountMeta[] = [
+    { pubkey: params.authority, isSigner: true, isWritable: false },
+    { pubkey: params.configPda, isSigner: false, isWritable: true },
+    { pubkey: roleAccountPda, isSigner: false, isWritable: false },
+    { pubkey: params.mint, isSigner: false, isWritable: true },
+    {
+      pubkey: params.token2022ProgramId ?? TOKEN_2022_PROGRAM_ID,
+      isSigner: false,
+      isWritable: false,
+    },
+  ];
+
+  const writer = new BorshWriter();
+  writer.writeBool(params.nullMintAuthority ?? false);
+  return buildInstruction("freeze_supply", writer.concat(), keys, programId);
+}
+
+export interface UpdateTreasuryInstructionParams {
+  authority: PublicKey;
+  configPda: PublicKey;
+  /** Wallet owning the treasury token account; null clears it. */
+  treasury: Optional<PublicKey>;
+  roleAccountPda?: PublicKey;
+  programId?: PublicKey;
+}
+
+export function buildUpdateTreasuryInstruction(
+  params: UpdateTreasuryInstructionParams,
+): TransactionInstruction {
+  const programId = params.programId ?? STABLECOIN_CORE_PROGRAM_ID;
+  const roleAccountPda =
+    params.roleAccountPda ??
+    findRoleAccountPda(params.configPda, params.authority, programId)[0];
+
+  const keys: AccountMeta[] = [
+    { pubkey: params.authority, isSigner: true, isWritable: false },
+    { pubkey: params.configPda, isSigner: false, isWritable: true },
+    { pubkey: roleAccountPda, isSigner: false, isWritable: false },
+  ];
+
+  const writer = new BorshWriter();
+  writer.writeOption(params.treasury, (value) => writer.writePubkey(value));
+  return buildInstruction("update_treasury", writer.concat(), keys, programId);
+}
+
+export interface UpdateDefaultAccountStateInstructionParams {
+  authority: PublicKey;
+  configPda: PublicKey;
+  mint: PublicKey;
+  /** Whether token accounts created from now on start frozen. */
+  defaultFrozen: boolean;
+  roleAccountPda?: PublicKey;
+  token2022ProgramId?: PublicKey;
+  programId?: PublicKey;
+}
+
+export function buildUpdateDefaultAccountStateInstruction(
+  params: UpdateDefaultAccountStateInstructionParams,
+): TransactionInstruction {
+  const programId = params.programId ?? STABLECOIN_CORE_PROGRAM_ID;
+  const roleAccountPda =
+    params.roleAccountPda ??
+    findRoleAccountPda(params.configPda, params.authority, programId)[0];
+
+  const keys: AccountMeta[] = [
+    { pubkey: params.authority, isSigner: true, isWritable: false },
+    { pubkey: params.configPda, isSigner: false, isWritable: true },
+    { pubkey: roleAccountPda, isSigner: false, isWritable: false },
+    { pubkey: params.mint, isSigner: false, isWritable: true },
+    {
+      pubkey: params.token2022ProgramId ?? TOKEN_2022_PROGRAM_ID,
+      isSigner: false,
+      isWritable: false,
+    },
+  ];
+
+  const writer = new BorshWriter();
+  writer.writeBool(params.defaultFrozen);
+  return buildInstruction("update_default_account_state", writer.concat(), keys, programId);
+}
+
+export interface MigrateConfigInstructionParams {
+  /** Pays the rent for the added bytes; any signer may. */
+  payer: PublicKey;
+  configPda: PublicKey;
+  systemProgramId?: PublicKey;
+  programId?: PublicKey;
+}
+
+/** Grows a config allocated before INIT_SPACE was raised; a no-op otherwise. */
+export function buildMigrateConfigInstruction(
+  params: MigrateConfigInstructionParams,
+): TransactionInstruction {
+  const programId = params.programId ?? STABLECOIN_CORE_PROGRAM_ID;
+  const keys: AccountMeta[] = [
+    { pubkey: params.payer, isSigner: true, isWritable: true },
+    { pubkey: params.configPda, isSigner: false, isWritable: true },
+    {
+      pubkey: params.systemProgramId ?? SystemProgram.programId,
+      isSigner: false,
+      isWritable: false,
+    },
+  ];
+  return buildInstruction("migrate_config", Buffer.alloc(0), keys, programId);
+}
+
+export interface EnableTransferHookInstructionParams {
+  authority: PublicKey;
+  configPda: PublicKey;
+  mint: PublicKey;
+  transferHookProgramId?: PublicKey;
+  roleAccountPda?: PublicKey;
+  token2022ProgramId?: PublicKey;
+  systemProgramId?: PublicKey;
+  programId?: PublicKey;
+}
+
+export function buildEnableTransferHookInstruction(
+  params: EnableTransferHookInstructionParams,
+): TransactionInstruction {
+  const programId = params.programId ?? STABLECOIN_CORE_PROGRAM_ID;
+  const roleAccountPda =
+    params.roleAccountPda ??
+    findRoleAccountPda(params.configPda, params.authority, programId)[0];
+  const transferHookProgramId =
+    params.transferHookProgramId ?? TRANSFER_HOOK_PROGRAM_ID;
+
+  const keys: AccountMeta[] = [
+    { pubkey: params.authority, isSigner: true, isWritable: true },
+    { pubkey: params.configPda, isSigner: false, isWritable: true },
+    { pubkey: roleAccountPda, isSigner: false, isWritable: false },
+    { pubkey: params.mint, isSigner: false, isWritable: true },
+    { pubkey: transferHookProgramId, isSigner: false, isWritable: false },
+    {
+      pubkey: findExtraAccountMetasPda(params.mint, transferHookProgramId)[0],
+      isSigner: false,
+      isWritable: true,
+    },
+    {
+      pubkey: params.token2022ProgramId ?? TOKEN_2022_PROGRAM_ID,
+      isSigner: false,
+      isWritable: false,
+    },
+    {
+      pubkey: params.systemProgramId ?? SystemProgram.programId,
+      isSigner: false,
+      isWritable: false,
+    },
+  ];
+
+  return buildInstruction("enable_transfer_hook", Buffer.alloc(0), keys, programId);
+}
+
+export interface InitializeLastTransferInstructionParams {
+  payer: PublicKey;
+  configPda: PublicKey;
+  owner: PublicKey;
+  transferHookProgramId?: PublicKey;
+  systemProgramId?: PublicKey;
+}
+
+/**
+ * Creates the LastTransfer PDA `owner` needs before sending on a mint with a
+ * transfer cooldown. A no-op when it already exists.
+ */
+export function buildInitializeLastTransferInstruction(
+  params: InitializeLastTransferInstructionParams,
+): TransactionInstruction {
+  const transferHookProgramId =
+    params.transferHookProgramId ?? TRANSFER_HOOK_PROGRAM_ID;
+
+  const keys: AccountMeta[] = [
+    { pubkey: params.payer, isSigner: true, isWritable: true },
+    { pubkey: params.configPda, isSigner: false, isWritable: false },
+    { pubkey: params.owner, isSigner: false, isWritable: false },
+    {
+      pubkey: findLastTransferPda(params.configPda, params.owner, transferHookProgramId)[0],
+      isSigner: false,
+      isWritable: true,
+    },
+    {
+      pubkey: params.systemProgramId ?? SystemProgram.programId,
+      isSigner: false,
+      isWritable: false,
+    },
+  ];
+
+  return buildInstruction(
+    "initialize_last_transfer",
+    Buffer.alloc(0),
+    keys,
+    transferHookProgramId,
+  );
+}
+
+export interface UpdateMetadataInstructionParams {
+  authority: PublicKey;
+  configPda: PublicKey;
+  mint: PublicKey;
+  name?: Optional<string>;
+  symbol?: Optional<string>;
+  uri?: Optional<string>;
+  roleAccountPda?: PublicKey;
+  token2022ProgramId?: PublicKey;
+  systemProgramId?: PublicKey;
+  programId?: PublicKey;
+}
+
+export function buildUpdateMetadataInstruction(
+  params: UpdateMetadataInstructionParams,
+): TransactionInstruction {
+  const programId = params.programId ?? STABLECOIN_CORE_PROGRAM_ID;
+  const roleAccountPda =
+    params.roleAccountPda ??
+    findRoleAccountPda(params.configPda, params.authority, programId)[0];
+
+  const keys: AccountMeta[] = [
+    { pubkey: params.authority, isSigner: true, isWritable: true },
+    { pubkey: params.configPda, isSigner: false, isWritable: true },
+    { pubkey: roleAccountPda, isSigner: false, isWritable: false },
+    { pubkey: params.mint, isSigner: false, isWritable: true },
+    {
+      pubkey: params.token2022ProgramId ?? TOKEN_2022_PROGRAM_ID,
+      isSigner: false,
+      isWritable: false,
+    },
+    {
+      pubkey: params.systemProgramId ?? SystemProgram.programId,
+      isSigner: false,
+      isWritable: false,
+    },
+  ];
+
+  const data = encodeUpdateMetadataArgs(params);
+  return buildInstruction("update_metadata", data, keys, programId);
+}
+
+export interface UpdateMetadataFieldInstructionParams {
+  authority: PublicKey;
+  configPda: PublicKey;
+  mint: PublicKey;
+  /** An additional_metadata key; name, symbol and uri go through update_metadata. */
+  key: string;
+  value: string;
+  roleAccountPda?: PublicKey;
+  token2022ProgramId?: PublicKey;
+  systemProgramId?: PublicKey;
+  programId?: PublicKey;
+}
+
+export function buildUpdateMetadataFieldInstruction(
+  params: UpdateMetadataFieldInstructionParams,
+): TransactionInstruction {
+  const programId = params.programId ?? STABLECOIN_CORE_PROGRAM_ID;
+  const roleAccountPda =
+    params.roleAccountPda ??
+    findRoleAccountPda(params.configPda, params.authority, programId)[0];
+
+  const keys: AccountMeta[] = [
+    { pubkey: params.authority, isSigner: true, isWritable: true },
+    { pubkey: params.configPda, isSigner: false, isWritable: true },
+    { pubkey: roleAccountPda, isSigner: false, isWritable: false },
+    { pubkey: params.mint, isSigner: false, isWritable: true },
+    {
+      pubkey: params.token2022ProgramId ?? TOKEN_2022_PROGRAM_ID,
+      isSigner: false,
+      isWritable: false,
+    },
+    {
+      pubkey: params.systemProgramId ?? SystemProgram.programId,
+      isSigner: false,
+      isWritable: false,
+    },
+  ];
+
+  const data = encodeUpdateMetadataFieldArgs(params.key, params.value);
+  return buildInstruction("update_metadata_field", data, keys, programId);
+}
+
+export interface AddToBlacklistInstructionParams {
+  blacklister: PublicKey;
+  configPda: PublicKey;
+  wallet: PublicKey;
+  reason: string;
+  /** Unix timestamp after which the entry stops applying; omit for no expiry. */
+  expiresAt?: Optional<Amount>;
+  /** Reason category; defaults to OTHER. */
+  reasonCode?: BlacklistReasonCode;
+  roleAccountPda?: PublicKey;
+  blacklistEntryPda?: PublicKey;
   systemProgramId?: PublicKey;
   programId?: PublicKey;
 }
@@ -555,10 +1399,46 @@ export function buildAddToBlacklistInstruction(
     },
   ];
 
-  const data = encodeAddToBlacklistArgs(params.wallet, params.reason);
+  const data = encodeAddToBlacklistArgs(
+    params.wallet,
+    params.reason,
+    params.expiresAt,
+    params.reasonCode ?? BlacklistReasonCode.OTHER,
+  );
   return buildInstruction("add_to_blacklist", data, keys, programId);
 }
 
+export interface UpdateBlacklistEntryInstructionParams {
+  blacklister: PublicKey;
+  configPda: PublicKey;
+  blacklistEntryPda: PublicKey;
+  reason: string;
+  /** New reason category; omit to keep the current one. */
+  reasonCode?: Optional<BlacklistReasonCode>;
+  roleAccountPda?: PublicKey;
+  programId?: PublicKey;
+}
+
+/** Rewrites the reason of an active entry, keeping blacklistedAt and blacklistedBy. */
+export function buildUpdateBlacklistEntryInstruction(
+  params: UpdateBlacklistEntryInstructionParams,
+): TransactionInstruction {
+  const programId = params.programId ?? STABLECOIN_CORE_PROGRAM_ID;
+  const roleAccountPda =
+    params.roleAccountPda ??
+    findRoleAccountPda(params.configPda, params.blacklister, programId)[0];
+
+  const keys: AccountMeta[] = [
+    { pubkey: params.blacklister, isSigner: true, isWritable: false },
+    { pubkey: params.configPda, isSigner: false, isWritable: true },
+    { pubkey: roleAccountPda, isSigner: false, isWritable: false },
+    { pubkey: params.blacklistEntryPda, isSigner: false, isWritable: true },
+  ];
+
+  const data = encodeUpdateBlacklistEntryArgs(params.reason, params.reasonCode);
+  return buildInstruction("update_blacklist_entry", data, keys, programId);
+}
+
 export interface RemoveFromBlacklistInstructionParams {
   blacklister: PublicKey;
   configPda: PublicKey;
@@ -590,6 +1470,108 @@ export function buildRemoveFromBlacklistInstruction(
   );
 }
 
+export interface CloseBlacklistEntryInstructionParams {
+  blacklister: PublicKey;
+  configPda: PublicKey;
+  blacklistEntryPda: PublicKey;
+  roleAccountPda?: PublicKey;
+  programId?: PublicKey;
+}
+
+/** Closes an inactive blacklist entry, refunding its rent to the blacklister. */
+export function buildCloseBlacklistEntryInstruction(
+  params: CloseBlacklistEntryInstructionParams,
+): TransactionInstruction {
+  const programId = params.programId ?? STABLECOIN_CORE_PROGRAM_ID;
+  const roleAccountPda =
+    params.roleAccountPda ??
+    findRoleAccountPda(params.configPda, params.blacklister, programId)[0];
+
+  const keys: AccountMeta[] = [
+    { pubkey: params.blacklister, isSigner: true, isWritable: true },
+    { pubkey: params.configPda, isSigner: false, isWritable: false },
+    { pubkey: roleAccountPda, isSigner: false, isWritable: false },
+    { pubkey: params.blacklistEntryPda, isSigner: false, isWritable: true },
+  ];
+
+  return buildInstruction(
+    "close_blacklist_entry",
+    Buffer.alloc(0),
+    keys,
+    programId,
+  );
+}
+
+export interface AddToAllowlistInstructionParams {
+  blacklister: PublicKey;
+  configPda: PublicKey;
+  wallet: PublicKey;
+  roleAccountPda?: PublicKey;
+  allowlistEntryPda?: PublicKey;
+  systemProgramId?: PublicKey;
+  programId?: PublicKey;
+}
+
+export function buildAddToAllowlistInstruction(
+  params: AddToAllowlistInstructionParams,
+): TransactionInstruction {
+  const programId = params.programId ?? STABLECOIN_CORE_PROGRAM_ID;
+  const roleAccountPda =
+    params.roleAccountPda ??
+    findRoleAccountPda(params.configPda, params.blacklister, programId)[0];
+  const allowlistEntryPda =
+    params.allowlistEntryPda ??
+    findAllowlistEntryPda(params.configPda, params.wallet, programId)[0];
+
+  const keys: AccountMeta[] = [
+    { pubkey: params.blacklister, isSigner: true, isWritable: true },
+    { pubkey: params.configPda, isSigner: false, isWritable: true },
+    { pubkey: roleAccountPda, isSigner: false, isWritable: true },
+    { pubkey: allowlistEntryPda, isSigner: false, isWritable: true },
+    { pubkey: params.wallet, isSigner: false, isWritable: false },
+    {
+      pubkey: params.systemProgramId ?? SystemProgram.programId,
+      isSigner: false,
+      isWritable: false,
+    },
+  ];
+
+  const writer = new BorshWriter();
+  writer.writePubkey(params.wallet);
+  return buildInstruction("add_to_allowlist", writer.concat(), keys, programId);
+}
+
+export interface RemoveFromAllowlistInstructionParams {
+  blacklister: PublicKey;
+  configPda: PublicKey;
+  allowlistEntryPda: PublicKey;
+  roleAccountPda?: PublicKey;
+  programId?: PublicKey;
+}
+
+export function buildRemoveFromAllowlistInstruction(
+  params: RemoveFromAllowlistInstructionParams,
+): TransactionInstruction {
+  const programId = params.programId ?? STABLECOIN_CORE_PROGRAM_ID;
+  const roleAccountPda =
+    params.roleAccountPda ??
+    findRoleAccountPda(params.configPda, params.blacklister, programId)[0];
+
+  const keys: AccountMeta[] = [
+    { pubkey: params.blacklister, isSigner: true, isWritable: false },
+    { pubkey: params.configPda, isSigner: false, isWritable: true },
+    { pubkey: roleAccountPda, isSigner: false, isWritable: true },
+    { pubkey: params.allowlistEntryPda, isSigner: false, isWritable: true },
+  ];
+
+  return buildInstruction(
+    "remove_from_allowlist",
+    Buffer.alloc(0),
+    keys,
+    programId,
+  );
+}
+
 export interface SeizeInstructionParams {
   seizer: PublicKey;
   configPda: PublicKey;
@@ -597,11 +1579,15 @@ export interface SeizeInstructionParams {
   targetAta: PublicKey;
   treasuryAta: PublicKey;
   blacklistEntry: PublicKey;
+  /** Amount to seize; the full balance when omitted. */
+  amount?: Optional<Amount>;
   roleAccountPda?: PublicKey;
   extraAccountMetasPda?: PublicKey;
   destinationBlacklistEntry?: PublicKey;
   transferHookProgramId?: PublicKey;
   token2022ProgramId?: PublicKey;
+  /** Target owner's LastTransfer PDA; required when the mint has a transfer cooldown. */
+  lastTransfer?: Optional<PublicKey>;
   programId?: PublicKey;
 }
 
@@ -636,6 +1622,11 @@ export function buildSeizeInstruction(
     { pubkey: transferHookProgramId, isSigner: false, isWritable: false },
     { pubkey: token2022ProgramId, isSigner: false, isWritable: false },
   ];
+  if (params.lastTransfer) {
+    keys.push({ pubkey: params.lastTransfer, isSigner: false, isWritable: true });
+  }
 
-  return buildInstruction("seize", Buffer.alloc(0), keys, programId);
+  const writer = new BorshWriter();
+  writer.writeOption(params.amount, (value) => writer.writeU64(value));
+  return buildInstruction("seize", writer.concat(), keys, programId);
 }
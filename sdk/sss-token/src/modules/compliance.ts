@@ -1,12 +1,20 @@
 import type { Connection } from "@solana/web3.js";
 import { Keypair, PublicKey } from "@solana/web3.js";
 
-import type { AuditLogEntry, AuditLogFilters, BlacklistEntryData, BlacklistStatus } from "../types";
+import type {
+  AuditLogEntry,
+  AuditLogFilters,
+  BlacklistEntryData,
+  BlacklistReasonCode,
+  BlacklistStatus,
+} from "../types";
 import { FeatureNotEnabledError } from "../errors";
 import {
   buildAddToBlacklistInstruction,
+  buildCloseBlacklistEntryInstruction,
   buildRemoveFromBlacklistInstruction,
   buildSeizeInstruction,
+  buildUpdateBlacklistEntryInstruction,
 } from "../instructions";
 import {
   decodeBlacklistEntry,
@@ -63,7 +71,12 @@ export class ComplianceModule {
     return this.authority;
   }
 
-  async blacklistAdd(wallet: PublicKey, reason: string): Promise<string> {
+  async blacklistAdd(
+    wallet: PublicKey,
+    reason: string,
+    expiresAt?: number,
+    reasonCode?: BlacklistReasonCode,
+  ): Promise<string> {
     this.ensureEnabled();
     const authority = this.requireAuthority();
     const instruction = buildAddToBlacklistInstruction({
@@ -71,6 +84,31 @@ export class ComplianceModule {
       configPda: this.configPda,
       wallet,
       reason,
+      expiresAt,
+      reasonCode,
+      programId: this.programId,
+    });
+    return sendInstructions(this.connection, [instruction], [authority]);
+  }
+
+  async blacklistUpdate(
+    wallet: PublicKey,
+    reason: string,
+    reasonCode?: BlacklistReasonCode,
+  ): Promise<string> {
+    this.ensureEnabled();
+    const authority = this.requireAuthority();
+    const blacklistEntryPda = findBlacklistEntryPda(
+      this.configPda,
+      wallet,
+      this.programId,
+    )[0];
+    const instruction = buildUpdateBlacklistEntryInstruction({
+      blacklister: authority.publicKey,
+      configPda: this.configPda,
+      blacklistEntryPda,
+      reason,
+      reasonCode,
       programId: this.programId,
     });
     return sendInstructions(this.connection, [instruction], [authority]);
@@ -93,6 +131,23 @@ export class ComplianceModule {
     return sendInstructions(this.connection, [instruction], [authority]);
   }
 
+  async blacklistClose(wallet: PublicKey): Promise<string> {
+    this.ensureEnabled();
+    const authority = this.requireAuthority();
+    const blacklistEntryPda = findBlacklistEntryPda(
+      this.configPda,
+      wallet,
+      this.programId,
+    )[0];
+    const instruction = buildCloseBlacklistEntryInstruction({
+      blacklister: authority.publicKey,
+      configPda: this.configPda,
+      blacklistEntryPda,
+      programId: this.programId,
+    });
+    return sendInstructions(this.connection, [instruction], [authority]);
+  }
+
   async blacklistCheck(wallet: PublicKey): Promise<BlacklistStatus> {
     this.ensureEnabled();
     const blacklistEntryPda = findBlacklistEntryPda(
@@ -109,9 +164,13 @@ export class ComplianceModule {
       wallet: decoded.wallet,
       isActive: decoded.isActive,
       reason: decoded.reason.length > 0 ? decoded.reason : undefined,
+      expiresAt: decoded.expiresAt ?? undefined,
+      reasonCode: decoded.reasonCode,
     };
+    const expired =
+      decoded.expiresAt !== null && Date.now() / 1000 > decoded.expiresAt;
     return {
-      isBlacklisted: decoded.isActive,
+      isBlacklisted: decoded.isActive && !expired,
       entry,
     };
   }
@@ -119,6 +178,7 @@ export class ComplianceModule {
   async seize(
     targetTokenAccount: PublicKey,
     treasuryTokenAccount: PublicKey,
+    amount?: bigint,
   ): Promise<string> {
     this.ensureEnabled();
     this.ensureSeizeEnabled();
@@ -143,6 +203,7 @@ export class ComplianceModule {
       targetAta: targetTokenAccount,
       treasuryAta: treasuryTokenAccount,
       blacklistEntry,
+      amount,
       programId: this.programId,
     });
     return sendInstructions(this.connection, [instruction], [authority]);
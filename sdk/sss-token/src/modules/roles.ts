@@ -4,6 +4,7 @@ import { Keypair, PublicKey } from "@solana/web3.js";
 import { Role } from "../types";
 import type { RoleAccountData } from "../types";
 import {
+  buildRenounceRoleInstruction,
   buildTransferAuthorityInstruction,
   buildUpdateMinterInstruction,
   buildUpdateRolesInstruction,
@@ -52,7 +53,7 @@ export class RoleManager {
   async grant(
     target: PublicKey,
     role: Role | Role[],
-    options?: { mintQuota?: bigint },
+    options?: { mintQuota?: bigint; lifetimeQuota?: bigint },
   ): Promise<string> {
     const authority = this.requireAuthority();
     const current = await this.getRole(target);
@@ -62,6 +63,10 @@ export class RoleManager {
       (nextRoles & Role.MINTER) !== 0
         ? options?.mintQuota ?? current?.mintQuota ?? null
         : null;
+    const lifetimeQuota =
+      (nextRoles & Role.MINTER) !== 0
+        ? options?.lifetimeQuota ?? current?.lifetimeQuota ?? null
+        : null;
 
     const instruction = buildUpdateRolesInstruction({
       authority: authority.publicKey,
@@ -69,6 +74,7 @@ export class RoleManager {
       target,
       roles: nextRoles,
       mintQuota,
+      lifetimeQuota,
       programId: this.programId,
     });
     return sendInstructions(this.connection, [instruction], [authority]);
@@ -84,6 +90,8 @@ export class RoleManager {
     const nextRoles = current.roles & ~roleMask;
     const mintQuota =
       (nextRoles & Role.MINTER) !== 0 ? current.mintQuota ?? null : null;
+    const lifetimeQuota =
+      (nextRoles & Role.MINTER) !== 0 ? current.lifetimeQuota ?? null : null;
 
     const instruction = buildUpdateRolesInstruction({
       authority: authority.publicKey,
@@ -91,6 +99,18 @@ export class RoleManager {
       target,
       roles: nextRoles,
       mintQuota,
+      lifetimeQuota,
+      programId: this.programId,
+    });
+    return sendInstructions(this.connection, [instruction], [authority]);
+  }
+
+  async renounce(role: Role | Role[]): Promise<string> {
+    const authority = this.requireAuthority();
+    const instruction = buildRenounceRoleInstruction({
+      authority: authority.publicKey,
+      configPda: this.configPda,
+      roles: toRoleMask(role),
       programId: this.programId,
     });
     return sendInstructions(this.connection, [instruction], [authority]);
@@ -125,6 +145,7 @@ export class RoleManager {
       authority: decoded.authority,
       roles: decoded.roles,
       mintQuota: decoded.mintQuota ?? undefined,
+      lifetimeQuota: decoded.lifetimeQuota ?? undefined,
     };
   }
 
@@ -148,6 +169,7 @@ export class RoleManager {
           authority: decoded.authority,
           roles: decoded.roles,
           mintQuota: decoded.mintQuota ?? undefined,
+          lifetimeQuota: decoded.lifetimeQuota ?? undefined,
         });
       } catch {
         continue;
@@ -1,4 +1,5 @@
 export enum Presets {
   SSS_1 = "SSS-1",
   SSS_2 = "SSS-2",
+  SSS_3 = "SSS-3",
 }
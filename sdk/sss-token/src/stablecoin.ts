@@ -18,7 +18,6 @@ import type {
 import { Presets } from "./presets";
 import { ComplianceModule } from "./modules/compliance";
 import { RoleManager } from "./modules/roles";
-import { UnsupportedPresetError } from "./errors";
 import {
   buildBurnInstruction,
   buildFreezeInstruction,
@@ -43,7 +42,7 @@ import {
   TRANSFER_HOOK_PROGRAM_ID,
 } from "./utils";
 
-type PresetLabel = "SSS-1" | "SSS-2" | "custom";
+type PresetLabel = "SSS-1" | "SSS-2" | "SSS-3" | "custom";
 
 interface StablecoinFeatures {
   permanentDelegate: boolean;
@@ -64,7 +63,7 @@ function resolvePreset(config: CreateStablecoinConfig): ResolvedPreset {
   const baseFeatures: StablecoinFeatures = {
     permanentDelegate: basePreset === Presets.SSS_2,
     transferHook: basePreset === Presets.SSS_2,
-    confidential: false,
+    confidential: basePreset === Presets.SSS_3,
     defaultFrozen: false,
   };
 
@@ -78,28 +77,26 @@ function resolvePreset(config: CreateStablecoinConfig): ResolvedPreset {
       config.extensions?.defaultAccountFrozen ?? baseFeatures.defaultFrozen,
   };
 
-  if (features.confidential) {
-    throw new UnsupportedPresetError("Confidential transfer not supported");
-  }
-
   let label: PresetLabel = "custom";
-  if (basePreset === Presets.SSS_1 || basePreset === Presets.SSS_2) {
+  if (
+    basePreset === Presets.SSS_1 ||
+    basePreset === Presets.SSS_2 ||
+    basePreset === Presets.SSS_3
+  ) {
     const expected = basePreset === Presets.SSS_2
       ? { permanentDelegate: true, transferHook: true }
       : { permanentDelegate: false, transferHook: false };
     if (
       features.permanentDelegate === expected.permanentDelegate &&
       features.transferHook === expected.transferHook &&
-      !hasExtensions
-    ) {
-      label = basePreset === Presets.SSS_2 ? "SSS-2" : "SSS-1";
-    } else if (
-      features.permanentDelegate === expected.permanentDelegate &&
-      features.transferHook === expected.transferHook &&
-      !config.extensions?.defaultAccountFrozen &&
-      !config.extensions?.confidentialTransfer
+      features.confidential === baseFeatures.confidential &&
+      !config.extensions?.defaultAccountFrozen
     ) {
-      label = basePreset === Presets.SSS_2 ? "SSS-2" : "SSS-1";
+      label = basePreset === Presets.SSS_2
+        ? "SSS-2"
+        : basePreset === Presets.SSS_3
+          ? "SSS-3"
+          : "SSS-1";
     }
   }
 
@@ -177,6 +174,11 @@ export class SolanaStablecoin {
       enableTransferHook: resolved.features.transferHook,
       defaultAccountFrozen: resolved.features.defaultFrozen,
       transferHookProgramId,
+      pauseCooldown: config.pauseCooldown,
+      enableMetadata: config.extensions?.metadata ?? true,
+      maxSupply: config.maxSupply,
+      enableAllowlist: config.extensions?.allowlist ?? false,
+      enableConfidential: resolved.features.confidential,
       configPda,
       roleAccountPda,
       extraAccountMetasPda,
@@ -212,7 +214,11 @@ export class SolanaStablecoin {
       throw new Error("Stablecoin config not found");
     }
     const decoded = decodeStablecoinConfig(configAccount.data);
-    const preset: PresetLabel = decoded.features.transferHook ? "SSS-2" : "SSS-1";
+    const preset: PresetLabel = decoded.features.confidential
+      ? "SSS-3"
+      : decoded.features.transferHook
+        ? "SSS-2"
+        : "SSS-1";
     const features: StablecoinFeatures = {
       permanentDelegate: decoded.features.permanentDelegate,
       transferHook: decoded.features.transferHook,
@@ -235,12 +241,16 @@ export class SolanaStablecoin {
     if (!minter) {
       throw new Error("Missing minter keypair");
     }
+    const transferHookEnabled = this.features?.transferHook ?? this.preset === "SSS-2";
     const instruction = buildMintInstruction({
       minter: minter.publicKey,
       mint: this.mintAddress,
       recipient: params.recipient,
       amount: params.amount,
       configPda: this.configPda,
+      recipientBlacklistEntry: transferHookEnabled
+        ? findBlacklistEntryPda(this.configPda, params.recipient, this.programId)[0]
+        : null,
       programId: this.programId,
     });
     return sendInstructions(this.connection, [instruction], [minter]);
@@ -295,7 +305,8 @@ export class SolanaStablecoin {
     return sendInstructions(this.connection, [instruction], [freezer]);
   }
 
-  async pause(): Promise<string> {
+  /** Pauses the PAUSE_* operations in `mask`, or every operation when omitted. */
+  async pause(mask?: number): Promise<string> {
     const pauser = this.authority;
     if (!pauser) {
       throw new Error("Missing pauser keypair");
@@ -303,12 +314,14 @@ export class SolanaStablecoin {
     const instruction = buildPauseInstruction({
       pauser: pauser.publicKey,
       configPda: this.configPda,
+      mask,
       programId: this.programId,
     });
     return sendInstructions(this.connection, [instruction], [pauser]);
   }
 
-  async unpause(): Promise<string> {
+  /** Resumes the PAUSE_* operations in `mask`, or every operation when omitted. */
+  async unpause(mask?: number): Promise<string> {
     const pauser = this.authority;
     if (!pauser) {
       throw new Error("Missing pauser keypair");
@@ -316,6 +329,7 @@ export class SolanaStablecoin {
     const instruction = buildUnpauseInstruction({
       pauser: pauser.publicKey,
       configPda: this.configPda,
+      mask,
       programId: this.programId,
     });
     return sendInstructions(this.connection, [instruction], [pauser]);
@@ -340,6 +354,7 @@ export class SolanaStablecoin {
       uri: decoded.uri,
       decimals: decoded.decimals,
       isPaused: decoded.isPaused,
+      pauseFlags: decoded.pauseFlags,
     };
   }
 
@@ -383,6 +398,7 @@ export class SolanaStablecoin {
           wallet: decoded.wallet,
           isActive: decoded.isActive,
           reason: decoded.reason.length > 0 ? decoded.reason : undefined,
+          reasonCode: decoded.reasonCode,
         });
       } catch {
         continue;
@@ -402,6 +418,7 @@ export class SolanaStablecoin {
       wallet: decoded.wallet,
       isActive: decoded.isActive,
       reason: decoded.reason.length > 0 ? decoded.reason : undefined,
+      reasonCode: decoded.reasonCode,
     };
     return {
       isBlacklisted: decoded.isActive,
@@ -420,6 +437,7 @@ export class SolanaStablecoin {
       authority: decoded.authority,
       roles: decoded.roles,
       mintQuota: decoded.mintQuota ?? undefined,
+      lifetimeQuota: decoded.lifetimeQuota ?? undefined,
     };
   }
 
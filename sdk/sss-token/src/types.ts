@@ -8,11 +8,18 @@ export interface CreateStablecoinConfig {
   decimals?: number;
   uri?: string;
   authority: Keypair;
+  /** Minimum seconds between pause state changes by non-master pausers. */
+  pauseCooldown?: number;
+  /** Hard supply ceiling in base units; omitted leaves supply uncapped. */
+  maxSupply?: bigint | number;
   extensions?: {
     permanentDelegate?: boolean;
     transferHook?: boolean;
     defaultAccountFrozen?: boolean;
     confidentialTransfer?: boolean;
+    metadata?: boolean;
+    /** Only allowlisted wallets may hold the token; requires the transfer hook. */
+    allowlist?: boolean;
   };
 }
 
@@ -24,6 +31,24 @@ export enum Role {
   PAUSER = 0x10,
   BLACKLISTER = 0x20,
   SEIZER = 0x40,
+  /** Grants and revokes the operational roles, never master or minter. */
+  ROLE_ADMIN = 0x80,
+}
+
+/** Why a wallet was blacklisted; UNSPECIFIED only marks entries that predate codes. */
+export enum BlacklistReasonCode {
+  UNSPECIFIED = 0,
+  SANCTIONS = 1,
+  FRAUD = 2,
+  COURT_ORDER = 3,
+  OTHER = 4,
+}
+
+/** Token program a mint is created under; encodes as the on-chain enum index. */
+export enum TokenProgram {
+  TOKEN_2022 = 0,
+  /** The original SPL Token program; plain SSS-1 mints without extensions only. */
+  LEGACY = 1,
 }
 
 export interface MintParams {
@@ -45,18 +70,23 @@ export interface StablecoinConfigData {
   uri: string;
   decimals: number;
   isPaused: boolean;
+  /** PAUSE_* bits of the paused operations. */
+  pauseFlags: number;
 }
 
 export interface RoleAccountData {
   authority: PublicKey;
   roles: number;
   mintQuota?: bigint;
+  lifetimeQuota?: bigint;
 }
 
 export interface BlacklistEntryData {
   wallet: PublicKey;
   isActive: boolean;
   reason?: string;
+  expiresAt?: number;
+  reasonCode: BlacklistReasonCode;
 }
 
 export interface BlacklistStatus {
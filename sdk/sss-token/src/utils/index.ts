@@ -30,7 +30,10 @@ export const RENT_SYSVAR_ID = SYSVAR_RENT_PUBKEY;
 const CONFIG_SEED = Buffer.from("stablecoin");
 const ROLE_SEED = Buffer.from("role");
 const BLACKLIST_SEED = Buffer.from("blacklist");
+const ALLOWLIST_SEED = Buffer.from("allowlist");
+const MINT_REQUEST_SEED = Buffer.from("mint_request");
 const EXTRA_ACCOUNT_METAS_SEED = Buffer.from("extra-account-metas");
+const LAST_TRANSFER_SEED = Buffer.from("last-transfer");
 
 export function findConfigPda(
   mint: PublicKey,
@@ -64,6 +67,30 @@ export function findBlacklistEntryPda(
   );
 }
 
+export function findAllowlistEntryPda(
+  config: PublicKey,
+  wallet: PublicKey,
+  programId: PublicKey = STABLECOIN_CORE_PROGRAM_ID,
+): [PublicKey, number] {
+  return PublicKey.findProgramAddressSync(
+    [ALLOWLIST_SEED, config.toBuffer(), wallet.toBuffer()],
+    programId,
+  );
+}
+
+export function findMintRequestPda(
+  config: PublicKey,
+  id: bigint | number,
+  programId: PublicKey = STABLECOIN_CORE_PROGRAM_ID,
+): [PublicKey, number] {
+  const idBytes = Buffer.alloc(8);
+  idBytes.writeBigUInt64LE(BigInt(id));
+  return PublicKey.findProgramAddressSync(
+    [MINT_REQUEST_SEED, config.toBuffer(), idBytes],
+    programId,
+  );
+}
+
 export function findExtraAccountMetasPda(
   mint: PublicKey,
   transferHookProgramId: PublicKey = TRANSFER_HOOK_PROGRAM_ID,
@@ -74,6 +101,18 @@ export function findExtraAccountMetasPda(
   );
 }
 
+/** Hook-owned record of when `owner` last sent a transfer. */
+export function findLastTransferPda(
+  config: PublicKey,
+  owner: PublicKey,
+  transferHookProgramId: PublicKey = TRANSFER_HOOK_PROGRAM_ID,
+): [PublicKey, number] {
+  return PublicKey.findProgramAddressSync(
+    [LAST_TRANSFER_SEED, config.toBuffer(), owner.toBuffer()],
+    transferHookProgramId,
+  );
+}
+
 export function getAssociatedTokenAddress(
   mint: PublicKey,
   owner: PublicKey,
@@ -176,13 +215,37 @@ function decodeAccountData<T>(
   return decode(reader);
 }
 
+/** Bits of `StablecoinConfig.pauseFlags`; PAUSE_LEGACY reads as all paused. */
+export const PAUSE_LEGACY = 0x01;
+export const PAUSE_MINT = 0x02;
+export const PAUSE_BURN = 0x04;
+export const PAUSE_TRANSFER = 0x08;
+export const PAUSE_ALL = PAUSE_MINT | PAUSE_BURN | PAUSE_TRANSFER;
+
+/** Bit positions of `StablecoinConfig.features`; other bits are reserved. */
+export const FEATURE_PERMANENT_DELEGATE = 1 << 0;
+export const FEATURE_TRANSFER_HOOK = 1 << 8;
+export const FEATURE_CONFIDENTIAL = 1 << 16;
+export const FEATURE_DEFAULT_FROZEN = 1 << 24;
+
 export interface FeatureFlagsData {
+  bits: number;
   permanentDelegate: boolean;
   transferHook: boolean;
   confidential: boolean;
   defaultFrozen: boolean;
 }
 
+export function decodeFeatureFlags(bits: number): FeatureFlagsData {
+  return {
+    bits,
+    permanentDelegate: (bits & FEATURE_PERMANENT_DELEGATE) !== 0,
+    transferHook: (bits & FEATURE_TRANSFER_HOOK) !== 0,
+    confidential: (bits & FEATURE_CONFIDENTIAL) !== 0,
+    defaultFrozen: (bits & FEATURE_DEFAULT_FROZEN) !== 0,
+  };
+}
+
 export interface StablecoinConfigAccountData {
   authority: PublicKey;
   mint: PublicKey;
@@ -190,6 +253,7 @@ export interface StablecoinConfigAccountData {
   symbol: string;
   uri: string;
   decimals: number;
+  pauseFlags: number;
   isPaused: boolean;
   totalMinted: bigint;
   totalBurned: bigint;
@@ -207,6 +271,9 @@ export interface RoleAccountDataInternal {
   mintedCurrentWindow: bigint;
   windowStart: number;
   bump: number;
+  lifetimeMinted: bigint;
+  windowResets: number;
+  lifetimeQuota: bigint | null;
 }
 
 export interface BlacklistEntryDataInternal {
@@ -217,6 +284,8 @@ export interface BlacklistEntryDataInternal {
   reason: string;
   isActive: boolean;
   bump: number;
+  expiresAt: number | null;
+  reasonCode: number;
 }
 
 export function decodeStablecoinConfig(data: Buffer): StablecoinConfigAccountData {
@@ -227,16 +296,11 @@ export function decodeStablecoinConfig(data: Buffer): StablecoinConfigAccountDat
     const symbol = reader.readString();
     const uri = reader.readString();
     const decimals = reader.readU8();
-    const isPaused = reader.readBool();
+    const pauseFlags = reader.readU8();
     const totalMinted = reader.readU64();
     const totalBurned = reader.readU64();
     const auditCounter = reader.readU64();
-    const features: FeatureFlagsData = {
-      permanentDelegate: reader.readBool(),
-      transferHook: reader.readBool(),
-      confidential: reader.readBool(),
-      defaultFrozen: reader.readBool(),
-    };
+    const features = decodeFeatureFlags(reader.readU32());
     const transferHookProgram = reader.readOption(() => reader.readPubkey());
     const bump = reader.readU8();
     return {
@@ -246,7 +310,8 @@ export function decodeStablecoinConfig(data: Buffer): StablecoinConfigAccountDat
       symbol,
       uri,
       decimals,
-      isPaused,
+      pauseFlags,
+      isPaused: pauseFlags !== 0,
       totalMinted,
       totalBurned,
       auditCounter,
@@ -266,6 +331,9 @@ export function decodeRoleAccount(data: Buffer): RoleAccountDataInternal {
     const mintedCurrentWindow = reader.readU64();
     const windowStart = reader.readI64();
     const bump = reader.readU8();
+    const lifetimeMinted = reader.readU64();
+    const windowResets = reader.readU32();
+    const lifetimeQuota = reader.readOption(() => reader.readU64());
     return {
       config,
       authority,
@@ -274,6 +342,9 @@ export function decodeRoleAccount(data: Buffer): RoleAccountDataInternal {
       mintedCurrentWindow,
       windowStart,
       bump,
+      lifetimeMinted,
+      windowResets,
+      lifetimeQuota,
     };
   });
 }
@@ -287,6 +358,8 @@ export function decodeBlacklistEntry(data: Buffer): BlacklistEntryDataInternal {
     const reason = reader.readString();
     const isActive = reader.readBool();
     const bump = reader.readU8();
+    const expiresAt = reader.readOption(() => reader.readI64());
+    const reasonCode = reader.readU8();
     return {
       config,
       wallet,
@@ -295,6 +368,8 @@ export function decodeBlacklistEntry(data: Buffer): BlacklistEntryDataInternal {
       reason,
       isActive,
       bump,
+      expiresAt,
+      reasonCode,
     };
   });
 }
@@ -28,6 +28,7 @@ export const stablecoinIdl = {
         { name: "minter", type: "publicKey", index: false },
         { name: "new_total_supply", type: "u64", index: false },
         { name: "timestamp", type: "i64", index: false },
+        { name: "schema_version", type: "u16", index: false },
       ],
     },
     {
@@ -39,6 +40,7 @@ export const stablecoinIdl = {
         { name: "amount", type: "u64", index: false },
         { name: "new_total_supply", type: "u64", index: false },
         { name: "timestamp", type: "i64", index: false },
+        { name: "schema_version", type: "u16", index: false },
       ],
     },
     {
@@ -65,6 +67,7 @@ export const stablecoinIdl = {
         { name: "config", type: "publicKey", index: false },
         { name: "paused_by", type: "publicKey", index: false },
         { name: "timestamp", type: "i64", index: false },
+        { name: "pause_flags", type: "u8", index: false },
       ],
     },
     {
@@ -73,6 +76,7 @@ export const stablecoinIdl = {
         { name: "config", type: "publicKey", index: false },
         { name: "unpaused_by", type: "publicKey", index: false },
         { name: "timestamp", type: "i64", index: false },
+        { name: "pause_flags", type: "u8", index: false },
       ],
     },
     {
@@ -81,6 +85,8 @@ export const stablecoinIdl = {
         { name: "config", type: "publicKey", index: false },
         { name: "target", type: "publicKey", index: false },
         { name: "new_roles", type: "u8", index: false },
+        { name: "added_roles", type: "u8", index: false },
+        { name: "removed_roles", type: "u8", index: false },
         { name: "updated_by", type: "publicKey", index: false },
         { name: "timestamp", type: "i64", index: false },
       ],
@@ -102,6 +108,20 @@ export const stablecoinIdl = {
         { name: "reason", type: "string", index: false },
         { name: "blacklisted_by", type: "publicKey", index: false },
         { name: "timestamp", type: "i64", index: false },
+        { name: "expires_at", type: { option: "i64" }, index: false },
+        { name: "schema_version", type: "u16", index: false },
+        { name: "reason_code", type: "u8", index: false },
+      ],
+    },
+    {
+      name: "BlacklistUpdated",
+      fields: [
+        { name: "config", type: "publicKey", index: false },
+        { name: "wallet", type: "publicKey", index: false },
+        { name: "reason", type: "string", index: false },
+        { name: "reason_code", type: "u8", index: false },
+        { name: "updated_by", type: "publicKey", index: false },
+        { name: "timestamp", type: "i64", index: false },
       ],
     },
     {
@@ -122,6 +142,30 @@ export const stablecoinIdl = {
         { name: "amount", type: "u64", index: false },
         { name: "seized_by", type: "publicKey", index: false },
         { name: "timestamp", type: "i64", index: false },
+        { name: "schema_version", type: "u16", index: false },
+        { name: "total_seized", type: "u64", index: false },
+      ],
+    },
+    {
+      name: "TokensSeizedAndBurned",
+      fields: [
+        { name: "config", type: "publicKey", index: false },
+        { name: "from_account", type: "publicKey", index: false },
+        { name: "amount", type: "u64", index: false },
+        { name: "new_total_supply", type: "u64", index: false },
+        { name: "seized_by", type: "publicKey", index: false },
+        { name: "timestamp", type: "i64", index: false },
+      ],
+    },
+    {
+      name: "QuotaWindowReset",
+      fields: [
+        { name: "config", type: "publicKey", index: false },
+        { name: "role_account", type: "publicKey", index: false },
+        { name: "minter", type: "publicKey", index: false },
+        { name: "previous_window_total", type: "u64", index: false },
+        { name: "window_start", type: "i64", index: false },
+        { name: "timestamp", type: "i64", index: false },
       ],
     },
   ],
@@ -28,6 +28,7 @@ export interface StablecoinConfigData {
   symbol: string;
   uri: string;
   decimals: number;
+  pauseFlags: number;
   isPaused: boolean;
   totalMinted: bigint;
   totalBurned: bigint;
@@ -37,10 +38,22 @@ export interface StablecoinConfigData {
   bump: number;
 }
 
+/** Mirrors stablecoin-core's `BlacklistReason`; 0 marks entries that predate codes. */
+export const BLACKLIST_REASON_CODES = {
+  sanctions: 1,
+  fraud: 2,
+  "court-order": 3,
+  other: 4,
+} as const;
+
+export type BlacklistReasonName = keyof typeof BLACKLIST_REASON_CODES;
+
 export interface BlacklistEntryData {
   wallet: PublicKey;
   reason: string | null;
   isActive: boolean;
+  expiresAt: number | null;
+  reasonCode: number;
 }
 
 export interface SolanaClientConfig {
@@ -162,7 +175,12 @@ export class StablecoinClient {
     return sendInstructions(this.connection, [instruction], [this.authority]);
   }
 
-  async addToBlacklist(wallet: PublicKey, reason: string): Promise<string> {
+  async addToBlacklist(
+    wallet: PublicKey,
+    reason: string,
+    expiresAt: number | null = null,
+    reasonCode: number = BLACKLIST_REASON_CODES.other,
+  ): Promise<string> {
     const configPda = findConfigPda(this.mint, this.programId)[0];
     const rolePda = findRolePda(configPda, this.authority.publicKey, this.programId)[0];
     const blacklistPda = findBlacklistPda(configPda, wallet, this.programId)[0];
@@ -173,6 +191,8 @@ export class StablecoinClient {
       blacklistPda,
       wallet,
       reason,
+      expiresAt,
+      reasonCode,
       programId: this.programId,
     });
     return sendInstructions(this.connection, [instruction], [this.authority]);
@@ -353,6 +373,12 @@ class BorshWriter {
     this.parts.push(buffer);
   }
 
+  writeI64(value: bigint | number): void {
+    const buffer = Buffer.alloc(8);
+    buffer.writeBigInt64LE(typeof value === "bigint" ? value : BigInt(value));
+    this.parts.push(buffer);
+  }
+
   writeString(value: string): void {
     const bytes = Buffer.from(value, "utf8");
     const length = Buffer.alloc(4);
@@ -465,15 +491,18 @@ export function decodeStablecoinConfig(data: Buffer): StablecoinConfigData {
     const symbol = reader.readString();
     const uri = reader.readString();
     const decimals = reader.readU8();
-    const isPaused = reader.readBool();
+    // Bits: legacy (all) 0x01, mint 0x02, burn 0x04, transfer 0x08.
+    const pauseFlags = reader.readU8();
     const totalMinted = reader.readU64();
     const totalBurned = reader.readU64();
     const auditCounter = reader.readU64();
+    // u32 bitfield; the flags sit on bit 0 of each of its bytes.
+    const featureBits = reader.readU32();
     const features = {
-      permanentDelegate: reader.readBool(),
-      transferHook: reader.readBool(),
-      confidential: reader.readBool(),
-      defaultFrozen: reader.readBool(),
+      permanentDelegate: (featureBits & (1 << 0)) !== 0,
+      transferHook: (featureBits & (1 << 8)) !== 0,
+      confidential: (featureBits & (1 << 16)) !== 0,
+      defaultFrozen: (featureBits & (1 << 24)) !== 0,
     };
     const transferHookProgram = reader.readOption(() => reader.readPubkey());
     const bump = reader.readU8();
@@ -484,7 +513,8 @@ export function decodeStablecoinConfig(data: Buffer): StablecoinConfigData {
       symbol,
       uri,
       decimals,
-      isPaused,
+      pauseFlags,
+      isPaused: pauseFlags !== 0,
       totalMinted,
       totalBurned,
       auditCounter,
@@ -504,10 +534,15 @@ export function decodeBlacklistEntry(data: Buffer): BlacklistEntryData {
     const reason = reader.readString();
     const isActive = reader.readBool();
     reader.readU8();
+    const expiresAt = reader.readOption(() => reader.readI64());
+    const reasonCode = reader.readU8();
+    const expired = expiresAt !== null && Date.now() / 1000 > expiresAt;
     return {
       wallet,
       reason: reason.length > 0 ? reason : null,
-      isActive,
+      isActive: isActive && !expired,
+      expiresAt,
+      reasonCode,
     };
   });
 }
@@ -538,10 +573,17 @@ function encodeMintBurnArgs(amount: bigint): Buffer {
   return writer.concat();
 }
 
-function encodeAddToBlacklistArgs(wallet: PublicKey, reason: string): Buffer {
+function encodeAddToBlacklistArgs(
+  wallet: PublicKey,
+  reason: string,
+  expiresAt: number | null,
+  reasonCode: number,
+): Buffer {
   const writer = new BorshWriter();
   writer.writePubkey(wallet);
   writer.writeString(reason);
+  writer.writeOption(expiresAt, (value) => writer.writeI64(value));
+  writer.writeU8(reasonCode);
   return writer.concat();
 }
 
@@ -654,6 +696,8 @@ function buildAddToBlacklistInstruction(params: {
   blacklistPda: PublicKey;
   wallet: PublicKey;
   reason: string;
+  expiresAt: number | null;
+  reasonCode: number;
   programId: PublicKey;
 }): TransactionInstruction {
   const keys = [
@@ -666,7 +710,12 @@ function buildAddToBlacklistInstruction(params: {
   ];
   return buildInstruction(
     "add_to_blacklist",
-    encodeAddToBlacklistArgs(params.wallet, params.reason),
+    encodeAddToBlacklistArgs(
+      params.wallet,
+      params.reason,
+      params.expiresAt,
+      params.reasonCode,
+    ),
     keys,
     params.programId,
   );
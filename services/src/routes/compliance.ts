@@ -4,7 +4,7 @@ import { z } from "zod";
 import type { AppContext } from "../context.js";
 import type { EventRecord } from "../lib/db.js";
 import { createMonitoringRule, listEvents } from "../lib/db.js";
-import { parsePublicKey } from "../lib/solana.js";
+import { BLACKLIST_REASON_CODES, parsePublicKey } from "../lib/solana.js";
 
 const ScreeningSchema = z.object({
   address: z.string().min(32),
@@ -13,6 +13,8 @@ const ScreeningSchema = z.object({
 const BlacklistSchema = z.object({
   address: z.string().min(32),
   reason: z.string().max(128).optional(),
+  expiresAt: z.number().int().positive().optional(),
+  code: z.enum(["sanctions", "fraud", "court-order", "other"]).default("other"),
 });
 
 const RuleSchema = z.object({
@@ -22,7 +24,12 @@ const RuleSchema = z.object({
   webhookUrl: z.string().url().optional(),
 });
 
-const mockBlacklist: Array<{ address: string; reason?: string; isActive: boolean }> = [];
+const mockBlacklist: Array<{
+  address: string;
+  reason?: string;
+  code: string;
+  isActive: boolean;
+}> = [];
 
 export function registerComplianceRoutes(
   app: FastifyInstance,
@@ -72,6 +79,7 @@ export function registerComplianceRoutes(
         items: entries.map((entry) => ({
           address: entry.wallet.toBase58(),
           reason: entry.reason ?? undefined,
+          reasonCode: entry.reasonCode,
           isActive: entry.isActive,
         })),
       });
@@ -102,12 +110,14 @@ export function registerComplianceRoutes(
         if (existing) {
           existing.isActive = true;
           existing.reason = payload.data.reason;
+          existing.code = payload.data.code;
           reply.send(existing);
           return;
         }
         const record = {
           address: payload.data.address,
           reason: payload.data.reason,
+          code: payload.data.code,
           isActive: true,
         };
         mockBlacklist.push(record);
@@ -120,6 +130,8 @@ export function registerComplianceRoutes(
         const signature = await context.solana.addToBlacklist(
           wallet,
           payload.data.reason ?? "",
+          payload.data.expiresAt ?? null,
+          BLACKLIST_REASON_CODES[payload.data.code],
         );
         reply.code(201).send({
           address: payload.data.address,
@@ -306,8 +318,12 @@ function mapEventToAction(eventType: string): string {
     RoleUpdated: "ROLE_UPDATED",
     AuthorityTransferred: "AUTHORITY_TRANSFER",
     BlacklistAdded: "BLACKLIST_ADD",
+    BlacklistUpdated: "BLACKLIST_UPDATE",
     BlacklistRemoved: "BLACKLIST_REMOVE",
+    BlacklistEntryClosed: "BLACKLIST_CLOSE",
     TokensSeized: "SEIZE",
+    TokensSeizedAndBurned: "SEIZE_BURN",
+    QuotaWindowReset: "QUOTA_WINDOW_RESET",
   };
   return map[eventType] ?? eventType;
 }
@@ -324,8 +340,12 @@ function mapActionToEventType(action: string): string | undefined {
     ROLE_UPDATED: "RoleUpdated",
     AUTHORITY_TRANSFER: "AuthorityTransferred",
     BLACKLIST_ADD: "BlacklistAdded",
+    BLACKLIST_UPDATE: "BlacklistUpdated",
     BLACKLIST_REMOVE: "BlacklistRemoved",
+    BLACKLIST_CLOSE: "BlacklistEntryClosed",
     SEIZE: "TokensSeized",
+    SEIZE_BURN: "TokensSeizedAndBurned",
+    QUOTA_WINDOW_RESET: "QuotaWindowReset",
   };
   return map[action];
 }
@@ -4,7 +4,10 @@ import { Keypair, PublicKey, SystemProgram } from "@solana/web3.js";
 import {
   ASSOCIATED_TOKEN_PROGRAM_ID,
   TOKEN_2022_PROGRAM_ID,
+  createAssociatedTokenAccountIdempotentInstruction,
   createAssociatedTokenAccountInstruction,
+  createAccount,
+  createTransferCheckedWithTransferHookInstruction,
   getAccount,
   getAssociatedTokenAddressSync,
 } from "@solana/spl-token";
@@ -41,6 +44,12 @@ describe("stablecoin-core sss-2", () => {
     ],
     program.programId,
   );
+  // Minting on a hook-enabled mint must pass the recipient's blacklist PDA.
+  const blacklistEntry = (config: PublicKey, wallet: PublicKey) =>
+    PublicKey.findProgramAddressSync(
+      [Buffer.from("blacklist"), config.toBuffer(), wallet.toBuffer()],
+      program.programId,
+    )[0];
 
   it("initializes SSS-2, blacklists, and seizes", async () => {
     await program.methods
@@ -53,6 +62,15 @@ describe("stablecoin-core sss-2", () => {
         enableTransferHook: true,
         defaultAccountFrozen: false,
         transferHookProgram: transferHookProgramId,
+        pauseCooldown: new anchor.BN(0),
+        enableMetadata: true,
+        maxSupply: null,
+        enableAllowlist: false,
+        enableConfidential: false,
+        treasury: null,
+        tokenProgram: { token2022: {} },
+        transferCooldownSeconds: null,
+        reserveTransferHook: false,
       })
       .accounts({
         authority: authority.publicKey,
@@ -61,7 +79,7 @@ describe("stablecoin-core sss-2", () => {
         roleAccount: rolePda,
         extraMetasAccount: extraMetasPda,
         transferHookProgram: transferHookProgramId,
-        token2022Program: TOKEN_2022_PROGRAM_ID,
+        tokenProgram: TOKEN_2022_PROGRAM_ID,
         systemProgram: SystemProgram.programId,
         rent: anchor.web3.SYSVAR_RENT_PUBKEY,
       })
@@ -87,9 +105,10 @@ describe("stablecoin-core sss-2", () => {
         mint: mintKeypair.publicKey,
         recipient: targetOwner.publicKey,
         recipientAta: targetAta,
-        token2022Program: TOKEN_2022_PROGRAM_ID,
+        tokenProgram: TOKEN_2022_PROGRAM_ID,
         associatedTokenProgram: ASSOCIATED_TOKEN_PROGRAM_ID,
         systemProgram: SystemProgram.programId,
+        recipientBlacklistEntry: blacklistEntry(configPda, targetOwner.publicKey),
       })
       .rpc();
 
@@ -101,7 +120,7 @@ describe("stablecoin-core sss-2", () => {
         roleAccount: rolePda,
         mint: mintKeypair.publicKey,
         targetAta,
-        token2022Program: TOKEN_2022_PROGRAM_ID,
+        tokenProgram: TOKEN_2022_PROGRAM_ID,
       })
       .rpc();
 
@@ -118,6 +137,8 @@ describe("stablecoin-core sss-2", () => {
       .addToBlacklist({
         wallet: targetOwner.publicKey,
         reason: "Test compliance",
+        expiresAt: null,
+        reasonCode: 1,
       })
       .accounts({
         blacklister: authority.publicKey,
@@ -133,6 +154,8 @@ describe("stablecoin-core sss-2", () => {
       .addToBlacklist({
         wallet: authority.publicKey,
         reason: "Treasury entry",
+        expiresAt: null,
+        reasonCode: 4,
       })
       .accounts({
         blacklister: authority.publicKey,
@@ -173,7 +196,7 @@ describe("stablecoin-core sss-2", () => {
     await provider.sendAndConfirm(treasuryTx);
 
     await program.methods
-      .seize()
+      .seize(null)
       .accounts({
         seizer: authority.publicKey,
         config: configPda,
@@ -206,4 +229,1099 @@ describe("stablecoin-core sss-2", () => {
     assert.equal(treasuryAccount.amount, BigInt(amount.toString()));
     assert.equal(targetAccount.amount, 0n);
   });
+
+  it("blocks wallet transfers while paused but still allows seize", async () => {
+    const treasuryAta = getAssociatedTokenAddressSync(
+      mintKeypair.publicKey,
+      authority.publicKey,
+      false,
+      TOKEN_2022_PROGRAM_ID,
+      ASSOCIATED_TOKEN_PROGRAM_ID,
+    );
+    const targetOwner = Keypair.generate();
+    const targetAta = getAssociatedTokenAddressSync(
+      mintKeypair.publicKey,
+      targetOwner.publicKey,
+      false,
+      TOKEN_2022_PROGRAM_ID,
+      ASSOCIATED_TOKEN_PROGRAM_ID,
+    );
+    const [blacklistEntryPda] = PublicKey.findProgramAddressSync(
+      [Buffer.from("blacklist"), configPda.toBuffer(), targetOwner.publicKey.toBuffer()],
+      program.programId,
+    );
+
+    await program.methods
+      .mint(new anchor.BN(1_000))
+      .accounts({
+        minter: authority.publicKey,
+        config: configPda,
+        roleAccount: rolePda,
+        mint: mintKeypair.publicKey,
+        recipient: targetOwner.publicKey,
+        recipientAta: targetAta,
+        tokenProgram: TOKEN_2022_PROGRAM_ID,
+        associatedTokenProgram: ASSOCIATED_TOKEN_PROGRAM_ID,
+        systemProgram: SystemProgram.programId,
+        recipientBlacklistEntry: blacklistEntry(configPda, targetOwner.publicKey),
+      })
+      .rpc();
+    await program.methods
+      .freezeAccount()
+      .accounts({
+        freezer: authority.publicKey,
+        config: configPda,
+        roleAccount: rolePda,
+        mint: mintKeypair.publicKey,
+        targetAta,
+        tokenProgram: TOKEN_2022_PROGRAM_ID,
+      })
+      .rpc();
+    await program.methods
+      .addToBlacklist({
+        wallet: targetOwner.publicKey,
+        reason: "Paused seize",
+        expiresAt: null,
+        reasonCode: 4,
+      })
+      .accounts({
+        blacklister: authority.publicKey,
+        config: configPda,
+        roleAccount: rolePda,
+        blacklistEntry: blacklistEntryPda,
+        wallet: targetOwner.publicKey,
+        systemProgram: SystemProgram.programId,
+      })
+      .rpc();
+
+    await program.methods
+      .pause({ mask: null })
+      .accounts({ pauser: authority.publicKey, config: configPda, roleAccount: rolePda })
+      .rpc();
+
+    const recipient = Keypair.generate();
+    const recipientAta = getAssociatedTokenAddressSync(
+      mintKeypair.publicKey,
+      recipient.publicKey,
+      false,
+      TOKEN_2022_PROGRAM_ID,
+      ASSOCIATED_TOKEN_PROGRAM_ID,
+    );
+    const transferIx = await createTransferCheckedWithTransferHookInstruction(
+      provider.connection,
+      treasuryAta,
+      mintKeypair.publicKey,
+      recipientAta,
+      authority.publicKey,
+      100n,
+      6,
+      [],
+      undefined,
+      TOKEN_2022_PROGRAM_ID,
+    );
+    const transferTx = new anchor.web3.Transaction().add(
+      createAssociatedTokenAccountIdempotentInstruction(
+        authority.publicKey,
+        recipientAta,
+        recipient.publicKey,
+        mintKeypair.publicKey,
+        TOKEN_2022_PROGRAM_ID,
+        ASSOCIATED_TOKEN_PROGRAM_ID,
+      ),
+      transferIx,
+    );
+    try {
+      await provider.sendAndConfirm(transferTx);
+      assert.fail("expected SystemPaused from the transfer hook");
+    } catch (err: any) {
+      const logs = (err.logs ?? []).join("\n");
+      assert.include(`${String(err)}\n${logs}`, "SystemPaused");
+    }
+
+    const seizeAccounts = {
+      seizer: authority.publicKey,
+      config: configPda,
+      roleAccount: rolePda,
+      mint: mintKeypair.publicKey,
+      targetAta,
+      treasuryAta,
+      blacklistEntry: blacklistEntryPda,
+      extraMetasAccount: extraMetasPda,
+      stablecoinCoreProgram: program.programId,
+      destinationBlacklistEntry: treasuryBlacklistEntryPda,
+      transferHookProgram: transferHookProgramId,
+      token2022Program: TOKEN_2022_PROGRAM_ID,
+    };
+    try {
+      await program.methods.seize(new anchor.BN(1_001)).accounts(seizeAccounts).rpc();
+      assert.fail("expected InsufficientBalance");
+    } catch (err) {
+      assert.include(String(err), "InsufficientBalance");
+    }
+    const beforeSeize = await (program.account as any).stablecoinConfig.fetch(configPda);
+    await program.methods.seize(new anchor.BN(400)).accounts(seizeAccounts).rpc();
+    const partial = await getAccount(
+      provider.connection,
+      targetAta,
+      undefined,
+      TOKEN_2022_PROGRAM_ID,
+    );
+    assert.equal(partial.amount, 600n);
+    assert.isTrue(partial.isFrozen);
+
+    await program.methods.seize(null).accounts(seizeAccounts).rpc();
+
+    const targetAccount = await getAccount(
+      provider.connection,
+      targetAta,
+      undefined,
+      TOKEN_2022_PROGRAM_ID,
+    );
+    assert.equal(targetAccount.amount, 0n);
+    const seizedConfig = await (program.account as any).stablecoinConfig.fetch(configPda);
+    assert.equal(
+      seizedConfig.totalSeized.sub(beforeSeize.totalSeized).toNumber(),
+      1_000,
+    );
+
+    await program.methods
+      .unpause({ mask: null })
+      .accounts({ pauser: authority.publicKey, config: configPda, roleAccount: rolePda })
+      .rpc();
+  });
+
+  it("only moves tokens between allowlisted wallets in allowlist mode", async () => {
+    const allowMint = Keypair.generate();
+    const [allowConfig] = PublicKey.findProgramAddressSync(
+      [Buffer.from("stablecoin"), allowMint.publicKey.toBuffer()],
+      program.programId,
+    );
+    const [allowRole] = PublicKey.findProgramAddressSync(
+      [Buffer.from("role"), allowConfig.toBuffer(), authority.publicKey.toBuffer()],
+      program.programId,
+    );
+    const [allowExtraMetas] = PublicKey.findProgramAddressSync(
+      [Buffer.from("extra-account-metas"), allowMint.publicKey.toBuffer()],
+      transferHookProgramId,
+    );
+    const allowlistEntry = (wallet: PublicKey) =>
+      PublicKey.findProgramAddressSync(
+        [Buffer.from("allowlist"), allowConfig.toBuffer(), wallet.toBuffer()],
+        program.programId,
+      )[0];
+    const ata = (owner: PublicKey) =>
+      getAssociatedTokenAddressSync(
+        allowMint.publicKey,
+        owner,
+        false,
+        TOKEN_2022_PROGRAM_ID,
+        ASSOCIATED_TOKEN_PROGRAM_ID,
+      );
+    const allow = (wallet: PublicKey) =>
+      program.methods
+        .addToAllowlist({ wallet })
+        .accounts({
+          blacklister: authority.publicKey,
+          config: allowConfig,
+          roleAccount: allowRole,
+          allowlistEntry: allowlistEntry(wallet),
+          wallet,
+          systemProgram: SystemProgram.programId,
+        })
+        .rpc();
+    const transfer = async (recipient: PublicKey) => {
+      const transferIx = await createTransferCheckedWithTransferHookInstruction(
+        provider.connection,
+        ata(authority.publicKey),
+        allowMint.publicKey,
+        ata(recipient),
+        authority.publicKey,
+        100n,
+        6,
+        [],
+        undefined,
+        TOKEN_2022_PROGRAM_ID,
+      );
+      const tx = new anchor.web3.Transaction().add(
+        createAssociatedTokenAccountIdempotentInstruction(
+          authority.publicKey,
+          ata(recipient),
+          recipient,
+          allowMint.publicKey,
+          TOKEN_2022_PROGRAM_ID,
+          ASSOCIATED_TOKEN_PROGRAM_ID,
+        ),
+        transferIx,
+      );
+      await provider.sendAndConfirm(tx);
+    };
+
+    await program.methods
+      .initialize({
+        name: "Allowlist USD",
+        symbol: "AUSD",
+        uri: "",
+        decimals: 6,
+        enablePermanentDelegate: true,
+        enableTransferHook: true,
+        defaultAccountFrozen: false,
+        transferHookProgram: transferHookProgramId,
+        pauseCooldown: new anchor.BN(0),
+        enableMetadata: true,
+        maxSupply: null,
+        enableAllowlist: true,
+        enableConfidential: false,
+        treasury: null,
+        tokenProgram: { token2022: {} },
+        transferCooldownSeconds: null,
+        reserveTransferHook: false,
+      })
+      .accounts({
+        authority: authority.publicKey,
+        mint: allowMint.publicKey,
+        config: allowConfig,
+        roleAccount: allowRole,
+        extraMetasAccount: allowExtraMetas,
+        transferHookProgram: transferHookProgramId,
+        tokenProgram: TOKEN_2022_PROGRAM_ID,
+        systemProgram: SystemProgram.programId,
+        rent: anchor.web3.SYSVAR_RENT_PUBKEY,
+      })
+      .signers([allowMint])
+      .rpc();
+
+    await program.methods
+      .mint(new anchor.BN(1_000))
+      .accounts({
+        minter: authority.publicKey,
+        config: allowConfig,
+        roleAccount: allowRole,
+        mint: allowMint.publicKey,
+        recipient: authority.publicKey,
+        recipientAta: ata(authority.publicKey),
+        tokenProgram: TOKEN_2022_PROGRAM_ID,
+        associatedTokenProgram: ASSOCIATED_TOKEN_PROGRAM_ID,
+        systemProgram: SystemProgram.programId,
+        recipientBlacklistEntry: blacklistEntry(allowConfig, authority.publicKey),
+      })
+      .rpc();
+
+    const recipient = Keypair.generate().publicKey;
+    await allow(authority.publicKey);
+    try {
+      await transfer(recipient);
+      assert.fail("expected NotAllowlisted from the transfer hook");
+    } catch (err: any) {
+      const logs = (err.logs ?? []).join("\n");
+      assert.include(`${String(err)}\n${logs}`, "NotAllowlisted");
+    }
+
+    await allow(recipient);
+    await transfer(recipient);
+    const recipientAccount = await getAccount(
+      provider.connection,
+      ata(recipient),
+      undefined,
+      TOKEN_2022_PROGRAM_ID,
+    );
+    assert.equal(recipientAccount.amount, 100n);
+
+    const [blacklistEntryPda] = PublicKey.findProgramAddressSync(
+      [Buffer.from("blacklist"), allowConfig.toBuffer(), recipient.toBuffer()],
+      program.programId,
+    );
+    try {
+      await program.methods
+        .addToBlacklist({
+          wallet: recipient,
+          reason: "Not used",
+          expiresAt: null,
+          reasonCode: 4,
+        })
+        .accounts({
+          blacklister: authority.publicKey,
+          config: allowConfig,
+          roleAccount: allowRole,
+          blacklistEntry: blacklistEntryPda,
+          wallet: recipient,
+          systemProgram: SystemProgram.programId,
+        })
+        .rpc();
+      assert.fail("expected AllowlistModeEnabled");
+    } catch (err: any) {
+      assert.include(String(err), "AllowlistModeEnabled");
+    }
+
+    const blacklister = Keypair.generate();
+    const [blacklisterRole] = PublicKey.findProgramAddressSync(
+      [Buffer.from("role"), allowConfig.toBuffer(), blacklister.publicKey.toBuffer()],
+      program.programId,
+    );
+    await program.methods
+      .updateRoles({
+        target: blacklister.publicKey,
+        roles: 0x20,
+        mintQuota: null,
+        lifetimeQuota: null,
+      })
+      .accounts({
+        authority: authority.publicKey,
+        config: allowConfig,
+        roleAccount: allowRole,
+        targetRoleAccount: blacklisterRole,
+        target: blacklister.publicKey,
+        systemProgram: SystemProgram.programId,
+      })
+      .rpc();
+    const setLock = (lockAdminOps: boolean) =>
+      program.methods
+        .updateAdminLock({ lockAdminOps })
+        .accounts({ authority: authority.publicKey, config: allowConfig, roleAccount: allowRole })
+        .rpc();
+
+    await setLock(true);
+    const lockedWallet = Keypair.generate().publicKey;
+    try {
+      await program.methods
+        .addToAllowlist({ wallet: lockedWallet })
+        .accounts({
+          blacklister: blacklister.publicKey,
+          config: allowConfig,
+          roleAccount: blacklisterRole,
+          allowlistEntry: allowlistEntry(lockedWallet),
+          wallet: lockedWallet,
+          systemProgram: SystemProgram.programId,
+        })
+        .preInstructions([
+          SystemProgram.transfer({
+            fromPubkey: authority.publicKey,
+            toPubkey: blacklister.publicKey,
+            lamports: 100_000_000,
+          }),
+        ])
+        .signers([blacklister])
+        .rpc();
+      assert.fail("expected SystemPaused");
+    } catch (err: any) {
+      assert.include(String(err), "SystemPaused");
+    }
+    try {
+      await program.methods
+        .removeFromAllowlist()
+        .accounts({
+          blacklister: blacklister.publicKey,
+          config: allowConfig,
+          roleAccount: blacklisterRole,
+          allowlistEntry: allowlistEntry(recipient),
+        })
+        .signers([blacklister])
+        .rpc();
+      assert.fail("expected SystemPaused");
+    } catch (err: any) {
+      assert.include(String(err), "SystemPaused");
+    }
+
+    await setLock(false);
+    await program.methods
+      .removeFromAllowlist()
+      .accounts({
+        blacklister: blacklister.publicKey,
+        config: allowConfig,
+        roleAccount: blacklisterRole,
+        allowlistEntry: allowlistEntry(recipient),
+      })
+      .signers([blacklister])
+      .rpc();
+  });
+
+  it("rejects wallet transfers above the per-transfer limit", async () => {
+    const setLimit = (maxTransferAmount: anchor.BN | null) =>
+      program.methods
+        .updateTransferLimit({ maxTransferAmount })
+        .accounts({ authority: authority.publicKey, config: configPda, roleAccount: rolePda })
+        .rpc();
+    const treasuryAta = getAssociatedTokenAddressSync(
+      mintKeypair.publicKey,
+      authority.publicKey,
+      false,
+      TOKEN_2022_PROGRAM_ID,
+      ASSOCIATED_TOKEN_PROGRAM_ID,
+    );
+    const recipient = Keypair.generate();
+    const recipientAta = getAssociatedTokenAddressSync(
+      mintKeypair.publicKey,
+      recipient.publicKey,
+      false,
+      TOKEN_2022_PROGRAM_ID,
+      ASSOCIATED_TOKEN_PROGRAM_ID,
+    );
+    const transfer = async (amount: bigint) => {
+      const transferIx = await createTransferCheckedWithTransferHookInstruction(
+        provider.connection,
+        treasuryAta,
+        mintKeypair.publicKey,
+        recipientAta,
+        authority.publicKey,
+        amount,
+        6,
+        [],
+        undefined,
+        TOKEN_2022_PROGRAM_ID,
+      );
+      const tx = new anchor.web3.Transaction().add(
+        createAssociatedTokenAccountIdempotentInstruction(
+          authority.publicKey,
+          recipientAta,
+          recipient.publicKey,
+          mintKeypair.publicKey,
+          TOKEN_2022_PROGRAM_ID,
+          ASSOCIATED_TOKEN_PROGRAM_ID,
+        ),
+        transferIx,
+      );
+      await provider.sendAndConfirm(tx);
+    };
+
+    await setLimit(new anchor.BN(50));
+    const config = await (program.account as any).stablecoinConfig.fetch(configPda);
+    assert.equal(config.maxTransferAmount.toString(), "50");
+
+    try {
+      await transfer(51n);
+      assert.fail("expected AmountLimitExceeded from the transfer hook");
+    } catch (err: any) {
+      const logs = (err.logs ?? []).join("\n");
+      assert.include(`${String(err)}\n${logs}`, "AmountLimitExceeded");
+    }
+    await transfer(50n);
+
+    await setLimit(null);
+    await transfer(100n);
+    const recipientAccount = await getAccount(
+      provider.connection,
+      recipientAta,
+      undefined,
+      TOKEN_2022_PROGRAM_ID,
+    );
+    assert.equal(recipientAccount.amount, 150n);
+  });
+
+  it("closes a removed blacklist entry and refunds its rent", async () => {
+    const entryInfo = await provider.connection.getAccountInfo(
+      treasuryBlacklistEntryPda,
+    );
+    assert.isNotNull(entryInfo);
+    const entry = await (program.account as any).blacklistEntry.fetch(
+      treasuryBlacklistEntryPda,
+    );
+    assert.isFalse(entry.isActive);
+
+    const balanceBefore = await provider.connection.getBalance(authority.publicKey);
+    await program.methods
+      .closeBlacklistEntry()
+      .accounts({
+        blacklister: authority.publicKey,
+        config: configPda,
+        roleAccount: rolePda,
+        blacklistEntry: treasuryBlacklistEntryPda,
+      })
+      .rpc();
+
+    assert.isNull(
+      await provider.connection.getAccountInfo(treasuryBlacklistEntryPda),
+    );
+    const balanceAfter = await provider.connection.getBalance(authority.publicKey);
+    assert.isAbove(balanceAfter, balanceBefore);
+  });
+
+  it("records blacklist expiries and rejects ones already in the past", async () => {
+    const wallet = Keypair.generate().publicKey;
+    const [entryPda] = PublicKey.findProgramAddressSync(
+      [Buffer.from("blacklist"), configPda.toBuffer(), wallet.toBuffer()],
+      program.programId,
+    );
+    const add = (expiresAt: anchor.BN) =>
+      program.methods
+        .addToBlacklist({ wallet, reason: "Temporary hold", expiresAt, reasonCode: 4 })
+        .accounts({
+          blacklister: authority.publicKey,
+          config: configPda,
+          roleAccount: rolePda,
+          blacklistEntry: entryPda,
+          wallet,
+          systemProgram: SystemProgram.programId,
+        })
+        .rpc();
+
+    const now = Math.floor(Date.now() / 1000);
+    try {
+      await add(new anchor.BN(now - 60));
+      assert.fail("expected InvalidExpiry");
+    } catch (err: any) {
+      assert.include(String(err), "InvalidExpiry");
+    }
+
+    await add(new anchor.BN(now + 3600));
+    const entry = await (program.account as any).blacklistEntry.fetch(entryPda);
+    assert.isTrue(entry.isActive);
+    assert.equal(entry.expiresAt.toString(), String(now + 3600));
+  });
+
+  it("stores blacklist reason codes and rejects unknown ones", async () => {
+    const wallet = Keypair.generate().publicKey;
+    const [entryPda] = PublicKey.findProgramAddressSync(
+      [Buffer.from("blacklist"), configPda.toBuffer(), wallet.toBuffer()],
+      program.programId,
+    );
+    const add = (reasonCode: number) =>
+      program.methods
+        .addToBlacklist({ wallet, reason: "", expiresAt: null, reasonCode })
+        .accounts({
+          blacklister: authority.publicKey,
+          config: configPda,
+          roleAccount: rolePda,
+          blacklistEntry: entryPda,
+          wallet,
+          systemProgram: SystemProgram.programId,
+        })
+        .rpc();
+
+    for (const reasonCode of [0, 5]) {
+      try {
+        await add(reasonCode);
+        assert.fail("expected InvalidReasonCode");
+      } catch (err: any) {
+        assert.include(String(err), "InvalidReasonCode");
+      }
+    }
+
+    await add(3);
+    const entry = await (program.account as any).blacklistEntry.fetch(entryPda);
+    assert.equal(entry.reasonCode, 3);
+    assert.equal(entry.reason, "");
+  });
+
+  it("updates a blacklist entry's reason in place", async () => {
+    const wallet = Keypair.generate().publicKey;
+    const [entryPda] = PublicKey.findProgramAddressSync(
+      [Buffer.from("blacklist"), configPda.toBuffer(), wallet.toBuffer()],
+      program.programId,
+    );
+    const accounts = {
+      blacklister: authority.publicKey,
+      config: configPda,
+      roleAccount: rolePda,
+      blacklistEntry: entryPda,
+    };
+    await program.methods
+      .addToBlacklist({ wallet, reason: "Pending review", expiresAt: null, reasonCode: 4 })
+      .accounts({ ...accounts, wallet, systemProgram: SystemProgram.programId })
+      .rpc();
+    const before = await (program.account as any).blacklistEntry.fetch(entryPda);
+
+    await program.methods
+      .updateBlacklistEntry({ reason: "Court order 2024-17", reasonCode: 3 })
+      .accounts(accounts)
+      .rpc();
+    let entry = await (program.account as any).blacklistEntry.fetch(entryPda);
+    assert.equal(entry.reason, "Court order 2024-17");
+    assert.equal(entry.reasonCode, 3);
+    assert.equal(entry.blacklistedAt.toString(), before.blacklistedAt.toString());
+    assert.ok(entry.blacklistedBy.equals(before.blacklistedBy));
+
+    await program.methods
+      .updateBlacklistEntry({ reason: "Court order 2024-17a", reasonCode: null })
+      .accounts(accounts)
+      .rpc();
+    entry = await (program.account as any).blacklistEntry.fetch(entryPda);
+    assert.equal(entry.reasonCode, 3);
+
+    try {
+      await program.methods
+        .updateBlacklistEntry({ reason: "x".repeat(129), reasonCode: null })
+        .accounts(accounts)
+        .rpc();
+      assert.fail("expected ReasonTooLong");
+    } catch (err: any) {
+      assert.include(String(err), "ReasonTooLong");
+    }
+
+    await program.methods.removeFromBlacklist().accounts(accounts).rpc();
+    try {
+      await program.methods
+        .updateBlacklistEntry({ reason: "Lifted", reasonCode: null })
+        .accounts(accounts)
+        .rpc();
+      assert.fail("expected NotBlacklisted");
+    } catch (err: any) {
+      assert.include(String(err), "NotBlacklisted");
+    }
+  });
+
+  it("seizes and burns a blacklisted balance", async () => {
+    const targetOwner = Keypair.generate();
+    const targetAta = getAssociatedTokenAddressSync(
+      mintKeypair.publicKey,
+      targetOwner.publicKey,
+      false,
+      TOKEN_2022_PROGRAM_ID,
+      ASSOCIATED_TOKEN_PROGRAM_ID,
+    );
+    const [blacklistEntryPda] = PublicKey.findProgramAddressSync(
+      [Buffer.from("blacklist"), configPda.toBuffer(), targetOwner.publicKey.toBuffer()],
+      program.programId,
+    );
+
+    await program.methods
+      .mint(new anchor.BN(700))
+      .accounts({
+        minter: authority.publicKey,
+        config: configPda,
+        roleAccount: rolePda,
+        mint: mintKeypair.publicKey,
+        recipient: targetOwner.publicKey,
+        recipientAta: targetAta,
+        tokenProgram: TOKEN_2022_PROGRAM_ID,
+        associatedTokenProgram: ASSOCIATED_TOKEN_PROGRAM_ID,
+        systemProgram: SystemProgram.programId,
+        recipientBlacklistEntry: blacklistEntry(configPda, targetOwner.publicKey),
+      })
+      .rpc();
+    await program.methods
+      .freezeAccount()
+      .accounts({
+        freezer: authority.publicKey,
+        config: configPda,
+        roleAccount: rolePda,
+        mint: mintKeypair.publicKey,
+        targetAta,
+        tokenProgram: TOKEN_2022_PROGRAM_ID,
+      })
+      .rpc();
+    await program.methods
+      .addToBlacklist({
+        wallet: targetOwner.publicKey,
+        reason: "Court order",
+        expiresAt: null,
+        reasonCode: 3,
+      })
+      .accounts({
+        blacklister: authority.publicKey,
+        config: configPda,
+        roleAccount: rolePda,
+        blacklistEntry: blacklistEntryPda,
+        wallet: targetOwner.publicKey,
+        systemProgram: SystemProgram.programId,
+      })
+      .rpc();
+
+    const before = await (program.account as any).stablecoinConfig.fetch(configPda);
+    await program.methods
+      .seizeAndBurn()
+      .accounts({
+        seizer: authority.publicKey,
+        config: configPda,
+        roleAccount: rolePda,
+        mint: mintKeypair.publicKey,
+        targetAta,
+        blacklistEntry: blacklistEntryPda,
+        token2022Program: TOKEN_2022_PROGRAM_ID,
+      })
+      .rpc();
+
+    const targetAccount = await getAccount(
+      provider.connection,
+      targetAta,
+      undefined,
+      TOKEN_2022_PROGRAM_ID,
+    );
+    assert.equal(targetAccount.amount, 0n);
+    assert.isTrue(targetAccount.isFrozen);
+    const after = await (program.account as any).stablecoinConfig.fetch(configPda);
+    assert.equal(after.totalBurned.sub(before.totalBurned).toNumber(), 700);
+    assert.equal(after.totalSeized.sub(before.totalSeized).toNumber(), 700);
+  });
+
+  it("lets a blacklisted owner consolidate its own accounts when allowed", async () => {
+    const holder = Keypair.generate();
+    const holderAta = getAssociatedTokenAddressSync(
+      mintKeypair.publicKey,
+      holder.publicKey,
+      false,
+      TOKEN_2022_PROGRAM_ID,
+      ASSOCIATED_TOKEN_PROGRAM_ID,
+    );
+    const [holderEntryPda] = PublicKey.findProgramAddressSync(
+      [Buffer.from("blacklist"), configPda.toBuffer(), holder.publicKey.toBuffer()],
+      program.programId,
+    );
+    const setSelfTransfer = (allowSelfTransfer: boolean) =>
+      program.methods
+        .updateSelfTransfer({ allowSelfTransfer })
+        .accounts({ authority: authority.publicKey, config: configPda, roleAccount: rolePda })
+        .rpc();
+
+    await program.methods
+      .mint(new anchor.BN(300))
+      .accounts({
+        minter: authority.publicKey,
+        config: configPda,
+        roleAccount: rolePda,
+        mint: mintKeypair.publicKey,
+        recipient: holder.publicKey,
+        recipientAta: holderAta,
+        tokenProgram: TOKEN_2022_PROGRAM_ID,
+        associatedTokenProgram: ASSOCIATED_TOKEN_PROGRAM_ID,
+        systemProgram: SystemProgram.programId,
+        recipientBlacklistEntry: blacklistEntry(configPda, holder.publicKey),
+      })
+      .rpc();
+    const holderSecondAccount = await createAccount(
+      provider.connection,
+      authority,
+      mintKeypair.publicKey,
+      holder.publicKey,
+      Keypair.generate(),
+      undefined,
+      TOKEN_2022_PROGRAM_ID,
+    );
+    const outsiderAta = getAssociatedTokenAddressSync(
+      mintKeypair.publicKey,
+      authority.publicKey,
+      false,
+      TOKEN_2022_PROGRAM_ID,
+      ASSOCIATED_TOKEN_PROGRAM_ID,
+    );
+    await program.methods
+      .addToBlacklist({
+        wallet: holder.publicKey,
+        reason: "Under review",
+        expiresAt: null,
+        reasonCode: 2,
+      })
+      .accounts({
+        blacklister: authority.publicKey,
+        config: configPda,
+        roleAccount: rolePda,
+        blacklistEntry: holderEntryPda,
+        wallet: holder.publicKey,
+        systemProgram: SystemProgram.programId,
+      })
+      .rpc();
+
+    const transfer = async (destination: PublicKey, amount: bigint) => {
+      const transferIx = await createTransferCheckedWithTransferHookInstruction(
+        provider.connection,
+        holderAta,
+        mintKeypair.publicKey,
+        destination,
+        holder.publicKey,
+        amount,
+        6,
+        [],
+        undefined,
+        TOKEN_2022_PROGRAM_ID,
+      );
+      await provider.sendAndConfirm(new anchor.web3.Transaction().add(transferIx), [holder]);
+    };
+    const expectDenied = async (destination: PublicKey) => {
+      try {
+        await transfer(destination, 10n);
+        assert.fail("expected TransferDenied from the transfer hook");
+      } catch (err: any) {
+        const logs = (err.logs ?? []).join("\n");
+        assert.include(`${String(err)}\n${logs}`, "TransferDenied");
+      }
+    };
+
+    await expectDenied(holderSecondAccount);
+
+    await setSelfTransfer(true);
+    const config = await (program.account as any).stablecoinConfig.fetch(configPda);
+    assert.isTrue(config.allowSelfTransfer);
+    await transfer(holderSecondAccount, 100n);
+    const secondAccount = await getAccount(
+      provider.connection,
+      holderSecondAccount,
+      undefined,
+      TOKEN_2022_PROGRAM_ID,
+    );
+    assert.equal(secondAccount.amount, 100n);
+    await expectDenied(outsiderAta);
+
+    await setSelfTransfer(false);
+    await expectDenied(holderSecondAccount);
+  });
+
+  it("enforces the transfer cooldown through the sender's LastTransfer PDA", async () => {
+    const cooldownMint = Keypair.generate();
+    const [cooldownConfig] = PublicKey.findProgramAddressSync(
+      [Buffer.from("stablecoin"), cooldownMint.publicKey.toBuffer()],
+      program.programId,
+    );
+    const [cooldownRole] = PublicKey.findProgramAddressSync(
+      [Buffer.from("role"), cooldownConfig.toBuffer(), authority.publicKey.toBuffer()],
+      program.programId,
+    );
+    const [cooldownExtraMetas] = PublicKey.findProgramAddressSync(
+      [Buffer.from("extra-account-metas"), cooldownMint.publicKey.toBuffer()],
+      transferHookProgramId,
+    );
+    const [lastTransfer] = PublicKey.findProgramAddressSync(
+      [
+        Buffer.from("last-transfer"),
+        cooldownConfig.toBuffer(),
+        authority.publicKey.toBuffer(),
+      ],
+      transferHookProgramId,
+    );
+    const ata = (owner: PublicKey) =>
+      getAssociatedTokenAddressSync(
+        cooldownMint.publicKey,
+        owner,
+        false,
+        TOKEN_2022_PROGRAM_ID,
+        ASSOCIATED_TOKEN_PROGRAM_ID,
+      );
+
+    await program.methods
+      .initialize({
+        name: "Cooldown USD",
+        symbol: "KUSD",
+        uri: "",
+        decimals: 6,
+        enablePermanentDelegate: true,
+        enableTransferHook: true,
+        defaultAccountFrozen: false,
+        transferHookProgram: transferHookProgramId,
+        pauseCooldown: new anchor.BN(0),
+        enableMetadata: true,
+        maxSupply: null,
+        enableAllowlist: false,
+        enableConfidential: false,
+        treasury: null,
+        tokenProgram: { token2022: {} },
+        transferCooldownSeconds: new anchor.BN(3_600),
+        reserveTransferHook: false,
+      })
+      .accounts({
+        authority: authority.publicKey,
+        mint: cooldownMint.publicKey,
+        config: cooldownConfig,
+        roleAccount: cooldownRole,
+        extraMetasAccount: cooldownExtraMetas,
+        transferHookProgram: transferHookProgramId,
+        tokenProgram: TOKEN_2022_PROGRAM_ID,
+        systemProgram: SystemProgram.programId,
+        rent: anchor.web3.SYSVAR_RENT_PUBKEY,
+      })
+      .signers([cooldownMint])
+      .rpc();
+    const config = await (program.account as any).stablecoinConfig.fetch(cooldownConfig);
+    assert.equal(config.transferCooldownSeconds.toString(), "3600");
+
+    await program.methods
+      .mint(new anchor.BN(1_000))
+      .accounts({
+        minter: authority.publicKey,
+        config: cooldownConfig,
+        roleAccount: cooldownRole,
+        mint: cooldownMint.publicKey,
+        recipient: authority.publicKey,
+        recipientAta: ata(authority.publicKey),
+        tokenProgram: TOKEN_2022_PROGRAM_ID,
+        associatedTokenProgram: ASSOCIATED_TOKEN_PROGRAM_ID,
+        systemProgram: SystemProgram.programId,
+        recipientBlacklistEntry: blacklistEntry(cooldownConfig, authority.publicKey),
+      })
+      .rpc();
+
+    // sha256("global:initialize_last_transfer")[..8]
+    const initializeLastTransfer = new anchor.web3.TransactionInstruction({
+      programId: transferHookProgramId,
+      keys: [
+        { pubkey: authority.publicKey, isSigner: true, isWritable: true },
+        { pubkey: cooldownConfig, isSigner: false, isWritable: false },
+        { pubkey: authority.publicKey, isSigner: false, isWritable: false },
+        { pubkey: lastTransfer, isSigner: false, isWritable: true },
+        { pubkey: SystemProgram.programId, isSigner: false, isWritable: false },
+      ],
+      data: Buffer.from([238, 132, 4, 224, 103, 174, 88, 127]),
+    });
+    const recipient = Keypair.generate().publicKey;
+    const transfer = async () => {
+      const transferIx = await createTransferCheckedWithTransferHookInstruction(
+        provider.connection,
+        ata(authority.publicKey),
+        cooldownMint.publicKey,
+        ata(recipient),
+        authority.publicKey,
+        100n,
+        6,
+        [],
+        undefined,
+        TOKEN_2022_PROGRAM_ID,
+      );
+      const tx = new anchor.web3.Transaction().add(
+        createAssociatedTokenAccountIdempotentInstruction(
+          authority.publicKey,
+          ata(recipient),
+          recipient,
+          cooldownMint.publicKey,
+          TOKEN_2022_PROGRAM_ID,
+          ASSOCIATED_TOKEN_PROGRAM_ID,
+        ),
+        transferIx,
+      );
+      await provider.sendAndConfirm(tx);
+    };
+    const expectError = async (name: string) => {
+      try {
+        await transfer();
+        assert.fail(`expected ${name} from the transfer hook`);
+      } catch (err: any) {
+        const logs = (err.logs ?? []).join("\n");
+        assert.include(`${String(err)}\n${logs}`, name);
+      }
+    };
+
+    await expectError("LastTransferMissing");
+    await provider.sendAndConfirm(new anchor.web3.Transaction().add(initializeLastTransfer));
+
+    await transfer();
+    await expectError("CooldownActive");
+    const recipientAccount = await getAccount(
+      provider.connection,
+      ata(recipient),
+      undefined,
+      TOKEN_2022_PROGRAM_ID,
+    );
+    assert.equal(recipientAccount.amount, 100n);
+  });
+
+  it("refuses to mint to a blacklisted recipient", async () => {
+    const wallet = Keypair.generate().publicKey;
+    const [entryPda] = PublicKey.findProgramAddressSync(
+      [Buffer.from("blacklist"), configPda.toBuffer(), wallet.toBuffer()],
+      program.programId,
+    );
+    const mintTo = (recipientBlacklistEntry: PublicKey | null) =>
+      program.methods
+        .mint(new anchor.BN(1_000))
+        .accounts({
+          minter: authority.publicKey,
+          config: configPda,
+          roleAccount: rolePda,
+          mint: mintKeypair.publicKey,
+          recipient: wallet,
+          recipientAta: getAssociatedTokenAddressSync(
+            mintKeypair.publicKey,
+            wallet,
+            false,
+            TOKEN_2022_PROGRAM_ID,
+            ASSOCIATED_TOKEN_PROGRAM_ID,
+          ),
+          tokenProgram: TOKEN_2022_PROGRAM_ID,
+          associatedTokenProgram: ASSOCIATED_TOKEN_PROGRAM_ID,
+          systemProgram: SystemProgram.programId,
+          recipientBlacklistEntry,
+        })
+        .rpc();
+
+    // A wallet that was never blacklisted has no entry, which passes.
+    await mintTo(entryPda);
+
+    await program.methods
+      .addToBlacklist({ wallet, reason: "", expiresAt: null, reasonCode: 2 })
+      .accounts({
+        blacklister: authority.publicKey,
+        config: configPda,
+        roleAccount: rolePda,
+        blacklistEntry: entryPda,
+        wallet,
+        systemProgram: SystemProgram.programId,
+      })
+      .rpc();
+    try {
+      await mintTo(entryPda);
+      assert.fail("expected TransferDenied");
+    } catch (err: any) {
+      assert.include(String(err), "TransferDenied");
+    }
+    // Leaving the entry out does not skip the check.
+    try {
+      await mintTo(null);
+      assert.fail("expected MissingBlacklistEntry");
+    } catch (err: any) {
+      assert.include(String(err), "MissingBlacklistEntry");
+    }
+  });
+
+  it("enables the hook on a mint that reserved the extension at initialize", async () => {
+    const lateMint = Keypair.generate();
+    const [lateConfig] = PublicKey.findProgramAddressSync(
+      [Buffer.from("stablecoin"), lateMint.publicKey.toBuffer()],
+      program.programId,
+    );
+    const [lateRole] = PublicKey.findProgramAddressSync(
+      [Buffer.from("role"), lateConfig.toBuffer(), authority.publicKey.toBuffer()],
+      program.programId,
+    );
+    const [lateExtraMetas] = PublicKey.findProgramAddressSync(
+      [Buffer.from("extra-account-metas"), lateMint.publicKey.toBuffer()],
+      transferHookProgramId,
+    );
+
+    await program.methods
+      .initialize({
+        name: "Late USD",
+        symbol: "LUSD",
+        uri: "",
+        decimals: 6,
+        enablePermanentDelegate: true,
+        enableTransferHook: false,
+        defaultAccountFrozen: false,
+        transferHookProgram: null,
+        pauseCooldown: new anchor.BN(0),
+        enableMetadata: true,
+        maxSupply: null,
+        enableAllowlist: false,
+        enableConfidential: false,
+        treasury: null,
+        tokenProgram: { token2022: {} },
+        transferCooldownSeconds: null,
+        reserveTransferHook: true,
+      })
+      .accounts({
+        authority: authority.publicKey,
+        mint: lateMint.publicKey,
+        config: lateConfig,
+        roleAccount: lateRole,
+        extraMetasAccount: null,
+        transferHookProgram: null,
+        tokenProgram: TOKEN_2022_PROGRAM_ID,
+        systemProgram: SystemProgram.programId,
+        rent: anchor.web3.SYSVAR_RENT_PUBKEY,
+      })
+      .signers([lateMint])
+      .rpc();
+
+    await program.methods
+      .enableTransferHook()
+      .accounts({
+        authority: authority.publicKey,
+        config: lateConfig,
+        roleAccount: lateRole,
+        mint: lateMint.publicKey,
+        transferHookProgram: transferHookProgramId,
+        extraMetasAccount: lateExtraMetas,
+        token2022Program: TOKEN_2022_PROGRAM_ID,
+        systemProgram: SystemProgram.programId,
+      })
+      .rpc();
+
+    const config = await (program.account as any).stablecoinConfig.fetch(lateConfig);
+    assert.equal(config.features.bits & (1 << 8), 1 << 8);
+    assert.ok(config.transferHookProgram.equals(transferHookProgramId));
+  });
 });
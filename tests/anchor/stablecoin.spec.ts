@@ -3,9 +3,16 @@ import type { Program } from "@coral-xyz/anchor";
 import { Keypair, PublicKey, SystemProgram } from "@solana/web3.js";
 import {
   ASSOCIATED_TOKEN_PROGRAM_ID,
+  ExtensionType,
   TOKEN_2022_PROGRAM_ID,
+  TOKEN_PROGRAM_ID,
+  createAccount,
+  createAssociatedTokenAccountIdempotentInstruction,
   getAccount,
   getAssociatedTokenAddressSync,
+  getExtensionTypes,
+  getMint,
+  getTokenMetadata,
 } from "@solana/spl-token";
 import { assert } from "chai";
 
@@ -37,6 +44,15 @@ describe("stablecoin-core", () => {
         enableTransferHook: false,
         defaultAccountFrozen: false,
         transferHookProgram: null,
+        pauseCooldown: new anchor.BN(0),
+        enableMetadata: true,
+        maxSupply: null,
+        enableAllowlist: false,
+        enableConfidential: false,
+        treasury: null,
+        tokenProgram: { token2022: {} },
+        transferCooldownSeconds: null,
+        reserveTransferHook: false,
       })
       .accounts({
         authority: authority.publicKey,
@@ -45,7 +61,7 @@ describe("stablecoin-core", () => {
         roleAccount: rolePda,
         extraMetasAccount: null,
         transferHookProgram: null,
-        token2022Program: TOKEN_2022_PROGRAM_ID,
+        tokenProgram: TOKEN_2022_PROGRAM_ID,
         systemProgram: SystemProgram.programId,
         rent: anchor.web3.SYSVAR_RENT_PUBKEY,
       })
@@ -56,8 +72,12 @@ describe("stablecoin-core", () => {
     assert.ok(config.mint.equals(mintKeypair.publicKey));
     assert.equal(config.symbol, "TUSD");
     assert.equal(config.decimals, 6);
-    assert.equal(config.isPaused, false);
+    assert.equal(config.pauseFlags, 0);
     assert.equal(config.features.transferHook, false);
+    assert.ok(config.creator.equals(authority.publicKey));
+    assert.ok(config.createdAt.toNumber() > 0);
+    assert.equal(config.lastActionAt.toNumber(), config.createdAt.toNumber());
+    assert.ok(config.lastActionBy.equals(authority.publicKey));
   });
 
   it("mints tokens to recipient", async () => {
@@ -80,7 +100,7 @@ describe("stablecoin-core", () => {
         mint: mintKeypair.publicKey,
         recipient: recipient.publicKey,
         recipientAta,
-        token2022Program: TOKEN_2022_PROGRAM_ID,
+        tokenProgram: TOKEN_2022_PROGRAM_ID,
         associatedTokenProgram: ASSOCIATED_TOKEN_PROGRAM_ID,
         systemProgram: SystemProgram.programId,
       })
@@ -96,5 +116,1611 @@ describe("stablecoin-core", () => {
 
     const config = await (program.account as any).stablecoinConfig.fetch(configPda);
     assert.equal(config.totalMinted.toString(), amount.toString());
+    assert.ok(config.lastActionBy.equals(authority.publicKey));
+    assert.ok(config.lastActionAt.toNumber() >= config.createdAt.toNumber());
+    const role = await (program.account as any).roleAccount.fetch(rolePda);
+    assert.equal(role.lifetimeMinted.toString(), amount.toString());
+  });
+
+  it("updates the metadata uri and keeps the other fields", async () => {
+    await program.methods
+      .updateMetadata({ name: null, symbol: null, uri: "https://example.com/tusd.json" })
+      .accounts({
+        authority: authority.publicKey,
+        config: configPda,
+        roleAccount: rolePda,
+        mint: mintKeypair.publicKey,
+        token2022Program: TOKEN_2022_PROGRAM_ID,
+        systemProgram: SystemProgram.programId,
+      })
+      .rpc();
+
+    const config = await (program.account as any).stablecoinConfig.fetch(configPda);
+    assert.equal(config.uri, "https://example.com/tusd.json");
+    assert.equal(config.symbol, "TUSD");
+
+    const metadata = await getTokenMetadata(
+      provider.connection,
+      mintKeypair.publicKey,
+      undefined,
+      TOKEN_2022_PROGRAM_ID,
+    );
+    assert.equal(metadata?.uri, "https://example.com/tusd.json");
+    assert.equal(metadata?.name, "Test USD");
+  });
+
+  it("sets an additional metadata field but not the mirrored ones", async () => {
+    const setField = (key: string, value: string) =>
+      program.methods
+        .updateMetadataField({ key, value })
+        .accounts({
+          authority: authority.publicKey,
+          config: configPda,
+          roleAccount: rolePda,
+          mint: mintKeypair.publicKey,
+          token2022Program: TOKEN_2022_PROGRAM_ID,
+          systemProgram: SystemProgram.programId,
+        })
+        .rpc();
+
+    await setField("attestation", "https://example.com/attestation.pdf");
+    const metadata = await getTokenMetadata(
+      provider.connection,
+      mintKeypair.publicKey,
+      undefined,
+      TOKEN_2022_PROGRAM_ID,
+    );
+    assert.deepInclude(metadata?.additionalMetadata ?? [], [
+      "attestation",
+      "https://example.com/attestation.pdf",
+    ]);
+
+    try {
+      await setField("symbol", "NOPE");
+      assert.fail("expected InvalidMetadataField");
+    } catch (err) {
+      assert.include(String(err), "InvalidMetadataField");
+    }
+  });
+
+  it("rejects a recipient-owned token account that is not the canonical ATA", async () => {
+    const recipient = Keypair.generate();
+    const nonAta = await createAccount(
+      provider.connection,
+      authority,
+      mintKeypair.publicKey,
+      recipient.publicKey,
+      Keypair.generate(),
+      undefined,
+      TOKEN_2022_PROGRAM_ID,
+    );
+    const account = await getAccount(provider.connection, nonAta, undefined, TOKEN_2022_PROGRAM_ID);
+    assert.ok(account.owner.equals(recipient.publicKey));
+
+    try {
+      await program.methods
+        .mint(new anchor.BN(1))
+        .accounts({
+          minter: authority.publicKey,
+          config: configPda,
+          roleAccount: rolePda,
+          mint: mintKeypair.publicKey,
+          recipient: recipient.publicKey,
+          recipientAta: nonAta,
+          tokenProgram: TOKEN_2022_PROGRAM_ID,
+          associatedTokenProgram: ASSOCIATED_TOKEN_PROGRAM_ID,
+          systemProgram: SystemProgram.programId,
+        })
+        .rpc();
+      assert.fail("mint to a non-ATA token account should fail");
+    } catch (err: any) {
+      assert.equal(err.error?.errorCode?.code, "AccountNotAssociatedTokenAccount");
+    }
+  });
+
+  it("mints to several recipients in one batch", async () => {
+    const recipients = [Keypair.generate(), Keypair.generate()];
+    const atas = recipients.map((recipient) =>
+      getAssociatedTokenAddressSync(
+        mintKeypair.publicKey,
+        recipient.publicKey,
+        false,
+        TOKEN_2022_PROGRAM_ID,
+        ASSOCIATED_TOKEN_PROGRAM_ID,
+      ),
+    );
+    const createTx = new anchor.web3.Transaction();
+    recipients.forEach((recipient, index) => {
+      createTx.add(
+        createAssociatedTokenAccountIdempotentInstruction(
+          authority.publicKey,
+          atas[index],
+          recipient.publicKey,
+          mintKeypair.publicKey,
+          TOKEN_2022_PROGRAM_ID,
+          ASSOCIATED_TOKEN_PROGRAM_ID,
+        ),
+      );
+    });
+    await provider.sendAndConfirm(createTx);
+
+    const before = await (program.account as any).stablecoinConfig.fetch(configPda);
+    await program.methods
+      .mintBatch([
+        { recipient: recipients[0].publicKey, amount: new anchor.BN(100) },
+        { recipient: recipients[1].publicKey, amount: new anchor.BN(250) },
+      ])
+      .accounts({
+        minter: authority.publicKey,
+        config: configPda,
+        roleAccount: rolePda,
+        mint: mintKeypair.publicKey,
+        tokenProgram: TOKEN_2022_PROGRAM_ID,
+      })
+      .remainingAccounts(
+        atas.map((pubkey) => ({ pubkey, isSigner: false, isWritable: true })),
+      )
+      .rpc();
+
+    const balances = await Promise.all(
+      atas.map((ata) =>
+        getAccount(provider.connection, ata, undefined, TOKEN_2022_PROGRAM_ID),
+      ),
+    );
+    assert.equal(balances[0].amount, 100n);
+    assert.equal(balances[1].amount, 250n);
+
+    const after = await (program.account as any).stablecoinConfig.fetch(configPda);
+    assert.equal(after.totalMinted.sub(before.totalMinted).toString(), "350");
+    assert.equal(after.auditCounter.sub(before.auditCounter).toString(), "2");
+  });
+
+  it("enforces the pause cooldown for pausers but not the master", async () => {
+    const cooldownMint = Keypair.generate();
+    const [cooldownConfig] = PublicKey.findProgramAddressSync(
+      [Buffer.from("stablecoin"), cooldownMint.publicKey.toBuffer()],
+      program.programId,
+    );
+    const [masterRole] = PublicKey.findProgramAddressSync(
+      [Buffer.from("role"), cooldownConfig.toBuffer(), authority.publicKey.toBuffer()],
+      program.programId,
+    );
+
+    await program.methods
+      .initialize({
+        name: "Cooldown USD",
+        symbol: "CDUSD",
+        uri: "",
+        decimals: 6,
+        enablePermanentDelegate: false,
+        enableTransferHook: false,
+        defaultAccountFrozen: false,
+        transferHookProgram: null,
+        pauseCooldown: new anchor.BN(3600),
+        enableMetadata: true,
+        maxSupply: null,
+        enableAllowlist: false,
+        enableConfidential: false,
+        treasury: null,
+        tokenProgram: { token2022: {} },
+        transferCooldownSeconds: null,
+        reserveTransferHook: false,
+      })
+      .accounts({
+        authority: authority.publicKey,
+        mint: cooldownMint.publicKey,
+        config: cooldownConfig,
+        roleAccount: masterRole,
+        extraMetasAccount: null,
+        transferHookProgram: null,
+        tokenProgram: TOKEN_2022_PROGRAM_ID,
+        systemProgram: SystemProgram.programId,
+        rent: anchor.web3.SYSVAR_RENT_PUBKEY,
+      })
+      .signers([cooldownMint])
+      .rpc();
+
+    const pauser = Keypair.generate();
+    const [pauserRole] = PublicKey.findProgramAddressSync(
+      [Buffer.from("role"), cooldownConfig.toBuffer(), pauser.publicKey.toBuffer()],
+      program.programId,
+    );
+    await program.methods
+      .updateRoles({ target: pauser.publicKey, roles: 0x10, mintQuota: null, lifetimeQuota: null })
+      .accounts({
+        authority: authority.publicKey,
+        config: cooldownConfig,
+        roleAccount: masterRole,
+        targetRoleAccount: pauserRole,
+        target: pauser.publicKey,
+        systemProgram: SystemProgram.programId,
+      })
+      .rpc();
+
+    await program.methods
+      .pause({ mask: null })
+      .accounts({ pauser: pauser.publicKey, config: cooldownConfig, roleAccount: pauserRole })
+      .signers([pauser])
+      .rpc();
+
+    try {
+      await program.methods
+        .unpause({ mask: null })
+        .accounts({ pauser: pauser.publicKey, config: cooldownConfig, roleAccount: pauserRole })
+        .signers([pauser])
+        .rpc();
+      assert.fail("expected PauseCooldownActive");
+    } catch (err) {
+      assert.include(String(err), "PauseCooldownActive");
+    }
+
+    await program.methods
+      .unpause({ mask: null })
+      .accounts({ pauser: authority.publicKey, config: cooldownConfig, roleAccount: masterRole })
+      .rpc();
+
+    const config = await (program.account as any).stablecoinConfig.fetch(cooldownConfig);
+    assert.equal(config.pauseFlags, 0);
+    assert.equal(config.pauseCooldown.toString(), "3600");
+  });
+
+  it("enforces the max supply cap and only lets it be lowered", async () => {
+    const cappedMint = Keypair.generate();
+    const [cappedConfig] = PublicKey.findProgramAddressSync(
+      [Buffer.from("stablecoin"), cappedMint.publicKey.toBuffer()],
+      program.programId,
+    );
+    const [masterRole] = PublicKey.findProgramAddressSync(
+      [Buffer.from("role"), cappedConfig.toBuffer(), authority.publicKey.toBuffer()],
+      program.programId,
+    );
+
+    await program.methods
+      .initialize({
+        name: "Capped USD",
+        symbol: "CAPUSD",
+        uri: "",
+        decimals: 6,
+        enablePermanentDelegate: false,
+        enableTransferHook: false,
+        defaultAccountFrozen: false,
+        transferHookProgram: null,
+        pauseCooldown: new anchor.BN(0),
+        enableMetadata: true,
+        maxSupply: new anchor.BN(1_000),
+        enableAllowlist: false,
+        enableConfidential: false,
+        treasury: null,
+        tokenProgram: { token2022: {} },
+        transferCooldownSeconds: null,
+        reserveTransferHook: false,
+      })
+      .accounts({
+        authority: authority.publicKey,
+        mint: cappedMint.publicKey,
+        config: cappedConfig,
+        roleAccount: masterRole,
+        extraMetasAccount: null,
+        transferHookProgram: null,
+        tokenProgram: TOKEN_2022_PROGRAM_ID,
+        systemProgram: SystemProgram.programId,
+        rent: anchor.web3.SYSVAR_RENT_PUBKEY,
+      })
+      .signers([cappedMint])
+      .rpc();
+
+    const recipient = Keypair.generate();
+    const recipientAta = getAssociatedTokenAddressSync(
+      cappedMint.publicKey,
+      recipient.publicKey,
+      false,
+      TOKEN_2022_PROGRAM_ID,
+      ASSOCIATED_TOKEN_PROGRAM_ID,
+    );
+    const mintTo = (amount: number) =>
+      program.methods
+        .mint(new anchor.BN(amount))
+        .accounts({
+          minter: authority.publicKey,
+          config: cappedConfig,
+          roleAccount: masterRole,
+          mint: cappedMint.publicKey,
+          recipient: recipient.publicKey,
+          recipientAta,
+          tokenProgram: TOKEN_2022_PROGRAM_ID,
+          associatedTokenProgram: ASSOCIATED_TOKEN_PROGRAM_ID,
+          systemProgram: SystemProgram.programId,
+        })
+        .rpc();
+    const updateCap = (maxSupply: number) =>
+      program.methods
+        .updateMaxSupply({ maxSupply: new anchor.BN(maxSupply) })
+        .accounts({
+          authority: authority.publicKey,
+          config: cappedConfig,
+          roleAccount: masterRole,
+          mint: cappedMint.publicKey,
+        })
+        .rpc();
+
+    await mintTo(600);
+    try {
+      await mintTo(500);
+      assert.fail("expected SupplyCapExceeded");
+    } catch (err) {
+      assert.include(String(err), "SupplyCapExceeded");
+    }
+
+    for (const rejected of [2_000, 500]) {
+      try {
+        await updateCap(rejected);
+        assert.fail("expected InvalidMaxSupply");
+      } catch (err) {
+        assert.include(String(err), "InvalidMaxSupply");
+      }
+    }
+
+    await updateCap(800);
+    const config = await (program.account as any).stablecoinConfig.fetch(cappedConfig);
+    assert.equal(config.maxSupply.toString(), "800");
+  });
+
+  it("keeps the creator and creation time across authority transfers", async () => {
+    const ownedMint = Keypair.generate();
+    const [ownedConfig] = PublicKey.findProgramAddressSync(
+      [Buffer.from("stablecoin"), ownedMint.publicKey.toBuffer()],
+      program.programId,
+    );
+    const [masterRole] = PublicKey.findProgramAddressSync(
+      [Buffer.from("role"), ownedConfig.toBuffer(), authority.publicKey.toBuffer()],
+      program.programId,
+    );
+    const newAuthority = Keypair.generate();
+    const [newRole] = PublicKey.findProgramAddressSync(
+      [Buffer.from("role"), ownedConfig.toBuffer(), newAuthority.publicKey.toBuffer()],
+      program.programId,
+    );
+
+    await program.methods
+      .initialize({
+        name: "Owned USD",
+        symbol: "OUSD",
+        uri: "",
+        decimals: 6,
+        enablePermanentDelegate: false,
+        enableTransferHook: false,
+        defaultAccountFrozen: false,
+        transferHookProgram: null,
+        pauseCooldown: new anchor.BN(0),
+        enableMetadata: true,
+        maxSupply: null,
+        enableAllowlist: false,
+        enableConfidential: false,
+        treasury: null,
+        tokenProgram: { token2022: {} },
+        transferCooldownSeconds: null,
+        reserveTransferHook: false,
+      })
+      .accounts({
+        authority: authority.publicKey,
+        mint: ownedMint.publicKey,
+        config: ownedConfig,
+        roleAccount: masterRole,
+        extraMetasAccount: null,
+        transferHookProgram: null,
+        tokenProgram: TOKEN_2022_PROGRAM_ID,
+        systemProgram: SystemProgram.programId,
+        rent: anchor.web3.SYSVAR_RENT_PUBKEY,
+      })
+      .signers([ownedMint])
+      .rpc();
+    const before = await (program.account as any).stablecoinConfig.fetch(ownedConfig);
+
+    // The new authority already mints; its role account must survive as is.
+    await program.methods
+      .updateRoles({
+        target: newAuthority.publicKey,
+        roles: 0x02,
+        mintQuota: new anchor.BN(5_000),
+        lifetimeQuota: null,
+      })
+      .accounts({
+        authority: authority.publicKey,
+        config: ownedConfig,
+        roleAccount: masterRole,
+        targetRoleAccount: newRole,
+        target: newAuthority.publicKey,
+        systemProgram: SystemProgram.programId,
+      })
+      .rpc();
+    const minterBefore = await (program.account as any).roleAccount.fetch(newRole);
+
+    await program.methods
+      .transferAuthority()
+      .accounts({
+        currentAuthority: authority.publicKey,
+        config: ownedConfig,
+        currentRoleAccount: masterRole,
+        newRoleAccount: newRole,
+        newAuthority: newAuthority.publicKey,
+        systemProgram: SystemProgram.programId,
+      })
+      .rpc();
+
+    const after = await (program.account as any).stablecoinConfig.fetch(ownedConfig);
+    assert.ok(after.authority.equals(newAuthority.publicKey));
+    assert.ok(after.creator.equals(authority.publicKey));
+    assert.equal(after.createdAt.toString(), before.createdAt.toString());
+
+    const minterAfter = await (program.account as any).roleAccount.fetch(newRole);
+    assert.equal(minterAfter.roles, 0x03);
+    assert.equal(minterAfter.bump, minterBefore.bump);
+    assert.equal(minterAfter.mintQuota.toString(), "5000");
+  });
+
+  it("updates the minter quota window with a 60 second minimum", async () => {
+    const updateWindow = (seconds: number) =>
+      program.methods
+        .updateQuotaWindow({ quotaWindowSeconds: new anchor.BN(seconds) })
+        .accounts({
+          authority: authority.publicKey,
+          config: configPda,
+          roleAccount: rolePda,
+        })
+        .rpc();
+
+    try {
+      await updateWindow(30);
+      assert.fail("expected InvalidQuotaWindow");
+    } catch (err) {
+      assert.include(String(err), "InvalidQuotaWindow");
+    }
+
+    await updateWindow(3_600);
+    const config = await (program.account as any).stablecoinConfig.fetch(configPda);
+    assert.equal(config.quotaWindowSeconds.toNumber(), 3_600);
+  });
+
+  it("sets and clears the treasury, rejecting unrecoverable wallets", async () => {
+    const updateTreasury = (treasury: PublicKey | null) =>
+      program.methods
+        .updateTreasury({ treasury })
+        .accounts({
+          authority: authority.publicKey,
+          config: configPda,
+          roleAccount: rolePda,
+        })
+        .rpc();
+
+    try {
+      await updateTreasury(configPda);
+      assert.fail("expected ForbiddenRecipient");
+    } catch (err) {
+      assert.include(String(err), "ForbiddenRecipient");
+    }
+
+    const treasury = Keypair.generate().publicKey;
+    await updateTreasury(treasury);
+    let config = await (program.account as any).stablecoinConfig.fetch(configPda);
+    assert.isTrue(config.treasury.equals(treasury));
+
+    await updateTreasury(null);
+    config = await (program.account as any).stablecoinConfig.fetch(configPda);
+    assert.isNull(config.treasury);
+  });
+
+  it("pauses minting on its own and still lets holders burn", async () => {
+    const PAUSE_MINT = 0x02;
+    const authorityAta = getAssociatedTokenAddressSync(
+      mintKeypair.publicKey,
+      authority.publicKey,
+      false,
+      TOKEN_2022_PROGRAM_ID,
+      ASSOCIATED_TOKEN_PROGRAM_ID,
+    );
+    const mintToAuthority = (amount: number) =>
+      program.methods
+        .mint(new anchor.BN(amount))
+        .accounts({
+          minter: authority.publicKey,
+          config: configPda,
+          roleAccount: rolePda,
+          mint: mintKeypair.publicKey,
+          recipient: authority.publicKey,
+          recipientAta: authorityAta,
+          tokenProgram: TOKEN_2022_PROGRAM_ID,
+          associatedTokenProgram: ASSOCIATED_TOKEN_PROGRAM_ID,
+          systemProgram: SystemProgram.programId,
+        })
+        .rpc();
+    const pauseAccounts = {
+      pauser: authority.publicKey,
+      config: configPda,
+      roleAccount: rolePda,
+    };
+
+    await mintToAuthority(1_000);
+    await program.methods.pause({ mask: PAUSE_MINT }).accounts(pauseAccounts).rpc();
+    let config = await (program.account as any).stablecoinConfig.fetch(configPda);
+    assert.equal(config.pauseFlags, PAUSE_MINT);
+
+    try {
+      await mintToAuthority(1);
+      assert.fail("expected SystemPaused");
+    } catch (err) {
+      assert.include(String(err), "SystemPaused");
+    }
+
+    await program.methods
+      .burn(new anchor.BN(400))
+      .accounts({
+        burner: authority.publicKey,
+        config: configPda,
+        roleAccount: rolePda,
+        mint: mintKeypair.publicKey,
+        burnerAta: authorityAta,
+        tokenProgram: TOKEN_2022_PROGRAM_ID,
+      })
+      .rpc();
+    const account = await getAccount(
+      provider.connection,
+      authorityAta,
+      undefined,
+      TOKEN_2022_PROGRAM_ID,
+    );
+    assert.equal(account.amount, 600n);
+
+    await program.methods.unpause({ mask: null }).accounts(pauseAccounts).rpc();
+    config = await (program.account as any).stablecoinConfig.fetch(configPda);
+    assert.equal(config.pauseFlags, 0);
+  });
+
+  it("lets holders renounce their own roles but never the master authority", async () => {
+    const holder = Keypair.generate();
+    const [holderRole] = PublicKey.findProgramAddressSync(
+      [Buffer.from("role"), configPda.toBuffer(), holder.publicKey.toBuffer()],
+      program.programId,
+    );
+    await program.methods
+      .updateRoles({
+        target: holder.publicKey,
+        roles: 0x1a,
+        mintQuota: new anchor.BN(1_000),
+        lifetimeQuota: new anchor.BN(5_000),
+      })
+      .accounts({
+        authority: authority.publicKey,
+        config: configPda,
+        roleAccount: rolePda,
+        targetRoleAccount: holderRole,
+        target: holder.publicKey,
+        systemProgram: SystemProgram.programId,
+      })
+      .rpc();
+
+    const renounce = (signer: Keypair | null, roles: number, roleAccount: PublicKey) => {
+      const builder = program.methods.renounceRole(roles).accounts({
+        authority: signer ? signer.publicKey : authority.publicKey,
+        config: configPda,
+        roleAccount,
+      });
+      return signer ? builder.signers([signer]).rpc() : builder.rpc();
+    };
+
+    // Dropping the minter bit takes both quotas with it.
+    await renounce(holder, 0x02, holderRole);
+    let role = await (program.account as any).roleAccount.fetch(holderRole);
+    assert.equal(role.roles, 0x18);
+    assert.isNull(role.mintQuota);
+    assert.isNull(role.lifetimeQuota);
+
+    await renounce(holder, 0x10, holderRole);
+    role = await (program.account as any).roleAccount.fetch(holderRole);
+    assert.equal(role.roles, 0x08);
+
+    await renounce(holder, 0x08, holderRole);
+    assert.isNull(await provider.connection.getAccountInfo(holderRole));
+
+    try {
+      await renounce(null, 0x01, rolePda);
+      assert.fail("expected CannotRenounceMasterAuthority");
+    } catch (err) {
+      assert.include(String(err), "CannotRenounceMasterAuthority");
+    }
+  });
+
+  it("refuses to strip the master authority from its own holder", async () => {
+    try {
+      await program.methods
+        .updateRoles({ target: authority.publicKey, roles: 0x02, mintQuota: null, lifetimeQuota: null })
+        .accounts({
+          authority: authority.publicKey,
+          config: configPda,
+          roleAccount: rolePda,
+          targetRoleAccount: rolePda,
+          target: authority.publicKey,
+          systemProgram: SystemProgram.programId,
+        })
+        .rpc();
+      assert.fail("expected LastMasterAuthority");
+    } catch (err) {
+      assert.include(String(err), "LastMasterAuthority");
+    }
+
+    const role = await (program.account as any).roleAccount.fetch(rolePda);
+    assert.equal(role.roles & 0x01, 0x01);
+  });
+
+  it("stops a minter at its lifetime quota", async () => {
+    const minter = Keypair.generate();
+    const [minterRole] = PublicKey.findProgramAddressSync(
+      [Buffer.from("role"), configPda.toBuffer(), minter.publicKey.toBuffer()],
+      program.programId,
+    );
+    await provider.connection.confirmTransaction(
+      await provider.connection.requestAirdrop(minter.publicKey, 1_000_000_000),
+    );
+    await program.methods
+      .updateRoles({
+        target: minter.publicKey,
+        roles: 0x02,
+        mintQuota: null,
+        lifetimeQuota: new anchor.BN(1_500),
+      })
+      .accounts({
+        authority: authority.publicKey,
+        config: configPda,
+        roleAccount: rolePda,
+        targetRoleAccount: minterRole,
+        target: minter.publicKey,
+        systemProgram: SystemProgram.programId,
+      })
+      .rpc();
+
+    const recipient = Keypair.generate();
+    const mintTo = (amount: number) =>
+      program.methods
+        .mint(new anchor.BN(amount))
+        .accounts({
+          minter: minter.publicKey,
+          config: configPda,
+          roleAccount: minterRole,
+          mint: mintKeypair.publicKey,
+          recipient: recipient.publicKey,
+          recipientAta: getAssociatedTokenAddressSync(
+            mintKeypair.publicKey,
+            recipient.publicKey,
+            false,
+            TOKEN_2022_PROGRAM_ID,
+            ASSOCIATED_TOKEN_PROGRAM_ID,
+          ),
+          tokenProgram: TOKEN_2022_PROGRAM_ID,
+          associatedTokenProgram: ASSOCIATED_TOKEN_PROGRAM_ID,
+          systemProgram: SystemProgram.programId,
+        })
+        .signers([minter])
+        .rpc();
+
+    await mintTo(1_000);
+    let role = await (program.account as any).roleAccount.fetch(minterRole);
+    assert.equal(role.lifetimeMinted.toString(), "1000");
+    try {
+      await mintTo(600);
+      assert.fail("expected LifetimeQuotaExceeded");
+    } catch (err) {
+      assert.include(String(err), "LifetimeQuotaExceeded");
+    }
+    await mintTo(500);
+    try {
+      await mintTo(1);
+      assert.fail("expected LifetimeQuotaExceeded");
+    } catch (err) {
+      assert.include(String(err), "LifetimeQuotaExceeded");
+    }
+
+    role = await (program.account as any).roleAccount.fetch(minterRole);
+    assert.equal(role.lifetimeMinted.toString(), "1500");
+    assert.equal(role.lifetimeQuota.toString(), "1500");
+  });
+
+  it("persists quota window rollovers across mint calls", async () => {
+    const minter = Keypair.generate();
+    const [minterRole] = PublicKey.findProgramAddressSync(
+      [Buffer.from("role"), configPda.toBuffer(), minter.publicKey.toBuffer()],
+      program.programId,
+    );
+    await provider.connection.confirmTransaction(
+      await provider.connection.requestAirdrop(minter.publicKey, 1_000_000_000),
+    );
+    await program.methods
+      .updateRoles({
+        target: minter.publicKey,
+        roles: 0x02,
+        mintQuota: new anchor.BN(1_000),
+        lifetimeQuota: null,
+      })
+      .accounts({
+        authority: authority.publicKey,
+        config: configPda,
+        roleAccount: rolePda,
+        targetRoleAccount: minterRole,
+        target: minter.publicKey,
+        systemProgram: SystemProgram.programId,
+      })
+      .rpc();
+    const updateWindow = (seconds: number) =>
+      program.methods
+        .updateQuotaWindow({ quotaWindowSeconds: new anchor.BN(seconds) })
+        .accounts({ authority: authority.publicKey, config: configPda, roleAccount: rolePda })
+        .rpc();
+
+    const recipient = Keypair.generate();
+    const mintTo = (amount: number) =>
+      program.methods
+        .mint(new anchor.BN(amount))
+        .accounts({
+          minter: minter.publicKey,
+          config: configPda,
+          roleAccount: minterRole,
+          mint: mintKeypair.publicKey,
+          recipient: recipient.publicKey,
+          recipientAta: getAssociatedTokenAddressSync(
+            mintKeypair.publicKey,
+            recipient.publicKey,
+            false,
+            TOKEN_2022_PROGRAM_ID,
+            ASSOCIATED_TOKEN_PROGRAM_ID,
+          ),
+          tokenProgram: TOKEN_2022_PROGRAM_ID,
+          associatedTokenProgram: ASSOCIATED_TOKEN_PROGRAM_ID,
+          systemProgram: SystemProgram.programId,
+        })
+        .signers([minter])
+        .rpc();
+
+    await updateWindow(60);
+    try {
+      await mintTo(400);
+      await mintTo(400);
+      let role = await (program.account as any).roleAccount.fetch(minterRole);
+      assert.equal(role.windowResets.toString(), "1");
+      assert.equal(role.mintedCurrentWindow.toString(), "800");
+      const firstWindowStart = role.windowStart.toNumber();
+
+      await new Promise((resolve) => setTimeout(resolve, 61_000));
+      await mintTo(400);
+      role = await (program.account as any).roleAccount.fetch(minterRole);
+      assert.equal(role.windowResets.toString(), "2");
+      assert.equal(role.mintedCurrentWindow.toString(), "400");
+      assert.isAbove(role.windowStart.toNumber(), firstWindowStart);
+    } finally {
+      await updateWindow(3_600);
+    }
+  });
+
+  it("mints a proposed request only once a second signer approves it", async () => {
+    const requester = Keypair.generate();
+    const [requesterRole] = PublicKey.findProgramAddressSync(
+      [Buffer.from("role"), configPda.toBuffer(), requester.publicKey.toBuffer()],
+      program.programId,
+    );
+    await provider.connection.confirmTransaction(
+      await provider.connection.requestAirdrop(requester.publicKey, 1_000_000_000),
+    );
+    await program.methods
+      .updateRoles({ target: requester.publicKey, roles: 0x02, mintQuota: null, lifetimeQuota: null })
+      .accounts({
+        authority: authority.publicKey,
+        config: configPda,
+        roleAccount: rolePda,
+        targetRoleAccount: requesterRole,
+        target: requester.publicKey,
+        systemProgram: SystemProgram.programId,
+      })
+      .rpc();
+
+    const id = new anchor.BN(1);
+    const [mintRequest] = PublicKey.findProgramAddressSync(
+      [Buffer.from("mint_request"), configPda.toBuffer(), id.toArrayLike(Buffer, "le", 8)],
+      program.programId,
+    );
+    const recipient = Keypair.generate();
+    const recipientAta = getAssociatedTokenAddressSync(
+      mintKeypair.publicKey,
+      recipient.publicKey,
+      false,
+      TOKEN_2022_PROGRAM_ID,
+      ASSOCIATED_TOKEN_PROGRAM_ID,
+    );
+    await program.methods
+      .proposeMint({ id, recipient: recipient.publicKey, amount: new anchor.BN(2_500) })
+      .accounts({
+        requester: requester.publicKey,
+        config: configPda,
+        roleAccount: requesterRole,
+        mintRequest,
+        systemProgram: SystemProgram.programId,
+      })
+      .signers([requester])
+      .rpc();
+
+    const approve = (approver: Keypair, approverRole: PublicKey) =>
+      program.methods
+        .approveMint()
+        .accounts({
+          approver: approver.publicKey,
+          config: configPda,
+          approverRoleAccount: approverRole,
+          requesterRoleAccount: requesterRole,
+          mintRequest,
+          requester: requester.publicKey,
+          mint: mintKeypair.publicKey,
+          recipient: recipient.publicKey,
+          recipientAta,
+          tokenProgram: TOKEN_2022_PROGRAM_ID,
+          associatedTokenProgram: ASSOCIATED_TOKEN_PROGRAM_ID,
+          systemProgram: SystemProgram.programId,
+        })
+        .signers([approver])
+        .rpc();
+
+    try {
+      await approve(requester, requesterRole);
+      assert.fail("expected SelfApproval");
+    } catch (err) {
+      assert.include(String(err), "SelfApproval");
+    }
+    await approve(authority, rolePda);
+
+    const account = await getAccount(
+      provider.connection,
+      recipientAta,
+      undefined,
+      TOKEN_2022_PROGRAM_ID,
+    );
+    assert.equal(account.amount, BigInt(2_500));
+    assert.isNull(await provider.connection.getAccountInfo(mintRequest));
+    const role = await (program.account as any).roleAccount.fetch(requesterRole);
+    assert.equal(role.lifetimeMinted.toString(), "2500");
+  });
+
+  it("lets the requester or the master authority cancel a mint request", async () => {
+    const PAUSE_MINT = 0x02;
+    const requester = Keypair.generate();
+    const other = Keypair.generate();
+    const roleOf = (wallet: PublicKey) =>
+      PublicKey.findProgramAddressSync(
+        [Buffer.from("role"), configPda.toBuffer(), wallet.toBuffer()],
+        program.programId,
+      )[0];
+    for (const minter of [requester, other]) {
+      await provider.connection.confirmTransaction(
+        await provider.connection.requestAirdrop(minter.publicKey, 1_000_000_000),
+      );
+      await program.methods
+        .updateRoles({ target: minter.publicKey, roles: 0x02, mintQuota: null, lifetimeQuota: null })
+        .accounts({
+          authority: authority.publicKey,
+          config: configPda,
+          roleAccount: rolePda,
+          targetRoleAccount: roleOf(minter.publicKey),
+          target: minter.publicKey,
+          systemProgram: SystemProgram.programId,
+        })
+        .rpc();
+    }
+
+    const requestPda = (id: anchor.BN) =>
+      PublicKey.findProgramAddressSync(
+        [Buffer.from("mint_request"), configPda.toBuffer(), id.toArrayLike(Buffer, "le", 8)],
+        program.programId,
+      )[0];
+    const recipient = Keypair.generate().publicKey;
+    const propose = (id: anchor.BN) =>
+      program.methods
+        .proposeMint({ id, recipient, amount: new anchor.BN(1_000) })
+        .accounts({
+          requester: requester.publicKey,
+          config: configPda,
+          roleAccount: roleOf(requester.publicKey),
+          mintRequest: requestPda(id),
+          systemProgram: SystemProgram.programId,
+        })
+        .signers([requester])
+        .rpc();
+    const cancel = (id: anchor.BN, signer: Keypair, roleAccount: PublicKey | null) =>
+      program.methods
+        .cancelMint()
+        .accounts({
+          authority: signer.publicKey,
+          config: configPda,
+          roleAccount,
+          mintRequest: requestPda(id),
+          requester: requester.publicKey,
+        })
+        .signers(signer === authority ? [] : [signer])
+        .rpc();
+
+    const pauseAccounts = { pauser: authority.publicKey, config: configPda, roleAccount: rolePda };
+    await program.methods.pause({ mask: PAUSE_MINT }).accounts(pauseAccounts).rpc();
+    try {
+      await propose(new anchor.BN(2));
+      assert.fail("expected SystemPaused");
+    } catch (err) {
+      assert.include(String(err), "SystemPaused");
+    }
+    await program.methods.unpause({ mask: null }).accounts(pauseAccounts).rpc();
+
+    const first = new anchor.BN(2);
+    await propose(first);
+    try {
+      await cancel(first, other, roleOf(other.publicKey));
+      assert.fail("expected Unauthorized");
+    } catch (err) {
+      assert.include(String(err), "Unauthorized");
+    }
+    await cancel(first, requester, null);
+    assert.isNull(await provider.connection.getAccountInfo(requestPda(first)));
+
+    const second = new anchor.BN(3);
+    await propose(second);
+    await cancel(second, authority, rolePda);
+    assert.isNull(await provider.connection.getAccountInfo(requestPda(second)));
+  });
+
+  it("rejects direct mints while mint approval is required", async () => {
+    const setApproval = (requireMintApproval: boolean) =>
+      program.methods
+        .updateMintApproval({ requireMintApproval })
+        .accounts({
+          authority: authority.publicKey,
+          config: configPda,
+          roleAccount: rolePda,
+        })
+        .rpc();
+    const recipient = Keypair.generate().publicKey;
+
+    await setApproval(true);
+    try {
+      await program.methods
+        .mint(new anchor.BN(1))
+        .accounts({
+          minter: authority.publicKey,
+          config: configPda,
+          roleAccount: rolePda,
+          mint: mintKeypair.publicKey,
+          recipient,
+          recipientAta: getAssociatedTokenAddressSync(
+            mintKeypair.publicKey,
+            recipient,
+            false,
+            TOKEN_2022_PROGRAM_ID,
+            ASSOCIATED_TOKEN_PROGRAM_ID,
+          ),
+          tokenProgram: TOKEN_2022_PROGRAM_ID,
+          associatedTokenProgram: ASSOCIATED_TOKEN_PROGRAM_ID,
+          systemProgram: SystemProgram.programId,
+        })
+        .rpc();
+      assert.fail("expected MintApprovalRequired");
+    } catch (err: any) {
+      assert.include(String(err), "MintApprovalRequired");
+    } finally {
+      await setApproval(false);
+    }
+    const config = await (program.account as any).stablecoinConfig.fetch(configPda);
+    assert.isFalse(config.requireMintApproval);
+  });
+
+  it("lets a role admin grant only non-master roles", async () => {
+    const admin = Keypair.generate();
+    const pauser = Keypair.generate();
+    const roleFor = (wallet: PublicKey) =>
+      PublicKey.findProgramAddressSync(
+        [Buffer.from("role"), configPda.toBuffer(), wallet.toBuffer()],
+        program.programId,
+      )[0];
+    await provider.connection.confirmTransaction(
+      await provider.connection.requestAirdrop(admin.publicKey, 1_000_000_000),
+    );
+    const updateRoles = (
+      signer: Keypair,
+      signerRole: PublicKey,
+      target: PublicKey,
+      roles: number,
+    ) =>
+      program.methods
+        .updateRoles({ target, roles, mintQuota: null, lifetimeQuota: null })
+        .accounts({
+          authority: signer.publicKey,
+          config: configPda,
+          roleAccount: signerRole,
+          targetRoleAccount: roleFor(target),
+          target,
+          systemProgram: SystemProgram.programId,
+        })
+        .signers(signer === authority ? [] : [signer])
+        .rpc();
+
+    await updateRoles(authority, rolePda, admin.publicKey, 0x80);
+    await updateRoles(admin, roleFor(admin.publicKey), pauser.publicKey, 0x10);
+    const granted = await (program.account as any).roleAccount.fetch(
+      roleFor(pauser.publicKey),
+    );
+    assert.equal(granted.roles, 0x10);
+
+    // Master, minter and role admin stay with the master authority.
+    for (const roles of [0x11, 0x12, 0x90]) {
+      try {
+        await updateRoles(admin, roleFor(admin.publicKey), pauser.publicKey, roles);
+        assert.fail("expected Unauthorized");
+      } catch (err) {
+        assert.include(String(err), "Unauthorized");
+      }
+    }
+    await updateRoles(admin, roleFor(admin.publicKey), pauser.publicKey, 0);
+  });
+
+  it("reserves freeze and thaw to the master authority under the emergency lock", async () => {
+    const freezer = Keypair.generate();
+    const [freezerRole] = PublicKey.findProgramAddressSync(
+      [Buffer.from("role"), configPda.toBuffer(), freezer.publicKey.toBuffer()],
+      program.programId,
+    );
+    await provider.connection.confirmTransaction(
+      await provider.connection.requestAirdrop(freezer.publicKey, 1_000_000_000),
+    );
+    await program.methods
+      .updateRoles({
+        target: freezer.publicKey,
+        roles: 0x08,
+        mintQuota: null,
+        lifetimeQuota: null,
+      })
+      .accounts({
+        authority: authority.publicKey,
+        config: configPda,
+        roleAccount: rolePda,
+        targetRoleAccount: freezerRole,
+        target: freezer.publicKey,
+        systemProgram: SystemProgram.programId,
+      })
+      .rpc();
+    const targetAta = await createAccount(
+      provider.connection,
+      authority,
+      mintKeypair.publicKey,
+      Keypair.generate().publicKey,
+      undefined,
+      undefined,
+      TOKEN_2022_PROGRAM_ID,
+    );
+    const setLock = (lockAdminOps: boolean) =>
+      program.methods
+        .updateAdminLock({ lockAdminOps })
+        .accounts({ authority: authority.publicKey, config: configPda, roleAccount: rolePda })
+        .rpc();
+    const freezeOrThaw = (thaw: boolean, signer: Keypair, roleAccount: PublicKey) =>
+      (thaw ? program.methods.thawAccount() : program.methods.freezeAccount())
+        .accounts({
+          freezer: signer.publicKey,
+          config: configPda,
+          roleAccount,
+          mint: mintKeypair.publicKey,
+          targetAta,
+          tokenProgram: TOKEN_2022_PROGRAM_ID,
+        })
+        .signers(signer === authority ? [] : [signer])
+        .rpc();
+
+    await setLock(true);
+    let config = await (program.account as any).stablecoinConfig.fetch(configPda);
+    assert.equal(config.lockAdminOps, true);
+    try {
+      await freezeOrThaw(false, freezer, freezerRole);
+      assert.fail("expected SystemPaused");
+    } catch (err) {
+      assert.include(String(err), "SystemPaused");
+    }
+    await freezeOrThaw(false, authority, rolePda);
+    try {
+      await freezeOrThaw(true, freezer, freezerRole);
+      assert.fail("expected SystemPaused");
+    } catch (err) {
+      assert.include(String(err), "SystemPaused");
+    }
+
+    await setLock(false);
+    config = await (program.account as any).stablecoinConfig.fetch(configPda);
+    assert.equal(config.lockAdminOps, false);
+    await freezeOrThaw(true, freezer, freezerRole);
+    const account = await getAccount(
+      provider.connection,
+      targetAta,
+      undefined,
+      TOKEN_2022_PROGRAM_ID,
+    );
+    assert.equal(account.isFrozen, false);
+  });
+
+  it("rejects more than 9 decimals at initialize", async () => {
+    const wideMint = Keypair.generate();
+    const [wideConfig] = PublicKey.findProgramAddressSync(
+      [Buffer.from("stablecoin"), wideMint.publicKey.toBuffer()],
+      program.programId,
+    );
+    const [wideRole] = PublicKey.findProgramAddressSync(
+      [Buffer.from("role"), wideConfig.toBuffer(), authority.publicKey.toBuffer()],
+      program.programId,
+    );
+
+    try {
+      await program.methods
+        .initialize({
+          name: "Wide USD",
+          symbol: "WUSD",
+          uri: "",
+          decimals: 10,
+          enablePermanentDelegate: false,
+          enableTransferHook: false,
+          defaultAccountFrozen: false,
+          transferHookProgram: null,
+          pauseCooldown: new anchor.BN(0),
+          enableMetadata: true,
+          maxSupply: null,
+          enableAllowlist: false,
+          enableConfidential: false,
+          treasury: null,
+          tokenProgram: { token2022: {} },
+          transferCooldownSeconds: null,
+          reserveTransferHook: false,
+        })
+        .accounts({
+          authority: authority.publicKey,
+          mint: wideMint.publicKey,
+          config: wideConfig,
+          roleAccount: wideRole,
+          extraMetasAccount: null,
+          transferHookProgram: null,
+          tokenProgram: TOKEN_2022_PROGRAM_ID,
+          systemProgram: SystemProgram.programId,
+          rent: anchor.web3.SYSVAR_RENT_PUBKEY,
+        })
+        .signers([wideMint])
+        .rpc();
+      assert.fail("expected InvalidDecimals");
+    } catch (err) {
+      assert.include(String(err), "InvalidDecimals");
+    }
+  });
+
+  it("refuses to repoint the transfer hook of an SSS-1 mint", async () => {
+    const [extraMetas] = PublicKey.findProgramAddressSync(
+      [Buffer.from("extra-account-metas"), mintKeypair.publicKey.toBuffer()],
+      TOKEN_2022_PROGRAM_ID,
+    );
+    try {
+      await program.methods
+        .updateTransferHookProgram()
+        .accounts({
+          authority: authority.publicKey,
+          config: configPda,
+          roleAccount: rolePda,
+          mint: mintKeypair.publicKey,
+          newTransferHookProgram: TOKEN_2022_PROGRAM_ID,
+          extraMetasAccount: extraMetas,
+          token2022Program: TOKEN_2022_PROGRAM_ID,
+          systemProgram: SystemProgram.programId,
+        })
+        .rpc();
+      assert.fail("expected FeatureNotEnabled");
+    } catch (err) {
+      assert.include(String(err), "FeatureNotEnabled");
+    }
+  });
+
+  it("refuses to enable a transfer hook on a mint without the extension", async () => {
+    const [extraMetas] = PublicKey.findProgramAddressSync(
+      [Buffer.from("extra-account-metas"), mintKeypair.publicKey.toBuffer()],
+      TOKEN_2022_PROGRAM_ID,
+    );
+    try {
+      await program.methods
+        .enableTransferHook()
+        .accounts({
+          authority: authority.publicKey,
+          config: configPda,
+          roleAccount: rolePda,
+          mint: mintKeypair.publicKey,
+          transferHookProgram: TOKEN_2022_PROGRAM_ID,
+          extraMetasAccount: extraMetas,
+          token2022Program: TOKEN_2022_PROGRAM_ID,
+          systemProgram: SystemProgram.programId,
+        })
+        .rpc();
+      assert.fail("expected FeatureNotEnabled");
+    } catch (err) {
+      assert.include(String(err), "FeatureNotEnabled");
+    }
+  });
+
+  it("initializes an SSS-3 mint with confidential transfers", async () => {
+    const privateMint = Keypair.generate();
+    const [privateConfig] = PublicKey.findProgramAddressSync(
+      [Buffer.from("stablecoin"), privateMint.publicKey.toBuffer()],
+      program.programId,
+    );
+    const [privateRole] = PublicKey.findProgramAddressSync(
+      [Buffer.from("role"), privateConfig.toBuffer(), authority.publicKey.toBuffer()],
+      program.programId,
+    );
+
+    await program.methods
+      .initialize({
+        name: "Private USD",
+        symbol: "PUSD",
+        uri: "",
+        decimals: 6,
+        enablePermanentDelegate: false,
+        enableTransferHook: false,
+        defaultAccountFrozen: false,
+        transferHookProgram: null,
+        pauseCooldown: new anchor.BN(0),
+        enableMetadata: true,
+        maxSupply: null,
+        enableAllowlist: false,
+        enableConfidential: true,
+        treasury: null,
+        tokenProgram: { token2022: {} },
+        transferCooldownSeconds: null,
+        reserveTransferHook: false,
+      })
+      .accounts({
+        authority: authority.publicKey,
+        mint: privateMint.publicKey,
+        config: privateConfig,
+        roleAccount: privateRole,
+        extraMetasAccount: null,
+        transferHookProgram: null,
+        tokenProgram: TOKEN_2022_PROGRAM_ID,
+        systemProgram: SystemProgram.programId,
+        rent: anchor.web3.SYSVAR_RENT_PUBKEY,
+      })
+      .signers([privateMint])
+      .rpc();
+
+    const config = await (program.account as any).stablecoinConfig.fetch(privateConfig);
+    assert.equal(config.features.bits & (1 << 16), 1 << 16);
+    const mint = await getMint(
+      provider.connection,
+      privateMint.publicKey,
+      undefined,
+      TOKEN_2022_PROGRAM_ID,
+    );
+    assert.include(getExtensionTypes(mint.tlvData), ExtensionType.ConfidentialTransferMint);
+  });
+
+  it("switches new accounts of a default-frozen mint between frozen and not", async () => {
+    const frozenMint = Keypair.generate();
+    const [frozenConfig] = PublicKey.findProgramAddressSync(
+      [Buffer.from("stablecoin"), frozenMint.publicKey.toBuffer()],
+      program.programId,
+    );
+    const [frozenRole] = PublicKey.findProgramAddressSync(
+      [Buffer.from("role"), frozenConfig.toBuffer(), authority.publicKey.toBuffer()],
+      program.programId,
+    );
+
+    await program.methods
+      .initialize({
+        name: "Frozen USD",
+        symbol: "FUSD",
+        uri: "",
+        decimals: 6,
+        enablePermanentDelegate: false,
+        enableTransferHook: false,
+        defaultAccountFrozen: true,
+        transferHookProgram: null,
+        pauseCooldown: new anchor.BN(0),
+        enableMetadata: true,
+        maxSupply: null,
+        enableAllowlist: false,
+        enableConfidential: false,
+        treasury: null,
+        tokenProgram: { token2022: {} },
+        transferCooldownSeconds: null,
+        reserveTransferHook: false,
+      })
+      .accounts({
+        authority: authority.publicKey,
+        mint: frozenMint.publicKey,
+        config: frozenConfig,
+        roleAccount: frozenRole,
+        extraMetasAccount: null,
+        transferHookProgram: null,
+        tokenProgram: TOKEN_2022_PROGRAM_ID,
+        systemProgram: SystemProgram.programId,
+        rent: anchor.web3.SYSVAR_RENT_PUBKEY,
+      })
+      .signers([frozenMint])
+      .rpc();
+
+    const createAta = async (owner: PublicKey) => {
+      const address = getAssociatedTokenAddressSync(
+        frozenMint.publicKey,
+        owner,
+        false,
+        TOKEN_2022_PROGRAM_ID,
+      );
+      await provider.sendAndConfirm(
+        new anchor.web3.Transaction().add(
+          createAssociatedTokenAccountIdempotentInstruction(
+            authority.publicKey,
+            address,
+            owner,
+            frozenMint.publicKey,
+            TOKEN_2022_PROGRAM_ID,
+            ASSOCIATED_TOKEN_PROGRAM_ID,
+          ),
+        ),
+      );
+      return getAccount(provider.connection, address, undefined, TOKEN_2022_PROGRAM_ID);
+    };
+    const earlier = await createAta(Keypair.generate().publicKey);
+    assert.equal(earlier.isFrozen, true);
+
+    await program.methods
+      .updateDefaultAccountState({ defaultFrozen: false })
+      .accounts({
+        authority: authority.publicKey,
+        config: frozenConfig,
+        roleAccount: frozenRole,
+        mint: frozenMint.publicKey,
+        token2022Program: TOKEN_2022_PROGRAM_ID,
+      })
+      .rpc();
+
+    const config = await (program.account as any).stablecoinConfig.fetch(frozenConfig);
+    assert.equal(config.features.bits & (1 << 24), 0);
+    const later = await createAta(Keypair.generate().publicKey);
+    assert.equal(later.isFrozen, false);
+    // Accounts created before the switch keep their state.
+    const unchanged = await getAccount(
+      provider.connection,
+      earlier.address,
+      undefined,
+      TOKEN_2022_PROGRAM_ID,
+    );
+    assert.equal(unchanged.isFrozen, true);
+
+    try {
+      await program.methods
+        .updateDefaultAccountState({ defaultFrozen: true })
+        .accounts({
+          authority: authority.publicKey,
+          config: configPda,
+          roleAccount: rolePda,
+          mint: mintKeypair.publicKey,
+          token2022Program: TOKEN_2022_PROGRAM_ID,
+        })
+        .rpc();
+      assert.fail("expected FeatureNotEnabled");
+    } catch (err) {
+      assert.include(String(err), "FeatureNotEnabled");
+    }
+  });
+
+  it("locks minting for good and can clear the mint authority", async () => {
+    const fixedMint = Keypair.generate();
+    const [fixedConfig] = PublicKey.findProgramAddressSync(
+      [Buffer.from("stablecoin"), fixedMint.publicKey.toBuffer()],
+      program.programId,
+    );
+    const [fixedRole] = PublicKey.findProgramAddressSync(
+      [Buffer.from("role"), fixedConfig.toBuffer(), authority.publicKey.toBuffer()],
+      program.programId,
+    );
+    await program.methods
+      .initialize({
+        name: "Fixed USD",
+        symbol: "FUSD",
+        uri: "",
+        decimals: 6,
+        enablePermanentDelegate: false,
+        enableTransferHook: false,
+        defaultAccountFrozen: false,
+        transferHookProgram: null,
+        pauseCooldown: new anchor.BN(0),
+        enableMetadata: false,
+        maxSupply: null,
+        enableAllowlist: false,
+        enableConfidential: false,
+        treasury: null,
+        tokenProgram: { token2022: {} },
+        transferCooldownSeconds: null,
+        reserveTransferHook: false,
+      })
+      .accounts({
+        authority: authority.publicKey,
+        mint: fixedMint.publicKey,
+        config: fixedConfig,
+        roleAccount: fixedRole,
+        extraMetasAccount: null,
+        transferHookProgram: null,
+        tokenProgram: TOKEN_2022_PROGRAM_ID,
+        systemProgram: SystemProgram.programId,
+        rent: anchor.web3.SYSVAR_RENT_PUBKEY,
+      })
+      .signers([fixedMint])
+      .rpc();
+
+    const recipient = Keypair.generate().publicKey;
+    const mintTo = () =>
+      program.methods
+        .mint(new anchor.BN(1_000))
+        .accounts({
+          minter: authority.publicKey,
+          config: fixedConfig,
+          roleAccount: fixedRole,
+          mint: fixedMint.publicKey,
+          recipient,
+          recipientAta: getAssociatedTokenAddressSync(
+            fixedMint.publicKey,
+            recipient,
+            false,
+            TOKEN_2022_PROGRAM_ID,
+            ASSOCIATED_TOKEN_PROGRAM_ID,
+          ),
+          tokenProgram: TOKEN_2022_PROGRAM_ID,
+          associatedTokenProgram: ASSOCIATED_TOKEN_PROGRAM_ID,
+          systemProgram: SystemProgram.programId,
+        })
+        .rpc();
+    const freezeSupply = (nullMintAuthority: boolean) =>
+      program.methods
+        .freezeSupply({ nullMintAuthority })
+        .accounts({
+          authority: authority.publicKey,
+          config: fixedConfig,
+          roleAccount: fixedRole,
+          mint: fixedMint.publicKey,
+          tokenProgram: TOKEN_2022_PROGRAM_ID,
+        })
+        .rpc();
+
+    await mintTo();
+    await freezeSupply(false);
+    const config = await (program.account as any).stablecoinConfig.fetch(fixedConfig);
+    assert.equal(config.mintingLocked, true);
+    try {
+      await mintTo();
+      assert.fail("expected MintingLocked");
+    } catch (err) {
+      assert.include(String(err), "MintingLocked");
+    }
+    try {
+      await freezeSupply(false);
+      assert.fail("expected MintingLocked");
+    } catch (err) {
+      assert.include(String(err), "MintingLocked");
+    }
+
+    await freezeSupply(true);
+    const mint = await getMint(
+      provider.connection,
+      fixedMint.publicKey,
+      undefined,
+      TOKEN_2022_PROGRAM_ID,
+    );
+    assert.isNull(mint.mintAuthority);
+    assert.ok(mint.freezeAuthority?.equals(fixedConfig));
+    assert.equal(mint.supply, BigInt(1_000));
+  });
+
+  it("creates a plain SSS-1 stablecoin on the legacy token program", async () => {
+    const legacyMint = Keypair.generate();
+    const [legacyConfig] = PublicKey.findProgramAddressSync(
+      [Buffer.from("stablecoin"), legacyMint.publicKey.toBuffer()],
+      program.programId,
+    );
+    const [legacyRole] = PublicKey.findProgramAddressSync(
+      [Buffer.from("role"), legacyConfig.toBuffer(), authority.publicKey.toBuffer()],
+      program.programId,
+    );
+    const initialize = (enableMetadata: boolean) =>
+      program.methods
+        .initialize({
+          name: "Legacy USD",
+          symbol: "LUSD",
+          uri: "",
+          decimals: 6,
+          enablePermanentDelegate: false,
+          enableTransferHook: false,
+          defaultAccountFrozen: false,
+          transferHookProgram: null,
+          pauseCooldown: new anchor.BN(0),
+          enableMetadata,
+          maxSupply: null,
+          enableAllowlist: false,
+          enableConfidential: false,
+          treasury: null,
+          tokenProgram: { legacy: {} },
+          transferCooldownSeconds: null,
+          reserveTransferHook: false,
+        })
+        .accounts({
+          authority: authority.publicKey,
+          mint: legacyMint.publicKey,
+          config: legacyConfig,
+          roleAccount: legacyRole,
+          extraMetasAccount: null,
+          transferHookProgram: null,
+          tokenProgram: TOKEN_PROGRAM_ID,
+          systemProgram: SystemProgram.programId,
+          rent: anchor.web3.SYSVAR_RENT_PUBKEY,
+        })
+        .signers([legacyMint])
+        .rpc();
+
+    try {
+      await initialize(true);
+      assert.fail("expected Token2022Required");
+    } catch (err) {
+      assert.include(String(err), "Token2022Required");
+    }
+    await initialize(false);
+    const config = await (program.account as any).stablecoinConfig.fetch(legacyConfig);
+    assert.ok(config.tokenProgram.equals(TOKEN_PROGRAM_ID));
+
+    const recipient = Keypair.generate().publicKey;
+    const recipientAta = getAssociatedTokenAddressSync(
+      legacyMint.publicKey,
+      recipient,
+      false,
+      TOKEN_PROGRAM_ID,
+      ASSOCIATED_TOKEN_PROGRAM_ID,
+    );
+    const mintTo = (tokenProgram: PublicKey) =>
+      program.methods
+        .mint(new anchor.BN(1_000))
+        .accounts({
+          minter: authority.publicKey,
+          config: legacyConfig,
+          roleAccount: legacyRole,
+          mint: legacyMint.publicKey,
+          recipient,
+          recipientAta,
+          tokenProgram,
+          associatedTokenProgram: ASSOCIATED_TOKEN_PROGRAM_ID,
+          systemProgram: SystemProgram.programId,
+        })
+        .rpc();
+    await mintTo(TOKEN_PROGRAM_ID);
+    try {
+      await mintTo(TOKEN_2022_PROGRAM_ID);
+      assert.fail("expected InvalidTokenProgram");
+    } catch (err) {
+      assert.include(String(err), "InvalidTokenProgram");
+    }
+
+    await program.methods
+      .freezeAccount()
+      .accounts({
+        freezer: authority.publicKey,
+        config: legacyConfig,
+        roleAccount: legacyRole,
+        mint: legacyMint.publicKey,
+        targetAta: recipientAta,
+        tokenProgram: TOKEN_PROGRAM_ID,
+      })
+      .rpc();
+    const account = await getAccount(
+      provider.connection,
+      recipientAta,
+      undefined,
+      TOKEN_PROGRAM_ID,
+    );
+    assert.equal(account.amount, BigInt(1_000));
+    assert.isTrue(account.isFrozen);
   });
 });